@@ -545,6 +545,9 @@ export const useSpacetimeTables = ({
                     // console.log(`[DEBUG] Spatial Update Needed! Added: [${addedChunks.join(',')}] Removed: [${removedChunks.join(',')}]`); // Log diff
                     console.log(`[Sub Update] Added: [${addedChunks.join(',')}] Removed: [${removedChunks.join(',')}]`);
 
+                    // Keep the server's interest management (row-level visibility) in sync with our viewport
+                    connection.reducers.updateViewport(viewport.minX, viewport.minY, viewport.maxX, viewport.maxY);
+
                     // --- Unsubscribe from Removed Chunks ---
                     removedChunks.forEach(chunkIndex => {
                         const handles = spatialSubHandlesMapRef.current.get(chunkIndex);
@@ -4,10 +4,19 @@ use crate::Player; // For the struct
 use crate::player; // For the table trait
 use crate::items::{ItemDefinition, item_definition as ItemDefinitionTableTrait}; // To check item properties
 use crate::items::{InventoryItem, inventory_item as InventoryItemTableTrait}; // Added for item consumption
+use crate::models::{DamageType, DeathCause};
 use log;
 
 const MAX_STAT_VALUE: f32 = 100.0;
 const MIN_STAT_VALUE: f32 = 0.0;
+/// A player can bleed from at most this many hits at once. Further hits refresh the oldest bleed.
+const MAX_BLEED_STACKS: usize = 3;
+/// Radiation stacks a player can carry at once while standing in a hazard zone.
+const MAX_RADIATION_STACKS: usize = 5;
+/// Stamina drained while sprinting is scaled by this while well-fed.
+const WELL_FED_STAMINA_DRAIN_MULTIPLIER: f32 = 0.75;
+/// How long a freshly respawned player is protected, unless they attack or loot first.
+pub(crate) const SPAWN_PROTECTION_DURATION_SECS: f32 = 10.0;
 
 #[table(name = active_consumable_effect, public)] // public for client UI if needed
 #[derive(Clone, Debug)]
@@ -35,6 +44,14 @@ pub enum EffectType {
     Burn,
     Bleed,
     BandageBurst,
+    Drowning, // Environmental one-shot damage while swimming with no stamina
+    Slow, // On-hit movement slow; total_amount holds the slow fraction (0.3 = 30% slower)
+    WellFed, // Varied diet bonus; total_amount is healed over the duration and stamina drains slower
+    Radiation, // Hazard zone exposure (see hazard_zone.rs); stacks up to MAX_RADIATION_STACKS
+    Stagger, // Brief slow after being knocked back by a hit; total_amount holds the slow fraction
+    Crushed, // One-shot damage from a falling tree (see tree_felling.rs)
+    SpawnProtection, // After respawning: no damage dealt or taken by players or campfires
+    Dysentery, // From unboiled water (see water.rs); total_amount is thirst drained over the duration
     // Potentially HungerRegen, ThirstRegen, StaminaRegen in future
 }
 
@@ -72,6 +89,8 @@ pub fn process_active_consumable_effects_tick(ctx: &ReducerContext, _args: Proce
     // A temporary Vec to store effects that need item consumption to avoid borrowing issues with ctx.db
     let mut effects_requiring_consumption: Vec<(u64, Identity, EffectType, Option<f32>)> = Vec::new();
     let mut player_ids_who_took_external_damage_this_tick = std::collections::HashSet::<Identity>::new(); // Renamed for clarity
+    // What last hurt each player this tick, in case it killed them
+    let mut last_damage_cause = std::collections::HashMap::<Identity, DeathCause>::new();
 
     for effect_row in ctx.db.active_consumable_effect().iter() {
         let effect = effect_row.clone(); // Clone to work with
@@ -98,8 +117,11 @@ pub fn process_active_consumable_effects_tick(ctx: &ReducerContext, _args: Proce
         let mut current_effect_applied_so_far = effect.amount_applied_so_far.unwrap_or(0.0);
 
         // --- Handle Environmental Damage (One-Shot) ---
-        if effect.effect_type == EffectType::Burn && effect.item_def_id == 0 {
-            if let Some(damage_to_apply) = effect.total_amount {
+        if (effect.effect_type == EffectType::Burn && effect.item_def_id == 0) || effect.effect_type == EffectType::Drowning {
+            if let Some(mut damage_to_apply) = effect.total_amount {
+                if effect.effect_type == EffectType::Burn {
+                    damage_to_apply *= 1.0 - crate::armor::calculate_total_protection(ctx, effect.player_id, DamageType::Burn);
+                }
                 log::trace!("[EffectTick] ENV_BURN Pre-Damage for Player {:?}: Health {:.2}, DamageThisTick {:.2}",
                     effect.player_id, player_to_update.health, damage_to_apply);
                 let health_before_env_damage = player_to_update.health;
@@ -128,10 +150,10 @@ pub fn process_active_consumable_effects_tick(ctx: &ReducerContext, _args: Proce
                     }
                     effect_ended = true;
 
-                    // If BandageBurst completes successfully, cancel bleed effects for this player.
+                    // If BandageBurst completes successfully, cleanse on-hit status effects for this player.
                     if player_effect_applied_this_iteration { // Ensure health was actually applied
-                        log::info!("[EffectTick] BandageBurst completed for player {:?}. Attempting to cancel bleed effects.", effect.player_id);
-                        cancel_bleed_effects(ctx, effect.player_id);
+                        log::info!("[EffectTick] BandageBurst completed for player {:?}. Cleansing status effects.", effect.player_id);
+                        cleanse_status_effects(ctx, effect.player_id);
                     }
                 } else {
                     // Timer still running for BandageBurst, do nothing to health, don't end yet.
@@ -144,6 +166,12 @@ pub fn process_active_consumable_effects_tick(ctx: &ReducerContext, _args: Proce
                 effect_ended = true; // End if no total_amount
             }
         }
+        // --- Handle Slow, Stagger and SpawnProtection (no per-tick change; read until they expire) ---
+        else if matches!(effect.effect_type, EffectType::Slow | EffectType::Stagger | EffectType::SpawnProtection) {
+            if current_time >= effect.ends_at {
+                effect_ended = true;
+            }
+        }
         // --- Handle Other Progressive Effects (HealthRegen, Bleed, item-based Damage) ---
         else if let Some(total_amount_val) = effect.total_amount {
             let total_duration_micros = effect.ends_at.to_micros_since_unix_epoch().saturating_sub(effect.started_at.to_micros_since_unix_epoch());
@@ -166,23 +194,33 @@ pub fn process_active_consumable_effects_tick(ctx: &ReducerContext, _args: Proce
 
                 if amount_this_tick > 0.0 { // Only proceed if there's a positive amount to apply
                     match effect.effect_type {
-                        EffectType::HealthRegen => {
+                        EffectType::HealthRegen | EffectType::WellFed => {
                             log::trace!("[EffectTick] HEALTH_REGEN Pre-Regen for Player {:?}: Health {:.2}, AmountThisTick {:.2}",
                                 effect.player_id, player_to_update.health, amount_this_tick);
                             player_to_update.health = (player_to_update.health + amount_this_tick).clamp(MIN_STAT_VALUE, MAX_STAT_VALUE);
                             log::trace!("[EffectTick] HEALTH_REGEN Post-Regen for Player {:?}: Health now {:.2}",
                                 effect.player_id, player_to_update.health);
                         }
-                        EffectType::Bleed | EffectType::Burn => {
+                        EffectType::Bleed | EffectType::Burn | EffectType::Drowning | EffectType::Radiation | EffectType::Crushed => {
+                            // Armor only shields against fire and radiation; progress still counts the unreduced amount
+                            let damage_this_tick = match effect.effect_type {
+                                EffectType::Burn => amount_this_tick * (1.0 - crate::armor::calculate_total_protection(ctx, effect.player_id, DamageType::Burn)),
+                                EffectType::Radiation => amount_this_tick * (1.0 - crate::armor::calculate_total_protection(ctx, effect.player_id, DamageType::Radiation)),
+                                _ => amount_this_tick,
+                            };
                             log::trace!("[EffectTick] {:?} Pre-Damage for Player {:?}: Health {:.2}, AmountThisTick {:.2}",
                                 effect.effect_type, effect.player_id, player_to_update.health, amount_this_tick);
-                            player_to_update.health = (player_to_update.health - amount_this_tick).clamp(MIN_STAT_VALUE, MAX_STAT_VALUE);
+                            player_to_update.health = (player_to_update.health - damage_this_tick).clamp(MIN_STAT_VALUE, MAX_STAT_VALUE);
                             log::trace!("[EffectTick] {:?} Post-Damage for Player {:?}: Health now {:.2}",
                                 effect.effect_type, effect.player_id, player_to_update.health);
                         }
-                        EffectType::BandageBurst => {
+                        EffectType::Dysentery => {
+                            player_to_update.thirst = (player_to_update.thirst - amount_this_tick).clamp(MIN_STAT_VALUE, MAX_STAT_VALUE);
+                            player_effect_applied_this_iteration = true; // Thirst changed, not health
+                        }
+                        EffectType::BandageBurst | EffectType::Slow | EffectType::Stagger | EffectType::SpawnProtection => {
                             // No healing per tick for BandageBurst, healing is applied only when the effect ends.
-                            // This arm handles the per-tick calculation, so it should be 0 here.
+                            // Slow, Stagger and SpawnProtection are handled above. This arm handles the per-tick calculation, so it should be 0 here.
                             amount_this_tick = 0.0; 
                         }
                     }
@@ -217,6 +255,14 @@ pub fn process_active_consumable_effects_tick(ctx: &ReducerContext, _args: Proce
         // --- Update player_updates map if health changed in this iteration ---
         if player_effect_applied_this_iteration {
             let health_was_reduced = player_to_update.health < old_health;
+            if health_was_reduced {
+                if let Some(cause) = death_cause_for_effect(&effect.effect_type) {
+                    last_damage_cause.insert(effect.player_id, cause);
+                }
+                if effect.effect_type == EffectType::Burn {
+                    crate::player_stats::tag_player_in_combat(ctx, effect.player_id);
+                }
+            }
 
             player_to_update.last_update = current_time;
             player_updates.insert(effect.player_id, player_to_update.clone());
@@ -257,7 +303,13 @@ pub fn process_active_consumable_effects_tick(ctx: &ReducerContext, _args: Proce
     }
 
     // --- Apply all accumulated player updates to the database ---
-    for (player_id, player) in player_updates {
+    for (player_id, mut player) in player_updates {
+        // Players left at zero health by something other than an effect die in the stats tick instead
+        let lethal_cause = if player.health <= 0.0 && !player.is_dead { last_damage_cause.get(&player_id).copied() } else { None };
+        if let Some(cause) = lethal_cause {
+            log::info!("[EffectTick] Player {:?} died from {:?}.", player_id, cause);
+            crate::combat::handle_player_death(ctx, &mut player, current_time, cause, "", None);
+        }
         ctx.db.player().identity().update(player); // This 'player' has the final health after all effects for them this tick
         log::debug!("[EffectTick] Final update for player {:?} applied to DB.", player_id);
     }
@@ -266,6 +318,7 @@ pub fn process_active_consumable_effects_tick(ctx: &ReducerContext, _args: Proce
     for player_id_damaged in player_ids_who_took_external_damage_this_tick {
         log::debug!("[EffectTick] Player {:?} took external damage this tick. Cancelling their BandageBurst effects.", player_id_damaged);
         cancel_bandage_burst_effects(ctx, player_id_damaged);
+        crate::channeled_use::cancel_channel(ctx, player_id_damaged);
     }
     
     // --- Consume items for effects that ended and had a consuming_item_instance_id ---
@@ -301,6 +354,396 @@ pub fn process_active_consumable_effects_tick(ctx: &ReducerContext, _args: Proce
     Ok(())
 }
 
+/// The death cause recorded when a damaging effect kills a player.
+fn death_cause_for_effect(effect_type: &EffectType) -> Option<DeathCause> {
+    match effect_type {
+        EffectType::Burn => Some(DeathCause::Burn),
+        EffectType::Bleed => Some(DeathCause::Bleed),
+        EffectType::Drowning => Some(DeathCause::Drowning),
+        EffectType::Radiation => Some(DeathCause::Radiation),
+        EffectType::Crushed => Some(DeathCause::FallingTree),
+        _ => None,
+    }
+}
+
+/// Queues one tick of drowning damage for a player who is swimming with no stamina left.
+/// Does nothing if a drowning tick is already pending for them.
+pub fn apply_drowning_damage(ctx: &ReducerContext, player_id: Identity, damage: f32) {
+    let effects = ctx.db.active_consumable_effect();
+    if effects.iter().any(|e| e.player_id == player_id && e.effect_type == EffectType::Drowning) {
+        return;
+    }
+    let now = ctx.timestamp;
+    effects.insert(ActiveConsumableEffect {
+        effect_id: 0, // Auto-incremented
+        player_id,
+        item_def_id: 0, // 0 for environmental/non-item effects
+        consuming_item_instance_id: None,
+        started_at: now,
+        ends_at: now + TimeDuration::from_micros(1_000_000),
+        total_amount: Some(damage),
+        amount_applied_so_far: Some(0.0),
+        effect_type: EffectType::Drowning,
+        tick_interval_micros: 1_000_000,
+        next_tick_at: now, // Apply on the next effect tick
+    });
+    log::debug!("[Drowning] Player {:?} is drowning. Queued {:.1} damage.", player_id, damage);
+}
+
+/// Queues the damage of a falling tree landing on a player, applied on the next effect tick.
+pub fn apply_crush_damage(ctx: &ReducerContext, player_id: Identity, damage: f32) {
+    let now = ctx.timestamp;
+    match ctx.db.active_consumable_effect().try_insert(ActiveConsumableEffect {
+        effect_id: 0, // Auto-incremented
+        player_id,
+        item_def_id: 0, // 0 for environmental/non-item effects
+        consuming_item_instance_id: None,
+        started_at: now,
+        ends_at: now + TimeDuration::from_micros(1_000_000),
+        total_amount: Some(damage),
+        amount_applied_so_far: Some(0.0),
+        effect_type: EffectType::Crushed,
+        tick_interval_micros: 1_000_000,
+        next_tick_at: now, // Apply on the next effect tick
+    }) {
+        Ok(_) => log::debug!("[Crushed] Queued {:.1} falling tree damage for player {:?}.", damage, player_id),
+        Err(e) => log::error!("[Crushed] Failed to apply falling tree damage to player {:?}: {:?}", player_id, e),
+    }
+}
+
+/// Queues contact damage from an environmental hazard (see environment_hazard.rs): a short
+/// bleed for cacti and thorns, a one-shot burn for hot stones and flame turrets. Does nothing
+/// while an earlier hazard effect of the same type is still pending, so standing on a hazard
+/// doesn't stack them.
+pub fn apply_hazard_contact_damage(ctx: &ReducerContext, player_id: Identity, effect_type: EffectType, damage: f32, duration_secs: f32) {
+    let effects = ctx.db.active_consumable_effect();
+    let now = ctx.timestamp;
+    if effects.iter().any(|e| e.player_id == player_id && e.effect_type == effect_type && e.item_def_id == 0 && now < e.ends_at) {
+        return;
+    }
+    let duration_micros = (duration_secs * 1_000_000.0) as u64;
+    match effects.try_insert(ActiveConsumableEffect {
+        effect_id: 0, // Auto-incremented
+        player_id,
+        item_def_id: 0, // 0 for environmental/non-item effects
+        consuming_item_instance_id: None,
+        started_at: now,
+        ends_at: now + TimeDuration::from_micros(duration_micros as i64),
+        total_amount: Some(damage),
+        amount_applied_so_far: Some(0.0),
+        effect_type: effect_type.clone(),
+        tick_interval_micros: 1_000_000,
+        next_tick_at: now, // Apply on the next effect tick
+    }) {
+        Ok(_) => log::debug!("[Hazard] Queued {:.1} {:?} damage for player {:?}.", damage, effect_type, player_id),
+        Err(e) => log::error!("[Hazard] Failed to apply hazard damage to player {:?}: {:?}", player_id, e),
+    }
+}
+
+/// Queues the heal of a bandage whose channel just finished, applied on the next effect tick.
+/// The bandage is consumed and on-hit statuses cleansed when it lands.
+pub fn apply_bandage_burst(ctx: &ReducerContext, player_id: Identity, item_def_id: u64, item_instance_id: u64, heal_amount: f32) {
+    let now = ctx.timestamp;
+    match ctx.db.active_consumable_effect().try_insert(ActiveConsumableEffect {
+        effect_id: 0, // Auto-incremented
+        player_id,
+        item_def_id,
+        consuming_item_instance_id: Some(item_instance_id),
+        started_at: now,
+        ends_at: now,
+        total_amount: Some(heal_amount),
+        amount_applied_so_far: Some(0.0),
+        effect_type: EffectType::BandageBurst,
+        tick_interval_micros: 1_000_000,
+        next_tick_at: now, // Apply on the next effect tick
+    }) {
+        Ok(_) => log::debug!("[BandageBurst] Queued {:.1} bandage heal for player {:?}.", heal_amount, player_id),
+        Err(e) => log::error!("[BandageBurst] Failed to apply bandage heal to player {:?}: {:?}", player_id, e),
+    }
+}
+
+/// Adds a stack of radiation to a player standing in a hazard zone. Each stack deals
+/// `damage_per_second` for `duration_secs`; once `MAX_RADIATION_STACKS` are active the
+/// oldest stack is refreshed instead, so damage keeps ramping up only to that cap.
+pub fn apply_radiation_stack(ctx: &ReducerContext, player_id: Identity, damage_per_second: f32, duration_secs: f32) {
+    let effects = ctx.db.active_consumable_effect();
+    let now = ctx.timestamp;
+    let ends_at = now + TimeDuration::from_micros((duration_secs * 1_000_000.0) as i64);
+    let total_damage = damage_per_second * duration_secs;
+
+    let active_stacks: Vec<ActiveConsumableEffect> = effects.iter()
+        .filter(|e| e.player_id == player_id && e.effect_type == EffectType::Radiation)
+        .collect();
+    if active_stacks.len() >= MAX_RADIATION_STACKS {
+        if let Some(mut oldest) = active_stacks.into_iter().min_by_key(|e| e.ends_at) {
+            oldest.started_at = now;
+            oldest.ends_at = ends_at;
+            oldest.total_amount = Some(total_damage);
+            oldest.amount_applied_so_far = Some(0.0);
+            log::trace!("[Radiation] Refreshed radiation stack {} on player {:?} (max stacks reached).", oldest.effect_id, player_id);
+            effects.effect_id().update(oldest);
+        }
+        return;
+    }
+
+    match effects.try_insert(ActiveConsumableEffect {
+        effect_id: 0, // Auto-incremented
+        player_id,
+        item_def_id: 0, // 0 for environmental/non-item effects
+        consuming_item_instance_id: None,
+        started_at: now,
+        ends_at,
+        total_amount: Some(total_damage),
+        amount_applied_so_far: Some(0.0),
+        effect_type: EffectType::Radiation,
+        tick_interval_micros: 1_000_000,
+        next_tick_at: now + TimeDuration::from_micros(1_000_000),
+    }) {
+        Ok(inserted_effect) => log::debug!("[Radiation] Applied radiation stack {} to player {:?} ({:.1} dmg/s).", inserted_effect.effect_id, player_id, damage_per_second),
+        Err(e) => log::error!("[Radiation] Failed to apply radiation to player {:?}: {:?}", player_id, e),
+    }
+}
+
+/// Applies the on-hit status effects defined on the weapon (or ammo) to the target.
+///
+/// Stacking rules: bleeds stack up to `MAX_BLEED_STACKS`, after which the oldest bleed is
+/// refreshed instead. Slows never stack; a new hit refreshes the duration and keeps the
+/// stronger slow.
+pub fn apply_on_hit_effects(ctx: &ReducerContext, target_id: Identity, item_def: &ItemDefinition, timestamp: Timestamp) {
+    if let (Some(dmg_per_tick), Some(duration_sec), Some(interval_sec)) = (
+        item_def.bleed_damage_per_tick,
+        item_def.bleed_duration_seconds,
+        item_def.bleed_tick_interval_seconds,
+    ) {
+        if dmg_per_tick > 0.0 && duration_sec > 0.0 && interval_sec > 0.0 {
+            let total_ticks = (duration_sec / interval_sec).floor();
+            apply_bleed(ctx, target_id, item_def, dmg_per_tick * total_ticks, duration_sec, interval_sec, timestamp);
+        }
+    }
+
+    if let (Some(slow_percent), Some(duration_sec)) = (item_def.slow_percent, item_def.slow_duration_seconds) {
+        if slow_percent > 0.0 && duration_sec > 0.0 {
+            apply_slow(ctx, target_id, item_def, slow_percent.min(1.0), duration_sec, timestamp);
+        }
+    }
+}
+
+fn apply_bleed(ctx: &ReducerContext, target_id: Identity, item_def: &ItemDefinition, total_damage: f32, duration_sec: f32, interval_sec: f32, timestamp: Timestamp) {
+    let effects = ctx.db.active_consumable_effect();
+    let tick_interval_micros = (interval_sec * 1_000_000.0) as u64;
+    let ends_at = timestamp + TimeDuration::from_micros((duration_sec * 1_000_000.0) as i64);
+    let next_tick_at = timestamp + TimeDuration::from_micros(tick_interval_micros as i64);
+
+    let active_bleeds: Vec<ActiveConsumableEffect> = effects.iter()
+        .filter(|e| e.player_id == target_id && e.effect_type == EffectType::Bleed)
+        .collect();
+    if active_bleeds.len() >= MAX_BLEED_STACKS {
+        if let Some(mut oldest) = active_bleeds.into_iter().min_by_key(|e| e.ends_at) {
+            oldest.item_def_id = item_def.id;
+            oldest.started_at = timestamp;
+            oldest.ends_at = ends_at;
+            oldest.total_amount = Some(total_damage);
+            oldest.amount_applied_so_far = Some(0.0);
+            oldest.tick_interval_micros = tick_interval_micros;
+            oldest.next_tick_at = next_tick_at;
+            log::info!("Refreshed bleed effect {} on player {:?} from item '{}' (max stacks reached).", oldest.effect_id, target_id, item_def.name);
+            effects.effect_id().update(oldest);
+        }
+        return;
+    }
+
+    match effects.try_insert(ActiveConsumableEffect {
+        effect_id: 0,
+        player_id: target_id,
+        item_def_id: item_def.id, // Store the ID of the item causing the bleed
+        consuming_item_instance_id: None,
+        started_at: timestamp,
+        ends_at,
+        total_amount: Some(total_damage), // Total potential damage
+        amount_applied_so_far: Some(0.0),
+        effect_type: EffectType::Bleed,
+        tick_interval_micros,
+        next_tick_at,
+    }) {
+        Ok(inserted_effect) => log::info!("Applied bleed effect {} to player {:?} from item '{}'.", inserted_effect.effect_id, target_id, item_def.name),
+        Err(e) => log::error!("Failed to apply bleed effect to player {:?} from item '{}': {:?}", target_id, item_def.name, e),
+    }
+}
+
+fn apply_slow(ctx: &ReducerContext, target_id: Identity, item_def: &ItemDefinition, slow_percent: f32, duration_sec: f32, timestamp: Timestamp) {
+    let effects = ctx.db.active_consumable_effect();
+    let ends_at = timestamp + TimeDuration::from_micros((duration_sec * 1_000_000.0) as i64);
+
+    if let Some(mut existing) = effects.iter().find(|e| e.player_id == target_id && e.effect_type == EffectType::Slow) {
+        existing.total_amount = Some(existing.total_amount.unwrap_or(0.0).max(slow_percent));
+        existing.ends_at = existing.ends_at.max(ends_at);
+        existing.next_tick_at = existing.ends_at; // Only needs a tick once it expires
+        log::info!("Refreshed slow effect {} on player {:?} from item '{}'.", existing.effect_id, target_id, item_def.name);
+        effects.effect_id().update(existing);
+        return;
+    }
+
+    match effects.try_insert(ActiveConsumableEffect {
+        effect_id: 0,
+        player_id: target_id,
+        item_def_id: item_def.id,
+        consuming_item_instance_id: None,
+        started_at: timestamp,
+        ends_at,
+        total_amount: Some(slow_percent),
+        amount_applied_so_far: None,
+        effect_type: EffectType::Slow,
+        tick_interval_micros: (duration_sec * 1_000_000.0) as u64,
+        next_tick_at: ends_at,
+    }) {
+        Ok(inserted_effect) => log::info!("Applied {:.0}% slow effect {} to player {:?} from item '{}'.", slow_percent * 100.0, inserted_effect.effect_id, target_id, item_def.name),
+        Err(e) => log::error!("Failed to apply slow effect to player {:?} from item '{}': {:?}", target_id, item_def.name, e),
+    }
+}
+
+/// Staggers a player who was just knocked back, slowing them by `slow_percent` for a moment.
+/// A new stagger replaces the current one rather than stacking.
+pub fn apply_stagger(ctx: &ReducerContext, target_id: Identity, slow_percent: f32, duration_sec: f32, timestamp: Timestamp) {
+    let effects = ctx.db.active_consumable_effect();
+    let ends_at = timestamp + TimeDuration::from_micros((duration_sec * 1_000_000.0) as i64);
+
+    if let Some(mut existing) = effects.iter().find(|e| e.player_id == target_id && e.effect_type == EffectType::Stagger) {
+        existing.started_at = timestamp;
+        existing.ends_at = ends_at;
+        existing.total_amount = Some(slow_percent);
+        existing.next_tick_at = ends_at;
+        effects.effect_id().update(existing);
+        return;
+    }
+
+    match effects.try_insert(ActiveConsumableEffect {
+        effect_id: 0,
+        player_id: target_id,
+        item_def_id: 0, // 0 for non-item effects
+        consuming_item_instance_id: None,
+        started_at: timestamp,
+        ends_at,
+        total_amount: Some(slow_percent),
+        amount_applied_so_far: None,
+        effect_type: EffectType::Stagger,
+        tick_interval_micros: (duration_sec * 1_000_000.0) as u64,
+        next_tick_at: ends_at,
+    }) {
+        Ok(inserted_effect) => log::debug!("Staggered player {:?} (effect {}).", target_id, inserted_effect.effect_id),
+        Err(e) => log::error!("Failed to stagger player {:?}: {:?}", target_id, e),
+    }
+}
+
+/// Gives a player dysentery, draining `total_thirst_loss` thirst over `duration_secs`.
+/// Does not stack; catching it again restarts the one already running.
+pub fn apply_dysentery(ctx: &ReducerContext, player_id: Identity, total_thirst_loss: f32, duration_secs: f32) {
+    let effects = ctx.db.active_consumable_effect();
+    let now = ctx.timestamp;
+    let ends_at = now + TimeDuration::from_micros((duration_secs * 1_000_000.0) as i64);
+
+    if let Some(mut existing) = effects.iter().find(|e| e.player_id == player_id && e.effect_type == EffectType::Dysentery) {
+        existing.started_at = now;
+        existing.ends_at = ends_at;
+        existing.total_amount = Some(total_thirst_loss);
+        existing.amount_applied_so_far = Some(0.0);
+        log::debug!("[Dysentery] Refreshed dysentery {} on player {:?}.", existing.effect_id, player_id);
+        effects.effect_id().update(existing);
+        return;
+    }
+
+    match effects.try_insert(ActiveConsumableEffect {
+        effect_id: 0, // Auto-incremented
+        player_id,
+        item_def_id: 0, // 0 for environmental/non-item effects
+        consuming_item_instance_id: None,
+        started_at: now,
+        ends_at,
+        total_amount: Some(total_thirst_loss),
+        amount_applied_so_far: Some(0.0),
+        effect_type: EffectType::Dysentery,
+        tick_interval_micros: 1_000_000,
+        next_tick_at: now + TimeDuration::from_micros(1_000_000),
+    }) {
+        Ok(inserted_effect) => log::info!("[Dysentery] Player {:?} caught dysentery (effect {}).", player_id, inserted_effect.effect_id),
+        Err(e) => log::error!("[Dysentery] Failed to apply dysentery to player {:?}: {:?}", player_id, e),
+    }
+}
+
+/// Protects a player who just respawned for SPAWN_PROTECTION_DURATION_SECS, replacing any
+/// protection they still had.
+pub fn apply_spawn_protection(ctx: &ReducerContext, player_id: Identity) {
+    cancel_spawn_protection(ctx, player_id);
+    let ends_at = ctx.timestamp + TimeDuration::from_micros((SPAWN_PROTECTION_DURATION_SECS * 1_000_000.0) as i64);
+    match ctx.db.active_consumable_effect().try_insert(ActiveConsumableEffect {
+        effect_id: 0,
+        player_id,
+        item_def_id: 0, // 0 for non-item effects
+        consuming_item_instance_id: None,
+        started_at: ctx.timestamp,
+        ends_at,
+        total_amount: None,
+        amount_applied_so_far: None,
+        effect_type: EffectType::SpawnProtection,
+        tick_interval_micros: (SPAWN_PROTECTION_DURATION_SECS * 1_000_000.0) as u64,
+        next_tick_at: ends_at,
+    }) {
+        Ok(inserted_effect) => log::info!("Applied spawn protection {} to player {:?}.", inserted_effect.effect_id, player_id),
+        Err(e) => log::error!("Failed to apply spawn protection to player {:?}: {:?}", player_id, e),
+    }
+}
+
+/// Whether the player is still under spawn protection.
+pub fn has_spawn_protection(ctx: &ReducerContext, player_id: Identity, timestamp: Timestamp) -> bool {
+    ctx.db.active_consumable_effect().iter()
+        .any(|e| e.player_id == player_id && e.effect_type == EffectType::SpawnProtection && timestamp < e.ends_at)
+}
+
+/// Whether campfires and environmental hazards can hurt the player right now. Dead players,
+/// players mid dodge-roll and spawn-protected players are spared.
+pub fn can_take_environmental_damage(ctx: &ReducerContext, player: &Player, timestamp: Timestamp) -> bool {
+    !player.is_dead
+        && !crate::is_dodge_invulnerable(player, timestamp)
+        && !has_spawn_protection(ctx, player.identity, timestamp)
+}
+
+/// Ends spawn protection early. Called when the player attacks or uses a container.
+pub fn cancel_spawn_protection(ctx: &ReducerContext, player_id: Identity) {
+    let mut effects_to_cancel = Vec::new();
+    for effect in ctx.db.active_consumable_effect().iter().filter(|e| e.player_id == player_id && e.effect_type == EffectType::SpawnProtection) {
+        effects_to_cancel.push(effect.effect_id);
+    }
+    for effect_id in effects_to_cancel {
+        ctx.db.active_consumable_effect().effect_id().delete(effect_id);
+        log::info!("Cancelled spawn protection effect {} for player {:?}.", effect_id, player_id);
+    }
+}
+
+/// Multiplier on stamina drained while sprinting (1.0 unless well-fed).
+pub fn get_stamina_drain_multiplier(ctx: &ReducerContext, player_id: Identity) -> f32 {
+    let is_well_fed = ctx.db.active_consumable_effect().iter()
+        .any(|e| e.player_id == player_id && e.effect_type == EffectType::WellFed && ctx.timestamp < e.ends_at);
+    if is_well_fed { WELL_FED_STAMINA_DRAIN_MULTIPLIER } else { 1.0 }
+}
+
+pub fn cancel_well_fed_effects(ctx: &ReducerContext, player_id: Identity) {
+    let mut effects_to_cancel = Vec::new();
+    for effect in ctx.db.active_consumable_effect().iter().filter(|e| e.player_id == player_id && e.effect_type == EffectType::WellFed) {
+        effects_to_cancel.push(effect.effect_id);
+    }
+    for effect_id in effects_to_cancel {
+        ctx.db.active_consumable_effect().effect_id().delete(effect_id);
+    }
+}
+
+/// Movement speed multiplier from active slows and staggers (1.0 when not slowed).
+pub fn get_movement_speed_multiplier(ctx: &ReducerContext, player_id: Identity) -> f32 {
+    let strongest_slow = ctx.db.active_consumable_effect().iter()
+        .filter(|e| e.player_id == player_id && matches!(e.effect_type, EffectType::Slow | EffectType::Stagger) && ctx.timestamp < e.ends_at)
+        .filter_map(|e| e.total_amount)
+        .fold(0.0f32, f32::max);
+    (1.0 - strongest_slow).clamp(0.0, 1.0)
+}
+
 pub fn cancel_health_regen_effects(ctx: &ReducerContext, player_id: Identity) {
     let mut effects_to_cancel = Vec::new();
     for effect in ctx.db.active_consumable_effect().iter().filter(|e| e.player_id == player_id && e.effect_type == EffectType::HealthRegen) {
@@ -323,6 +766,23 @@ pub fn cancel_bleed_effects(ctx: &ReducerContext, player_id: Identity) {
     }
 }
 
+pub fn cancel_slow_effects(ctx: &ReducerContext, player_id: Identity) {
+    let mut effects_to_cancel = Vec::new();
+    for effect in ctx.db.active_consumable_effect().iter().filter(|e| e.player_id == player_id && e.effect_type == EffectType::Slow) {
+        effects_to_cancel.push(effect.effect_id);
+    }
+    for effect_id in effects_to_cancel {
+        ctx.db.active_consumable_effect().effect_id().delete(effect_id);
+        log::info!("Cancelled slow effect {} for player {:?} (e.g., by bandage).", effect_id, player_id);
+    }
+}
+
+/// Removes every on-hit status effect (bleed, slow). Called when a bandage finishes applying.
+pub fn cleanse_status_effects(ctx: &ReducerContext, player_id: Identity) {
+    cancel_bleed_effects(ctx, player_id);
+    cancel_slow_effects(ctx, player_id);
+}
+
 pub fn cancel_bandage_burst_effects(ctx: &ReducerContext, player_id: Identity) {
     let mut effects_to_cancel = Vec::new();
     for effect in ctx.db.active_consumable_effect().iter().filter(|e| e.player_id == player_id && e.effect_type == EffectType::BandageBurst) {
@@ -19,12 +19,11 @@ use rand::{Rng, SeedableRng};
 // Combat system imports
 use crate::combat::{RESPAWN_TIME_MS};
 use crate::combat::{
-    find_targets_in_cone, find_best_target, process_attack
+    find_targets_in_cone, find_best_target, process_attack, Swing
 };
 
 // Consumable and active effects imports
 use crate::consumables::MAX_STAT_VALUE;
-use crate::consumables::apply_item_effects_and_consume;
 use crate::active_effects::{ActiveConsumableEffect, EffectType, cancel_bleed_effects, cancel_health_regen_effects, active_consumable_effect as ActiveConsumableEffectTableTrait, cancel_bandage_burst_effects};
 
 // Collision constants
@@ -62,6 +61,12 @@ const PLAYER_INTERACT_DISTANCE: f32 = 80.0;
 /// Squared interaction distance for faster distance checks
 const PLAYER_INTERACT_DISTANCE_SQUARED: f32 = PLAYER_INTERACT_DISTANCE * PLAYER_INTERACT_DISTANCE;
 
+// --- Heavy Attack Constants ---
+/// A heavy attack needs this many times the item's attack interval since the last swing
+const HEAVY_ATTACK_INTERVAL_MULTIPLIER: f32 = 2.0;
+/// Stamina a heavy attack costs relative to a normal swing
+const HEAVY_ATTACK_STAMINA_MULTIPLIER: f32 = 2.5;
+
 /// Represents a player's equipped items, both in hand and armor slots
 #[spacetimedb::table(name = active_equipment, public)]
 #[derive(Clone, Default, Debug)]
@@ -92,8 +97,9 @@ pub fn set_active_item_reducer(ctx: &ReducerContext, item_instance_id: u64) -> R
     let active_equipments = ctx.db.active_equipment();
     let mut players_table = ctx.db.player(); // Get a mutable reference for player updates
 
-    // Cancel any ongoing BandageBurst effect before equipping a new item or re-equipping.
+    // Cancel any ongoing BandageBurst effect or channel before equipping a new item or re-equipping.
     cancel_bandage_burst_effects(ctx, sender_id);
+    crate::channeled_use::cancel_channel(ctx, sender_id);
 
     let item_to_make_active = inventory_items.instance_id().find(item_instance_id)
         .ok_or_else(|| format!("Inventory item with instance ID {} not found.", item_instance_id))?;
@@ -201,8 +207,9 @@ pub fn clear_active_item_reducer(ctx: &ReducerContext, player_identity: Identity
     let item_defs = ctx.db.item_definition(); // For checking item name
     let mut players_table = ctx.db.player(); // For updating player state
 
-    // Cancel any ongoing BandageBurst effect when clearing the active item.
+    // Cancel any ongoing BandageBurst effect or channel when clearing the active item.
     cancel_bandage_burst_effects(ctx, player_identity);
+    crate::channeled_use::cancel_channel(ctx, player_identity);
 
     if let Some(mut equipment) = active_equipments.player_identity().find(player_identity) {
         // Store old item def ID before clearing for torch check
@@ -249,6 +256,26 @@ pub fn clear_active_item_reducer(ctx: &ReducerContext, player_identity: Identity
 /// damage application, and resource gathering.
 #[spacetimedb::reducer]
 pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
+    swing_equipped_item(ctx, Swing::default())
+}
+
+/// Same as `use_equipped_item`, but also says where on the tree or stone being hit the
+/// swing landed, relative to its centre, so it can earn the sweet-spot bonus.
+#[spacetimedb::reducer]
+pub fn use_equipped_item_at(ctx: &ReducerContext, hit_offset_x: f32, hit_offset_y: f32) -> Result<(), String> {
+    swing_equipped_item(ctx, Swing { hit_offset: Some((hit_offset_x, hit_offset_y)), heavy: false })
+}
+
+/// A slower, harder swing with the equipped melee item. Needs a longer pause since the
+/// last swing and more stamina, and deals and yields more than a normal swing.
+#[spacetimedb::reducer]
+pub fn heavy_attack(ctx: &ReducerContext) -> Result<(), String> {
+    swing_equipped_item(ctx, Swing { hit_offset: None, heavy: true })
+}
+
+fn swing_equipped_item(ctx: &ReducerContext, swing: Swing) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    crate::moderation::ensure_not_sanctioned(ctx, ctx.sender)?;
     let sender_id = ctx.sender;
     let now_ts = ctx.timestamp;
     let now_micros = now_ts.to_micros_since_unix_epoch();
@@ -274,9 +301,21 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
     let item_def = item_defs.id().find(item_def_id)
         .ok_or_else(|| "Equipped item definition not found".to_string())?;
 
+    // --- BROKEN ITEM CHECK ---
+    if let Some(equipped_item) = ctx.db.inventory_item().instance_id().find(equipped_item_instance_id) {
+        if crate::combat::is_item_broken(&equipped_item, &item_def) {
+            return Err(format!("Your {} is broken and needs to be repaired.", item_def.name));
+        }
+    }
+
+    if swing.heavy && item_def.stamina_cost_per_swing.is_none() {
+        return Err(format!("{} can't make heavy attacks.", item_def.name));
+    }
+
     // --- BEGIN ATTACK SPEED CHECK ---
     if let Some(attack_interval_seconds) = item_def.attack_interval_secs {
         if attack_interval_seconds > 0.0 { // Only check if interval is positive
+            let attack_interval_seconds = if swing.heavy { attack_interval_seconds * HEAVY_ATTACK_INTERVAL_MULTIPLIER } else { attack_interval_seconds };
             let attack_interval_micros_u64 = (attack_interval_seconds * 1_000_000.0) as u64;
             if let Some(last_attack_record) = player_last_attack_timestamps.player_id().find(&sender_id) {
                 let time_since_last_attack_micros_u64 = now_micros.saturating_sub(last_attack_record.last_attack_timestamp.to_micros_since_unix_epoch());
@@ -299,44 +338,24 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
         log::info!("[UseEquippedItem] Player {:?} is using an equipped Bandage (Instance: {}, Def: {}).", 
             sender_id, equipped_item_instance_id, item_def.id);
 
-        // Check for existing active BandageBurst effect from ANY bandage item to prevent stacking this specific effect type.
-        let has_active_bandage_burst_effect = ctx.db.active_consumable_effect().iter().any(|effect| {
-            effect.player_id == sender_id && 
-            effect.effect_type == EffectType::BandageBurst 
-            // We don't need to check effect.item_def_id == item_def.id here, 
-            // as we just want to prevent any BandageBurst from stacking, regardless of specific bandage type if we had multiple.
-        });
-
-        if has_active_bandage_burst_effect {
-            log::warn!("[UseEquippedItem] Player {:?} tried to use Bandage while another BandageBurst effect is already active.", sender_id);
-            return Err("You are already applying a bandage.".to_string());
-        }
-
-        // Player state is needed for apply_item_effects_and_consume (though it won't change health directly for BandageBurst)
-        // and for last_consumed_at update.
-        let mut player_stats = players_table.identity().find(sender_id)
-            .ok_or_else(|| "Player not found for bandage use".to_string())?;
-        
-        // This will create the BandageBurst effect. Item is NOT consumed here.
-        match apply_item_effects_and_consume(ctx, sender_id, &item_def, equipped_item_instance_id, &mut player_stats) {
-            Ok(_) => {
-                // Update player for last_consumed_at (which is set by apply_item_effects_and_consume)
-                players_table.identity().update(player_stats); 
-                
-                log::info!("[UseEquippedItem] BandageBurst effect initiated for player {:?} with bandage instance {}.", 
-                    sender_id, equipped_item_instance_id);
-                // No change to current_equipment is needed here as item is not consumed yet and bandaging_start_time_ms is removed.
-                // The active_equipments table also doesn't need an update here just for using a bandage.
-            },
-            Err(e) => {
-                log::error!("[UseEquippedItem] Failed to apply bandage effects for player {:?}: {}", sender_id, e);
-                return Err(format!("Failed to apply bandage effects: {}", e));
-            }
-        }
+        // Bandaging is channeled; moving or taking damage before it finishes interrupts it.
+        crate::channeled_use::begin_channel(ctx, sender_id, equipped_item_instance_id, &item_def)?;
         return Ok(()); // Bandage handling complete
     }
     // --- END BANDAGE HANDLING ---
 
+    // --- STAMINA COST ---
+    let stamina_cost = item_def.stamina_cost_per_swing.unwrap_or(0.0)
+        * if swing.heavy { HEAVY_ATTACK_STAMINA_MULTIPLIER } else { 1.0 };
+    if stamina_cost > 0.0 {
+        if player.stamina < stamina_cost {
+            return Err("Not enough stamina to swing.".to_string());
+        }
+        let mut tired_player = player.clone();
+        tired_player.stamina -= stamina_cost;
+        players_table.identity().update(tired_player);
+    }
+
     // Default values for attack cone
     let mut actual_attack_range = PLAYER_RADIUS * 4.0;
     let mut actual_attack_angle_degrees = 90.0;
@@ -352,6 +371,9 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
     let mut current_equipment_mut = current_equipment.clone(); // Clone to modify for swing time
     current_equipment_mut.swing_start_time_ms = now_ms;
     active_equipments.player_identity().update(current_equipment_mut); // Update with new swing time
+    // Swinging counts as exertion for hunger and thirst drain, and gives up spawn protection
+    crate::player_stats::record_player_attack(ctx, sender_id);
+    crate::active_effects::cancel_spawn_protection(ctx, sender_id);
 
     // --- UPDATE LAST ATTACK TIMESTAMP ---
     if item_def.attack_interval_secs.is_some() && item_def.attack_interval_secs.unwrap_or(0.0) > 0.0 {
@@ -371,10 +393,13 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
     log::debug!("[UseEquippedItem] Player {:?} started using non-bandage item '{}' (ID: {}). Swing time set.",
              sender_id, item_def.name, item_def_id);
     
+    // Every swing wears the tool down, hit or miss
+    crate::combat::apply_durability_loss(ctx, equipped_item_instance_id, &item_def);
+
     let targets = find_targets_in_cone(ctx, &player, actual_attack_range, actual_attack_angle_degrees);
     
     if let Some(target) = find_best_target(&targets, &item_def) {
-        match process_attack(ctx, sender_id, &target, &item_def, now_ts, &mut rng) {
+        match process_attack(ctx, sender_id, &target, &item_def, swing, now_ts, &mut rng) {
             Ok(result) => {
                 if result.hit {
                     log::debug!("Player {:?} hit a {:?} with {}.", sender_id, result.target_type, item_def.name);
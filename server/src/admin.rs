@@ -0,0 +1,25 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines server administrators. The admin table is private: the database   *
+ * owner grants admin rights by inserting identities directly, e.g.           *
+ * `spacetime sql <db> "INSERT INTO admin (identity) VALUES (0x...)"`.        *
+ * Other modules check rights through `is_admin`.                             *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext};
+
+use crate::admin::admin as AdminTableTrait;
+
+/// --- Admin Data Structure ---
+#[spacetimedb::table(name = admin)]
+#[derive(Clone, Debug)]
+pub struct Admin {
+    #[primary_key]
+    pub identity: Identity,
+}
+
+/// Returns true if the identity has been granted admin rights.
+pub(crate) fn is_admin(ctx: &ReducerContext, identity: Identity) -> bool {
+    ctx.db.admin().identity().find(identity).is_some()
+}
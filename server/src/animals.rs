@@ -0,0 +1,496 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines wild animals (deer and wolves). A scheduled AI tick keeps the      *
+ * population topped up and moves every animal: deer flee nearby players,     *
+ * wolves chase and bite them. Animals are damaged through combat.rs and      *
+ * drop meat, fat and hide (cloth) when killed. Raiders spawned by raid       *
+ * events (events.rs) share the wolf chase logic but are never respawned.     *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+use rand::{Rng, SeedableRng};
+
+use crate::{WORLD_WIDTH_PX, WORLD_HEIGHT_PX};
+use crate::Player;
+use crate::player as PlayerTableTrait;
+use crate::items::item_definition as ItemDefinitionTableTrait;
+use crate::dropped_item;
+use crate::utils::get_distance_squared;
+use crate::environment::{calculate_chunk_index, sync_chunk_index};
+use crate::models::{DamageType, DeathCause};
+use crate::spatial_grid::{EntityType, get_entities_in_range, track_entity};
+use crate::tree::{tree as TreeTableTrait, TREE_TRUNK_RADIUS, TREE_COLLISION_Y_OFFSET};
+use crate::stone::{stone as StoneTableTrait, STONE_RADIUS, STONE_COLLISION_Y_OFFSET};
+use crate::wooden_storage_box::{wooden_storage_box as WoodenStorageBoxTableTrait, BOX_COLLISION_RADIUS, BOX_COLLISION_Y_OFFSET};
+use crate::furnace::{furnace as FurnaceTableTrait, FURNACE_COLLISION_RADIUS, FURNACE_COLLISION_Y_OFFSET};
+use crate::building::{building_piece as BuildingPieceTableTrait, BUILDING_COLLISION_Y_OFFSET};
+use crate::animals::wild_animal as WildAnimalTableTrait;
+use crate::animals::animal_ai_schedule as AnimalAiScheduleTableTrait;
+
+// --- Constants ---
+pub(crate) const ANIMAL_AI_TICK_INTERVAL_MS: u64 = 250;
+pub(crate) const ANIMAL_COLLISION_RADIUS: f32 = 24.0;
+const TARGET_DEER_COUNT: usize = 60;
+const TARGET_WOLF_COUNT: usize = 25;
+const MAX_SPAWNS_PER_TICK: usize = 2;
+/// Animals never spawn this close to a living player.
+const MIN_SPAWN_DISTANCE_FROM_PLAYER: f32 = 800.0;
+const MAX_SPAWN_ATTEMPTS: u32 = 10;
+/// How long a wandering animal keeps its heading (or stays idle) before re-rolling.
+const WANDER_DECISION_INTERVAL_MS: u64 = 4000;
+/// How long a deer keeps running after being hit, even if no one is nearby.
+const FLEE_AFTER_HIT_MS: u64 = 5000;
+
+/// Harvestable items dropped on death: (item name, min quantity, max quantity).
+type AnimalLootTable = &'static [(&'static str, u32, u32)];
+
+/// Per-species tuning values.
+pub(crate) struct AnimalStats {
+    pub max_health: f32,
+    pub walk_speed: f32,
+    pub run_speed: f32,
+    /// Deer flee from / wolves aggro on players inside this radius.
+    pub perception_radius: f32,
+    /// Wolves give up a chase once the target is this far away.
+    pub leash_radius: f32,
+    pub attack_range: f32,
+    pub attack_damage: f32,
+    pub attack_cooldown_ms: u64,
+    pub loot: AnimalLootTable,
+}
+
+const DEER_STATS: AnimalStats = AnimalStats {
+    max_health: 100.0,
+    walk_speed: 60.0,
+    run_speed: 260.0,
+    perception_radius: 300.0,
+    leash_radius: 0.0,
+    attack_range: 0.0,
+    attack_damage: 0.0,
+    attack_cooldown_ms: 0,
+    loot: &[("Raw Meat", 3, 5), ("Animal Fat", 2, 4), ("Cloth", 5, 10)],
+};
+
+const WOLF_STATS: AnimalStats = AnimalStats {
+    max_health: 150.0,
+    walk_speed: 80.0,
+    run_speed: 240.0,
+    perception_radius: 350.0,
+    leash_radius: 700.0,
+    attack_range: 56.0,
+    attack_damage: 12.0,
+    attack_cooldown_ms: 1200,
+    loot: &[("Raw Meat", 2, 4), ("Animal Fat", 1, 3), ("Cloth", 3, 6)],
+};
+
+/// Raiders leave a loot crate instead of harvestable loot (see `combat::damage_animal`).
+const RAIDER_STATS: AnimalStats = AnimalStats {
+    max_health: 120.0,
+    walk_speed: 80.0,
+    run_speed: 200.0,
+    perception_radius: 1200.0,
+    leash_radius: 2000.0,
+    attack_range: 56.0,
+    attack_damage: 10.0,
+    attack_cooldown_ms: 1000,
+    loot: &[],
+};
+
+#[derive(SpacetimeType, Copy, Clone, Debug, PartialEq)]
+pub enum AnimalSpecies {
+    Deer,
+    Wolf,
+    Raider,
+}
+
+impl AnimalSpecies {
+    pub(crate) fn stats(&self) -> &'static AnimalStats {
+        match self {
+            AnimalSpecies::Deer => &DEER_STATS,
+            AnimalSpecies::Wolf => &WOLF_STATS,
+            AnimalSpecies::Raider => &RAIDER_STATS,
+        }
+    }
+
+    /// Display name used as the damage source in death messages.
+    pub(crate) fn display_name(&self) -> &'static str {
+        match self {
+            AnimalSpecies::Deer => "Deer",
+            AnimalSpecies::Wolf => "Wolf",
+            AnimalSpecies::Raider => "Raider",
+        }
+    }
+}
+
+#[derive(SpacetimeType, Copy, Clone, Debug, PartialEq)]
+pub enum AnimalState {
+    Idle,
+    Wandering,
+    Fleeing,
+    Chasing,
+}
+
+/// --- Wild Animal Data Structure ---
+/// `target_player_id` is the player being fled from (deer) or chased (wolf, raider).
+/// `raid_event_id` links raiders to the raid event that spawned them.
+#[spacetimedb::table(name = wild_animal, public)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct WildAnimal {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub species: AnimalSpecies,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    #[index(btree)]
+    pub chunk_index: u32,
+    pub health: f32,
+    pub max_health: f32,
+    pub state: AnimalState,
+    pub target_player_id: Option<Identity>,
+    pub direction_x: f32,
+    pub direction_y: f32,
+    pub state_changed_at: Timestamp,
+    pub last_attack_time: Option<Timestamp>,
+    pub last_hit_time: Option<Timestamp>,
+    pub raid_event_id: Option<u64>,
+}
+
+/// --- Animal AI Schedule ---
+#[spacetimedb::table(name = animal_ai_schedule, scheduled(process_animal_ai))]
+#[derive(Clone, Debug)]
+pub struct AnimalAiSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/// Starts the animal AI tick if it isn't already scheduled. Called from `init_module`.
+pub fn init_animal_ai_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.animal_ai_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting animal AI schedule (every {}ms).", ANIMAL_AI_TICK_INTERVAL_MS);
+        let interval = Duration::from_millis(ANIMAL_AI_TICK_INTERVAL_MS);
+        schedule_table.try_insert(AnimalAiSchedule {
+            id: 0,
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert animal AI schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                                 AI TICK                                    *
+ ******************************************************************************/
+
+/// Scheduled reducer: spawns animals up to the target population and runs one AI step for each.
+#[spacetimedb::reducer]
+pub fn process_animal_ai(ctx: &ReducerContext, _schedule: AnimalAiSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("process_animal_ai may only be called by the scheduler.".to_string());
+    }
+
+    let mut rng = rand::rngs::StdRng::from_rng(ctx.rng()).map_err(|e| format!("Failed to create RNG: {}", e))?;
+    let living_players: Vec<Player> = ctx.db.player().iter()
+        .filter(|p| p.is_online && !p.is_dead)
+        .collect();
+
+    maintain_population(ctx, &living_players, &mut rng);
+
+    let animals: Vec<WildAnimal> = ctx.db.wild_animal().iter().collect();
+    if animals.is_empty() {
+        return Ok(());
+    }
+
+    let delta_secs = ANIMAL_AI_TICK_INTERVAL_MS as f32 / 1000.0;
+
+    for mut animal in animals {
+        let before = animal.clone();
+        let speed = match animal.species {
+            AnimalSpecies::Deer => update_deer(ctx, &mut animal, &living_players, &mut rng),
+            AnimalSpecies::Wolf | AnimalSpecies::Raider => update_predator(ctx, &mut animal, &living_players, &mut rng),
+        };
+        if speed > 0.0 {
+            move_animal(ctx, &mut animal, speed * delta_secs);
+        }
+        if animal != before {
+            ctx.db.wild_animal().id().update(animal);
+        }
+    }
+    Ok(())
+}
+
+/// Deer flee from the nearest player in range, otherwise wander. Returns the movement speed for this tick.
+fn update_deer(ctx: &ReducerContext, deer: &mut WildAnimal, players: &[Player], rng: &mut impl Rng) -> f32 {
+    let stats = deer.species.stats();
+    let now = ctx.timestamp;
+
+    let threat = find_nearest_player(deer, players, stats.perception_radius)
+        .map(|p| (p.identity, p.position_x, p.position_y))
+        .or_else(|| {
+            // Recently hit: keep running from the attacker even if they are out of sight
+            if deer.state != AnimalState::Fleeing || millis_since(deer.state_changed_at, now) > FLEE_AFTER_HIT_MS {
+                return None;
+            }
+            let attacker_id = deer.target_player_id?;
+            players.iter().find(|p| p.identity == attacker_id).map(|p| (p.identity, p.position_x, p.position_y))
+        });
+
+    match threat {
+        Some((threat_id, threat_x, threat_y)) => {
+            if deer.state != AnimalState::Fleeing || deer.target_player_id != Some(threat_id) {
+                set_state(deer, AnimalState::Fleeing, Some(threat_id), now);
+            }
+            face_towards(deer, deer.pos_x * 2.0 - threat_x, deer.pos_y * 2.0 - threat_y);
+            stats.run_speed
+        }
+        None => wander(deer, rng, now),
+    }
+}
+
+/// Wolves and raiders chase the nearest player in range and attack them when close enough.
+/// Returns the movement speed for this tick.
+fn update_predator(ctx: &ReducerContext, predator: &mut WildAnimal, players: &[Player], rng: &mut impl Rng) -> f32 {
+    let stats = predator.species.stats();
+    let now = ctx.timestamp;
+
+    // Keep the current target while it is alive and within the leash, otherwise look for a new one
+    let current_target = predator.target_player_id
+        .filter(|_| predator.state == AnimalState::Chasing)
+        .and_then(|target_id| players.iter().find(|p| p.identity == target_id))
+        .filter(|p| get_distance_squared(predator.pos_x, predator.pos_y, p.position_x, p.position_y) <= stats.leash_radius * stats.leash_radius);
+    let target = current_target.or_else(|| find_nearest_player(predator, players, stats.perception_radius));
+
+    let Some(target) = target else {
+        return wander(predator, rng, now);
+    };
+    if predator.state != AnimalState::Chasing || predator.target_player_id != Some(target.identity) {
+        set_state(predator, AnimalState::Chasing, Some(target.identity), now);
+    }
+    face_towards(predator, target.position_x, target.position_y);
+
+    let dist_sq = get_distance_squared(predator.pos_x, predator.pos_y, target.position_x, target.position_y);
+    if dist_sq > stats.attack_range * stats.attack_range {
+        return stats.run_speed;
+    }
+
+    let cooldown_ready = predator.last_attack_time
+        .is_none_or(|last| millis_since(last, now) >= stats.attack_cooldown_ms);
+    if cooldown_ready {
+        predator.last_attack_time = Some(now);
+        match crate::combat::damage_player_from_environment(ctx, target.identity, stats.attack_damage, DamageType::Melee, DeathCause::Animal, predator.species.display_name(), now) {
+            Ok(true) => {
+                // Target died, go back to roaming
+                set_state(predator, AnimalState::Idle, None, now);
+            }
+            Ok(false) => {}
+            Err(e) => log::error!("[AnimalAI] {:?} {} failed to attack {:?}: {}", predator.species, predator.id, target.identity, e),
+        }
+    }
+    0.0 // Stand still while biting
+}
+
+/// Alternates between idling and walking in a random direction. Returns the movement speed for this tick.
+fn wander(animal: &mut WildAnimal, rng: &mut impl Rng, now: Timestamp) -> f32 {
+    let calm = matches!(animal.state, AnimalState::Idle | AnimalState::Wandering);
+    if !calm || millis_since(animal.state_changed_at, now) >= WANDER_DECISION_INTERVAL_MS {
+        if rng.gen_bool(0.5) {
+            set_state(animal, AnimalState::Idle, None, now);
+        } else {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            animal.direction_x = angle.cos();
+            animal.direction_y = angle.sin();
+            set_state(animal, AnimalState::Wandering, None, now);
+        }
+    }
+    match animal.state {
+        AnimalState::Wandering => animal.species.stats().walk_speed,
+        _ => 0.0,
+    }
+}
+
+/// Moves the animal along its heading, sliding along an axis if the direct path is blocked.
+fn move_animal(ctx: &ReducerContext, animal: &mut WildAnimal, distance: f32) {
+    let step_x = animal.direction_x * distance;
+    let step_y = animal.direction_y * distance;
+    let candidates = [
+        (animal.pos_x + step_x, animal.pos_y + step_y),
+        (animal.pos_x + step_x, animal.pos_y),
+        (animal.pos_x, animal.pos_y + step_y),
+    ];
+
+    for (x, y) in candidates {
+        let x = x.clamp(ANIMAL_COLLISION_RADIUS, WORLD_WIDTH_PX - ANIMAL_COLLISION_RADIUS);
+        let y = y.clamp(ANIMAL_COLLISION_RADIUS, WORLD_HEIGHT_PX - ANIMAL_COLLISION_RADIUS);
+        if !is_position_blocked(ctx, x, y) {
+            animal.pos_x = x;
+            animal.pos_y = y;
+            sync_chunk_index(&mut animal.chunk_index, x, y);
+            track_entity(ctx, EntityType::Animal(animal.id), x, y);
+            return;
+        }
+    }
+
+    // Fully blocked: a wandering animal picks a new heading on its next decision
+    if animal.state == AnimalState::Wandering {
+        animal.direction_x = -animal.direction_x;
+        animal.direction_y = -animal.direction_y;
+    }
+}
+
+/// Returns true if an animal at (x, y) would overlap a tree, stone, storage box, furnace or solid building piece.
+fn is_position_blocked(ctx: &ReducerContext, x: f32, y: f32) -> bool {
+    let overlaps = |cx: f32, cy: f32, radius: f32| {
+        let min_dist = radius + ANIMAL_COLLISION_RADIUS;
+        get_distance_squared(x, y, cx, cy) < min_dist * min_dist
+    };
+
+    get_entities_in_range(ctx, x, y).into_iter().any(|entity| match entity {
+        EntityType::Tree(tree_id) => ctx.db.tree().id().find(tree_id)
+            .is_some_and(|t| t.health > 0 && overlaps(t.pos_x, t.pos_y - TREE_COLLISION_Y_OFFSET, TREE_TRUNK_RADIUS)),
+        EntityType::Stone(stone_id) => ctx.db.stone().id().find(stone_id)
+            .is_some_and(|s| s.health > 0 && overlaps(s.pos_x, s.pos_y - STONE_COLLISION_Y_OFFSET, STONE_RADIUS)),
+        EntityType::WoodenStorageBox(box_id) => ctx.db.wooden_storage_box().id().find(box_id)
+            .is_some_and(|b| !b.is_destroyed && overlaps(b.pos_x, b.pos_y - BOX_COLLISION_Y_OFFSET, BOX_COLLISION_RADIUS)),
+        EntityType::Furnace(furnace_id) => ctx.db.furnace().id().find(furnace_id)
+            .is_some_and(|f| !f.is_destroyed && overlaps(f.pos_x, f.pos_y - FURNACE_COLLISION_Y_OFFSET, FURNACE_COLLISION_RADIUS)),
+        EntityType::BuildingPiece(piece_id) => ctx.db.building_piece().id().find(piece_id)
+            .is_some_and(|p| p.is_solid() && overlaps(p.pos_x, p.pos_y - BUILDING_COLLISION_Y_OFFSET, p.collision_radius())),
+        _ => false,
+    })
+}
+
+/// Spawns a few animals per tick until each species reaches its target count.
+fn maintain_population(ctx: &ReducerContext, players: &[Player], rng: &mut impl Rng) {
+    let animals = ctx.db.wild_animal();
+    let deer_count = animals.iter().filter(|a| a.species == AnimalSpecies::Deer).count();
+    let wolf_count = animals.iter().filter(|a| a.species == AnimalSpecies::Wolf).count();
+
+    let mut to_spawn: Vec<AnimalSpecies> = Vec::new();
+    to_spawn.extend(std::iter::repeat_n(AnimalSpecies::Deer, TARGET_DEER_COUNT.saturating_sub(deer_count)));
+    to_spawn.extend(std::iter::repeat_n(AnimalSpecies::Wolf, TARGET_WOLF_COUNT.saturating_sub(wolf_count)));
+
+    for species in to_spawn.into_iter().take(MAX_SPAWNS_PER_TICK) {
+        let position = (0..MAX_SPAWN_ATTEMPTS)
+            .map(|_| (
+                rng.gen_range(ANIMAL_COLLISION_RADIUS..WORLD_WIDTH_PX - ANIMAL_COLLISION_RADIUS),
+                rng.gen_range(ANIMAL_COLLISION_RADIUS..WORLD_HEIGHT_PX - ANIMAL_COLLISION_RADIUS),
+            ))
+            .find(|&(x, y)| players.iter().all(|p| {
+                get_distance_squared(x, y, p.position_x, p.position_y) >= MIN_SPAWN_DISTANCE_FROM_PLAYER * MIN_SPAWN_DISTANCE_FROM_PLAYER
+            }));
+        let Some((pos_x, pos_y)) = position else { continue; };
+
+        let max_health = species.stats().max_health;
+        match animals.try_insert(WildAnimal {
+            id: 0, // Auto-incremented
+            species,
+            pos_x,
+            pos_y,
+            chunk_index: calculate_chunk_index(pos_x, pos_y),
+            health: max_health,
+            max_health,
+            state: AnimalState::Idle,
+            target_player_id: None,
+            direction_x: 0.0,
+            direction_y: 1.0,
+            state_changed_at: ctx.timestamp,
+            last_attack_time: None,
+            last_hit_time: None,
+            raid_event_id: None,
+        }) {
+            Ok(animal) => {
+                track_entity(ctx, EntityType::Animal(animal.id), pos_x, pos_y);
+                log::debug!("[AnimalAI] Spawned {:?} {} at ({:.1}, {:.1}).", species, animal.id, pos_x, pos_y);
+            },
+            Err(e) => log::error!("[AnimalAI] Failed to spawn {:?}: {}", species, e),
+        }
+    }
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Called by combat.rs when an animal survives a hit: deer bolt, wolves turn on the attacker.
+pub(crate) fn react_to_attack(animal: &mut WildAnimal, attacker_id: Identity, timestamp: Timestamp) {
+    let state = match animal.species {
+        AnimalSpecies::Deer => AnimalState::Fleeing,
+        AnimalSpecies::Wolf | AnimalSpecies::Raider => AnimalState::Chasing,
+    };
+    set_state(animal, state, Some(attacker_id), timestamp);
+}
+
+/// Spawns a raider for a raid event, already chasing the targeted player.
+pub(crate) fn spawn_raider(ctx: &ReducerContext, raid_event_id: u64, pos_x: f32, pos_y: f32, target_player_id: Identity) -> Result<WildAnimal, String> {
+    let max_health = RAIDER_STATS.max_health;
+    let raider = ctx.db.wild_animal().try_insert(WildAnimal {
+        id: 0, // Auto-incremented
+        species: AnimalSpecies::Raider,
+        pos_x,
+        pos_y,
+        chunk_index: calculate_chunk_index(pos_x, pos_y),
+        health: max_health,
+        max_health,
+        state: AnimalState::Chasing,
+        target_player_id: Some(target_player_id),
+        direction_x: 0.0,
+        direction_y: 1.0,
+        state_changed_at: ctx.timestamp,
+        last_attack_time: None,
+        last_hit_time: None,
+        raid_event_id: Some(raid_event_id),
+    }).map_err(|e| format!("Failed to spawn raider: {}", e))?;
+    track_entity(ctx, EntityType::Animal(raider.id), pos_x, pos_y);
+    Ok(raider)
+}
+
+/// Scatters the species' loot around the animal's position. Called by combat.rs on death.
+pub(crate) fn drop_animal_loot(ctx: &ReducerContext, animal: &WildAnimal, rng: &mut impl Rng) {
+    let item_defs = ctx.db.item_definition();
+    for &(item_name, min_qty, max_qty) in animal.species.stats().loot {
+        let Some(def) = item_defs.iter().find(|d| d.name == item_name) else {
+            log::error!("[AnimalLoot] Item definition '{}' not found.", item_name);
+            continue;
+        };
+        let quantity = rng.gen_range(min_qty..=max_qty);
+        let offset_x = (rng.gen::<f32>() - 0.5) * 2.0 * 20.0; // Spread within +/- 20px
+        let offset_y = (rng.gen::<f32>() - 0.5) * 2.0 * 20.0;
+        if let Err(e) = dropped_item::create_dropped_item_entity(ctx, def.id, quantity, animal.pos_x + offset_x, animal.pos_y + offset_y) {
+            log::error!("[AnimalLoot] Failed to drop {} {}: {}", quantity, item_name, e);
+        }
+    }
+}
+
+fn find_nearest_player<'a>(animal: &WildAnimal, players: &'a [Player], radius: f32) -> Option<&'a Player> {
+    players.iter()
+        .map(|p| (p, get_distance_squared(animal.pos_x, animal.pos_y, p.position_x, p.position_y)))
+        .filter(|(_, d)| *d <= radius * radius)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(p, _)| p)
+}
+
+fn set_state(animal: &mut WildAnimal, state: AnimalState, target: Option<Identity>, now: Timestamp) {
+    animal.state = state;
+    animal.target_player_id = target;
+    animal.state_changed_at = now;
+}
+
+/// Points the animal's heading at (x, y). Leaves it unchanged if already there.
+fn face_towards(animal: &mut WildAnimal, x: f32, y: f32) {
+    let dx = x - animal.pos_x;
+    let dy = y - animal.pos_y;
+    let len = (dx * dx + dy * dy).sqrt();
+    if len > 0.001 {
+        animal.direction_x = dx / len;
+        animal.direction_y = dy / len;
+    }
+}
+
+fn millis_since(earlier: Timestamp, now: Timestamp) -> u64 {
+    (now.to_micros_since_unix_epoch() - earlier.to_micros_since_unix_epoch()).max(0) as u64 / 1000
+}
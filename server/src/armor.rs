@@ -1,41 +1,148 @@
 use spacetimedb::{Identity, ReducerContext, Table};
 use crate::active_equipment::{ActiveEquipment, active_equipment as ActiveEquipmentTableTrait};
-use crate::items::{ItemDefinition, item_definition as ItemDefinitionTableTrait, InventoryItem, inventory_item as InventoryItemTableTrait};
-use crate::models::EquipmentSlotType; // For matching slot types if needed in future extensions
+use crate::items::{ItemCategory, ItemDefinition, item_definition as ItemDefinitionTableTrait, InventoryItem, inventory_item as InventoryItemTableTrait};
+use crate::models::{DamageType, EquipmentSlotType};
+use crate::armor::armor_resistance as ArmorResistanceTableTrait;
 use log;
 
-/// Calculates the total damage resistance from all equipped armor pieces.
-/// Resistance is a float (e.g., 0.1 for 10%), and they stack additively for now.
-pub fn calculate_total_damage_resistance(ctx: &ReducerContext, player_id: Identity) -> f32 {
-    let active_equipments = ctx.db.active_equipment();
+/// Durability of wearable armor pieces; they stop protecting once it reaches zero.
+pub(crate) const ARMOR_MAX_DURABILITY: f32 = 100.0;
+/// Durability each equipped armor piece loses when its wearer is hit.
+pub(crate) const ARMOR_DURABILITY_LOSS_PER_HIT: f32 = 1.0;
+/// Total protection is capped to prevent invulnerability.
+const MAX_TOTAL_PROTECTION: f32 = 0.9;
+
+/// (armor name, melee, projectile, burn, radiation) resistances seeded into `ArmorResistance`.
+/// Armor without an entry resists melee, projectile and burn damage by its definition's
+/// `damage_resistance`, and gives no radiation protection.
+const ARMOR_RESISTANCES: &[(&str, f32, f32, f32, f32)] = &[
+    ("Cloth Hood",   0.03, 0.02, 0.01, 0.0),
+    ("Cloth Shirt",  0.05, 0.04, 0.02, 0.0),
+    ("Cloth Pants",  0.04, 0.03, 0.02, 0.0),
+    ("Cloth Gloves", 0.02, 0.01, 0.01, 0.0),
+    ("Cloth Boots",  0.02, 0.01, 0.01, 0.0),
+    ("Cloth Cape",   0.02, 0.02, 0.03, 0.0),
+    ("Hazmat Suit",  0.02, 0.02, 0.02, 0.6),
+];
+
+/// Per-damage-type resistances of an armor item definition.
+/// Resistance is a float (e.g., 0.1 for 10%); pieces stack additively.
+#[spacetimedb::table(name = armor_resistance, public)]
+#[derive(Clone, Debug)]
+pub struct ArmorResistance {
+    #[primary_key]
+    pub item_def_id: u64,
+    pub melee: f32,
+    pub projectile: f32,
+    pub burn: f32,
+    pub radiation: f32,
+}
+
+impl ArmorResistance {
+    pub fn against(&self, damage_type: DamageType) -> f32 {
+        match damage_type {
+            DamageType::Melee => self.melee,
+            DamageType::Projectile => self.projectile,
+            DamageType::Burn => self.burn,
+            DamageType::Radiation => self.radiation,
+        }
+    }
+}
+
+/// Seeds resistances for every armor definition that doesn't have a row yet.
+pub fn seed_armor_resistances(ctx: &ReducerContext) -> Result<(), String> {
+    let resistances = ctx.db.armor_resistance();
+    let mut seeded_count = 0;
+    for item_def in ctx.db.item_definition().iter().filter(|def| def.category == ItemCategory::Armor) {
+        if resistances.item_def_id().find(item_def.id).is_some() {
+            continue;
+        }
+        let base = item_def.damage_resistance.unwrap_or(0.0);
+        let (melee, projectile, burn, radiation) = ARMOR_RESISTANCES.iter()
+            .find(|(name, ..)| *name == item_def.name)
+            .map(|&(_, melee, projectile, burn, radiation)| (melee, projectile, burn, radiation))
+            .unwrap_or((base, base, base, 0.0));
+        resistances.try_insert(ArmorResistance { item_def_id: item_def.id, melee, projectile, burn, radiation })
+            .map_err(|e| format!("Failed to seed armor resistance for {}: {}", item_def.name, e))?;
+        seeded_count += 1;
+    }
+    if seeded_count > 0 {
+        log::info!("Seeded resistances for {} armor definitions.", seeded_count);
+    }
+    Ok(())
+}
+
+/// Equipped armor that counts towards protection, paired with its definition.
+/// Pieces sitting in a slot other than their definition's slot, and broken pieces, are skipped.
+fn equipped_armor_pieces(ctx: &ReducerContext, equipment: &ActiveEquipment) -> Vec<(InventoryItem, ItemDefinition)> {
     let inventory_items = ctx.db.inventory_item();
     let item_defs = ctx.db.item_definition();
-    let mut total_resistance = 0.0;
+    let armor_slots = [
+        (EquipmentSlotType::Head, equipment.head_item_instance_id),
+        (EquipmentSlotType::Chest, equipment.chest_item_instance_id),
+        (EquipmentSlotType::Legs, equipment.legs_item_instance_id),
+        (EquipmentSlotType::Feet, equipment.feet_item_instance_id),
+        (EquipmentSlotType::Hands, equipment.hands_item_instance_id),
+        (EquipmentSlotType::Back, equipment.back_item_instance_id),
+    ];
 
-    if let Some(equipment) = active_equipments.player_identity().find(player_id) {
-        let armor_instance_ids = [
-            equipment.head_item_instance_id,
-            equipment.chest_item_instance_id,
-            equipment.legs_item_instance_id,
-            equipment.feet_item_instance_id,
-            equipment.hands_item_instance_id,
-            equipment.back_item_instance_id,
-        ];
+    let mut pieces = Vec::new();
+    for (slot_type, maybe_instance_id) in armor_slots {
+        let Some(instance_id) = maybe_instance_id else { continue; };
+        let Some(item_instance) = inventory_items.instance_id().find(instance_id) else { continue; };
+        let Some(item_def) = item_defs.id().find(item_instance.item_def_id) else { continue; };
+        if item_def.category != ItemCategory::Armor || item_def.equipment_slot_type != Some(slot_type) {
+            log::warn!("[Armor] Ignoring {} (Instance ID: {}) equipped in mismatched slot {:?}.", item_def.name, instance_id, slot_type);
+            continue;
+        }
+        if crate::combat::is_item_broken(&item_instance, &item_def) {
+            continue;
+        }
+        pieces.push((item_instance, item_def));
+    }
+    pieces
+}
 
-        for maybe_instance_id in armor_instance_ids.iter().flatten() {
-            if let Some(item_instance) = inventory_items.instance_id().find(*maybe_instance_id) {
-                if let Some(item_def) = item_defs.id().find(item_instance.item_def_id) {
-                    if let Some(resistance) = item_def.damage_resistance {
-                        total_resistance += resistance;
-                        log::trace!("[Armor] Player {:?} adding resistance {:.2}% from {} (Instance ID: {})", 
-                                   player_id, resistance * 100.0, item_def.name, *maybe_instance_id);
-                    }
-                }
-            }
+/// Calculates the player's total protection against a damage type from all equipped armor.
+/// Used by combat, burn and radiation effects: incoming damage is multiplied by `1.0 - protection`.
+pub fn calculate_total_protection(ctx: &ReducerContext, player_id: Identity, damage_type: DamageType) -> f32 {
+    let Some(equipment) = ctx.db.active_equipment().player_identity().find(player_id) else {
+        return 0.0;
+    };
+    let resistances = ctx.db.armor_resistance();
+    let mut total_protection = 0.0;
+
+    for (item_instance, item_def) in equipped_armor_pieces(ctx, &equipment) {
+        let resistance = match resistances.item_def_id().find(item_def.id) {
+            Some(row) => row.against(damage_type),
+            None if damage_type == DamageType::Radiation => 0.0,
+            None => item_def.damage_resistance.unwrap_or(0.0),
+        };
+        total_protection += resistance;
+        log::trace!("[Armor] Player {:?} adding {:?} resistance {:.2}% from {} (Instance ID: {})",
+                   player_id, damage_type, resistance * 100.0, item_def.name, item_instance.instance_id);
+    }
+    total_protection.min(MAX_TOTAL_PROTECTION)
+}
+
+/// Wears down every equipped armor piece of a player who was just hit.
+pub fn apply_armor_durability_loss(ctx: &ReducerContext, player_id: Identity) {
+    let Some(equipment) = ctx.db.active_equipment().player_identity().find(player_id) else {
+        return;
+    };
+    let inventory_items = ctx.db.inventory_item();
+
+    for (mut item_instance, item_def) in equipped_armor_pieces(ctx, &equipment) {
+        let Some(max_durability) = item_def.max_durability else { continue; };
+        let old_durability = item_instance.durability.unwrap_or(max_durability);
+        let new_durability = (old_durability - ARMOR_DURABILITY_LOSS_PER_HIT).max(0.0);
+        item_instance.durability = Some(new_durability);
+        let instance_id = item_instance.instance_id;
+        inventory_items.instance_id().update(item_instance);
+        if new_durability <= 0.0 {
+            log::info!("[Armor] {} (Instance ID: {}) worn by {:?} broke.", item_def.name, instance_id, player_id);
         }
     }
-    // Clamp resistance to a max (e.g., 90%) to prevent invulnerability
-    total_resistance.min(0.9) 
 }
 
 /// Calculates the total warmth bonus from all equipped armor pieces.
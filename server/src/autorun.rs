@@ -0,0 +1,178 @@
+/******************************************************************************
+ *                                                                            *
+ * Auto-run. `set_autorun` stores a direction the player keeps walking in     *
+ * and `move_to_point` a spot to walk to; the global tick advances them       *
+ * server-side through the same movement code as `update_player_position`     *
+ * (speed modifiers, collisions, stats), so the client doesn't have to send   *
+ * input every frame. Manual input, death, spectating, logging off or         *
+ * reaching the target ends it.                                               *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+
+use crate::{MAX_MOVEMENT_DELTA_SECS, WORLD_WIDTH_PX, WORLD_HEIGHT_PX};
+use crate::player as PlayerTableTrait;
+use crate::global_tick::GLOBAL_TICK_INTERVAL_SECS;
+use crate::autorun::autorun_intent as AutorunIntentTableTrait;
+
+// --- Constants ---
+/// Farthest away a move-to-point target can be.
+const MAX_MOVE_TO_POINT_DISTANCE: f32 = 2000.0;
+/// A move-to-point ends once the player is this close to the target.
+const MOVE_TO_POINT_ARRIVAL_DISTANCE: f32 = 8.0;
+
+/// --- Autorun Intent ---
+/// A player's standing movement order. Public so clients can show that auto-run is on.
+#[spacetimedb::table(name = autorun_intent, public)]
+#[derive(Clone, Debug)]
+pub struct AutorunIntent {
+    #[primary_key]
+    pub player_id: Identity,
+    pub direction_x: f32, // Normalized; ignored while a target is set
+    pub direction_y: f32,
+    pub target_x: Option<f32>, // Set for move-to-point
+    pub target_y: Option<f32>,
+    pub last_advanced_at: Timestamp,
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Set Autorun ---
+/// Keeps the player walking in (direction_x, direction_y) until cancelled.
+/// A zero direction turns auto-run off.
+#[spacetimedb::reducer]
+pub fn set_autorun(ctx: &ReducerContext, direction_x: f32, direction_y: f32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let length = (direction_x * direction_x + direction_y * direction_y).sqrt();
+    if !length.is_finite() || length < 0.01 {
+        cancel_autorun(ctx, sender_id);
+        return Ok(());
+    }
+    ensure_can_autorun(ctx, sender_id)?;
+    upsert_intent(ctx, AutorunIntent {
+        player_id: sender_id,
+        direction_x: direction_x / length,
+        direction_y: direction_y / length,
+        target_x: None,
+        target_y: None,
+        last_advanced_at: ctx.timestamp,
+    });
+    log::debug!("[Autorun] Player {:?} auto-running towards ({:.2}, {:.2}).", sender_id, direction_x / length, direction_y / length);
+    Ok(())
+}
+
+/// --- Move To Point ---
+/// Walks the player to (target_x, target_y), then stops.
+#[spacetimedb::reducer]
+pub fn move_to_point(ctx: &ReducerContext, target_x: f32, target_y: f32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let player = ensure_can_autorun(ctx, sender_id)?;
+    if !(0.0..=WORLD_WIDTH_PX).contains(&target_x) || !(0.0..=WORLD_HEIGHT_PX).contains(&target_y) {
+        return Err("Target is outside the world.".to_string());
+    }
+    let (dx, dy) = (target_x - player.position_x, target_y - player.position_y);
+    if dx * dx + dy * dy > MAX_MOVE_TO_POINT_DISTANCE * MAX_MOVE_TO_POINT_DISTANCE {
+        return Err("Target is too far away.".to_string());
+    }
+    upsert_intent(ctx, AutorunIntent {
+        player_id: sender_id,
+        direction_x: 0.0,
+        direction_y: 0.0,
+        target_x: Some(target_x),
+        target_y: Some(target_y),
+        last_advanced_at: ctx.timestamp,
+    });
+    log::debug!("[Autorun] Player {:?} moving to ({:.1}, {:.1}).", sender_id, target_x, target_y);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Stops the player's auto-run or move-to-point, if any.
+pub(crate) fn cancel_autorun(ctx: &ReducerContext, player_id: Identity) {
+    if ctx.db.autorun_intent().player_id().delete(player_id) {
+        log::debug!("[Autorun] Player {:?} stopped auto-running.", player_id);
+    }
+}
+
+/// Advances every auto-running player by the time since their last advance, in steps no
+/// longer than a normal movement update so collisions aren't skipped. Called every global tick.
+pub(crate) fn tick_autorun(ctx: &ReducerContext) {
+    let intents = ctx.db.autorun_intent();
+    let all_intents: Vec<AutorunIntent> = intents.iter().collect();
+    for mut intent in all_intents {
+        let player_id = intent.player_id;
+        let Some(player) = ctx.db.player().identity().find(player_id) else {
+            intents.player_id().delete(player_id);
+            continue;
+        };
+        if !player.is_online || player.is_dead || crate::spectator::is_spectating(ctx, player_id) {
+            cancel_autorun(ctx, player_id);
+            continue;
+        }
+
+        let elapsed_micros = ctx.timestamp.to_micros_since_unix_epoch() - intent.last_advanced_at.to_micros_since_unix_epoch();
+        let mut remaining_secs = (elapsed_micros as f32 / 1_000_000.0).clamp(0.0, GLOBAL_TICK_INTERVAL_SECS as f32);
+        let mut arrived = false;
+        while remaining_secs > 0.0 {
+            let step_secs = remaining_secs.min(MAX_MOVEMENT_DELTA_SECS);
+            remaining_secs -= step_secs;
+            let Some((move_x, move_y)) = current_direction(ctx, &intent) else {
+                arrived = true;
+                break;
+            };
+            if let Err(e) = crate::move_player(ctx, player_id, move_x, move_y, Some(step_secs)) {
+                log::warn!("[Autorun] Failed to move player {:?}: {}", player_id, e);
+                break;
+            }
+            // A step can carry the player past a move-to-point target; that counts as arriving
+            if current_direction(ctx, &intent).is_some_and(|(x, y)| x * move_x + y * move_y <= 0.0) {
+                arrived = true;
+                break;
+            }
+        }
+
+        if arrived || current_direction(ctx, &intent).is_none() {
+            cancel_autorun(ctx, player_id);
+        } else {
+            intent.last_advanced_at = ctx.timestamp;
+            intents.player_id().update(intent);
+        }
+    }
+}
+
+/// Direction the player should move in right now, or None once a move-to-point has arrived.
+fn current_direction(ctx: &ReducerContext, intent: &AutorunIntent) -> Option<(f32, f32)> {
+    let (Some(target_x), Some(target_y)) = (intent.target_x, intent.target_y) else {
+        return Some((intent.direction_x, intent.direction_y));
+    };
+    let player = ctx.db.player().identity().find(intent.player_id)?;
+    let (dx, dy) = (target_x - player.position_x, target_y - player.position_y);
+    let distance = (dx * dx + dy * dy).sqrt();
+    (distance > MOVE_TO_POINT_ARRIVAL_DISTANCE).then(|| (dx / distance, dy / distance))
+}
+
+/// Errors unless the player exists, is alive and isn't spectating.
+fn ensure_can_autorun(ctx: &ReducerContext, player_id: Identity) -> Result<crate::Player, String> {
+    crate::spectator::ensure_not_spectating(ctx, player_id)?;
+    let player = ctx.db.player().identity().find(player_id)
+        .ok_or_else(|| "Player not found".to_string())?;
+    if player.is_dead {
+        return Err("Cannot move while dead.".to_string());
+    }
+    Ok(player)
+}
+
+fn upsert_intent(ctx: &ReducerContext, intent: AutorunIntent) {
+    let intents = ctx.db.autorun_intent();
+    if intents.player_id().find(intent.player_id).is_some() {
+        intents.player_id().update(intent);
+    } else {
+        intents.insert(intent);
+    }
+}
@@ -0,0 +1,155 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines the berry bush resource. Unlike mushrooms or corn, a bush is not   *
+ * used up in one go: it holds a number of ripe berries, each interaction     *
+ * picks a few of them, and a schedule grows them back one at a time. A bush  *
+ * with no berries left stays in the world in a bare (depleted) state.        *
+ *                                                                            *
+ ******************************************************************************/
+
+// SpacetimeDB imports
+use spacetimedb::{Table, ReducerContext, Timestamp, TimeDuration, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+
+// Module imports
+use crate::collectible_resources::{
+    validate_player_resource_interaction,
+    scale_respawn_secs,
+    RespawnableResource
+};
+
+// Table trait imports for database access
+use crate::items::item_definition as ItemDefinitionTableTrait;
+use crate::berry_bush::berry_bush as BerryBushTableTrait;
+use crate::berry_bush::berry_bush_regrowth_schedule as BerryBushRegrowthScheduleTableTrait;
+
+// --- Spawning Constants ---
+/// Target percentage of map tiles containing berry bushes
+pub const BERRY_BUSH_DENSITY_PERCENT: f32 = 0.0015;
+/// Minimum distance between berry bushes to prevent clustering
+pub const MIN_BERRY_BUSH_DISTANCE_SQ: f32 = 45.0 * 45.0;
+/// Minimum distance from trees for better distribution
+pub const MIN_BERRY_BUSH_TREE_DISTANCE_SQ: f32 = 25.0 * 25.0;
+/// Minimum distance from stones for better distribution
+pub const MIN_BERRY_BUSH_STONE_DISTANCE_SQ: f32 = 25.0 * 25.0;
+
+// --- Harvest / Regrowth Constants ---
+const BERRY_ITEM_NAME: &str = "Wild Berries";
+/// Berries on a fully grown bush
+pub const BERRY_BUSH_MAX_BERRIES: u32 = 9;
+/// Berries picked per interaction (fewer if the bush has fewer left)
+const BERRIES_PER_HARVEST: u32 = 3;
+/// Time for a single berry to grow back, before the day growth multiplier
+const BERRY_REGROWTH_SECS: u64 = 60;
+const BERRY_BUSH_REGROWTH_CHECK_INTERVAL_SECS: u64 = 10;
+
+/// Represents a berry bush in the game world.
+/// `respawn_at` is when the next berry ripens; `None` while the bush is full.
+#[spacetimedb::table(name = berry_bush, public)]
+#[derive(Clone, Debug)]
+pub struct BerryBush {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    #[index(btree)]
+    pub chunk_index: u32,
+    pub berries_remaining: u32,
+    pub respawn_at: Option<Timestamp>,
+}
+
+// Implement RespawnableResource trait for BerryBush
+impl RespawnableResource for BerryBush {
+    fn id(&self) -> u64 { self.id }
+    fn pos_x(&self) -> f32 { self.pos_x }
+    fn pos_y(&self) -> f32 { self.pos_y }
+    fn respawn_at(&self) -> Option<Timestamp> { self.respawn_at }
+    fn set_respawn_at(&mut self, time: Option<Timestamp>) { self.respawn_at = time; }
+}
+
+/// --- Berry Bush Regrowth Schedule ---
+#[spacetimedb::table(name = berry_bush_regrowth_schedule, scheduled(process_berry_bush_regrowth))]
+#[derive(Clone, Debug)]
+pub struct BerryBushRegrowthSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/// Handles player interactions with a berry bush, picking a few of its ripe berries.
+#[spacetimedb::reducer]
+pub fn interact_with_berry_bush(ctx: &ReducerContext, bush_id: u64) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let sender_id = ctx.sender;
+    let bushes = ctx.db.berry_bush();
+
+    let mut bush = bushes.id().find(bush_id)
+        .ok_or_else(|| format!("Berry bush {} not found", bush_id))?;
+
+    // Validate player can interact (distance check)
+    let _player = validate_player_resource_interaction(ctx, sender_id, bush.pos_x(), bush.pos_y())?;
+
+    if bush.berries_remaining == 0 {
+        return Err("This bush has no ripe berries yet.".to_string());
+    }
+
+    let berry_def = ctx.db.item_definition().iter()
+        .find(|def| def.name == BERRY_ITEM_NAME)
+        .ok_or_else(|| format!("Item definition '{}' not found", BERRY_ITEM_NAME))?;
+    let picked = BERRIES_PER_HARVEST.min(bush.berries_remaining);
+    crate::items::add_item_to_player_inventory(ctx, sender_id, berry_def.id, picked)?;
+
+    bush.berries_remaining -= picked;
+    // Start regrowing if the bush was full; a bush already regrowing keeps its timer
+    if bush.respawn_at.is_none() {
+        bush.set_respawn_at(Some(next_berry_time(ctx)));
+    }
+    log::info!("Player {:?} picked {} berries from bush {} ({} left).", sender_id, picked, bush_id, bush.berries_remaining);
+    bushes.id().update(bush);
+    Ok(())
+}
+
+/// Starts the berry regrowth schedule if it isn't already scheduled. Called from `init_module`.
+pub fn init_berry_bush_regrowth_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.berry_bush_regrowth_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting berry bush regrowth schedule (every {}s).", BERRY_BUSH_REGROWTH_CHECK_INTERVAL_SECS);
+        let interval = Duration::from_secs(BERRY_BUSH_REGROWTH_CHECK_INTERVAL_SECS);
+        schedule_table.try_insert(BerryBushRegrowthSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert berry bush regrowth schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: ripens one berry on every bush whose regrowth timer has elapsed.
+#[spacetimedb::reducer]
+pub fn process_berry_bush_regrowth(ctx: &ReducerContext, _schedule: BerryBushRegrowthSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("process_berry_bush_regrowth may only be called by the scheduler.".to_string());
+    }
+
+    let bushes = ctx.db.berry_bush();
+    let ripening: Vec<BerryBush> = bushes.iter()
+        .filter(|b| b.respawn_at.is_some_and(|t| ctx.timestamp >= t))
+        .collect();
+    for mut bush in ripening {
+        bush.berries_remaining = (bush.berries_remaining + 1).min(BERRY_BUSH_MAX_BERRIES);
+        let next = if bush.berries_remaining < BERRY_BUSH_MAX_BERRIES { Some(next_berry_time(ctx)) } else { None };
+        bush.set_respawn_at(next);
+        bushes.id().update(bush);
+    }
+    Ok(())
+}
+
+/// When the next berry should ripen, counting from now.
+fn next_berry_time(ctx: &ReducerContext) -> Timestamp {
+    let growth_multiplier = crate::game_config::get_game_config(ctx).day_plant_growth_multiplier
+        * crate::world_state::current_season(ctx).plant_growth_multiplier();
+    let secs = scale_respawn_secs(BERRY_REGROWTH_SECS, growth_multiplier);
+    ctx.timestamp + TimeDuration::from(Duration::from_secs(secs))
+}
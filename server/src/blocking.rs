@@ -0,0 +1,128 @@
+/******************************************************************************
+ *                                                                            *
+ * Blocking with a shield. A player holding a Wooden Shield can raise it with *
+ * `set_blocking(true)`. While blocking, melee and projectile damage taken    *
+ * through combat.rs `damage_player` is reduced, stamina drains instead of    *
+ * recovering (player_stats.rs) and movement is slowed (lib.rs). Running out  *
+ * of stamina breaks the block, and it can't be raised again until            *
+ * BLOCK_BREAK_SECS have passed. Putting the shield away ends the block.      *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, TimeDuration, log};
+use std::time::Duration;
+
+use crate::models::DamageType;
+use crate::player as PlayerTableTrait;
+use crate::active_equipment::active_equipment as ActiveEquipmentTableTrait;
+use crate::items::item_definition as ItemDefinitionTableTrait;
+use crate::blocking::player_block as PlayerBlockTableTrait;
+
+// --- Constants ---
+const SHIELD_ITEM_NAME: &str = "Wooden Shield";
+/// Fraction of melee and projectile damage a raised shield stops.
+const BLOCK_DAMAGE_REDUCTION: f32 = 0.6;
+const BLOCK_STAMINA_DRAIN_PER_SECOND: f32 = 6.0;
+const BLOCK_SPEED_MULTIPLIER: f32 = 0.6;
+/// How long a block broken by running out of stamina stays down.
+const BLOCK_BREAK_SECS: u64 = 3;
+
+/// --- Player Block ---
+/// Whether a player has their shield raised. Public so clients can show the blocking pose.
+#[spacetimedb::table(name = player_block, public)]
+#[derive(Clone, Debug)]
+pub struct PlayerBlock {
+    #[primary_key]
+    pub player_id: Identity,
+    pub is_blocking: bool,
+    pub broken_until: Option<Timestamp>, // Set when stamina ran out mid-block
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Set Blocking ---
+/// Raises or lowers the sender's shield. Raising needs a shield in hand, some stamina, and
+/// the last broken block to have recovered.
+#[spacetimedb::reducer]
+pub fn set_blocking(ctx: &ReducerContext, blocking: bool) -> Result<(), String> {
+    let player_id = ctx.sender;
+    let blocks = ctx.db.player_block();
+    let existing = blocks.player_id().find(player_id);
+
+    if blocking {
+        crate::spectator::ensure_not_spectating(ctx, player_id)?;
+        let player = ctx.db.player().identity().find(player_id)
+            .ok_or_else(|| "Player not found".to_string())?;
+        if player.is_dead {
+            return Err("Cannot block while dead.".to_string());
+        }
+        if !is_holding_shield(ctx, player_id) {
+            return Err(format!("You need a {} in hand to block.", SHIELD_ITEM_NAME));
+        }
+        if player.stamina <= 0.0 {
+            return Err("Too tired to block.".to_string());
+        }
+        if existing.as_ref().and_then(|b| b.broken_until).is_some_and(|until| ctx.timestamp < until) {
+            return Err("Your guard is broken. Catch your breath first.".to_string());
+        }
+    }
+
+    let block = PlayerBlock {
+        player_id,
+        is_blocking: blocking,
+        broken_until: existing.as_ref().and_then(|b| b.broken_until),
+    };
+    if existing.is_some() {
+        blocks.player_id().update(block);
+    } else {
+        blocks.insert(block);
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Whether the player has their shield raised and is still holding it.
+pub(crate) fn is_blocking(ctx: &ReducerContext, player_id: Identity) -> bool {
+    ctx.db.player_block().player_id().find(player_id).is_some_and(|b| b.is_blocking)
+        && is_holding_shield(ctx, player_id)
+}
+
+/// Multiplier on damage of this type taken by the player. Below 1 for melee and
+/// projectile hits while they block.
+pub(crate) fn get_damage_multiplier(ctx: &ReducerContext, player_id: Identity, damage_type: DamageType) -> f32 {
+    match damage_type {
+        DamageType::Melee | DamageType::Projectile if is_blocking(ctx, player_id) => 1.0 - BLOCK_DAMAGE_REDUCTION,
+        _ => 1.0,
+    }
+}
+
+pub(crate) fn get_movement_speed_multiplier(ctx: &ReducerContext, player_id: Identity) -> f32 {
+    if is_blocking(ctx, player_id) { BLOCK_SPEED_MULTIPLIER } else { 1.0 }
+}
+
+/// Drains stamina for `elapsed_seconds` of blocking and returns what is left. Breaks the
+/// block when stamina runs out. Called from the stat tick while the player blocks.
+pub(crate) fn drain_block_stamina(ctx: &ReducerContext, player_id: Identity, stamina: f32, elapsed_seconds: f32) -> f32 {
+    let new_stamina = (stamina - elapsed_seconds * BLOCK_STAMINA_DRAIN_PER_SECOND).max(0.0);
+    if new_stamina <= 0.0 {
+        ctx.db.player_block().player_id().update(PlayerBlock {
+            player_id,
+            is_blocking: false,
+            broken_until: Some(ctx.timestamp + TimeDuration::from(Duration::from_secs(BLOCK_BREAK_SECS))),
+        });
+        log::debug!("Player {:?} ran out of stamina and lost their block.", player_id);
+    }
+    new_stamina
+}
+
+fn is_holding_shield(ctx: &ReducerContext, player_id: Identity) -> bool {
+    ctx.db.active_equipment().player_identity().find(player_id)
+        .and_then(|equipment| equipment.equipped_item_def_id)
+        .and_then(|def_id| ctx.db.item_definition().id().find(def_id))
+        .is_some_and(|def| def.name == SHIELD_ITEM_NAME)
+}
@@ -0,0 +1,336 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines the building system: placeable structural pieces (foundations,     *
+ * walls, doorways and doors) that snap to the tile grid. Walls and closed    *
+ * doors are solid and take part in player collision via the spatial grid,    *
+ * and all pieces can be damaged/destroyed through combat.rs.                 *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+
+// --- Constants ---
+pub(crate) const WALL_COLLISION_RADIUS: f32 = 24.0; // Half a tile, adjacent walls touch
+pub(crate) const DOOR_COLLISION_RADIUS: f32 = 24.0;
+pub(crate) const BUILDING_COLLISION_Y_OFFSET: f32 = 0.0; // Pieces are centered on their tile
+pub(crate) const PLAYER_WALL_COLLISION_DISTANCE_SQUARED: f32 = (super::PLAYER_RADIUS + WALL_COLLISION_RADIUS) * (super::PLAYER_RADIUS + WALL_COLLISION_RADIUS);
+const BUILDING_INTERACTION_DISTANCE_SQUARED: f32 = 96.0 * 96.0; // Slightly larger than boxes, doors are tall
+const BUILDING_PLACEMENT_RANGE_SQ: f32 = 128.0 * 128.0;
+
+pub(crate) const FOUNDATION_MAX_HEALTH: f32 = 1500.0;
+pub(crate) const WALL_MAX_HEALTH: f32 = 1000.0;
+pub(crate) const DOORWAY_MAX_HEALTH: f32 = 800.0;
+pub(crate) const DOOR_MAX_HEALTH: f32 = 500.0;
+
+// --- Import Table Traits and Concrete Types ---
+use crate::player as PlayerTableTrait;
+use crate::Player;
+use crate::items::{
+    inventory_item as InventoryItemTableTrait,
+    item_definition as ItemDefinitionTableTrait,
+};
+use crate::building::building_piece as BuildingPieceTableTrait;
+use crate::environment::calculate_chunk_index;
+use crate::models::ItemLocation;
+use crate::player_inventory::get_player_item;
+use crate::TILE_SIZE_PX;
+
+/// The different kinds of structural pieces a player can build.
+#[derive(SpacetimeType, Copy, Clone, Debug, PartialEq)]
+pub enum BuildingPieceType {
+    Foundation, // Floor layer, never blocks movement
+    Wall,       // Always blocks movement
+    Doorway,    // Wall with an opening, a Door can be hung in it
+    Door,       // Blocks movement while closed
+}
+
+impl BuildingPieceType {
+    /// Maps an item definition name to the piece it places, if any.
+    pub fn from_item_name(name: &str) -> Option<Self> {
+        match name {
+            "Wooden Foundation" => Some(BuildingPieceType::Foundation),
+            "Wooden Wall" => Some(BuildingPieceType::Wall),
+            "Wooden Doorway" => Some(BuildingPieceType::Doorway),
+            "Wooden Door" => Some(BuildingPieceType::Door),
+            _ => None,
+        }
+    }
+
+    pub fn max_health(&self) -> f32 {
+        match self {
+            BuildingPieceType::Foundation => FOUNDATION_MAX_HEALTH,
+            BuildingPieceType::Wall => WALL_MAX_HEALTH,
+            BuildingPieceType::Doorway => DOORWAY_MAX_HEALTH,
+            BuildingPieceType::Door => DOOR_MAX_HEALTH,
+        }
+    }
+}
+
+/// --- Building Piece Data Structure ---
+/// A single structural piece occupying one tile of the world grid.
+/// Position is the center of the tile the piece was snapped to.
+#[spacetimedb::table(name = building_piece, public)]
+#[derive(Clone)]
+pub struct BuildingPiece {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+
+    pub piece_type: BuildingPieceType,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub tile_x: i32,
+    pub tile_y: i32,
+    #[index(btree)]
+    pub chunk_index: u32,
+
+    pub placed_by: Identity,
+    pub placed_at: Timestamp,
+    pub is_open: bool, // Only meaningful for doors
+
+    // --- Destruction Fields ---
+    pub health: f32,
+    pub max_health: f32,
+    pub is_destroyed: bool,
+    pub destroyed_at: Option<Timestamp>,
+    pub last_hit_time: Option<Timestamp>,
+}
+
+impl BuildingPiece {
+    /// Whether this piece currently blocks player movement.
+    pub fn is_solid(&self) -> bool {
+        if self.is_destroyed {
+            return false;
+        }
+        match self.piece_type {
+            BuildingPieceType::Wall => true,
+            BuildingPieceType::Door => !self.is_open,
+            BuildingPieceType::Foundation | BuildingPieceType::Doorway => false,
+        }
+    }
+
+    /// Collision radius used for solid pieces.
+    pub fn collision_radius(&self) -> f32 {
+        match self.piece_type {
+            BuildingPieceType::Door => DOOR_COLLISION_RADIUS,
+            _ => WALL_COLLISION_RADIUS,
+        }
+    }
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Place Building Piece ---
+/// Places a foundation, wall, doorway or door from the player's inventory.
+/// The piece snaps to the tile under (world_x, world_y). Foundations live on their own
+/// layer; walls and doorways share a layer; doors must be hung in an empty doorway.
+#[spacetimedb::reducer]
+pub fn place_building_piece(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let sender_id = ctx.sender;
+    let inventory_items = ctx.db.inventory_item();
+    let item_defs = ctx.db.item_definition();
+    let players = ctx.db.player();
+    let pieces = ctx.db.building_piece();
+
+    log::info!("[PlaceBuilding] Player {:?} attempting to place item {} at ({:.1}, {:.1})", sender_id, item_instance_id, world_x, world_y);
+
+    // 1. Validate Player
+    let player = players.identity().find(sender_id)
+        .ok_or_else(|| "Player not found.".to_string())?;
+
+    // 2. Validate Item
+    let mut item_to_place = get_player_item(ctx, item_instance_id)?;
+    let item_def = item_defs.id().find(item_to_place.item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found.", item_to_place.item_def_id))?;
+    let piece_type = BuildingPieceType::from_item_name(&item_def.name)
+        .ok_or_else(|| format!("'{}' is not a building piece.", item_def.name))?;
+
+    match &item_to_place.location {
+        ItemLocation::Inventory(_) | ItemLocation::Hotbar(_) => {},
+        _ => return Err("Building piece must be in inventory or hotbar to be placed.".to_string()),
+    }
+
+    // 3. Snap to tile and validate placement
+    let (tile_x, tile_y, snapped_x, snapped_y) = check_building_piece_placement(ctx, &player, piece_type, world_x, world_y)?;
+
+    // 4. Create the piece
+    let max_health = piece_type.max_health();
+    let new_piece = BuildingPiece {
+        id: 0, // Auto-incremented
+        piece_type,
+        pos_x: snapped_x,
+        pos_y: snapped_y,
+        tile_x,
+        tile_y,
+        chunk_index: calculate_chunk_index(snapped_x, snapped_y),
+        placed_by: sender_id,
+        placed_at: ctx.timestamp,
+        is_open: true, // Doors are hung open so the builder isn't shut in
+        health: max_health,
+        max_health,
+        is_destroyed: false,
+        destroyed_at: None,
+        last_hit_time: None,
+    };
+    let inserted = pieces.insert(new_piece);
+    crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::BuildingPiece(inserted.id), inserted.pos_x, inserted.pos_y);
+    log::info!("[PlaceBuilding] Player {:?} placed {:?} {} at tile ({}, {}).", sender_id, piece_type, inserted.id, tile_x, tile_y);
+
+    // 5. Consume the item
+    if item_to_place.quantity > 1 {
+        item_to_place.quantity -= 1;
+        inventory_items.instance_id().update(item_to_place);
+    } else {
+        inventory_items.instance_id().delete(item_instance_id);
+    }
+
+    Ok(())
+}
+
+/// Checks that `player` can put a piece of `piece_type` on the tile under (world_x, world_y):
+/// in reach, tile free for that layer, nobody standing in a new wall and outside other
+/// players' building privilege. Returns the tile and its centre. Used by
+/// `place_building_piece` and `placement::validate_placement`.
+pub(crate) fn check_building_piece_placement(
+    ctx: &ReducerContext,
+    player: &crate::Player,
+    piece_type: BuildingPieceType,
+    world_x: f32,
+    world_y: f32,
+) -> Result<(i32, i32, f32, f32), String> {
+    let players = ctx.db.player();
+    let pieces = ctx.db.building_piece();
+    if player.is_dead {
+        return Err("Cannot build while dead.".to_string());
+    }
+
+    // Snap to tile and validate distance
+    let (tile_x, tile_y) = world_pos_to_tile(world_x, world_y);
+    let (snapped_x, snapped_y) = tile_center(tile_x, tile_y);
+    if snapped_x < 0.0 || snapped_y < 0.0 || snapped_x > crate::WORLD_WIDTH_PX || snapped_y > crate::WORLD_HEIGHT_PX {
+        return Err("Cannot build outside the world.".to_string());
+    }
+    let dx = player.position_x - snapped_x;
+    let dy = player.position_y - snapped_y;
+    if (dx * dx + dy * dy) > BUILDING_PLACEMENT_RANGE_SQ {
+        return Err("Placement location is too far away.".to_string());
+    }
+
+    // Validate tile occupancy
+    let pieces_on_tile: Vec<BuildingPiece> = pieces.iter()
+        .filter(|p| !p.is_destroyed && p.tile_x == tile_x && p.tile_y == tile_y)
+        .collect();
+    match piece_type {
+        BuildingPieceType::Foundation => {
+            if pieces_on_tile.iter().any(|p| p.piece_type == BuildingPieceType::Foundation) {
+                return Err("There is already a foundation here.".to_string());
+            }
+        }
+        BuildingPieceType::Wall | BuildingPieceType::Doorway => {
+            if pieces_on_tile.iter().any(|p| p.piece_type != BuildingPieceType::Foundation) {
+                return Err("There is already a structure on this tile.".to_string());
+            }
+        }
+        BuildingPieceType::Door => {
+            if !pieces_on_tile.iter().any(|p| p.piece_type == BuildingPieceType::Doorway) {
+                return Err("Doors must be placed in a doorway.".to_string());
+            }
+            if pieces_on_tile.iter().any(|p| p.piece_type == BuildingPieceType::Door) {
+                return Err("This doorway already has a door.".to_string());
+            }
+        }
+    }
+
+    // Solid pieces cannot be placed on top of a player
+    if piece_type == BuildingPieceType::Wall {
+        let blocked = players.iter().any(|p| {
+            if p.is_dead { return false; }
+            let pdx = p.position_x - snapped_x;
+            let pdy = p.position_y - snapped_y;
+            (pdx * pdx + pdy * pdy) < PLAYER_WALL_COLLISION_DISTANCE_SQUARED
+        });
+        if blocked {
+            return Err("Something is in the way.".to_string());
+        }
+    }
+
+    crate::placement::check_building_privilege(ctx, player.identity, snapped_x, snapped_y)?;
+    crate::zone::check_build_allowed(ctx, snapped_x, snapped_y)?;
+    Ok((tile_x, tile_y, snapped_x, snapped_y))
+}
+
+/// --- Interact With Door ---
+/// Opens a closed door or closes an open one. Closing is refused while
+/// someone is standing in the doorway so players can't be trapped inside it.
+#[spacetimedb::reducer]
+pub fn interact_with_door(ctx: &ReducerContext, door_id: u32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let (_player, mut door) = validate_building_interaction(ctx, door_id)?;
+
+    if door.piece_type != BuildingPieceType::Door {
+        return Err(format!("Building piece {} is not a door.", door_id));
+    }
+    crate::locks::check_lock_access(ctx, crate::locks::LockTargetType::Door, door_id, ctx.sender)?;
+
+    if door.is_open {
+        let min_dist = crate::PLAYER_RADIUS + door.collision_radius();
+        let min_dist_sq = min_dist * min_dist;
+        let occupied = ctx.db.player().iter().any(|p| {
+            if p.is_dead { return false; }
+            let dx = p.position_x - door.pos_x;
+            let dy = p.position_y - (door.pos_y - BUILDING_COLLISION_Y_OFFSET);
+            (dx * dx + dy * dy) < min_dist_sq
+        });
+        if occupied {
+            return Err("Something is blocking the door.".to_string());
+        }
+    }
+
+    door.is_open = !door.is_open;
+    log::info!("Player {:?} {} door {}.", ctx.sender, if door.is_open { "opened" } else { "closed" }, door_id);
+    ctx.db.building_piece().id().update(door);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Converts a world position into tile coordinates.
+pub fn world_pos_to_tile(world_x: f32, world_y: f32) -> (i32, i32) {
+    let tile_size = TILE_SIZE_PX as f32;
+    ((world_x / tile_size).floor() as i32, (world_y / tile_size).floor() as i32)
+}
+
+/// Returns the world position of the center of a tile.
+pub fn tile_center(tile_x: i32, tile_y: i32) -> (f32, f32) {
+    let tile_size = TILE_SIZE_PX as f32;
+    ((tile_x as f32 + 0.5) * tile_size, (tile_y as f32 + 0.5) * tile_size)
+}
+
+/// --- Validate Building Interaction ---
+/// Checks that the piece exists, is intact, and the player is close enough.
+/// Does NOT check ownership.
+fn validate_building_interaction(
+    ctx: &ReducerContext,
+    piece_id: u32,
+) -> Result<(Player, BuildingPiece), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or_else(|| "Player not found".to_string())?;
+    let piece = ctx.db.building_piece().id().find(piece_id)
+        .ok_or_else(|| format!("Building piece {} not found", piece_id))?;
+
+    if piece.is_destroyed {
+        return Err(format!("Building piece {} is destroyed.", piece_id));
+    }
+
+    let dx = player.position_x - piece.pos_x;
+    let dy = player.position_y - piece.pos_y;
+    if (dx * dx + dy * dy) > BUILDING_INTERACTION_DISTANCE_SQUARED {
+        return Err("Too far away".to_string());
+    }
+    Ok((player, piece))
+}
@@ -8,9 +8,7 @@
  ******************************************************************************/
 
  use spacetimedb::{Identity, Timestamp, ReducerContext, Table, log, SpacetimeType, TimeDuration, ScheduleAt};
- use std::cmp::min;
  use std::time::Duration;   
- use rand::Rng; // Added for random chance
  
  // Import new models
  use crate::models::{ContainerType, ItemLocation, EquipmentSlotType, ContainerLocationData}; // Added ContainerLocationData
@@ -20,15 +18,14 @@
  use crate::player as PlayerTableTrait;
  use crate::Player;
  use crate::items::{
+     ItemDefinition,
      inventory_item as InventoryItemTableTrait,
      item_definition as ItemDefinitionTableTrait,
-     InventoryItem, ItemDefinition,
      calculate_merge_result, split_stack_helper, add_item_to_player_inventory
  };
  use crate::inventory_management::{self, ItemContainer, ContainerItemClearer, merge_or_place_into_container_slot};
  use crate::player_inventory::{move_item_to_inventory, move_item_to_hotbar, find_first_empty_player_slot, get_player_item};
  use crate::environment::calculate_chunk_index; // Assuming helper is here or in utils
- use crate::dropped_item::create_dropped_item_entity; // For dropping charcoal
  
  // --- ADDED: Import for active effects ---
  use crate::active_effects::{ActiveConsumableEffect, EffectType};
@@ -40,13 +37,14 @@
 pub(crate) const CAMPFIRE_COLLISION_Y_OFFSET: f32 = 0.0; // Changed from 25.0 to center on visual sprite
 pub(crate) const PLAYER_CAMPFIRE_COLLISION_DISTANCE_SQUARED: f32 = 
     (super::PLAYER_RADIUS + CAMPFIRE_COLLISION_RADIUS) * (super::PLAYER_RADIUS + CAMPFIRE_COLLISION_RADIUS);
-pub(crate) const CAMPFIRE_CAMPFIRE_COLLISION_DISTANCE_SQUARED: f32 = 
-    (CAMPFIRE_COLLISION_RADIUS * 2.0) * (CAMPFIRE_COLLISION_RADIUS * 2.0);
  
  // Interaction constants
  pub(crate) const PLAYER_CAMPFIRE_INTERACTION_DISTANCE: f32 = 96.0; // New radius: 96px
  pub(crate) const PLAYER_CAMPFIRE_INTERACTION_DISTANCE_SQUARED: f32 = 
     PLAYER_CAMPFIRE_INTERACTION_DISTANCE * PLAYER_CAMPFIRE_INTERACTION_DISTANCE; // 96.0 * 96.0
+ /// Interaction distance is measured to the campfire's visual center: CAMPFIRE_HEIGHT on the
+ /// client (64px) divided by 2 plus CAMPFIRE_RENDER_Y_OFFSET (10px), roughly 42px above its base.
+ pub(crate) const CAMPFIRE_VISUAL_CENTER_Y_OFFSET: f32 = 42.0;
  
  // Warmth and fuel constants
  pub(crate) const WARMTH_RADIUS: f32 = 300.0; // Doubled from 150.0
@@ -56,13 +54,12 @@ pub(crate) const CAMPFIRE_CAMPFIRE_COLLISION_DISTANCE_SQUARED: f32 =
  pub const NUM_FUEL_SLOTS: usize = 5;
  const FUEL_CHECK_INTERVAL_SECS: u64 = 1;
  pub const CAMPFIRE_PROCESS_INTERVAL_SECS: u64 = 1; // How often to run the main logic when burning
- const CHARCOAL_PRODUCTION_CHANCE: u8 = 75; // 75% chance
  
  // --- ADDED: Campfire Damage Constants ---
 const CAMPFIRE_DAMAGE_CENTER_Y_OFFSET: f32 = 0.0; // Changed from 30.0 to center with visual sprite
 const CAMPFIRE_DAMAGE_RADIUS: f32 = 50.0; // Increased damage radius
 const CAMPFIRE_DAMAGE_RADIUS_SQUARED: f32 = 2500.0; // 50.0 * 50.0
- const CAMPFIRE_DAMAGE_PER_TICK: f32 = 5.0; // How much damage is applied per tick
+ pub(crate) const CAMPFIRE_DAMAGE_PER_TICK: f32 = 5.0; // How much damage is applied per tick
  const CAMPFIRE_DAMAGE_EFFECT_DURATION_SECONDS: u64 = 1; // Duration of the damage effect (short, effectively one tick)
  const CAMPFIRE_DAMAGE_APPLICATION_COOLDOWN_SECONDS: u64 = 0; // MODIFIED: Apply damage every process tick if player is present
  
@@ -77,6 +74,7 @@ const CAMPFIRE_DAMAGE_RADIUS_SQUARED: f32 = 2500.0; // 50.0 * 50.0
      pub id: u32,
      pub pos_x: f32,
      pub pos_y: f32,
+     #[index(btree)]
      pub chunk_index: u32,
      pub placed_by: Identity, // Track who placed it
      pub placed_at: Timestamp,
@@ -182,6 +180,7 @@ const CAMPFIRE_DAMAGE_RADIUS_SQUARED: f32 = 2500.0; // 50.0 * 50.0
          .ok_or_else(|| format!("Failed to find definition for new item {}", new_item.item_def_id))?;
  
      merge_or_place_into_container_slot(ctx, &mut campfire, target_slot_index, &mut new_item, &new_item_def)?;
+     crate::cooking::record_cook_contributor(ctx, &campfire, target_slot_index, ctx.sender);
      
      // Update the source item (quantity changed by split_stack_helper)
      ctx.db.inventory_item().instance_id().update(source_item); 
@@ -411,6 +410,7 @@ const CAMPFIRE_DAMAGE_RADIUS_SQUARED: f32 = 2500.0; // 50.0 * 50.0
  /// Allows a player to interact with a campfire if they are close enough.
  #[spacetimedb::reducer]
  pub fn interact_with_campfire(ctx: &ReducerContext, campfire_id: u32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
      let (_player, _campfire) = validate_campfire_interaction(ctx, campfire_id)?;
      Ok(())
  }
@@ -420,7 +420,18 @@ const CAMPFIRE_DAMAGE_RADIUS_SQUARED: f32 = 2500.0; // 50.0 * 50.0
  /// Relies on checking if *any* fuel slot has Wood with quantity > 0.
  #[spacetimedb::reducer]
  pub fn toggle_campfire_burning(ctx: &ReducerContext, campfire_id: u32) -> Result<(), String> {
-     let (_player, mut campfire) = validate_campfire_interaction(ctx, campfire_id)?;
+     let (_player, campfire) = validate_campfire_interaction(ctx, campfire_id)?;
+     let burning = !campfire.is_burning;
+     set_campfire_burning(ctx, campfire, burning)
+ }
+
+ /// Lights or extinguishes a campfire without any interaction checks. Does nothing if it is
+ /// already in that state. Used by the toggle reducer and by wired switches (wiring.rs).
+ pub(crate) fn set_campfire_burning(ctx: &ReducerContext, mut campfire: Campfire, burning: bool) -> Result<(), String> {
+     if campfire.is_burning == burning {
+         return Ok(());
+     }
+     let campfire_id = campfire.id;
      if campfire.is_burning {
          campfire.is_burning = false;
          campfire.current_fuel_def_id = None;
@@ -439,6 +450,84 @@ const CAMPFIRE_DAMAGE_RADIUS_SQUARED: f32 = 2500.0; // 50.0 * 50.0
      Ok(())
  }
  
+ /// Creates an unlit campfire with INITIAL_CAMPFIRE_FUEL_AMOUNT Wood in its first fuel slot
+ /// and schedules its processing. The placement constructor for Camp Fire items.
+ pub(crate) fn spawn_campfire(ctx: &ReducerContext, placed_by: Identity, _item_def: &ItemDefinition, _item_durability: Option<f32>, world_x: f32, world_y: f32) -> Result<(), String> {
+     let campfires = ctx.db.campfire();
+     let wood_def_id = ctx.db.item_definition().iter()
+         .find(|def| def.name == "Wood")
+         .map(|def| def.id)
+         .ok_or_else(|| "Item definition for 'Wood' not found.".to_string())?;
+
+     let new_campfire = Campfire {
+         id: 0, // Auto-incremented
+         pos_x: world_x,
+         pos_y: world_y,
+         chunk_index: calculate_chunk_index(world_x, world_y),
+         placed_by,
+         placed_at: ctx.timestamp,
+         is_burning: false, // Campfires start unlit
+         // Initialize all fuel slots to None
+         fuel_instance_id_0: None,
+         fuel_def_id_0: None,
+         fuel_instance_id_1: None,
+         fuel_def_id_1: None,
+         fuel_instance_id_2: None,
+         fuel_def_id_2: None,
+         fuel_instance_id_3: None,
+         fuel_def_id_3: None,
+         fuel_instance_id_4: None,
+         fuel_def_id_4: None,
+         current_fuel_def_id: None, 
+         remaining_fuel_burn_time_secs: None,
+         health: 100.0, // Example initial health
+         max_health: 100.0, // Example max health
+         is_destroyed: false,
+         destroyed_at: None,
+         last_hit_time: None,
+         // Initialize cooking progress to None
+         slot_0_cooking_progress: None,
+         slot_1_cooking_progress: None,
+         slot_2_cooking_progress: None,
+         slot_3_cooking_progress: None,
+         slot_4_cooking_progress: None,
+         last_damage_application_time: None,
+         is_player_in_hot_zone: false, // Initialize new field
+     };
+     let inserted_campfire = campfires.try_insert(new_campfire)
+         .map_err(|e| format!("Failed to insert campfire entity: {}", e))?;
+     let new_campfire_id = inserted_campfire.id;
+     crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::Campfire(new_campfire_id), inserted_campfire.pos_x, inserted_campfire.pos_y);
+
+     // The starting fuel goes straight into slot 0; processing picks it up once lit
+     let inserted_fuel_item = ctx.db.inventory_item().try_insert(crate::items::InventoryItem {
+         instance_id: 0, // Auto-inc
+         item_def_id: wood_def_id,
+         quantity: crate::INITIAL_CAMPFIRE_FUEL_AMOUNT,
+         location: ItemLocation::Container(ContainerLocationData {
+             container_type: ContainerType::Campfire,
+             container_id: new_campfire_id as u64,
+             slot_index: 0,
+         }),
+         durability: None,
+         is_locked: false,
+         charges: None,
+     }).map_err(|e| format!("Failed to insert initial fuel item: {}", e))?;
+     let mut campfire_to_update = inserted_campfire;
+     campfire_to_update.fuel_instance_id_0 = Some(inserted_fuel_item.instance_id);
+     campfire_to_update.fuel_def_id_0 = Some(wood_def_id);
+     campfires.id().update(campfire_to_update);
+
+     log::info!("Player {:?} placed a campfire {} at ({:.1}, {:.1}) with initial fuel (Item {} in slot 0).",
+              placed_by, new_campfire_id, world_x, world_y, inserted_fuel_item.instance_id);
+
+     // Schedule initial processing for the new campfire
+     if let Err(e) = schedule_next_campfire_processing(ctx, new_campfire_id) {
+         log::error!("[PlaceCampfire] Failed to schedule initial processing for campfire {}: {}", new_campfire_id, e);
+     }
+     Ok(())
+ }
+
  /******************************************************************************
   *                           SCHEDULED REDUCERS                               *
   ******************************************************************************/
@@ -475,7 +564,6 @@ const CAMPFIRE_DAMAGE_RADIUS_SQUARED: f32 = 2500.0; // 50.0 * 50.0
      }
  
      let mut made_changes_to_campfire_struct = false;
-     let mut produced_charcoal_and_modified_campfire_struct = false; // For charcoal logic
  
      // Reset is_player_in_hot_zone at the beginning of each tick for this campfire
      if campfire.is_player_in_hot_zone { // Only change if it was true, to minimize DB writes if it's already false
@@ -511,18 +599,16 @@ const CAMPFIRE_DAMAGE_RADIUS_SQUARED: f32 = 2500.0; // 50.0 * 50.0
              let mut a_player_is_in_hot_zone_this_tick = false; // Track if any player is in the zone this tick
 
              for player_entity in ctx.db.player().iter() {
-                 if player_entity.is_dead { continue; } // Skip dead players
+                 // Skip dead players, players rolling through the fire and spawn-protected players
+                 if !crate::active_effects::can_take_environmental_damage(ctx, &player_entity, current_time) { continue; }
                  
                  // Check if player is in hot zone (for setting the flag, separate from damage application logic)
                  // UPDATED: Use the same visual center offset for damage calculations
                  // This ensures damage is applied based on the visual fire location the player sees
                  const VISUAL_CENTER_Y_OFFSET: f32 = 42.0;
                  
-                 let dx = player_entity.position_x - campfire.pos_x;
-                 let dy = player_entity.position_y - (campfire.pos_y - VISUAL_CENTER_Y_OFFSET);
-                 let dist_sq = dx * dx + dy * dy;
-
-                 if dist_sq < CAMPFIRE_DAMAGE_RADIUS_SQUARED {
+                 if crate::utils::is_in_damage_zone(player_entity.position_x, player_entity.position_y,
+                         campfire.pos_x, campfire.pos_y - VISUAL_CENTER_Y_OFFSET, CAMPFIRE_DAMAGE_RADIUS_SQUARED) {
                      a_player_is_in_hot_zone_this_tick = true; // A player is in the zone
 
                      // Proceed with damage effect application as before
@@ -534,7 +620,7 @@ const CAMPFIRE_DAMAGE_RADIUS_SQUARED: f32 = 2500.0; // 50.0 * 50.0
                          consuming_item_instance_id: None, // Added: Campfire damage doesn't consume an item instance
                          started_at: current_time,
                          ends_at: current_time + TimeDuration::from_micros(CAMPFIRE_DAMAGE_EFFECT_DURATION_SECONDS as i64 * 1_000_000),
-                         total_amount: Some(CAMPFIRE_DAMAGE_PER_TICK),
+                         total_amount: Some(crate::game_config::get_game_config(ctx).campfire_damage_per_tick),
                          amount_applied_so_far: Some(0.0),
                          effect_type: EffectType::Burn, // CHANGED from Damage to Burn
                          tick_interval_micros: CAMPFIRE_DAMAGE_EFFECT_DURATION_SECONDS * 1_000_000,
@@ -613,7 +699,7 @@ const CAMPFIRE_DAMAGE_RADIUS_SQUARED: f32 = 2500.0; // 50.0 * 50.0
                              if let Some(instance_id) = campfire.get_slot_instance_id(i) {
                                  if let Some(mut fuel_item) = inventory_items_table.instance_id().find(instance_id) {
                                      active_fuel_slot_idx_found = Some(i);
-                                     let consumed_item_def_id_for_charcoal = fuel_item.item_def_id;
+                                     let consumed_item_def_id = fuel_item.item_def_id;
                                      fuel_item.quantity -= 1;
  
                                      if fuel_item.quantity > 0 {
@@ -632,13 +718,9 @@ const CAMPFIRE_DAMAGE_RADIUS_SQUARED: f32 = 2500.0; // 50.0 * 50.0
                                      }
                                      made_changes_to_campfire_struct = true;
  
-                                     if let Some(consumed_def) = item_definition_table.id().find(consumed_item_def_id_for_charcoal) {
-                                         if consumed_def.name == "Wood" && ctx.rng().gen_range(0..100) < CHARCOAL_PRODUCTION_CHANCE {
-                                             if let Some(charcoal_def) = get_item_def_by_name(ctx, "Charcoal") {
-                                                 if try_add_charcoal_to_campfire_or_drop(ctx, &mut campfire, &charcoal_def, 1).unwrap_or(false) {
-                                                     produced_charcoal_and_modified_campfire_struct = true;
-                                                 }
-                                             }
+                                     if let Some(consumed_def) = item_definition_table.id().find(consumed_item_def_id) {
+                                         if crate::cooking::roll_by_products(ctx, &mut campfire, &consumed_def.name, crate::cooking::FUEL_BY_PRODUCTS) {
+                                             made_changes_to_campfire_struct = true;
                                          }
                                      }
                                      break; 
@@ -682,7 +764,7 @@ const CAMPFIRE_DAMAGE_RADIUS_SQUARED: f32 = 2500.0; // 50.0 * 50.0
          log::debug!("[CampfireProcess {}] Is NOT burning. Skipping damage and fuel/cooking.", campfire_id);
      }
  
-     if made_changes_to_campfire_struct || produced_charcoal_and_modified_campfire_struct {
+     if made_changes_to_campfire_struct {
          campfires_table.id().update(campfire); // Update the owned campfire variable
      }
  
@@ -917,12 +999,8 @@ const CAMPFIRE_DAMAGE_RADIUS_SQUARED: f32 = 2500.0; // 50.0 * 50.0
      // OPTIMIZED: Check distance between player and campfire's visual center
      // Since the visual campfire is rendered with its center offset from the base position,
      // we need to adjust the y-coordinate to match where the player sees the campfire
-     // Using CAMPFIRE_HEIGHT constant from client (64px) divided by 2 plus CAMPFIRE_RENDER_Y_OFFSET (10px)
-     // Total offset is roughly 32 + 10 = 42px upward from base position
-     const VISUAL_CENTER_Y_OFFSET: f32 = 42.0;
-     
      let dx = player.position_x - campfire.pos_x;
-     let dy = player.position_y - (campfire.pos_y - VISUAL_CENTER_Y_OFFSET);
+     let dy = player.position_y - (campfire.pos_y - CAMPFIRE_VISUAL_CENTER_Y_OFFSET);
      let dist_sq = dx * dx + dy * dy;
 
      if dist_sq > PLAYER_CAMPFIRE_INTERACTION_DISTANCE_SQUARED {
@@ -1060,84 +1138,6 @@ const CAMPFIRE_DAMAGE_RADIUS_SQUARED: f32 = 2500.0; // 50.0 * 50.0
      Ok(())
  }
  
- // --- Helper: Get Item Definition by Name ---
- fn get_item_def_by_name<'a>(ctx: &'a ReducerContext, name: &str) -> Option<ItemDefinition> {
-     ctx.db.item_definition().iter().find(|def| def.name == name)
- }
-
-// --- Helper: Try to add charcoal to campfire or drop it ---
-// Returns Ok(bool) where true means campfire struct was modified (charcoal added to slots)
-// and false means it was dropped or not produced.
-fn try_add_charcoal_to_campfire_or_drop(
-    ctx: &ReducerContext,
-    campfire: &mut Campfire,
-    charcoal_def: &ItemDefinition,
-    quantity: u32
-) -> Result<bool, String> {
-    let mut inventory_items_table = ctx.db.inventory_item(); // Changed to mut
-    let charcoal_def_id = charcoal_def.id;
-    let charcoal_stack_size = charcoal_def.stack_size;
-    let mut charcoal_added_to_campfire_slots = false;
-
-    // 1. Try to stack with existing charcoal in campfire slots
-    for i in 0..NUM_FUEL_SLOTS as u8 {
-        if campfire.get_slot_def_id(i) == Some(charcoal_def_id) {
-            if let Some(instance_id) = campfire.get_slot_instance_id(i) {
-                if let Some(mut existing_charcoal_item) = inventory_items_table.instance_id().find(instance_id) {
-                    if existing_charcoal_item.quantity < charcoal_stack_size {
-                        let can_add = charcoal_stack_size - existing_charcoal_item.quantity;
-                        let to_add = min(quantity, can_add); // quantity is usually 1 from charcoal production
-                        existing_charcoal_item.quantity += to_add;
-                        inventory_items_table.instance_id().update(existing_charcoal_item);
-                        log::info!("[Charcoal] Campfire {}: Stacked {} charcoal onto existing stack in slot {}.", campfire.id, to_add, i);
-                        // Campfire struct (slots) didn't change, only InventoryItem quantity
-                        // Return false because campfire struct itself was not modified for its slots.
-                        return Ok(false); 
-                    }
-                }
-            }
-        }
-    }
-
-    // 2. Try to place in an empty slot
-    for i in 0..NUM_FUEL_SLOTS as u8 {
-        if campfire.get_slot_instance_id(i).is_none() {
-            let new_charcoal_location = ItemLocation::Container(ContainerLocationData {
-                container_type: ContainerType::Campfire,
-                container_id: campfire.id as u64,
-                slot_index: i,
-            });
-            let new_charcoal_item = InventoryItem {
-                instance_id: 0, 
-                item_def_id: charcoal_def_id,
-                quantity, // This will be 1 from production
-                location: new_charcoal_location,
-            };
-            match inventory_items_table.try_insert(new_charcoal_item) {
-                Ok(inserted_item) => {
-                    campfire.set_slot(i, Some(inserted_item.instance_id), Some(charcoal_def_id));
-                    log::info!("[Charcoal] Campfire {}: Placed {} charcoal into empty slot {}.", campfire.id, quantity, i);
-                    charcoal_added_to_campfire_slots = true; // Campfire struct was modified
-                    return Ok(charcoal_added_to_campfire_slots);
-                }
-                Err(e) => {
-                    log::error!("[Charcoal] Campfire {}: Failed to insert new charcoal item for slot {}: {:?}", campfire.id, i, e);
-                    // Continue to drop if insert fails
-                    break; 
-                }
-            }
-        }
-    }
-
-    // 3. If not added to campfire (full or insert error), drop it
-    log::info!("[Charcoal] Campfire {}: Slots full or error encountered. Dropping {} charcoal.", campfire.id, quantity);
-    let drop_x = campfire.pos_x;
-    let drop_y = campfire.pos_y + crate::dropped_item::DROP_OFFSET / 2.0; 
-    create_dropped_item_entity(ctx, charcoal_def_id, quantity, drop_x, drop_y)?;
-    
-    Ok(charcoal_added_to_campfire_slots) // False, as it was dropped or failed to add to slots by modifying campfire struct
-}
-
 // --- CookableAppliance Trait Implementation for Campfire ---
 impl crate::cooking::CookableAppliance for Campfire {
     fn num_processing_slots(&self) -> usize {
@@ -0,0 +1,174 @@
+/******************************************************************************
+ *                                                                            *
+ * Channeled item use. Medical items aren't instant: `start_using_item`       *
+ * (or using an equipped bandage) starts a ChanneledUse row, and the item     *
+ * only takes effect once the channel has run its full duration. Moving,      *
+ * taking damage, switching the equipped item, dying or logging off cancels   *
+ * it and the item is kept. The global tick completes finished channels       *
+ * through the usual consumable path, which applies the item's effect via     *
+ * `active_effects`.                                                          *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, TimeDuration, log};
+
+use crate::items::{ItemDefinition, ConsumableCooldownCategory};
+use crate::items::item_definition as ItemDefinitionTableTrait;
+use crate::items::inventory_item as InventoryItemTableTrait;
+use crate::models::ItemLocation;
+use crate::player as PlayerTableTrait;
+use crate::channeled_use::channeled_use as ChanneledUseTableTrait;
+
+// --- Constants ---
+/// Channel time for medical items that don't specify their own.
+const DEFAULT_MEDICAL_CHANNEL_SECS: f32 = 3.0;
+
+/// --- Channeled Use ---
+/// An item a player is in the middle of using. Public so clients can show a progress bar.
+#[spacetimedb::table(name = channeled_use, public)]
+#[derive(Clone, Debug)]
+pub struct ChanneledUse {
+    #[primary_key]
+    pub player_id: Identity,
+    pub item_instance_id: u64,
+    pub item_def_id: u64,
+    pub started_at: Timestamp,
+    pub completes_at: Timestamp,
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Start Using Item ---
+/// Starts channeling a medical item from the player's inventory or hotbar.
+#[spacetimedb::reducer]
+pub fn start_using_item(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let item = ctx.db.inventory_item().instance_id().find(item_instance_id)
+        .ok_or_else(|| format!("Item instance {} not found.", item_instance_id))?;
+    let item_def = ctx.db.item_definition().id().find(item.item_def_id)
+        .ok_or_else(|| format!("Definition for item ID {} not found.", item.item_def_id))?;
+    begin_channel(ctx, sender_id, item_instance_id, &item_def)
+}
+
+/// --- Cancel Using Item ---
+/// Stops the player's current channel, keeping the item.
+#[spacetimedb::reducer]
+pub fn cancel_using_item(ctx: &ReducerContext) -> Result<(), String> {
+    cancel_channel(ctx, ctx.sender);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Whether using this item has to be channeled.
+pub(crate) fn is_channeled(item_def: &ItemDefinition) -> bool {
+    item_def.consumable_cooldown_category == Some(ConsumableCooldownCategory::Medical)
+}
+
+/// How long using the item takes. A bandage's duration is the time it takes to apply;
+/// for other medical items the duration is how long they heal for, so they use the default.
+fn channel_duration_secs(item_def: &ItemDefinition) -> f32 {
+    match item_def.consumable_duration_secs {
+        Some(duration_secs) if item_def.name == "Bandage" && duration_secs > 0.0 => duration_secs,
+        _ => DEFAULT_MEDICAL_CHANNEL_SECS,
+    }
+}
+
+/// Starts a channel for `item_instance_id`, which the player must be carrying.
+/// Errors if they're already channeling or the item's cooldown hasn't finished.
+pub(crate) fn begin_channel(ctx: &ReducerContext, player_id: Identity, item_instance_id: u64, item_def: &ItemDefinition) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, player_id)?;
+    let player = ctx.db.player().identity().find(player_id)
+        .ok_or_else(|| "Player not found.".to_string())?;
+    if player.is_dead {
+        return Err("Cannot use items while dead.".to_string());
+    }
+    if !is_channeled(item_def) {
+        return Err(format!("{} can't be used this way.", item_def.name));
+    }
+    if !is_carried_by(ctx, item_instance_id, player_id) {
+        return Err("Cannot use an item not in your inventory or hotbar.".to_string());
+    }
+    let channels = ctx.db.channeled_use();
+    if channels.player_id().find(player_id).is_some() {
+        return Err("You are already using an item.".to_string());
+    }
+    crate::consumables::check_consumable_cooldown(ctx, player_id, item_def, &player)?;
+
+    // Channeling needs the player standing still
+    crate::autorun::cancel_autorun(ctx, player_id);
+    let duration_secs = channel_duration_secs(item_def);
+    channels.insert(ChanneledUse {
+        player_id,
+        item_instance_id,
+        item_def_id: item_def.id,
+        started_at: ctx.timestamp,
+        completes_at: ctx.timestamp + TimeDuration::from_micros((duration_secs * 1_000_000.0) as i64),
+    });
+    log::info!("[ChanneledUse] Player {:?} started using {} (instance {}) for {:.1}s.", player_id, item_def.name, item_instance_id, duration_secs);
+    Ok(())
+}
+
+/// Cancels the player's channel, if any. Called on movement, damage and item switches.
+pub(crate) fn cancel_channel(ctx: &ReducerContext, player_id: Identity) {
+    if ctx.db.channeled_use().player_id().delete(player_id) {
+        log::info!("[ChanneledUse] Player {:?} was interrupted.", player_id);
+    }
+}
+
+/// Completes every channel that has run its full duration. Called every global tick.
+pub(crate) fn tick_channeled_uses(ctx: &ReducerContext) {
+    let channels = ctx.db.channeled_use();
+    let finished: Vec<ChanneledUse> = channels.iter().filter(|c| ctx.timestamp >= c.completes_at).collect();
+    for channel in finished {
+        channels.player_id().delete(channel.player_id);
+        if let Err(e) = complete_channel(ctx, &channel) {
+            log::warn!("[ChanneledUse] Player {:?} could not finish using item {}: {}", channel.player_id, channel.item_instance_id, e);
+        }
+    }
+
+    // Channels of players who died or left are dropped
+    let stale: Vec<Identity> = channels.iter()
+        .filter(|c| ctx.db.player().identity().find(c.player_id).is_none_or(|p| p.is_dead || !p.is_online))
+        .map(|c| c.player_id)
+        .collect();
+    for player_id in stale {
+        cancel_channel(ctx, player_id);
+    }
+}
+
+/// Applies the channeled item's effects and consumes it, as `consume_item` would have.
+fn complete_channel(ctx: &ReducerContext, channel: &ChanneledUse) -> Result<(), String> {
+    let players = ctx.db.player();
+    let mut player = players.identity().find(channel.player_id)
+        .ok_or_else(|| "Player not found.".to_string())?;
+    if player.is_dead || !player.is_online {
+        return Err("Player is no longer able to use items.".to_string());
+    }
+    if !is_carried_by(ctx, channel.item_instance_id, channel.player_id) {
+        return Err("Item is no longer in the player's inventory or hotbar.".to_string());
+    }
+    let item_def = ctx.db.item_definition().id().find(channel.item_def_id)
+        .ok_or_else(|| format!("Definition for item ID {} not found.", channel.item_def_id))?;
+
+    crate::consumables::apply_item_effects_and_consume(ctx, channel.player_id, &item_def, channel.item_instance_id, &mut player)?;
+    players.identity().update(player);
+    // Timed effects consume the item when they end; instant ones are consumed now
+    if item_def.consumable_duration_secs.is_none_or(|d| d <= 0.0) {
+        crate::consumables::consume_single_item(ctx, channel.item_instance_id, channel.player_id)?;
+    }
+    log::info!("[ChanneledUse] Player {:?} finished using {}.", channel.player_id, item_def.name);
+    Ok(())
+}
+
+fn is_carried_by(ctx: &ReducerContext, item_instance_id: u64, player_id: Identity) -> bool {
+    ctx.db.inventory_item().instance_id().find(item_instance_id).is_some_and(|item| match &item.location {
+        ItemLocation::Inventory(data) => data.owner_id == player_id,
+        ItemLocation::Hotbar(data) => data.owner_id == player_id,
+        _ => false,
+    })
+}
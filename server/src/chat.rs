@@ -1,9 +1,18 @@
 // server/src/chat.rs
 //
 // Module for managing chat functionality including messages and related
-// operations in the multiplayer game.
+// operations in the multiplayer game: global chat, team chat, whispers,
+// per-player mute lists and the server-side word filter. Players muted by
+// an admin (see moderation.rs) can't send anything.
+//
+// Global chat is kept for a limited time by a retention schedule. Instead of
+// subscribing to the whole message table, clients read the recent history
+// copied into their own snapshot rows on connect, page further back with
+// `fetch_chat_history`, and subscribe only to messages newer than that.
 
-use spacetimedb::{ReducerContext, Identity, Timestamp, Table};
+use spacetimedb::{client_visibility_filter, Filter, ReducerContext, Identity, Timestamp, Table, TimeDuration};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
 use log;
 // Import necessary table traits and structs
 use crate::PlayerKillCommandCooldown;
@@ -13,6 +22,25 @@ use crate::player_corpse; // To call create_player_corpse
 use crate::active_equipment; // To call clear_active_item_reducer
 use crate::PrivateMessage; // Struct for private messages
 use crate::private_message as PrivateMessageTableTrait; // Trait for private messages
+use crate::chat::chat_mute as ChatMuteTableTrait;
+use crate::chat::chat_filtered_word as ChatFilteredWordTableTrait;
+use crate::chat::chat_retention_schedule as ChatRetentionScheduleTableTrait;
+use crate::chat::chat_history_snapshot as ChatHistorySnapshotTableTrait;
+use crate::chat::chat_history_page as ChatHistoryPageTableTrait;
+use crate::admin;
+use crate::models::DeathCause;
+
+// --- Constants ---
+const MAX_MUTED_PLAYERS: usize = 100;
+const MAX_FILTERED_WORD_LENGTH: usize = 32;
+const CHAT_RETENTION_INTERVAL_SECS: u64 = 300;
+/// Global messages older than this are deleted by the retention schedule.
+const CHAT_RETENTION_SECS: u64 = 48 * 60 * 60;
+/// The retention schedule also trims global chat to at most this many messages.
+const MAX_STORED_MESSAGES: usize = 5000;
+/// Messages copied into a client's snapshot when they connect.
+const CHAT_SNAPSHOT_SIZE: usize = 100;
+const MAX_CHAT_HISTORY_PAGE: u32 = 100;
 
 // --- Table Definitions ---
 
@@ -27,11 +55,87 @@ pub struct Message {
     pub sent: Timestamp, // Timestamp for sorting
 }
 
+/// A player the muter no longer wants to hear from. Team messages and whispers from
+/// muted players are not delivered; clients hide their global messages.
+#[spacetimedb::table(name = chat_mute, public)]
+#[derive(Clone, Debug)]
+pub struct ChatMute {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub muter_id: Identity,
+    pub muted_id: Identity,
+}
+
+/// A word blocked by the server-side chat filter. Private so players can't read the list.
+#[spacetimedb::table(name = chat_filtered_word)]
+#[derive(Clone, Debug)]
+pub struct ChatFilteredWord {
+    #[primary_key]
+    pub word: String, // Lowercase
+    pub reject: bool, // true: reject the whole message, false: replace the word with asterisks
+}
+
+/// Runs `run_chat_retention` every CHAT_RETENTION_INTERVAL_SECS.
+#[spacetimedb::table(name = chat_retention_schedule, scheduled(run_chat_retention))]
+#[derive(Clone, Debug)]
+pub struct ChatRetentionSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/// A recent global message copied for a client when they connected. Each client only
+/// sees their own rows.
+#[spacetimedb::table(name = chat_history_snapshot, public)]
+#[derive(Clone, Debug)]
+pub struct ChatHistorySnapshot {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub client_id: Identity,
+    pub message: Message,
+}
+
+/// One message of the page a client last fetched with `fetch_chat_history`. Each client
+/// only sees their own rows.
+#[spacetimedb::table(name = chat_history_page, public)]
+#[derive(Clone, Debug)]
+pub struct ChatHistoryPage {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub client_id: Identity,
+    pub message: Message,
+}
+
+// --- Row-Level Security Filters ---
+
+#[client_visibility_filter]
+const CHAT_MUTE_OWN_ROWS: Filter = Filter::Sql(
+    "SELECT * FROM chat_mute WHERE muter_id = :sender"
+);
+
+#[client_visibility_filter]
+const CHAT_HISTORY_SNAPSHOT_OWN_ROWS: Filter = Filter::Sql(
+    "SELECT * FROM chat_history_snapshot WHERE client_id = :sender"
+);
+
+#[client_visibility_filter]
+const CHAT_HISTORY_PAGE_OWN_ROWS: Filter = Filter::Sql(
+    "SELECT * FROM chat_history_page WHERE client_id = :sender"
+);
+
 // --- Reducers ---
 
 /// Sends a chat message that will be visible to all players
 #[spacetimedb::reducer]
 pub fn send_message(ctx: &ReducerContext, text: String) -> Result<(), String> {
+    crate::moderation::ensure_not_sanctioned(ctx, ctx.sender)?;
     if text.is_empty() {
         return Err("Message cannot be empty.".to_string());
     }
@@ -82,9 +186,10 @@ pub fn send_message(ctx: &ReducerContext, text: String) -> Result<(), String> {
                     player.death_timestamp = Some(current_time);
                     player.last_update = current_time; // Update timestamp
                     players.identity().update(player.clone()); // Update player state
+                    crate::kill_feed::record_death(ctx, sender_id, DeathCause::Suicide, "");
 
                     // Create corpse
-                    if let Err(e) = player_corpse::create_player_corpse(ctx, sender_id, player.position_x, player.position_y, &player.username) {
+                    if let Err(e) = player_corpse::create_player_corpse(ctx, sender_id, player.position_x, player.position_y, &player.username, DeathCause::Suicide, None) {
                         log::error!("Failed to create corpse for player {:?} after {}: {}", sender_id, command, e);
                     }
 
@@ -110,6 +215,74 @@ pub fn send_message(ctx: &ReducerContext, text: String) -> Result<(), String> {
                     return Err(format!("Player not found for {} command.", command));
                 }
             }
+            "/t" | "/team" => {
+                let team_text = parts[1..].join(" ");
+                if team_text.is_empty() {
+                    return Err(format!("Usage: {} <message>", command));
+                }
+                crate::moderation::ensure_not_muted(ctx, sender_id)?;
+                let team_text = apply_word_filter(ctx, &team_text)?;
+                let team_id = crate::team::get_player_team_id(ctx, sender_id)
+                    .ok_or_else(|| "You are not in a team.".to_string())?;
+                let sender_name = ctx.db.player().identity().find(sender_id)
+                    .map(|p| p.username)
+                    .unwrap_or_else(|| "Unknown".to_string());
+
+                // Team messages are delivered as private messages to each member
+                for member_id in crate::team::get_team_member_ids(ctx, team_id) {
+                    if is_muted_by(ctx, member_id, sender_id) {
+                        continue;
+                    }
+                    ctx.db.private_message().insert(PrivateMessage {
+                        id: 0, // Auto-incremented
+                        recipient_identity: member_id,
+                        sender_display_name: format!("[Team] {}", sender_name),
+                        text: team_text.clone(),
+                        sent: current_time,
+                    });
+                }
+                log::info!("Player {:?} sent team message to team {}: {}", sender_id, team_id, team_text);
+                return Ok(());
+            }
+            "/w" | "/whisper" | "/msg" => {
+                let (Some(target_name), true) = (parts.get(1), parts.len() > 2) else {
+                    return Err(format!("Usage: {} <player> <message>", command));
+                };
+                crate::moderation::ensure_not_muted(ctx, sender_id)?;
+                let whisper_text = apply_word_filter(ctx, &parts[2..].join(" "))?;
+                let target = ctx.db.player().iter()
+                    .find(|p| p.username.eq_ignore_ascii_case(target_name))
+                    .ok_or_else(|| format!("No player named {}.", target_name))?;
+                if target.identity == sender_id {
+                    return Err("You can't whisper to yourself.".to_string());
+                }
+                if !target.is_online {
+                    return Err(format!("{} is not online.", target.username));
+                }
+                let sender_name = ctx.db.player().identity().find(sender_id)
+                    .map(|p| p.username)
+                    .unwrap_or_else(|| "Unknown".to_string());
+
+                // Whispers are delivered as private messages; the sender gets a copy for their own chat log
+                if !is_muted_by(ctx, target.identity, sender_id) {
+                    ctx.db.private_message().insert(PrivateMessage {
+                        id: 0, // Auto-incremented
+                        recipient_identity: target.identity,
+                        sender_display_name: format!("[Whisper] {}", sender_name),
+                        text: whisper_text.clone(),
+                        sent: current_time,
+                    });
+                }
+                ctx.db.private_message().insert(PrivateMessage {
+                    id: 0, // Auto-incremented
+                    recipient_identity: sender_id,
+                    sender_display_name: format!("[To {}]", target.username),
+                    text: whisper_text,
+                    sent: current_time,
+                });
+                log::info!("Player {:?} whispered to {:?}.", sender_id, target.identity);
+                return Ok(());
+            }
             "/players" => {
                 log::info!("[Command] Player {:?} used /players command.", sender_id);
                 let online_players_count = ctx.db.player().iter().filter(|p| p.is_online && !p.is_dead).count();
@@ -132,6 +305,8 @@ pub fn send_message(ctx: &ReducerContext, text: String) -> Result<(), String> {
     }
     // --- End Command Handling ---
 
+    crate::moderation::ensure_not_muted(ctx, sender_id)?;
+    let text = apply_word_filter(ctx, &text)?;
 
     let new_message = Message {
         id: 0, // Auto-incremented
@@ -148,8 +323,223 @@ pub fn send_message(ctx: &ReducerContext, text: String) -> Result<(), String> {
     Ok(())
 }
 
+/// Posts a server-authored message to global chat (e.g. event announcements).
+/// The sender is the module's own identity so clients can style it as a system message.
+pub(crate) fn send_system_message(ctx: &ReducerContext, text: String) {
+    log::info!("[Chat] System message: {}", text);
+    ctx.db.message().insert(Message {
+        id: 0, // Auto-incremented
+        sender: ctx.identity(),
+        text,
+        sent: ctx.timestamp,
+    });
+}
+
+/// Mutes another player for the sender.
+#[spacetimedb::reducer]
+pub fn mute_player(ctx: &ReducerContext, target_id: Identity) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    if target_id == sender_id {
+        return Err("You can't mute yourself.".to_string());
+    }
+    if ctx.db.player().identity().find(target_id).is_none() {
+        return Err("Player not found".to_string());
+    }
+    if is_muted_by(ctx, sender_id, target_id) {
+        return Ok(());
+    }
+    if ctx.db.chat_mute().muter_id().filter(sender_id).count() >= MAX_MUTED_PLAYERS {
+        return Err(format!("You can't mute more than {} players.", MAX_MUTED_PLAYERS));
+    }
+    ctx.db.chat_mute().insert(ChatMute {
+        id: 0, // Auto-incremented
+        muter_id: sender_id,
+        muted_id: target_id,
+    });
+    log::info!("Player {:?} muted {:?}.", sender_id, target_id);
+    Ok(())
+}
+
+/// Removes a player from the sender's mute list.
+#[spacetimedb::reducer]
+pub fn unmute_player(ctx: &ReducerContext, target_id: Identity) -> Result<(), String> {
+    let mutes = ctx.db.chat_mute();
+    let mute = mutes.muter_id().filter(ctx.sender)
+        .find(|m| m.muted_id == target_id)
+        .ok_or_else(|| "That player is not muted.".to_string())?;
+    mutes.id().delete(mute.id);
+    log::info!("Player {:?} unmuted {:?}.", ctx.sender, target_id);
+    Ok(())
+}
+
+/// Admin only: adds (or updates) a word in the chat filter. Rejecting words block the whole
+/// message; other words are replaced with asterisks.
+#[spacetimedb::reducer]
+pub fn add_filtered_word(ctx: &ReducerContext, word: String, reject: bool) -> Result<(), String> {
+    if !admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can change the chat filter.".to_string());
+    }
+    let word = word.trim().to_lowercase();
+    if word.is_empty() || word.chars().count() > MAX_FILTERED_WORD_LENGTH || word.contains(char::is_whitespace) {
+        return Err(format!("Filtered words must be a single word of at most {} characters.", MAX_FILTERED_WORD_LENGTH));
+    }
+    let words = ctx.db.chat_filtered_word();
+    let entry = ChatFilteredWord { word: word.clone(), reject };
+    if words.word().find(&word).is_some() {
+        words.word().update(entry);
+    } else {
+        words.insert(entry);
+    }
+    log::info!("Admin {:?} added '{}' to the chat filter (reject: {}).", ctx.sender, word, reject);
+    Ok(())
+}
+
+/// Admin only: removes a word from the chat filter.
+#[spacetimedb::reducer]
+pub fn remove_filtered_word(ctx: &ReducerContext, word: String) -> Result<(), String> {
+    if !admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can change the chat filter.".to_string());
+    }
+    let word = word.trim().to_lowercase();
+    if !ctx.db.chat_filtered_word().word().delete(&word) {
+        return Err(format!("'{}' is not in the chat filter.", word));
+    }
+    log::info!("Admin {:?} removed '{}' from the chat filter.", ctx.sender, word);
+    Ok(())
+}
+
+/// Loads up to `limit` global messages older than `before_id` into the sender's page
+/// rows, replacing the previous page. `before_id` 0 starts from the newest message.
+#[spacetimedb::reducer]
+pub fn fetch_chat_history(ctx: &ReducerContext, before_id: u64, limit: u32) -> Result<(), String> {
+    if limit == 0 || limit > MAX_CHAT_HISTORY_PAGE {
+        return Err(format!("Page size must be between 1 and {}.", MAX_CHAT_HISTORY_PAGE));
+    }
+    let client_id = ctx.sender;
+    let pages = ctx.db.chat_history_page();
+    let previous: Vec<u64> = pages.client_id().filter(client_id).map(|row| row.id).collect();
+    for row_id in previous {
+        pages.id().delete(row_id);
+    }
+
+    let before_id = if before_id == 0 { u64::MAX } else { before_id };
+    for message in newest_messages(ctx, before_id, limit as usize) {
+        pages.insert(ChatHistoryPage {
+            id: 0, // Auto-incremented
+            client_id,
+            message,
+        });
+    }
+    Ok(())
+}
+
+/// Starts the chat retention schedule if it isn't already scheduled. Called from `init_module`.
+pub(crate) fn init_chat_retention_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.chat_retention_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting chat retention schedule (every {}s).", CHAT_RETENTION_INTERVAL_SECS);
+        let interval = Duration::from_secs(CHAT_RETENTION_INTERVAL_SECS);
+        schedule_table.try_insert(ChatRetentionSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert chat retention schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: deletes global messages past CHAT_RETENTION_SECS, then the oldest
+/// messages beyond MAX_STORED_MESSAGES.
+#[spacetimedb::reducer]
+pub fn run_chat_retention(ctx: &ReducerContext, _schedule: ChatRetentionSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("run_chat_retention may only be called by the scheduler.".to_string());
+    }
+    let messages = ctx.db.message();
+    let cutoff = ctx.timestamp - TimeDuration::from(Duration::from_secs(CHAT_RETENTION_SECS));
+    let mut kept: Vec<(u64, Timestamp)> = messages.iter().map(|m| (m.id, m.sent)).collect();
+    kept.sort_by_key(|(id, _)| std::cmp::Reverse(*id));
+
+    let mut removed = 0;
+    for (index, (id, sent)) in kept.into_iter().enumerate() {
+        if sent < cutoff || index >= MAX_STORED_MESSAGES {
+            messages.id().delete(id);
+            removed += 1;
+        }
+    }
+    if removed > 0 {
+        log::info!("[Chat] Retention removed {} old messages.", removed);
+    }
+    Ok(())
+}
+
+/// Replaces the client's snapshot with the newest CHAT_SNAPSHOT_SIZE global messages.
+/// Called when a client connects.
+pub(crate) fn write_chat_snapshot(ctx: &ReducerContext, client_id: Identity) {
+    let snapshots = ctx.db.chat_history_snapshot();
+    let previous: Vec<u64> = snapshots.client_id().filter(client_id).map(|row| row.id).collect();
+    for row_id in previous {
+        snapshots.id().delete(row_id);
+    }
+    for message in newest_messages(ctx, u64::MAX, CHAT_SNAPSHOT_SIZE) {
+        snapshots.insert(ChatHistorySnapshot {
+            id: 0, // Auto-incremented
+            client_id,
+            message,
+        });
+    }
+}
+
+/// Removes a client's snapshot and history page. Called when a client disconnects.
+pub(crate) fn clear_chat_history(ctx: &ReducerContext, client_id: Identity) {
+    let snapshots = ctx.db.chat_history_snapshot();
+    let snapshot_ids: Vec<u64> = snapshots.client_id().filter(client_id).map(|row| row.id).collect();
+    for row_id in snapshot_ids {
+        snapshots.id().delete(row_id);
+    }
+    let pages = ctx.db.chat_history_page();
+    let page_ids: Vec<u64> = pages.client_id().filter(client_id).map(|row| row.id).collect();
+    for row_id in page_ids {
+        pages.id().delete(row_id);
+    }
+}
+
+/// Up to `limit` global messages with an id below `before_id`, newest first.
+fn newest_messages(ctx: &ReducerContext, before_id: u64, limit: usize) -> Vec<Message> {
+    let mut messages: Vec<Message> = ctx.db.message().iter()
+        .filter(|m| m.id < before_id)
+        .collect();
+    messages.sort_by_key(|m| std::cmp::Reverse(m.id));
+    messages.truncate(limit);
+    messages
+}
+
+/// Returns true if `muter_id` has muted `muted_id`.
+pub(crate) fn is_muted_by(ctx: &ReducerContext, muter_id: Identity, muted_id: Identity) -> bool {
+    ctx.db.chat_mute().muter_id().filter(muter_id).any(|m| m.muted_id == muted_id)
+}
+
+/// Runs the text through the word filter. Words are compared case-insensitively, ignoring
+/// surrounding punctuation. Returns the censored text, or an error if a rejecting word was found.
+pub(crate) fn apply_word_filter(ctx: &ReducerContext, text: &str) -> Result<String, String> {
+    let words = ctx.db.chat_filtered_word();
+    if words.count() == 0 {
+        return Ok(text.to_string());
+    }
+
+    let mut censored = Vec::new();
+    for token in text.split(' ') {
+        let normalized: String = token.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+        match words.word().find(&normalized) {
+            Some(entry) if entry.reject => {
+                return Err("Your message contains a blocked word.".to_string());
+            }
+            Some(_) => censored.push("*".repeat(token.chars().count())),
+            None => censored.push(token.to_string()),
+        }
+    }
+    Ok(censored.join(" "))
+}
+
 // Could add more chat-related functionality in the future:
-// - Private messages
-// - Chat filtering
 // - Chat commands/emotes
 // - Chat history management (pruning old messages)
\ No newline at end of file
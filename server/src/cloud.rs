@@ -98,21 +98,9 @@ pub fn update_cloud_positions(ctx: &ReducerContext, schedule_args: CloudUpdateSc
             log::info!("Cloud {} wrapped from bottom to top edge", cloud.id);
         }
 
-        // Recalculate chunk_index after position update and potential wrapping
-        // Note: This assumes world coordinates start at (0,0) in the top-left.
-        let chunk_x = (cloud.pos_x / environment::CHUNK_SIZE_PX).floor() as i32;
-        let chunk_y = (cloud.pos_y / environment::CHUNK_SIZE_PX).floor() as i32;
-        
-        // Handle potential edge cases where chunk coords might go negative after wrapping
-        let new_chunk_x = if chunk_x < 0 { 0 } else if chunk_x >= environment::WORLD_WIDTH_CHUNKS as i32 { environment::WORLD_WIDTH_CHUNKS - 1 } else { chunk_x as u32 };
-        let new_chunk_y = if chunk_y < 0 { 0 } else if chunk_y >= environment::WORLD_WIDTH_CHUNKS as i32 { environment::WORLD_WIDTH_CHUNKS - 1 } else { chunk_y as u32 };
-        
-        let new_chunk_index = new_chunk_x + new_chunk_y * environment::WORLD_WIDTH_CHUNKS;
-
-        if cloud.chunk_index != new_chunk_index {
-            cloud.chunk_index = new_chunk_index;
-        }
-        
+        // Wrapping can carry the cloud into another chunk
+        environment::sync_chunk_index(&mut cloud.chunk_index, cloud.pos_x, cloud.pos_y);
+
         // Update the cloud entity in the database
         ctx.db.cloud().id().update(cloud);
     }
@@ -146,6 +146,12 @@ where
     Ok(())
 }
 
+/// Scales a respawn time by a plant growth multiplier from the game config
+/// (2.0 regrows in half the time). Tiny multipliers are clamped so a zero can't stall regrowth forever.
+pub fn scale_respawn_secs(secs: u64, growth_multiplier: f32) -> u64 {
+    (secs as f32 / growth_multiplier.max(0.1)).round() as u64
+}
+
 /// Common trait for resource tables that can respawn
 ///
 /// Implemented by specific resource types like Mushroom, Corn, etc.
@@ -13,7 +13,7 @@ use std::time::Duration;
 use rand::{Rng, SeedableRng};
 
 // SpacetimeDB imports
-use spacetimedb::{Identity, ReducerContext, Table, Timestamp, TimeDuration};
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
 use log;
 
 // Core game types
@@ -21,14 +21,14 @@ use crate::Player;
 use crate::PLAYER_RADIUS;
 use crate::{WORLD_WIDTH_PX, WORLD_HEIGHT_PX};
 use crate::items::{ItemDefinition, ItemCategory};
-use crate::models::TargetType;
+use crate::models::{DamageType, DeathCause, ResourceRichness, TargetType};
 use crate::tree;
 use crate::stone;
 use crate::wooden_storage_box;
 
 // Specific constants needed
-use crate::tree::{MIN_TREE_RESPAWN_TIME_SECS, MAX_TREE_RESPAWN_TIME_SECS, TREE_COLLISION_Y_OFFSET, PLAYER_TREE_COLLISION_DISTANCE_SQUARED};
-use crate::stone::{MIN_STONE_RESPAWN_TIME_SECS, MAX_STONE_RESPAWN_TIME_SECS, STONE_COLLISION_Y_OFFSET, PLAYER_STONE_COLLISION_DISTANCE_SQUARED};
+use crate::tree::{MIN_TREE_RESPAWN_TIME_SECS, MAX_TREE_RESPAWN_TIME_SECS, TREE_TRUNK_RADIUS, TREE_COLLISION_Y_OFFSET, PLAYER_TREE_COLLISION_DISTANCE_SQUARED};
+use crate::stone::{MIN_STONE_RESPAWN_TIME_SECS, MAX_STONE_RESPAWN_TIME_SECS, STONE_RADIUS, STONE_COLLISION_Y_OFFSET, PLAYER_STONE_COLLISION_DISTANCE_SQUARED};
 use crate::wooden_storage_box::{WoodenStorageBox, BOX_COLLISION_RADIUS, BOX_COLLISION_Y_OFFSET, wooden_storage_box as WoodenStorageBoxTableTrait};
 
 // Table trait imports for database access
@@ -39,29 +39,59 @@ use crate::items::inventory_item as InventoryItemTableTrait;
 use crate::player as PlayerTableTrait;
 use crate::active_equipment::active_equipment as ActiveEquipmentTableTrait;
 use crate::dropped_item;
-use crate::player_corpse::{PlayerCorpse, PlayerCorpseDespawnSchedule, NUM_CORPSE_SLOTS, create_player_corpse};
+use crate::player_corpse::{self, CorpseStage, CORPSE_COLLISION_Y_OFFSET, CORPSE_BONE_YIELD_MIN, CORPSE_BONE_YIELD_MAX, create_player_corpse};
 use crate::player_corpse::player_corpse as PlayerCorpseTableTrait;
 use crate::player_corpse::player_corpse_despawn_schedule as PlayerCorpseDespawnScheduleTableTrait;
 use crate::inventory_management::ItemContainer;
 use crate::environment::calculate_chunk_index;
 use crate::campfire::{Campfire, CAMPFIRE_COLLISION_RADIUS, CAMPFIRE_COLLISION_Y_OFFSET, campfire as CampfireTableTrait, campfire_processing_schedule as CampfireProcessingScheduleTableTrait};
+use crate::furnace::{Furnace, FURNACE_COLLISION_RADIUS, FURNACE_COLLISION_Y_OFFSET, furnace as FurnaceTableTrait};
+use crate::large_storage_chest::{LARGE_CHEST_COLLISION_Y_OFFSET, large_storage_chest as LargeStorageChestTableTrait};
 use crate::stash::{Stash, stash as StashTableTrait};
 use crate::sleeping_bag::{SleepingBag, SLEEPING_BAG_COLLISION_RADIUS, SLEEPING_BAG_COLLISION_Y_OFFSET, sleeping_bag as SleepingBagTableTrait};
-use crate::active_effects::{self, ActiveConsumableEffect, EffectType, active_consumable_effect as ActiveConsumableEffectTableTrait};
+use crate::building::{BUILDING_COLLISION_Y_OFFSET, building_piece as BuildingPieceTableTrait};
+use crate::sleeping_player::sleeping_player as SleepingPlayerTableTrait;
+use crate::animals::{self, AnimalSpecies, wild_animal as WildAnimalTableTrait};
+use crate::workbench::{Workbench, WORKBENCH_COLLISION_Y_OFFSET, workbench as WorkbenchTableTrait};
+use crate::sign::{Sign, SIGN_COLLISION_Y_OFFSET, sign as SignTableTrait};
+use crate::wiring::{WiringSwitch, SWITCH_COLLISION_Y_OFFSET, wiring_switch as WiringSwitchTableTrait};
+use crate::tree_felling::tree_stump as TreeStumpTableTrait;
+use crate::environment_hazard::environment_hazard as EnvironmentHazardTableTrait;
+use crate::mount::{Mount, mount as MountTableTrait};
+use crate::recycler::{Recycler, RECYCLER_COLLISION_Y_OFFSET, recycler as RecyclerTableTrait};
+use crate::flame_turret::{FlameTurret, FLAME_TURRET_COLLISION_Y_OFFSET, flame_turret as FlameTurretTableTrait};
+use crate::loot_crate::{self, LootCrateType, LOOT_CRATE_COLLISION_Y_OFFSET, MIN_LOOT_CRATE_RESPAWN_TIME_SECS, MAX_LOOT_CRATE_RESPAWN_TIME_SECS, loot_crate as LootCrateTableTrait};
+use crate::active_effects;
 use crate::consumables::MAX_STAT_VALUE;
 // Import the armor module
 use crate::armor;
+use crate::skills::{self, SkillType};
+use crate::quests::QuestObjective;
+use crate::server_modifiers;
+use crate::spatial_grid;
 // Player inventory imports (commented out previously, keeping them commented if unresolved)
 // use crate::player_inventory::{drop_all_inventory_on_death, drop_all_equipped_armor_on_death};
 // Import the player stats module
 use crate::player_stats;
 // Import the utils module
-use crate::utils::get_distance_squared;
+use crate::utils::get_distance_squared_to_segment;
 // --- Game Balance Constants ---
 /// Time in milliseconds before a dead player can respawn
 pub const RESPAWN_TIME_MS: u64 = 5000; // 5 seconds
 /// Distance player is knocked back in PvP
 pub const PVP_KNOCKBACK_DISTANCE: f32 = 32.0;
+/// How long a knocked-back player stays staggered, and how much it slows them
+const STAGGER_DURATION_SECS: f32 = 1.0;
+const STAGGER_SLOW_PERCENT: f32 = 0.4;
+/// Extra lookup radius for cone targeting, covering collision-center Y offsets.
+const CONE_LOOKUP_MARGIN: f32 = 64.0;
+/// Durability a tool/weapon loses each time it is swung
+pub const DURABILITY_LOSS_PER_SWING: f32 = 1.0;
+/// Percent chance that a stone hit also yields Metal Ore
+pub const METAL_ORE_CHANCE_PER_STONE_HIT: u32 = 35;
+/// Damage and yield of a heavy attack (see active_equipment.rs `heavy_attack`) relative to a normal swing
+const HEAVY_ATTACK_DAMAGE_MULTIPLIER: f32 = 1.8;
+const HEAVY_ATTACK_YIELD_MULTIPLIER: f32 = 1.5;
 
 // --- Combat System Types ---
 
@@ -75,6 +105,28 @@ pub enum TargetId {
     WoodenStorageBox(u32),
     Stash(u32),
     SleepingBag(u32),
+    BuildingPiece(u32),
+    Furnace(u32),
+    Animal(u64),
+    LargeStorageChest(u32),
+    SleepingPlayer(u32),
+    LootCrate(u64),
+    Workbench(u32),
+    Recycler(u32),
+    FlameTurret(u32),
+    PlayerCorpse(u32),
+    Sign(u32),
+    TreeStump(u64),
+    EnvironmentHazard(u64),
+    Mount(u64),
+    Switch(u32),
+}
+
+/// How a melee swing was made
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Swing {
+    pub hit_offset: Option<(f32, f32)>, // Where on a tree or stone the client says the hit landed (see sweet_spot.rs)
+    pub heavy: bool, // Heavy attacks deal and yield more
 }
 
 /// Represents a potential target within attack range
@@ -113,7 +165,8 @@ pub fn get_player_forward_vector(direction: &str) -> (f32, f32) {
 /// Finds all potential targets within an attack cone
 ///
 /// Searches for trees, stones, and other players within range of the attacker
-/// and within the specified angle cone in front of the player.
+/// and within the specified angle cone in front of the player. Targets hidden
+/// behind a tree, stone or wall (see `has_line_of_sight`) are left out.
 /// Returns a vector of targets sorted by distance (closest first).
 pub fn find_targets_in_cone(
     ctx: &ReducerContext, 
@@ -127,9 +180,19 @@ pub fn find_targets_in_cone(
     
     // Get player's forward vector
     let (forward_x, forward_y) = get_player_forward_vector(&player.direction);
+
+    // Only entities in spatial grid cells the swing can reach are considered
+    let nearby = spatial_grid::get_entities_in_radius(ctx, player.position_x, player.position_y, attack_range + CONE_LOOKUP_MARGIN);
+    // Whether the target at offset (dx, dy) from the attacker is not hidden behind an obstacle
+    let in_sight = |dx: f32, dy: f32, ignore: Option<spatial_grid::EntityType>| {
+        has_line_of_sight(ctx, player.position_x, player.position_y, player.position_x + dx, player.position_y + dy, ignore)
+    };
     
     // Check trees
-    for tree in ctx.db.tree().iter() {
+    for tree in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::Tree(id) => ctx.db.tree().id().find(id),
+        _ => None,
+    }) {
         let dx = tree.pos_x - player.position_x;
         let target_y = tree.pos_y - TREE_COLLISION_Y_OFFSET;
         let dy = target_y - player.position_y;
@@ -144,7 +207,7 @@ pub fn find_targets_in_cone(
             let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
             let angle_rad = dot_product.acos();
 
-            if angle_rad <= half_attack_angle_rad {
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, Some(spatial_grid::EntityType::Tree(tree.id))) {
                 targets.push(Target {
                     target_type: TargetType::Tree,
                     id: TargetId::Tree(tree.id),
@@ -153,9 +216,39 @@ pub fn find_targets_in_cone(
             }
         }
     }
+
+    // Check tree stumps
+    for stump in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::TreeStump(id) => ctx.db.tree_stump().id().find(id),
+        _ => None,
+    }) {
+        let dx = stump.pos_x - player.position_x;
+        let dy = stump.pos_y - player.position_y;
+        let dist_sq = dx * dx + dy * dy;
+
+        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
+            let distance = dist_sq.sqrt();
+            let target_vec_x = dx / distance;
+            let target_vec_y = dy / distance;
+
+            let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
+            let angle_rad = dot_product.acos();
+
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
+                targets.push(Target {
+                    target_type: TargetType::TreeStump,
+                    id: TargetId::TreeStump(stump.id),
+                    distance_sq: dist_sq,
+                });
+            }
+        }
+    }
     
     // Check stones
-    for stone in ctx.db.stone().iter() {
+    for stone in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::Stone(id) => ctx.db.stone().id().find(id),
+        _ => None,
+    }) {
         let dx = stone.pos_x - player.position_x;
         let target_y = stone.pos_y - STONE_COLLISION_Y_OFFSET;
         let dy = target_y - player.position_y;
@@ -169,7 +262,7 @@ pub fn find_targets_in_cone(
             let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
             let angle_rad = dot_product.acos();
 
-            if angle_rad <= half_attack_angle_rad {
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, Some(spatial_grid::EntityType::Stone(stone.id))) {
                 targets.push(Target {
                     target_type: TargetType::Stone,
                     id: TargetId::Stone(stone.id),
@@ -179,9 +272,42 @@ pub fn find_targets_in_cone(
         }
     }
     
-    // Check other players
-    for other_player in ctx.db.player().iter() {
-        if other_player.identity == player.identity || other_player.is_dead {
+    // Check environmental hazards (used-up ones wait to regrow)
+    for hazard in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::EnvironmentHazard(id) => ctx.db.environment_hazard().id().find(id),
+        _ => None,
+    }) {
+        if hazard.health == 0 {
+            continue;
+        }
+        let dx = hazard.pos_x - player.position_x;
+        let dy = hazard.pos_y - player.position_y;
+        let dist_sq = dx * dx + dy * dy;
+
+        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
+            let distance = dist_sq.sqrt();
+            let target_vec_x = dx / distance;
+            let target_vec_y = dy / distance;
+
+            let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
+            let angle_rad = dot_product.acos();
+
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
+                targets.push(Target {
+                    target_type: TargetType::EnvironmentHazard,
+                    id: TargetId::EnvironmentHazard(hazard.id),
+                    distance_sq: dist_sq,
+                });
+            }
+        }
+    }
+
+    // Check other players (offline players are targeted through their sleeping body instead)
+    for other_player in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::Player(id) => ctx.db.player().identity().find(id),
+        _ => None,
+    }) {
+        if other_player.identity == player.identity || other_player.is_dead || !other_player.is_online {
             continue;
         }
         
@@ -197,7 +323,7 @@ pub fn find_targets_in_cone(
             let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
             let angle_rad = dot_product.acos();
 
-            if angle_rad <= half_attack_angle_rad {
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
                 targets.push(Target {
                     target_type: TargetType::Player,
                     id: TargetId::Player(other_player.identity),
@@ -207,8 +333,35 @@ pub fn find_targets_in_cone(
         }
     }
     
+    // Check sleeping players
+    for sleeper in ctx.db.sleeping_player().iter() {
+        let dx = sleeper.pos_x - player.position_x;
+        let dy = sleeper.pos_y - player.position_y;
+        let dist_sq = dx * dx + dy * dy;
+
+        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
+            let distance = dist_sq.sqrt();
+            let target_vec_x = dx / distance;
+            let target_vec_y = dy / distance;
+
+            let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
+            let angle_rad = dot_product.acos();
+
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
+                targets.push(Target {
+                    target_type: TargetType::SleepingPlayer,
+                    id: TargetId::SleepingPlayer(sleeper.id),
+                    distance_sq: dist_sq,
+                });
+            }
+        }
+    }
+
     // Check campfires
-    for campfire_entity in ctx.db.campfire().iter() {
+    for campfire_entity in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::Campfire(id) => ctx.db.campfire().id().find(id),
+        _ => None,
+    }) {
         if campfire_entity.is_destroyed {
             continue;
         }
@@ -228,7 +381,7 @@ pub fn find_targets_in_cone(
             let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
             let angle_rad = dot_product.acos();
 
-            if angle_rad <= half_attack_angle_rad {
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
                 targets.push(Target {
                     target_type: TargetType::Campfire,
                     id: TargetId::Campfire(campfire_entity.id),
@@ -238,13 +391,16 @@ pub fn find_targets_in_cone(
         }
     }
 
-    // Check wooden storage boxes
-    for box_entity in ctx.db.wooden_storage_box().iter() {
-        if box_entity.is_destroyed {
+    // Check furnaces
+    for furnace_entity in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::Furnace(id) => ctx.db.furnace().id().find(id),
+        _ => None,
+    }) {
+        if furnace_entity.is_destroyed {
             continue;
         }
-        let dx = box_entity.pos_x - player.position_x;
-        let target_y = box_entity.pos_y - BOX_COLLISION_Y_OFFSET;
+        let dx = furnace_entity.pos_x - player.position_x;
+        let target_y = furnace_entity.pos_y - FURNACE_COLLISION_Y_OFFSET;
         let dy = target_y - player.position_y;
         let dist_sq = dx * dx + dy * dy;
 
@@ -256,24 +412,27 @@ pub fn find_targets_in_cone(
             let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
             let angle_rad = dot_product.acos();
 
-            if angle_rad <= half_attack_angle_rad {
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
                 targets.push(Target {
-                    target_type: TargetType::WoodenStorageBox,
-                    id: TargetId::WoodenStorageBox(box_entity.id),
+                    target_type: TargetType::Furnace,
+                    id: TargetId::Furnace(furnace_entity.id),
                     distance_sq: dist_sq,
                 });
             }
         }
     }
 
-    // Check stashes
-    for stash_entity in ctx.db.stash().iter() {
-        if stash_entity.is_destroyed || stash_entity.is_hidden {
-            continue; // Skip destroyed or hidden stashes
+    // Check workbenches
+    for workbench in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::Workbench(id) => ctx.db.workbench().id().find(id),
+        _ => None,
+    }) {
+        if workbench.is_destroyed {
+            continue;
         }
-        // Treat stash as a point target for now, or use a very small radius if needed for cone
-        let dx = stash_entity.pos_x - player.position_x;
-        let dy = stash_entity.pos_y - player.position_y; // No Y-offset for point target
+        let dx = workbench.pos_x - player.position_x;
+        let target_y = workbench.pos_y - WORKBENCH_COLLISION_Y_OFFSET;
+        let dy = target_y - player.position_y;
         let dist_sq = dx * dx + dy * dy;
 
         if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
@@ -284,23 +443,26 @@ pub fn find_targets_in_cone(
             let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
             let angle_rad = dot_product.acos();
 
-            if angle_rad <= half_attack_angle_rad {
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
                 targets.push(Target {
-                    target_type: TargetType::Stash,
-                    id: TargetId::Stash(stash_entity.id),
+                    target_type: TargetType::Workbench,
+                    id: TargetId::Workbench(workbench.id),
                     distance_sq: dist_sq,
                 });
             }
         }
     }
 
-    // Check sleeping bags
-    for bag_entity in ctx.db.sleeping_bag().iter() {
-        if bag_entity.is_destroyed {
+    // Check signs
+    for sign in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::Sign(id) => ctx.db.sign().id().find(id),
+        _ => None,
+    }) {
+        if sign.is_destroyed {
             continue;
         }
-        let dx = bag_entity.pos_x - player.position_x;
-        let target_y = bag_entity.pos_y - SLEEPING_BAG_COLLISION_Y_OFFSET;
+        let dx = sign.pos_x - player.position_x;
+        let target_y = sign.pos_y - SIGN_COLLISION_Y_OFFSET;
         let dy = target_y - player.position_y;
         let dist_sq = dx * dx + dy * dy;
 
@@ -312,594 +474,1707 @@ pub fn find_targets_in_cone(
             let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
             let angle_rad = dot_product.acos();
 
-            if angle_rad <= half_attack_angle_rad {
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
                 targets.push(Target {
-                    target_type: TargetType::SleepingBag,
-                    id: TargetId::SleepingBag(bag_entity.id),
+                    target_type: TargetType::Sign,
+                    id: TargetId::Sign(sign.id),
                     distance_sq: dist_sq,
                 });
             }
         }
     }
-    
-    // Sort by distance (closest first)
-    targets.sort_by(|a, b| a.distance_sq.partial_cmp(&b.distance_sq).unwrap());
-    
-    targets
-}
 
-/// Determines the best target based on weapon type and available targets
-///
-/// Different weapons have different priorities (e.g., pickaxes prioritize stones).
-/// This function selects the appropriate target based on the weapon and available targets.
-pub fn find_best_target(targets: &[Target], item_def: &ItemDefinition) -> Option<Target> {
-    if targets.is_empty() {
-        return None;
-    }
-    
-    // 1. Check for primary target type
-    if let Some(primary_type) = item_def.primary_target_type {
-        if let Some(target) = targets.iter().find(|t| t.target_type == primary_type) {
-            return Some(target.clone());
+    // Check switches
+    for switch in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::Switch(id) => ctx.db.wiring_switch().id().find(id),
+        _ => None,
+    }) {
+        if switch.is_destroyed {
+            continue;
         }
-    }
+        let dx = switch.pos_x - player.position_x;
+        let target_y = switch.pos_y - SWITCH_COLLISION_Y_OFFSET;
+        let dy = target_y - player.position_y;
+        let dist_sq = dx * dx + dy * dy;
 
-    // 2. Check for secondary target type
-    if let Some(secondary_type) = item_def.secondary_target_type {
-        if let Some(target) = targets.iter().find(|t| t.target_type == secondary_type) {
-            return Some(target.clone());
+        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
+            let distance = dist_sq.sqrt();
+            let target_vec_x = dx / distance;
+            let target_vec_y = dy / distance;
+
+            let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
+            let angle_rad = dot_product.acos();
+
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
+                targets.push(Target {
+                    target_type: TargetType::Switch,
+                    id: TargetId::Switch(switch.id),
+                    distance_sq: dist_sq,
+                });
+            }
         }
     }
 
-    // 3. If tool has PvP damage, check for Player targets if no resource target was found
-    if item_def.pvp_damage_min.is_some() || item_def.pvp_damage_max.is_some() { // Check if any PvP damage is defined
-        if let Some(player_target) = targets.iter().find(|t| t.target_type == TargetType::Player) {
-            // Only return player if primary/secondary types weren't found or aren't defined
-            if item_def.primary_target_type.is_none() && item_def.secondary_target_type.is_none() {
-                return Some(player_target.clone());
-            } else if item_def.primary_target_type.is_some() && targets.iter().find(|t| t.target_type == item_def.primary_target_type.unwrap()).is_none() &&
-                      item_def.secondary_target_type.is_some() && targets.iter().find(|t| t.target_type == item_def.secondary_target_type.unwrap()).is_none() {
-                 return Some(player_target.clone()); // Primary & secondary not found
-            } else if item_def.primary_target_type.is_some() && targets.iter().find(|t| t.target_type == item_def.primary_target_type.unwrap()).is_none() && item_def.secondary_target_type.is_none(){
-                return Some(player_target.clone()); // Primary not found, no secondary defined
-            } else if item_def.secondary_target_type.is_some() && targets.iter().find(|t| t.target_type == item_def.secondary_target_type.unwrap()).is_none() && item_def.primary_target_type.is_none(){
-                 return Some(player_target.clone()); // Secondary not found, no primary defined
+    // Check mounts, except the one the attacker is riding
+    for mount in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::Mount(id) => ctx.db.mount().id().find(id),
+        _ => None,
+    }) {
+        if mount.rider_id == Some(player.identity) {
+            continue;
+        }
+        let dx = mount.pos_x - player.position_x;
+        let dy = mount.pos_y - player.position_y;
+        let dist_sq = dx * dx + dy * dy;
+
+        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
+            let distance = dist_sq.sqrt();
+            let target_vec_x = dx / distance;
+            let target_vec_y = dy / distance;
+
+            let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
+            let angle_rad = dot_product.acos();
+
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
+                targets.push(Target {
+                    target_type: TargetType::Mount,
+                    id: TargetId::Mount(mount.id),
+                    distance_sq: dist_sq,
+                });
             }
         }
     }
 
-    // 4. If no specific preferred target found, return the closest target of any type.
-    // This allows hitting unintended targets, and calculate_damage_and_yield will determine effect (possibly zero).
-    return targets.first().cloned();
-}
-
-// --- Resource & Damage Functions ---
+    // Check recyclers
+    for recycler in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::Recycler(id) => ctx.db.recycler().id().find(id),
+        _ => None,
+    }) {
+        if recycler.is_destroyed {
+            continue;
+        }
+        let dx = recycler.pos_x - player.position_x;
+        let target_y = recycler.pos_y - RECYCLER_COLLISION_Y_OFFSET;
+        let dy = target_y - player.position_y;
+        let dist_sq = dx * dx + dy * dy;
 
-/// Grants resource items to a player based on what they hit
-///
-/// Looks up the proper resource definition and adds it to the player's inventory.
-pub fn grant_resource(
-    ctx: &ReducerContext, 
-    player_id: Identity, 
-    resource_name: &str, 
-    amount: u32
-) -> Result<(), String> {
-    let item_defs = ctx.db.item_definition();
-    let resource_def = item_defs.iter()
-        .find(|def| def.name == resource_name)
-        .ok_or_else(|| format!("{} item definition not found.", resource_name))?;
-        
-    crate::items::add_item_to_player_inventory(ctx, player_id, resource_def.id, amount)
-        .map(|_| ())
-        .map_err(|e| format!("Failed to grant {} to player: {}", resource_name, e))
-}
+        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
+            let distance = dist_sq.sqrt();
+            let target_vec_x = dx / distance;
+            let target_vec_y = dy / distance;
 
-/// Calculates damage amount based on item definition, target type, and RNG.
-/// Returns a random f32 damage value within the defined min/max range for the interaction.
-pub fn calculate_damage_and_yield(
-    item_def: &ItemDefinition, 
-    target_type: TargetType,
-    rng: &mut impl Rng,
-) -> (f32, u32, String) {
-    let mut damage_min = 0u32;
-    let mut damage_max = 0u32;
-    let mut yield_min = 0u32;
-    let mut yield_max = 0u32;
-    let mut resource_name = "None".to_string(); // Default to None, especially for PvP
+            let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
+            let angle_rad = dot_product.acos();
 
-    if target_type == TargetType::Player {
-        damage_min = item_def.pvp_damage_min.unwrap_or(0);
-        damage_max = item_def.pvp_damage_max.unwrap_or(damage_min); 
-        yield_min = 0; // No yield from players
-        yield_max = 0;
-        // resource_name is already "None"
-    } else if target_type == TargetType::Campfire || target_type == TargetType::WoodenStorageBox {
-        // For structures, use PvP damage as a baseline if specific structure damage isn't defined.
-        // Ideally, we would add specific fields like `campfire_damage_min`, etc., to ItemDefinition.
-        damage_min = item_def.pvp_damage_min.unwrap_or(0); // Example: Use PvP damage for now
-        damage_max = item_def.pvp_damage_max.unwrap_or(damage_min);
-        yield_min = 0; // No resource yield from destroying structures directly
-        yield_max = 0;
-        resource_name = "None".to_string();
-    } else if target_type == TargetType::Stash || target_type == TargetType::SleepingBag {
-        // For stashes and sleeping bags, use PvP damage as a baseline.
-        damage_min = item_def.pvp_damage_min.unwrap_or(0);
-        damage_max = item_def.pvp_damage_max.unwrap_or(damage_min);
-        yield_min = 0; // No resource yield
-        yield_max = 0;
-        resource_name = "None".to_string();
-    } else if Some(target_type) == item_def.primary_target_type {
-        // Target matches the item's primary target type
-        damage_min = item_def.primary_target_damage_min.unwrap_or(0);
-        damage_max = item_def.primary_target_damage_max.unwrap_or(damage_min);
-        yield_min = item_def.primary_target_yield_min.unwrap_or(0);
-        yield_max = item_def.primary_target_yield_max.unwrap_or(yield_min);
-        resource_name = item_def.primary_yield_resource_name.clone().unwrap_or_else(|| "None".to_string());
-    } else if Some(target_type) == item_def.secondary_target_type {
-        // Target matches the item's secondary target type
-        damage_min = item_def.secondary_target_damage_min.unwrap_or(0);
-        damage_max = item_def.secondary_target_damage_max.unwrap_or(damage_min);
-        yield_min = item_def.secondary_target_yield_min.unwrap_or(0);
-        yield_max = item_def.secondary_target_yield_max.unwrap_or(yield_min);
-        resource_name = item_def.secondary_yield_resource_name.clone().unwrap_or_else(|| "None".to_string());
-    } else {
-        // Tool is not designed for this target type (e.g., trying to hit a tree with something that has no tree affinity)
-        // Fallback to very low/no damage and no yield.
-        // If it has PvP damage defined, use that as a last resort even for non-player, otherwise 0.
-        damage_min = item_def.pvp_damage_min.unwrap_or(0); // Could be 0 if not a weapon
-        damage_max = item_def.pvp_damage_max.unwrap_or(damage_min);
-        yield_min = 0;
-        yield_max = 0;
-        // resource_name is already "None"
-        log::warn!(
-            "Item '{}' used against unhandled target type '{:?}'. Primary: {:?}, Secondary: {:?}. Defaulting to minimal/no effect.", 
-            item_def.name, 
-            target_type,
-            item_def.primary_target_type,
-            item_def.secondary_target_type
-        );
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
+                targets.push(Target {
+                    target_type: TargetType::Recycler,
+                    id: TargetId::Recycler(recycler.id),
+                    distance_sq: dist_sq,
+                });
+            }
+        }
     }
 
-    // Ensure max is not less than min
-    if damage_max < damage_min { damage_max = damage_min; }
-    if yield_max < yield_min { yield_max = yield_min; }
+    // Check flame turrets
+    for turret in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::FlameTurret(id) => ctx.db.flame_turret().id().find(id),
+        _ => None,
+    }) {
+        if turret.is_destroyed {
+            continue;
+        }
+        let dx = turret.pos_x - player.position_x;
+        let target_y = turret.pos_y - FLAME_TURRET_COLLISION_Y_OFFSET;
+        let dy = target_y - player.position_y;
+        let dist_sq = dx * dx + dy * dy;
+
+        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
+            let distance = dist_sq.sqrt();
+            let target_vec_x = dx / distance;
+            let target_vec_y = dy / distance;
+
+            let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
+            let angle_rad = dot_product.acos();
+
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
+                targets.push(Target {
+                    target_type: TargetType::FlameTurret,
+                    id: TargetId::FlameTurret(turret.id),
+                    distance_sq: dist_sq,
+                });
+            }
+        }
+    }
+
+    // Check fresh corpses (harvestable for bones)
+    for corpse in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::PlayerCorpse(id) => ctx.db.player_corpse().id().find(id),
+        _ => None,
+    }) {
+        if corpse.stage != CorpseStage::Fresh {
+            continue;
+        }
+        let dx = corpse.pos_x - player.position_x;
+        let target_y = corpse.pos_y - CORPSE_COLLISION_Y_OFFSET;
+        let dy = target_y - player.position_y;
+        let dist_sq = dx * dx + dy * dy;
+
+        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
+            let distance = dist_sq.sqrt();
+            let target_vec_x = dx / distance;
+            let target_vec_y = dy / distance;
+
+            let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
+            let angle_rad = dot_product.acos();
+
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
+                targets.push(Target {
+                    target_type: TargetType::PlayerCorpse,
+                    id: TargetId::PlayerCorpse(corpse.id),
+                    distance_sq: dist_sq,
+                });
+            }
+        }
+    }
+
+    // Check large storage chests
+    for chest in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::LargeStorageChest(id) => ctx.db.large_storage_chest().id().find(id),
+        _ => None,
+    }) {
+        if chest.is_destroyed {
+            continue;
+        }
+        let dx = chest.pos_x - player.position_x;
+        let target_y = chest.pos_y - LARGE_CHEST_COLLISION_Y_OFFSET;
+        let dy = target_y - player.position_y;
+        let dist_sq = dx * dx + dy * dy;
+
+        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
+            let distance = dist_sq.sqrt();
+            let target_vec_x = dx / distance;
+            let target_vec_y = dy / distance;
+
+            let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
+            let angle_rad = dot_product.acos();
+
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
+                targets.push(Target {
+                    target_type: TargetType::LargeStorageChest,
+                    id: TargetId::LargeStorageChest(chest.id),
+                    distance_sq: dist_sq,
+                });
+            }
+        }
+    }
+
+    // Check wild animals
+    for animal in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::Animal(id) => ctx.db.wild_animal().id().find(id),
+        _ => None,
+    }) {
+        let dx = animal.pos_x - player.position_x;
+        let dy = animal.pos_y - player.position_y;
+        let dist_sq = dx * dx + dy * dy;
+
+        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
+            let distance = dist_sq.sqrt();
+            let target_vec_x = dx / distance;
+            let target_vec_y = dy / distance;
+
+            let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
+            let angle_rad = dot_product.acos();
+
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
+                targets.push(Target {
+                    target_type: TargetType::Animal,
+                    id: TargetId::Animal(animal.id),
+                    distance_sq: dist_sq,
+                });
+            }
+        }
+    }
+
+    // Check loot crates
+    for loot_crate in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::LootCrate(id) => ctx.db.loot_crate().id().find(id),
+        _ => None,
+    }) {
+        if loot_crate.health <= 0.0 {
+            continue;
+        }
+        let dx = loot_crate.pos_x - player.position_x;
+        let target_y = loot_crate.pos_y - LOOT_CRATE_COLLISION_Y_OFFSET;
+        let dy = target_y - player.position_y;
+        let dist_sq = dx * dx + dy * dy;
+
+        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
+            let distance = dist_sq.sqrt();
+            let target_vec_x = dx / distance;
+            let target_vec_y = dy / distance;
+
+            let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
+            let angle_rad = dot_product.acos();
+
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
+                targets.push(Target {
+                    target_type: TargetType::LootCrate,
+                    id: TargetId::LootCrate(loot_crate.id),
+                    distance_sq: dist_sq,
+                });
+            }
+        }
+    }
+
+    // Check wooden storage boxes
+    for box_entity in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::WoodenStorageBox(id) => ctx.db.wooden_storage_box().id().find(id),
+        _ => None,
+    }) {
+        if box_entity.is_destroyed {
+            continue;
+        }
+        let dx = box_entity.pos_x - player.position_x;
+        let target_y = box_entity.pos_y - BOX_COLLISION_Y_OFFSET;
+        let dy = target_y - player.position_y;
+        let dist_sq = dx * dx + dy * dy;
+
+        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
+            let distance = dist_sq.sqrt();
+            let target_vec_x = dx / distance;
+            let target_vec_y = dy / distance;
+
+            let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
+            let angle_rad = dot_product.acos();
+
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
+                targets.push(Target {
+                    target_type: TargetType::WoodenStorageBox,
+                    id: TargetId::WoodenStorageBox(box_entity.id),
+                    distance_sq: dist_sq,
+                });
+            }
+        }
+    }
+
+    // Check stashes
+    for stash_entity in ctx.db.stash().iter() {
+        if stash_entity.is_destroyed || stash_entity.is_hidden {
+            continue; // Skip destroyed or hidden stashes
+        }
+        // Treat stash as a point target for now, or use a very small radius if needed for cone
+        let dx = stash_entity.pos_x - player.position_x;
+        let dy = stash_entity.pos_y - player.position_y; // No Y-offset for point target
+        let dist_sq = dx * dx + dy * dy;
+
+        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
+            let distance = dist_sq.sqrt();
+            let target_vec_x = dx / distance;
+            let target_vec_y = dy / distance;
+
+            let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
+            let angle_rad = dot_product.acos();
+
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
+                targets.push(Target {
+                    target_type: TargetType::Stash,
+                    id: TargetId::Stash(stash_entity.id),
+                    distance_sq: dist_sq,
+                });
+            }
+        }
+    }
+
+    // Check sleeping bags
+    for bag_entity in ctx.db.sleeping_bag().iter() {
+        if bag_entity.is_destroyed {
+            continue;
+        }
+        let dx = bag_entity.pos_x - player.position_x;
+        let target_y = bag_entity.pos_y - SLEEPING_BAG_COLLISION_Y_OFFSET;
+        let dy = target_y - player.position_y;
+        let dist_sq = dx * dx + dy * dy;
+
+        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
+            let distance = dist_sq.sqrt();
+            let target_vec_x = dx / distance;
+            let target_vec_y = dy / distance;
+
+            let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
+            let angle_rad = dot_product.acos();
+
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, None) {
+                targets.push(Target {
+                    target_type: TargetType::SleepingBag,
+                    id: TargetId::SleepingBag(bag_entity.id),
+                    distance_sq: dist_sq,
+                });
+            }
+        }
+    }
+    
+    // Check building pieces (walls, doors, foundations)
+    for piece in nearby.iter().filter_map(|entity| match *entity {
+        spatial_grid::EntityType::BuildingPiece(id) => ctx.db.building_piece().id().find(id),
+        _ => None,
+    }) {
+        if piece.is_destroyed {
+            continue;
+        }
+        let dx = piece.pos_x - player.position_x;
+        let target_y = piece.pos_y - BUILDING_COLLISION_Y_OFFSET;
+        let dy = target_y - player.position_y;
+        let dist_sq = dx * dx + dy * dy;
+
+        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
+            let distance = dist_sq.sqrt();
+            let target_vec_x = dx / distance;
+            let target_vec_y = dy / distance;
+
+            let dot_product = forward_x * target_vec_x + forward_y * target_vec_y;
+            let angle_rad = dot_product.acos();
+
+            if angle_rad <= half_attack_angle_rad && in_sight(dx, dy, Some(spatial_grid::EntityType::BuildingPiece(piece.id))) {
+                targets.push(Target {
+                    target_type: TargetType::BuildingPiece,
+                    id: TargetId::BuildingPiece(piece.id),
+                    distance_sq: dist_sq,
+                });
+            }
+        }
+    }
+    
+    // Sort by distance (closest first)
+    targets.sort_by(|a, b| a.distance_sq.partial_cmp(&b.distance_sq).unwrap());
+    
+    targets
+}
+
+/// Returns true if nothing solid blocks the straight line between two points.
+///
+/// Walks the spatial grid cells along the line and tests it against standing trees,
+/// stones and solid building pieces (walls, closed doors). `ignore` excludes the entity
+/// being aimed at, since the line ends inside its own collision circle.
+pub fn has_line_of_sight(
+    ctx: &ReducerContext,
+    from_x: f32,
+    from_y: f32,
+    to_x: f32,
+    to_y: f32,
+    ignore: Option<spatial_grid::EntityType>,
+) -> bool {
+    let (dx, dy) = (to_x - from_x, to_y - from_y);
+    let length = (dx * dx + dy * dy).sqrt();
+    let steps = (length / spatial_grid::GRID_CELL_SIZE).ceil().max(1.0) as usize;
+    let mut checked: Vec<spatial_grid::EntityType> = Vec::new();
+
+    for step in 0..=steps {
+        let t = step as f32 / steps as f32;
+        for entity in spatial_grid::get_entities_in_range(ctx, from_x + dx * t, from_y + dy * t) {
+            if Some(entity) == ignore || checked.contains(&entity) {
+                continue;
+            }
+            checked.push(entity);
+            let obstacle = match entity {
+                spatial_grid::EntityType::Tree(id) => ctx.db.tree().id().find(id)
+                    .filter(|tree| tree.health > 0)
+                    .map(|tree| (tree.pos_x, tree.pos_y - TREE_COLLISION_Y_OFFSET, TREE_TRUNK_RADIUS)),
+                spatial_grid::EntityType::Stone(id) => ctx.db.stone().id().find(id)
+                    .filter(|stone| stone.health > 0)
+                    .map(|stone| (stone.pos_x, stone.pos_y - STONE_COLLISION_Y_OFFSET, STONE_RADIUS)),
+                spatial_grid::EntityType::BuildingPiece(id) => ctx.db.building_piece().id().find(id)
+                    .filter(|piece| piece.is_solid())
+                    .map(|piece| (piece.pos_x, piece.pos_y - BUILDING_COLLISION_Y_OFFSET, piece.collision_radius())),
+                _ => None,
+            };
+            if let Some((obstacle_x, obstacle_y, radius)) = obstacle {
+                if get_distance_squared_to_segment(obstacle_x, obstacle_y, from_x, from_y, to_x, to_y) < radius * radius {
+                    return false;
+                }
+            }
+        }
+    }
+    true
+}
+
+/// Determines the best target based on weapon type and available targets
+///
+/// Different weapons have different priorities (e.g., pickaxes prioritize stones).
+/// This function selects the appropriate target based on the weapon and available targets.
+pub fn find_best_target(targets: &[Target], item_def: &ItemDefinition) -> Option<Target> {
+    if targets.is_empty() {
+        return None;
+    }
+    
+    // 1. Check for primary target type
+    if let Some(primary_type) = item_def.primary_target_type {
+        if let Some(target) = targets.iter().find(|t| t.target_type == primary_type) {
+            return Some(target.clone());
+        }
+    }
+
+    // 2. Check for secondary target type
+    if let Some(secondary_type) = item_def.secondary_target_type {
+        if let Some(target) = targets.iter().find(|t| t.target_type == secondary_type) {
+            return Some(target.clone());
+        }
+    }
+
+    // 3. If tool has PvP damage, check for Player targets if no resource target was found
+    if item_def.pvp_damage_min.is_some() || item_def.pvp_damage_max.is_some() { // Check if any PvP damage is defined
+        if let Some(player_target) = targets.iter().find(|t| t.target_type == TargetType::Player) {
+            // Only return player if primary/secondary types weren't found or aren't defined
+            if item_def.primary_target_type.is_none() && item_def.secondary_target_type.is_none() {
+                return Some(player_target.clone());
+            } else if item_def.primary_target_type.is_some() && targets.iter().find(|t| t.target_type == item_def.primary_target_type.unwrap()).is_none() &&
+                      item_def.secondary_target_type.is_some() && targets.iter().find(|t| t.target_type == item_def.secondary_target_type.unwrap()).is_none() {
+                 return Some(player_target.clone()); // Primary & secondary not found
+            } else if item_def.primary_target_type.is_some() && targets.iter().find(|t| t.target_type == item_def.primary_target_type.unwrap()).is_none() && item_def.secondary_target_type.is_none(){
+                return Some(player_target.clone()); // Primary not found, no secondary defined
+            } else if item_def.secondary_target_type.is_some() && targets.iter().find(|t| t.target_type == item_def.secondary_target_type.unwrap()).is_none() && item_def.primary_target_type.is_none(){
+                 return Some(player_target.clone()); // Secondary not found, no primary defined
+            }
+        }
+    }
+
+    // 4. If no specific preferred target found, return the closest target of any type.
+    // This allows hitting unintended targets, and calculate_damage_and_yield will determine effect (possibly zero).
+    return targets.first().cloned();
+}
+
+// --- Resource & Damage Functions ---
+
+/// Grants resource items to a player based on what they hit
+///
+/// Looks up the proper resource definition and adds it to the player's inventory.
+pub fn grant_resource(
+    ctx: &ReducerContext, 
+    player_id: Identity, 
+    resource_name: &str, 
+    amount: u32
+) -> Result<(), String> {
+    let item_defs = ctx.db.item_definition();
+    let resource_def = item_defs.iter()
+        .find(|def| def.name == resource_name)
+        .ok_or_else(|| format!("{} item definition not found.", resource_name))?;
+        
+    crate::items::add_item_to_player_inventory(ctx, player_id, resource_def.id, amount)
+        .map_err(|e| format!("Failed to grant {} to player: {}", resource_name, e))?;
+    crate::kill_feed::record_resources_harvested(ctx, player_id, amount);
+    Ok(())
+}
+
+/// Base damage and yield ranges of an item against a target type, before skill and richness bonuses.
+#[derive(Clone, Debug)]
+pub(crate) struct DamageYieldRange {
+    pub damage_min: u32,
+    pub damage_max: u32,
+    pub yield_min: u32,
+    pub yield_max: u32,
+    pub resource_name: String, // "None" when nothing is yielded
+}
+
+/// Looks up the damage and yield ranges for hitting `target_type` with the item.
+/// Shared by `calculate_damage_and_yield` and the item stat sheet so they can't disagree.
+pub(crate) fn damage_and_yield_range(item_def: &ItemDefinition, target_type: TargetType) -> DamageYieldRange {
+    let mut damage_min = 0u32;
+    let mut damage_max = 0u32;
+    let mut yield_min = 0u32;
+    let mut yield_max = 0u32;
+    let mut resource_name = "None".to_string(); // Default to None, especially for PvP
+
+    if target_type == TargetType::Player || target_type == TargetType::Animal || target_type == TargetType::SleepingPlayer || target_type == TargetType::Mount {
+        damage_min = item_def.pvp_damage_min.unwrap_or(0);
+        damage_max = item_def.pvp_damage_max.unwrap_or(damage_min); 
+        yield_min = 0; // No yield from players; animals drop their loot on death instead
+        yield_max = 0;
+        // resource_name is already "None"
+    } else if target_type == TargetType::Campfire || target_type == TargetType::WoodenStorageBox || target_type == TargetType::Furnace || target_type == TargetType::LargeStorageChest || target_type == TargetType::LootCrate || target_type == TargetType::Workbench || target_type == TargetType::Recycler || target_type == TargetType::FlameTurret || target_type == TargetType::Sign || target_type == TargetType::Switch {
+        // For structures, use PvP damage as a baseline if specific structure damage isn't defined.
+        // Ideally, we would add specific fields like `campfire_damage_min`, etc., to ItemDefinition.
+        damage_min = item_def.pvp_damage_min.unwrap_or(0); // Example: Use PvP damage for now
+        damage_max = item_def.pvp_damage_max.unwrap_or(damage_min);
+        yield_min = 0; // No resource yield from destroying structures directly
+        yield_max = 0;
+        resource_name = "None".to_string();
+    } else if target_type == TargetType::Stash || target_type == TargetType::SleepingBag || target_type == TargetType::BuildingPiece {
+        // For stashes and sleeping bags, use PvP damage as a baseline.
+        damage_min = item_def.pvp_damage_min.unwrap_or(0);
+        damage_max = item_def.pvp_damage_max.unwrap_or(damage_min);
+        yield_min = 0; // No resource yield
+        yield_max = 0;
+        resource_name = "None".to_string();
+    } else if target_type == TargetType::PlayerCorpse {
+        // Any item that deals damage can strip a fresh corpse for bones
+        damage_min = item_def.pvp_damage_min.unwrap_or(0);
+        damage_max = item_def.pvp_damage_max.unwrap_or(damage_min);
+        if damage_max > 0 {
+            yield_min = CORPSE_BONE_YIELD_MIN;
+            yield_max = CORPSE_BONE_YIELD_MAX;
+            resource_name = "Bone Fragments".to_string();
+        }
+    } else if target_type == TargetType::EnvironmentHazard {
+        // Any tool harvests hazards; what they yield depends on the hazard (see damage_environment_hazard)
+        if item_def.primary_target_type.is_some() {
+            damage_min = item_def.primary_target_damage_min.unwrap_or(0);
+            damage_max = item_def.primary_target_damage_max.unwrap_or(damage_min);
+            yield_min = crate::environment_hazard::HAZARD_YIELD_MIN;
+            yield_max = crate::environment_hazard::HAZARD_YIELD_MAX;
+        } else {
+            damage_min = item_def.pvp_damage_min.unwrap_or(0);
+            damage_max = item_def.pvp_damage_max.unwrap_or(damage_min);
+        }
+    } else if Some(target_type) == item_def.primary_target_type {
+        // Target matches the item's primary target type
+        damage_min = item_def.primary_target_damage_min.unwrap_or(0);
+        damage_max = item_def.primary_target_damage_max.unwrap_or(damage_min);
+        yield_min = item_def.primary_target_yield_min.unwrap_or(0);
+        yield_max = item_def.primary_target_yield_max.unwrap_or(yield_min);
+        resource_name = item_def.primary_yield_resource_name.clone().unwrap_or_else(|| "None".to_string());
+    } else if Some(target_type) == item_def.secondary_target_type {
+        // Target matches the item's secondary target type
+        damage_min = item_def.secondary_target_damage_min.unwrap_or(0);
+        damage_max = item_def.secondary_target_damage_max.unwrap_or(damage_min);
+        yield_min = item_def.secondary_target_yield_min.unwrap_or(0);
+        yield_max = item_def.secondary_target_yield_max.unwrap_or(yield_min);
+        resource_name = item_def.secondary_yield_resource_name.clone().unwrap_or_else(|| "None".to_string());
+    } else {
+        // Tool is not designed for this target type (e.g., trying to hit a tree with something that has no tree affinity)
+        // Fallback to very low/no damage and no yield.
+        // If it has PvP damage defined, use that as a last resort even for non-player, otherwise 0.
+        damage_min = item_def.pvp_damage_min.unwrap_or(0); // Could be 0 if not a weapon
+        damage_max = item_def.pvp_damage_max.unwrap_or(damage_min);
+        yield_min = 0;
+        yield_max = 0;
+        // resource_name is already "None"
+        log::warn!(
+            "Item '{}' used against unhandled target type '{:?}'. Primary: {:?}, Secondary: {:?}. Defaulting to minimal/no effect.", 
+            item_def.name, 
+            target_type,
+            item_def.primary_target_type,
+            item_def.secondary_target_type
+        );
+    }
+
+    // Ensure max is not less than min
+    if damage_max < damage_min { damage_max = damage_min; }
+    if yield_max < yield_min { yield_max = yield_min; }
+
+    DamageYieldRange { damage_min, damage_max, yield_min, yield_max, resource_name }
+}
+
+/// Calculates damage amount based on item definition, target type, and RNG.
+/// Returns a random f32 damage value within the defined min/max range for the interaction.
+pub fn calculate_damage_and_yield(
+    ctx: &ReducerContext,
+    item_def: &ItemDefinition, 
+    target_type: TargetType,
+    yield_bonus: f32, // Fractional bonus to the yield range, e.g. 0.1 for +10% (from skills)
+    richness: ResourceRichness, // Tier of the tree/stone being hit; Normal for everything else
+    rng: &mut impl Rng,
+) -> (f32, u32, String) {
+    let DamageYieldRange { damage_min, damage_max, mut yield_min, mut yield_max, resource_name } =
+        damage_and_yield_range(item_def, target_type);
+
+    let yield_multiplier = (1.0 + yield_bonus.max(0.0)) * richness.yield_multiplier()
+        * server_modifiers::yield_multiplier_for_resource(ctx, &resource_name);
+    if yield_multiplier != 1.0 {
+        yield_min = (yield_min as f32 * yield_multiplier).round() as u32;
+        yield_max = (yield_max as f32 * yield_multiplier).round() as u32;
+    }
 
     let mut final_damage = if damage_min == damage_max {
         damage_min as f32
     } else {
-        rng.gen_range(damage_min..=damage_max) as f32
-    };
+        rng.gen_range(damage_min..=damage_max) as f32
+    };
+
+    let final_yield = if yield_min == yield_max {
+        yield_min
+    } else {
+        rng.gen_range(yield_min..=yield_max)
+    };
+    
+    // Apply PVP multiplier if target is a player. This is now the authoritative damage for PvP.
+    if target_type == TargetType::Player {
+        let pvp_min = item_def.pvp_damage_min.unwrap_or(0); // Default to 0 if not specified
+        let pvp_max = item_def.pvp_damage_max.unwrap_or(pvp_min);
+        let base_pvp_damage = if pvp_min == pvp_max { pvp_min } else { rng.gen_range(pvp_min..=pvp_max) };
+        final_damage = base_pvp_damage as f32;
+        // Yield and resource_name for PvP are already 0 and "None"
+        return (final_damage, 0, "None".to_string());
+    }
+
+    (final_damage, final_yield, resource_name)
+}
+
+/// Applies damage to a tree and handles destruction/respawning
+///
+/// Reduces tree health, grants wood resources, and schedules respawn if depleted.
+pub fn damage_tree(
+    ctx: &ReducerContext, 
+    attacker_id: Identity, 
+    tree_id: u64, 
+    damage: f32,
+    yield_amount: u32,
+    resource_name_to_grant: &str,
+    hit_offset: Option<(f32, f32)>,
+    timestamp: Timestamp,
+    rng: &mut impl Rng
+) -> Result<AttackResult, String> {
+    let mut tree = ctx.db.tree().id().find(tree_id)
+        .ok_or_else(|| "Target tree disappeared".to_string())?;
+
+    let node = crate::sweet_spot::SweetSpotNode {
+        target_type: TargetType::Tree,
+        target_id: tree_id,
+        center_x: tree.pos_x,
+        center_y: tree.pos_y - crate::tree::TREE_COLLISION_Y_OFFSET,
+        radius: crate::tree::TREE_TRUNK_RADIUS,
+    };
+    let sweet_spot_multiplier = crate::sweet_spot::resolve_hit(ctx, attacker_id, &node, &mut tree.sweet_spot_angle, hit_offset, rng);
+    let yield_amount = (yield_amount as f32 * sweet_spot_multiplier).round() as u32;
+    
+    let old_health = tree.health;
+    tree.health = tree.health.saturating_sub(damage as u32);
+    tree.last_hit_time = Some(timestamp);
+    
+    log::info!("Player {:?} hit Tree {} for {:.1} damage. Health: {} -> {}", 
+           attacker_id, tree_id, damage, old_health, tree.health);
+    
+    let resource_result = grant_resource(ctx, attacker_id, resource_name_to_grant, yield_amount);
+    
+    if let Err(e) = resource_result {
+        log::error!("Failed to grant {} to player {:?}: {}", resource_name_to_grant, attacker_id, e);
+    }
+    
+    if tree.health == 0 {
+        log::info!("Tree {} destroyed by Player {:?}. Scheduling respawn.", tree_id, attacker_id);
+        // Calculate random respawn time for trees
+        let respawn_duration_secs = if MIN_TREE_RESPAWN_TIME_SECS >= MAX_TREE_RESPAWN_TIME_SECS {
+            MIN_TREE_RESPAWN_TIME_SECS
+        } else {
+            rng.gen_range(MIN_TREE_RESPAWN_TIME_SECS..=MAX_TREE_RESPAWN_TIME_SECS)
+        };
+        let respawn_duration_secs = (respawn_duration_secs as f32 * crate::world_state::current_season(ctx).resource_respawn_time_multiplier()).round() as u64;
+        let respawn_time = timestamp + spacetimedb::TimeDuration::from(Duration::from_secs(respawn_duration_secs));
+        tree.respawn_at = Some(respawn_time);
+        crate::tree_felling::fell_tree(ctx, &tree, attacker_id);
+    }
+    
+    ctx.db.tree().id().update(tree);
+    
+    Ok(AttackResult {
+        hit: true,
+        target_type: Some(TargetType::Tree),
+        resource_granted: Some((resource_name_to_grant.to_string(), yield_amount)),
+    })
+}
+
+/// Applies damage to a tree stump, granting wood, and removes it once used up
+pub fn damage_tree_stump(
+    ctx: &ReducerContext,
+    attacker_id: Identity,
+    stump_id: u64,
+    damage: f32,
+    yield_amount: u32,
+    resource_name_to_grant: &str,
+    timestamp: Timestamp,
+) -> Result<AttackResult, String> {
+    let stumps_table = ctx.db.tree_stump();
+    let mut stump = stumps_table.id().find(stump_id)
+        .ok_or_else(|| "Target tree stump disappeared".to_string())?;
+
+    let old_health = stump.health;
+    stump.health = stump.health.saturating_sub(damage as u32);
+    stump.last_hit_time = Some(timestamp);
+
+    log::info!("Player {:?} hit Tree Stump {} for {:.1} damage. Health: {} -> {}",
+           attacker_id, stump_id, damage, old_health, stump.health);
+
+    if let Err(e) = grant_resource(ctx, attacker_id, resource_name_to_grant, yield_amount) {
+        log::error!("Failed to grant {} to player {:?}: {}", resource_name_to_grant, attacker_id, e);
+    }
+
+    if stump.health == 0 {
+        stumps_table.id().delete(stump_id);
+        spatial_grid::untrack_entity(ctx, spatial_grid::EntityType::TreeStump(stump_id));
+        log::info!("Tree Stump {} used up by Player {:?}.", stump_id, attacker_id);
+    } else {
+        stumps_table.id().update(stump);
+    }
+
+    Ok(AttackResult {
+        hit: true,
+        target_type: Some(TargetType::TreeStump),
+        resource_granted: Some((resource_name_to_grant.to_string(), yield_amount)),
+    })
+}
+
+/// Applies damage to an environmental hazard, granting its resource, and schedules its
+/// regrowth once it is used up
+pub fn damage_environment_hazard(
+    ctx: &ReducerContext,
+    attacker_id: Identity,
+    hazard_id: u64,
+    damage: f32,
+    yield_amount: u32,
+    timestamp: Timestamp,
+    rng: &mut impl Rng
+) -> Result<AttackResult, String> {
+    let hazards_table = ctx.db.environment_hazard();
+    let mut hazard = hazards_table.id().find(hazard_id)
+        .ok_or_else(|| "Target hazard disappeared".to_string())?;
+    let resource_name = hazard.hazard_type.resource_name();
+
+    let old_health = hazard.health;
+    hazard.health = hazard.health.saturating_sub(damage as u32);
+    hazard.last_hit_time = Some(timestamp);
+
+    log::info!("Player {:?} hit {:?} {} for {:.1} damage. Health: {} -> {}",
+           attacker_id, hazard.hazard_type, hazard_id, damage, old_health, hazard.health);
+
+    let yield_amount = (yield_amount as f32 * server_modifiers::yield_multiplier_for_resource(ctx, resource_name)).round() as u32;
+    if yield_amount > 0 {
+        if let Err(e) = grant_resource(ctx, attacker_id, resource_name, yield_amount) {
+            log::error!("Failed to grant {} to player {:?}: {}", resource_name, attacker_id, e);
+        }
+    }
+
+    if hazard.health == 0 {
+        log::info!("{:?} {} used up by Player {:?}. Scheduling respawn.", hazard.hazard_type, hazard_id, attacker_id);
+        let respawn_duration_secs = rng.gen_range(crate::environment_hazard::MIN_HAZARD_RESPAWN_TIME_SECS..=crate::environment_hazard::MAX_HAZARD_RESPAWN_TIME_SECS);
+        let respawn_duration_secs = (respawn_duration_secs as f32 * crate::world_state::current_season(ctx).resource_respawn_time_multiplier()).round() as u64;
+        hazard.respawn_at = Some(timestamp + spacetimedb::TimeDuration::from(Duration::from_secs(respawn_duration_secs)));
+    }
+    hazards_table.id().update(hazard);
+
+    Ok(AttackResult {
+        hit: true,
+        target_type: Some(TargetType::EnvironmentHazard),
+        resource_granted: (yield_amount > 0).then(|| (resource_name.to_string(), yield_amount)),
+    })
+}
+
+/// Applies damage to a stone and handles destruction/respawning
+///
+/// Reduces stone health, grants stone resources, and schedules respawn if depleted.
+pub fn damage_stone(
+    ctx: &ReducerContext, 
+    attacker_id: Identity, 
+    stone_id: u64, 
+    damage: f32,
+    yield_amount: u32,
+    resource_name_to_grant: &str,
+    hit_offset: Option<(f32, f32)>,
+    timestamp: Timestamp,
+    rng: &mut impl Rng
+) -> Result<AttackResult, String> {
+    let mut stone = ctx.db.stone().id().find(stone_id)
+        .ok_or_else(|| "Target stone disappeared".to_string())?;
+
+    let node = crate::sweet_spot::SweetSpotNode {
+        target_type: TargetType::Stone,
+        target_id: stone_id,
+        center_x: stone.pos_x,
+        center_y: stone.pos_y - crate::stone::STONE_COLLISION_Y_OFFSET,
+        radius: crate::stone::STONE_RADIUS,
+    };
+    let sweet_spot_multiplier = crate::sweet_spot::resolve_hit(ctx, attacker_id, &node, &mut stone.sweet_spot_angle, hit_offset, rng);
+    let yield_amount = (yield_amount as f32 * sweet_spot_multiplier).round() as u32;
+    
+    let old_health = stone.health;
+    stone.health = stone.health.saturating_sub(damage as u32);
+    stone.last_hit_time = Some(timestamp);
+    
+    log::info!("Player {:?} hit Stone {} for {:.1} damage. Health: {} -> {}", 
+           attacker_id, stone_id, damage, old_health, stone.health);
+    
+    let resource_result = grant_resource(ctx, attacker_id, resource_name_to_grant, yield_amount);
+    
+    if let Err(e) = resource_result {
+        log::error!("Failed to grant {} to player {:?}: {}", resource_name_to_grant, attacker_id, e);
+    }
+
+    // Stones occasionally give up some metal ore alongside the stone yield
+    if yield_amount > 0 && rng.gen_range(0..100) < METAL_ORE_CHANCE_PER_STONE_HIT {
+        let ore_amount = (yield_amount / 2).max(1);
+        if let Err(e) = grant_resource(ctx, attacker_id, "Metal Ore", ore_amount) {
+            log::error!("Failed to grant Metal Ore to player {:?}: {}", attacker_id, e);
+        }
+    }
+    
+    if stone.health == 0 {
+        log::info!("Stone {} depleted by Player {:?}. Scheduling respawn.", stone_id, attacker_id);
+        // Calculate random respawn time for stones
+        let respawn_duration_secs = if MIN_STONE_RESPAWN_TIME_SECS >= MAX_STONE_RESPAWN_TIME_SECS {
+            MIN_STONE_RESPAWN_TIME_SECS
+        } else {
+            rng.gen_range(MIN_STONE_RESPAWN_TIME_SECS..=MAX_STONE_RESPAWN_TIME_SECS)
+        };
+        let respawn_duration_secs = (respawn_duration_secs as f32 * crate::world_state::current_season(ctx).resource_respawn_time_multiplier()).round() as u64;
+        let respawn_time = timestamp + spacetimedb::TimeDuration::from(Duration::from_secs(respawn_duration_secs));
+        stone.respawn_at = Some(respawn_time);
+    }
+    
+    ctx.db.stone().id().update(stone);
+    
+    Ok(AttackResult {
+        hit: true,
+        target_type: Some(TargetType::Stone),
+        resource_granted: Some((resource_name_to_grant.to_string(), yield_amount)),
+    })
+}
+
+/// Applies damage to another player and handles death
+///
+/// Reduces player health, handles death state, creates a corpse, and schedules despawn.
+pub fn damage_player(
+    ctx: &ReducerContext, 
+    attacker_id: Identity, 
+    target_id: Identity, 
+    damage: f32, 
+    item_def: &ItemDefinition,
+    damage_type: DamageType,
+    timestamp: Timestamp
+) -> Result<AttackResult, String> {
+    log::debug!(
+        "Attempting to damage player {:?} from attacker {:?} with item {}", 
+        target_id, attacker_id, item_def.name
+    );
+    let players = ctx.db.player();
+    let active_equipment_table = ctx.db.active_equipment();
+    let inventory_items_table = ctx.db.inventory_item();
+    let player_corpse_table = ctx.db.player_corpse();
+    let player_corpse_schedule_table = ctx.db.player_corpse_despawn_schedule();
+    let trees_table = ctx.db.tree();
+    let stones_table = ctx.db.stone();
+    let wooden_storage_boxes_table = ctx.db.wooden_storage_box();
+
+    let attacker_player_opt = players.identity().find(&attacker_id);
+    let mut target_player = players.identity().find(&target_id)
+        .ok_or_else(|| format!("Target player {:?} not found for damage.", target_id))?;
+
+    if target_player.is_dead {
+        log::debug!("Target player {:?} is already dead. No damage applied.", target_id);
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::Player), resource_granted: None });
+    }
+
+    if crate::is_dodge_invulnerable(&target_player, timestamp) {
+        log::debug!("Target player {:?} is mid-dodge. No damage applied.", target_id);
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::Player), resource_granted: None });
+    }
+    if crate::active_effects::has_spawn_protection(ctx, target_id, timestamp) || crate::active_effects::has_spawn_protection(ctx, attacker_id, timestamp) {
+        log::debug!("Player {:?} or {:?} is spawn protected. No damage applied.", attacker_id, target_id);
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::Player), resource_granted: None });
+    }
+
+    if crate::team::is_friendly_fire_blocked(ctx, attacker_id, target_id) {
+        log::debug!("Player {:?} hit teammate {:?} with friendly fire disabled. No damage applied.", attacker_id, target_id);
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::Player), resource_granted: None });
+    }
+    if !crate::zone::is_pvp_allowed_between(ctx, attacker_id, target_player.position_x, target_player.position_y) {
+        log::debug!("Player {:?} hit {:?} where PvP is off. No damage applied.", attacker_id, target_id);
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::Player), resource_granted: None });
+    }
+
+    // A raised shield stops part of melee and projectile hits (see blocking.rs)
+    let mut final_damage = damage * crate::blocking::get_damage_multiplier(ctx, target_id, damage_type);
+
+    // <<< APPLY ARMOR RESISTANCE >>>
+    let resistance = armor::calculate_total_protection(ctx, target_id, damage_type);
+    armor::apply_armor_durability_loss(ctx, target_id);
+    if resistance > 0.0 {
+        let damage_reduction = final_damage * resistance;
+        let resisted_damage = final_damage - damage_reduction;
+        
+        log::info!(
+            "Player {:?} attacking Player {:?}. Initial Damage: {:.2}, Resistance: {:.2} ({:.0}%), Final Damage after resistance: {:.2}",
+            attacker_id, target_id, 
+            final_damage, // Log the damage before resistance
+            resistance,
+            resistance * 100.0,
+            resisted_damage.max(0.0)
+        );
+        final_damage = resisted_damage.max(0.0); // Damage cannot be negative
+    } else {
+        log::info!(
+            "Player {:?} attacking Player {:?}. Initial Damage: {:.2} (No resistance). Final Damage: {:.2}",
+            attacker_id, target_id, 
+            final_damage, 
+            final_damage
+        );
+    }
+    // <<< END APPLY ARMOR RESISTANCE >>>
+
+    // A "hit" has occurred. Set last_hit_time immediately for client visuals.
+    target_player.last_hit_time = Some(timestamp);
+
+    let old_health = target_player.health;
+    target_player.health = (target_player.health - final_damage).clamp(0.0, MAX_STAT_VALUE);
+    let actual_damage_applied = old_health - target_player.health; // This is essentially final_damage clamped by remaining health
+    // Logged before knockback so positions are where the hit landed
+    crate::combat_log::record_pvp_hit(ctx, attacker_id, attacker_player_opt.as_ref(), &target_player, &item_def.name, actual_damage_applied, target_player.health <= 0.0);
+
+    // --- APPLY KNOCKBACK and update timestamp if damage was dealt ---
+    if actual_damage_applied > 0.0 { // Only apply knockback and update timestamp if actual damage occurred
+        target_player.last_update = timestamp; // Update target's timestamp due to health change and potential knockback
+        crate::player_stats::tag_player_in_combat(ctx, target_id);
+
+        if let Some(mut attacker) = attacker_player_opt.clone() { // Clone attacker_player_opt to get a mutable attacker if needed
+            let dx_target_from_attacker = target_player.position_x - attacker.position_x;
+            let dy_target_from_attacker = target_player.position_y - attacker.position_y;
+            let distance_sq = dx_target_from_attacker * dx_target_from_attacker + dy_target_from_attacker * dy_target_from_attacker;
+
+            if distance_sq > 0.001 { // Avoid division by zero or tiny distances
+                let distance = distance_sq.sqrt();
+                let knockback_distance = crate::game_config::get_game_config(ctx).pvp_knockback_distance;
+                // Knockback for Target
+                let knockback_dx_target = (dx_target_from_attacker / distance) * knockback_distance;
+                let knockback_dy_target = (dy_target_from_attacker / distance) * knockback_distance;
+                
+                let (final_target_x, final_target_y) = apply_knockback(
+                    ctx,
+                    target_player.identity,
+                    target_player.position_x,
+                    target_player.position_y,
+                    knockback_dx_target,
+                    knockback_dy_target,
+                );
+                target_player.set_position(final_target_x, final_target_y);
+                crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::Player(target_player.identity), final_target_x, final_target_y);
+                log::debug!("Applied knockback to target player {:?}: new pos ({:.1}, {:.1})", 
+                    target_id, target_player.position_x, target_player.position_y);
+                active_effects::apply_stagger(ctx, target_id, STAGGER_SLOW_PERCENT, STAGGER_DURATION_SECS, timestamp);
+
+                // Knockback for Attacker (recoil)
+                let attacker_recoil_distance = knockback_distance / 3.0; // Example: attacker recoils less
+                let knockback_dx_attacker = (-dx_target_from_attacker / distance) * attacker_recoil_distance; // Opposite direction
+                let knockback_dy_attacker = (-dy_target_from_attacker / distance) * attacker_recoil_distance; // Opposite direction
+                
+                let (final_attacker_x, final_attacker_y) = apply_knockback(
+                    ctx,
+                    attacker.identity,
+                    attacker.position_x,
+                    attacker.position_y,
+                    knockback_dx_attacker,
+                    knockback_dy_attacker,
+                );
+                attacker.set_position(final_attacker_x, final_attacker_y);
+                crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::Player(attacker.identity), final_attacker_x, final_attacker_y);
+                attacker.last_update = timestamp; // Update attacker's timestamp as their position changed
+                players.identity().update(attacker.clone()); // Update attacker player in DB
+                 log::debug!("Applied recoil to attacking player {:?}: new pos ({:.1}, {:.1})", 
+                    attacker_id, attacker.position_x, attacker.position_y);
+            }
+        }
+    }
+    // --- END KNOCKBACK ---
+
+    let killed = target_player.health <= 0.0;
+
+    log::info!(
+        "Player {:?} damaged Player {:?} for {:.2} (raw: {:.2}) with {}. Health: {:.2} -> {:.2}",
+        attacker_id, target_id, actual_damage_applied, damage, item_def.name, old_health, target_player.health
+    );
+
+    // Apply the weapon's on-hit status effects (bleed, slow)
+    active_effects::apply_on_hit_effects(ctx, target_id, item_def, timestamp);
+
+    // INTERRUPT BANDAGE IF DAMAGED
+    active_effects::cancel_bandage_burst_effects(ctx, target_id);
+    crate::channeled_use::cancel_channel(ctx, target_id);
+
+    if killed {
+        handle_player_death(ctx, &mut target_player, timestamp, DeathCause::PvP, "", Some(attacker_id));
+        players.identity().update(target_player.clone());
+        log::info!("Player {:?} marked as dead.", target_id);
+        let kill_distance = attacker_player_opt.as_ref()
+            .map_or(0.0, |a| ((target_player.position_x - a.position_x).powi(2) + (target_player.position_y - a.position_y).powi(2)).sqrt());
+        crate::kill_feed::record_kill(ctx, attacker_id, target_id, &item_def.name, kill_distance);
+
+    } else if target_player.health > 0.0 {
+        // Player is alive. last_hit_time and last_update were set at the beginning.
+        // Simply update the player state with new health etc.
+        players.identity().update(target_player);
+    }
+
+    Ok(AttackResult {
+        hit: true,
+        target_type: Some(TargetType::Player),
+        resource_granted: None,
+    })
+}
+
+/// Applies damage to a player from a non-player source (e.g. a wild animal).
+/// Armor resistance applies, but there is no knockback, bleed or friendly fire check.
+/// Returns true if the player was killed, in which case the death is recorded as `death_cause`.
+pub fn damage_player_from_environment(
+    ctx: &ReducerContext,
+    target_id: Identity,
+    damage: f32,
+    damage_type: DamageType,
+    death_cause: DeathCause,
+    source_name: &str,
+    timestamp: Timestamp
+) -> Result<bool, String> {
+    let players = ctx.db.player();
+    let mut target_player = players.identity().find(target_id)
+        .ok_or_else(|| format!("Target player {:?} not found for damage.", target_id))?;
+
+    if target_player.is_dead {
+        return Ok(false);
+    }
+
+    let resistance = armor::calculate_total_protection(ctx, target_id, damage_type);
+    armor::apply_armor_durability_loss(ctx, target_id);
+    let final_damage = (damage - damage * resistance).max(0.0);
+
+    target_player.last_hit_time = Some(timestamp);
+    target_player.last_update = timestamp;
+    let old_health = target_player.health;
+    target_player.health = (target_player.health - final_damage).clamp(0.0, MAX_STAT_VALUE);
+
+    log::info!(
+        "{} damaged Player {:?} for {:.2} (raw: {:.2}). Health: {:.2} -> {:.2}",
+        source_name, target_id, old_health - target_player.health, damage, old_health, target_player.health
+    );
+
+    active_effects::cancel_bandage_burst_effects(ctx, target_id);
+    crate::channeled_use::cancel_channel(ctx, target_id);
+
+    let killed = target_player.health <= 0.0;
+    if killed {
+        handle_player_death(ctx, &mut target_player, timestamp, death_cause, source_name, None);
+        log::info!("Player {:?} killed by {}.", target_id, source_name);
+    }
+    players.identity().update(target_player);
+    Ok(killed)
+}
+
+/// Applies damage to an offline player's sleeping body. The damage goes to the offline
+/// player's health (after armor); killing the sleeper kills the player and leaves a corpse.
+pub fn damage_sleeping_player(
+    ctx: &ReducerContext,
+    attacker_id: Identity,
+    sleeper_id: u32,
+    damage: f32,
+    damage_type: DamageType,
+    timestamp: Timestamp,
+) -> Result<AttackResult, String> {
+    let sleepers = ctx.db.sleeping_player();
+    let mut sleeper = sleepers.id().find(sleeper_id)
+        .ok_or_else(|| format!("Target sleeping player {} disappeared", sleeper_id))?;
+    let players = ctx.db.player();
+    let mut sleeping_player = players.identity().find(sleeper.player_identity)
+        .ok_or_else(|| format!("Player {:?} for sleeper {} not found", sleeper.player_identity, sleeper_id))?;
+
+    if sleeping_player.is_dead {
+        sleepers.id().delete(sleeper_id);
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::SleepingPlayer), resource_granted: None });
+    }
+    if crate::team::is_friendly_fire_blocked(ctx, attacker_id, sleeper.player_identity) {
+        log::debug!("Player {:?} hit sleeping teammate {:?} with friendly fire disabled. No damage applied.", attacker_id, sleeper.player_identity);
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::SleepingPlayer), resource_granted: None });
+    }
+    if !crate::zone::is_pvp_allowed_between(ctx, attacker_id, sleeper.pos_x, sleeper.pos_y) {
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::SleepingPlayer), resource_granted: None });
+    }
+
+    let resistance = armor::calculate_total_protection(ctx, sleeping_player.identity, damage_type);
+    armor::apply_armor_durability_loss(ctx, sleeping_player.identity);
+    let final_damage = (damage - damage * resistance).max(0.0);
+    let old_health = sleeping_player.health;
+    sleeping_player.health = (sleeping_player.health - final_damage).clamp(0.0, MAX_STAT_VALUE);
+    sleeping_player.last_hit_time = Some(timestamp);
+    sleeper.health = sleeping_player.health;
+    sleeper.last_hit_time = Some(timestamp);
+
+    log::info!(
+        "Player {:?} hit sleeping player {} ({:?}) for {:.1} damage. Health: {:.1} -> {:.1}",
+        attacker_id, sleeper.username, sleeper.player_identity, final_damage, old_health, sleeping_player.health
+    );
+
+    if sleeping_player.health <= 0.0 {
+        handle_player_death(ctx, &mut sleeping_player, timestamp, DeathCause::PvP, "", Some(attacker_id));
+        crate::kill_feed::credit_kill(ctx, attacker_id);
+        sleepers.id().delete(sleeper_id);
+        log::info!("Sleeping player {:?} killed by {:?}.", sleeper.player_identity, attacker_id);
+    } else {
+        sleepers.id().update(sleeper);
+    }
+    players.identity().update(sleeping_player);
+
+    Ok(AttackResult {
+        hit: true,
+        target_type: Some(TargetType::SleepingPlayer),
+        resource_granted: None,
+    })
+}
+
+/// Marks a player as dead, clears their active item and leaves a corpse with their items.
+/// The death is recorded with its cause; `source_name` (e.g. the animal) may be empty.
+/// `killer_id` is the player who landed the killing blow in PvP, who gets first pick of the corpse.
+/// The caller is responsible for persisting the updated player row.
+pub(crate) fn handle_player_death(ctx: &ReducerContext, target_player: &mut crate::Player, timestamp: Timestamp, death_cause: DeathCause, source_name: &str, killer_id: Option<Identity>) {
+    target_player.is_dead = true;
+    target_player.death_timestamp = Some(timestamp);
+    crate::mount::dismount_player(ctx, target_player.identity);
+    crate::kill_feed::record_death(ctx, target_player.identity, death_cause, source_name);
+    // last_update and last_hit_time are already set from the initial hit registration.
+    // No need to set them again here unless there's a specific reason for death to override.
+    // Keeping them as set at the start of the hit interaction is consistent.
+
+    match crate::active_equipment::clear_active_item_reducer(ctx, target_player.identity) {
+        Ok(_) => log::info!("[PlayerDeath] Active item cleared for dying player {}", target_player.identity),
+        Err(e) => log::error!("[PlayerDeath] Failed to clear active item for dying player {}: {}", target_player.identity, e),
+    }
+
+    match create_player_corpse(ctx, target_player.identity, target_player.position_x, target_player.position_y, &target_player.username, death_cause, killer_id) {
+        Ok(_) => {
+            log::info!("Successfully created corpse via combat death for player {:?}", target_player.identity);
+            if let Some(active_equip) = ctx.db.active_equipment().player_identity().find(target_player.identity) {
+                if active_equip.equipped_item_instance_id.is_some() {
+                    match crate::active_equipment::clear_active_item_reducer(ctx, target_player.identity) {
+                        Ok(_) => log::info!("[CombatDeath] Active item cleared for target {}", target_player.identity),
+                        Err(e) => log::error!("[CombatDeath] Failed to clear active item for target {}: {}", target_player.identity, e),
+                    }
+                }
+            }
+        }
+        Err(e) => {
+            log::error!("Failed to create corpse via combat death for player {:?}: {}", target_player.identity, e);
+        }
+    }
+}
+
+/// Applies damage to a campfire and handles destruction/item scattering
+pub fn damage_campfire(
+    ctx: &ReducerContext,
+    attacker_id: Identity,
+    campfire_id: u32,
+    damage: f32,
+    timestamp: Timestamp,
+    rng: &mut impl Rng // Added RNG for item scattering
+) -> Result<AttackResult, String> {
+    let mut campfires_table = ctx.db.campfire();
+    let mut campfire = campfires_table.id().find(campfire_id)
+        .ok_or_else(|| format!("Target campfire {} disappeared", campfire_id))?;
+
+    if campfire.is_destroyed {
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::Campfire), resource_granted: None });
+    }
+
+    let old_health = campfire.health;
+    campfire.health = (campfire.health - damage).max(0.0);
+    campfire.last_hit_time = Some(timestamp);
+
+    log::info!(
+        "Player {:?} hit Campfire {} for {:.1} damage. Health: {:.1} -> {:.1}",
+        attacker_id, campfire_id, damage, old_health, campfire.health
+    );
+
+    if campfire.health <= 0.0 {
+        campfire.is_destroyed = true;
+        campfire.destroyed_at = Some(timestamp);
+        crate::world_event::emit_structure_destroyed(ctx, "Campfire", campfire.pos_x, campfire.pos_y, attacker_id);
+        // Scatter items
+        let mut items_to_drop: Vec<(u64, u32)> = Vec::new(); // (item_def_id, quantity)
+        for i in 0..crate::campfire::NUM_FUEL_SLOTS {
+            if let (Some(instance_id), Some(def_id)) = (campfire.get_slot_instance_id(i as u8), campfire.get_slot_def_id(i as u8)) {
+                if let Some(item) = ctx.db.inventory_item().instance_id().find(instance_id) {
+                    items_to_drop.push((def_id, item.quantity));
+                    // Delete the InventoryItem from the central table
+                    ctx.db.inventory_item().instance_id().delete(instance_id);
+                }
+                campfire.set_slot(i as u8, None, None); // Clear slot in campfire struct (though it's about to be deleted)
+            }
+        }
+
+        // Update the campfire one last time to ensure is_destroyed and destroyed_at are sent to client
+        campfires_table.id().update(campfire.clone()); 
+        // Then immediately delete the campfire entity itself
+        campfires_table.id().delete(campfire_id);
+        crate::spatial_grid::untrack_entity(ctx, crate::spatial_grid::EntityType::Campfire(campfire_id));
+
+        log::info!(
+            "Campfire {} destroyed by player {:?}. Dropping items.",
+            campfire_id, attacker_id
+        );
+
+        // Scatter collected items around the campfire's location
+        for (item_def_id, quantity) in items_to_drop {
+            // Spawn slightly offset from campfire center
+            let offset_x = (rng.gen::<f32>() - 0.5) * 2.0 * 20.0; // Spread within +/- 20px
+            let offset_y = (rng.gen::<f32>() - 0.5) * 2.0 * 20.0;
+            let drop_pos_x = campfire.pos_x + offset_x;
+            let drop_pos_y = campfire.pos_y + offset_y;
+
+            match dropped_item::create_dropped_item_entity(ctx, item_def_id, quantity, drop_pos_x, drop_pos_y) {
+                Ok(_) => log::debug!("Dropped {} of item_def_id {} from destroyed campfire {}", quantity, item_def_id, campfire_id),
+                Err(e) => log::error!("Failed to drop item_def_id {}: {}", item_def_id, e),
+            }
+        }
+
+    } else {
+        // Campfire still has health, just update it
+        campfires_table.id().update(campfire);
+    }
 
-    let final_yield = if yield_min == yield_max {
-        yield_min
+    Ok(AttackResult {
+        hit: true,
+        target_type: Some(TargetType::Campfire),
+        resource_granted: None,
+    })
+}
+
+/// Applies a hit to a placed structure's health and logs it. Returns true if the hit destroyed it.
+fn hit_structure(attacker_id: Identity, label: &str, id: u32, health: &mut f32, last_hit_time: &mut Option<Timestamp>, damage: f32, timestamp: Timestamp) -> bool {
+    let old_health = *health;
+    *health = (*health - damage).max(0.0);
+    *last_hit_time = Some(timestamp);
+
+    log::info!(
+        "Player {:?} hit {} {} for {:.1} damage. Health: {:.1} -> {:.1}",
+        attacker_id, label, id, damage, old_health, *health
+    );
+    *health <= 0.0
+}
+
+/// Announces a destroyed structure and takes it off the spatial grid. The caller deletes its row.
+fn finish_structure_destroyed(ctx: &ReducerContext, attacker_id: Identity, label: &str, id: u32, pos_x: f32, pos_y: f32, entity: crate::spatial_grid::EntityType) {
+    crate::world_event::emit_structure_destroyed(ctx, label, pos_x, pos_y, attacker_id);
+    crate::spatial_grid::untrack_entity(ctx, entity);
+    log::info!("{} {} destroyed by player {:?}.", label, id, attacker_id);
+}
+
+/// Empties a destroyed container, scattering its items on the ground within `spread` px of it.
+fn scatter_container_items<C: ItemContainer>(ctx: &ReducerContext, pos_x: f32, pos_y: f32, container: &mut C, spread: f32, rng: &mut impl Rng) {
+    let mut items_to_drop: Vec<(u64, u32)> = Vec::new(); // (item_def_id, quantity)
+    for i in 0..container.num_slots() as u8 {
+        if let (Some(instance_id), Some(def_id)) = (container.get_slot_instance_id(i), container.get_slot_def_id(i)) {
+            if let Some(item) = ctx.db.inventory_item().instance_id().find(instance_id) {
+                items_to_drop.push((def_id, item.quantity));
+                ctx.db.inventory_item().instance_id().delete(instance_id);
+            }
+            container.set_slot(i, None, None);
+        }
+    }
+
+    for (item_def_id, quantity) in items_to_drop {
+        let offset_x = (rng.gen::<f32>() - 0.5) * 2.0 * spread;
+        let offset_y = (rng.gen::<f32>() - 0.5) * 2.0 * spread;
+        if let Err(e) = dropped_item::create_dropped_item_entity(ctx, item_def_id, quantity, pos_x + offset_x, pos_y + offset_y) {
+            log::error!("Failed to drop item_def_id {}: {}", item_def_id, e);
+        }
+    }
+}
+
+/// Applies damage to a workbench and removes it once destroyed
+pub fn damage_workbench(
+    ctx: &ReducerContext,
+    attacker_id: Identity,
+    workbench_id: u32,
+    damage: f32,
+    timestamp: Timestamp,
+) -> Result<AttackResult, String> {
+    let workbenches_table = ctx.db.workbench();
+    let mut workbench: Workbench = workbenches_table.id().find(workbench_id)
+        .ok_or_else(|| format!("Target workbench {} disappeared", workbench_id))?;
+
+    if workbench.is_destroyed {
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::Workbench), resource_granted: None });
+    }
+
+    if hit_structure(attacker_id, "Workbench", workbench_id, &mut workbench.health, &mut workbench.last_hit_time, damage, timestamp) {
+        workbenches_table.id().delete(workbench_id);
+        finish_structure_destroyed(ctx, attacker_id, "Workbench", workbench_id, workbench.pos_x, workbench.pos_y, crate::spatial_grid::EntityType::Workbench(workbench_id));
     } else {
-        rng.gen_range(yield_min..=yield_max)
-    };
-    
-    // Apply PVP multiplier if target is a player. This is now the authoritative damage for PvP.
-    if target_type == TargetType::Player {
-        let pvp_min = item_def.pvp_damage_min.unwrap_or(0); // Default to 0 if not specified
-        let pvp_max = item_def.pvp_damage_max.unwrap_or(pvp_min);
-        let base_pvp_damage = if pvp_min == pvp_max { pvp_min } else { rng.gen_range(pvp_min..=pvp_max) };
-        final_damage = base_pvp_damage as f32;
-        // Yield and resource_name for PvP are already 0 and "None"
-        return (final_damage, 0, "None".to_string());
+        workbenches_table.id().update(workbench);
     }
 
-    (final_damage, final_yield, resource_name)
+    Ok(AttackResult {
+        hit: true,
+        target_type: Some(TargetType::Workbench),
+        resource_granted: None,
+    })
 }
 
-/// Applies damage to a tree and handles destruction/respawning
-///
-/// Reduces tree health, grants wood resources, and schedules respawn if depleted.
-pub fn damage_tree(
-    ctx: &ReducerContext, 
-    attacker_id: Identity, 
-    tree_id: u64, 
+/// Applies damage to a sign and removes it once destroyed
+pub fn damage_sign(
+    ctx: &ReducerContext,
+    attacker_id: Identity,
+    sign_id: u32,
     damage: f32,
-    yield_amount: u32,
-    resource_name_to_grant: &str,
     timestamp: Timestamp,
-    rng: &mut impl Rng
 ) -> Result<AttackResult, String> {
-    let mut tree = ctx.db.tree().id().find(tree_id)
-        .ok_or_else(|| "Target tree disappeared".to_string())?;
-    
-    let old_health = tree.health;
-    tree.health = tree.health.saturating_sub(damage as u32);
-    tree.last_hit_time = Some(timestamp);
-    
-    log::info!("Player {:?} hit Tree {} for {:.1} damage. Health: {} -> {}", 
-           attacker_id, tree_id, damage, old_health, tree.health);
-    
-    let resource_result = grant_resource(ctx, attacker_id, resource_name_to_grant, yield_amount);
-    
-    if let Err(e) = resource_result {
-        log::error!("Failed to grant {} to player {:?}: {}", resource_name_to_grant, attacker_id, e);
+    let signs_table = ctx.db.sign();
+    let mut sign: Sign = signs_table.id().find(sign_id)
+        .ok_or_else(|| format!("Target sign {} disappeared", sign_id))?;
+
+    if sign.is_destroyed {
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::Sign), resource_granted: None });
     }
-    
-    if tree.health == 0 {
-        log::info!("Tree {} destroyed by Player {:?}. Scheduling respawn.", tree_id, attacker_id);
-        // Calculate random respawn time for trees
-        let respawn_duration_secs = if MIN_TREE_RESPAWN_TIME_SECS >= MAX_TREE_RESPAWN_TIME_SECS {
-            MIN_TREE_RESPAWN_TIME_SECS
-        } else {
-            rng.gen_range(MIN_TREE_RESPAWN_TIME_SECS..=MAX_TREE_RESPAWN_TIME_SECS)
-        };
-        let respawn_time = timestamp + spacetimedb::TimeDuration::from(Duration::from_secs(respawn_duration_secs));
-        tree.respawn_at = Some(respawn_time);
+
+    if hit_structure(attacker_id, "Sign", sign_id, &mut sign.health, &mut sign.last_hit_time, damage, timestamp) {
+        signs_table.id().delete(sign_id);
+        finish_structure_destroyed(ctx, attacker_id, "Sign", sign_id, sign.pos_x, sign.pos_y, crate::spatial_grid::EntityType::Sign(sign_id));
+    } else {
+        signs_table.id().update(sign);
     }
-    
-    ctx.db.tree().id().update(tree);
-    
+
     Ok(AttackResult {
         hit: true,
-        target_type: Some(TargetType::Tree),
-        resource_granted: Some((resource_name_to_grant.to_string(), yield_amount)),
+        target_type: Some(TargetType::Sign),
+        resource_granted: None,
     })
 }
 
-/// Applies damage to a stone and handles destruction/respawning
-///
-/// Reduces stone health, grants stone resources, and schedules respawn if depleted.
-pub fn damage_stone(
-    ctx: &ReducerContext, 
-    attacker_id: Identity, 
-    stone_id: u64, 
+/// Applies damage to a switch and removes it, and its wires, once destroyed
+pub fn damage_switch(
+    ctx: &ReducerContext,
+    attacker_id: Identity,
+    switch_id: u32,
     damage: f32,
-    yield_amount: u32,
-    resource_name_to_grant: &str,
     timestamp: Timestamp,
-    rng: &mut impl Rng
 ) -> Result<AttackResult, String> {
-    let mut stone = ctx.db.stone().id().find(stone_id)
-        .ok_or_else(|| "Target stone disappeared".to_string())?;
-    
-    let old_health = stone.health;
-    stone.health = stone.health.saturating_sub(damage as u32);
-    stone.last_hit_time = Some(timestamp);
-    
-    log::info!("Player {:?} hit Stone {} for {:.1} damage. Health: {} -> {}", 
-           attacker_id, stone_id, damage, old_health, stone.health);
-    
-    let resource_result = grant_resource(ctx, attacker_id, resource_name_to_grant, yield_amount);
-    
-    if let Err(e) = resource_result {
-        log::error!("Failed to grant {} to player {:?}: {}", resource_name_to_grant, attacker_id, e);
+    let switches_table = ctx.db.wiring_switch();
+    let mut switch: WiringSwitch = switches_table.id().find(switch_id)
+        .ok_or_else(|| format!("Target switch {} disappeared", switch_id))?;
+
+    if switch.is_destroyed {
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::Switch), resource_granted: None });
     }
-    
-    if stone.health == 0 {
-        log::info!("Stone {} depleted by Player {:?}. Scheduling respawn.", stone_id, attacker_id);
-        // Calculate random respawn time for stones
-        let respawn_duration_secs = if MIN_STONE_RESPAWN_TIME_SECS >= MAX_STONE_RESPAWN_TIME_SECS {
-            MIN_STONE_RESPAWN_TIME_SECS
-        } else {
-            rng.gen_range(MIN_STONE_RESPAWN_TIME_SECS..=MAX_STONE_RESPAWN_TIME_SECS)
-        };
-        let respawn_time = timestamp + spacetimedb::TimeDuration::from(Duration::from_secs(respawn_duration_secs));
-        stone.respawn_at = Some(respawn_time);
+
+    if hit_structure(attacker_id, "Switch", switch_id, &mut switch.health, &mut switch.last_hit_time, damage, timestamp) {
+        switches_table.id().delete(switch_id);
+        finish_structure_destroyed(ctx, attacker_id, "Switch", switch_id, switch.pos_x, switch.pos_y, crate::spatial_grid::EntityType::Switch(switch_id));
+        crate::wiring::remove_switch_links(ctx, switch_id);
+    } else {
+        switches_table.id().update(switch);
     }
-    
-    ctx.db.stone().id().update(stone);
-    
+
     Ok(AttackResult {
         hit: true,
-        target_type: Some(TargetType::Stone),
-        resource_granted: Some((resource_name_to_grant.to_string(), yield_amount)),
+        target_type: Some(TargetType::Switch),
+        resource_granted: None,
     })
 }
 
-/// Applies damage to another player and handles death
-///
-/// Reduces player health, handles death state, creates a corpse, and schedules despawn.
-pub fn damage_player(
-    ctx: &ReducerContext, 
-    attacker_id: Identity, 
-    target_id: Identity, 
-    damage: f32, 
-    item_def: &ItemDefinition,
-    timestamp: Timestamp
+/// Applies damage to a mount and removes it once killed. A rider on it is left on foot.
+pub fn damage_mount(
+    ctx: &ReducerContext,
+    attacker_id: Identity,
+    mount_id: u64,
+    damage: f32,
+    timestamp: Timestamp,
 ) -> Result<AttackResult, String> {
-    log::debug!(
-        "Attempting to damage player {:?} from attacker {:?} with item {}", 
-        target_id, attacker_id, item_def.name
+    let mounts_table = ctx.db.mount();
+    let mut mount: Mount = mounts_table.id().find(mount_id)
+        .ok_or_else(|| format!("Target mount {} disappeared", mount_id))?;
+
+    // Mounts follow the PvP zone rules, and a ridden mount shares its rider's protection
+    let miss = AttackResult { hit: false, target_type: Some(TargetType::Mount), resource_granted: None };
+    if !crate::zone::is_pvp_allowed_between(ctx, attacker_id, mount.pos_x, mount.pos_y) {
+        return Ok(miss);
+    }
+    if let Some(rider_id) = mount.rider_id {
+        if crate::active_effects::has_spawn_protection(ctx, rider_id, timestamp)
+            || crate::active_effects::has_spawn_protection(ctx, attacker_id, timestamp)
+            || crate::team::is_friendly_fire_blocked(ctx, attacker_id, rider_id) {
+            return Ok(miss);
+        }
+    }
+
+    let old_health = mount.health;
+    mount.health = (mount.health - damage).max(0.0);
+    mount.last_hit_time = Some(timestamp);
+
+    log::info!(
+        "Player {:?} hit {:?} {} for {:.1} damage. Health: {:.1} -> {:.1}",
+        attacker_id, mount.mount_type, mount_id, damage, old_health, mount.health
     );
-    let players = ctx.db.player();
-    let active_equipment_table = ctx.db.active_equipment();
-    let inventory_items_table = ctx.db.inventory_item();
-    let player_corpse_table = ctx.db.player_corpse();
-    let player_corpse_schedule_table = ctx.db.player_corpse_despawn_schedule();
-    let trees_table = ctx.db.tree();
-    let stones_table = ctx.db.stone();
-    let wooden_storage_boxes_table = ctx.db.wooden_storage_box();
 
-    let attacker_player_opt = players.identity().find(&attacker_id);
-    let mut target_player = players.identity().find(&target_id)
-        .ok_or_else(|| format!("Target player {:?} not found for damage.", target_id))?;
+    if mount.health <= 0.0 {
+        crate::mount::remove_mount(ctx, mount_id);
+        log::info!("{:?} {} killed by player {:?}.", mount.mount_type, mount_id, attacker_id);
+    } else {
+        mounts_table.id().update(mount);
+    }
+
+    Ok(AttackResult {
+        hit: true,
+        target_type: Some(TargetType::Mount),
+        resource_granted: None,
+    })
+}
+
+/// Applies damage to a furnace and handles destruction/item scattering
+pub fn damage_furnace(
+    ctx: &ReducerContext,
+    attacker_id: Identity,
+    furnace_id: u32,
+    damage: f32,
+    timestamp: Timestamp,
+    rng: &mut impl Rng
+) -> Result<AttackResult, String> {
+    let furnaces_table = ctx.db.furnace();
+    let mut furnace: Furnace = furnaces_table.id().find(furnace_id)
+        .ok_or_else(|| format!("Target furnace {} disappeared", furnace_id))?;
+
+    if furnace.is_destroyed {
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::Furnace), resource_granted: None });
+    }
+
+    if hit_structure(attacker_id, "Furnace", furnace_id, &mut furnace.health, &mut furnace.last_hit_time, damage, timestamp) {
+        scatter_container_items(ctx, furnace.pos_x, furnace.pos_y, &mut furnace, 20.0, rng);
+        furnaces_table.id().delete(furnace_id);
+        finish_structure_destroyed(ctx, attacker_id, "Furnace", furnace_id, furnace.pos_x, furnace.pos_y, crate::spatial_grid::EntityType::Furnace(furnace_id));
+    } else {
+        furnaces_table.id().update(furnace);
+    }
+
+    Ok(AttackResult {
+        hit: true,
+        target_type: Some(TargetType::Furnace),
+        resource_granted: None,
+    })
+}
+
+/// Applies damage to a large storage chest and handles destruction/item scattering
+pub fn damage_large_storage_chest(
+    ctx: &ReducerContext,
+    attacker_id: Identity,
+    chest_id: u32,
+    damage: f32,
+    timestamp: Timestamp,
+    rng: &mut impl Rng
+) -> Result<AttackResult, String> {
+    let chests_table = ctx.db.large_storage_chest();
+    let mut chest = chests_table.id().find(chest_id)
+        .ok_or_else(|| format!("Target large chest {} disappeared", chest_id))?;
+
+    if chest.is_destroyed {
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::LargeStorageChest), resource_granted: None });
+    }
+
+    if hit_structure(attacker_id, "Large Storage Chest", chest_id, &mut chest.health, &mut chest.last_hit_time, damage, timestamp) {
+        scatter_container_items(ctx, chest.pos_x, chest.pos_y, &mut chest, 30.0, rng);
+        chests_table.id().delete(chest_id);
+        finish_structure_destroyed(ctx, attacker_id, "Large Storage Chest", chest_id, chest.pos_x, chest.pos_y, crate::spatial_grid::EntityType::LargeStorageChest(chest_id));
+        crate::locks::remove_locks_for_target(ctx, crate::locks::LockTargetType::LargeStorageChest, chest_id);
+    } else {
+        chests_table.id().update(chest);
+    }
+
+    Ok(AttackResult {
+        hit: true,
+        target_type: Some(TargetType::LargeStorageChest),
+        resource_granted: None,
+    })
+}
+
+/// Applies damage to a recycler and handles destruction/item scattering
+pub fn damage_recycler(
+    ctx: &ReducerContext,
+    attacker_id: Identity,
+    recycler_id: u32,
+    damage: f32,
+    timestamp: Timestamp,
+    rng: &mut impl Rng
+) -> Result<AttackResult, String> {
+    let recyclers_table = ctx.db.recycler();
+    let mut recycler: Recycler = recyclers_table.id().find(recycler_id)
+        .ok_or_else(|| format!("Target recycler {} disappeared", recycler_id))?;
 
-    if target_player.is_dead {
-        log::debug!("Target player {:?} is already dead. No damage applied.", target_id);
-        return Ok(AttackResult { hit: false, target_type: Some(TargetType::Player), resource_granted: None });
+    if recycler.is_destroyed {
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::Recycler), resource_granted: None });
     }
 
-    let mut final_damage = damage; // Start with the damage passed in (already calculated from weapon stats)
-
-    // <<< APPLY ARMOR RESISTANCE >>>
-    let resistance = armor::calculate_total_damage_resistance(ctx, target_id);
-    if resistance > 0.0 {
-        let damage_reduction = final_damage * resistance;
-        let resisted_damage = final_damage - damage_reduction;
-        
-        log::info!(
-            "Player {:?} attacking Player {:?}. Initial Damage: {:.2}, Resistance: {:.2} ({:.0}%), Final Damage after resistance: {:.2}",
-            attacker_id, target_id, 
-            final_damage, // Log the damage before resistance
-            resistance,
-            resistance * 100.0,
-            resisted_damage.max(0.0)
-        );
-        final_damage = resisted_damage.max(0.0); // Damage cannot be negative
+    if hit_structure(attacker_id, "Recycler", recycler_id, &mut recycler.health, &mut recycler.last_hit_time, damage, timestamp) {
+        scatter_container_items(ctx, recycler.pos_x, recycler.pos_y, &mut recycler, 30.0, rng);
+        recyclers_table.id().delete(recycler_id);
+        finish_structure_destroyed(ctx, attacker_id, "Recycler", recycler_id, recycler.pos_x, recycler.pos_y, crate::spatial_grid::EntityType::Recycler(recycler_id));
     } else {
-        log::info!(
-            "Player {:?} attacking Player {:?}. Initial Damage: {:.2} (No resistance). Final Damage: {:.2}",
-            attacker_id, target_id, 
-            final_damage, 
-            final_damage
-        );
+        recyclers_table.id().update(recycler);
     }
-    // <<< END APPLY ARMOR RESISTANCE >>>
 
-    // A "hit" has occurred. Set last_hit_time immediately for client visuals.
-    target_player.last_hit_time = Some(timestamp);
+    Ok(AttackResult {
+        hit: true,
+        target_type: Some(TargetType::Recycler),
+        resource_granted: None,
+    })
+}
 
-    let old_health = target_player.health;
-    target_player.health = (target_player.health - final_damage).clamp(0.0, MAX_STAT_VALUE);
-    let actual_damage_applied = old_health - target_player.health; // This is essentially final_damage clamped by remaining health
+/// Applies damage to a flame turret and handles destruction/item scattering
+pub fn damage_flame_turret(
+    ctx: &ReducerContext,
+    attacker_id: Identity,
+    turret_id: u32,
+    damage: f32,
+    timestamp: Timestamp,
+    rng: &mut impl Rng
+) -> Result<AttackResult, String> {
+    let turrets_table = ctx.db.flame_turret();
+    let mut turret: FlameTurret = turrets_table.id().find(turret_id)
+        .ok_or_else(|| format!("Target flame turret {} disappeared", turret_id))?;
 
-    // --- APPLY KNOCKBACK and update timestamp if damage was dealt ---
-    if actual_damage_applied > 0.0 { // Only apply knockback and update timestamp if actual damage occurred
-        target_player.last_update = timestamp; // Update target's timestamp due to health change and potential knockback
+    if turret.is_destroyed {
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::FlameTurret), resource_granted: None });
+    }
 
-        if let Some(mut attacker) = attacker_player_opt.clone() { // Clone attacker_player_opt to get a mutable attacker if needed
-            let dx_target_from_attacker = target_player.position_x - attacker.position_x;
-            let dy_target_from_attacker = target_player.position_y - attacker.position_y;
-            let distance_sq = dx_target_from_attacker * dx_target_from_attacker + dy_target_from_attacker * dy_target_from_attacker;
+    if hit_structure(attacker_id, "Flame Turret", turret_id, &mut turret.health, &mut turret.last_hit_time, damage, timestamp) {
+        scatter_container_items(ctx, turret.pos_x, turret.pos_y, &mut turret, 30.0, rng);
+        turrets_table.id().delete(turret_id);
+        finish_structure_destroyed(ctx, attacker_id, "Flame Turret", turret_id, turret.pos_x, turret.pos_y, crate::spatial_grid::EntityType::FlameTurret(turret_id));
+    } else {
+        turrets_table.id().update(turret);
+    }
 
-            if distance_sq > 0.001 { // Avoid division by zero or tiny distances
-                let distance = distance_sq.sqrt();
-                // Knockback for Target
-                let knockback_dx_target = (dx_target_from_attacker / distance) * PVP_KNOCKBACK_DISTANCE;
-                let knockback_dy_target = (dy_target_from_attacker / distance) * PVP_KNOCKBACK_DISTANCE;
-                
-                let current_target_x = target_player.position_x;
-                let current_target_y = target_player.position_y;
-                let proposed_target_x = current_target_x + knockback_dx_target;
-                let proposed_target_y = current_target_y + knockback_dy_target;
+    Ok(AttackResult {
+        hit: true,
+        target_type: Some(TargetType::FlameTurret),
+        resource_granted: None,
+    })
+}
 
-                let (final_target_x, final_target_y) = resolve_knockback_collision(
-                    ctx,
-                    target_player.identity,
-                    current_target_x,
-                    current_target_y,
-                    proposed_target_x,
-                    proposed_target_y,
-                );
-                target_player.position_x = final_target_x;
-                target_player.position_y = final_target_y;
-                log::debug!("Applied knockback to target player {:?}: new pos ({:.1}, {:.1})", 
-                    target_id, target_player.position_x, target_player.position_y);
+/// Harvests a fresh corpse for bones
+///
+/// Each hit grants Bone Fragments and strips flesh; once none is left the corpse
+/// decays into a backpack early. Backpacks can no longer be harvested.
+pub fn harvest_player_corpse(
+    ctx: &ReducerContext,
+    attacker_id: Identity,
+    corpse_id: u32,
+    damage: f32,
+    yield_amount: u32,
+    resource_name_to_grant: &str,
+) -> Result<AttackResult, String> {
+    let mut corpse = ctx.db.player_corpse().id().find(corpse_id)
+        .ok_or_else(|| format!("Target corpse {} disappeared", corpse_id))?;
 
-                // Knockback for Attacker (recoil)
-                let attacker_recoil_distance = PVP_KNOCKBACK_DISTANCE / 3.0; // Example: attacker recoils less
-                let knockback_dx_attacker = (-dx_target_from_attacker / distance) * attacker_recoil_distance; // Opposite direction
-                let knockback_dy_attacker = (-dy_target_from_attacker / distance) * attacker_recoil_distance; // Opposite direction
-                
-                let current_attacker_x = attacker.position_x;
-                let current_attacker_y = attacker.position_y;
-                let proposed_attacker_x = current_attacker_x + knockback_dx_attacker;
-                let proposed_attacker_y = current_attacker_y + knockback_dy_attacker;
+    if corpse.stage != CorpseStage::Fresh || damage <= 0.0 {
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::PlayerCorpse), resource_granted: None });
+    }
+    // Butchering spills the corpse's items, so it waits out the killer's loot window too
+    if !player_corpse::can_loot(ctx, &corpse, attacker_id, ctx.timestamp) {
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::PlayerCorpse), resource_granted: None });
+    }
 
-                let (final_attacker_x, final_attacker_y) = resolve_knockback_collision(
-                    ctx,
-                    attacker.identity,
-                    current_attacker_x,
-                    current_attacker_y,
-                    proposed_attacker_x,
-                    proposed_attacker_y,
-                );
-                attacker.position_x = final_attacker_x;
-                attacker.position_y = final_attacker_y;
-                attacker.last_update = timestamp; // Update attacker's timestamp as their position changed
-                players.identity().update(attacker.clone()); // Update attacker player in DB
-                 log::debug!("Applied recoil to attacking player {:?}: new pos ({:.1}, {:.1})", 
-                    attacker_id, attacker.position_x, attacker.position_y);
-            }
+    corpse.flesh_remaining = (corpse.flesh_remaining - damage).max(0.0);
+    log::info!("Player {:?} harvested Corpse {} for {:.1}. Flesh remaining: {:.1}", attacker_id, corpse_id, damage, corpse.flesh_remaining);
+
+    let mut resource_granted = None;
+    if yield_amount > 0 {
+        match grant_resource(ctx, attacker_id, resource_name_to_grant, yield_amount) {
+            Ok(()) => resource_granted = Some((resource_name_to_grant.to_string(), yield_amount)),
+            Err(e) => log::error!("Failed to grant {} to player {:?}: {}", resource_name_to_grant, attacker_id, e),
         }
     }
-    // --- END KNOCKBACK ---
 
-    let killed = target_player.health <= 0.0;
+    if corpse.flesh_remaining <= 0.0 {
+        player_corpse::convert_corpse_to_backpack(ctx, &mut corpse);
+    }
+    ctx.db.player_corpse().id().update(corpse);
+
+    Ok(AttackResult {
+        hit: true,
+        target_type: Some(TargetType::PlayerCorpse),
+        resource_granted,
+    })
+}
+
+/// Applies damage to a wild animal. Killed animals are removed and drop their loot.
+pub fn damage_animal(
+    ctx: &ReducerContext,
+    attacker_id: Identity,
+    animal_id: u64,
+    damage: f32,
+    timestamp: Timestamp,
+    rng: &mut impl Rng
+) -> Result<AttackResult, String> {
+    let animals_table = ctx.db.wild_animal();
+    let mut animal = animals_table.id().find(animal_id)
+        .ok_or_else(|| format!("Target animal {} disappeared", animal_id))?;
+
+    let old_health = animal.health;
+    animal.health = (animal.health - damage).max(0.0);
+    animal.last_hit_time = Some(timestamp);
 
     log::info!(
-        "Player {:?} damaged Player {:?} for {:.2} (raw: {:.2}) with {}. Health: {:.2} -> {:.2}",
-        attacker_id, target_id, actual_damage_applied, damage, item_def.name, old_health, target_player.health
+        "Player {:?} hit {:?} {} for {:.1} damage. Health: {:.1} -> {:.1}",
+        attacker_id, animal.species, animal_id, damage, old_health, animal.health
     );
 
-    // Log the item_name and item_def_id being checked for bleed application
-    // let item_def_id_for_bleed_check = ctx.db.item_definition().iter().find(|def| def.name == item_name).map_or(0, |def| def.id);
-    log::info!("[BleedCheck] Item used: '{}' (Def ID: {}). Checking if it should apply bleed based on its definition.", item_def.name, item_def.id);
-
-    // Apply bleed effect if the weapon has bleed damage defined in its properties
-    if let (Some(dmg_per_tick), Some(duration_sec), Some(interval_sec)) = (
-        item_def.bleed_damage_per_tick, 
-        item_def.bleed_duration_seconds, 
-        item_def.bleed_tick_interval_seconds
-    ) {
-        if dmg_per_tick > 0.0 && duration_sec > 0.0 && interval_sec > 0.0 {
-            log::info!(
-                "[BleedCheck] Item '{}' (Def ID: {}) has positive bleed properties (Dmg: {}, Dur: {}, Int: {}). Attempting to apply bleed effect to player {:?}.", 
-                item_def.name, item_def.id, dmg_per_tick, duration_sec, interval_sec, target_id
-            );
-            
-            let total_ticks = (duration_sec / interval_sec).floor();
-            let bleed_total_damage = dmg_per_tick * total_ticks;
-
-            let time_until_next_tick = TimeDuration::from_micros((interval_sec * 1_000_000.0) as i64);
-
-            let bleed_effect = ActiveConsumableEffect {
-                effect_id: 0, 
-                player_id: target_id,
-                item_def_id: item_def.id, // Store the ID of the item causing the bleed
-                consuming_item_instance_id: None, 
-                started_at: timestamp,
-                ends_at: timestamp + TimeDuration::from_micros((duration_sec * 1_000_000.0) as i64),
-                total_amount: Some(bleed_total_damage), // Total potential damage
-                amount_applied_so_far: Some(0.0),
-                effect_type: EffectType::Bleed,
-                tick_interval_micros: (interval_sec * 1_000_000.0) as u64,
-                next_tick_at: timestamp + time_until_next_tick,
-            };
-            match ctx.db.active_consumable_effect().try_insert(bleed_effect) {
-                Ok(inserted_effect) => {
-                    log::info!(
-                        "Successfully applied bleed effect with ID {} to player {:?} from item '{}'",
-                        inserted_effect.effect_id, 
-                        target_id,
-                        item_def.name
-                    );
-                }
-                Err(e) => {
-                    log::error!("Failed to apply bleed effect to player {:?} from item '{}': {:?}", target_id, item_def.name, e);
-                }
+    if animal.health <= 0.0 {
+        animals_table.id().delete(animal_id);
+        crate::spatial_grid::untrack_entity(ctx, crate::spatial_grid::EntityType::Animal(animal_id));
+        log::info!("{:?} {} killed by player {:?}. Dropping loot.", animal.species, animal_id, attacker_id);
+        if animal.species == AnimalSpecies::Raider {
+            if let Err(e) = loot_crate::spawn_loot_crate(ctx, LootCrateType::RaiderCrate, animal.pos_x, animal.pos_y) {
+                log::error!("Failed to drop crate for raider {}: {}", animal_id, e);
             }
         } else {
-            log::info!("[BleedCheck] Item '{}' has bleed properties, but one or more are zero. Not applying bleed.", item_def.name);
+            animals::drop_animal_loot(ctx, &animal, rng);
         }
     } else {
-        log::info!("[BleedCheck] Item '{}' does not have all necessary bleed properties defined. Not applying bleed.", item_def.name);
-    }
-
-    // INTERRUPT BANDAGE IF DAMAGED
-    active_effects::cancel_bandage_burst_effects(ctx, target_id);
-
-    if killed {
-        target_player.is_dead = true;
-        target_player.death_timestamp = Some(timestamp);
-        // last_update and last_hit_time are already set from the initial hit registration.
-        // No need to set them again here unless there's a specific reason for death to override.
-        // Keeping them as set at the start of the hit interaction is consistent.
-
-        match crate::active_equipment::clear_active_item_reducer(ctx, target_player.identity) {
-            Ok(_) => log::info!("[PlayerDeath] Active item cleared for dying player {}", target_player.identity),
-            Err(e) => log::error!("[PlayerDeath] Failed to clear active item for dying player {}: {}", target_player.identity, e),
-        }
-
-        match create_player_corpse(ctx, target_player.identity, target_player.position_x, target_player.position_y, &target_player.username) {
-            Ok(_) => {
-                log::info!("Successfully created corpse via combat death for player {:?}", target_id);
-                if let Some(active_equip) = ctx.db.active_equipment().player_identity().find(&target_id) {
-                    if active_equip.equipped_item_instance_id.is_some() {
-                        match crate::active_equipment::clear_active_item_reducer(ctx, target_id) {
-                            Ok(_) => log::info!("[CombatDeath] Active item cleared for target {}", target_id),
-                            Err(e) => log::error!("[CombatDeath] Failed to clear active item for target {}: {}", target_id, e),
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                log::error!("Failed to create corpse via combat death for player {:?}: {}", target_id, e);
-            }
-        }
-        players.identity().update(target_player.clone());
-        log::info!("Player {:?} marked as dead.", target_id);
-
-    } else if target_player.health > 0.0 {
-        // Player is alive. last_hit_time and last_update were set at the beginning.
-        // Simply update the player state with new health etc.
-        players.identity().update(target_player);
+        animals::react_to_attack(&mut animal, attacker_id, timestamp);
+        animals_table.id().update(animal);
     }
 
     Ok(AttackResult {
         hit: true,
-        target_type: Some(TargetType::Player),
+        target_type: Some(TargetType::Animal),
         resource_granted: None,
     })
 }
 
-/// Applies damage to a campfire and handles destruction/item scattering
-pub fn damage_campfire(
+/// Applies damage to a loot crate. Broken crates scatter their loot; world crates are
+/// scheduled to respawn while event crates are removed.
+pub fn damage_loot_crate(
     ctx: &ReducerContext,
     attacker_id: Identity,
-    campfire_id: u32,
+    crate_id: u64,
     damage: f32,
     timestamp: Timestamp,
-    rng: &mut impl Rng // Added RNG for item scattering
+    rng: &mut impl Rng
 ) -> Result<AttackResult, String> {
-    let mut campfires_table = ctx.db.campfire();
-    let mut campfire = campfires_table.id().find(campfire_id)
-        .ok_or_else(|| format!("Target campfire {} disappeared", campfire_id))?;
+    let crates_table = ctx.db.loot_crate();
+    let mut loot_crate = crates_table.id().find(crate_id)
+        .ok_or_else(|| format!("Target loot crate {} disappeared", crate_id))?;
 
-    if campfire.is_destroyed {
-        return Ok(AttackResult { hit: false, target_type: Some(TargetType::Campfire), resource_granted: None });
+    if loot_crate.health <= 0.0 {
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::LootCrate), resource_granted: None });
     }
 
-    let old_health = campfire.health;
-    campfire.health = (campfire.health - damage).max(0.0);
-    campfire.last_hit_time = Some(timestamp);
+    let old_health = loot_crate.health;
+    loot_crate.health = (loot_crate.health - damage).max(0.0);
+    loot_crate.last_hit_time = Some(timestamp);
 
     log::info!(
-        "Player {:?} hit Campfire {} for {:.1} damage. Health: {:.1} -> {:.1}",
-        attacker_id, campfire_id, damage, old_health, campfire.health
+        "Player {:?} hit {:?} {} for {:.1} damage. Health: {:.1} -> {:.1}",
+        attacker_id, loot_crate.crate_type, crate_id, damage, old_health, loot_crate.health
     );
 
-    if campfire.health <= 0.0 {
-        campfire.is_destroyed = true;
-        campfire.destroyed_at = Some(timestamp);
-        // Scatter items
-        let mut items_to_drop: Vec<(u64, u32)> = Vec::new(); // (item_def_id, quantity)
-        for i in 0..crate::campfire::NUM_FUEL_SLOTS {
-            if let (Some(instance_id), Some(def_id)) = (campfire.get_slot_instance_id(i as u8), campfire.get_slot_def_id(i as u8)) {
-                if let Some(item) = ctx.db.inventory_item().instance_id().find(instance_id) {
-                    items_to_drop.push((def_id, item.quantity));
-                    // Delete the InventoryItem from the central table
-                    ctx.db.inventory_item().instance_id().delete(instance_id);
-                }
-                campfire.set_slot(i as u8, None, None); // Clear slot in campfire struct (though it's about to be deleted)
-            }
-        }
-
-        // Update the campfire one last time to ensure is_destroyed and destroyed_at are sent to client
-        campfires_table.id().update(campfire.clone()); 
-        // Then immediately delete the campfire entity itself
-        campfires_table.id().delete(campfire_id);
-
-        log::info!(
-            "Campfire {} destroyed by player {:?}. Dropping items.",
-            campfire_id, attacker_id
-        );
-
-        // Scatter collected items around the campfire's location
-        for (item_def_id, quantity) in items_to_drop {
-            // Spawn slightly offset from campfire center
-            let offset_x = (rng.gen::<f32>() - 0.5) * 2.0 * 20.0; // Spread within +/- 20px
-            let offset_y = (rng.gen::<f32>() - 0.5) * 2.0 * 20.0;
-            let drop_pos_x = campfire.pos_x + offset_x;
-            let drop_pos_y = campfire.pos_y + offset_y;
-
-            match dropped_item::create_dropped_item_entity(ctx, item_def_id, quantity, drop_pos_x, drop_pos_y) {
-                Ok(_) => log::debug!("Dropped {} of item_def_id {} from destroyed campfire {}", quantity, item_def_id, campfire_id),
-                Err(e) => log::error!("Failed to drop item_def_id {}: {}", item_def_id, e),
-            }
+    if loot_crate.health <= 0.0 {
+        log::info!("{:?} {} broken by player {:?}. Dropping loot.", loot_crate.crate_type, crate_id, attacker_id);
+        loot_crate::drop_crate_loot(ctx, &loot_crate, rng);
+        if loot_crate.crate_type.respawns() {
+            let respawn_duration_secs = rng.gen_range(MIN_LOOT_CRATE_RESPAWN_TIME_SECS..=MAX_LOOT_CRATE_RESPAWN_TIME_SECS);
+            loot_crate.respawn_at = Some(timestamp + spacetimedb::TimeDuration::from(Duration::from_secs(respawn_duration_secs)));
+            crates_table.id().update(loot_crate);
+        } else {
+            crates_table.id().delete(crate_id);
+            crate::spatial_grid::untrack_entity(ctx, crate::spatial_grid::EntityType::LootCrate(crate_id));
         }
-
     } else {
-        // Campfire still has health, just update it
-        campfires_table.id().update(campfire);
+        crates_table.id().update(loot_crate);
     }
 
     Ok(AttackResult {
         hit: true,
-        target_type: Some(TargetType::Campfire),
+        target_type: Some(TargetType::LootCrate),
         resource_granted: None,
     })
 }
@@ -933,6 +2208,7 @@ pub fn damage_wooden_storage_box(
     if wooden_box.health <= 0.0 {
         wooden_box.is_destroyed = true;
         wooden_box.destroyed_at = Some(timestamp);
+        crate::world_event::emit_structure_destroyed(ctx, "Wooden Storage Box", wooden_box.pos_x, wooden_box.pos_y, attacker_id);
 
         let mut items_to_drop: Vec<(u64, u32)> = Vec::new();
         for i in 0..crate::wooden_storage_box::NUM_BOX_SLOTS {
@@ -949,6 +2225,9 @@ pub fn damage_wooden_storage_box(
         boxes_table.id().update(wooden_box.clone());
         // Then immediately delete the box entity itself
         boxes_table.id().delete(box_id);
+        crate::spatial_grid::untrack_entity(ctx, crate::spatial_grid::EntityType::WoodenStorageBox(box_id));
+        crate::locks::remove_locks_for_target(ctx, crate::locks::LockTargetType::WoodenStorageBox, box_id);
+        crate::container_access::remove_container_access(ctx, crate::models::ContainerType::WoodenStorageBox, box_id);
 
         log::info!(
             "WoodenStorageBox {} destroyed by player {:?}. Dropping contents.",
@@ -1013,6 +2292,7 @@ pub fn damage_stash(
     if stash.health <= 0.0 {
         stash.is_destroyed = true;
         stash.destroyed_at = Some(timestamp);
+        crate::world_event::emit_structure_destroyed(ctx, "Stash", stash.pos_x, stash.pos_y, attacker_id);
 
         let mut items_to_drop: Vec<(u64, u32)> = Vec::new();
         for i in 0..crate::stash::NUM_STASH_SLOTS { // Use NUM_STASH_SLOTS
@@ -1027,6 +2307,8 @@ pub fn damage_stash(
         
         stashes_table.id().update(stash.clone());
         stashes_table.id().delete(stash_id);
+        spatial_grid::untrack_entity(ctx, spatial_grid::EntityType::Stash(stash_id));
+        crate::container_access::remove_container_access(ctx, crate::models::ContainerType::Stash, stash_id);
 
         log::info!(
             "Stash {} destroyed by player {:?}. Dropping contents.",
@@ -1084,9 +2366,11 @@ pub fn damage_sleeping_bag(
     if bag.health <= 0.0 {
         bag.is_destroyed = true;
         bag.destroyed_at = Some(timestamp);
+        crate::world_event::emit_structure_destroyed(ctx, "Sleeping Bag", bag.pos_x, bag.pos_y, attacker_id);
         
         bags_table.id().update(bag.clone()); 
         bags_table.id().delete(bag_id);
+        spatial_grid::untrack_entity(ctx, spatial_grid::EntityType::SleepingBag(bag_id));
 
         log::info!(
             "SleepingBag {} destroyed by player {:?}.",
@@ -1103,29 +2387,99 @@ pub fn damage_sleeping_bag(
     })
 }
 
+/// Applies damage to a building piece (wall, door, foundation) and handles destruction
+pub fn damage_building_piece(
+    ctx: &ReducerContext,
+    attacker_id: Identity,
+    piece_id: u32,
+    damage: f32,
+    timestamp: Timestamp,
+) -> Result<AttackResult, String> {
+    let pieces_table = ctx.db.building_piece();
+    let mut piece = pieces_table.id().find(piece_id)
+        .ok_or_else(|| format!("Target building piece {} disappeared", piece_id))?;
+
+    if piece.is_destroyed {
+        return Ok(AttackResult { hit: false, target_type: Some(TargetType::BuildingPiece), resource_granted: None });
+    }
+
+    let old_health = piece.health;
+    piece.health = (piece.health - damage).max(0.0);
+    piece.last_hit_time = Some(timestamp);
+
+    log::info!(
+        "Player {:?} hit {:?} {} for {:.1} damage. Health: {:.1} -> {:.1}",
+        attacker_id, piece.piece_type, piece_id, damage, old_health, piece.health
+    );
+
+    if piece.health <= 0.0 {
+        piece.is_destroyed = true;
+        piece.destroyed_at = Some(timestamp);
+        crate::world_event::emit_structure_destroyed(ctx, &format!("{:?}", piece.piece_type), piece.pos_x, piece.pos_y, attacker_id);
+
+        pieces_table.id().update(piece.clone());
+        pieces_table.id().delete(piece_id);
+        crate::spatial_grid::untrack_entity(ctx, crate::spatial_grid::EntityType::BuildingPiece(piece_id));
+        crate::locks::remove_locks_for_target(ctx, crate::locks::LockTargetType::Door, piece_id);
+
+        log::info!(
+            "{:?} {} destroyed by player {:?}.",
+            piece.piece_type, piece_id, attacker_id
+        );
+    } else {
+        pieces_table.id().update(piece);
+    }
+
+    Ok(AttackResult {
+        hit: true,
+        target_type: Some(TargetType::BuildingPiece),
+        resource_granted: None,
+    })
+}
+
 /// Processes an attack against a target
 ///
 /// Main entry point for weapon damage application. Handles different target types
-/// and applies appropriate damage and effects.
+/// and applies appropriate damage and effects. Heavy swings multiply the damage and yield
+/// before they reach the per-target damage functions.
 pub fn process_attack(
     ctx: &ReducerContext,
     attacker_id: Identity,
     target: &Target,
     item_def: &ItemDefinition,
+    swing: Swing,
     timestamp: Timestamp,
     rng: &mut impl Rng
 ) -> Result<AttackResult, String> {
-    let (damage, yield_amount, resource_name) = calculate_damage_and_yield(item_def, target.target_type, rng);
+    let yield_bonus = skills::get_yield_bonus(ctx, attacker_id, target.target_type);
+    let richness = match &target.id {
+        TargetId::Tree(tree_id) => ctx.db.tree().id().find(*tree_id).map(|t| t.richness),
+        TargetId::Stone(stone_id) => ctx.db.stone().id().find(*stone_id).map(|s| s.richness),
+        _ => None,
+    }.unwrap_or(ResourceRichness::Normal);
+    // Stumps are chopped with the same tools and yields as trees
+    let yield_target_type = if target.target_type == TargetType::TreeStump { TargetType::Tree } else { target.target_type };
+    let (mut damage, mut yield_amount, resource_name) = calculate_damage_and_yield(ctx, item_def, yield_target_type, yield_bonus, richness, rng);
+    if swing.heavy {
+        damage *= HEAVY_ATTACK_DAMAGE_MULTIPLIER;
+        yield_amount = (yield_amount as f32 * HEAVY_ATTACK_YIELD_MULTIPLIER).round() as u32;
+    }
+    // Going hungry, thirsty or cold weakens every swing (see player_stats::StatThresholdTier)
+    damage *= crate::player_stats::get_need_damage_multiplier(ctx, attacker_id);
+    let is_combat_target = matches!(target.target_type, TargetType::Player | TargetType::Animal | TargetType::SleepingPlayer);
+    if is_combat_target {
+        damage *= skills::get_combat_damage_multiplier(ctx, attacker_id);
+    }
 
-    match &target.id {
+    let result = match &target.id {
         TargetId::Tree(tree_id) => {
-            damage_tree(ctx, attacker_id, *tree_id, damage, yield_amount, &resource_name, timestamp, rng)
+            damage_tree(ctx, attacker_id, *tree_id, damage, yield_amount, &resource_name, swing.hit_offset, timestamp, rng)
         },
         TargetId::Stone(stone_id) => {
-            damage_stone(ctx, attacker_id, *stone_id, damage, yield_amount, &resource_name, timestamp, rng)
+            damage_stone(ctx, attacker_id, *stone_id, damage, yield_amount, &resource_name, swing.hit_offset, timestamp, rng)
         },
         TargetId::Player(player_id) => {
-            damage_player(ctx, attacker_id, *player_id, damage, item_def, timestamp)
+            damage_player(ctx, attacker_id, *player_id, damage, item_def, DamageType::Melee, timestamp)
         },
         TargetId::Campfire(campfire_id) => {
             damage_campfire(ctx, attacker_id, *campfire_id, damage, timestamp, rng)
@@ -1139,11 +2493,119 @@ pub fn process_attack(
         TargetId::SleepingBag(bag_id) => {
             damage_sleeping_bag(ctx, attacker_id, *bag_id, damage, timestamp, rng)
         },
+        TargetId::BuildingPiece(piece_id) => {
+            damage_building_piece(ctx, attacker_id, *piece_id, damage, timestamp)
+        },
+        TargetId::Furnace(furnace_id) => {
+            damage_furnace(ctx, attacker_id, *furnace_id, damage, timestamp, rng)
+        },
+        TargetId::Animal(animal_id) => {
+            damage_animal(ctx, attacker_id, *animal_id, damage, timestamp, rng)
+        },
+        TargetId::LargeStorageChest(chest_id) => {
+            damage_large_storage_chest(ctx, attacker_id, *chest_id, damage, timestamp, rng)
+        },
+        TargetId::SleepingPlayer(sleeper_id) => {
+            damage_sleeping_player(ctx, attacker_id, *sleeper_id, damage, DamageType::Melee, timestamp)
+        },
+        TargetId::LootCrate(crate_id) => {
+            damage_loot_crate(ctx, attacker_id, *crate_id, damage, timestamp, rng)
+        },
+        TargetId::Workbench(workbench_id) => {
+            damage_workbench(ctx, attacker_id, *workbench_id, damage, timestamp)
+        },
+        TargetId::Recycler(recycler_id) => {
+            damage_recycler(ctx, attacker_id, *recycler_id, damage, timestamp, rng)
+        },
+        TargetId::FlameTurret(turret_id) => {
+            damage_flame_turret(ctx, attacker_id, *turret_id, damage, timestamp, rng)
+        },
+        TargetId::PlayerCorpse(corpse_id) => {
+            harvest_player_corpse(ctx, attacker_id, *corpse_id, damage, yield_amount, &resource_name)
+        },
+        TargetId::Sign(sign_id) => {
+            damage_sign(ctx, attacker_id, *sign_id, damage, timestamp)
+        },
+        TargetId::TreeStump(stump_id) => {
+            damage_tree_stump(ctx, attacker_id, *stump_id, damage, yield_amount, &resource_name, timestamp)
+        },
+        TargetId::Mount(mount_id) => {
+            damage_mount(ctx, attacker_id, *mount_id, damage, timestamp)
+        },
+        TargetId::Switch(switch_id) => {
+            damage_switch(ctx, attacker_id, *switch_id, damage, timestamp)
+        },
+        TargetId::EnvironmentHazard(hazard_id) => {
+            damage_environment_hazard(ctx, attacker_id, *hazard_id, damage, yield_amount, timestamp, rng)
+        },
+    };
+
+    // Award skill XP for successful hits
+    if let Ok(attack_result) = &result {
+        if let Some(skill) = skills::gathering_skill_for_target(target.target_type) {
+            let gathered = attack_result.resource_granted.as_ref().map_or(0, |(_, amount)| *amount);
+            skills::award_xp(ctx, attacker_id, skill, gathered as u64 * skills::XP_PER_RESOURCE_GATHERED);
+            if let Some((resource_name, amount)) = &attack_result.resource_granted {
+                crate::quests::record_progress(ctx, attacker_id, QuestObjective::Gather, resource_name, *amount);
+            }
+        } else if is_combat_target && attack_result.hit {
+            skills::award_xp(ctx, attacker_id, SkillType::Combat, skills::XP_PER_COMBAT_HIT);
+        }
+    }
+    result
+}
+
+// --- Durability Functions ---
+
+/// Returns true if the item instance has worn down to zero durability.
+/// Items whose definition has no max durability never break.
+pub fn is_item_broken(item: &crate::items::InventoryItem, item_def: &ItemDefinition) -> bool {
+    item_def.max_durability.is_some() && item.durability.is_some_and(|d| d <= 0.0)
+}
+
+/// Wears down the swung item by `DURABILITY_LOSS_PER_SWING`.
+///
+/// A `None` durability means the item is pristine, so the first swing starts from the
+/// definition's max durability. Returns true if the item broke on this swing.
+pub fn apply_durability_loss(ctx: &ReducerContext, item_instance_id: u64, item_def: &ItemDefinition) -> bool {
+    let max_durability = match item_def.max_durability {
+        Some(max) => max,
+        None => return false, // Item doesn't wear out
+    };
+    let inventory_items = ctx.db.inventory_item();
+    let mut item = match inventory_items.instance_id().find(item_instance_id) {
+        Some(item) => item,
+        None => return false,
+    };
+
+    let old_durability = item.durability.unwrap_or(max_durability);
+    let new_durability = (old_durability - crate::game_config::get_game_config(ctx).durability_loss_per_swing).max(0.0);
+    item.durability = Some(new_durability);
+    inventory_items.instance_id().update(item);
+
+    let broke = old_durability > 0.0 && new_durability <= 0.0;
+    if broke {
+        log::info!("[Durability] {} (Instance: {}) broke.", item_def.name, item_instance_id);
+    }
+    broke
+}
+
+/// Displaces a player by (dx, dy), pushing them out of anything they would end up
+/// overlapping with the same resolution used for movement. The move is split into
+/// steps no longer than a player radius so a hit can't knock anyone through a wall.
+fn apply_knockback(ctx: &ReducerContext, player_id: Identity, x: f32, y: f32, dx: f32, dy: f32) -> (f32, f32) {
+    let distance = (dx * dx + dy * dy).sqrt();
+    let steps = (distance / PLAYER_RADIUS).ceil().max(1.0) as u32;
+    let (step_x, step_y) = (dx / steps as f32, dy / steps as f32);
+    let (mut x, mut y) = (x, y);
+    for _ in 0..steps {
+        (x, y) = crate::resolve_player_overlaps(ctx, player_id, x + step_x, y + step_y);
     }
+    (x, y)
 }
 
 // --- NEW Helper function for knockback collision resolution ---
-fn resolve_knockback_collision(
+pub(crate) fn resolve_knockback_collision(
     ctx: &ReducerContext,
     colliding_player_id: Identity, // The player being knocked back
     current_x: f32,
@@ -1225,6 +2687,20 @@ fn resolve_knockback_collision(
         }
     }
 
+    // Check against Furnaces
+    for furnace in ctx.db.furnace().iter() {
+        if furnace.is_destroyed { continue; }
+        let furnace_collision_center_y = furnace.pos_y - FURNACE_COLLISION_Y_OFFSET;
+        let dx = proposed_x - furnace.pos_x;
+        let dy = proposed_y - furnace_collision_center_y;
+        let player_furnace_collision_dist_sq = (PLAYER_RADIUS + FURNACE_COLLISION_RADIUS) * (PLAYER_RADIUS + FURNACE_COLLISION_RADIUS);
+        if (dx * dx + dy * dy) < player_furnace_collision_dist_sq {
+            log::debug!("[KnockbackCollision] Player ID {:?} would collide with Furnace ID {} at proposed ({:.1}, {:.1}). Reverting knockback.", 
+                       colliding_player_id, furnace.id, proposed_x, proposed_y);
+            return (current_x, current_y);
+        }
+    }
+
     // Check against SleepingBags
     for bag in ctx.db.sleeping_bag().iter() {
         if bag.is_destroyed { continue; }
@@ -1239,6 +2715,20 @@ fn resolve_knockback_collision(
         }
     }
     
+    // Check against solid building pieces (walls, closed doors)
+    for piece in ctx.db.building_piece().iter() {
+        if !piece.is_solid() { continue; }
+        let piece_collision_center_y = piece.pos_y - BUILDING_COLLISION_Y_OFFSET;
+        let dx = proposed_x - piece.pos_x;
+        let dy = proposed_y - piece_collision_center_y;
+        let min_dist = PLAYER_RADIUS + piece.collision_radius();
+        if (dx * dx + dy * dy) < min_dist * min_dist {
+            log::debug!("[KnockbackCollision] Player ID {:?} would collide with BuildingPiece ID {} at proposed ({:.1}, {:.1}). Reverting knockback.", 
+                       colliding_player_id, piece.id, proposed_x, proposed_y);
+            return (current_x, current_y);
+        }
+    }
+
     // Note: Stashes are typically not solid. Add collision check if their behavior changes.
 
     // If no collisions, return the (boundary-clamped) proposed position
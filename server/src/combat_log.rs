@@ -0,0 +1,203 @@
+/******************************************************************************
+ *                                                                            *
+ * Combat log for moderation. Every PvP hit that gets through (see            *
+ * combat.rs `damage_player`) is written to the private CombatLogEntry        *
+ * table and kept for COMBAT_LOG_RETENTION_SECS. Admins look a player up      *
+ * with `get_recent_combat`, which copies that player's recent hits into      *
+ * the public CombatLogResult table under the admin's identity, so kill       *
+ * complaints can be checked without exposing the whole log. Result rows are  *
+ * only visible to that admin and are pruned after a short while.             *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{client_visibility_filter, Filter, Identity, ReducerContext, Table, Timestamp, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+
+use crate::admin;
+use crate::Player;
+use crate::combat_log::combat_log as CombatLogTableTrait;
+use crate::combat_log::combat_log_result as CombatLogResultTableTrait;
+use crate::combat_log::combat_log_cleanup_schedule as CombatLogCleanupScheduleTableTrait;
+
+// --- Constants ---
+const COMBAT_LOG_CLEANUP_INTERVAL_SECS: u64 = 60;
+/// Hits older than this are removed from the log.
+const COMBAT_LOG_RETENTION_SECS: i64 = 24 * 60 * 60;
+/// Lookup results older than this are removed; admins can simply query again.
+const COMBAT_LOG_RESULT_RETENTION_SECS: i64 = 15 * 60;
+/// `get_recent_combat` returns at most this many hits, newest first.
+const MAX_COMBAT_LOOKUP_RESULTS: usize = 200;
+
+/// --- Combat Log Entry ---
+/// One PvP hit. Private; admins read it through `get_recent_combat`.
+#[spacetimedb::table(name = combat_log)]
+#[derive(Clone, Debug)]
+pub struct CombatLogEntry {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub attacker_id: Identity,
+    #[index(btree)]
+    pub victim_id: Identity,
+    pub weapon_name: String,
+    pub damage: f32, // Health actually removed, after armor
+    pub attacker_pos_x: f32,
+    pub attacker_pos_y: f32,
+    pub victim_pos_x: f32,
+    pub victim_pos_y: f32,
+    pub killed: bool,
+    pub hit_at: Timestamp,
+}
+
+/// --- Combat Log Result ---
+/// A copy of a log entry made for the admin who looked it up.
+/// Only visible to the admin who ran the lookup (see filter below).
+#[spacetimedb::table(name = combat_log_result, public)]
+#[derive(Clone, Debug)]
+pub struct CombatLogResult {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub admin_id: Identity,
+    pub queried_player_id: Identity,
+    pub entry: CombatLogEntry,
+    pub queried_at: Timestamp,
+}
+
+// --- Row-Level Security Filters ---
+
+/// Each admin only sees the lookups they ran themselves.
+#[client_visibility_filter]
+const COMBAT_LOG_RESULT_OWN_ROWS: Filter = Filter::Sql(
+    "SELECT * FROM combat_log_result WHERE admin_id = :sender"
+);
+
+/// --- Combat Log Cleanup Schedule ---
+#[spacetimedb::table(name = combat_log_cleanup_schedule, scheduled(prune_combat_log))]
+#[derive(Clone, Debug)]
+pub struct CombatLogCleanupSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/******************************************************************************
+ *                                 REDUCERS                                   *
+ ******************************************************************************/
+
+/// --- Get Recent Combat ---
+/// Admin only. Replaces the sender's CombatLogResult rows with the player's most
+/// recent hits, dealt or taken.
+#[spacetimedb::reducer]
+pub fn get_recent_combat(ctx: &ReducerContext, player_id: Identity) -> Result<(), String> {
+    let admin_id = ctx.sender;
+    if !admin::is_admin(ctx, admin_id) {
+        return Err("Only admins can read the combat log.".to_string());
+    }
+
+    let results = ctx.db.combat_log_result();
+    let previous: Vec<u64> = results.admin_id().filter(admin_id).map(|r| r.id).collect();
+    for result_id in previous {
+        results.id().delete(result_id);
+    }
+
+    let log_table = ctx.db.combat_log();
+    let mut entries: Vec<CombatLogEntry> = log_table.attacker_id().filter(player_id)
+        .chain(log_table.victim_id().filter(player_id).filter(|e| e.attacker_id != player_id))
+        .collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.hit_at));
+    entries.truncate(MAX_COMBAT_LOOKUP_RESULTS);
+
+    let count = entries.len();
+    for entry in entries {
+        results.insert(CombatLogResult {
+            id: 0, // Auto-incremented
+            admin_id,
+            queried_player_id: player_id,
+            entry,
+            queried_at: ctx.timestamp,
+        });
+    }
+    log::info!("[CombatLog] Admin {:?} looked up {} hits for player {:?}.", admin_id, count, player_id);
+    Ok(())
+}
+
+/******************************************************************************
+ *                           SCHEDULED REDUCERS                               *
+ ******************************************************************************/
+
+/// Starts the combat log cleanup schedule if it isn't already scheduled. Called from `init_module`.
+pub fn init_combat_log_cleanup_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.combat_log_cleanup_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting combat log cleanup schedule (every {}s).", COMBAT_LOG_CLEANUP_INTERVAL_SECS);
+        let interval = Duration::from_secs(COMBAT_LOG_CLEANUP_INTERVAL_SECS);
+        schedule_table.try_insert(CombatLogCleanupSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert combat log cleanup schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: removes hits older than COMBAT_LOG_RETENTION_SECS and lookup
+/// results older than COMBAT_LOG_RESULT_RETENTION_SECS.
+#[spacetimedb::reducer]
+pub fn prune_combat_log(ctx: &ReducerContext, _schedule: CombatLogCleanupSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("prune_combat_log may only be called by the scheduler.".to_string());
+    }
+
+    let log_table = ctx.db.combat_log();
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let max_age_micros = COMBAT_LOG_RETENTION_SECS * 1_000_000;
+    let expired: Vec<u64> = log_table.iter()
+        .filter(|e| now_micros - e.hit_at.to_micros_since_unix_epoch() > max_age_micros)
+        .map(|e| e.id)
+        .collect();
+    for entry_id in &expired {
+        log_table.id().delete(*entry_id);
+    }
+    if !expired.is_empty() {
+        log::debug!("[CombatLog] Pruned {} expired hits.", expired.len());
+    }
+
+    let results = ctx.db.combat_log_result();
+    let max_result_age_micros = COMBAT_LOG_RESULT_RETENTION_SECS * 1_000_000;
+    let expired_results: Vec<u64> = results.iter()
+        .filter(|r| now_micros - r.queried_at.to_micros_since_unix_epoch() > max_result_age_micros)
+        .map(|r| r.id)
+        .collect();
+    for result_id in &expired_results {
+        results.id().delete(*result_id);
+    }
+    if !expired_results.is_empty() {
+        log::debug!("[CombatLog] Pruned {} stale lookup results.", expired_results.len());
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Logs a PvP hit. `attacker` is None if the attacker has since disconnected and been removed.
+pub(crate) fn record_pvp_hit(ctx: &ReducerContext, attacker_id: Identity, attacker: Option<&Player>, victim: &Player, weapon_name: &str, damage: f32, killed: bool) {
+    ctx.db.combat_log().insert(CombatLogEntry {
+        id: 0, // Auto-incremented
+        attacker_id,
+        victim_id: victim.identity,
+        weapon_name: weapon_name.to_string(),
+        damage,
+        attacker_pos_x: attacker.map_or(0.0, |a| a.position_x),
+        attacker_pos_y: attacker.map_or(0.0, |a| a.position_y),
+        victim_pos_x: victim.position_x,
+        victim_pos_y: victim.position_y,
+        killed,
+        hit_at: ctx.timestamp,
+    });
+}
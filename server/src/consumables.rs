@@ -8,19 +8,63 @@ use crate::Player; // For the struct
 use crate::player; // For the table trait
 use crate::items::{InventoryItem, inventory_item as InventoryItemTableTrait};
 use crate::items::{ItemDefinition, item_definition as ItemDefinitionTableTrait};
-use crate::items::ItemCategory; // Import the enum itself
+use crate::items::{ItemCategory, FoodGroup, ConsumableCooldownCategory}; // Import the enums themselves
 use crate::models::ItemLocation; // Added import
 
 // Import active effects related items
-use crate::active_effects::{ActiveConsumableEffect, EffectType, active_consumable_effect as ActiveConsumableEffectTableTrait, cancel_bleed_effects, cancel_health_regen_effects};
+use crate::active_effects::{ActiveConsumableEffect, EffectType, active_consumable_effect as ActiveConsumableEffectTableTrait, cancel_health_regen_effects, cancel_well_fed_effects};
+use crate::active_effects::apply_bandage_burst;
+use crate::consumables::diet_entry as DietEntryTableTrait;
+use crate::consumables::consumable_cooldown as ConsumableCooldownTableTrait;
 
 // --- Max Stat Value ---
 pub const MAX_STAT_VALUE: f32 = 100.0; // Max value for health, hunger, thirst
 const MIN_STAT_VALUE: f32 = 0.0;   // Min value for stats like health
-const CONSUMPTION_COOLDOWN_MICROS: u64 = 1_000_000; // 1 second cooldown
+const CONSUMPTION_COOLDOWN_MICROS: u64 = 1_000_000; // 1 second cooldown for items without a cooldown category
+
+// --- Cooldown Categories ---
+const FOOD_COOLDOWN_MICROS: u64 = 1_000_000;
+const MEDICAL_COOLDOWN_MICROS: u64 = 8_000_000;
+const ELIXIR_COOLDOWN_MICROS: u64 = 30_000_000;
+
+// --- Nutrition ---
+const DIET_HISTORY_SIZE: usize = 6; // Most recent meals remembered per player
+const DIET_HISTORY_WINDOW_SECS: i64 = 20 * 60; // Meals older than this no longer count
+const WELL_FED_MIN_DISTINCT_FOODS: usize = 3;
+const WELL_FED_DURATION_SECS: f32 = 300.0;
+const WELL_FED_TOTAL_HEALTH_REGEN: f32 = 15.0; // Healed slowly over the whole duration
+
+/// One meal in a player's recent diet. Server-side only; used to reward varied eating.
+#[spacetimedb::table(name = diet_entry)]
+#[derive(Clone, Debug)]
+pub struct DietEntry {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub player_id: Identity,
+    pub item_def_id: u64,
+    pub food_group: FoodGroup,
+    pub is_cooked_food: bool,
+    pub eaten_at: Timestamp,
+}
+
+/// When a player last used a consumable of a cooldown category. One row per player and category.
+#[spacetimedb::table(name = consumable_cooldown)]
+#[derive(Clone, Debug)]
+pub struct ConsumableCooldown {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub player_id: Identity,
+    pub category: ConsumableCooldownCategory,
+    pub last_used_at: Timestamp,
+}
 
 #[spacetimedb::reducer]
 pub fn consume_item(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
     let sender_id = ctx.sender;
     let players_table = ctx.db.player();
     let item_defs = ctx.db.item_definition();
@@ -30,13 +74,6 @@ pub fn consume_item(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), S
     let mut player_to_update = players_table.identity().find(&sender_id)
         .ok_or_else(|| "Player not found.".to_string())?;
 
-    if let Some(last_consumed_ts) = player_to_update.last_consumed_at {
-        let cooldown_duration = TimeDuration::from_micros(CONSUMPTION_COOLDOWN_MICROS as i64);
-        if ctx.timestamp < last_consumed_ts + cooldown_duration {
-            return Err("You are consuming items too quickly.".to_string());
-        }
-    }
-
     let item_to_consume = ctx.db.inventory_item().instance_id().find(item_instance_id)
         .ok_or_else(|| format!("Item instance {} not found.", item_instance_id))?;
 
@@ -62,6 +99,11 @@ pub fn consume_item(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), S
         return Err(format!("Item '{}' is not a consumable category item and cannot be used this way.", item_def.name));
     }
 
+    // Medical items take time to use; their effects apply once the channel completes
+    if crate::channeled_use::is_channeled(&item_def) {
+        return crate::channeled_use::begin_channel(ctx, sender_id, item_instance_id, &item_def);
+    }
+
     // Call the centralized helper function
     apply_item_effects_and_consume(ctx, sender_id, &item_def, item_instance_id, &mut player_to_update)?;
 
@@ -74,30 +116,36 @@ pub fn consume_item(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), S
     
     if has_instant_effect {
         // Consume the item directly here since no timed effect will handle it
-        let mut item_to_consume = ctx.db.inventory_item().instance_id().find(item_instance_id)
-            .ok_or_else(|| format!("Item instance {} suddenly disappeared.", item_instance_id))?;
-            
-        // Decrease quantity
-        if item_to_consume.quantity > 0 {
-            item_to_consume.quantity -= 1;
-        }
-        
-        // Remove item if quantity is 0
-        if item_to_consume.quantity == 0 {
-            ctx.db.inventory_item().instance_id().delete(&item_instance_id);
-            log::info!("[ConsumeItem] Instantly consumed and deleted item_instance_id: {} for player {:?}.", 
-                item_instance_id, sender_id);
-        } else {
-            // Update with decreased quantity
-            ctx.db.inventory_item().instance_id().update(item_to_consume.clone());
-            log::info!("[ConsumeItem] Instantly consumed item_instance_id: {}, new quantity: {} for player {:?}.", 
-                item_instance_id, item_to_consume.quantity, sender_id);
-        }
+        consume_single_item(ctx, item_instance_id, sender_id)?;
     }
 
     Ok(())
 }
 
+/// Removes one of the item from its stack, deleting the stack when it runs out.
+pub(crate) fn consume_single_item(ctx: &ReducerContext, item_instance_id: u64, player_id: Identity) -> Result<(), String> {
+    let mut item_to_consume = ctx.db.inventory_item().instance_id().find(item_instance_id)
+        .ok_or_else(|| format!("Item instance {} suddenly disappeared.", item_instance_id))?;
+
+    // Decrease quantity
+    if item_to_consume.quantity > 0 {
+        item_to_consume.quantity -= 1;
+    }
+
+    // Remove item if quantity is 0
+    if item_to_consume.quantity == 0 {
+        ctx.db.inventory_item().instance_id().delete(item_instance_id);
+        log::info!("[ConsumeItem] Instantly consumed and deleted item_instance_id: {} for player {:?}.", 
+            item_instance_id, player_id);
+    } else {
+        // Update with decreased quantity
+        ctx.db.inventory_item().instance_id().update(item_to_consume.clone());
+        log::info!("[ConsumeItem] Instantly consumed item_instance_id: {}, new quantity: {} for player {:?}.", 
+            item_instance_id, item_to_consume.quantity, player_id);
+    }
+    Ok(())
+}
+
 // NEW PUBLIC HELPER FUNCTION
 pub fn apply_item_effects_and_consume(
     ctx: &ReducerContext,
@@ -106,6 +154,8 @@ pub fn apply_item_effects_and_consume(
     item_instance_id: u64,
     player_to_update: &mut Player, // Pass mutable player to update directly
 ) -> Result<(), String> {
+    check_and_start_consumable_cooldown(ctx, player_id, item_def, player_to_update)?;
+
     let mut stat_changed_instantly = false;
     let old_health = player_to_update.health;
     let old_hunger = player_to_update.hunger;
@@ -116,15 +166,11 @@ pub fn apply_item_effects_and_consume(
             if item_def.name == "Bandage" {
                 if let Some(total_bandage_heal) = item_def.consumable_health_gain {
                     if total_bandage_heal != 0.0 {
-                        // Cancel any existing HealthRegen OR BandageBurst effects for this player to prevent stacking similar effects.
-                        // We might need a more nuanced approach if different types of healing shouldn't cancel each other.
+                        // Cancel any existing HealthRegen effects for this player to prevent stacking similar effects.
                         cancel_health_regen_effects(ctx, player_id); 
-                        // It might be wise to also explicitly cancel any existing BandageBurst here if a player tries to use another bandage while one is active.
-                        // For now, let's assume one bandage at a time. A dedicated cancel_bandage_burst_effects could be called too.
-                        // crate::active_effects::cancel_bleed_effects(ctx, player_id); // Bandages still cancel bleed - REMOVED, handled by BandageBurst effect completion
-                        
-                        log::info!("[EffectsHelper] Player {:?} using Bandage. Creating BandageBurst effect.", player_id);
-                        apply_timed_effect_for_helper(ctx, player_id, item_def, item_instance_id, EffectType::BandageBurst, total_bandage_heal, duration_secs, 1.0)?;
+                        // The bandage was already applied over its channel (see channeled_use.rs), so the heal lands right away
+                        log::info!("[EffectsHelper] Player {:?} finished applying Bandage. Creating BandageBurst effect.", player_id);
+                        apply_bandage_burst(ctx, player_id, item_def.id, item_instance_id, total_bandage_heal);
                     }
                 }
             } else {
@@ -169,12 +215,119 @@ pub fn apply_item_effects_and_consume(
         );
     }
 
+    if let Some(food_group) = item_def.food_group {
+        record_meal(ctx, player_id, item_def, food_group)?;
+    }
+
     player_to_update.last_consumed_at = Some(ctx.timestamp);
     // The caller of this helper will be responsible for updating the player table.
 
     Ok(())
 }
 
+/// Enforces the use cooldown of the item's cooldown category and restarts it. Categories are
+/// tracked independently, so e.g. eating doesn't delay the next bandage. Items without a
+/// category fall back to the general cooldown since the player's last consumption of anything.
+fn check_and_start_consumable_cooldown(ctx: &ReducerContext, player_id: Identity, item_def: &ItemDefinition, player: &Player) -> Result<(), String> {
+    check_consumable_cooldown(ctx, player_id, item_def, player)?;
+    let Some(category) = item_def.consumable_cooldown_category else {
+        return Ok(());
+    };
+
+    let cooldowns = ctx.db.consumable_cooldown();
+    match cooldowns.player_id().filter(player_id).find(|c| c.category == category) {
+        Some(mut cooldown) => {
+            cooldown.last_used_at = ctx.timestamp;
+            cooldowns.id().update(cooldown);
+        }
+        None => {
+            cooldowns.insert(ConsumableCooldown { id: 0, player_id, category, last_used_at: ctx.timestamp });
+        }
+    }
+    Ok(())
+}
+
+/// Errors if the item's cooldown hasn't finished, without restarting it.
+pub(crate) fn check_consumable_cooldown(ctx: &ReducerContext, player_id: Identity, item_def: &ItemDefinition, player: &Player) -> Result<(), String> {
+    let Some(category) = item_def.consumable_cooldown_category else {
+        if let Some(last_consumed_ts) = player.last_consumed_at {
+            if ctx.timestamp < last_consumed_ts + TimeDuration::from_micros(CONSUMPTION_COOLDOWN_MICROS as i64) {
+                return Err("You are consuming items too quickly.".to_string());
+            }
+        }
+        return Ok(());
+    };
+
+    let cooldown_micros = match category {
+        ConsumableCooldownCategory::Food => FOOD_COOLDOWN_MICROS,
+        ConsumableCooldownCategory::Medical => MEDICAL_COOLDOWN_MICROS,
+        ConsumableCooldownCategory::Elixir => ELIXIR_COOLDOWN_MICROS,
+    };
+    if let Some(cooldown) = ctx.db.consumable_cooldown().player_id().filter(player_id).find(|c| c.category == category) {
+        let ready_at = cooldown.last_used_at + TimeDuration::from_micros(cooldown_micros as i64);
+        if ctx.timestamp < ready_at {
+            let remaining_secs = (ready_at.to_micros_since_unix_epoch() - ctx.timestamp.to_micros_since_unix_epoch()) as f32 / 1_000_000.0;
+            return Err(format!("{:?} items are on cooldown for {:.1}s.", category, remaining_secs));
+        }
+    }
+    Ok(())
+}
+
+/// Adds a meal to the player's diet history, forgetting old meals, and makes the player
+/// well-fed when the recent diet is varied: both protein and vegetables, at least
+/// `WELL_FED_MIN_DISTINCT_FOODS` different foods, and at least one of them cooked.
+fn record_meal(ctx: &ReducerContext, player_id: Identity, item_def: &ItemDefinition, food_group: FoodGroup) -> Result<(), String> {
+    let diet = ctx.db.diet_entry();
+    diet.insert(DietEntry {
+        id: 0, // Auto-incremented
+        player_id,
+        item_def_id: item_def.id,
+        food_group,
+        is_cooked_food: item_def.is_cooked_food,
+        eaten_at: ctx.timestamp,
+    });
+
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let mut meals: Vec<DietEntry> = diet.player_id().filter(player_id).collect();
+    meals.sort_by_key(|m| std::cmp::Reverse(m.eaten_at));
+    let mut recent: Vec<DietEntry> = Vec::new();
+    for (index, meal) in meals.into_iter().enumerate() {
+        let age_micros = now_micros - meal.eaten_at.to_micros_since_unix_epoch();
+        if index < DIET_HISTORY_SIZE && age_micros <= DIET_HISTORY_WINDOW_SECS * 1_000_000 {
+            recent.push(meal);
+        } else {
+            diet.id().delete(meal.id);
+        }
+    }
+
+    let has_protein = recent.iter().any(|m| m.food_group == FoodGroup::Protein);
+    let has_vegetable = recent.iter().any(|m| m.food_group == FoodGroup::Vegetable);
+    let has_cooked = recent.iter().any(|m| m.is_cooked_food);
+    let distinct_foods = recent.iter().map(|m| m.item_def_id).collect::<std::collections::HashSet<_>>().len();
+    if !(has_protein && has_vegetable && has_cooked && distinct_foods >= WELL_FED_MIN_DISTINCT_FOODS) {
+        return Ok(());
+    }
+
+    // Refresh rather than stack
+    cancel_well_fed_effects(ctx, player_id);
+    let duration_micros = (WELL_FED_DURATION_SECS * 1_000_000.0) as i64;
+    ctx.db.active_consumable_effect().try_insert(ActiveConsumableEffect {
+        effect_id: 0, // Auto-incremented
+        player_id,
+        item_def_id: item_def.id,
+        consuming_item_instance_id: None, // The meal itself is consumed as usual
+        started_at: ctx.timestamp,
+        ends_at: ctx.timestamp + TimeDuration::from_micros(duration_micros),
+        total_amount: Some(WELL_FED_TOTAL_HEALTH_REGEN),
+        amount_applied_so_far: Some(0.0),
+        effect_type: EffectType::WellFed,
+        tick_interval_micros: 1_000_000,
+        next_tick_at: ctx.timestamp + TimeDuration::from_micros(1_000_000),
+    }).map_err(|e| format!("Failed to apply well-fed effect: {:?}", e))?;
+    log::info!("[Nutrition] Player {:?} is well-fed after eating {}.", player_id, item_def.name);
+    Ok(())
+}
+
 // Renamed and adapted apply_instant_effects to be used by the helper
 fn apply_instant_effects_for_helper(item_def: &ItemDefinition, player: &mut Player, stat_changed: &mut bool) {
     if let Some(health_gain) = item_def.consumable_health_gain {
@@ -0,0 +1,237 @@
+/******************************************************************************
+ *                                                                            *
+ * Authorization lists and labels for storage boxes and stashes. A container  *
+ * with no authorizations can be used by anyone, as before. Once its owner    *
+ * (the player who placed it) authorizes a player or their own team, only the *
+ * owner and those grantees can use it. Container modules call                *
+ * `check_container_access` from their interaction validation, alongside      *
+ * the lock check. Owners can also give a container a short name with         *
+ * `rename_container`, shown by clients instead of the generic item name.     *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp, log};
+
+use crate::models::ContainerType;
+use crate::player as PlayerTableTrait;
+use crate::wooden_storage_box::{wooden_storage_box as WoodenStorageBoxTableTrait, BOX_INTERACTION_DISTANCE_SQUARED};
+use crate::stash::{stash as StashTableTrait, STASH_INTERACTION_DISTANCE_SQUARED};
+use crate::container_access::container_authorization as ContainerAuthorizationTableTrait;
+use crate::container_access::container_label as ContainerLabelTableTrait;
+
+// --- Constants ---
+const MAX_CONTAINER_LABEL_CHARS: usize = 32;
+
+/// Who an authorization grants access to.
+#[derive(SpacetimeType, Copy, Clone, Debug, PartialEq)]
+pub enum ContainerGrantee {
+    Player(Identity),
+    OwnerTeam, // Whoever is on the owner's team at the time of access
+}
+
+/// --- Container Authorization ---
+/// Grants a player or the owner's team access to a storage box or stash.
+#[spacetimedb::table(name = container_authorization, public)]
+#[derive(Clone, Debug)]
+pub struct ContainerAuthorization {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub container_type: ContainerType,
+    #[index(btree)]
+    pub container_id: u32,
+    pub grantee: ContainerGrantee,
+    pub granted_at: Timestamp,
+}
+
+/// --- Container Label ---
+/// The name an owner gave a storage box or stash. Containers without a row show their
+/// item name.
+#[spacetimedb::table(name = container_label, public)]
+#[derive(Clone, Debug)]
+pub struct ContainerLabel {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub container_type: ContainerType,
+    #[index(btree)]
+    pub container_id: u32,
+    pub name: String,
+    pub set_by: Identity,
+    pub set_at: Timestamp,
+}
+
+/******************************************************************************
+ *                                 REDUCERS                                   *
+ ******************************************************************************/
+
+/// Grants a player or the owner's team access to a container. Only the container's owner
+/// can authorize, and the first authorization makes the container private.
+#[spacetimedb::reducer]
+pub fn authorize_on_container(ctx: &ReducerContext, container_type: ContainerType, container_id: u32, grantee: ContainerGrantee) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let owner_id = validate_owned_container(ctx, container_type, container_id)?;
+    if let ContainerGrantee::Player(player_id) = grantee {
+        if ctx.db.player().identity().find(player_id).is_none() {
+            return Err("Player to authorize not found.".to_string());
+        }
+        if player_id == owner_id {
+            return Err("The owner always has access.".to_string());
+        }
+    }
+    if find_authorization(ctx, container_type, container_id, grantee).is_some() {
+        return Err("Already authorized.".to_string());
+    }
+
+    ctx.db.container_authorization().insert(ContainerAuthorization {
+        id: 0, // Auto-incremented
+        container_type,
+        container_id,
+        grantee,
+        granted_at: ctx.timestamp,
+    });
+    log::info!("Player {:?} authorized {:?} on {:?} {}.", sender_id, grantee, container_type, container_id);
+    Ok(())
+}
+
+/// Removes an authorization from a container. Only the container's owner can revoke.
+/// Revoking the last authorization makes the container open to everyone again.
+#[spacetimedb::reducer]
+pub fn revoke_on_container(ctx: &ReducerContext, container_type: ContainerType, container_id: u32, grantee: ContainerGrantee) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    validate_owned_container(ctx, container_type, container_id)?;
+    let authorization = find_authorization(ctx, container_type, container_id, grantee)
+        .ok_or_else(|| "That isn't authorized on this container.".to_string())?;
+
+    ctx.db.container_authorization().id().delete(authorization.id);
+    log::info!("Player {:?} revoked {:?} on {:?} {}.", sender_id, grantee, container_type, container_id);
+    Ok(())
+}
+
+/// Names a container, or clears its name when `name` is blank. Only the container's owner
+/// can rename it.
+#[spacetimedb::reducer]
+pub fn rename_container(ctx: &ReducerContext, container_type: ContainerType, container_id: u32, name: String) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    validate_owned_container(ctx, container_type, container_id)?;
+    let name = validate_container_label(ctx, &name)?;
+    let labels = ctx.db.container_label();
+    let existing = find_label(ctx, container_type, container_id);
+
+    match (existing, name.is_empty()) {
+        (Some(label), true) => {
+            labels.id().delete(label.id);
+        }
+        (None, true) => {}
+        (Some(mut label), false) => {
+            label.name = name.clone();
+            label.set_by = sender_id;
+            label.set_at = ctx.timestamp;
+            labels.id().update(label);
+        }
+        (None, false) => {
+            labels.insert(ContainerLabel {
+                id: 0, // Auto-incremented
+                container_type,
+                container_id,
+                name: name.clone(),
+                set_by: sender_id,
+                set_at: ctx.timestamp,
+            });
+        }
+    }
+    log::info!("Player {:?} renamed {:?} {} to '{}'.", sender_id, container_type, container_id, name);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Returns Ok if the container has no authorizations, or the player is its owner or a grantee.
+/// Called by container interaction checks.
+pub(crate) fn check_container_access(ctx: &ReducerContext, container_type: ContainerType, container_id: u32, owner_id: Identity, player_id: Identity) -> Result<(), String> {
+    let mut authorizations = ctx.db.container_authorization().container_id().filter(container_id)
+        .filter(|auth| auth.container_type == container_type)
+        .peekable();
+    if authorizations.peek().is_none() || player_id == owner_id {
+        return Ok(());
+    }
+    let has_access = authorizations.any(|auth| match auth.grantee {
+        ContainerGrantee::Player(grantee_id) => grantee_id == player_id,
+        ContainerGrantee::OwnerTeam => crate::team::are_teammates(ctx, owner_id, player_id),
+    });
+    if has_access {
+        Ok(())
+    } else {
+        Err("You are not authorized on this container.".to_string())
+    }
+}
+
+/// Removes all authorizations and the label from a container that was picked up or destroyed.
+pub(crate) fn remove_container_access(ctx: &ReducerContext, container_type: ContainerType, container_id: u32) {
+    let authorizations = ctx.db.container_authorization();
+    let auth_ids: Vec<u64> = authorizations.container_id().filter(container_id)
+        .filter(|auth| auth.container_type == container_type)
+        .map(|auth| auth.id)
+        .collect();
+    for auth_id in auth_ids {
+        authorizations.id().delete(auth_id);
+    }
+    if let Some(label) = find_label(ctx, container_type, container_id) {
+        ctx.db.container_label().id().delete(label.id);
+    }
+}
+
+fn find_authorization(ctx: &ReducerContext, container_type: ContainerType, container_id: u32, grantee: ContainerGrantee) -> Option<ContainerAuthorization> {
+    ctx.db.container_authorization().container_id().filter(container_id)
+        .find(|auth| auth.container_type == container_type && auth.grantee == grantee)
+}
+
+fn find_label(ctx: &ReducerContext, container_type: ContainerType, container_id: u32) -> Option<ContainerLabel> {
+    ctx.db.container_label().container_id().filter(container_id)
+        .find(|label| label.container_type == container_type)
+}
+
+/// Trims the name, strips control characters, checks its length and runs it through the
+/// chat word filter. An empty result means the label should be cleared.
+fn validate_container_label(ctx: &ReducerContext, name: &str) -> Result<String, String> {
+    let name: String = name.trim().chars().filter(|c| !c.is_control()).collect();
+    if name.chars().count() > MAX_CONTAINER_LABEL_CHARS {
+        return Err(format!("Container names can be at most {} characters.", MAX_CONTAINER_LABEL_CHARS));
+    }
+    crate::chat::apply_word_filter(ctx, &name)
+}
+
+/// Checks the container exists, is intact, is within reach and belongs to the sender.
+/// Returns the owner.
+fn validate_owned_container(ctx: &ReducerContext, container_type: ContainerType, container_id: u32) -> Result<Identity, String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or_else(|| "Player not found".to_string())?;
+
+    let (pos_x, pos_y, owner_id, max_distance_squared) = match container_type {
+        ContainerType::WoodenStorageBox => {
+            let storage_box = ctx.db.wooden_storage_box().id().find(container_id)
+                .filter(|b| !b.is_destroyed)
+                .ok_or_else(|| format!("Storage Box {} not found", container_id))?;
+            (storage_box.pos_x, storage_box.pos_y, storage_box.placed_by, BOX_INTERACTION_DISTANCE_SQUARED)
+        }
+        ContainerType::Stash => {
+            let stash = ctx.db.stash().id().find(container_id)
+                .filter(|s| !s.is_destroyed)
+                .ok_or_else(|| format!("Stash {} not found", container_id))?;
+            (stash.pos_x, stash.pos_y, stash.placed_by, STASH_INTERACTION_DISTANCE_SQUARED)
+        }
+        _ => return Err(format!("{:?} containers don't support authorization or names.", container_type)),
+    };
+
+    if owner_id != ctx.sender {
+        return Err("Only the container's owner can change who has access or rename it.".to_string());
+    }
+    let dx = player.position_x - pos_x;
+    let dy = player.position_y - pos_y;
+    if (dx * dx + dy * dy) > max_distance_squared {
+        return Err("Too far away".to_string());
+    }
+    Ok(owner_id)
+}
@@ -3,6 +3,20 @@ use crate::models::{ItemLocation, ContainerType, ContainerLocationData}; // May
 use crate::items::{InventoryItem, ItemDefinition, inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait}; // For function signatures
 use std::cmp::min;
 use crate::dropped_item; // For DROP_OFFSET and create_dropped_item_entity
+use crate::quests::QuestObjective;
+use crate::inventory_management::ItemContainer;
+use crate::cooking::cook_contributor as CookContributorTableTrait;
+use rand::Rng;
+
+/// Who put an item stack into a campfire or furnace, so cooking quests credit them rather
+/// than whoever placed the appliance. Private; stale rows are cleared by the janitor.
+#[spacetimedb::table(name = cook_contributor)]
+#[derive(Clone, Debug)]
+pub struct CookContributor {
+    #[primary_key]
+    pub item_instance_id: u64,
+    pub player_id: Identity,
+}
 
 // CookingProgress struct (moved from campfire.rs)
 #[derive(SpacetimeType, Clone, Debug, PartialEq)]
@@ -12,6 +26,23 @@ pub struct CookingProgress {
     pub target_item_def_name: String,
 }
 
+// An extra item a process can leave behind. Rolled once per unit processed.
+pub struct ByProduct {
+    pub source_item_name: &'static str,
+    pub by_product_name: &'static str,
+    pub chance_percent: u8,
+}
+
+// By-products of cooking, rolled each time a unit of the source item finishes cooking.
+pub(crate) const COOKING_BY_PRODUCTS: &[ByProduct] = &[
+    ByProduct { source_item_name: "Raw Meat", by_product_name: "Animal Fat", chance_percent: 25 },
+];
+
+// By-products of fuel, rolled each time a unit of the source item burns out.
+pub(crate) const FUEL_BY_PRODUCTS: &[ByProduct] = &[
+    ByProduct { source_item_name: "Wood", by_product_name: "Charcoal", chance_percent: 75 },
+];
+
 // Trait for appliances that can cook/transform items
 pub trait CookableAppliance {
     // --- Slot Access ---
@@ -30,6 +61,10 @@ pub trait CookableAppliance {
 
     // --- Container Type (for ItemLocation when placing transformed items back) ---
     fn get_appliance_container_type(&self) -> ContainerType;
+
+    // --- Smelting ---
+    // Smelting appliances (furnaces) only process smeltable items; cooking appliances never do.
+    fn is_smelting_appliance(&self) -> bool { false }
 }
 
 // Renamed and generalized from transform_campfire_item
@@ -77,7 +112,10 @@ pub(crate) fn transform_item_in_appliance<T: CookableAppliance>(
         instance_id: 0, 
         item_def_id: new_item_def.id,
         quantity: 1, 
-        location: ItemLocation::Unknown, 
+        location: ItemLocation::Unknown,
+        durability: None,
+        is_locked: false,
+        charges: source_item.charges, // Only water jugs hold charges; a boiled jug keeps its drinks
     };
 
     let inserted_item = inventory_items_table.try_insert(new_inventory_item)
@@ -154,6 +192,51 @@ pub(crate) fn handle_transformed_item_placement<T: CookableAppliance>(
     Ok(false) 
 }
 
+// Rolls each by-product listed for `source_item_name` and adds the ones produced to the
+// appliance like a transformed item (stacked, placed in an empty slot, or dropped).
+// Returns true if the appliance struct was modified.
+pub(crate) fn roll_by_products<T: CookableAppliance>(
+    ctx: &ReducerContext,
+    appliance: &mut T,
+    source_item_name: &str,
+    by_products: &[ByProduct],
+) -> bool {
+    let mut appliance_struct_modified = false;
+    for by_product in by_products.iter().filter(|b| b.source_item_name == source_item_name) {
+        if ctx.rng().gen_range(0..100) >= by_product.chance_percent {
+            continue;
+        }
+        let Some(by_product_def) = ctx.db.item_definition().iter().find(|def| def.name == by_product.by_product_name) else {
+            log::error!("[ByProduct] Item definition '{}' not found.", by_product.by_product_name);
+            continue;
+        };
+        let inserted = match ctx.db.inventory_item().try_insert(InventoryItem {
+            instance_id: 0,
+            item_def_id: by_product_def.id,
+            quantity: 1,
+            location: ItemLocation::Unknown, // Placed below
+            durability: None,
+            is_locked: false,
+            charges: None,
+        }) {
+            Ok(item) => item,
+            Err(e) => {
+                log::error!("[ByProduct] Failed to insert {}: {}", by_product.by_product_name, e);
+                continue;
+            }
+        };
+        match handle_transformed_item_placement(ctx, appliance, inserted.instance_id, by_product_def.id) {
+            Ok(placement_modified_appliance) => appliance_struct_modified |= placement_modified_appliance,
+            Err(e) => {
+                log::error!("[ByProduct] Appliance {}: Error placing {}: {}", appliance.get_appliance_entity_id(), by_product.by_product_name, e);
+                ctx.db.inventory_item().instance_id().delete(inserted.instance_id);
+            }
+        }
+        log::debug!("[ByProduct] Appliance {}: {} produced {}.", appliance.get_appliance_entity_id(), source_item_name, by_product.by_product_name);
+    }
+    appliance_struct_modified
+}
+
 // --- Main Processing Function for Cookable Appliances ---
 pub fn process_appliance_cooking_tick<T: CookableAppliance>(
     ctx: &ReducerContext,
@@ -192,9 +275,14 @@ pub fn process_appliance_cooking_tick<T: CookableAppliance>(
                                  appliance.get_appliance_entity_id(), i, current_item_def.id, progress_data.current_cook_time_secs, progress_data.target_cook_time_secs, progress_data.target_item_def_name);
 
                         if progress_data.current_cook_time_secs >= progress_data.target_cook_time_secs {
+                            let cook = ctx.db.cook_contributor().item_instance_id().find(current_item_instance_id).map(|c| c.player_id);
                             match transform_item_in_appliance(ctx, appliance, i, &progress_data.target_item_def_name) {
                                 Ok((transformed_item_def, new_instance_id)) => {
                                     appliance_struct_modified = true; // transform_item_in_appliance might have modified it
+                                    if let Some(cook_id) = cook {
+                                        crate::quests::record_progress(ctx, cook_id, QuestObjective::Cook, &transformed_item_def.name, 1);
+                                        set_cook_contributor(ctx, new_instance_id, cook_id);
+                                    }
                                     match handle_transformed_item_placement(ctx, appliance, new_instance_id, transformed_item_def.id) {
                                         Ok(placement_modified_appliance) => {
                                             if placement_modified_appliance {
@@ -231,6 +319,10 @@ pub fn process_appliance_cooking_tick<T: CookableAppliance>(
                                         // Further cooking of the transformed item would happen in its new slot, if applicable.
                                         slot_cooking_progress_opt = None;
                                     }
+
+                                    if roll_by_products(ctx, appliance, &current_item_def.name, COOKING_BY_PRODUCTS) {
+                                        appliance_struct_modified = true;
+                                    }
                                 }
                                 Err(e) => {
                                     log::error!("[ApplianceCooking] Appliance {}: Error transforming item in slot {}: {}. Halting for this slot.", 
@@ -248,7 +340,10 @@ pub fn process_appliance_cooking_tick<T: CookableAppliance>(
                         // For a generic cooking tick, we might simplify this: if `is_slot_active_fuel` is false, it *can* cook.
                         // The caller (campfire) will ensure `is_slot_active_fuel` is true for its burning fuel.
                         
-                        if let (Some(target_name), Some(target_time)) = (&current_item_def.cooked_item_def_name, current_item_def.cook_time_secs) {
+                        let item_needs_smelting = crate::furnace::is_smeltable_item(&current_item_def);
+                        if item_needs_smelting != appliance.is_smelting_appliance() {
+                            // Ore doesn't cook over a campfire, and food doesn't go in a furnace.
+                        } else if let (Some(target_name), Some(target_time)) = (&current_item_def.cooked_item_def_name, current_item_def.cook_time_secs) {
                             if target_time > 0.0 {
                                 slot_cooking_progress_opt = Some(CookingProgress {
                                     current_cook_time_secs: 0.0, 
@@ -277,3 +372,41 @@ pub fn process_appliance_cooking_tick<T: CookableAppliance>(
     Ok(appliance_struct_modified)
 }
 
+/// Remembers that `player_id` put the stack now in `slot_index` into the container, if the
+/// container is a campfire or furnace. Called by the container move handlers.
+pub(crate) fn record_cook_contributor<C: ItemContainer>(ctx: &ReducerContext, container: &C, slot_index: u8, player_id: Identity) {
+    if !matches!(container.get_container_type(), ContainerType::Campfire | ContainerType::Furnace) {
+        return;
+    }
+    if let Some(instance_id) = container.get_slot_instance_id(slot_index) {
+        set_cook_contributor(ctx, instance_id, player_id);
+    }
+}
+
+fn set_cook_contributor(ctx: &ReducerContext, item_instance_id: u64, player_id: Identity) {
+    let contributors = ctx.db.cook_contributor();
+    let row = CookContributor { item_instance_id, player_id };
+    if contributors.item_instance_id().find(item_instance_id).is_some() {
+        contributors.item_instance_id().update(row);
+    } else {
+        contributors.insert(row);
+    }
+}
+
+/// Deletes contributor rows for items that no longer exist or have left their campfire or
+/// furnace. Returns how many were removed. Called by the janitor.
+pub(crate) fn remove_stale_cook_contributors(ctx: &ReducerContext) -> u32 {
+    let contributors = ctx.db.cook_contributor();
+    let items = ctx.db.inventory_item();
+    let stale: Vec<u64> = contributors.iter()
+        .filter(|c| !items.instance_id().find(c.item_instance_id).is_some_and(|item| matches!(
+            item.location,
+            ItemLocation::Container(ContainerLocationData { container_type: ContainerType::Campfire | ContainerType::Furnace, .. })
+        )))
+        .map(|c| c.item_instance_id)
+        .collect();
+    for instance_id in &stale {
+        contributors.item_instance_id().delete(*instance_id);
+    }
+    stale.len() as u32
+}
@@ -17,6 +17,7 @@ use crate::collectible_resources::{
     BASE_RESOURCE_RADIUS, PLAYER_RESOURCE_INTERACTION_DISTANCE_SQUARED,
     validate_player_resource_interaction,
     collect_resource_and_schedule_respawn,
+    scale_respawn_secs,
     RespawnableResource
 };
 
@@ -94,6 +95,7 @@ impl RespawnableResource for Corn {
 /// inventory and the corn resource is scheduled for respawn.
 #[spacetimedb::reducer]
 pub fn interact_with_corn(ctx: &ReducerContext, corn_id: u64) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
     let player_id = ctx.sender;
     
     // Find the corn
@@ -108,6 +110,9 @@ pub fn interact_with_corn(ctx: &ReducerContext, corn_id: u64) -> Result<(), Stri
     // Validate player can interact with this corn (distance check)
     let _player = validate_player_resource_interaction(ctx, player_id, corn.pos_x, corn.pos_y)?;
 
+    let growth_multiplier = crate::game_config::get_game_config(ctx).day_plant_growth_multiplier
+        * crate::world_state::current_season(ctx).plant_growth_multiplier();
+
     // Add to inventory and schedule respawn
     collect_resource_and_schedule_respawn(
         ctx,
@@ -132,8 +137,8 @@ pub fn interact_with_corn(ctx: &ReducerContext, corn_id: u64) -> Result<(), Stri
                 Err(format!("Corn {} disappeared before respawn scheduling.", corn.id))
             }
         },
-        MIN_CORN_RESPAWN_TIME_SECS,     // min_respawn_secs
-        MAX_CORN_RESPAWN_TIME_SECS      // max_respawn_secs
+        scale_respawn_secs(MIN_CORN_RESPAWN_TIME_SECS, growth_multiplier),     // min_respawn_secs
+        scale_respawn_secs(MAX_CORN_RESPAWN_TIME_SECS, growth_multiplier)      // max_respawn_secs
     )?;
 
     // Log statement is now handled within collect_resource_and_schedule_respawn
@@ -0,0 +1,172 @@
+/******************************************************************************
+ *                                                                            *
+ * Cosmetic unlocks: titles, name colours and corpse styles. Each cosmetic    *
+ * in COSMETIC_UNLOCKS is earned once, either by finishing a tutorial quest   *
+ * (quests.rs) or by reaching a lifetime stat milestone tracked in            *
+ * PlayerStatsSummary (kill_feed.rs). Unlocked cosmetics are stored per       *
+ * player in player_cosmetic. A player picks one of each kind to show; the    *
+ * chosen title is also copied onto the public Player row next to the         *
+ * username so every client can render it.                                    *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp, log};
+
+use crate::PrivateMessage;
+use crate::private_message as PrivateMessageTableTrait;
+use crate::player as PlayerTableTrait;
+use crate::kill_feed::PlayerStatsSummary;
+use crate::cosmetics::player_cosmetic as PlayerCosmeticTableTrait;
+
+#[derive(SpacetimeType, Copy, Clone, Debug, PartialEq)]
+pub enum CosmeticKind {
+    Title,
+    NameColor,
+    CorpseStyle,
+}
+
+/// What earns a cosmetic.
+enum Unlock {
+    Quest(&'static str), // Quest name
+    Kills(u32),
+    ResourcesHarvested(u64),
+    PlaytimeHours(u64),
+}
+
+/// Every cosmetic that can be unlocked: (kind, name, value, unlock). The value is what the
+/// client renders: the title text, a hex colour or a corpse style key.
+const COSMETIC_UNLOCKS: &[(CosmeticKind, &str, &str, Unlock)] = &[
+    (CosmeticKind::Title, "Lumberjack", "Lumberjack", Unlock::Quest("Timber!")),
+    (CosmeticKind::Title, "Firestarter", "Firestarter", Unlock::Quest("Light in the Dark")),
+    (CosmeticKind::Title, "Camp Cook", "Camp Cook", Unlock::Quest("A Hot Meal")),
+    (CosmeticKind::Title, "Hunter", "Hunter", Unlock::Kills(10)),
+    (CosmeticKind::Title, "Warlord", "Warlord", Unlock::Kills(100)),
+    (CosmeticKind::Title, "Gatherer", "Gatherer", Unlock::ResourcesHarvested(10_000)),
+    (CosmeticKind::Title, "Veteran", "Veteran", Unlock::PlaytimeHours(24)),
+    (CosmeticKind::NameColor, "Ember", "#FF7043", Unlock::Kills(25)),
+    (CosmeticKind::NameColor, "Moss", "#7CB342", Unlock::ResourcesHarvested(50_000)),
+    (CosmeticKind::CorpseStyle, "Bedroll", "bedroll", Unlock::PlaytimeHours(10)),
+    (CosmeticKind::CorpseStyle, "Bone Pile", "bone_pile", Unlock::Kills(50)),
+];
+
+/// --- Player Cosmetic ---
+/// A cosmetic a player has unlocked. At most one of each kind is active.
+#[spacetimedb::table(name = player_cosmetic, public)]
+#[derive(Clone, Debug)]
+pub struct PlayerCosmetic {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub player_id: Identity,
+    pub kind: CosmeticKind,
+    pub name: String,
+    pub value: String,
+    pub unlocked_at: Timestamp,
+    pub is_active: bool,
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Set Active Title ---
+/// Shows one of the sender's unlocked titles next to their name, or none with `None`.
+#[spacetimedb::reducer]
+pub fn set_active_title(ctx: &ReducerContext, title: Option<String>) -> Result<(), String> {
+    set_active_cosmetic(ctx, CosmeticKind::Title, title)
+}
+
+/// --- Set Active Cosmetic ---
+/// Picks which unlocked cosmetic of a kind the sender shows, or none with `None`.
+#[spacetimedb::reducer]
+pub fn set_active_cosmetic(ctx: &ReducerContext, kind: CosmeticKind, name: Option<String>) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let mut player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Player not found".to_string())?;
+    let cosmetics = ctx.db.player_cosmetic();
+    let owned: Vec<PlayerCosmetic> = cosmetics.player_id().filter(sender_id)
+        .filter(|c| c.kind == kind)
+        .collect();
+    if let Some(name) = &name {
+        if !owned.iter().any(|c| &c.name == name) {
+            return Err(format!("You haven't unlocked the {:?} '{}'.", kind, name));
+        }
+    }
+
+    if kind == CosmeticKind::Title {
+        player.active_title = name.as_ref()
+            .and_then(|name| owned.iter().find(|c| &c.name == name))
+            .map(|c| c.value.clone());
+        ctx.db.player().identity().update(player);
+    }
+    for mut cosmetic in owned {
+        let is_active = name.as_ref() == Some(&cosmetic.name);
+        if cosmetic.is_active != is_active {
+            cosmetic.is_active = is_active;
+            cosmetics.id().update(cosmetic);
+        }
+    }
+    log::info!("Player {:?} set active {:?} to {:?}.", sender_id, kind, name);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Unlocks the cosmetics earned by finishing the named quest. Called from quests.rs.
+pub(crate) fn unlock_for_quest(ctx: &ReducerContext, player_id: Identity, quest_name: &str) {
+    for (kind, name, value, unlock) in COSMETIC_UNLOCKS {
+        if matches!(unlock, Unlock::Quest(quest) if *quest == quest_name) {
+            unlock_cosmetic(ctx, player_id, *kind, name, value);
+        }
+    }
+}
+
+/// Unlocks any stat milestone cosmetics the player's totals have reached. Called from
+/// kill_feed.rs whenever the summary changes.
+pub(crate) fn unlock_for_stats(ctx: &ReducerContext, summary: &PlayerStatsSummary) {
+    for (kind, name, value, unlock) in COSMETIC_UNLOCKS {
+        let reached = match unlock {
+            Unlock::Quest(_) => false,
+            Unlock::Kills(kills) => summary.kills >= *kills,
+            Unlock::ResourcesHarvested(amount) => summary.resources_harvested >= *amount,
+            Unlock::PlaytimeHours(hours) => summary.playtime_secs >= hours * 3600,
+        };
+        if reached {
+            unlock_cosmetic(ctx, summary.player_id, *kind, name, value);
+        }
+    }
+}
+
+/// Gives the player a cosmetic they don't have yet and tells them about it.
+fn unlock_cosmetic(ctx: &ReducerContext, player_id: Identity, kind: CosmeticKind, name: &str, value: &str) {
+    let cosmetics = ctx.db.player_cosmetic();
+    if cosmetics.player_id().filter(player_id).any(|c| c.kind == kind && c.name == name) {
+        return;
+    }
+    cosmetics.insert(PlayerCosmetic {
+        id: 0, // Auto-incremented
+        player_id,
+        kind,
+        name: name.to_string(),
+        value: value.to_string(),
+        unlocked_at: ctx.timestamp,
+        is_active: false,
+    });
+
+    let kind_label = match kind {
+        CosmeticKind::Title => "title",
+        CosmeticKind::NameColor => "name colour",
+        CosmeticKind::CorpseStyle => "corpse style",
+    };
+    ctx.db.private_message().insert(PrivateMessage {
+        id: 0, // Auto-incremented
+        recipient_identity: player_id,
+        sender_display_name: "SYSTEM".to_string(),
+        text: format!("Unlocked {}: {}!", kind_label, name),
+        sent: ctx.timestamp,
+    });
+    log::info!("[Cosmetics] Player {:?} unlocked {:?} '{}'.", player_id, kind, name);
+}
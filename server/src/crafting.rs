@@ -1,14 +1,20 @@
 /*
  * server/src/crafting.rs
  *
- * Purpose: Defines crafting recipes and related data structures.
+ * Purpose: Defines crafting recipes and related data structures, including the
+ *          category, sub-category, search tags and icon clients use to build the
+ *          crafting menu. Also handles repairing worn tools, priced from their recipe.
  */
 
-use spacetimedb::{SpacetimeType, Table, ReducerContext};
+use spacetimedb::{Identity, SpacetimeType, Table, ReducerContext};
+use std::collections::HashMap;
 use crate::items::{ItemDefinition, ItemCategory};
+use crate::items::inventory_item as InventoryItemTableTrait;
+use crate::models::ItemLocation;
 use crate::models::{EquipmentSlotType, TargetType};
 use crate::items::item_definition;
 use crate::items::item_definition__TableHandle;
+use crate::player as PlayerTableTrait;
 
 // Represents a single ingredient required for a recipe
 #[derive(Clone, Debug, PartialEq, SpacetimeType)]
@@ -17,6 +23,21 @@ pub struct RecipeIngredient {
     pub quantity: u32,
 }
 
+// Top-level grouping of the crafting menu
+#[derive(Clone, Copy, Debug, PartialEq, SpacetimeType)]
+pub enum RecipeCategory {
+    Tools,
+    Weapons,
+    Ammunition,
+    Armor,
+    Medical,
+    Building,
+    Storage,
+    Stations, // Workbenches, furnaces and other crafting/processing stations
+    Utility,
+    Materials,
+}
+
 // Defines a crafting recipe
 #[spacetimedb::table(name = recipe, public)]
 #[derive(Clone, Debug)]
@@ -28,7 +49,97 @@ pub struct Recipe {
     pub output_quantity: u32,    // How many items are crafted
     pub ingredients: Vec<RecipeIngredient>, // List of required ingredients
     pub crafting_time_secs: u32, // Time in seconds to craft
-    // pub required_station: Option<String>, // Future extension: e.g., "Workbench"
+    pub required_station_tier: u8, // Minimum nearby workbench tier (0 = craftable anywhere)
+    pub category: RecipeCategory,
+    pub sub_category: String, // Grouping within the category, e.g. "Melee"; empty if none
+    pub tags: Vec<String>, // Search terms besides the output item's name
+    pub output_icon_asset_name: String, // Icon of the output item, for the menu
+}
+
+/// Recipes that need a workbench nearby, by output item name. Unlisted recipes need no station.
+const RECIPE_STATION_TIERS: &[(&str, u8)] = &[
+    ("Advanced Workbench", 1),
+    ("Furnace", 1),
+    ("Large Storage Chest", 1),
+    ("Lock", 1),
+    ("Wooden Door", 1),
+    ("Hunting Bow", 1),
+    ("Bone Arrow", 1),
+    ("Recycler", 1),
+    ("Flame Turret", 1),
+    ("Vending Machine", 1),
+    ("Satchel Charge", 2),
+];
+
+fn required_station_tier_for(item_name: &str) -> u8 {
+    RECIPE_STATION_TIERS.iter()
+        .find(|(name, _)| *name == item_name)
+        .map_or(0, |&(_, tier)| tier)
+}
+
+/// Menu category, sub-category and search tags by output item name. Unlisted recipes get a
+/// category from their item category and no sub-category or tags.
+const RECIPE_METADATA: &[(&str, RecipeCategory, &str, &[&str])] = &[
+    ("Rock", RecipeCategory::Tools, "Gathering", &["stone", "starter"]),
+    ("Stone Hatchet", RecipeCategory::Tools, "Gathering", &["axe", "wood", "chopping"]),
+    ("Stone Pickaxe", RecipeCategory::Tools, "Gathering", &["pick", "mining", "ore"]),
+    ("Fishing Rod", RecipeCategory::Tools, "Fishing", &["fish", "food"]),
+    ("Bone Club", RecipeCategory::Weapons, "Melee", &["bone", "blunt"]),
+    ("Bone Knife", RecipeCategory::Weapons, "Melee", &["bone", "blade", "butchering"]),
+    ("Combat Ladle", RecipeCategory::Weapons, "Melee", &["blunt", "cooking"]),
+    ("Wooden Spear", RecipeCategory::Weapons, "Melee", &["reach", "thrust"]),
+    ("Stone Spear", RecipeCategory::Weapons, "Melee", &["reach", "thrust"]),
+    ("Hunting Bow", RecipeCategory::Weapons, "Ranged", &["bow", "arrows", "hunting"]),
+    ("Satchel Charge", RecipeCategory::Weapons, "Explosives", &["explosive", "raid"]),
+    ("Wooden Shield", RecipeCategory::Weapons, "Defense", &["block", "shield"]),
+    ("Wooden Arrow", RecipeCategory::Ammunition, "Arrows", &["bow", "ammo"]),
+    ("Bone Arrow", RecipeCategory::Ammunition, "Arrows", &["bow", "ammo", "bone"]),
+    ("Cloth Hood", RecipeCategory::Armor, "Cloth", &["head", "clothing"]),
+    ("Cloth Shirt", RecipeCategory::Armor, "Cloth", &["chest", "clothing"]),
+    ("Cloth Pants", RecipeCategory::Armor, "Cloth", &["legs", "clothing"]),
+    ("Cloth Gloves", RecipeCategory::Armor, "Cloth", &["hands", "clothing"]),
+    ("Cloth Boots", RecipeCategory::Armor, "Cloth", &["feet", "clothing"]),
+    ("Cloth Cape", RecipeCategory::Armor, "Cloth", &["back", "clothing", "warmth"]),
+    ("Hazmat Suit", RecipeCategory::Armor, "Protective", &["radiation", "hazard"]),
+    ("Bandage", RecipeCategory::Medical, "Healing", &["heal", "bleeding"]),
+    ("Wooden Foundation", RecipeCategory::Building, "Structure", &["base", "floor"]),
+    ("Wooden Wall", RecipeCategory::Building, "Structure", &["base"]),
+    ("Wooden Doorway", RecipeCategory::Building, "Structure", &["base", "door"]),
+    ("Wooden Door", RecipeCategory::Building, "Doors", &["base", "entrance"]),
+    ("Lock", RecipeCategory::Building, "Security", &["door", "box", "code"]),
+    ("Flame Turret", RecipeCategory::Building, "Security", &["defense", "trap", "fire"]),
+    ("Wooden Storage Box", RecipeCategory::Storage, "Containers", &["box", "chest"]),
+    ("Large Storage Chest", RecipeCategory::Storage, "Containers", &["box", "chest", "large"]),
+    ("Stash", RecipeCategory::Storage, "Hidden", &["hidden", "buried"]),
+    ("Camp Fire", RecipeCategory::Stations, "Cooking", &["fire", "cooking", "warmth", "light"]),
+    ("Furnace", RecipeCategory::Stations, "Smelting", &["fire", "ore", "metal"]),
+    ("Workbench", RecipeCategory::Stations, "Workbenches", &["crafting", "tier 1"]),
+    ("Advanced Workbench", RecipeCategory::Stations, "Workbenches", &["crafting", "tier 2"]),
+    ("Recycler", RecipeCategory::Stations, "Recycling", &["scrap", "salvage"]),
+    ("Research Table", RecipeCategory::Stations, "Research", &["blueprint", "learn"]),
+    ("Torch", RecipeCategory::Utility, "Lighting", &["light", "fire", "warmth"]),
+    ("Sleeping Bag", RecipeCategory::Utility, "Respawn", &["bed", "spawn point"]),
+    ("Wooden Sign", RecipeCategory::Utility, "Decoration", &["message", "text"]),
+    ("Note", RecipeCategory::Utility, "Writing", &["message", "text", "paper"]),
+    ("Switch", RecipeCategory::Utility, "Automation", &["wiring", "lever", "fire"]),
+    ("Vending Machine", RecipeCategory::Utility, "Trading", &["shop", "trade", "sell"]),
+    ("Water Jug", RecipeCategory::Utility, "Survival", &["water", "thirst", "drink"]),
+    ("Cloth", RecipeCategory::Materials, "Textiles", &["fabric", "fiber"]),
+];
+
+fn recipe_metadata_for(item_def: &ItemDefinition) -> (RecipeCategory, String, Vec<String>) {
+    if let Some(&(_, category, sub_category, tags)) = RECIPE_METADATA.iter().find(|(name, ..)| *name == item_def.name) {
+        return (category, sub_category.to_string(), tags.iter().map(|tag| tag.to_string()).collect());
+    }
+    let category = match item_def.category {
+        ItemCategory::Tool => RecipeCategory::Tools,
+        ItemCategory::Material => RecipeCategory::Materials,
+        ItemCategory::Placeable => RecipeCategory::Building,
+        ItemCategory::Armor => RecipeCategory::Armor,
+        ItemCategory::Consumable => RecipeCategory::Medical,
+        ItemCategory::Ammunition => RecipeCategory::Ammunition,
+    };
+    (category, String::new(), Vec::new())
 }
 
 // Function to get the initial set of recipes data (before resolving IDs)
@@ -49,7 +160,8 @@ fn find_def_id_by_name(name_to_find: &str, item_definitions_table: &item_definit
 pub fn seed_recipes(ctx: &ReducerContext) -> Result<(), String> {
     let recipe_table = ctx.db.recipe();
     if recipe_table.iter().count() > 0 {
-        log::info!("Recipes already seeded. Skipping.");
+        refresh_recipe_metadata(ctx);
+        log::info!("Recipes already seeded. Refreshed menu metadata only.");
         return Ok(());
     }
 
@@ -89,12 +201,18 @@ pub fn seed_recipes(ctx: &ReducerContext) -> Result<(), String> {
             }
 
             if ingredients_valid {
+                let (category, sub_category, tags) = recipe_metadata_for(&item_def_for_output);
                 let recipe = Recipe {
                     recipe_id: 0, // Auto-incremented
                     output_item_def_id: item_def_for_output.id,
                     output_quantity: output_qty,
                     ingredients: resolved_ingredients_for_recipe,
                     crafting_time_secs: time_secs,
+                    required_station_tier: required_station_tier_for(&item_def_for_output.name),
+                    category,
+                    sub_category,
+                    tags,
+                    output_icon_asset_name: item_def_for_output.icon_asset_name.clone(),
                 };
 
                 log::debug!("Inserting recipe for: {}", item_def_for_output.name);
@@ -109,3 +227,134 @@ pub fn seed_recipes(ctx: &ReducerContext) -> Result<(), String> {
     log::info!("Finished seeding {} recipes from ItemDefinitions.", recipes_created_count);
     Ok(())
 }
+
+/// Re-applies category, sub-category, tags and icon to already seeded recipes, so changes
+/// to the menu metadata reach existing worlds on the next publish.
+fn refresh_recipe_metadata(ctx: &ReducerContext) {
+    let recipe_table = ctx.db.recipe();
+    let item_defs_table = ctx.db.item_definition();
+    let recipes: Vec<Recipe> = recipe_table.iter().collect();
+    for mut recipe in recipes {
+        let Some(output_def) = item_defs_table.id().find(recipe.output_item_def_id) else {
+            continue;
+        };
+        let (category, sub_category, tags) = recipe_metadata_for(&output_def);
+        if recipe.category == category && recipe.sub_category == sub_category && recipe.tags == tags
+            && recipe.output_icon_asset_name == output_def.icon_asset_name {
+            continue;
+        }
+        recipe.category = category;
+        recipe.sub_category = sub_category;
+        recipe.tags = tags;
+        recipe.output_icon_asset_name = output_def.icon_asset_name;
+        recipe_table.recipe_id().update(recipe);
+    }
+}
+
+// --- Repair ---
+
+/// Fraction of the original recipe cost charged to repair an item from 0 to full durability.
+pub const REPAIR_COST_FRACTION: f32 = 0.5;
+
+/// Repairs a worn tool/weapon back to full durability.
+/// Costs a share of the item's recipe ingredients proportional to the durability missing.
+#[spacetimedb::reducer]
+pub fn repair_item(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    crate::spectator::ensure_not_spectating(ctx, sender_id)?;
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Player not found".to_string())?;
+    if player.is_dead {
+        return Err("Cannot repair items while dead.".to_string());
+    }
+    let inventory_table = ctx.db.inventory_item();
+
+    let mut item = crate::player_inventory::get_player_item(ctx, item_instance_id)?;
+    let item_def = ctx.db.item_definition().id().find(item.item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found.", item.item_def_id))?;
+
+    let max_durability = item_def.max_durability
+        .ok_or_else(|| format!("{} cannot be repaired.", item_def.name))?;
+    let current_durability = item.durability.unwrap_or(max_durability);
+    if current_durability >= max_durability {
+        return Err(format!("{} is already in full repair.", item_def.name));
+    }
+
+    let recipe = find_recipe_for_item(ctx, item_def.id)
+        .ok_or_else(|| format!("{} has no recipe and cannot be repaired.", item_def.name))?;
+
+    let missing_fraction = (max_durability - current_durability) / max_durability;
+    let repair_cost: Vec<RecipeIngredient> = recipe.ingredients.iter()
+        .map(|ing| RecipeIngredient {
+            item_def_id: ing.item_def_id,
+            quantity: ((ing.quantity as f32) * missing_fraction * REPAIR_COST_FRACTION).ceil().max(1.0) as u32,
+        })
+        .collect();
+
+    consume_player_resources(ctx, sender_id, &repair_cost)?;
+
+    // Re-fetch in case the consumed resources touched the same row (shouldn't, tools don't stack)
+    if let Some(fresh) = inventory_table.instance_id().find(item_instance_id) {
+        item = fresh;
+    }
+    item.durability = Some(max_durability);
+    inventory_table.instance_id().update(item);
+
+    log::info!("[Repair] Player {:?} repaired {} (Instance: {}) from {:.1} to {:.1}.",
+        sender_id, item_def.name, item_instance_id, current_durability, max_durability);
+    Ok(())
+}
+
+/// Returns the recipe that crafts the given item, if there is one.
+pub(crate) fn find_recipe_for_item(ctx: &ReducerContext, item_def_id: u64) -> Option<Recipe> {
+    ctx.db.recipe().iter().find(|r| r.output_item_def_id == item_def_id)
+}
+
+/// Checks that the player carries all of the given ingredients in inventory/hotbar and consumes them.
+/// Locked stacks are left alone. Nothing is consumed if any ingredient is short.
+pub(crate) fn consume_player_resources(ctx: &ReducerContext, player_id: Identity, requirements: &[RecipeIngredient]) -> Result<(), String> {
+    let inventory_table = ctx.db.inventory_item();
+
+    let mut still_needed: HashMap<u64, u32> = HashMap::new();
+    for req in requirements {
+        *still_needed.entry(req.item_def_id).or_insert(0) += req.quantity;
+    }
+
+    let mut items_to_consume: Vec<(u64, u32)> = Vec::new(); // (instance_id, quantity)
+    for item in inventory_table.iter() {
+        let in_player_slots = match &item.location {
+            ItemLocation::Inventory(data) => data.owner_id == player_id,
+            ItemLocation::Hotbar(data) => data.owner_id == player_id,
+            _ => false,
+        };
+        if !in_player_slots || item.is_locked {
+            continue;
+        }
+        if let Some(needed) = still_needed.get_mut(&item.item_def_id) {
+            if *needed > 0 {
+                let take = std::cmp::min(item.quantity, *needed);
+                items_to_consume.push((item.instance_id, take));
+                *needed -= take;
+            }
+        }
+    }
+
+    for (def_id, missing) in still_needed.iter() {
+        if *missing > 0 {
+            let item_name = ctx.db.item_definition().id().find(*def_id).map(|d| d.name.clone()).unwrap_or_else(|| format!("ID {}", def_id));
+            return Err(format!("Missing {} {}.", missing, item_name));
+        }
+    }
+
+    for (instance_id, quantity) in items_to_consume {
+        if let Some(mut item) = inventory_table.instance_id().find(instance_id) {
+            if quantity >= item.quantity {
+                inventory_table.instance_id().delete(instance_id);
+            } else {
+                item.quantity -= quantity;
+                inventory_table.instance_id().update(item);
+            }
+        }
+    }
+    Ok(())
+}
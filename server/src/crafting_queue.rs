@@ -2,6 +2,16 @@
  * server/src/crafting_queue.rs
  *
  * Purpose: Manages the player's crafting queue and handles crafting completion.
+ *          Queue entries craft one after another in `finish_time` order; players can
+ *          move an entry to the front or cancel it for a refund of the ingredients
+ *          proportional to the crafting time it still had left. Only recipes the
+ *          player has learned (see research.rs) can be queued. Several crafts of one
+ *          recipe can be queued in a single call, either a given number or as many as the
+ *          player's ingredients and queue room allow.
+ *          Items the player has locked are never taken as ingredients.
+ *          A group craft can be queued without all of its ingredients; it waits outside
+ *          the queue's timeline until nearby teammates contribute the rest, and any
+ *          refund is split between the crafter and contributors by what they put in.
  */
 
 use spacetimedb::{Identity, ReducerContext, Table, Timestamp, TimeDuration};
@@ -18,6 +28,7 @@ use crate::player as PlayerTableTrait;
 use crate::dropped_item; // For dropping items
 use crate::models::ItemLocation; // Corrected import
 use crate::player_inventory::{find_first_empty_player_slot, get_player_item};
+use crate::workbench;
 
 // --- Crafting Queue Table ---
 #[spacetimedb::table(name = crafting_queue_item, public)]
@@ -32,6 +43,31 @@ pub struct CraftingQueueItem {
     pub output_quantity: u32, // Store for granting
     pub start_time: Timestamp,
     pub finish_time: Timestamp, // When this specific item should finish
+    pub missing_ingredients: Vec<RecipeIngredient>, // Still needed before a group craft can start. Empty for normal crafts
+}
+
+impl CraftingQueueItem {
+    /// A group craft still waiting for contributions. It isn't crafted, and its
+    /// start/finish times only record how long it will take once it is supplied.
+    pub fn is_waiting_for_ingredients(&self) -> bool {
+        !self.missing_ingredients.is_empty()
+    }
+}
+
+// --- Craft Contribution Table ---
+// Ingredients a player put into someone else's group craft, kept for refunds on cancel.
+#[spacetimedb::table(name = craft_contribution, public)]
+#[derive(Clone, Debug)]
+pub struct CraftContribution {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub queue_item_id: u64,
+    pub contributor_id: Identity,
+    pub item_def_id: u64,
+    pub quantity: u32,
+    pub contributed_at: Timestamp,
 }
 
 // --- Scheduled Reducer Table --- 
@@ -46,6 +82,10 @@ pub struct CraftingFinishSchedule {
 }
 
 const CRAFTING_CHECK_INTERVAL_SECS: u64 = 1; // Check every second
+/// Default for the game config's `max_queued_crafts`.
+pub(crate) const MAX_QUEUED_CRAFTS_PER_PLAYER: u32 = 20;
+/// How close a contributor has to stand to the crafter.
+const CRAFT_CONTRIBUTION_DISTANCE_SQUARED: f32 = 128.0 * 128.0;
 
 // --- Reducers ---
 
@@ -53,6 +93,7 @@ const CRAFTING_CHECK_INTERVAL_SECS: u64 = 1; // Check every second
 #[spacetimedb::reducer]
 pub fn start_crafting(ctx: &ReducerContext, recipe_id: u64) -> Result<(), String> {
     let sender_id = ctx.sender;
+    crate::spectator::ensure_not_spectating(ctx, sender_id)?;
     let recipe_table = ctx.db.recipe();
     let inventory_table = ctx.db.inventory_item();
     let queue_table = ctx.db.crafting_queue_item();
@@ -60,6 +101,9 @@ pub fn start_crafting(ctx: &ReducerContext, recipe_id: u64) -> Result<(), String
     // 1. Find the Recipe
     let recipe = recipe_table.recipe_id().find(&recipe_id)
         .ok_or(format!("Recipe with ID {} not found.", recipe_id))?;
+    crate::research::ensure_recipe_known(ctx, sender_id, &recipe)?;
+    let workbench_tier = workbench::require_station_tier(ctx, sender_id, recipe.required_station_tier)?;
+    ensure_queue_has_room(ctx, sender_id, 1)?;
 
     // 2. Check Resources
     let mut required_resources_map: HashMap<u64, u32> = HashMap::new();
@@ -78,7 +122,7 @@ pub fn start_crafting(ctx: &ReducerContext, recipe_id: u64) -> Result<(), String
             _ => false,
         };
 
-        if is_in_player_possession {
+        if is_in_player_possession && !item.is_locked {
             // Track total available for this item definition
             *available_resources_check.entry(item.item_def_id).or_insert(0) += item.quantity;
 
@@ -132,12 +176,12 @@ pub fn start_crafting(ctx: &ReducerContext, recipe_id: u64) -> Result<(), String
     let now = ctx.timestamp;
     let mut last_finish_time = now;
     // Find the latest finish time for items already in this player's queue
-    for item in queue_table.iter().filter(|q| q.player_identity == sender_id) {
+    for item in queue_table.iter().filter(|q| q.player_identity == sender_id && !q.is_waiting_for_ingredients()) {
         if item.finish_time > last_finish_time {
             last_finish_time = item.finish_time;
         }
     }
-    let crafting_duration = Duration::from_secs(recipe.crafting_time_secs as u64);
+    let crafting_duration = Duration::from_millis(adjusted_craft_time_ms(ctx, sender_id, &recipe, workbench_tier));
     let finish_time = last_finish_time + spacetimedb::TimeDuration::from(crafting_duration);
 
     // 5. Add to Queue
@@ -149,6 +193,7 @@ pub fn start_crafting(ctx: &ReducerContext, recipe_id: u64) -> Result<(), String
         output_quantity: recipe.output_quantity,
         start_time: now,
         finish_time,
+        missing_ingredients: Vec::new(),
     };
     queue_table.insert(queue_item);
 
@@ -159,123 +204,49 @@ pub fn start_crafting(ctx: &ReducerContext, recipe_id: u64) -> Result<(), String
 }
 
 /// Starts crafting multiple items of the same recipe if the player has the required resources.
+/// Kept for existing clients; same as `enqueue_craft_multiple`.
 #[spacetimedb::reducer]
 pub fn start_crafting_multiple(ctx: &ReducerContext, recipe_id: u64, quantity_to_craft: u32) -> Result<(), String> {
-    if quantity_to_craft == 0 {
+    enqueue_craft_multiple(ctx, recipe_id, quantity_to_craft)
+}
+
+/// Queues `count` crafts of a recipe in one call. Fails without consuming anything unless
+/// the player has the ingredients for all of them and room for them in their queue.
+#[spacetimedb::reducer]
+pub fn enqueue_craft_multiple(ctx: &ReducerContext, recipe_id: u64, count: u32) -> Result<(), String> {
+    if count == 0 {
         return Err("Quantity to craft must be greater than 0.".to_string());
     }
-
     let sender_id = ctx.sender;
-    let recipe_table = ctx.db.recipe();
-    let inventory_table = ctx.db.inventory_item();
-    let queue_table = ctx.db.crafting_queue_item();
-    let item_def_table = ctx.db.item_definition(); // For item names in errors
-
-    // 1. Find the Recipe
-    let recipe = recipe_table.recipe_id().find(&recipe_id)
+    crate::spectator::ensure_not_spectating(ctx, sender_id)?;
+    let recipe = ctx.db.recipe().recipe_id().find(recipe_id)
         .ok_or(format!("Recipe with ID {} not found.", recipe_id))?;
+    crate::research::ensure_recipe_known(ctx, sender_id, &recipe)?;
+    let workbench_tier = workbench::require_station_tier(ctx, sender_id, recipe.required_station_tier)?;
+    ensure_queue_has_room(ctx, sender_id, count)?;
+    queue_crafts(ctx, sender_id, &recipe, workbench_tier, count)
+}
 
-    // 2. Check Resources for the total quantity
-    let mut total_required_resources_map: HashMap<u64, u32> = HashMap::new();
-    for ingredient in &recipe.ingredients {
-        *total_required_resources_map.entry(ingredient.item_def_id).or_insert(0) += ingredient.quantity * quantity_to_craft;
-    }
-
-    let mut available_resources_check: HashMap<u64, u32> = HashMap::new();
-    // This map will store {item_instance_id, quantity_to_consume_from_this_stack}
-    let mut items_to_consume_map: HashMap<u64, u32> = HashMap::new();
-
-    // Iterate over player's inventory and hotbar items
-    for item_instance in inventory_table.iter() {
-        let is_in_player_possession = match &item_instance.location {
-            ItemLocation::Inventory(crate::models::InventoryLocationData { owner_id, .. }) => *owner_id == sender_id,
-            ItemLocation::Hotbar(crate::models::HotbarLocationData { owner_id, .. }) => *owner_id == sender_id,
-            _ => false,
-        };
-
-        if is_in_player_possession {
-            *available_resources_check.entry(item_instance.item_def_id).or_insert(0) += item_instance.quantity;
-
-            if let Some(total_needed_for_def) = total_required_resources_map.get_mut(&item_instance.item_def_id) {
-                if *total_needed_for_def > 0 { // Still need some of this item definition
-                    let already_marked_to_consume_from_this_def = items_to_consume_map.iter()
-                        .filter(|(id, _)| inventory_table.instance_id().find(**id).map_or(false, |i| i.item_def_id == item_instance.item_def_id))
-                        .map(|(_, qty)| qty)
-                        .sum::<u32>();
-                    
-                    let remaining_needed_for_def = (*total_needed_for_def).saturating_sub(already_marked_to_consume_from_this_def);
-                    let can_take_from_stack = std::cmp::min(item_instance.quantity, remaining_needed_for_def);
-                    
-                    if can_take_from_stack > 0 {
-                        *items_to_consume_map.entry(item_instance.instance_id).or_insert(0) += can_take_from_stack;
-                        // No, don't decrement total_needed_for_def here. We verify it at the end.
-                    }
-                }
-            }
-        }
-    }
-    
-    // Verify all requirements met
-    for (def_id, total_required) in total_required_resources_map.iter() {
-        let total_available_for_def = available_resources_check.get(def_id).copied().unwrap_or(0);
-        if total_available_for_def < *total_required {
-            let item_name = item_def_table.id().find(*def_id).map(|d| d.name.clone()).unwrap_or_else(|| format!("ID {}", def_id));
-            return Err(format!("Missing {} {} to craft {}x. You have {}.",
-                total_required - total_available_for_def, item_name, quantity_to_craft, total_available_for_def));
-        }
-    }
-
-    // 3. Consume Resources
-    log::info!("[Crafting Multiple] Consuming resources for Recipe ID {} ({}x) for player {:?}", recipe_id, quantity_to_craft, sender_id);
-    for (instance_id, qty_to_consume) in items_to_consume_map {
-        if let Some(mut item) = inventory_table.instance_id().find(instance_id) {
-            if qty_to_consume >= item.quantity {
-                inventory_table.instance_id().delete(instance_id);
-            } else {
-                item.quantity -= qty_to_consume;
-                inventory_table.instance_id().update(item);
-            }
-        } else {
-            log::error!("[Crafting Multiple] Failed to find item instance {} to consume resources.", instance_id);
-            return Err("Internal error consuming resources for multiple craft.".to_string());
-        }
-    }
+/// Queues as many crafts of a recipe as the player has ingredients for, up to the room
+/// left in their queue.
+#[spacetimedb::reducer]
+pub fn enqueue_craft_max(ctx: &ReducerContext, recipe_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    crate::spectator::ensure_not_spectating(ctx, sender_id)?;
+    let recipe = ctx.db.recipe().recipe_id().find(recipe_id)
+        .ok_or(format!("Recipe with ID {} not found.", recipe_id))?;
+    crate::research::ensure_recipe_known(ctx, sender_id, &recipe)?;
+    let workbench_tier = workbench::require_station_tier(ctx, sender_id, recipe.required_station_tier)?;
 
-    // 4. Calculate Finish Times and Add to Queue
-    let mut current_item_start_time = ctx.timestamp;
-    // Find the latest finish time for items already in this player's queue
-    // This becomes the start time for the first item in this batch.
-    for item in queue_table.iter().filter(|q| q.player_identity == sender_id) {
-        if item.finish_time > current_item_start_time {
-            current_item_start_time = item.finish_time;
-        }
+    let craftable = max_craftable(ctx, sender_id, &recipe);
+    if craftable == 0 {
+        return Err("You don't have the ingredients to craft this.".to_string());
     }
-
-    let crafting_duration_per_item = TimeDuration::from(Duration::from_secs(recipe.crafting_time_secs as u64));
-
-    for i in 0..quantity_to_craft {
-        let item_finish_time = current_item_start_time + crafting_duration_per_item;
-        
-        let queue_item = CraftingQueueItem {
-            queue_item_id: 0, // Auto-increment
-            player_identity: sender_id,
-            recipe_id,
-            output_item_def_id: recipe.output_item_def_id,
-            output_quantity: recipe.output_quantity,
-            start_time: current_item_start_time, // The effective start time for this item in the sequence
-            finish_time: item_finish_time,
-        };
-        queue_table.insert(queue_item.clone()); // Clone here if insert takes ownership and we log after
-
-        current_item_start_time = item_finish_time; // Next item starts when this one finishes
-
-        if i == 0 { // Log the start of the batch
-             let item_name = item_def_table.id().find(recipe.output_item_def_id).map(|d| d.name.clone()).unwrap_or_else(|| format!("ID {}", recipe.output_item_def_id));
-             log::info!("[Crafting Multiple] Player {:?} started crafting {}x {} (Recipe ID {}). First item finishes: {:?}, Last item finishes: {:?}",
-                 sender_id, quantity_to_craft, item_name, recipe_id, item_finish_time, current_item_start_time);
-        }
+    let count = craftable.min(queue_slots_left(ctx, sender_id));
+    if count == 0 {
+        return Err("Crafting queue is full.".to_string());
     }
-    Ok(())
+    queue_crafts(ctx, sender_id, &recipe, workbench_tier, count)
 }
 
 /// Scheduled reducer to check for and grant finished crafting items.
@@ -288,7 +259,7 @@ pub fn check_finished_crafting(ctx: &ReducerContext, _schedule: CraftingFinishSc
 
     // Find items ready to finish
     for item in queue_table.iter() {
-        if now >= item.finish_time {
+        if now >= item.finish_time && !item.is_waiting_for_ingredients() {
             items_to_finish.push(item.clone());
         }
     }
@@ -308,39 +279,39 @@ pub fn check_finished_crafting(ctx: &ReducerContext, _schedule: CraftingFinishSc
             // Refund resources (or they are lost if player doesn't exist?)
             // For simplicity now, just delete the queue item. Refund on death handles it.
             queue_table.queue_item_id().delete(item.queue_item_id);
+            remove_craft_contributions(ctx, item.queue_item_id);
             continue; // Skip to next item
         }
 
-        let player = player_opt.as_ref().unwrap(); // Use as_ref() here
-
-        // Grant item or drop if inventory is full
+        // Grant item; whatever doesn't fit goes into a backpack at the player's feet
         log::info!("[Crafting Check] Finishing item {} for player {:?}. Output: DefID {}, Qty {}",
                   item.queue_item_id, item.player_identity, item.output_item_def_id, item.output_quantity);
 
-        match crate::items::add_item_to_player_inventory(ctx, item.player_identity, item.output_item_def_id, item.output_quantity) {
+        match crate::overflow_backpack::add_item_to_player_inventory_or_overflow(ctx, item.player_identity, item.output_item_def_id, item.output_quantity) {
             Ok(_) => {
                  let item_name = ctx.db.item_definition().id().find(item.output_item_def_id).map(|d| d.name.clone()).unwrap_or_else(|| format!("ID {}", item.output_item_def_id));
                  log::info!("[Crafting Check] Granted {} {} to player {:?}", item.output_quantity, item_name, item.player_identity);
             }
             Err(e) => {
-                log::warn!("[Crafting Check] Inventory full for player {:?}. Dropping item {}: {}", item.player_identity, item.output_item_def_id, e);
-                // Drop item near player
-                let (drop_x, drop_y) = dropped_item::calculate_drop_position(&player);
-                if let Err(drop_err) = dropped_item::create_dropped_item_entity(ctx, item.output_item_def_id, item.output_quantity, drop_x, drop_y) {
-                     log::error!("[Crafting Check] Failed to drop item {} for player {:?}: {}", item.output_item_def_id, item.player_identity, drop_err);
-                     // Item is lost if dropping fails too
-                }
+                log::error!("[Crafting Check] Failed to grant item {} to player {:?}: {}", item.output_item_def_id, item.player_identity, e);
             }
         }
 
+        crate::skills::award_xp(ctx, item.player_identity, crate::skills::SkillType::Crafting, crate::skills::XP_PER_CRAFT_COMPLETED);
+        if let Some(output_def) = ctx.db.item_definition().id().find(item.output_item_def_id) {
+            crate::quests::record_progress(ctx, item.player_identity, crate::quests::QuestObjective::Craft, &output_def.name, item.output_quantity);
+        }
+
         // Delete the finished item from the queue
         queue_table.queue_item_id().delete(item.queue_item_id);
+        remove_craft_contributions(ctx, item.queue_item_id);
     }
 
     Ok(())
 }
 
-/// Cancels a specific item in the player's crafting queue and refunds resources.
+/// Cancels a specific item in the player's crafting queue. Ingredients are refunded in
+/// proportion to the crafting time the item had left, and the entries behind it move up.
 #[spacetimedb::reducer]
 pub fn cancel_crafting_item(ctx: &ReducerContext, queue_item_id: u64) -> Result<(), String> {
     let sender_id = ctx.sender;
@@ -349,7 +320,7 @@ pub fn cancel_crafting_item(ctx: &ReducerContext, queue_item_id: u64) -> Result<
     let player_table = ctx.db.player();
 
     // 1. Find the Queue Item
-    let queue_item = queue_table.queue_item_id().find(&queue_item_id)
+    let queue_item = queue_table.queue_item_id().find(queue_item_id)
         .ok_or(format!("Crafting queue item {} not found.", queue_item_id))?;
 
     // 2. Verify Ownership
@@ -361,24 +332,32 @@ pub fn cancel_crafting_item(ctx: &ReducerContext, queue_item_id: u64) -> Result<
     let recipe = recipe_table.recipe_id().find(&queue_item.recipe_id)
         .ok_or(format!("Recipe {} for queue item {} not found.", queue_item.recipe_id, queue_item_id))?;
 
-    log::info!("[Crafting Cancel] Player {:?} cancelling queue item {} (Recipe ID {}). Refunding resources...",
-             sender_id, queue_item_id, queue_item.recipe_id);
+    // Refund only the share of ingredients matching the crafting time left
+    let mut remaining_queue = player_queue_with_remaining_micros(ctx, sender_id);
+    let refund_fraction = remaining_queue.iter()
+        .find(|(item, _)| item.queue_item_id == queue_item_id)
+        .map_or(1.0, |(item, remaining)| remaining_fraction(item, *remaining));
+
+    log::info!("[Crafting Cancel] Player {:?} cancelling queue item {} (Recipe ID {}). Refunding {:.0}% of resources...",
+             sender_id, queue_item_id, queue_item.recipe_id, refund_fraction * 100.0);
 
-    // 4. Refund Resources
+    // 4. Refund Resources (contributors get their share back directly)
     let mut refund_failed = false;
-    for ingredient in &recipe.ingredients {
-        match crate::items::add_item_to_player_inventory(ctx, sender_id, ingredient.item_def_id, ingredient.quantity) {
+    for ingredient in refund_craft_contributors(ctx, &queue_item, &recipe, refund_fraction) {
+        let refund_quantity = ingredient.quantity;
+        if refund_quantity == 0 { continue; }
+        match crate::items::add_item_to_player_inventory(ctx, sender_id, ingredient.item_def_id, refund_quantity) {
             Ok(_) => {
                 let item_name = ctx.db.item_definition().id().find(ingredient.item_def_id).map(|d| d.name.clone()).unwrap_or_else(|| format!("ID {}", ingredient.item_def_id));
-                log::debug!("[Crafting Cancel] Refunded {} {} to player {:?}.", ingredient.quantity, item_name, sender_id);
+                log::debug!("[Crafting Cancel] Refunded {} {} to player {:?}.", refund_quantity, item_name, sender_id);
             }
             Err(e) => {
                 log::warn!("[Crafting Cancel] Inventory full for player {:?}. Dropping refunded item {}: {}", sender_id, ingredient.item_def_id, e);
                 refund_failed = true;
                 // Find player position to drop item
-                if let Some(player) = player_table.identity().find(&sender_id) {
+                if let Some(player) = player_table.identity().find(sender_id) {
                      let (drop_x, drop_y) = dropped_item::calculate_drop_position(&player);
-                     if let Err(drop_err) = dropped_item::create_dropped_item_entity(ctx, ingredient.item_def_id, ingredient.quantity, drop_x, drop_y) {
+                     if let Err(drop_err) = dropped_item::create_owned_dropped_item_entity(ctx, Some(sender_id), ingredient.item_def_id, refund_quantity, drop_x, drop_y, None, None) {
                          log::error!("[Crafting Cancel] Failed to drop refunded item {} for player {:?}: {}", ingredient.item_def_id, sender_id, drop_err);
                          // Resource is lost if dropping fails
                      }
@@ -389,8 +368,10 @@ pub fn cancel_crafting_item(ctx: &ReducerContext, queue_item_id: u64) -> Result<
         }
     }
 
-    // 5. Delete Queue Item (this implicitly cancels the scheduled finish check)
+    // 5. Delete Queue Item (this implicitly cancels the scheduled finish check) and close the gap it leaves
     queue_table.queue_item_id().delete(queue_item_id);
+    remaining_queue.retain(|(item, _)| item.queue_item_id != queue_item_id);
+    reschedule_player_queue(ctx, remaining_queue);
     log::info!("[Crafting Cancel] Deleted queue item {}.", queue_item_id);
 
     if refund_failed {
@@ -402,6 +383,178 @@ pub fn cancel_crafting_item(ctx: &ReducerContext, queue_item_id: u64) -> Result<
     }
 }
 
+/// Queues a group craft using whatever ingredients the player has. The rest is listed in
+/// `missing_ingredients` for teammates to add with `contribute_to_craft`, and the craft
+/// joins the end of the player's queue once nothing is missing.
+#[spacetimedb::reducer]
+pub fn start_group_craft(ctx: &ReducerContext, recipe_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    crate::spectator::ensure_not_spectating(ctx, sender_id)?;
+    let inventory_table = ctx.db.inventory_item();
+    let recipe = ctx.db.recipe().recipe_id().find(recipe_id)
+        .ok_or(format!("Recipe with ID {} not found.", recipe_id))?;
+    crate::research::ensure_recipe_known(ctx, sender_id, &recipe)?;
+    let workbench_tier = workbench::require_station_tier(ctx, sender_id, recipe.required_station_tier)?;
+    ensure_queue_has_room(ctx, sender_id, 1)?;
+
+    // Take what the player has of each ingredient and note the shortfall
+    let mut missing_ingredients: Vec<RecipeIngredient> = Vec::new();
+    for ingredient in &recipe.ingredients {
+        let mut still_needed = ingredient.quantity;
+        let stacks: Vec<InventoryItem> = inventory_table.iter()
+            .filter(|i| i.item_def_id == ingredient.item_def_id && is_usable_ingredient(i, sender_id))
+            .collect();
+        for mut stack in stacks {
+            if still_needed == 0 { break; }
+            let take = stack.quantity.min(still_needed);
+            still_needed -= take;
+            if take >= stack.quantity {
+                inventory_table.instance_id().delete(stack.instance_id);
+            } else {
+                stack.quantity -= take;
+                inventory_table.instance_id().update(stack);
+            }
+        }
+        if still_needed > 0 {
+            missing_ingredients.push(RecipeIngredient { item_def_id: ingredient.item_def_id, quantity: still_needed });
+        }
+    }
+
+    let crafting_duration = TimeDuration::from(Duration::from_millis(adjusted_craft_time_ms(ctx, sender_id, &recipe, workbench_tier)));
+    let start_time = if missing_ingredients.is_empty() { last_queued_finish_time(ctx, sender_id) } else { ctx.timestamp };
+    let queue_item = ctx.db.crafting_queue_item().insert(CraftingQueueItem {
+        queue_item_id: 0, // Auto-increment
+        player_identity: sender_id,
+        recipe_id,
+        output_item_def_id: recipe.output_item_def_id,
+        output_quantity: recipe.output_quantity,
+        start_time,
+        finish_time: start_time + crafting_duration,
+        missing_ingredients,
+    });
+
+    log::info!("[Crafting Group] Player {:?} started group craft {} (Recipe ID {}). Missing: {:?}",
+        sender_id, queue_item.queue_item_id, recipe_id, queue_item.missing_ingredients);
+    Ok(())
+}
+
+/// Adds `quantity` from one of the sender's stacks to a group craft that is still missing
+/// that ingredient. Only the crafter and their teammates standing nearby can contribute.
+#[spacetimedb::reducer]
+pub fn contribute_to_craft(ctx: &ReducerContext, queue_item_id: u64, item_instance_id: u64, quantity: u32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    crate::spectator::ensure_not_spectating(ctx, sender_id)?;
+    let queue_table = ctx.db.crafting_queue_item();
+    let inventory_table = ctx.db.inventory_item();
+    if quantity == 0 {
+        return Err("Quantity must be greater than zero.".to_string());
+    }
+
+    let mut queue_item = queue_table.queue_item_id().find(queue_item_id)
+        .ok_or(format!("Crafting queue item {} not found.", queue_item_id))?;
+    if !queue_item.is_waiting_for_ingredients() {
+        return Err("That craft already has all of its ingredients.".to_string());
+    }
+    let crafter_id = queue_item.player_identity;
+    if crafter_id != sender_id {
+        if !crate::team::are_teammates(ctx, crafter_id, sender_id) {
+            return Err("You can only contribute to your teammates' crafts.".to_string());
+        }
+        let players = ctx.db.player();
+        let contributor = players.identity().find(sender_id)
+            .ok_or_else(|| "Player not found".to_string())?;
+        let crafter = players.identity().find(crafter_id)
+            .ok_or_else(|| "Crafter not found".to_string())?;
+        if contributor.is_dead || crafter.is_dead {
+            return Err("Cannot contribute while you or the crafter are dead.".to_string());
+        }
+        let dx = contributor.position_x - crafter.position_x;
+        let dy = contributor.position_y - crafter.position_y;
+        if (dx * dx + dy * dy) > CRAFT_CONTRIBUTION_DISTANCE_SQUARED {
+            return Err("Too far away from the crafter.".to_string());
+        }
+    }
+
+    let mut item = get_player_item(ctx, item_instance_id)?;
+    if matches!(item.location, ItemLocation::Equipped(_)) {
+        return Err("Unequip the item before contributing it.".to_string());
+    }
+    if item.is_locked {
+        return Err("This item is locked. Unlock it before contributing it.".to_string());
+    }
+    if quantity > item.quantity {
+        return Err(format!("You only have {} of that item.", item.quantity));
+    }
+    let missing = queue_item.missing_ingredients.iter_mut()
+        .find(|ingredient| ingredient.item_def_id == item.item_def_id)
+        .ok_or_else(|| "That craft doesn't need this item.".to_string())?;
+    let contributed = quantity.min(missing.quantity);
+    missing.quantity -= contributed;
+    queue_item.missing_ingredients.retain(|ingredient| ingredient.quantity > 0);
+
+    if contributed >= item.quantity {
+        inventory_table.instance_id().delete(item.instance_id);
+    } else {
+        item.quantity -= contributed;
+        inventory_table.instance_id().update(item.clone());
+    }
+
+    // The crafter's own additions need no bookkeeping; they get whatever isn't owed to others
+    if sender_id != crafter_id {
+        ctx.db.craft_contribution().insert(CraftContribution {
+            id: 0, // Auto-increment
+            queue_item_id,
+            contributor_id: sender_id,
+            item_def_id: item.item_def_id,
+            quantity: contributed,
+            contributed_at: ctx.timestamp,
+        });
+    }
+
+    if !queue_item.is_waiting_for_ingredients() {
+        // Fully supplied: join the end of the crafter's queue, keeping the craft's duration
+        let duration_micros = queue_item.finish_time.to_micros_since_unix_epoch() - queue_item.start_time.to_micros_since_unix_epoch();
+        let start_time = last_queued_finish_time(ctx, crafter_id);
+        queue_item.start_time = start_time;
+        queue_item.finish_time = start_time + TimeDuration::from_micros(duration_micros);
+        log::info!("[Crafting Group] Queue item {} for player {:?} is fully supplied and finishes at {:?}.",
+            queue_item_id, crafter_id, queue_item.finish_time);
+    }
+    queue_table.queue_item_id().update(queue_item);
+
+    log::info!("[Crafting Group] Player {:?} contributed {} of item def {} to queue item {} (crafter {:?}).",
+        sender_id, contributed, item.item_def_id, queue_item_id, crafter_id);
+    Ok(())
+}
+
+/// Moves an entry to the front of the player's crafting queue so it is crafted next.
+/// Entries that were already partly crafted keep their progress.
+#[spacetimedb::reducer]
+pub fn move_craft_to_front(ctx: &ReducerContext, queue_item_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let queue_item = ctx.db.crafting_queue_item().queue_item_id().find(queue_item_id)
+        .ok_or(format!("Crafting queue item {} not found.", queue_item_id))?;
+    if queue_item.player_identity != sender_id {
+        return Err("Cannot reorder crafting items started by another player.".to_string());
+    }
+    if queue_item.is_waiting_for_ingredients() {
+        return Err("That craft is still waiting for ingredients.".to_string());
+    }
+
+    let mut queue = player_queue_with_remaining_micros(ctx, sender_id);
+    let position = queue.iter().position(|(item, _)| item.queue_item_id == queue_item_id)
+        .ok_or(format!("Crafting queue item {} not found.", queue_item_id))?;
+    if position == 0 {
+        return Ok(()); // Already being crafted
+    }
+    let entry = queue.remove(position);
+    queue.insert(0, entry);
+    reschedule_player_queue(ctx, queue);
+
+    log::info!("[Crafting Reorder] Player {:?} moved queue item {} to the front.", sender_id, queue_item_id);
+    Ok(())
+}
+
 /// Helper function to clear the crafting queue for a player and refund resources.
 /// Called on player death/disconnect.
 pub fn clear_player_crafting_queue(ctx: &ReducerContext, player_id: Identity) {
@@ -419,7 +572,7 @@ pub fn clear_player_crafting_queue(ctx: &ReducerContext, player_id: Identity) {
         items_to_remove.push(item.queue_item_id);
         // Find the recipe to determine resources to refund
         if let Some(recipe) = recipe_table.recipe_id().find(&item.recipe_id) {
-            for ingredient in &recipe.ingredients {
+            for ingredient in refund_craft_contributors(ctx, &item, &recipe, 1.0) {
                 resources_to_refund.push((ingredient.item_def_id, ingredient.quantity));
             }
         } else {
@@ -449,7 +602,7 @@ pub fn clear_player_crafting_queue(ctx: &ReducerContext, player_id: Identity) {
                 // Inventory full or other error, try to drop
                 if let Some(ref player) = player_opt { // Use ref player to borrow instead of move
                     let (drop_x, drop_y) = dropped_item::calculate_drop_position(&player);
-                    if let Err(drop_err) = dropped_item::create_dropped_item_entity(ctx, def_id, quantity, drop_x, drop_y) {
+                    if let Err(drop_err) = dropped_item::create_owned_dropped_item_entity(ctx, Some(player_id), def_id, quantity, drop_x, drop_y, None, None) {
                         log::error!("[Clear Queue] Failed to add AND drop refunded item {} (qty {}) for player {:?}: {}", def_id, quantity, player_id, drop_err);
                     } else {
                         refund_failed_and_dropped = true;
@@ -500,7 +653,8 @@ pub fn clear_player_crafting_queue(ctx: &ReducerContext, player_id: Identity) {
     }
 }
 
-/// Cancels all items in the player's crafting queue and refunds all resources.
+/// Cancels all items in the player's crafting queue. Like `cancel_crafting_item`, the item
+/// being crafted only refunds the share of ingredients matching its remaining time.
 #[spacetimedb::reducer]
 pub fn cancel_all_crafting(ctx: &ReducerContext) -> Result<(), String> {
     let sender_id = ctx.sender;
@@ -514,11 +668,18 @@ pub fn cancel_all_crafting(ctx: &ReducerContext) -> Result<(), String> {
 
     log::info!("[Cancel All Crafting] Player {:?} initiated cancel all.", sender_id);
 
-    // 1. Collect all queued items and their ingredients for the player
-    for item in queue_table.iter().filter(|q| q.player_identity == sender_id) {
+    // 1. Collect all queued items and their ingredients for the player (waiting group crafts refund in full)
+    let waiting_crafts = queue_table.iter()
+        .filter(|q| q.player_identity == sender_id && q.is_waiting_for_ingredients())
+        .map(|q| (q, None));
+    let timed_crafts = player_queue_with_remaining_micros(ctx, sender_id).into_iter()
+        .map(|(q, remaining)| (q, Some(remaining)));
+    let all_crafts: Vec<(CraftingQueueItem, Option<i64>)> = timed_crafts.chain(waiting_crafts).collect();
+    for (item, remaining_micros) in all_crafts {
         items_to_remove_from_queue.push(item.queue_item_id);
-        if let Some(recipe) = recipe_table.recipe_id().find(&item.recipe_id) {
-            for ingredient in &recipe.ingredients {
+        if let Some(recipe) = recipe_table.recipe_id().find(item.recipe_id) {
+            let refund_fraction = remaining_micros.map_or(1.0, |remaining| remaining_fraction(&item, remaining));
+            for ingredient in refund_craft_contributors(ctx, &item, &recipe, refund_fraction) {
                 *total_resources_to_refund.entry(ingredient.item_def_id).or_insert(0) += ingredient.quantity;
             }
         } else {
@@ -553,7 +714,7 @@ pub fn cancel_all_crafting(ctx: &ReducerContext) -> Result<(), String> {
                     log::warn!("[Cancel All Crafting] Inventory full for player {:?} while refunding {} {}. Attempting to drop. Error: {}", sender_id, quantity_to_refund, item_name, e);
                     refund_partially_failed_and_dropped = true;
                     let (drop_x, drop_y) = dropped_item::calculate_drop_position(&player);
-                    if let Err(drop_err) = dropped_item::create_dropped_item_entity(ctx, item_def_id, quantity_to_refund, drop_x, drop_y) {
+                    if let Err(drop_err) = dropped_item::create_owned_dropped_item_entity(ctx, Some(sender_id), item_def_id, quantity_to_refund, drop_x, drop_y, None, None) {
                         log::error!("[Cancel All Crafting] Failed to drop refunded item {} (DefID: {}) for player {:?}: {}", item_name, item_def_id, sender_id, drop_err);
                         // Resource is lost if dropping also fails
                     } else {
@@ -597,4 +758,228 @@ pub fn init_crafting_schedule(ctx: &ReducerContext) -> Result<(), String> {
         log::debug!("Crafting finish check schedule already exists.");
     }
     Ok(())
-} 
\ No newline at end of file
+} 
+/// Fails if queueing `additional` more crafts would go over the player's queue limit.
+fn ensure_queue_has_room(ctx: &ReducerContext, player_id: Identity, additional: u32) -> Result<(), String> {
+    let max_queued = crate::game_config::get_game_config(ctx).max_queued_crafts as u32;
+    let queued = ctx.db.crafting_queue_item().iter().filter(|q| q.player_identity == player_id).count() as u32;
+    if queued.saturating_add(additional) > max_queued {
+        return Err(format!("Crafting queue is full ({} of {} slots used).", queued, max_queued));
+    }
+    Ok(())
+}
+
+/// How many more crafts fit in the player's queue.
+fn queue_slots_left(ctx: &ReducerContext, player_id: Identity) -> u32 {
+    let max_queued = crate::game_config::get_game_config(ctx).max_queued_crafts as u32;
+    let queued = ctx.db.crafting_queue_item().iter().filter(|q| q.player_identity == player_id).count() as u32;
+    max_queued.saturating_sub(queued)
+}
+
+/// Total of each ingredient needed for `count` crafts of the recipe, by item definition.
+/// Errors instead of wrapping if the totals don't fit in a u32.
+fn required_ingredients(recipe: &Recipe, count: u32) -> Result<HashMap<u64, u32>, String> {
+    let mut required: HashMap<u64, u32> = HashMap::new();
+    for ingredient in &recipe.ingredients {
+        let total = required.entry(ingredient.item_def_id).or_insert(0);
+        *total = ingredient.quantity.checked_mul(count)
+            .and_then(|needed| total.checked_add(needed))
+            .ok_or_else(|| format!("Cannot craft {}x of this recipe at once.", count))?;
+    }
+    Ok(required)
+}
+
+/// How many crafts of the recipe the ingredients in the player's inventory and hotbar cover.
+fn max_craftable(ctx: &ReducerContext, player_id: Identity, recipe: &Recipe) -> u32 {
+    let mut available: HashMap<u64, u32> = HashMap::new();
+    for item in ctx.db.inventory_item().iter().filter(|i| is_usable_ingredient(i, player_id)) {
+        *available.entry(item.item_def_id).or_insert(0) += item.quantity;
+    }
+    let Ok(per_craft) = required_ingredients(recipe, 1) else {
+        return 0;
+    };
+    per_craft.iter()
+        .filter(|(_, per_craft)| **per_craft > 0)
+        .map(|(def_id, per_craft)| available.get(def_id).copied().unwrap_or(0) / per_craft)
+        .min()
+        .unwrap_or(0)
+}
+
+/// Consumes the ingredients for `count` crafts from the player's inventory and hotbar and
+/// queues them back to back after the player's last timed craft. Checks every ingredient
+/// before consuming any, so a failed call takes nothing.
+fn queue_crafts(ctx: &ReducerContext, player_id: Identity, recipe: &Recipe, workbench_tier: u8, count: u32) -> Result<(), String> {
+    let inventory_table = ctx.db.inventory_item();
+    let item_def_table = ctx.db.item_definition();
+    let slots_left = queue_slots_left(ctx, player_id);
+    if count > slots_left {
+        return Err(format!("Only {} more crafts fit in your queue.", slots_left));
+    }
+    let required = required_ingredients(recipe, count)?;
+
+    // 1. Pick the stacks to take from, failing if anything is short
+    let mut items_to_consume: Vec<(InventoryItem, u32)> = Vec::new();
+    for (&def_id, &total_required) in &required {
+        let stacks: Vec<InventoryItem> = inventory_table.iter()
+            .filter(|i| i.item_def_id == def_id && is_usable_ingredient(i, player_id))
+            .collect();
+        let available: u32 = stacks.iter().map(|i| i.quantity).sum();
+        if available < total_required {
+            let item_name = item_def_table.id().find(def_id).map(|d| d.name.clone()).unwrap_or_else(|| format!("ID {}", def_id));
+            return Err(format!("Missing {} {} to craft {}x. You have {}.",
+                total_required - available, item_name, count, available));
+        }
+        let mut still_needed = total_required;
+        for stack in stacks {
+            if still_needed == 0 { break; }
+            let take = stack.quantity.min(still_needed);
+            still_needed -= take;
+            items_to_consume.push((stack, take));
+        }
+    }
+
+    // 2. Consume Resources
+    log::info!("[Crafting Multiple] Consuming resources for Recipe ID {} ({}x) for player {:?}", recipe.recipe_id, count, player_id);
+    for (mut item, take) in items_to_consume {
+        if take >= item.quantity {
+            inventory_table.instance_id().delete(item.instance_id);
+        } else {
+            item.quantity -= take;
+            inventory_table.instance_id().update(item);
+        }
+    }
+
+    // 3. Queue the batch back to back after whatever is already crafting
+    let queue_table = ctx.db.crafting_queue_item();
+    let crafting_duration_per_item = TimeDuration::from(Duration::from_millis(adjusted_craft_time_ms(ctx, player_id, recipe, workbench_tier)));
+    let mut current_item_start_time = last_queued_finish_time(ctx, player_id);
+    let mut first_finish_time = None;
+    for _ in 0..count {
+        let item_finish_time = current_item_start_time + crafting_duration_per_item;
+        queue_table.insert(CraftingQueueItem {
+            queue_item_id: 0, // Auto-increment
+            player_identity: player_id,
+            recipe_id: recipe.recipe_id,
+            output_item_def_id: recipe.output_item_def_id,
+            output_quantity: recipe.output_quantity,
+            start_time: current_item_start_time, // The effective start time for this item in the sequence
+            finish_time: item_finish_time,
+            missing_ingredients: Vec::new(),
+        });
+        first_finish_time.get_or_insert(item_finish_time);
+        current_item_start_time = item_finish_time; // Next item starts when this one finishes
+    }
+
+    let item_name = item_def_table.id().find(recipe.output_item_def_id).map(|d| d.name.clone()).unwrap_or_else(|| format!("ID {}", recipe.output_item_def_id));
+    log::info!("[Crafting Multiple] Player {:?} started crafting {}x {} (Recipe ID {}). First item finishes: {:?}, Last item finishes: {:?}",
+        player_id, count, item_name, recipe.recipe_id, first_finish_time, current_item_start_time);
+    Ok(())
+}
+
+/// The player's queue in crafting order, each entry paired with the crafting time (in
+/// microseconds) it still needs. Entries craft back to back, so an entry's remaining time
+/// is its finish time minus when the entry before it finishes (or now, for the first one).
+/// Group crafts still waiting for ingredients aren't part of the timeline and are left out.
+fn player_queue_with_remaining_micros(ctx: &ReducerContext, player_id: Identity) -> Vec<(CraftingQueueItem, i64)> {
+    let mut queue: Vec<CraftingQueueItem> = ctx.db.crafting_queue_item().iter()
+        .filter(|q| q.player_identity == player_id && !q.is_waiting_for_ingredients())
+        .collect();
+    queue.sort_by_key(|q| q.finish_time);
+
+    let mut previous_finish = ctx.timestamp.to_micros_since_unix_epoch();
+    queue.into_iter().map(|item| {
+        let finish = item.finish_time.to_micros_since_unix_epoch();
+        let remaining = (finish - previous_finish.max(ctx.timestamp.to_micros_since_unix_epoch())).max(0);
+        previous_finish = finish;
+        (item, remaining)
+    }).collect()
+}
+
+/// Share of the entry's full crafting time that is still left, between 0 and 1.
+fn remaining_fraction(item: &CraftingQueueItem, remaining_micros: i64) -> f32 {
+    let total_micros = item.finish_time.to_micros_since_unix_epoch() - item.start_time.to_micros_since_unix_epoch();
+    if total_micros <= 0 {
+        return 0.0;
+    }
+    (remaining_micros as f32 / total_micros as f32).clamp(0.0, 1.0)
+}
+
+/// Lays the given entries out back to back from now, in the given order.
+/// `start_time` is kept at `finish_time` minus the entry's full crafting time, so
+/// partly crafted entries keep showing (and refunding) their progress.
+fn reschedule_player_queue(ctx: &ReducerContext, queue: Vec<(CraftingQueueItem, i64)>) {
+    let queue_table = ctx.db.crafting_queue_item();
+    let mut cursor = ctx.timestamp.to_micros_since_unix_epoch();
+    for (mut item, remaining_micros) in queue {
+        let total_micros = item.finish_time.to_micros_since_unix_epoch() - item.start_time.to_micros_since_unix_epoch();
+        let finish = cursor + remaining_micros;
+        item.finish_time = Timestamp::from_micros_since_unix_epoch(finish);
+        item.start_time = Timestamp::from_micros_since_unix_epoch(finish - total_micros);
+        queue_table.queue_item_id().update(item);
+        cursor = finish;
+    }
+}
+
+/// Crafting time for one item of the recipe after skill, workbench and crafting event bonuses.
+fn adjusted_craft_time_ms(ctx: &ReducerContext, player_id: Identity, recipe: &Recipe, workbench_tier: u8) -> u64 {
+    let skill_adjusted_ms = crate::skills::get_adjusted_craft_time_ms(ctx, player_id, recipe.crafting_time_secs);
+    let event_speed = crate::server_modifiers::modifier_multiplier(ctx, crate::server_modifiers::ModifierKind::CraftingSpeed);
+    (skill_adjusted_ms as f32 * workbench::craft_time_multiplier(workbench_tier) / event_speed).round() as u64
+}
+
+/// When the player's last timed craft finishes, or now if nothing is being crafted.
+fn last_queued_finish_time(ctx: &ReducerContext, player_id: Identity) -> Timestamp {
+    ctx.db.crafting_queue_item().iter()
+        .filter(|q| q.player_identity == player_id && !q.is_waiting_for_ingredients())
+        .map(|q| q.finish_time)
+        .fold(ctx.timestamp, |latest, finish| if finish > latest { finish } else { latest })
+}
+
+fn is_in_player_possession(item: &InventoryItem, player_id: Identity) -> bool {
+    match &item.location {
+        ItemLocation::Inventory(data) => data.owner_id == player_id,
+        ItemLocation::Hotbar(data) => data.owner_id == player_id,
+        _ => false,
+    }
+}
+
+/// Whether crafting may take this stack as an ingredient: it is carried by the player and
+/// not locked (see `toggle_item_lock`).
+fn is_usable_ingredient(item: &InventoryItem, player_id: Identity) -> bool {
+    is_in_player_possession(item, player_id) && !item.is_locked
+}
+
+/// Splits the refund for a cancelled entry. Every contributor gets `refund_fraction` of what
+/// they put in straight back (into a backpack at their feet if their inventory is full), and
+/// the rest is returned as the crafter's share. Ingredients a group craft never received
+/// aren't refunded. Removes the entry's contribution records.
+fn refund_craft_contributors(ctx: &ReducerContext, item: &CraftingQueueItem, recipe: &Recipe, refund_fraction: f32) -> Vec<RecipeIngredient> {
+    let contributions: Vec<CraftContribution> = ctx.db.craft_contribution().queue_item_id().filter(item.queue_item_id).collect();
+    let mut crafter_refund = Vec::new();
+    for ingredient in &recipe.ingredients {
+        let missing = item.missing_ingredients.iter()
+            .find(|m| m.item_def_id == ingredient.item_def_id)
+            .map_or(0, |m| m.quantity);
+        let mut refund_left = (ingredient.quantity.saturating_sub(missing) as f32 * refund_fraction).round() as u32;
+        for contribution in contributions.iter().filter(|c| c.item_def_id == ingredient.item_def_id) {
+            let share = ((contribution.quantity as f32 * refund_fraction).round() as u32).min(refund_left);
+            if share == 0 { continue; }
+            refund_left -= share;
+            if let Err(e) = crate::overflow_backpack::add_item_to_player_inventory_or_overflow(ctx, contribution.contributor_id, ingredient.item_def_id, share) {
+                log::error!("[Crafting Group] Failed to refund {} of item def {} to contributor {:?}: {}",
+                    share, ingredient.item_def_id, contribution.contributor_id, e);
+            }
+        }
+        crafter_refund.push(RecipeIngredient { item_def_id: ingredient.item_def_id, quantity: refund_left });
+    }
+    remove_craft_contributions(ctx, item.queue_item_id);
+    crafter_refund
+}
+
+fn remove_craft_contributions(ctx: &ReducerContext, queue_item_id: u64) {
+    let contributions = ctx.db.craft_contribution();
+    let contribution_ids: Vec<u64> = contributions.queue_item_id().filter(queue_item_id).map(|c| c.id).collect();
+    for contribution_id in contribution_ids {
+        contributions.id().delete(contribution_id);
+    }
+}
@@ -1,16 +1,18 @@
-use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, TimeDuration};
 use log;
 // Use the specific import path from Blackholio
 use spacetimedb::spacetimedb_lib::ScheduleAt;
 // Import Duration for interval
 use std::time::Duration;
+use std::collections::HashMap;
 
 // Import necessary items from other modules
 // Need to use the generated table trait alias for InventoryItemTable operations
 use crate::items::inventory_item as InventoryItemTableTrait;
 use crate::items::item_definition as ItemDefinitionTableTrait; // Import ItemDefinition trait
 use crate::player as PlayerTableTrait; // Import Player trait
-use crate::items::{add_item_to_player_inventory, InventoryItem, ItemDefinition};
+use crate::items::{add_item_to_player_inventory, ItemRarity};
+use crate::player_corpse::player_corpse as PlayerCorpseTableTrait;
 // Corrected imports for Player and PLAYER_RADIUS from crate root
 use crate::{Player, PLAYER_RADIUS}; 
 use crate::utils::get_distance_squared; // Assuming a utility function for distance
@@ -27,8 +29,14 @@ pub struct DroppedItem {
     pub quantity: u32,         // How many of this item are in the sack
     pub pos_x: f32,            // World X position
     pub pos_y: f32,            // World Y position
+    #[index(btree)]
     pub chunk_index: u32,      // <<< ADDED chunk_index
     pub created_at: Timestamp, // When the item was dropped (for potential cleanup)
+    pub durability: Option<f32>, // Carried over from the InventoryItem so wear survives drop/pickup
+    pub charges: Option<u32>, // Likewise for charged items such as water jugs
+    pub owner_id: Option<Identity>, // Player who dropped it (or whose corpse spilled it), if any
+    pub protected_until: Option<Timestamp>, // Only the owner can pick it up before this time
+    pub despawn_at: Timestamp, // From the item's rarity (see ItemRarity::dropped_despawn_secs)
 }
 
 // --- Schedule Table --- 
@@ -44,15 +52,26 @@ pub struct DroppedItemDespawnSchedule {
 
 // Constants
 const PICKUP_RADIUS: f32 = 64.0; // How close the player needs to be to pick up (adjust as needed)
-const PICKUP_RADIUS_SQUARED: f32 = PICKUP_RADIUS * PICKUP_RADIUS;
+pub(crate) const PICKUP_RADIUS_SQUARED: f32 = PICKUP_RADIUS * PICKUP_RADIUS;
 pub(crate) const DROP_OFFSET: f32 = 40.0; // How far in front of the player to drop the item
 const DESPAWN_CHECK_INTERVAL_SECS: u64 = 60; // Check every 1 minute
+const AUTO_PICKUP_RADIUS: f32 = 40.0; // Smaller than PICKUP_RADIUS so players can still walk past loot
+const AUTO_PICKUP_RADIUS_SQUARED: f32 = AUTO_PICKUP_RADIUS * AUTO_PICKUP_RADIUS;
+/// Default seconds a player's own drops are reserved for them (see `GameConfig::dropped_item_protection_secs`).
+pub(crate) const DROPPED_ITEM_PROTECTION_SECS: f32 = 30.0;
+/// Drops of the same stackable item this close together are merged into one stack.
+const MERGE_RADIUS: f32 = 48.0;
+const MERGE_RADIUS_SQUARED: f32 = MERGE_RADIUS * MERGE_RADIUS;
+/// Drops this close to a player corpse don't despawn while the corpse is still there.
+const CORPSE_ADJACENT_RADIUS: f32 = 128.0;
+const CORPSE_ADJACENT_RADIUS_SQUARED: f32 = CORPSE_ADJACENT_RADIUS * CORPSE_ADJACENT_RADIUS;
 
 // --- Reducers ---
 
 /// Called by the client when they attempt to pick up a dropped item.
 #[spacetimedb::reducer]
 pub fn pickup_dropped_item(ctx: &ReducerContext, dropped_item_id: u64) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
     let sender_id = ctx.sender;
     let dropped_items_table = ctx.db.dropped_item();
     let players_table = ctx.db.player();
@@ -68,7 +87,10 @@ pub fn pickup_dropped_item(ctx: &ReducerContext, dropped_item_id: u64) -> Result
     let dropped_item = dropped_items_table.id().find(dropped_item_id)
         .ok_or_else(|| format!("Dropped item with ID {} not found.", dropped_item_id))?;
 
-    // 3. Check Proximity
+    // 3. Check Proximity and owner protection
+    if !can_pick_up(&dropped_item, sender_id, ctx.timestamp) {
+        return Err("Someone else dropped this moments ago. Only they can pick it up for now.".to_string());
+    }
     let distance_sq = get_distance_squared(player.position_x, player.position_y, dropped_item.pos_x, dropped_item.pos_y);
 
     if distance_sq > PICKUP_RADIUS_SQUARED {
@@ -83,9 +105,12 @@ pub fn pickup_dropped_item(ctx: &ReducerContext, dropped_item_id: u64) -> Result
 
     // Call the helper function from the items module
     match crate::items::add_item_to_player_inventory(ctx, sender_id, dropped_item.item_def_id, dropped_item.quantity) {
-        Ok(_) => {
+        Ok(new_instance_id) => {
+            // Restore wear on the picked-up instance (durable items never stack, so a new instance is created)
+            restore_item_state(ctx, new_instance_id, &dropped_item);
             // 5. If successful, delete the dropped item entity
             dropped_items_table.id().delete(dropped_item_id);
+            crate::spatial_grid::untrack_entity(ctx, crate::spatial_grid::EntityType::DroppedItem(dropped_item_id));
             let item_name = item_defs_table.id().find(dropped_item.item_def_id)
                                .map(|def| def.name.clone())
                                .unwrap_or_else(|| format!("[Def ID {}]", dropped_item.item_def_id));
@@ -102,6 +127,87 @@ pub fn pickup_dropped_item(ctx: &ReducerContext, dropped_item_id: u64) -> Result
     }
 }
 
+/// Picks up part of a dropped stack. The rest stays on the ground and keeps its despawn time.
+#[spacetimedb::reducer]
+pub fn pickup_partial(ctx: &ReducerContext, dropped_item_id: u64, quantity: u32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let sender_id = ctx.sender;
+    let dropped_items_table = ctx.db.dropped_item();
+
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Player not found.".to_string())?;
+    let mut dropped_item = dropped_items_table.id().find(dropped_item_id)
+        .ok_or_else(|| format!("Dropped item with ID {} not found.", dropped_item_id))?;
+    if quantity == 0 || quantity > dropped_item.quantity {
+        return Err(format!("Invalid quantity {}: the stack holds {}.", quantity, dropped_item.quantity));
+    }
+    if !can_pick_up(&dropped_item, sender_id, ctx.timestamp) {
+        return Err("Someone else dropped this moments ago. Only they can pick it up for now.".to_string());
+    }
+    if get_distance_squared(player.position_x, player.position_y, dropped_item.pos_x, dropped_item.pos_y) > PICKUP_RADIUS_SQUARED {
+        return Err("Too far away to pick up the item.".to_string());
+    }
+
+    let item_def = ctx.db.item_definition().id().find(dropped_item.item_def_id)
+        .ok_or_else(|| format!("Definition for item ID {} not found.", dropped_item.item_def_id))?;
+    let space = crate::items::player_inventory_space_for_item(ctx, sender_id, &item_def);
+    if space < quantity {
+        return Err(format!("Not enough room: you can carry {} more {}.", space, item_def.name));
+    }
+
+    let new_instance_id = add_item_to_player_inventory(ctx, sender_id, dropped_item.item_def_id, quantity)
+        .map_err(|e| format!("Could not pick up item: {}", e))?;
+    restore_item_state(ctx, new_instance_id, &dropped_item);
+
+    if quantity == dropped_item.quantity {
+        dropped_items_table.id().delete(dropped_item_id);
+        crate::spatial_grid::untrack_entity(ctx, crate::spatial_grid::EntityType::DroppedItem(dropped_item_id));
+    } else {
+        dropped_item.quantity -= quantity; // despawn_at is left as it was
+        dropped_items_table.id().update(dropped_item);
+    }
+    log::info!("[PickupDropped] Player {:?} picked up {} {} from dropped item {}.", sender_id, quantity, item_def.name, dropped_item_id);
+    Ok(())
+}
+
+/// Collects dropped items within `AUTO_PICKUP_RADIUS` of the player. Called after movement for
+/// players with auto pickup enabled. Only as much as fits is taken; the rest stays on the ground.
+pub(crate) fn auto_pickup_nearby_items(ctx: &ReducerContext, player_id: Identity, pos_x: f32, pos_y: f32) {
+    let dropped_items_table = ctx.db.dropped_item();
+    let item_defs_table = ctx.db.item_definition();
+
+    for entity in crate::spatial_grid::get_entities_in_range(ctx, pos_x, pos_y) {
+        let crate::spatial_grid::EntityType::DroppedItem(dropped_item_id) = entity else { continue; };
+        let Some(mut dropped_item) = dropped_items_table.id().find(dropped_item_id) else { continue; };
+        if !can_pick_up(&dropped_item, player_id, ctx.timestamp) {
+            continue;
+        }
+        if get_distance_squared(pos_x, pos_y, dropped_item.pos_x, dropped_item.pos_y) > AUTO_PICKUP_RADIUS_SQUARED {
+            continue;
+        }
+        let Some(item_def) = item_defs_table.id().find(dropped_item.item_def_id) else { continue; };
+
+        let quantity = dropped_item.quantity.min(crate::items::player_inventory_space_for_item(ctx, player_id, &item_def));
+        if quantity == 0 {
+            continue;
+        }
+        match add_item_to_player_inventory(ctx, player_id, dropped_item.item_def_id, quantity) {
+            Ok(new_instance_id) => {
+                restore_item_state(ctx, new_instance_id, &dropped_item);
+                if quantity >= dropped_item.quantity {
+                    dropped_items_table.id().delete(dropped_item_id);
+                    crate::spatial_grid::untrack_entity(ctx, entity);
+                } else {
+                    dropped_item.quantity -= quantity;
+                    dropped_items_table.id().update(dropped_item);
+                }
+                log::debug!("[AutoPickup] Player {:?} picked up {} {}.", player_id, quantity, item_def.name);
+            }
+            Err(e) => log::warn!("[AutoPickup] Failed to pick up item {} for player {:?}: {}", dropped_item_id, player_id, e),
+        }
+    }
+}
+
 // --- Scheduled Despawn Reducer ---
 
 /// Scheduled reducer that runs periodically to remove expired dropped items.
@@ -110,39 +216,30 @@ pub fn pickup_dropped_item(ctx: &ReducerContext, dropped_item_id: u64) -> Result
 pub fn despawn_expired_items(ctx: &ReducerContext, _schedule: DroppedItemDespawnSchedule) -> Result<(), String> {
     let current_time = ctx.timestamp;
     let dropped_items_table = ctx.db.dropped_item();
-    let item_defs_table = ctx.db.item_definition(); // <<< ADDED: Need ItemDefinition table
     let mut items_to_despawn: Vec<u64> = Vec::new();
     let mut despawn_count = 0;
 
     log::trace!("[DespawnCheck] Running scheduled check for expired dropped items at {:?}", current_time);
 
     for item in dropped_items_table.iter() {
-        // --- Get respawn time from ItemDefinition --- 
-        let item_def_respawn_seconds = match item_defs_table.id().find(item.item_def_id) {
-            Some(def) => def.respawn_time_seconds.unwrap_or(300), // Default to 5 mins if not set
-            None => {
-                log::warn!("[DespawnCheck] ItemDefinition not found for dropped item ID {} (DefID {}). Using default despawn time.", item.id, item.item_def_id);
-                300 // Default to 5 mins if definition is missing
-            }
-        };
-
-        // Calculate elapsed time in microseconds
-        let elapsed_micros = current_time.to_micros_since_unix_epoch()
-                               .saturating_sub(item.created_at.to_micros_since_unix_epoch());
-        // Ensure comparison is between i64
-        let elapsed_seconds = (elapsed_micros / 1_000_000) as i64;
-
-        if elapsed_seconds >= item_def_respawn_seconds as i64 { // <<< MODIFIED: Use item_def_respawn_seconds
-            log::info!("[DespawnCheck] Despawning item ID {} (DefID {}, created at {:?}, elapsed: {}s, despawn_time: {}s)", 
-                     item.id, item.item_def_id, item.created_at, elapsed_seconds, item_def_respawn_seconds);
-            items_to_despawn.push(item.id);
+        if current_time < item.despawn_at {
+            continue;
+        }
+        // Loot spilled around a corpse stays as long as the corpse does
+        if is_near_player_corpse(ctx, item.pos_x, item.pos_y) {
+            log::trace!("[DespawnCheck] Keeping expired item ID {} next to a player corpse.", item.id);
+            continue;
         }
+        log::info!("[DespawnCheck] Despawning item ID {} (DefID {}, created at {:?}, despawn at {:?})",
+                 item.id, item.item_def_id, item.created_at, item.despawn_at);
+        items_to_despawn.push(item.id);
     }
 
     // Delete the expired items
     for item_id in items_to_despawn {
         if dropped_items_table.id().find(item_id).is_some() { // Check if still exists
             dropped_items_table.id().delete(item_id);
+            crate::spatial_grid::untrack_entity(ctx, crate::spatial_grid::EntityType::DroppedItem(item_id));
             despawn_count += 1;
         } else {
             log::warn!("[DespawnCheck] Tried to despawn item ID {}, but it was already gone.", item_id);
@@ -153,6 +250,7 @@ pub fn despawn_expired_items(ctx: &ReducerContext, _schedule: DroppedItemDespawn
         log::info!("[DespawnCheck] Despawned {} items.", despawn_count);
     }
 
+    consolidate_dropped_items(ctx);
     Ok(())
 }
 
@@ -167,9 +265,55 @@ pub(crate) fn create_dropped_item_entity(
     pos_x: f32,
     pos_y: f32,
 ) -> Result<(), String> { // Changed return type to Result<(), String> as we don't need the entity back
+    create_dropped_item_entity_with_durability(ctx, item_def_id, quantity, pos_x, pos_y, None, None)
+}
+
+/// Same as `create_dropped_item_entity`, but keeps the current durability of a worn tool
+/// and the charges of a charged item.
+pub(crate) fn create_dropped_item_entity_with_durability(
+    ctx: &ReducerContext,
+    item_def_id: u64,
+    quantity: u32,
+    pos_x: f32,
+    pos_y: f32,
+    durability: Option<f32>,
+    charges: Option<u32>,
+) -> Result<(), String> {
+    create_owned_dropped_item_entity(ctx, None, item_def_id, quantity, pos_x, pos_y, durability, charges)
+}
+
+/// Creates a dropped item belonging to a player. Only the owner can pick it up until the
+/// configured protection window has passed; `None` creates an unprotected drop.
+pub(crate) fn create_owned_dropped_item_entity(
+    ctx: &ReducerContext,
+    owner_id: Option<Identity>,
+    item_def_id: u64,
+    quantity: u32,
+    pos_x: f32,
+    pos_y: f32,
+    durability: Option<f32>,
+    charges: Option<u32>,
+) -> Result<(), String> {
     // --- ADD: Calculate chunk index ---
     let chunk_idx = calculate_chunk_index(pos_x, pos_y);
     // --- END ADD ---
+    let protection_secs = crate::game_config::get_game_config(ctx).dropped_item_protection_secs;
+    let protected_until = owner_id
+        .filter(|_| protection_secs > 0.0)
+        .map(|_| ctx.timestamp + TimeDuration::from_micros((protection_secs * 1_000_000.0) as i64));
+
+    // Top up nearby stacks of the same item first; only what doesn't fit becomes a new drop
+    let quantity = if durability.is_none() && charges.is_none() {
+        merge_into_nearby_drops(ctx, owner_id, item_def_id, quantity, pos_x, pos_y, protected_until)
+    } else {
+        quantity
+    };
+    if quantity == 0 {
+        return Ok(());
+    }
+    let despawn_secs = ctx.db.item_definition().id().find(item_def_id)
+        .map_or(ItemRarity::Common, |def| def.rarity)
+        .dropped_despawn_secs();
      let new_dropped_item = DroppedItem {
         id: 0, // Auto-incremented
         item_def_id,
@@ -178,10 +322,16 @@ pub(crate) fn create_dropped_item_entity(
         pos_y,
         chunk_index: chunk_idx, // <<< SET chunk_index
         created_at: ctx.timestamp,
+        durability,
+        charges,
+        owner_id,
+        protected_until,
+        despawn_at: ctx.timestamp + Duration::from_secs(despawn_secs),
     };
 
     match ctx.db.dropped_item().try_insert(new_dropped_item) {
-        Ok(_) => {
+        Ok(inserted) => {
+            crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::DroppedItem(inserted.id), pos_x, pos_y);
             log::info!("[CreateDroppedItem] Created dropped item entity (DefID: {}, Qty: {}) at ({:.1}, {:.1})",
                      item_def_id, quantity, pos_x, pos_y);
             Ok(())
@@ -193,6 +343,142 @@ pub(crate) fn create_dropped_item_entity(
     }
 }
 
+/// Copies the durability and charges of a dropped item onto the instance it was picked up as.
+/// Durable and charged items never stack, so pickup always creates a new instance for them.
+fn restore_item_state(ctx: &ReducerContext, new_instance_id: Option<u64>, dropped_item: &DroppedItem) {
+    let Some(instance_id) = new_instance_id else { return; };
+    if dropped_item.durability.is_none() && dropped_item.charges.is_none() {
+        return;
+    }
+    let inventory_items = ctx.db.inventory_item();
+    if let Some(mut new_item) = inventory_items.instance_id().find(instance_id) {
+        new_item.durability = dropped_item.durability;
+        new_item.charges = dropped_item.charges;
+        inventory_items.instance_id().update(new_item);
+    }
+}
+
+/// Adds up to `quantity` of the item to stacks already on the ground within `MERGE_RADIUS`
+/// that have the same item and owner and aren't full. Returns the quantity left over.
+fn merge_into_nearby_drops(
+    ctx: &ReducerContext,
+    owner_id: Option<Identity>,
+    item_def_id: u64,
+    quantity: u32,
+    pos_x: f32,
+    pos_y: f32,
+    protected_until: Option<Timestamp>,
+) -> u32 {
+    let Some(item_def) = ctx.db.item_definition().id().find(item_def_id) else { return quantity; };
+    if !item_def.is_stackable {
+        return quantity;
+    }
+    let dropped_items_table = ctx.db.dropped_item();
+    let mut remaining = quantity;
+    for entity in crate::spatial_grid::get_entities_in_radius(ctx, pos_x, pos_y, MERGE_RADIUS) {
+        if remaining == 0 {
+            break;
+        }
+        let crate::spatial_grid::EntityType::DroppedItem(dropped_item_id) = entity else { continue; };
+        let Some(mut existing) = dropped_items_table.id().find(dropped_item_id) else { continue; };
+        if existing.item_def_id != item_def_id || existing.owner_id != owner_id || existing.durability.is_some() || existing.charges.is_some()
+            || get_distance_squared(pos_x, pos_y, existing.pos_x, existing.pos_y) > MERGE_RADIUS_SQUARED {
+            continue;
+        }
+        let space = item_def.stack_size.saturating_sub(existing.quantity);
+        let added = remaining.min(space);
+        if added == 0 {
+            continue;
+        }
+        existing.quantity += added;
+        // Fresh items restart the despawn timer
+        existing.created_at = ctx.timestamp;
+        existing.despawn_at = existing.despawn_at.max(ctx.timestamp + Duration::from_secs(item_def.rarity.dropped_despawn_secs()));
+        existing.protected_until = existing.protected_until.max(protected_until);
+        dropped_items_table.id().update(existing);
+        remaining -= added;
+        log::debug!("[CreateDroppedItem] Merged {} of DefID {} into dropped item {}.", added, item_def_id, dropped_item_id);
+    }
+    remaining
+}
+
+/// Merges dropped stacks of the same item and owner lying close together in the same
+/// chunk, so drops spilled before merging existed (or by many sources) don't pile up.
+/// Runs with the despawn check.
+fn consolidate_dropped_items(ctx: &ReducerContext) {
+    let dropped_items_table = ctx.db.dropped_item();
+    let item_defs_table = ctx.db.item_definition();
+    let mut by_chunk: HashMap<u32, Vec<DroppedItem>> = HashMap::new();
+    for item in dropped_items_table.iter().filter(|item| item.durability.is_none()) {
+        by_chunk.entry(item.chunk_index).or_default().push(item);
+    }
+
+    let mut removed = 0;
+    for (_, mut items) in by_chunk {
+        if items.len() < 2 {
+            continue;
+        }
+        // Largest stacks absorb the smaller ones
+        items.sort_by_key(|item| std::cmp::Reverse(item.quantity));
+        for i in 0..items.len() {
+            if items[i].quantity == 0 {
+                continue;
+            }
+            let Some(stack_size) = item_defs_table.id().find(items[i].item_def_id)
+                .filter(|def| def.is_stackable)
+                .map(|def| def.stack_size) else { continue; };
+            let mut target_changed = false;
+            for j in (i + 1)..items.len() {
+                let (target, source) = (&items[i], &items[j]);
+                if source.quantity == 0 || source.item_def_id != target.item_def_id || source.owner_id != target.owner_id
+                    || get_distance_squared(target.pos_x, target.pos_y, source.pos_x, source.pos_y) > MERGE_RADIUS_SQUARED {
+                    continue;
+                }
+                let moved = source.quantity.min(stack_size.saturating_sub(target.quantity));
+                if moved == 0 {
+                    break; // Target is full
+                }
+                items[i].quantity += moved;
+                items[i].protected_until = items[i].protected_until.max(items[j].protected_until);
+                items[i].despawn_at = items[i].despawn_at.max(items[j].despawn_at);
+                items[j].quantity -= moved;
+                target_changed = true;
+                if items[j].quantity == 0 {
+                    dropped_items_table.id().delete(items[j].id);
+                    crate::spatial_grid::untrack_entity(ctx, crate::spatial_grid::EntityType::DroppedItem(items[j].id));
+                    removed += 1;
+                } else {
+                    dropped_items_table.id().update(items[j].clone());
+                }
+            }
+            if target_changed {
+                dropped_items_table.id().update(items[i].clone());
+            }
+        }
+    }
+    if removed > 0 {
+        log::info!("[DespawnCheck] Consolidated {} dropped item stacks into nearby stacks.", removed);
+    }
+}
+
+/// Whether a player corpse lies within `CORPSE_ADJACENT_RADIUS` of (pos_x, pos_y).
+fn is_near_player_corpse(ctx: &ReducerContext, pos_x: f32, pos_y: f32) -> bool {
+    let corpses = ctx.db.player_corpse();
+    crate::spatial_grid::get_entities_in_radius(ctx, pos_x, pos_y, CORPSE_ADJACENT_RADIUS).into_iter().any(|entity| {
+        let crate::spatial_grid::EntityType::PlayerCorpse(corpse_id) = entity else { return false; };
+        corpses.id().find(corpse_id)
+            .is_some_and(|corpse| get_distance_squared(pos_x, pos_y, corpse.pos_x, corpse.pos_y) <= CORPSE_ADJACENT_RADIUS_SQUARED)
+    })
+}
+
+/// Whether the player may pick up the item: anyone can once its protection has run out.
+pub(crate) fn can_pick_up(item: &DroppedItem, player_id: Identity, now: Timestamp) -> bool {
+    match (item.owner_id, item.protected_until) {
+        (Some(owner_id), Some(protected_until)) => owner_id == player_id || now >= protected_until,
+        _ => true,
+    }
+}
+
 /// Calculates a position slightly in front of the player based on their direction.
 pub(crate) fn calculate_drop_position(player: &Player) -> (f32, f32) {
     let mut drop_x = player.position_x;
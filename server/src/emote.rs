@@ -0,0 +1,155 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines emotes (waves, dances, points...). `play_emote` writes a row to    *
+ * the public PlayerEmote table for clients to animate; a player has at most  *
+ * one emote playing, and starting another replaces it. Rows are transient:   *
+ * a schedule removes them once the emote has finished. A short per-player    *
+ * cooldown keeps emotes from being spammed.                                  *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, TimeDuration, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+
+use crate::player as PlayerTableTrait;
+use crate::emote::player_emote as PlayerEmoteTableTrait;
+use crate::emote::emote_cooldown as EmoteCooldownTableTrait;
+use crate::emote::emote_cleanup_schedule as EmoteCleanupScheduleTableTrait;
+
+// --- Constants ---
+/// (emote id, seconds it plays for). Clients must use these ids.
+const EMOTES: &[(&str, u64)] = &[
+    ("wave", 2),
+    ("point", 2),
+    ("thumbs_up", 2),
+    ("laugh", 3),
+    ("sit", 10),
+    ("dance", 8),
+];
+/// Minimum time between two emotes from the same player.
+const EMOTE_COOLDOWN_SECS: u64 = 2;
+const EMOTE_CLEANUP_INTERVAL_SECS: u64 = 2;
+
+/// --- Player Emote ---
+/// The emote a player is currently playing.
+#[spacetimedb::table(name = player_emote, public)]
+#[derive(Clone, Debug)]
+pub struct PlayerEmote {
+    #[primary_key]
+    pub player_id: Identity,
+    pub emote_id: String,
+    pub started_at: Timestamp,
+    pub ends_at: Timestamp,
+}
+
+/// --- Emote Cooldown ---
+/// When each player last started an emote. Kept separately because emote rows are deleted.
+#[spacetimedb::table(name = emote_cooldown)]
+#[derive(Clone, Debug)]
+pub struct EmoteCooldown {
+    #[primary_key]
+    pub player_id: Identity,
+    pub last_emote_at: Timestamp,
+}
+
+/// --- Emote Cleanup Schedule ---
+#[spacetimedb::table(name = emote_cleanup_schedule, scheduled(cleanup_finished_emotes))]
+#[derive(Clone, Debug)]
+pub struct EmoteCleanupSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Play Emote ---
+/// Starts an emote for the sender, replacing any emote they are already playing.
+#[spacetimedb::reducer]
+pub fn play_emote(ctx: &ReducerContext, emote_id: String) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let sender_id = ctx.sender;
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Player not found.".to_string())?;
+    if player.is_dead {
+        return Err("Cannot emote while dead.".to_string());
+    }
+
+    let &(_, duration_secs) = EMOTES.iter()
+        .find(|(id, _)| *id == emote_id)
+        .ok_or_else(|| format!("Unknown emote '{}'.", emote_id))?;
+
+    let cooldowns = ctx.db.emote_cooldown();
+    let cooldown = TimeDuration::from(Duration::from_secs(EMOTE_COOLDOWN_SECS));
+    if let Some(last) = cooldowns.player_id().find(sender_id) {
+        if ctx.timestamp < last.last_emote_at + cooldown {
+            return Err("You are emoting too quickly.".to_string());
+        }
+        cooldowns.player_id().update(EmoteCooldown { player_id: sender_id, last_emote_at: ctx.timestamp });
+    } else {
+        cooldowns.insert(EmoteCooldown { player_id: sender_id, last_emote_at: ctx.timestamp });
+    }
+
+    let emotes = ctx.db.player_emote();
+    let emote = PlayerEmote {
+        player_id: sender_id,
+        emote_id,
+        started_at: ctx.timestamp,
+        ends_at: ctx.timestamp + TimeDuration::from(Duration::from_secs(duration_secs)),
+    };
+    log::debug!("[Emote] Player {:?} plays '{}'.", sender_id, emote.emote_id);
+    if emotes.player_id().find(sender_id).is_some() {
+        emotes.player_id().update(emote);
+    } else {
+        emotes.insert(emote);
+    }
+    Ok(())
+}
+
+/// --- Stop Emote ---
+/// Ends the sender's current emote early, e.g. when they start moving.
+#[spacetimedb::reducer]
+pub fn stop_emote(ctx: &ReducerContext) -> Result<(), String> {
+    ctx.db.player_emote().player_id().delete(ctx.sender);
+    Ok(())
+}
+
+/******************************************************************************
+ *                           SCHEDULED REDUCERS                               *
+ ******************************************************************************/
+
+/// Starts the emote cleanup schedule if it isn't already scheduled. Called from `init_module`.
+pub fn init_emote_cleanup_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.emote_cleanup_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting emote cleanup schedule (every {}s).", EMOTE_CLEANUP_INTERVAL_SECS);
+        let interval = Duration::from_secs(EMOTE_CLEANUP_INTERVAL_SECS);
+        schedule_table.try_insert(EmoteCleanupSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert emote cleanup schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: removes emotes that have finished playing.
+#[spacetimedb::reducer]
+pub fn cleanup_finished_emotes(ctx: &ReducerContext, _schedule: EmoteCleanupSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("cleanup_finished_emotes may only be called by the scheduler.".to_string());
+    }
+
+    let emotes = ctx.db.player_emote();
+    let finished: Vec<Identity> = emotes.iter()
+        .filter(|e| ctx.timestamp >= e.ends_at)
+        .map(|e| e.player_id)
+        .collect();
+    for player_id in finished {
+        emotes.player_id().delete(player_id);
+    }
+    Ok(())
+}
@@ -0,0 +1,138 @@
+/******************************************************************************
+ *                                                                            *
+ * Inventory weight and encumbrance. Every ItemDefinition has a per-unit      *
+ * weight; the global tick sums what each player carries (inventory, hotbar   *
+ * and equipped items) into the public PlayerWeight table so the UI can show  *
+ * it. Past ENCUMBERED_WEIGHT a player can't sprint and moves slower; past    *
+ * OVERLOADED_WEIGHT they slow to a crawl. Containers can also declare a      *
+ * weight cap (see `ItemContainer::max_weight`).                              *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+use std::collections::HashMap;
+
+use crate::player as PlayerTableTrait;
+use crate::items::{ItemDefinition, inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
+use crate::inventory_management::ItemContainer;
+use crate::encumbrance::player_weight as PlayerWeightTableTrait;
+
+// --- Constants ---
+/// Carried weight at which a player becomes encumbered.
+pub(crate) const ENCUMBERED_WEIGHT: f32 = 50.0;
+/// Carried weight at which a player becomes overloaded.
+pub(crate) const OVERLOADED_WEIGHT: f32 = 80.0;
+const ENCUMBERED_SPEED_MULTIPLIER: f32 = 0.8;
+const OVERLOADED_SPEED_MULTIPLIER: f32 = 0.5;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum EncumbranceLevel {
+    Unencumbered,
+    Encumbered, // Slower, no sprinting
+    Overloaded, // Much slower, no sprinting
+}
+
+/// --- Player Weight ---
+/// How much a player is carrying, refreshed every global tick.
+#[spacetimedb::table(name = player_weight, public)]
+#[derive(Clone, Debug)]
+pub struct PlayerWeight {
+    #[primary_key]
+    pub player_id: Identity,
+    pub carried_weight: f32,
+    pub level: EncumbranceLevel,
+    pub updated_at: Timestamp,
+}
+
+/// Weight of `quantity` units of an item.
+pub(crate) fn item_weight(item_def: &ItemDefinition, quantity: u32) -> f32 {
+    item_def.weight * quantity as f32
+}
+
+/// Encumbrance level for a carried weight.
+pub(crate) fn encumbrance_level(carried_weight: f32) -> EncumbranceLevel {
+    if carried_weight >= OVERLOADED_WEIGHT {
+        EncumbranceLevel::Overloaded
+    } else if carried_weight >= ENCUMBERED_WEIGHT {
+        EncumbranceLevel::Encumbered
+    } else {
+        EncumbranceLevel::Unencumbered
+    }
+}
+
+/// Recomputes every player's carried weight. Called every global tick.
+/// Rows are only written when the weight changed, to spare subscribers.
+pub(crate) fn tick_player_weights(ctx: &ReducerContext) {
+    let item_defs = ctx.db.item_definition();
+    let def_weights: HashMap<u64, f32> = item_defs.iter().map(|def| (def.id, def.weight)).collect();
+    let mut weights: HashMap<Identity, f32> = HashMap::new();
+    for item in ctx.db.inventory_item().iter() {
+        if let Some(owner_id) = item.location.is_player_bound() {
+            let unit_weight = def_weights.get(&item.item_def_id).copied().unwrap_or(0.0);
+            *weights.entry(owner_id).or_insert(0.0) += unit_weight * item.quantity as f32;
+        }
+    }
+
+    let player_weights = ctx.db.player_weight();
+    for player in ctx.db.player().iter() {
+        let carried_weight = weights.get(&player.identity).copied().unwrap_or(0.0);
+        let row = PlayerWeight {
+            player_id: player.identity,
+            carried_weight,
+            level: encumbrance_level(carried_weight),
+            updated_at: ctx.timestamp,
+        };
+        match player_weights.player_id().find(player.identity) {
+            Some(existing) if (existing.carried_weight - carried_weight).abs() < f32::EPSILON => {}
+            Some(_) => { player_weights.player_id().update(row); }
+            None => { player_weights.insert(row); }
+        }
+    }
+}
+
+/// The player's encumbrance level as of the last global tick.
+pub(crate) fn get_encumbrance_level(ctx: &ReducerContext, player_id: Identity) -> EncumbranceLevel {
+    ctx.db.player_weight().player_id().find(player_id)
+        .map_or(EncumbranceLevel::Unencumbered, |w| w.level)
+}
+
+/// Movement speed multiplier from carried weight (1.0 = unaffected).
+pub(crate) fn get_movement_speed_multiplier(ctx: &ReducerContext, player_id: Identity) -> f32 {
+    match get_encumbrance_level(ctx, player_id) {
+        EncumbranceLevel::Unencumbered => 1.0,
+        EncumbranceLevel::Encumbered => ENCUMBERED_SPEED_MULTIPLIER,
+        EncumbranceLevel::Overloaded => OVERLOADED_SPEED_MULTIPLIER,
+    }
+}
+
+/// Whether the player is light enough to sprint.
+pub(crate) fn can_sprint(ctx: &ReducerContext, player_id: Identity) -> bool {
+    get_encumbrance_level(ctx, player_id) == EncumbranceLevel::Unencumbered
+}
+
+/// Total weight of the items in a container's slots.
+pub(crate) fn container_weight<C: ItemContainer>(ctx: &ReducerContext, container: &C) -> f32 {
+    let inventory_items = ctx.db.inventory_item();
+    let item_defs = ctx.db.item_definition();
+    (0..container.num_slots() as u8)
+        .filter_map(|slot| container.get_slot_instance_id(slot))
+        .filter_map(|instance_id| inventory_items.instance_id().find(instance_id))
+        .filter_map(|item| item_defs.id().find(item.item_def_id).map(|def| item_weight(&def, item.quantity)))
+        .sum()
+}
+
+/// Errors if putting `added_weight` more into the container (and taking `removed_weight` out,
+/// for swaps) would go over its weight cap. Containers without a cap always pass.
+pub(crate) fn check_container_weight_cap<C: ItemContainer>(
+    ctx: &ReducerContext,
+    container: &C,
+    added_weight: f32,
+    removed_weight: f32,
+) -> Result<(), String> {
+    let Some(max_weight) = container.max_weight() else { return Ok(()); };
+    let new_weight = container_weight(ctx, container) + added_weight - removed_weight;
+    if added_weight > removed_weight && new_weight > max_weight {
+        return Err(format!("That would be too heavy for this container ({:.1} / {:.1}).", new_weight, max_weight));
+    }
+    Ok(())
+}
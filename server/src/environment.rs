@@ -5,17 +5,30 @@
  *          excluding player-specific state.
  *
  * Responsibilities:
- *   - `seed_environment`: Populates the world with initial resources (trees, stones, mushrooms)
- *                         on server startup if the environment is empty. Uses helpers from `utils.rs`.
+ *   - `seed_environment`: Seeds clouds and the minimap summary (`map_summary.rs`) and generates
+ *                         the chunks around the default spawn.
+ *   - `ensure_chunk_generated`: Lazily generates a chunk's water and resources (trees, stones,
+ *                               mushrooms, berry bushes, loot crates, ...) from the world seed the
+ *                               first time it is observed. Deserts and forests also get their
+ *                               environmental hazards (cacti, hot stones, thorn bushes).
+ *                               The chunk's minimap summary is filled in once it is generated.
+ *                               Uses helpers from `utils.rs`.
+ *   - `biome_for_chunk` / `get_biome_at`: Assigns each chunk a biome (forest, snow, desert)
+ *                               from the world seed. Biomes change warmth and thirst drain.
+ *   - `richness_for_position`: Rolls the richness tier of trees and stones, favouring rich nodes
+ *                               far from spawn.
  *   - `check_resource_respawns`: Checks periodically if any depleted resources (trees, stones,
- *                                mushrooms with `respawn_at` set) are ready to respawn.
+ *                                mushrooms, loot crates, hazards with `respawn_at` set) are ready
+ *                                to respawn.
+ *                                Mushrooms and hemp only regrow at night, corn and pumpkins only
+ *                                during the day.
  *                                Uses a macro from `utils.rs` for conciseness.
  *
  * Note: Resource definitions (structs, constants) are in their respective modules (e.g., `tree.rs`).
  */
 
 // server/src/environment.rs
-use spacetimedb::{ReducerContext, Table, Timestamp};
+use spacetimedb::{ReducerContext, SpacetimeType, Table, Timestamp};
 use crate::{WORLD_WIDTH_PX, WORLD_HEIGHT_PX, TILE_SIZE_PX, WORLD_WIDTH_TILES, WORLD_HEIGHT_TILES};
 
 // Import resource modules
@@ -34,11 +47,17 @@ use crate::mushroom::mushroom as MushroomTableTrait;
 use crate::corn::corn as CornTableTrait;
 use crate::pumpkin::pumpkin as PumpkinTableTrait;
 use crate::hemp::hemp as HempTableTrait;
+use crate::berry_bush::berry_bush as BerryBushTableTrait;
+use crate::loot_crate::loot_crate as LootCrateTableTrait;
+use crate::environment_hazard::environment_hazard as EnvironmentHazardTableTrait;
 use crate::items::ItemDefinition;
+use crate::models::ResourceRichness;
 use crate::cloud::{Cloud, CloudShapeType, CloudUpdateSchedule};
 use crate::utils::*;
 use crate::cloud::cloud as CloudTableTrait;
 use crate::cloud::cloud_update_schedule as CloudUpdateScheduleTableTrait;
+use crate::world_state::world_state as WorldStateTableTrait;
+use crate::environment::generated_chunk as GeneratedChunkTableTrait;
 
 // Import utils helpers and macro
 use crate::utils::{calculate_tile_bounds, attempt_single_spawn};
@@ -47,7 +66,6 @@ use crate::check_and_respawn_resource; // Import the macro
 use noise::{NoiseFn, Perlin, Fbm};
 use rand::{Rng, SeedableRng};
 use rand::rngs::StdRng;
-use std::collections::HashSet;
 use log;
 
 // --- Constants for Chunk Calculation ---
@@ -55,6 +73,8 @@ use log;
 pub const CHUNK_SIZE_TILES: u32 = 20;
 // World width in chunks
 pub const WORLD_WIDTH_CHUNKS: u32 = (WORLD_WIDTH_TILES + CHUNK_SIZE_TILES - 1) / CHUNK_SIZE_TILES;
+// World height in chunks
+pub const WORLD_HEIGHT_CHUNKS: u32 = WORLD_HEIGHT_TILES.div_ceil(CHUNK_SIZE_TILES);
 // Size of a chunk in pixels
 pub const CHUNK_SIZE_PX: f32 = CHUNK_SIZE_TILES as f32 * TILE_SIZE_PX as f32;
 
@@ -72,78 +92,245 @@ pub fn calculate_chunk_index(pos_x: f32, pos_y: f32) -> u32 {
     chunk_y * WORLD_WIDTH_CHUNKS + chunk_x
 }
 
+/// Recomputes a moving entity's `chunk_index` for its new position. Returns true if it
+/// crossed into another chunk. Anything that moves after it's placed (players, animals,
+/// mounts, projectiles, clouds) calls this whenever its position changes.
+pub fn sync_chunk_index(chunk_index: &mut u32, pos_x: f32, pos_y: f32) -> bool {
+    let new_chunk_index = calculate_chunk_index(pos_x, pos_y);
+    if *chunk_index == new_chunk_index {
+        return false;
+    }
+    *chunk_index = new_chunk_index;
+    true
+}
+
+// --- Chunk Generation Tracking ---
+
+/// Climate of a chunk. Affects warmth and thirst drain (see `player_stats`).
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum Biome {
+    Forest,
+    Snow,   // Cold even during the day
+    Desert, // Thirst drains faster
+}
+
+/// Marks a chunk whose terrain and resources have already been generated from the world seed.
+/// Public so clients can tint terrain by biome.
+#[spacetimedb::table(name = generated_chunk, public)]
+#[derive(Clone, Debug)]
+pub struct GeneratedChunk {
+    #[primary_key]
+    pub chunk_index: u32,
+    pub generated_at: Timestamp,
+    pub biome: Biome,
+}
+
+// --- Biomes ---
+const BIOME_NOISE_SALT: u32 = 0xB10E;
+const BIOME_NOISE_FREQUENCY: f64 = 0.12; // Per chunk; biomes span several chunks
+const BIOME_COLD_THRESHOLD: f64 = -0.3;
+const BIOME_HOT_THRESHOLD: f64 = 0.3;
+
+/// Biome of a chunk. Depends only on the world seed, so it is the same whether or not
+/// the chunk has been generated yet. The area around the default spawn is always forest.
+pub fn biome_for_chunk(world_seed: u64, chunk_index: u32) -> Biome {
+    let chunk_x = (chunk_index % WORLD_WIDTH_CHUNKS) as i32;
+    let chunk_y = (chunk_index / WORLD_WIDTH_CHUNKS) as i32;
+    let spawn_chunk = calculate_chunk_index(DEFAULT_SPAWN_POS.0, DEFAULT_SPAWN_POS.1);
+    let spawn_chunk_x = (spawn_chunk % WORLD_WIDTH_CHUNKS) as i32;
+    let spawn_chunk_y = (spawn_chunk / WORLD_WIDTH_CHUNKS) as i32;
+    if (chunk_x - spawn_chunk_x).abs() <= SPAWN_PREGENERATE_RADIUS_CHUNKS && (chunk_y - spawn_chunk_y).abs() <= SPAWN_PREGENERATE_RADIUS_CHUNKS {
+        return Biome::Forest;
+    }
+
+    let temperature_noise = Perlin::new((world_seed as u32) ^ BIOME_NOISE_SALT);
+    let temperature = temperature_noise.get([chunk_x as f64 * BIOME_NOISE_FREQUENCY, chunk_y as f64 * BIOME_NOISE_FREQUENCY]);
+    if temperature < BIOME_COLD_THRESHOLD {
+        Biome::Snow
+    } else if temperature > BIOME_HOT_THRESHOLD {
+        Biome::Desert
+    } else {
+        Biome::Forest
+    }
+}
+
+/// Biome at a world position.
+pub fn get_biome_at(ctx: &ReducerContext, pos_x: f32, pos_y: f32) -> Biome {
+    let chunk_index = calculate_chunk_index(pos_x, pos_y);
+    if let Some(chunk) = ctx.db.generated_chunk().chunk_index().find(chunk_index) {
+        return chunk.biome;
+    }
+    let world_seed = ctx.db.world_state().iter().next().map_or(0, |world_state| world_state.world_seed);
+    biome_for_chunk(world_seed, chunk_index)
+}
+
+/// Chunks within this many chunks of the default spawn point are generated up front,
+/// so new players have a populated area to spawn into.
+const SPAWN_PREGENERATE_RADIUS_CHUNKS: i32 = 1;
+pub(crate) const DEFAULT_SPAWN_POS: (f32, f32) = (640.0, 480.0);
+/// Distance from spawn at which tree/stone richness odds stop improving.
+const RICHNESS_MAX_DISTANCE_PX: f32 = 12000.0;
+
+/// Picks a tree/stone richness tier from a 0..1 roll. Near spawn most nodes are sparse or
+/// normal; further out sparse nodes fade away and rich ones become common.
+fn richness_for_position(roll: f64, pos_x: f32, pos_y: f32) -> ResourceRichness {
+    let dx = pos_x - DEFAULT_SPAWN_POS.0;
+    let dy = pos_y - DEFAULT_SPAWN_POS.1;
+    let t = ((dx * dx + dy * dy).sqrt() / RICHNESS_MAX_DISTANCE_PX).min(1.0) as f64;
+    let sparse_chance = 0.35 - 0.30 * t; // 35% at spawn, 5% far out
+    let rich_chance = 0.05 + 0.40 * t; // 5% at spawn, 45% far out
+    if roll < sparse_chance {
+        ResourceRichness::Sparse
+    } else if roll >= 1.0 - rich_chance {
+        ResourceRichness::Rich
+    } else {
+        ResourceRichness::Normal
+    }
+}
+
 // --- Environment Seeding ---
 
+/// Seeds the global parts of the environment (clouds) and generates the chunks around the
+/// default spawn. Every other chunk is generated lazily by `ensure_chunk_generated` the first
+/// time a client observes it.
 #[spacetimedb::reducer]
 pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
+    let generated_chunks = ctx.db.generated_chunk();
+
+    // Worlds seeded before per-chunk generation already contain every resource.
+    if generated_chunks.iter().count() == 0 && ctx.db.tree().iter().count() > 0 {
+        log::info!("Environment was seeded globally. Marking all chunks as generated.");
+        let world_seed = ctx.db.world_state().iter().next().map_or(0, |world_state| world_state.world_seed);
+        for chunk_index in 0..WORLD_WIDTH_CHUNKS * WORLD_HEIGHT_CHUNKS {
+            let biome = biome_for_chunk(world_seed, chunk_index);
+            generated_chunks.insert(GeneratedChunk { chunk_index, generated_at: ctx.timestamp, biome });
+        }
+    }
+
+    seed_clouds(ctx)?;
+    crate::map_summary::seed_map_summaries(ctx)?;
+
+    let spawn_chunk = calculate_chunk_index(DEFAULT_SPAWN_POS.0, DEFAULT_SPAWN_POS.1);
+    let spawn_chunk_x = (spawn_chunk % WORLD_WIDTH_CHUNKS) as i32;
+    let spawn_chunk_y = (spawn_chunk / WORLD_WIDTH_CHUNKS) as i32;
+    for chunk_y in (spawn_chunk_y - SPAWN_PREGENERATE_RADIUS_CHUNKS)..=(spawn_chunk_y + SPAWN_PREGENERATE_RADIUS_CHUNKS) {
+        for chunk_x in (spawn_chunk_x - SPAWN_PREGENERATE_RADIUS_CHUNKS)..=(spawn_chunk_x + SPAWN_PREGENERATE_RADIUS_CHUNKS) {
+            if chunk_x < 0 || chunk_y < 0 || chunk_x as u32 >= WORLD_WIDTH_CHUNKS || chunk_y as u32 >= WORLD_HEIGHT_CHUNKS {
+                continue;
+            }
+            ensure_chunk_generated(ctx, chunk_y as u32 * WORLD_WIDTH_CHUNKS + chunk_x as u32)?;
+        }
+    }
+    Ok(())
+}
+
+/// Generates the chunk's terrain and resources if that hasn't happened yet.
+/// Called for every chunk that newly enters a client's viewport.
+pub fn ensure_chunk_generated(ctx: &ReducerContext, chunk_index: u32) -> Result<(), String> {
+    if chunk_index >= WORLD_WIDTH_CHUNKS * WORLD_HEIGHT_CHUNKS {
+        return Err(format!("Chunk {} is outside the world.", chunk_index));
+    }
+    let generated_chunks = ctx.db.generated_chunk();
+    if generated_chunks.chunk_index().find(chunk_index).is_some() {
+        return Ok(());
+    }
+    let world_seed = ctx.db.world_state().iter().next()
+        .map(|world_state| world_state.world_seed)
+        .ok_or_else(|| "WorldState not seeded; cannot generate chunks.".to_string())?;
+
+    generate_chunk(ctx, world_seed, chunk_index)?;
+    generated_chunks.insert(GeneratedChunk { chunk_index, generated_at: ctx.timestamp, biome: biome_for_chunk(world_seed, chunk_index) });
+    crate::map_summary::refresh_chunk_summary(ctx, chunk_index, true);
+    Ok(())
+}
+
+/// Per-chunk RNG seed. Mixing in the chunk index keeps neighbouring chunks uncorrelated.
+fn chunk_seed(world_seed: u64, chunk_index: u32) -> u64 {
+    world_seed ^ (chunk_index as u64 + 1).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+}
+
+/// Rounds an expected (fractional) spawn count up or down at random, so rare resources
+/// still appear in roughly `density` of tiles across many chunks.
+fn chunk_target_count(rng: &mut StdRng, chunk_tiles: u32, density: f32) -> u32 {
+    let expected = chunk_tiles as f32 * density;
+    let whole = expected.floor();
+    whole as u32 + if rng.gen::<f32>() < expected - whole { 1 } else { 0 }
+}
+
+/// Generates water and resources for a single chunk. The result depends only on the world
+/// seed and the chunk index, so a chunk looks the same no matter when it is first observed.
+fn generate_chunk(ctx: &ReducerContext, world_seed: u64, chunk_index: u32) -> Result<(), String> {
     let trees = ctx.db.tree();
     let stones = ctx.db.stone();
     let mushrooms = ctx.db.mushroom();
     let corns = ctx.db.corn();
     let pumpkins = ctx.db.pumpkin();
     let hemps = ctx.db.hemp();
-    let clouds = ctx.db.cloud();
+    let berry_bushes = ctx.db.berry_bush();
+    let loot_crates = ctx.db.loot_crate();
+    let hazards = ctx.db.environment_hazard();
+
+    let chunk_min_tile_x = (chunk_index % WORLD_WIDTH_CHUNKS) * CHUNK_SIZE_TILES;
+    let chunk_min_tile_y = (chunk_index / WORLD_WIDTH_CHUNKS) * CHUNK_SIZE_TILES;
+    let chunk_max_tile_x = (chunk_min_tile_x + CHUNK_SIZE_TILES).min(WORLD_WIDTH_TILES);
+    let chunk_max_tile_y = (chunk_min_tile_y + CHUNK_SIZE_TILES).min(WORLD_HEIGHT_TILES);
+
+    // --- Generate Water (lakes & rivers) --- Resources never spawn on water tiles
+    let water_tiles = crate::world_tile::generate_chunk_water_tiles(
+        ctx, world_seed, chunk_min_tile_x, chunk_max_tile_x, chunk_min_tile_y, chunk_max_tile_y,
+    );
 
-    if trees.iter().count() > 0 || stones.iter().count() > 0 || mushrooms.iter().count() > 0 || corns.iter().count() > 0 || pumpkins.iter().count() > 0 || hemps.iter().count() > 0 || clouds.iter().count() > 0 {
-        log::info!(
-            "Environment already seeded (Trees: {}, Stones: {}, Mushrooms: {}, Corns: {}, Hemps: {}, Pumpkins: {}, Clouds: {}). Skipping.",
-            trees.iter().count(), stones.iter().count(), mushrooms.iter().count(), corns.iter().count(), hemps.iter().count(), pumpkins.iter().count(), clouds.iter().count()
-        );
-        return Ok(());
+    // Resources keep clear of the world edge
+    let (world_min_tile_x, world_max_tile_x, world_min_tile_y, world_max_tile_y) =
+        calculate_tile_bounds(WORLD_WIDTH_TILES, WORLD_HEIGHT_TILES, crate::tree::TREE_SPAWN_WORLD_MARGIN_TILES);
+    let min_tile_x = chunk_min_tile_x.max(world_min_tile_x);
+    let max_tile_x = chunk_max_tile_x.min(world_max_tile_x);
+    let min_tile_y = chunk_min_tile_y.max(world_min_tile_y);
+    let max_tile_y = chunk_max_tile_y.min(world_max_tile_y);
+    if min_tile_x >= max_tile_x || min_tile_y >= max_tile_y {
+        return Ok(()); // Chunk lies entirely within the world margin
     }
 
-    log::info!("Seeding environment (trees, stones, mushrooms, corn, pumpkins, hemp, clouds)..." );
+    // One noise field for the whole world so resource clusters continue across chunk borders
+    let fbm = Fbm::<Perlin>::new(world_seed as u32);
+    let mut rng = StdRng::seed_from_u64(chunk_seed(world_seed, chunk_index));
 
-    let fbm = Fbm::<Perlin>::new(ctx.rng().gen());
-    let mut rng = StdRng::from_rng(ctx.rng()).map_err(|e| format!("Failed to seed RNG: {}", e))?;
+    let chunk_tiles = (max_tile_x - min_tile_x) * (max_tile_y - min_tile_y);
 
-    let total_tiles = crate::WORLD_WIDTH_TILES * crate::WORLD_HEIGHT_TILES;
+    // Plants generated outside their growing phase start dormant and sprout in the
+    // respawn check once it comes round (night for mushrooms/hemp, day for corn/pumpkins)
+    let time_of_day = crate::world_state::current_time_of_day(ctx);
+    let night_plant_respawn_at = if time_of_day.is_night() { None } else { Some(ctx.timestamp) };
+    let day_plant_respawn_at = if time_of_day.is_daytime() { None } else { Some(ctx.timestamp) };
 
     // Calculate targets and limits
-    let target_tree_count = (total_tiles as f32 * crate::tree::TREE_DENSITY_PERCENT) as u32;
+    let target_tree_count = chunk_target_count(&mut rng, chunk_tiles, crate::tree::TREE_DENSITY_PERCENT);
     let max_tree_attempts = target_tree_count * crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR;
-    let target_stone_count = (total_tiles as f32 * crate::stone::STONE_DENSITY_PERCENT) as u32;
-    let max_stone_attempts = target_stone_count * crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR; 
-    let target_mushroom_count = (total_tiles as f32 * crate::mushroom::MUSHROOM_DENSITY_PERCENT) as u32;
-    let max_mushroom_attempts = target_mushroom_count * crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR; 
-    let target_corn_count = (total_tiles as f32 * crate::corn::CORN_DENSITY_PERCENT) as u32;
+    let target_stone_count = chunk_target_count(&mut rng, chunk_tiles, crate::stone::STONE_DENSITY_PERCENT);
+    let max_stone_attempts = target_stone_count * crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR;
+    let target_mushroom_count = chunk_target_count(&mut rng, chunk_tiles, crate::mushroom::MUSHROOM_DENSITY_PERCENT);
+    let max_mushroom_attempts = target_mushroom_count * crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR;
+    let target_corn_count = chunk_target_count(&mut rng, chunk_tiles, crate::corn::CORN_DENSITY_PERCENT);
     let max_corn_attempts = target_corn_count * crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR;
-    let target_pumpkin_count = (total_tiles as f32 * crate::pumpkin::PUMPKIN_DENSITY_PERCENT) as u32;
+    let target_pumpkin_count = chunk_target_count(&mut rng, chunk_tiles, crate::pumpkin::PUMPKIN_DENSITY_PERCENT);
     let max_pumpkin_attempts = target_pumpkin_count * crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR;
-    let target_hemp_count = (total_tiles as f32 * crate::hemp::HEMP_DENSITY_PERCENT) as u32;
+    let target_hemp_count = chunk_target_count(&mut rng, chunk_tiles, crate::hemp::HEMP_DENSITY_PERCENT);
     let max_hemp_attempts = target_hemp_count * crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR;
-
-    // Cloud seeding parameters
-    const CLOUD_DENSITY_PERCENT: f32 = 0.005; // Example: 0.5% of tiles might have a cloud center
-    const MAX_CLOUD_SEEDING_ATTEMPTS_FACTOR: u32 = 3;
-    let target_cloud_count = (total_tiles as f32 * CLOUD_DENSITY_PERCENT) as u32;
-    let max_cloud_attempts = target_cloud_count * MAX_CLOUD_SEEDING_ATTEMPTS_FACTOR;
-
-    // Cloud drift parameters
-    const CLOUD_BASE_DRIFT_X: f32 = 4.0; // Base speed in pixels per second (e.g., gentle eastward drift) - Doubled
-    const CLOUD_BASE_DRIFT_Y: f32 = 1.0; // Doubled
-    const CLOUD_DRIFT_VARIATION: f32 = 1.0; // Max variation from base speed
-
-    log::info!("Target Trees: {}, Max Attempts: {}", target_tree_count, max_tree_attempts);
-    log::info!("Target Stones: {}, Max Attempts: {}", target_stone_count, max_stone_attempts);
-    log::info!("Target Mushrooms: {}, Max Attempts: {}", target_mushroom_count, max_mushroom_attempts);
-    log::info!("Target Corns: {}, Max Attempts: {}", target_corn_count, max_corn_attempts);
-    log::info!("Target Hemps: {}, Max Attempts: {}", target_hemp_count, max_hemp_attempts);
-    log::info!("Target Pumpkins: {}, Max Attempts: {}", target_pumpkin_count, max_pumpkin_attempts);
-    log::info!("Target Clouds: {}, Max Attempts: {}", target_cloud_count, max_cloud_attempts);
-    // Calculate spawn bounds using helper
-    let (min_tile_x, max_tile_x, min_tile_y, max_tile_y) = 
-        calculate_tile_bounds(WORLD_WIDTH_TILES, WORLD_HEIGHT_TILES, crate::tree::TREE_SPAWN_WORLD_MARGIN_TILES);
+    let target_berry_bush_count = chunk_target_count(&mut rng, chunk_tiles, crate::berry_bush::BERRY_BUSH_DENSITY_PERCENT);
+    let max_berry_bush_attempts = target_berry_bush_count * crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR;
+    let target_crate_count = chunk_target_count(&mut rng, chunk_tiles, crate::loot_crate::LOOT_CRATE_DENSITY_PERCENT);
+    let max_crate_attempts = target_crate_count * crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR;
 
     // Initialize tracking collections
-    let mut occupied_tiles = HashSet::<(u32, u32)>::new();
+    let mut occupied_tiles = water_tiles;
     let mut spawned_tree_positions = Vec::<(f32, f32)>::new();
     let mut spawned_stone_positions = Vec::<(f32, f32)>::new();
     let mut spawned_mushroom_positions = Vec::<(f32, f32)>::new();
     let mut spawned_corn_positions = Vec::<(f32, f32)>::new();
     let mut spawned_pumpkin_positions = Vec::<(f32, f32)>::new();
     let mut spawned_hemp_positions = Vec::<(f32, f32)>::new();
-    let mut spawned_cloud_positions = Vec::<(f32, f32)>::new();
+    let mut spawned_berry_bush_positions = Vec::<(f32, f32)>::new();
+    let mut spawned_crate_positions = Vec::<(f32, f32)>::new();
 
     let mut spawned_tree_count = 0;
     let mut tree_attempts = 0;
@@ -155,18 +342,20 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
     let mut corn_attempts = 0;
     let mut spawned_hemp_count = 0;
     let mut hemp_attempts = 0;
+    let mut spawned_berry_bush_count = 0;
+    let mut berry_bush_attempts = 0;
     let mut spawned_pumpkin_count = 0;
     let mut pumpkin_attempts = 0;
-    let mut spawned_cloud_count = 0;
-    let mut cloud_attempts = 0;
+    let mut spawned_crate_count = 0;
+    let mut crate_attempts = 0;
 
     // --- Seed Trees --- Use helper function --- 
-    log::info!("Seeding Trees...");
     while spawned_tree_count < target_tree_count && tree_attempts < max_tree_attempts {
         tree_attempts += 1;
 
-        // Determine tree type roll *before* calling attempt_single_spawn
+        // Determine tree type and richness rolls *before* calling attempt_single_spawn
         let tree_type_roll_for_this_attempt: f64 = rng.gen_range(0.0..1.0);
+        let richness_roll_for_this_attempt: f64 = rng.gen_range(0.0..1.0);
 
         match attempt_single_spawn(
             &mut rng,
@@ -181,7 +370,7 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
             crate::tree::MIN_TREE_DISTANCE_SQ,
             0.0,
             0.0,
-            |pos_x, pos_y, tree_type_roll: f64| { // Closure now accepts the pre-calculated roll
+            |pos_x, pos_y, (tree_type_roll, richness_roll): (f64, f64)| { // Closure now accepts the pre-calculated rolls
                 // Calculate chunk index for the tree
                 let chunk_idx = calculate_chunk_index(pos_x, pos_y);
                 
@@ -194,18 +383,21 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
                     crate::tree::TreeType::MannaAsh
                 };
                 
+                let richness = richness_for_position(richness_roll, pos_x, pos_y);
                 crate::tree::Tree {
                     id: 0,
                     pos_x,
                     pos_y,
-                    health: crate::tree::TREE_INITIAL_HEALTH,
+                    health: richness.scale_health(crate::tree::TREE_INITIAL_HEALTH),
                     tree_type, // Assign the chosen type
+                    richness,
                     chunk_index: chunk_idx, // Set the chunk index
                     last_hit_time: None,
                     respawn_at: None,
+                    sweet_spot_angle: crate::sweet_spot::initial_sweet_spot_angle(pos_x, pos_y),
                 }
             },
-            tree_type_roll_for_this_attempt, // Pass the roll as extra_args
+            (tree_type_roll_for_this_attempt, richness_roll_for_this_attempt), // Pass the rolls as extra_args
             trees,
         ) {
             Ok(true) => spawned_tree_count += 1,
@@ -213,16 +405,12 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
             Err(_) => { /* Error already logged in helper, continue */ }
         }
     }
-     log::info!(
-        "Finished seeding {} trees (target: {}, attempts: {}).",
-        spawned_tree_count, target_tree_count, tree_attempts
-    );
-
+ 
     // --- Seed Stones --- Use helper function ---
-    log::info!("Seeding Stones...");
     while spawned_stone_count < target_stone_count && stone_attempts < max_stone_attempts {
         stone_attempts += 1;
-         match attempt_single_spawn(
+        let richness_roll_for_this_attempt: f64 = rng.gen_range(0.0..1.0);
+        match attempt_single_spawn(
             &mut rng,
             &mut occupied_tiles,
             &mut spawned_stone_positions,
@@ -235,21 +423,24 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
             crate::stone::MIN_STONE_DISTANCE_SQ,
             crate::stone::MIN_STONE_TREE_DISTANCE_SQ,
             0.0,
-            |pos_x, pos_y, _extra: ()| {
+            |pos_x, pos_y, richness_roll: f64| {
                 // Calculate chunk index for the stone
                 let chunk_idx = calculate_chunk_index(pos_x, pos_y);
+                let richness = richness_for_position(richness_roll, pos_x, pos_y);
                 
                 crate::stone::Stone {
                     id: 0,
                     pos_x,
                     pos_y,
-                    health: crate::stone::STONE_INITIAL_HEALTH,
+                    health: richness.scale_health(crate::stone::STONE_INITIAL_HEALTH),
+                    richness,
                     chunk_index: chunk_idx, // Set the chunk index
                     last_hit_time: None,
                     respawn_at: None,
+                    sweet_spot_angle: crate::sweet_spot::initial_sweet_spot_angle(pos_x, pos_y),
                 }
             },
-            (),
+            richness_roll_for_this_attempt,
             stones,
         ) {
             Ok(true) => spawned_stone_count += 1,
@@ -257,13 +448,8 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
             Err(_) => { /* Error already logged in helper, continue */ }
         }
     }
-    log::info!(
-        "Finished seeding {} stones (target: {}, attempts: {}).",
-        spawned_stone_count, target_stone_count, stone_attempts
-    );
 
     // --- Seed Mushrooms --- Use helper function ---
-    log::info!("Seeding Mushrooms...");
     let mushroom_noise_threshold = 0.65; // Specific threshold for mushrooms
     while spawned_mushroom_count < target_mushroom_count && mushroom_attempts < max_mushroom_attempts {
         mushroom_attempts += 1;
@@ -289,7 +475,7 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
                     pos_x,
                     pos_y,
                     chunk_index: chunk_idx, // Set the chunk index
-                    respawn_at: None,
+                    respawn_at: night_plant_respawn_at,
                 }
             },
             (),
@@ -300,13 +486,8 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
             Err(_) => { /* Error already logged in helper, continue */ }
         }
     }
-    log::info!(
-        "Finished seeding {} mushrooms (target: {}, attempts: {}).",
-        spawned_mushroom_count, target_mushroom_count, mushroom_attempts
-    );
 
     // --- Seed Corn --- Use helper function ---
-    log::info!("Seeding Corn...");
     let corn_noise_threshold = 0.70; // Specific threshold for corn
     while spawned_corn_count < target_corn_count && corn_attempts < max_corn_attempts {
         corn_attempts += 1;
@@ -332,7 +513,7 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
                     pos_x,
                     pos_y,
                     chunk_index: chunk_idx, // Set the chunk index
-                    respawn_at: None,
+                    respawn_at: day_plant_respawn_at,
                 }
             },
             (),
@@ -343,13 +524,8 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
             Err(_) => { /* Error already logged in helper, continue */ }
         }
     }
-    log::info!(
-        "Finished seeding {} corn plants (target: {}, attempts: {}).",
-        spawned_corn_count, target_corn_count, corn_attempts
-    );
 
     // --- Seed Pumpkins --- Use helper function ---
-    log::info!("Seeding Pumpkins...");
     let pumpkin_noise_threshold = 0.75; // Specific threshold for pumpkins
     while spawned_pumpkin_count < target_pumpkin_count && pumpkin_attempts < max_pumpkin_attempts {
         pumpkin_attempts += 1;
@@ -375,7 +551,7 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
                     pos_x,
                     pos_y,
                     chunk_index: chunk_idx,
-                    respawn_at: None,
+                    respawn_at: day_plant_respawn_at,
                 }
             },
             (),
@@ -386,13 +562,8 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
             Err(_) => { /* Error already logged in helper, continue */ }
         }
     }
-    log::info!(
-        "Finished seeding {} pumpkins (target: {}, attempts: {}).",
-        spawned_pumpkin_count, target_pumpkin_count, pumpkin_attempts
-    );
 
     // --- Seed Hemp --- Use helper function ---
-    log::info!("Seeding Hemp...");
     let hemp_noise_threshold = 0.68; // Specific threshold for hemp (adjust as needed)
     while spawned_hemp_count < target_hemp_count && hemp_attempts < max_hemp_attempts {
         hemp_attempts += 1;
@@ -416,7 +587,7 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
                     pos_x,
                     pos_y,
                     chunk_index: chunk_idx,
-                    respawn_at: None,
+                    respawn_at: night_plant_respawn_at,
                 }
             },
             (),
@@ -427,10 +598,163 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
             Err(_) => { /* Error already logged in helper, continue */ }
         }
     }
-    log::info!(
-        "Finished seeding {} hemps (target: {}, attempts: {}).",
-        spawned_hemp_count, target_hemp_count, hemp_attempts
+
+    // --- Seed Berry Bushes --- Use helper function ---
+    let berry_bush_noise_threshold = 0.66; // Specific threshold for berry bushes
+    while spawned_berry_bush_count < target_berry_bush_count && berry_bush_attempts < max_berry_bush_attempts {
+        berry_bush_attempts += 1;
+        match attempt_single_spawn(
+            &mut rng,
+            &mut occupied_tiles,
+            &mut spawned_berry_bush_positions,
+            &spawned_tree_positions,
+            &spawned_stone_positions,
+            min_tile_x, max_tile_x, min_tile_y, max_tile_y,
+            &fbm,
+            crate::tree::TREE_SPAWN_NOISE_FREQUENCY,
+            berry_bush_noise_threshold,
+            crate::berry_bush::MIN_BERRY_BUSH_DISTANCE_SQ,
+            crate::berry_bush::MIN_BERRY_BUSH_TREE_DISTANCE_SQ,
+            crate::berry_bush::MIN_BERRY_BUSH_STONE_DISTANCE_SQ,
+            |pos_x, pos_y, _extra: ()| {
+                let chunk_idx = calculate_chunk_index(pos_x, pos_y);
+                crate::berry_bush::BerryBush {
+                    id: 0,
+                    pos_x,
+                    pos_y,
+                    chunk_index: chunk_idx,
+                    berries_remaining: crate::berry_bush::BERRY_BUSH_MAX_BERRIES,
+                    respawn_at: None,
+                }
+            },
+            (),
+            berry_bushes,
+        ) {
+            Ok(true) => spawned_berry_bush_count += 1,
+            Ok(false) => { /* Condition not met, continue */ }
+            Err(_) => { /* Error already logged in helper, continue */ }
+        }
+    }
+
+    // --- Seed Loot Crates (barrels and wooden crates) --- Use helper function ---
+    while spawned_crate_count < target_crate_count && crate_attempts < max_crate_attempts {
+        crate_attempts += 1;
+        let crate_type = if rng.gen_bool(crate::loot_crate::BARREL_SPAWN_CHANCE) {
+            crate::loot_crate::LootCrateType::Barrel
+        } else {
+            crate::loot_crate::LootCrateType::WoodenCrate
+        };
+        match attempt_single_spawn(
+            &mut rng,
+            &mut occupied_tiles,
+            &mut spawned_crate_positions,
+            &spawned_tree_positions,
+            &spawned_stone_positions,
+            min_tile_x, max_tile_x, min_tile_y, max_tile_y,
+            &fbm,
+            crate::tree::TREE_SPAWN_NOISE_FREQUENCY,
+            0.0, // Crates are not tied to the vegetation noise
+            crate::loot_crate::MIN_LOOT_CRATE_DISTANCE_SQ,
+            crate::loot_crate::MIN_LOOT_CRATE_TREE_DISTANCE_SQ,
+            crate::loot_crate::MIN_LOOT_CRATE_STONE_DISTANCE_SQ,
+            |pos_x, pos_y, crate_type| crate::loot_crate::LootCrate::new(crate_type, pos_x, pos_y),
+            crate_type,
+            loot_crates,
+        ) {
+            Ok(true) => spawned_crate_count += 1,
+            Ok(false) => { /* Condition not met, continue */ }
+            Err(_) => { /* Error already logged in helper, continue */ }
+        }
+    }
+
+    // --- Seed Hazards (cacti and hot stones in deserts, thorn bushes in forests) --- Use helper function ---
+    let mut spawned_hazard_positions = Vec::<(f32, f32)>::new();
+    let mut spawned_hazard_count = 0;
+    for &(hazard_type, density) in crate::environment_hazard::hazard_spawns_for_biome(biome_for_chunk(world_seed, chunk_index)) {
+        let target_hazard_count = chunk_target_count(&mut rng, chunk_tiles, density);
+        let max_hazard_attempts = target_hazard_count * crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR;
+        let mut spawned_of_type = 0;
+        let mut hazard_attempts = 0;
+        while spawned_of_type < target_hazard_count && hazard_attempts < max_hazard_attempts {
+            hazard_attempts += 1;
+            match attempt_single_spawn(
+                &mut rng,
+                &mut occupied_tiles,
+                &mut spawned_hazard_positions,
+                &spawned_tree_positions,
+                &spawned_stone_positions,
+                min_tile_x, max_tile_x, min_tile_y, max_tile_y,
+                &fbm,
+                crate::tree::TREE_SPAWN_NOISE_FREQUENCY,
+                0.0, // Hazards are not tied to the vegetation noise
+                crate::environment_hazard::MIN_HAZARD_DISTANCE_SQ,
+                crate::environment_hazard::MIN_HAZARD_TREE_DISTANCE_SQ,
+                crate::environment_hazard::MIN_HAZARD_STONE_DISTANCE_SQ,
+                |pos_x, pos_y, hazard_type| crate::environment_hazard::EnvironmentHazard::new(hazard_type, pos_x, pos_y),
+                hazard_type,
+                hazards,
+            ) {
+                Ok(true) => spawned_of_type += 1,
+                Ok(false) => { /* Condition not met, continue */ }
+                Err(_) => { /* Error already logged in helper, continue */ }
+            }
+        }
+        spawned_hazard_count += spawned_of_type;
+    }
+
+    // Static resources enter the spatial grid once; they never move or get deleted
+    for tree in trees.chunk_index().filter(chunk_index) {
+        crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::Tree(tree.id), tree.pos_x, tree.pos_y);
+    }
+    for stone in stones.chunk_index().filter(chunk_index) {
+        crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::Stone(stone.id), stone.pos_x, stone.pos_y);
+    }
+    for mushroom in mushrooms.chunk_index().filter(chunk_index) {
+        crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::Mushroom(mushroom.id as u32), mushroom.pos_x, mushroom.pos_y);
+    }
+    for loot_crate in loot_crates.chunk_index().filter(chunk_index) {
+        crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::LootCrate(loot_crate.id), loot_crate.pos_x, loot_crate.pos_y);
+    }
+    for hazard in hazards.chunk_index().filter(chunk_index) {
+        crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::EnvironmentHazard(hazard.id), hazard.pos_x, hazard.pos_y);
+    }
+
+    log::debug!(
+        "Generated chunk {}: {} trees, {} stones, {} mushrooms, {} corn, {} pumpkins, {} hemp, {} berry bushes, {} loot crates, {} hazards.",
+        chunk_index, spawned_tree_count, spawned_stone_count, spawned_mushroom_count,
+        spawned_corn_count, spawned_pumpkin_count, spawned_hemp_count, spawned_berry_bush_count, spawned_crate_count,
+        spawned_hazard_count
     );
+    Ok(())
+}
+
+/// Seeds the drifting clouds across the whole world. Clouds move between chunks, so they
+/// are not part of per-chunk generation.
+fn seed_clouds(ctx: &ReducerContext) -> Result<(), String> {
+    let clouds = ctx.db.cloud();
+    if clouds.iter().count() > 0 {
+        log::debug!("Clouds already seeded ({}). Skipping.", clouds.iter().count());
+        return Ok(());
+    }
+
+    let mut rng = StdRng::from_rng(ctx.rng()).map_err(|e| format!("Failed to seed RNG: {}", e))?;
+    let total_tiles = crate::WORLD_WIDTH_TILES * crate::WORLD_HEIGHT_TILES;
+
+    // Cloud seeding parameters
+    const CLOUD_DENSITY_PERCENT: f32 = 0.005; // Example: 0.5% of tiles might have a cloud center
+    const MAX_CLOUD_SEEDING_ATTEMPTS_FACTOR: u32 = 3;
+    let target_cloud_count = (total_tiles as f32 * CLOUD_DENSITY_PERCENT) as u32;
+    let max_cloud_attempts = target_cloud_count * MAX_CLOUD_SEEDING_ATTEMPTS_FACTOR;
+
+    // Cloud drift parameters
+    const CLOUD_BASE_DRIFT_X: f32 = 4.0; // Base speed in pixels per second (e.g., gentle eastward drift) - Doubled
+    const CLOUD_BASE_DRIFT_Y: f32 = 1.0; // Doubled
+    const CLOUD_DRIFT_VARIATION: f32 = 1.0; // Max variation from base speed
+
+    log::info!("Target Clouds: {}, Max Attempts: {}", target_cloud_count, max_cloud_attempts);
+    let mut spawned_cloud_positions = Vec::<(f32, f32)>::new();
+    let mut spawned_cloud_count = 0;
+    let mut cloud_attempts = 0;
 
     // --- Seed Clouds ---
     log::info!("Seeding Clouds...");
@@ -532,7 +856,6 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
     // --- End Schedule initial cloud update ---
 
 
-    log::info!("Environment seeding complete.");
     Ok(())
 }
 
@@ -540,7 +863,10 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
 
 #[spacetimedb::reducer]
 pub fn check_resource_respawns(ctx: &ReducerContext) -> Result<(), String> {
-    
+    let time_of_day = crate::world_state::current_time_of_day(ctx);
+    let is_night = time_of_day.is_night();
+    let is_daytime = time_of_day.is_daytime();
+
     // Respawn Stones
     check_and_respawn_resource!(
         ctx,
@@ -549,7 +875,7 @@ pub fn check_resource_respawns(ctx: &ReducerContext) -> Result<(), String> {
         "Stone", // Name for logging
         |s: &crate::stone::Stone| s.health == 0, // Filter: only check stones with 0 health
         |s: &mut crate::stone::Stone| { // Update logic
-            s.health = crate::stone::STONE_INITIAL_HEALTH;
+            s.health = s.richness.scale_health(crate::stone::STONE_INITIAL_HEALTH);
             s.respawn_at = None;
             s.last_hit_time = None;
         }
@@ -563,9 +889,10 @@ pub fn check_resource_respawns(ctx: &ReducerContext) -> Result<(), String> {
         "Tree",
         |t: &crate::tree::Tree| t.health == 0,
         |t: &mut crate::tree::Tree| {
-            t.health = crate::tree::TREE_INITIAL_HEALTH;
+            t.health = t.richness.scale_health(crate::tree::TREE_INITIAL_HEALTH);
             t.respawn_at = None;
             t.last_hit_time = None;
+            crate::tree_felling::remove_stumps_for_tree(ctx, t.id);
             // Position doesn't change during respawn, so chunk_index stays the same
         }
     );
@@ -576,7 +903,7 @@ pub fn check_resource_respawns(ctx: &ReducerContext) -> Result<(), String> {
         mushroom,
         crate::mushroom::Mushroom,
         "Mushroom",
-        |_m: &crate::mushroom::Mushroom| true, // Filter: Always check mushrooms if respawn_at is set (handled internally by macro)
+        |_m: &crate::mushroom::Mushroom| is_night, // Filter: Mushrooms only regrow at night
         |m: &mut crate::mushroom::Mushroom| {
             m.respawn_at = None;
         }
@@ -588,7 +915,7 @@ pub fn check_resource_respawns(ctx: &ReducerContext) -> Result<(), String> {
         corn,
         crate::corn::Corn,
         "Corn",
-        |_c: &crate::corn::Corn| true, // Filter: Always check corn if respawn_at is set (handled internally by macro)
+        |_c: &crate::corn::Corn| is_daytime, // Filter: Corn only regrows during the day
         |c: &mut crate::corn::Corn| {
             c.respawn_at = None;
         }
@@ -600,7 +927,7 @@ pub fn check_resource_respawns(ctx: &ReducerContext) -> Result<(), String> {
         pumpkin,
         crate::pumpkin::Pumpkin,
         "Pumpkin",
-        |_p: &crate::pumpkin::Pumpkin| true, // Filter: Always check pumpkins if respawn_at is set (handled internally by macro)
+        |_p: &crate::pumpkin::Pumpkin| is_daytime, // Filter: Pumpkins only regrow during the day
         |p: &mut crate::pumpkin::Pumpkin| {
             p.respawn_at = None;
         }
@@ -612,12 +939,46 @@ pub fn check_resource_respawns(ctx: &ReducerContext) -> Result<(), String> {
         hemp, // Table symbol
         crate::hemp::Hemp, // Entity type
         "Hemp", // Name for logging
-        |_h: &crate::hemp::Hemp| true, // Filter: Always check if respawn_at is set
+        |_h: &crate::hemp::Hemp| is_night, // Filter: Hemp only regrows at night
         |h: &mut crate::hemp::Hemp| {
             h.respawn_at = None;
         }
     );
 
+    // Respawn Loot Crates (event crates are deleted when broken, so only world crates wait here)
+    check_and_respawn_resource!(
+        ctx,
+        loot_crate,
+        crate::loot_crate::LootCrate,
+        "Loot Crate",
+        |c: &crate::loot_crate::LootCrate| c.health <= 0.0,
+        |c: &mut crate::loot_crate::LootCrate| {
+            c.health = c.max_health;
+            c.respawn_at = None;
+            c.last_hit_time = None;
+            // The macro may have nudged the crate to a clear spot
+            sync_chunk_index(&mut c.chunk_index, c.pos_x, c.pos_y);
+            crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::LootCrate(c.id), c.pos_x, c.pos_y);
+        }
+    );
+
+    // Respawn Hazards
+    check_and_respawn_resource!(
+        ctx,
+        environment_hazard,
+        crate::environment_hazard::EnvironmentHazard,
+        "Hazard",
+        |h: &crate::environment_hazard::EnvironmentHazard| h.health == 0,
+        |h: &mut crate::environment_hazard::EnvironmentHazard| {
+            h.health = crate::environment_hazard::HAZARD_INITIAL_HEALTH;
+            h.respawn_at = None;
+            h.last_hit_time = None;
+            // The macro may have nudged the hazard to a clear spot
+            sync_chunk_index(&mut h.chunk_index, h.pos_x, h.pos_y);
+            crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::EnvironmentHazard(h.id), h.pos_x, h.pos_y);
+        }
+    );
+
     // Note: Clouds are static for now, so no respawn logic needed in check_resource_respawns.
     // If they were to drift or change, a similar `check_and_respawn_resource!` or a dedicated
     // scheduled reducer would be needed here or in `cloud.rs`.
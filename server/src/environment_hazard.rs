@@ -0,0 +1,135 @@
+/******************************************************************************
+ *                                                                            *
+ * Environmental hazards. Cacti and hot stones grow in desert chunks and      *
+ * thorn bushes in forests, generated with the rest of a chunk's resources    *
+ * (environment.rs). A player whose resolved movement position (move_player   *
+ * in lib.rs) overlaps one takes a small damage effect: cacti and thorns make *
+ * them bleed, hot stones burn them. The overlap test is the same damage-zone *
+ * check campfire hot zones use. Any tool harvests a hazard for its own       *
+ * resource; used-up hazards regrow in `check_resource_respawns`.             *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Timestamp};
+
+use crate::active_effects::EffectType;
+use crate::environment::{Biome, calculate_chunk_index};
+use crate::player as PlayerTableTrait;
+use crate::spatial_grid::{self, EntityType};
+use crate::environment_hazard::environment_hazard as EnvironmentHazardTableTrait;
+
+// --- Constants ---
+pub(crate) const HAZARD_RADIUS: f32 = 24.0;
+/// Players whose position is this close to a hazard's centre are touching it.
+const HAZARD_CONTACT_RADIUS: f32 = HAZARD_RADIUS + 12.0;
+const HAZARD_CONTACT_RADIUS_SQUARED: f32 = HAZARD_CONTACT_RADIUS * HAZARD_CONTACT_RADIUS;
+pub(crate) const HAZARD_INITIAL_HEALTH: u32 = 150;
+/// Resource granted per tool hit, before heavy swings and server modifiers.
+pub(crate) const HAZARD_YIELD_MIN: u32 = 1;
+pub(crate) const HAZARD_YIELD_MAX: u32 = 3;
+pub(crate) const MIN_HAZARD_RESPAWN_TIME_SECS: u64 = 300; // 5 minutes
+pub(crate) const MAX_HAZARD_RESPAWN_TIME_SECS: u64 = 600; // 10 minutes
+
+// --- Generation ---
+pub(crate) const CACTUS_DENSITY_PERCENT: f32 = 0.004;
+pub(crate) const HOT_STONE_DENSITY_PERCENT: f32 = 0.0015;
+pub(crate) const THORN_BUSH_DENSITY_PERCENT: f32 = 0.002;
+pub(crate) const MIN_HAZARD_DISTANCE_PX: f32 = 120.0;
+pub(crate) const MIN_HAZARD_DISTANCE_SQ: f32 = MIN_HAZARD_DISTANCE_PX * MIN_HAZARD_DISTANCE_PX;
+pub(crate) const MIN_HAZARD_TREE_DISTANCE_PX: f32 = 80.0;
+pub(crate) const MIN_HAZARD_TREE_DISTANCE_SQ: f32 = MIN_HAZARD_TREE_DISTANCE_PX * MIN_HAZARD_TREE_DISTANCE_PX;
+pub(crate) const MIN_HAZARD_STONE_DISTANCE_PX: f32 = 80.0;
+pub(crate) const MIN_HAZARD_STONE_DISTANCE_SQ: f32 = MIN_HAZARD_STONE_DISTANCE_PX * MIN_HAZARD_STONE_DISTANCE_PX;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum HazardType {
+    Cactus,    // Desert
+    HotStone,  // Desert
+    ThornBush, // Forest
+}
+
+impl HazardType {
+    /// The resource this hazard yields when harvested with a tool.
+    pub(crate) fn resource_name(&self) -> &'static str {
+        match self {
+            HazardType::Cactus => "Cactus Flesh",
+            HazardType::HotStone => "Sulfur",
+            HazardType::ThornBush => "Thorns",
+        }
+    }
+
+    /// Effect a player touching the hazard takes: (effect, damage, duration in seconds).
+    fn contact_effect(&self) -> (EffectType, f32, f32) {
+        match self {
+            HazardType::Cactus => (EffectType::Bleed, 3.0, 2.0),
+            HazardType::HotStone => (EffectType::Burn, 2.0, 1.0), // Environmental burns land in one tick
+            HazardType::ThornBush => (EffectType::Bleed, 2.0, 2.0),
+        }
+    }
+}
+
+/// Hazards generated in chunks of a biome, with their spawn densities.
+pub(crate) fn hazard_spawns_for_biome(biome: Biome) -> &'static [(HazardType, f32)] {
+    match biome {
+        Biome::Desert => &[(HazardType::Cactus, CACTUS_DENSITY_PERCENT), (HazardType::HotStone, HOT_STONE_DENSITY_PERCENT)],
+        Biome::Forest => &[(HazardType::ThornBush, THORN_BUSH_DENSITY_PERCENT)],
+        Biome::Snow => &[],
+    }
+}
+
+/// --- Environment Hazard ---
+/// A used-up hazard keeps its row with zero health until `respawn_at`.
+#[spacetimedb::table(name = environment_hazard, public)]
+#[derive(Clone, Debug)]
+pub struct EnvironmentHazard {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub hazard_type: HazardType,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    #[index(btree)]
+    pub chunk_index: u32,
+    pub health: u32,
+    pub last_hit_time: Option<Timestamp>,
+    pub respawn_at: Option<Timestamp>,
+}
+
+impl EnvironmentHazard {
+    /// Creates a full-health hazard at the given position. Used by chunk generation.
+    pub(crate) fn new(hazard_type: HazardType, pos_x: f32, pos_y: f32) -> Self {
+        Self {
+            id: 0, // Auto-incremented
+            hazard_type,
+            pos_x,
+            pos_y,
+            chunk_index: calculate_chunk_index(pos_x, pos_y),
+            health: HAZARD_INITIAL_HEALTH,
+            last_hit_time: None,
+            respawn_at: None,
+        }
+    }
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Hurts a player who has just moved to (pos_x, pos_y) for every hazard they now touch.
+/// Called from move_player with the position after collision resolution.
+pub(crate) fn apply_hazard_contact(ctx: &ReducerContext, player_id: Identity, pos_x: f32, pos_y: f32) {
+    let Some(player) = ctx.db.player().identity().find(player_id) else { return; };
+    if !crate::active_effects::can_take_environmental_damage(ctx, &player, ctx.timestamp) {
+        return;
+    }
+    let hazards = ctx.db.environment_hazard();
+    for entity in spatial_grid::get_entities_in_range(ctx, pos_x, pos_y) {
+        let EntityType::EnvironmentHazard(hazard_id) = entity else { continue; };
+        let Some(hazard) = hazards.id().find(hazard_id).filter(|h| h.health > 0) else { continue; };
+        if !crate::utils::is_in_damage_zone(pos_x, pos_y, hazard.pos_x, hazard.pos_y, HAZARD_CONTACT_RADIUS_SQUARED) {
+            continue;
+        }
+        let (effect_type, damage, duration_secs) = hazard.hazard_type.contact_effect();
+        crate::active_effects::apply_hazard_contact_damage(ctx, player_id, effect_type, damage, duration_secs);
+    }
+}
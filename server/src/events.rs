@@ -0,0 +1,199 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines world events. A scheduled check occasionally starts a raid: a      *
+ * small group of hostile raiders (see animals.rs) spawns near a random       *
+ * online player and hunts them down. Each active raid is a public RaidEvent  *
+ * row so clients can show a warning banner, and its start and end are        *
+ * announced in chat. Killed raiders leave loot crates (see combat.rs). A     *
+ * raid ends once all its raiders are dead or when it times out, at which     *
+ * point any surviving raiders retreat (are removed).                         *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, TimeDuration, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+use rand::{Rng, SeedableRng};
+
+use crate::{WORLD_WIDTH_PX, WORLD_HEIGHT_PX};
+use crate::Player;
+use crate::player as PlayerTableTrait;
+use crate::animals::{self, ANIMAL_COLLISION_RADIUS, wild_animal as WildAnimalTableTrait};
+use crate::chat;
+use crate::spatial_grid::{EntityType, untrack_entity};
+use crate::world_tile::is_water_at;
+use crate::events::raid_event as RaidEventTableTrait;
+use crate::events::raid_event_schedule as RaidEventScheduleTableTrait;
+
+// --- Constants ---
+const RAID_CHECK_INTERVAL_SECS: u64 = 10;
+/// Chance per check to start a raid when none is active (about one every 8 minutes).
+const RAID_START_CHANCE: f64 = 0.02;
+const MIN_RAIDERS: u32 = 3;
+const MAX_RAIDERS: u32 = 5;
+/// Raiders appear this far from the targeted player, just outside their view.
+const RAID_SPAWN_DISTANCE: f32 = 700.0;
+/// Raiders are scattered within this radius around the group's spawn point.
+const RAID_GROUP_SPREAD: f32 = 60.0;
+const MAX_SPAWN_ATTEMPTS: u32 = 8;
+/// Surviving raiders retreat after this long.
+const RAID_DURATION_SECS: u64 = 300;
+
+/// --- Raid Event Data Structure ---
+/// An active raid. Public so clients can show a warning banner while it lasts.
+#[spacetimedb::table(name = raid_event, public)]
+#[derive(Clone, Debug)]
+pub struct RaidEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub target_player_id: Identity,
+    pub target_username: String,
+    pub pos_x: f32, // Where the raiders spawned
+    pub pos_y: f32,
+    pub raider_count: u32,
+    pub started_at: Timestamp,
+    pub ends_at: Timestamp,
+}
+
+/// --- Raid Event Schedule ---
+#[spacetimedb::table(name = raid_event_schedule, scheduled(process_raid_events))]
+#[derive(Clone, Debug)]
+pub struct RaidEventSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/// Starts the raid event check if it isn't already scheduled. Called from `init_module`.
+pub fn init_raid_event_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.raid_event_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting raid event schedule (every {}s).", RAID_CHECK_INTERVAL_SECS);
+        let interval = Duration::from_secs(RAID_CHECK_INTERVAL_SECS);
+        schedule_table.try_insert(RaidEventSchedule {
+            id: 0,
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert raid event schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: ends finished raids, clears out stale raider crates and occasionally
+/// starts a new one.
+#[spacetimedb::reducer]
+pub fn process_raid_events(ctx: &ReducerContext, _schedule: RaidEventSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("process_raid_events may only be called by the scheduler.".to_string());
+    }
+
+    for raid in ctx.db.raid_event().iter() {
+        update_raid(ctx, raid);
+    }
+    crate::loot_crate::despawn_expired_event_crates(ctx);
+
+    if ctx.db.raid_event().count() > 0 {
+        return Ok(());
+    }
+    let mut rng = rand::rngs::StdRng::from_rng(ctx.rng()).map_err(|e| format!("Failed to create RNG: {}", e))?;
+    if !rng.gen_bool(RAID_START_CHANCE) {
+        return Ok(());
+    }
+
+    let candidates: Vec<Player> = ctx.db.player().iter()
+        .filter(|p| p.is_online && !p.is_dead)
+        .collect();
+    if candidates.is_empty() {
+        return Ok(());
+    }
+    let target = &candidates[rng.gen_range(0..candidates.len())];
+    start_raid(ctx, target, &mut rng)
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Spawns a raider group near the target player and announces the raid.
+fn start_raid(ctx: &ReducerContext, target: &Player, rng: &mut impl Rng) -> Result<(), String> {
+    let Some((origin_x, origin_y)) = find_raid_spawn_point(ctx, target, rng) else {
+        log::debug!("[Events] No valid raid spawn point near {}; skipping raid.", target.username);
+        return Ok(());
+    };
+
+    let raid = ctx.db.raid_event().try_insert(RaidEvent {
+        id: 0, // Auto-incremented
+        target_player_id: target.identity,
+        target_username: target.username.clone(),
+        pos_x: origin_x,
+        pos_y: origin_y,
+        raider_count: 0,
+        started_at: ctx.timestamp,
+        ends_at: ctx.timestamp + TimeDuration::from(Duration::from_secs(RAID_DURATION_SECS)),
+    }).map_err(|e| format!("Failed to insert raid event: {}", e))?;
+
+    let mut spawned = 0;
+    for _ in 0..rng.gen_range(MIN_RAIDERS..=MAX_RAIDERS) {
+        let x = (origin_x + rng.gen_range(-RAID_GROUP_SPREAD..RAID_GROUP_SPREAD))
+            .clamp(ANIMAL_COLLISION_RADIUS, WORLD_WIDTH_PX - ANIMAL_COLLISION_RADIUS);
+        let y = (origin_y + rng.gen_range(-RAID_GROUP_SPREAD..RAID_GROUP_SPREAD))
+            .clamp(ANIMAL_COLLISION_RADIUS, WORLD_HEIGHT_PX - ANIMAL_COLLISION_RADIUS);
+        match animals::spawn_raider(ctx, raid.id, x, y, target.identity) {
+            Ok(_) => spawned += 1,
+            Err(e) => log::error!("[Events] Raid {}: {}", raid.id, e),
+        }
+    }
+
+    if spawned == 0 {
+        ctx.db.raid_event().id().delete(raid.id);
+        return Err("Failed to spawn any raiders.".to_string());
+    }
+    ctx.db.raid_event().id().update(RaidEvent { raider_count: spawned, ..raid.clone() });
+    log::info!("[Events] Raid {} started: {} raiders hunting {} from ({:.1}, {:.1}).",
+             raid.id, spawned, target.username, origin_x, origin_y);
+    chat::send_system_message(ctx, format!("Raiders have been spotted near {}!", target.username));
+    Ok(())
+}
+
+/// Ends the raid once all of its raiders are dead, or sends the survivors away when it times out.
+fn update_raid(ctx: &ReducerContext, raid: RaidEvent) {
+    let raiders: Vec<u64> = ctx.db.wild_animal().iter()
+        .filter(|a| a.raid_event_id == Some(raid.id))
+        .map(|a| a.id)
+        .collect();
+
+    if raiders.is_empty() {
+        ctx.db.raid_event().id().delete(raid.id);
+        log::info!("[Events] Raid {} defeated.", raid.id);
+        chat::send_system_message(ctx, "The raiders have been defeated.".to_string());
+        return;
+    }
+
+    if ctx.timestamp >= raid.ends_at {
+        for raider_id in &raiders {
+            ctx.db.wild_animal().id().delete(raider_id);
+            untrack_entity(ctx, EntityType::Animal(*raider_id));
+        }
+        ctx.db.raid_event().id().delete(raid.id);
+        log::info!("[Events] Raid {} timed out; {} raiders retreated.", raid.id, raiders.len());
+        chat::send_system_message(ctx, "The raiders have retreated.".to_string());
+    }
+}
+
+/// Picks a dry spot `RAID_SPAWN_DISTANCE` away from the player in a random direction.
+fn find_raid_spawn_point(ctx: &ReducerContext, target: &Player, rng: &mut impl Rng) -> Option<(f32, f32)> {
+    (0..MAX_SPAWN_ATTEMPTS)
+        .map(|_| {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            (
+                target.position_x + angle.cos() * RAID_SPAWN_DISTANCE,
+                target.position_y + angle.sin() * RAID_SPAWN_DISTANCE,
+            )
+        })
+        .find(|&(x, y)| {
+            (RAID_GROUP_SPREAD..=WORLD_WIDTH_PX - RAID_GROUP_SPREAD).contains(&x)
+                && (RAID_GROUP_SPREAD..=WORLD_HEIGHT_PX - RAID_GROUP_SPREAD).contains(&y)
+                && !is_water_at(ctx, x, y)
+        })
+}
@@ -0,0 +1,328 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines placed explosives (Satchel Charges) used for raiding. Placing a    *
+ * charge next to a structure starts a fuse; when it runs out a scheduled     *
+ * reducer deals area damage to nearby structures and players through the     *
+ * regular combat.rs damage functions. The owner can defuse a charge before   *
+ * it goes off to get the item back.                                          *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use rand::SeedableRng;
+use std::time::Duration;
+
+use crate::combat::{self, TargetId};
+use crate::environment::calculate_chunk_index;
+use crate::models::{DamageType, DeathCause, ItemLocation};
+use crate::player as PlayerTableTrait;
+use crate::items::{
+    inventory_item as InventoryItemTableTrait,
+    item_definition as ItemDefinitionTableTrait,
+    add_item_to_player_inventory,
+};
+use crate::campfire::campfire as CampfireTableTrait;
+use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
+use crate::large_storage_chest::large_storage_chest as LargeStorageChestTableTrait;
+use crate::stash::stash as StashTableTrait;
+use crate::sleeping_bag::sleeping_bag as SleepingBagTableTrait;
+use crate::furnace::furnace as FurnaceTableTrait;
+use crate::building::building_piece as BuildingPieceTableTrait;
+use crate::sleeping_player::sleeping_player as SleepingPlayerTableTrait;
+use crate::loot_crate::loot_crate as LootCrateTableTrait;
+use crate::workbench::workbench as WorkbenchTableTrait;
+use crate::sign::sign as SignTableTrait;
+use crate::wiring::wiring_switch as WiringSwitchTableTrait;
+use crate::mount::mount as MountTableTrait;
+use crate::recycler::recycler as RecyclerTableTrait;
+use crate::flame_turret::flame_turret as FlameTurretTableTrait;
+use crate::world_event::WorldEventKind;
+use crate::explosives::placed_explosive as PlacedExplosiveTableTrait;
+use crate::explosives::explosive_detonation_schedule as ExplosiveDetonationScheduleTableTrait;
+
+// --- Constants ---
+const SATCHEL_CHARGE_ITEM_NAME: &str = "Satchel Charge";
+const SATCHEL_FUSE_SECS: u64 = 10;
+/// Structures and players within this radius of the charge take damage.
+const SATCHEL_BLAST_RADIUS: f32 = 120.0;
+const SATCHEL_BLAST_RADIUS_SQUARED: f32 = SATCHEL_BLAST_RADIUS * SATCHEL_BLAST_RADIUS;
+/// Damage at the center of the blast; falls off linearly to half at the edge.
+const SATCHEL_STRUCTURE_DAMAGE: f32 = 400.0;
+const SATCHEL_PLAYER_DAMAGE: f32 = 60.0;
+/// A charge must be placed within this distance of a structure.
+const SATCHEL_ATTACH_DISTANCE_SQUARED: f32 = 64.0 * 64.0;
+const PLACEMENT_RANGE_SQ: f32 = 96.0 * 96.0;
+const DEFUSE_DISTANCE_SQUARED: f32 = 64.0 * 64.0;
+
+/// --- Placed Explosive Data Structure ---
+/// A lit charge in the world. Public so clients can render the countdown from `detonates_at`.
+#[spacetimedb::table(name = placed_explosive, public)]
+#[derive(Clone, Debug)]
+pub struct PlacedExplosive {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub chunk_index: u32,
+    pub placed_by: Identity,
+    pub placed_at: Timestamp,
+    pub detonates_at: Timestamp,
+}
+
+/// One-shot schedule that detonates a placed explosive when its fuse runs out.
+#[spacetimedb::table(name = explosive_detonation_schedule, scheduled(detonate_explosive))]
+#[derive(Clone)]
+pub struct ExplosiveDetonationSchedule {
+    #[primary_key]
+    pub explosive_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/******************************************************************************
+ *                                 REDUCERS                                   *
+ ******************************************************************************/
+
+/// --- Place Explosive ---
+/// Consumes a Satchel Charge from the sender's inventory and plants it next to a structure.
+#[spacetimedb::reducer]
+pub fn place_explosive(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let sender_id = ctx.sender;
+    let inventory_items = ctx.db.inventory_item();
+
+    let item = inventory_items.instance_id().find(item_instance_id)
+        .ok_or_else(|| format!("Item instance {} not found.", item_instance_id))?;
+    let is_owned_and_in_player_slots = match &item.location {
+        ItemLocation::Inventory(data) => data.owner_id == sender_id,
+        ItemLocation::Hotbar(data) => data.owner_id == sender_id,
+        _ => false,
+    };
+    if !is_owned_and_in_player_slots {
+        return Err("Satchel Charge must be in your inventory or hotbar.".to_string());
+    }
+    let item_def = ctx.db.item_definition().id().find(item.item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found.", item.item_def_id))?;
+    if item_def.name != SATCHEL_CHARGE_ITEM_NAME {
+        return Err(format!("Item instance {} is not a Satchel Charge.", item_instance_id));
+    }
+
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Could not find player data.".to_string())?;
+    if player.is_dead {
+        return Err("Cannot place explosives while dead.".to_string());
+    }
+    let dx = player.position_x - world_x;
+    let dy = player.position_y - world_y;
+    if (dx * dx + dy * dy) > PLACEMENT_RANGE_SQ {
+        return Err("Placement location is too far away.".to_string());
+    }
+    crate::zone::check_build_allowed(ctx, world_x, world_y)?;
+    if !crate::zone::is_pvp_allowed_at(ctx, world_x, world_y) {
+        return Err("Explosives can't be used where PvP is off.".to_string());
+    }
+    let next_to_structure = structures_in_radius(ctx, world_x, world_y, SATCHEL_ATTACH_DISTANCE_SQUARED)
+        .iter()
+        .any(|(id, _)| !matches!(id, TargetId::Player(_) | TargetId::SleepingPlayer(_)));
+    if !next_to_structure {
+        return Err("Satchel Charges must be placed against a structure.".to_string());
+    }
+
+    // Consume one charge
+    if item.quantity > 1 {
+        let mut remaining = item.clone();
+        remaining.quantity -= 1;
+        inventory_items.instance_id().update(remaining);
+    } else {
+        inventory_items.instance_id().delete(item_instance_id);
+    }
+
+    let detonates_at = ctx.timestamp + Duration::from_secs(SATCHEL_FUSE_SECS);
+    let explosive = ctx.db.placed_explosive().insert(PlacedExplosive {
+        id: 0, // Auto-incremented
+        pos_x: world_x,
+        pos_y: world_y,
+        chunk_index: calculate_chunk_index(world_x, world_y),
+        placed_by: sender_id,
+        placed_at: ctx.timestamp,
+        detonates_at,
+    });
+    ctx.db.explosive_detonation_schedule().insert(ExplosiveDetonationSchedule {
+        explosive_id: explosive.id,
+        scheduled_at: detonates_at.into(),
+    });
+
+    log::info!(
+        "[Explosives] Player {:?} planted Satchel Charge {} at ({:.1}, {:.1}). Detonates in {}s.",
+        sender_id, explosive.id, world_x, world_y, SATCHEL_FUSE_SECS
+    );
+    Ok(())
+}
+
+/// --- Defuse Explosive ---
+/// Lets the player who planted a charge pull it before it detonates, returning the item.
+#[spacetimedb::reducer]
+pub fn defuse_explosive(ctx: &ReducerContext, explosive_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    crate::spectator::ensure_not_spectating(ctx, sender_id)?;
+    let explosive = ctx.db.placed_explosive().id().find(explosive_id)
+        .ok_or_else(|| format!("Explosive {} not found.", explosive_id))?;
+    if explosive.placed_by != sender_id {
+        return Err("Only the player who planted this charge can defuse it.".to_string());
+    }
+
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Could not find player data.".to_string())?;
+    if player.is_dead {
+        return Err("Cannot defuse a charge while dead.".to_string());
+    }
+    let dx = player.position_x - explosive.pos_x;
+    let dy = player.position_y - explosive.pos_y;
+    if (dx * dx + dy * dy) > DEFUSE_DISTANCE_SQUARED {
+        return Err("Too far away".to_string());
+    }
+
+    let charge_def = ctx.db.item_definition().iter()
+        .find(|def| def.name == SATCHEL_CHARGE_ITEM_NAME)
+        .ok_or_else(|| "ItemDefinition for 'Satchel Charge' not found.".to_string())?;
+    add_item_to_player_inventory(ctx, sender_id, charge_def.id, 1)
+        .map_err(|e| format!("Could not return the charge to your inventory: {}", e))?;
+
+    ctx.db.explosive_detonation_schedule().explosive_id().delete(explosive_id);
+    ctx.db.placed_explosive().id().delete(explosive_id);
+    log::info!("[Explosives] Player {:?} defused Satchel Charge {}.", sender_id, explosive_id);
+    Ok(())
+}
+
+/// --- Detonate Explosive (Scheduled) ---
+/// Deals area damage around the charge and removes it.
+#[spacetimedb::reducer]
+pub fn detonate_explosive(ctx: &ReducerContext, args: ExplosiveDetonationSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("detonate_explosive can only be called by the scheduler".to_string());
+    }
+    let explosive = match ctx.db.placed_explosive().id().find(args.explosive_id) {
+        Some(explosive) => explosive,
+        None => {
+            log::debug!("[Explosives] Explosive {} no longer exists (defused?).", args.explosive_id);
+            return Ok(());
+        }
+    };
+    ctx.db.placed_explosive().id().delete(explosive.id);
+    crate::world_event::emit_world_event(ctx, WorldEventKind::Explosion, explosive.pos_x, explosive.pos_y, Some(explosive.placed_by), None);
+
+    let mut rng = rand::rngs::StdRng::from_rng(ctx.rng()).map_err(|e| format!("Failed to create RNG: {}", e))?;
+    let attacker_id = explosive.placed_by;
+    let timestamp = ctx.timestamp;
+    let targets = structures_in_radius(ctx, explosive.pos_x, explosive.pos_y, SATCHEL_BLAST_RADIUS_SQUARED);
+    log::info!(
+        "[Explosives] Satchel Charge {} detonated at ({:.1}, {:.1}), {} targets in range.",
+        explosive.id, explosive.pos_x, explosive.pos_y, targets.len()
+    );
+
+    for (target_id, distance_sq) in targets {
+        // Linear falloff from full damage at the center to half at the edge of the blast
+        let falloff = 1.0 - 0.5 * (distance_sq.sqrt() / SATCHEL_BLAST_RADIUS).min(1.0);
+        let structure_damage = SATCHEL_STRUCTURE_DAMAGE * falloff;
+        let result = match target_id {
+            TargetId::Player(player_id) => {
+                // Blast damage respects safe zones like any other PvP hit.
+                let protected = ctx.db.player().identity().find(player_id)
+                    .is_some_and(|p| !crate::zone::is_pvp_allowed_at(ctx, p.position_x, p.position_y));
+                if protected {
+                    continue;
+                }
+                combat::damage_player_from_environment(ctx, player_id, SATCHEL_PLAYER_DAMAGE * falloff, DamageType::Projectile, DeathCause::Explosion, "Satchel Charge", timestamp).map(|_| ())
+            }
+            TargetId::Campfire(id) => combat::damage_campfire(ctx, attacker_id, id, structure_damage, timestamp, &mut rng).map(|_| ()),
+            TargetId::WoodenStorageBox(id) => combat::damage_wooden_storage_box(ctx, attacker_id, id, structure_damage, timestamp, &mut rng).map(|_| ()),
+            TargetId::LargeStorageChest(id) => combat::damage_large_storage_chest(ctx, attacker_id, id, structure_damage, timestamp, &mut rng).map(|_| ()),
+            TargetId::Stash(id) => combat::damage_stash(ctx, attacker_id, id, structure_damage, timestamp, &mut rng).map(|_| ()),
+            TargetId::SleepingBag(id) => combat::damage_sleeping_bag(ctx, attacker_id, id, structure_damage, timestamp, &mut rng).map(|_| ()),
+            TargetId::Furnace(id) => combat::damage_furnace(ctx, attacker_id, id, structure_damage, timestamp, &mut rng).map(|_| ()),
+            TargetId::BuildingPiece(id) => combat::damage_building_piece(ctx, attacker_id, id, structure_damage, timestamp).map(|_| ()),
+            TargetId::SleepingPlayer(id) => combat::damage_sleeping_player(ctx, attacker_id, id, SATCHEL_PLAYER_DAMAGE * falloff, DamageType::Projectile, timestamp).map(|_| ()),
+            TargetId::LootCrate(id) => combat::damage_loot_crate(ctx, attacker_id, id, structure_damage, timestamp, &mut rng).map(|_| ()),
+            TargetId::Workbench(id) => combat::damage_workbench(ctx, attacker_id, id, structure_damage, timestamp).map(|_| ()),
+            TargetId::Sign(id) => combat::damage_sign(ctx, attacker_id, id, structure_damage, timestamp).map(|_| ()),
+            TargetId::Switch(id) => combat::damage_switch(ctx, attacker_id, id, structure_damage, timestamp).map(|_| ()),
+            TargetId::Recycler(id) => combat::damage_recycler(ctx, attacker_id, id, structure_damage, timestamp, &mut rng).map(|_| ()),
+            TargetId::FlameTurret(id) => combat::damage_flame_turret(ctx, attacker_id, id, structure_damage, timestamp, &mut rng).map(|_| ()),
+            TargetId::Mount(id) => combat::damage_mount(ctx, attacker_id, id, structure_damage, timestamp).map(|_| ()),
+            _ => Ok(()),
+        };
+        if let Err(e) = result {
+            log::error!("[Explosives] Failed to apply blast damage to {:?}: {}", target_id, e);
+        }
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Collects every intact structure, mount, living player and sleeping body within `radius_sq` of a point,
+/// along with its squared distance.
+fn structures_in_radius(ctx: &ReducerContext, x: f32, y: f32, radius_sq: f32) -> Vec<(TargetId, f32)> {
+    let mut targets = Vec::new();
+    let mut consider = |target_id: TargetId, pos_x: f32, pos_y: f32| {
+        let dx = pos_x - x;
+        let dy = pos_y - y;
+        let distance_sq = dx * dx + dy * dy;
+        if distance_sq <= radius_sq {
+            targets.push((target_id, distance_sq));
+        }
+    };
+
+    for campfire in ctx.db.campfire().iter().filter(|c| !c.is_destroyed) {
+        consider(TargetId::Campfire(campfire.id), campfire.pos_x, campfire.pos_y);
+    }
+    for storage_box in ctx.db.wooden_storage_box().iter().filter(|b| !b.is_destroyed) {
+        consider(TargetId::WoodenStorageBox(storage_box.id), storage_box.pos_x, storage_box.pos_y);
+    }
+    for chest in ctx.db.large_storage_chest().iter().filter(|c| !c.is_destroyed) {
+        consider(TargetId::LargeStorageChest(chest.id), chest.pos_x, chest.pos_y);
+    }
+    for stash in ctx.db.stash().iter().filter(|s| !s.is_destroyed) {
+        consider(TargetId::Stash(stash.id), stash.pos_x, stash.pos_y);
+    }
+    for bag in ctx.db.sleeping_bag().iter().filter(|b| !b.is_destroyed) {
+        consider(TargetId::SleepingBag(bag.id), bag.pos_x, bag.pos_y);
+    }
+    for furnace in ctx.db.furnace().iter().filter(|f| !f.is_destroyed) {
+        consider(TargetId::Furnace(furnace.id), furnace.pos_x, furnace.pos_y);
+    }
+    for piece in ctx.db.building_piece().iter().filter(|p| !p.is_destroyed) {
+        consider(TargetId::BuildingPiece(piece.id), piece.pos_x, piece.pos_y);
+    }
+    for loot_crate in ctx.db.loot_crate().iter().filter(|c| c.health > 0.0) {
+        consider(TargetId::LootCrate(loot_crate.id), loot_crate.pos_x, loot_crate.pos_y);
+    }
+    for workbench in ctx.db.workbench().iter().filter(|w| !w.is_destroyed) {
+        consider(TargetId::Workbench(workbench.id), workbench.pos_x, workbench.pos_y);
+    }
+    for sign in ctx.db.sign().iter().filter(|s| !s.is_destroyed) {
+        consider(TargetId::Sign(sign.id), sign.pos_x, sign.pos_y);
+    }
+    for switch in ctx.db.wiring_switch().iter().filter(|s| !s.is_destroyed) {
+        consider(TargetId::Switch(switch.id), switch.pos_x, switch.pos_y);
+    }
+    for recycler in ctx.db.recycler().iter().filter(|r| !r.is_destroyed) {
+        consider(TargetId::Recycler(recycler.id), recycler.pos_x, recycler.pos_y);
+    }
+    for turret in ctx.db.flame_turret().iter().filter(|t| !t.is_destroyed) {
+        consider(TargetId::FlameTurret(turret.id), turret.pos_x, turret.pos_y);
+    }
+    for mount in ctx.db.mount().iter() {
+        consider(TargetId::Mount(mount.id), mount.pos_x, mount.pos_y);
+    }
+    for player in ctx.db.player().iter().filter(|p| !p.is_dead && p.is_online) {
+        consider(TargetId::Player(player.identity), player.position_x, player.position_y);
+    }
+    for sleeper in ctx.db.sleeping_player().iter() {
+        consider(TargetId::SleepingPlayer(sleeper.id), sleeper.pos_x, sleeper.pos_y);
+    }
+    targets
+}
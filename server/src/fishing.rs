@@ -0,0 +1,248 @@
+/******************************************************************************
+ *                                                                            *
+ * Fishing. A player holding a Fishing Rod casts onto a water tile with       *
+ * `start_fishing`. While the line is out, a schedule rolls for bites; a bite *
+ * hooks one catch from the weighted FISHING_LOOT table (mostly Raw Fish,     *
+ * sometimes junk) and the player has a few seconds to `reel_in` before it    *
+ * gets away. Reeling in without a bite just ends fishing. The line breaks    *
+ * if the player dies, logs off, lowers the rod or walks away from it.        *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, TimeDuration, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use rand::Rng;
+use std::time::Duration;
+
+use crate::player as PlayerTableTrait;
+use crate::items::{inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
+use crate::active_equipment::active_equipment as ActiveEquipmentTableTrait;
+use crate::fishing::fishing_state as FishingStateTableTrait;
+use crate::fishing::fishing_schedule as FishingScheduleTableTrait;
+
+// --- Constants ---
+pub(crate) const FISHING_ROD_ITEM_NAME: &str = "Fishing Rod";
+const FISHING_TICK_INTERVAL_SECS: u64 = 2;
+/// Farthest a player can cast from where they stand, and how far they can wander while fishing.
+const MAX_CAST_DISTANCE: f32 = 160.0;
+/// No bites for this long after casting.
+const MIN_SECS_BEFORE_BITE: u64 = 4;
+/// Chance of a bite on each fishing tick once the line has settled.
+const BITE_CHANCE_PER_TICK: f32 = 0.2;
+/// How long a hooked catch stays on the line before it gets away.
+const BITE_WINDOW_SECS: u64 = 3;
+
+/// What can be caught: (item name, weight, min quantity, max quantity).
+const FISHING_LOOT: &[(&str, u32, u32, u32)] = &[
+    ("Raw Fish", 70, 1, 1),
+    ("Cloth", 8, 1, 3),
+    ("Bone Fragments", 8, 2, 5),
+    ("Wood", 8, 2, 6),
+    ("Scrap", 6, 1, 3),
+];
+
+/// --- Fishing State ---
+/// A player's line in the water. Public so clients can draw the bobber and bite.
+#[spacetimedb::table(name = fishing_state, public)]
+#[derive(Clone, Debug)]
+pub struct FishingState {
+    #[primary_key]
+    pub player_id: Identity,
+    pub rod_instance_id: u64,
+    pub bobber_x: f32,
+    pub bobber_y: f32,
+    pub cast_at: Timestamp,
+    pub hooked_item_name: Option<String>, // Set while a catch is on the line
+    pub hooked_quantity: u32,
+    pub bite_expires_at: Option<Timestamp>,
+}
+
+/// --- Fishing Schedule ---
+#[spacetimedb::table(name = fishing_schedule, scheduled(process_fishing))]
+#[derive(Clone, Debug)]
+pub struct FishingSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Start Fishing ---
+/// Casts the equipped Fishing Rod onto the water tile at (target_x, target_y).
+/// Casting again while already fishing recasts the line.
+#[spacetimedb::reducer]
+pub fn start_fishing(ctx: &ReducerContext, target_x: f32, target_y: f32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let sender_id = ctx.sender;
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Player not found".to_string())?;
+    if player.is_dead {
+        return Err("Cannot fish while dead.".to_string());
+    }
+    let rod_instance_id = equipped_rod_instance_id(ctx, sender_id)
+        .ok_or_else(|| "You need to hold a Fishing Rod to fish.".to_string())?;
+
+    let dx = target_x - player.position_x;
+    let dy = target_y - player.position_y;
+    if dx * dx + dy * dy > MAX_CAST_DISTANCE * MAX_CAST_DISTANCE {
+        return Err("Cannot cast that far.".to_string());
+    }
+    if !crate::world_tile::is_water_at(ctx, target_x, target_y) {
+        return Err("You can only fish in water.".to_string());
+    }
+
+    let state = FishingState {
+        player_id: sender_id,
+        rod_instance_id,
+        bobber_x: target_x,
+        bobber_y: target_y,
+        cast_at: ctx.timestamp,
+        hooked_item_name: None,
+        hooked_quantity: 0,
+        bite_expires_at: None,
+    };
+    let states = ctx.db.fishing_state();
+    if states.player_id().find(sender_id).is_some() {
+        states.player_id().update(state);
+    } else {
+        states.insert(state);
+    }
+    log::info!("[Fishing] Player {:?} cast at ({:.1}, {:.1}).", sender_id, target_x, target_y);
+    Ok(())
+}
+
+/// --- Reel In ---
+/// Ends fishing. If a catch is on the line it goes to the player's inventory, and the rod wears a little.
+#[spacetimedb::reducer]
+pub fn reel_in(ctx: &ReducerContext) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let states = ctx.db.fishing_state();
+    let state = states.player_id().find(sender_id)
+        .ok_or_else(|| "You are not fishing.".to_string())?;
+    states.player_id().delete(sender_id);
+
+    let hooked = state.hooked_item_name.as_ref()
+        .filter(|_| state.bite_expires_at.is_some_and(|expires| ctx.timestamp <= expires));
+    let Some(item_name) = hooked else {
+        log::debug!("[Fishing] Player {:?} reeled in with nothing on the line.", sender_id);
+        return Ok(());
+    };
+
+    let item_defs = ctx.db.item_definition();
+    let catch_def = item_defs.iter().find(|def| def.name == *item_name)
+        .ok_or_else(|| format!("Item definition '{}' not found.", item_name))?;
+    crate::items::add_item_to_player_inventory(ctx, sender_id, catch_def.id, state.hooked_quantity)?;
+
+    if let Some(rod_def) = item_defs.iter().find(|def| def.name == FISHING_ROD_ITEM_NAME) {
+        if ctx.db.inventory_item().instance_id().find(state.rod_instance_id).is_some() {
+            crate::combat::apply_durability_loss(ctx, state.rod_instance_id, &rod_def);
+        }
+    }
+    log::info!("[Fishing] Player {:?} caught {} x{}.", sender_id, item_name, state.hooked_quantity);
+    Ok(())
+}
+
+/******************************************************************************
+ *                           SCHEDULED REDUCERS                               *
+ ******************************************************************************/
+
+/// Starts the fishing schedule if it isn't already scheduled. Called from `init_module`.
+pub fn init_fishing_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.fishing_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting fishing schedule (every {}s).", FISHING_TICK_INTERVAL_SECS);
+        let interval = Duration::from_secs(FISHING_TICK_INTERVAL_SECS);
+        schedule_table.try_insert(FishingSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert fishing schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: ends fishing for players who can no longer fish, lets unreeled
+/// catches get away, and rolls for new bites.
+#[spacetimedb::reducer]
+pub fn process_fishing(ctx: &ReducerContext, _schedule: FishingSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("process_fishing may only be called by the scheduler.".to_string());
+    }
+
+    let states = ctx.db.fishing_state();
+    let settle_time = TimeDuration::from(Duration::from_secs(MIN_SECS_BEFORE_BITE));
+    let bite_window = TimeDuration::from(Duration::from_secs(BITE_WINDOW_SECS));
+    let all_states: Vec<FishingState> = states.iter().collect();
+    for mut state in all_states {
+        if !can_keep_fishing(ctx, &state) {
+            states.player_id().delete(state.player_id);
+            log::debug!("[Fishing] Player {:?} stopped fishing.", state.player_id);
+            continue;
+        }
+
+        if let Some(expires_at) = state.bite_expires_at {
+            if ctx.timestamp > expires_at {
+                log::debug!("[Fishing] The catch got away from player {:?}.", state.player_id);
+                state.hooked_item_name = None;
+                state.hooked_quantity = 0;
+                state.bite_expires_at = None;
+                states.player_id().update(state);
+            }
+            continue;
+        }
+
+        if ctx.timestamp < state.cast_at + settle_time || ctx.rng().gen::<f32>() >= BITE_CHANCE_PER_TICK {
+            continue;
+        }
+        let (item_name, quantity) = roll_fishing_loot(ctx);
+        state.hooked_item_name = Some(item_name.to_string());
+        state.hooked_quantity = quantity;
+        state.bite_expires_at = Some(ctx.timestamp + bite_window);
+        states.player_id().update(state);
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// The instance id of the Fishing Rod the player is holding, if any.
+fn equipped_rod_instance_id(ctx: &ReducerContext, player_id: Identity) -> Option<u64> {
+    let equipment = ctx.db.active_equipment().player_identity().find(player_id)?;
+    let instance_id = equipment.equipped_item_instance_id?;
+    let item = ctx.db.inventory_item().instance_id().find(instance_id)?;
+    let def = ctx.db.item_definition().id().find(item.item_def_id)?;
+    (def.name == FISHING_ROD_ITEM_NAME && !crate::combat::is_item_broken(&item, &def)).then_some(instance_id)
+}
+
+/// Whether the player is still online, alive, holding the same rod and near their bobber.
+fn can_keep_fishing(ctx: &ReducerContext, state: &FishingState) -> bool {
+    let Some(player) = ctx.db.player().identity().find(state.player_id) else { return false };
+    if !player.is_online || player.is_dead {
+        return false;
+    }
+    if equipped_rod_instance_id(ctx, state.player_id) != Some(state.rod_instance_id) {
+        return false;
+    }
+    let dx = state.bobber_x - player.position_x;
+    let dy = state.bobber_y - player.position_y;
+    dx * dx + dy * dy <= MAX_CAST_DISTANCE * MAX_CAST_DISTANCE
+}
+
+/// Picks one entry of FISHING_LOOT by weight and a quantity for it.
+fn roll_fishing_loot(ctx: &ReducerContext) -> (&'static str, u32) {
+    let total_weight: u32 = FISHING_LOOT.iter().map(|(_, weight, _, _)| weight).sum();
+    let mut roll = ctx.rng().gen_range(0..total_weight);
+    for &(item_name, weight, min_quantity, max_quantity) in FISHING_LOOT {
+        if roll < weight {
+            return (item_name, ctx.rng().gen_range(min_quantity..=max_quantity));
+        }
+        roll -= weight;
+    }
+    let (item_name, _, min_quantity, _) = FISHING_LOOT[0];
+    (item_name, min_quantity)
+}
@@ -0,0 +1,468 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines the Flame Turret: a placeable defence that scorches intruders.     *
+ * While switched on it burns fuel from its slots (any campfire fuel) and     *
+ * every tick sets fire to players inside its radius, using the same          *
+ * damage-zone check and burn effect as campfire hot zones. The owner and     *
+ * their teammates are never targeted, and only they can open the turret,     *
+ * refuel it or switch it. Players in zones where PvP is off are left alone.  *
+ * Slots are stored as a slot array, so item movement goes through the        *
+ * generic container handlers. Damaged and destroyed through combat.rs.       *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+
+use crate::player as PlayerTableTrait;
+use crate::Player;
+use crate::active_effects::EffectType;
+use crate::items::{
+    ItemDefinition,
+    inventory_item as InventoryItemTableTrait,
+    item_definition as ItemDefinitionTableTrait,
+    add_item_to_player_inventory
+};
+use crate::inventory_management::{self, ItemContainer, ContainerItemClearer, ContainerSlot, SlotArrayContainer, empty_container_slots, is_container_empty};
+use crate::environment::calculate_chunk_index;
+use crate::models::{ContainerType, ItemLocation};
+use crate::spatial_grid::{self, EntityType};
+use crate::flame_turret::flame_turret as FlameTurretTableTrait;
+use crate::flame_turret::flame_turret_schedule as FlameTurretScheduleTableTrait;
+
+// --- Constants ---
+pub(crate) const FLAME_TURRET_COLLISION_RADIUS: f32 = 20.0;
+pub(crate) const FLAME_TURRET_COLLISION_Y_OFFSET: f32 = 0.0;
+const FLAME_TURRET_INTERACTION_DISTANCE_SQUARED: f32 = 80.0 * 80.0;
+pub const NUM_FLAME_TURRET_SLOTS: usize = 3;
+const FLAME_TURRET_INITIAL_HEALTH: f32 = 400.0;
+pub(crate) const FLAME_TURRET_ITEM_NAME: &str = "Flame Turret";
+const FLAME_TURRET_PROCESS_INTERVAL_SECS: u64 = 1;
+/// Players whose position is this close to the turret get burned.
+const FLAME_TURRET_RANGE: f32 = 160.0;
+const FLAME_TURRET_RANGE_SQUARED: f32 = FLAME_TURRET_RANGE * FLAME_TURRET_RANGE;
+/// Burn damage per tick a target takes, applied as a one-second burn effect.
+const FLAME_TURRET_BURN_DAMAGE: f32 = 6.0;
+const FLAME_TURRET_BURN_DURATION_SECS: f32 = 1.0;
+
+/// --- Flame Turret Data Structure ---
+#[spacetimedb::table(name = flame_turret, public)]
+#[derive(Clone, Debug)]
+pub struct FlameTurret {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub chunk_index: u32,
+
+    pub placed_by: Identity,
+    pub placed_at: Timestamp,
+
+    pub slots: Vec<ContainerSlot>, // Fuel only
+    pub is_active: bool,
+    /// Seconds left on the unit of fuel currently burning; 0 when none is loaded.
+    pub remaining_fuel_burn_secs: f32,
+    pub last_fired_at: Option<Timestamp>, // Lets clients show the flames
+
+    pub health: f32,
+    pub max_health: f32,
+    pub is_destroyed: bool,
+    pub destroyed_at: Option<Timestamp>,
+    pub last_hit_time: Option<Timestamp>,
+}
+
+/// --- Flame Turret Processing Schedule ---
+#[spacetimedb::table(name = flame_turret_schedule, scheduled(process_flame_turrets))]
+#[derive(Clone, Debug)]
+pub struct FlameTurretSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/******************************************************************************
+ *                           REDUCERS (Generic Handlers)                      *
+ ******************************************************************************/
+
+/// Moves fuel from the player's inventory/hotbar INTO a flame turret slot.
+#[spacetimedb::reducer]
+pub fn move_item_to_flame_turret(ctx: &ReducerContext, turret_id: u32, target_slot_index: u8, item_instance_id: u64) -> Result<(), String> {
+    let (_player, mut turret) = validate_flame_turret_interaction(ctx, turret_id)?;
+    ensure_fuel(ctx, item_instance_id)?;
+    inventory_management::handle_move_to_container_slot(ctx, &mut turret, target_slot_index, item_instance_id)?;
+    ctx.db.flame_turret().id().update(turret);
+    Ok(())
+}
+
+/// Moves an item FROM a flame turret slot INTO the player's inventory/hotbar.
+#[spacetimedb::reducer]
+pub fn move_item_from_flame_turret(
+    ctx: &ReducerContext,
+    turret_id: u32,
+    source_slot_index: u8,
+    target_slot_type: String,
+    target_slot_index: u32
+) -> Result<(), String> {
+    let (_player, mut turret) = validate_flame_turret_interaction(ctx, turret_id)?;
+    inventory_management::handle_move_from_container_slot(ctx, &mut turret, source_slot_index, target_slot_type, target_slot_index)?;
+    ctx.db.flame_turret().id().update(turret);
+    Ok(())
+}
+
+/// Moves an item BETWEEN two slots within the same flame turret.
+#[spacetimedb::reducer]
+pub fn move_item_within_flame_turret(ctx: &ReducerContext, turret_id: u32, source_slot_index: u8, target_slot_index: u8) -> Result<(), String> {
+    let (_player, mut turret) = validate_flame_turret_interaction(ctx, turret_id)?;
+    inventory_management::handle_move_within_container(ctx, &mut turret, source_slot_index, target_slot_index)?;
+    ctx.db.flame_turret().id().update(turret);
+    Ok(())
+}
+
+/// Splits a stack of fuel from player inventory/hotbar into a specific flame turret slot.
+#[spacetimedb::reducer]
+pub fn split_stack_into_flame_turret(
+    ctx: &ReducerContext,
+    turret_id: u32,
+    target_slot_index: u8,
+    source_item_instance_id: u64,
+    quantity_to_split: u32,
+) -> Result<(), String> {
+    let (_player, mut turret) = validate_flame_turret_interaction(ctx, turret_id)?;
+    ensure_fuel(ctx, source_item_instance_id)?;
+    inventory_management::handle_split_into_container(ctx, &mut turret, target_slot_index, source_item_instance_id, quantity_to_split)?;
+    ctx.db.flame_turret().id().update(turret);
+    Ok(())
+}
+
+/// Splits a stack from a flame turret slot into the player's inventory/hotbar.
+#[spacetimedb::reducer]
+pub fn split_stack_from_flame_turret(
+    ctx: &ReducerContext,
+    turret_id: u32,
+    source_slot_index: u8,
+    quantity_to_split: u32,
+    target_slot_type: String,
+    target_slot_index: u32,
+) -> Result<(), String> {
+    let (_player, mut turret) = validate_flame_turret_interaction(ctx, turret_id)?;
+    inventory_management::handle_split_from_container(ctx, &mut turret, source_slot_index, quantity_to_split, target_slot_type, target_slot_index)?;
+    ctx.db.flame_turret().id().update(turret);
+    Ok(())
+}
+
+/// Splits a stack FROM one flame turret slot TO another within the same turret.
+#[spacetimedb::reducer]
+pub fn split_stack_within_flame_turret(
+    ctx: &ReducerContext,
+    turret_id: u32,
+    source_slot_index: u8,
+    target_slot_index: u8,
+    quantity_to_split: u32,
+) -> Result<(), String> {
+    let (_player, mut turret) = validate_flame_turret_interaction(ctx, turret_id)?;
+    inventory_management::handle_split_within_container(ctx, &mut turret, source_slot_index, target_slot_index, quantity_to_split)?;
+    ctx.db.flame_turret().id().update(turret);
+    Ok(())
+}
+
+/// Quickly moves an item FROM a flame turret slot TO the player inventory.
+#[spacetimedb::reducer]
+pub fn quick_move_from_flame_turret(ctx: &ReducerContext, turret_id: u32, source_slot_index: u8) -> Result<(), String> {
+    let (_player, mut turret) = validate_flame_turret_interaction(ctx, turret_id)?;
+    inventory_management::handle_quick_move_from_container(ctx, &mut turret, source_slot_index)?;
+    ctx.db.flame_turret().id().update(turret);
+    Ok(())
+}
+
+/// Quickly moves fuel FROM player inventory/hotbar TO the first available/mergeable turret slot.
+#[spacetimedb::reducer]
+pub fn quick_move_to_flame_turret(ctx: &ReducerContext, turret_id: u32, item_instance_id: u64) -> Result<(), String> {
+    let (_player, mut turret) = validate_flame_turret_interaction(ctx, turret_id)?;
+    ensure_fuel(ctx, item_instance_id)?;
+    inventory_management::handle_quick_move_to_container(ctx, &mut turret, item_instance_id)?;
+    ctx.db.flame_turret().id().update(turret);
+    Ok(())
+}
+
+/// Drops the whole stack in a flame turret slot onto the ground near the player.
+#[spacetimedb::reducer]
+pub fn drop_item_from_flame_turret_slot_to_world(ctx: &ReducerContext, turret_id: u32, slot_index: u8) -> Result<(), String> {
+    let (player, mut turret) = validate_flame_turret_interaction(ctx, turret_id)?;
+    inventory_management::handle_drop_from_container_slot(ctx, &mut turret, slot_index, &player)?;
+    ctx.db.flame_turret().id().update(turret);
+    Ok(())
+}
+
+/******************************************************************************
+ *                   REDUCERS (Flame Turret-Specific Logic)                   *
+ ******************************************************************************/
+
+/// --- Place Flame Turret ---
+/// Places a Flame Turret item from the player's inventory/hotbar into the world.
+#[spacetimedb::reducer]
+pub fn place_flame_turret(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    crate::placement::place_deployable(ctx, item_instance_id, world_x, world_y, |name| name == FLAME_TURRET_ITEM_NAME)
+}
+
+/// Creates a switched-off, empty flame turret. The placement constructor for Flame Turret items.
+pub(crate) fn spawn_flame_turret(ctx: &ReducerContext, placed_by: Identity, _item_def: &ItemDefinition, _item_durability: Option<f32>, world_x: f32, world_y: f32) -> Result<(), String> {
+    let inserted = ctx.db.flame_turret().try_insert(FlameTurret {
+        id: 0, // Auto-incremented
+        pos_x: world_x,
+        pos_y: world_y,
+        chunk_index: calculate_chunk_index(world_x, world_y),
+        placed_by,
+        placed_at: ctx.timestamp,
+        slots: empty_container_slots(NUM_FLAME_TURRET_SLOTS),
+        is_active: false,
+        remaining_fuel_burn_secs: 0.0,
+        last_fired_at: None,
+        health: FLAME_TURRET_INITIAL_HEALTH,
+        max_health: FLAME_TURRET_INITIAL_HEALTH,
+        is_destroyed: false,
+        destroyed_at: None,
+        last_hit_time: None,
+    }).map_err(|e| format!("Failed to insert flame turret entity: {}", e))?;
+    spatial_grid::track_entity(ctx, EntityType::FlameTurret(inserted.id), world_x, world_y);
+    log::info!("[FlameTurret] Player {:?} placed Flame Turret {} at ({:.1}, {:.1}).", placed_by, inserted.id, world_x, world_y);
+    Ok(())
+}
+
+/// --- Interact with Flame Turret ---
+#[spacetimedb::reducer]
+pub fn interact_with_flame_turret(ctx: &ReducerContext, turret_id: u32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    validate_flame_turret_interaction(ctx, turret_id)?;
+    Ok(())
+}
+
+/// --- Toggle Flame Turret ---
+/// Switches the turret on or off. Switching on requires fuel in its slots.
+#[spacetimedb::reducer]
+pub fn toggle_flame_turret(ctx: &ReducerContext, turret_id: u32) -> Result<(), String> {
+    let (_player, mut turret) = validate_flame_turret_interaction(ctx, turret_id)?;
+    if turret.is_active {
+        turret.is_active = false;
+        log::info!("[FlameTurret] Flame Turret {} switched off by player {:?}.", turret_id, ctx.sender);
+    } else {
+        if turret.remaining_fuel_burn_secs <= 0.0 && find_fuel_slot(ctx, &turret).is_none() {
+            return Err("The flame turret needs fuel.".to_string());
+        }
+        turret.is_active = true;
+        log::info!("[FlameTurret] Flame Turret {} switched on by player {:?}.", turret_id, ctx.sender);
+    }
+    ctx.db.flame_turret().id().update(turret);
+    Ok(())
+}
+
+/// --- Pickup Flame Turret ---
+/// Picks up an *empty* flame turret, returning it to the player's inventory.
+#[spacetimedb::reducer]
+pub fn pickup_flame_turret(ctx: &ReducerContext, turret_id: u32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let (_player, turret) = validate_flame_turret_interaction(ctx, turret_id)?;
+    if !is_container_empty(&turret) {
+        return Err("Cannot pick up flame turret: It is not empty.".to_string());
+    }
+
+    let turret_item_def = ctx.db.item_definition().iter()
+        .find(|def| def.name == FLAME_TURRET_ITEM_NAME)
+        .ok_or_else(|| format!("ItemDefinition for '{}' not found.", FLAME_TURRET_ITEM_NAME))?;
+    add_item_to_player_inventory(ctx, sender_id, turret_item_def.id, 1)
+        .map_err(|e| format!("Could not add {} to your inventory: {}", FLAME_TURRET_ITEM_NAME, e))?;
+
+    ctx.db.flame_turret().id().delete(turret_id);
+    spatial_grid::untrack_entity(ctx, EntityType::FlameTurret(turret_id));
+    log::info!("[FlameTurret] Flame Turret {} picked up by player {:?}.", turret_id, sender_id);
+    Ok(())
+}
+
+/******************************************************************************
+ *                           SCHEDULED REDUCERS                               *
+ ******************************************************************************/
+
+/// Starts the flame turret schedule if it isn't already scheduled. Called from `init_module`.
+pub fn init_flame_turret_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.flame_turret_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting flame turret schedule (every {}s).", FLAME_TURRET_PROCESS_INTERVAL_SECS);
+        let interval = Duration::from_secs(FLAME_TURRET_PROCESS_INTERVAL_SECS);
+        schedule_table.try_insert(FlameTurretSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert flame turret schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: burns fuel in every active turret and sets fire to intruders in range.
+/// Turrets that run out of fuel switch off.
+#[spacetimedb::reducer]
+pub fn process_flame_turrets(ctx: &ReducerContext, _schedule: FlameTurretSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("process_flame_turrets may only be called by the scheduler.".to_string());
+    }
+
+    let active: Vec<FlameTurret> = ctx.db.flame_turret().iter()
+        .filter(|t| t.is_active && !t.is_destroyed)
+        .collect();
+    for mut turret in active {
+        if turret.remaining_fuel_burn_secs <= 0.0 && !load_next_fuel_unit(ctx, &mut turret) {
+            log::debug!("[FlameTurret] Flame Turret {} ran out of fuel. Switching off.", turret.id);
+            turret.is_active = false;
+            ctx.db.flame_turret().id().update(turret);
+            continue;
+        }
+        turret.remaining_fuel_burn_secs = (turret.remaining_fuel_burn_secs - FLAME_TURRET_PROCESS_INTERVAL_SECS as f32).max(0.0);
+
+        if burn_intruders(ctx, &turret) > 0 {
+            turret.last_fired_at = Some(ctx.timestamp);
+        }
+        ctx.db.flame_turret().id().update(turret);
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                            TRAIT IMPLEMENTATIONS                           *
+ ******************************************************************************/
+
+impl SlotArrayContainer for FlameTurret {
+    fn slots(&self) -> &[ContainerSlot] {
+        &self.slots
+    }
+
+    fn slots_mut(&mut self) -> &mut [ContainerSlot] {
+        &mut self.slots
+    }
+
+    fn slot_container_type(&self) -> ContainerType {
+        ContainerType::FlameTurret
+    }
+
+    fn slot_container_id(&self) -> u64 {
+        self.id as u64
+    }
+}
+
+/// --- Helper struct to implement the ContainerItemClearer trait for FlameTurret ---
+pub struct FlameTurretClearer;
+
+impl ContainerItemClearer for FlameTurretClearer {
+    fn clear_item(ctx: &ReducerContext, item_instance_id: u64) -> bool {
+        let turrets = ctx.db.flame_turret();
+        for mut turret in turrets.iter() {
+            let Some(slot_index) = (0..turret.num_slots() as u8).find(|&i| turret.get_slot_instance_id(i) == Some(item_instance_id)) else {
+                continue;
+            };
+            turret.set_slot(slot_index, None, None);
+            turrets.id().update(turret);
+
+            let inventory_items = ctx.db.inventory_item();
+            if let Some(mut item) = inventory_items.instance_id().find(item_instance_id) {
+                item.location = ItemLocation::Unknown;
+                inventory_items.instance_id().update(item);
+            }
+            return true;
+        }
+        false
+    }
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Whether the turret leaves this player alone: its owner and the owner's teammates.
+fn is_authorized(ctx: &ReducerContext, turret: &FlameTurret, player_id: Identity) -> bool {
+    crate::team::is_owner_or_teammate(ctx, player_id, turret.placed_by)
+}
+
+/// Validates that the sender can use the turret: it exists, isn't destroyed, they are
+/// authorized on it and within reach.
+fn validate_flame_turret_interaction(ctx: &ReducerContext, turret_id: u32) -> Result<(Player, FlameTurret), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or_else(|| "Player not found".to_string())?;
+    let turret = ctx.db.flame_turret().id().find(turret_id)
+        .ok_or_else(|| format!("Flame Turret {} not found", turret_id))?;
+    if turret.is_destroyed {
+        return Err(format!("Flame Turret {} is destroyed.", turret_id));
+    }
+    if !is_authorized(ctx, &turret, ctx.sender) {
+        return Err("Only the turret's owner and their team can use it.".to_string());
+    }
+
+    let dx = player.position_x - turret.pos_x;
+    let dy = player.position_y - turret.pos_y;
+    if (dx * dx + dy * dy) > FLAME_TURRET_INTERACTION_DISTANCE_SQUARED {
+        return Err("Too far away".to_string());
+    }
+    Ok((player, turret))
+}
+
+/// Rejects items that don't burn before they are put into a turret.
+fn ensure_fuel(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), String> {
+    let item = ctx.db.inventory_item().instance_id().find(item_instance_id)
+        .ok_or_else(|| format!("Item instance {} not found.", item_instance_id))?;
+    let item_def = ctx.db.item_definition().id().find(item.item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found.", item.item_def_id))?;
+    if !item_def.fuel_burn_duration_secs.is_some_and(|secs| secs > 0.0) {
+        return Err(format!("{} can't fuel a flame turret.", item_def.name));
+    }
+    Ok(())
+}
+
+/// Returns the first slot holding fuel, with that fuel's burn time.
+fn find_fuel_slot(ctx: &ReducerContext, turret: &FlameTurret) -> Option<(u8, f32)> {
+    (0..NUM_FLAME_TURRET_SLOTS as u8).find_map(|i| {
+        let def_id = turret.get_slot_def_id(i)?;
+        let burn_secs = ctx.db.item_definition().id().find(def_id)?.fuel_burn_duration_secs?;
+        (burn_secs > 0.0).then_some((i, burn_secs))
+    })
+}
+
+/// Consumes one unit of fuel from the turret's slots and loads its burn time.
+/// Returns false if there was no fuel left.
+fn load_next_fuel_unit(ctx: &ReducerContext, turret: &mut FlameTurret) -> bool {
+    let Some((slot_index, burn_secs)) = find_fuel_slot(ctx, turret) else { return false; };
+    let Some(instance_id) = turret.get_slot_instance_id(slot_index) else { return false; };
+    let inventory_items = ctx.db.inventory_item();
+    let Some(mut fuel_item) = inventory_items.instance_id().find(instance_id) else {
+        turret.set_slot(slot_index, None, None);
+        return false;
+    };
+    if fuel_item.quantity > 1 {
+        fuel_item.quantity -= 1;
+        inventory_items.instance_id().update(fuel_item);
+    } else {
+        inventory_items.instance_id().delete(instance_id);
+        turret.set_slot(slot_index, None, None);
+    }
+    turret.remaining_fuel_burn_secs = burn_secs;
+    true
+}
+
+/// Sets fire to every unauthorized player in range that can take environmental damage
+/// and isn't in a zone with PvP turned off. Returns how many were hit.
+fn burn_intruders(ctx: &ReducerContext, turret: &FlameTurret) -> u32 {
+    let mut hit = 0;
+    for entity in spatial_grid::get_entities_in_radius(ctx, turret.pos_x, turret.pos_y, FLAME_TURRET_RANGE) {
+        let EntityType::Player(player_id) = entity else { continue; };
+        if is_authorized(ctx, turret, player_id) {
+            continue;
+        }
+        let Some(player) = ctx.db.player().identity().find(player_id) else { continue; };
+        if !crate::active_effects::can_take_environmental_damage(ctx, &player, ctx.timestamp)
+            || !crate::utils::is_in_damage_zone(player.position_x, player.position_y, turret.pos_x, turret.pos_y, FLAME_TURRET_RANGE_SQUARED)
+            || !crate::zone::is_pvp_allowed_at(ctx, player.position_x, player.position_y) {
+            continue;
+        }
+        crate::active_effects::apply_hazard_contact_damage(ctx, player_id, EffectType::Burn, FLAME_TURRET_BURN_DAMAGE, FLAME_TURRET_BURN_DURATION_SECS);
+        hit += 1;
+    }
+    hit
+}
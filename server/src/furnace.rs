@@ -0,0 +1,696 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines the Furnace entity, its data structure, and associated logic.      *
+ * Mirrors campfire.rs: handles placement, adding/removing fuel and ore,      *
+ * lighting/extinguishing, fuel consumption and smelting Metal Ore into       *
+ * Metal Fragments. Uses generic handlers from inventory_management.rs and    *
+ * cooking.rs where applicable.                                               *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, Timestamp, ReducerContext, Table, log, TimeDuration, ScheduleAt};
+
+use crate::models::{ContainerType, ItemLocation, ContainerLocationData};
+use crate::cooking::CookingProgress;
+
+// Import table traits and concrete types
+use crate::player as PlayerTableTrait;
+use crate::Player;
+use crate::items::{
+    inventory_item as InventoryItemTableTrait,
+    item_definition as ItemDefinitionTableTrait,
+    ItemDefinition,
+    split_stack_helper,
+};
+use crate::inventory_management::{self, ItemContainer, ContainerItemClearer, merge_or_place_into_container_slot};
+use crate::player_inventory::get_player_item;
+use crate::environment::calculate_chunk_index;
+
+// --- Constants ---
+// Collision constants
+pub(crate) const FURNACE_COLLISION_RADIUS: f32 = 24.0;
+pub(crate) const FURNACE_COLLISION_Y_OFFSET: f32 = 0.0;
+
+// Interaction constants
+pub(crate) const PLAYER_FURNACE_INTERACTION_DISTANCE: f32 = 96.0;
+pub(crate) const PLAYER_FURNACE_INTERACTION_DISTANCE_SQUARED: f32 =
+    PLAYER_FURNACE_INTERACTION_DISTANCE * PLAYER_FURNACE_INTERACTION_DISTANCE;
+
+// Fuel and smelting constants
+pub const NUM_FURNACE_SLOTS: usize = 5;
+pub const FURNACE_PROCESS_INTERVAL_SECS: u64 = 1; // How often to run the main logic when burning
+const FURNACE_INITIAL_HEALTH: f32 = 500.0;
+
+/// Items that can only be processed by a smelting appliance (see `CookableAppliance::is_smelting_appliance`).
+pub(crate) const SMELTABLE_ITEM_NAMES: &[&str] = &["Metal Ore"];
+
+/// --- Furnace Data Structure ---
+/// Represents a furnace in the game world with position, owner, burning state,
+/// slots shared by fuel and ore (using individual fields instead of arrays), and fuel consumption timing.
+#[spacetimedb::table(name = furnace, public)]
+#[derive(Clone)]
+pub struct Furnace {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    #[index(btree)]
+    pub chunk_index: u32,
+    pub placed_by: Identity,
+    pub placed_at: Timestamp,
+    pub is_burning: bool,
+    pub slot_instance_id_0: Option<u64>,
+    pub slot_def_id_0: Option<u64>,
+    pub slot_instance_id_1: Option<u64>,
+    pub slot_def_id_1: Option<u64>,
+    pub slot_instance_id_2: Option<u64>,
+    pub slot_def_id_2: Option<u64>,
+    pub slot_instance_id_3: Option<u64>,
+    pub slot_def_id_3: Option<u64>,
+    pub slot_instance_id_4: Option<u64>,
+    pub slot_def_id_4: Option<u64>,
+    pub current_fuel_def_id: Option<u64>,
+    pub remaining_fuel_burn_time_secs: Option<f32>,
+    pub health: f32,
+    pub max_health: f32,
+    pub is_destroyed: bool,
+    pub destroyed_at: Option<Timestamp>,
+    pub last_hit_time: Option<Timestamp>,
+
+    // Smelting progress for each slot
+    pub slot_0_cooking_progress: Option<CookingProgress>,
+    pub slot_1_cooking_progress: Option<CookingProgress>,
+    pub slot_2_cooking_progress: Option<CookingProgress>,
+    pub slot_3_cooking_progress: Option<CookingProgress>,
+    pub slot_4_cooking_progress: Option<CookingProgress>,
+}
+
+// Schedule table for per-furnace processing
+#[spacetimedb::table(name = furnace_processing_schedule, scheduled(process_furnace_logic_scheduled))]
+#[derive(Clone)]
+pub struct FurnaceProcessingSchedule {
+    #[primary_key] // Stores the furnace_id to make the schedule unique per furnace
+    pub furnace_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/******************************************************************************
+ *                           REDUCERS (Generic Handlers)                      *
+ ******************************************************************************/
+
+/// --- Add Item to Furnace ---
+/// Moves an item (fuel or ore) from the player's inventory into a specific furnace slot.
+#[spacetimedb::reducer]
+pub fn add_item_to_furnace(ctx: &ReducerContext, furnace_id: u32, target_slot_index: u8, item_instance_id: u64) -> Result<(), String> {
+    let (_player, mut furnace) = validate_furnace_interaction(ctx, furnace_id)?;
+    inventory_management::handle_move_to_container_slot(ctx, &mut furnace, target_slot_index, item_instance_id)?;
+    ctx.db.furnace().id().update(furnace);
+    schedule_next_furnace_processing(ctx, furnace_id)
+}
+
+/// --- Remove Item from Furnace ---
+/// Returns the item in a furnace slot to the player inventory/hotbar (merge first, then first empty slot).
+#[spacetimedb::reducer]
+pub fn auto_remove_item_from_furnace(ctx: &ReducerContext, furnace_id: u32, source_slot_index: u8) -> Result<(), String> {
+    let (_player, mut furnace) = validate_furnace_interaction(ctx, furnace_id)?;
+    inventory_management::handle_quick_move_from_container(ctx, &mut furnace, source_slot_index)?;
+    extinguish_if_out_of_fuel(ctx, &mut furnace);
+    ctx.db.furnace().id().update(furnace);
+    schedule_next_furnace_processing(ctx, furnace_id)
+}
+
+/// --- Split Stack Into Furnace ---
+/// Splits a stack from player inventory into a furnace slot.
+#[spacetimedb::reducer]
+pub fn split_stack_into_furnace(
+    ctx: &ReducerContext,
+    source_item_instance_id: u64,
+    quantity_to_split: u32,
+    target_furnace_id: u32,
+    target_slot_index: u8,
+) -> Result<(), String> {
+    let (_player, mut furnace) = validate_furnace_interaction(ctx, target_furnace_id)?;
+    let mut source_item = get_player_item(ctx, source_item_instance_id)?;
+    let new_item_target_location = ItemLocation::Container(ContainerLocationData {
+        container_type: ContainerType::Furnace,
+        container_id: furnace.id as u64,
+        slot_index: target_slot_index,
+    });
+    let new_item_instance_id = split_stack_helper(ctx, &mut source_item, quantity_to_split, new_item_target_location)?;
+
+    let mut new_item = ctx.db.inventory_item().instance_id().find(new_item_instance_id)
+        .ok_or_else(|| format!("Failed to find newly split item instance {}", new_item_instance_id))?;
+    let new_item_def = ctx.db.item_definition().id().find(new_item.item_def_id)
+        .ok_or_else(|| format!("Failed to find definition for new item {}", new_item.item_def_id))?;
+
+    merge_or_place_into_container_slot(ctx, &mut furnace, target_slot_index, &mut new_item, &new_item_def)?;
+    crate::cooking::record_cook_contributor(ctx, &furnace, target_slot_index, ctx.sender);
+
+    ctx.db.inventory_item().instance_id().update(source_item);
+    ctx.db.furnace().id().update(furnace);
+    schedule_next_furnace_processing(ctx, target_furnace_id)
+}
+
+/// --- Furnace Internal Item Movement ---
+/// Moves/merges/swaps an item BETWEEN two slots within the same furnace.
+#[spacetimedb::reducer]
+pub fn move_item_within_furnace(
+    ctx: &ReducerContext,
+    furnace_id: u32,
+    source_slot_index: u8,
+    target_slot_index: u8,
+) -> Result<(), String> {
+    let (_player, mut furnace) = validate_furnace_interaction(ctx, furnace_id)?;
+    inventory_management::handle_move_within_container(ctx, &mut furnace, source_slot_index, target_slot_index)?;
+    ctx.db.furnace().id().update(furnace);
+    schedule_next_furnace_processing(ctx, furnace_id)
+}
+
+/// --- Furnace Internal Stack Splitting ---
+/// Splits a stack FROM one furnace slot TO another within the same furnace.
+#[spacetimedb::reducer]
+pub fn split_stack_within_furnace(
+    ctx: &ReducerContext,
+    furnace_id: u32,
+    source_slot_index: u8,
+    quantity_to_split: u32,
+    target_slot_index: u8,
+) -> Result<(), String> {
+    let (_player, mut furnace) = validate_furnace_interaction(ctx, furnace_id)?;
+    inventory_management::handle_split_within_container(ctx, &mut furnace, source_slot_index, target_slot_index, quantity_to_split)?;
+    ctx.db.furnace().id().update(furnace);
+    schedule_next_furnace_processing(ctx, furnace_id)
+}
+
+/// --- Quick Move to Furnace ---
+/// Quickly moves an item from player inventory/hotbar to the first available/mergeable furnace slot.
+#[spacetimedb::reducer]
+pub fn quick_move_to_furnace(ctx: &ReducerContext, furnace_id: u32, item_instance_id: u64) -> Result<(), String> {
+    let (_player, mut furnace) = validate_furnace_interaction(ctx, furnace_id)?;
+    inventory_management::handle_quick_move_to_container(ctx, &mut furnace, item_instance_id)?;
+    ctx.db.furnace().id().update(furnace);
+    schedule_next_furnace_processing(ctx, furnace_id)
+}
+
+/// --- Move From Furnace to Player ---
+/// Moves an item FROM a furnace slot TO a specific player inventory/hotbar slot.
+#[spacetimedb::reducer]
+pub fn move_item_from_furnace_to_player_slot(
+    ctx: &ReducerContext,
+    furnace_id: u32,
+    source_slot_index: u8,
+    target_slot_type: String,
+    target_slot_index: u32,
+) -> Result<(), String> {
+    let (_player, mut furnace) = validate_furnace_interaction(ctx, furnace_id)?;
+    inventory_management::handle_move_from_container_slot(ctx, &mut furnace, source_slot_index, target_slot_type, target_slot_index)?;
+    extinguish_if_out_of_fuel(ctx, &mut furnace);
+    ctx.db.furnace().id().update(furnace);
+    schedule_next_furnace_processing(ctx, furnace_id)
+}
+
+/// --- Split From Furnace to Player ---
+/// Splits a stack FROM a furnace slot TO a specific player inventory/hotbar slot.
+#[spacetimedb::reducer]
+pub fn split_stack_from_furnace(
+    ctx: &ReducerContext,
+    source_furnace_id: u32,
+    source_slot_index: u8,
+    quantity_to_split: u32,
+    target_slot_type: String,    // "inventory" or "hotbar"
+    target_slot_index: u32,
+) -> Result<(), String> {
+    let (_player, mut furnace) = validate_furnace_interaction(ctx, source_furnace_id)?;
+    inventory_management::handle_split_from_container(
+        ctx,
+        &mut furnace,
+        source_slot_index,
+        quantity_to_split,
+        target_slot_type,
+        target_slot_index,
+    )?;
+    ctx.db.furnace().id().update(furnace);
+    Ok(())
+}
+
+/// --- Drop Item from Furnace Slot to World ---
+#[spacetimedb::reducer]
+pub fn drop_item_from_furnace_slot_to_world(ctx: &ReducerContext, furnace_id: u32, slot_index: u8) -> Result<(), String> {
+    let (player, mut furnace) = validate_furnace_interaction(ctx, furnace_id)?;
+    inventory_management::handle_drop_from_container_slot(ctx, &mut furnace, slot_index, &player)?;
+    extinguish_if_out_of_fuel(ctx, &mut furnace);
+    ctx.db.furnace().id().update(furnace);
+    schedule_next_furnace_processing(ctx, furnace_id)
+}
+
+/// --- Split and Drop Item from Furnace Slot to World ---
+#[spacetimedb::reducer]
+pub fn split_and_drop_item_from_furnace_slot_to_world(
+    ctx: &ReducerContext,
+    furnace_id: u32,
+    slot_index: u8,
+    quantity_to_split: u32,
+) -> Result<(), String> {
+    let (player, mut furnace) = validate_furnace_interaction(ctx, furnace_id)?;
+    inventory_management::handle_split_and_drop_from_container_slot(ctx, &mut furnace, slot_index, quantity_to_split, &player)?;
+    ctx.db.furnace().id().update(furnace);
+    Ok(())
+}
+
+/******************************************************************************
+ *                       REDUCERS (Furnace-Specific Logic)                    *
+ ******************************************************************************/
+
+/// --- Place Furnace ---
+/// Consumes a Furnace item from the player's inventory/hotbar and places the entity in the world.
+#[spacetimedb::reducer]
+pub fn place_furnace(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    crate::placement::place_deployable(ctx, item_instance_id, world_x, world_y, |name| name == "Furnace")
+}
+
+/// Creates an unlit, empty furnace. The placement constructor for Furnace items.
+pub(crate) fn spawn_furnace(ctx: &ReducerContext, placed_by: Identity, _item_def: &ItemDefinition, _item_durability: Option<f32>, world_x: f32, world_y: f32) -> Result<(), String> {
+    let new_furnace = Furnace {
+        id: 0, // Auto-incremented
+        pos_x: world_x,
+        pos_y: world_y,
+        chunk_index: calculate_chunk_index(world_x, world_y),
+        placed_by,
+        placed_at: ctx.timestamp,
+        is_burning: false, // Furnaces start unlit
+        slot_instance_id_0: None, slot_def_id_0: None,
+        slot_instance_id_1: None, slot_def_id_1: None,
+        slot_instance_id_2: None, slot_def_id_2: None,
+        slot_instance_id_3: None, slot_def_id_3: None,
+        slot_instance_id_4: None, slot_def_id_4: None,
+        current_fuel_def_id: None,
+        remaining_fuel_burn_time_secs: None,
+        health: FURNACE_INITIAL_HEALTH,
+        max_health: FURNACE_INITIAL_HEALTH,
+        is_destroyed: false,
+        destroyed_at: None,
+        last_hit_time: None,
+        slot_0_cooking_progress: None,
+        slot_1_cooking_progress: None,
+        slot_2_cooking_progress: None,
+        slot_3_cooking_progress: None,
+        slot_4_cooking_progress: None,
+    };
+    let inserted_furnace = ctx.db.furnace().try_insert(new_furnace)
+        .map_err(|e| format!("Failed to insert furnace entity: {}", e))?;
+    crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::Furnace(inserted_furnace.id), inserted_furnace.pos_x, inserted_furnace.pos_y);
+    log::info!("[PlaceFurnace] Player {:?} placed Furnace {} at ({:.1}, {:.1}).", placed_by, inserted_furnace.id, world_x, world_y);
+    Ok(())
+}
+
+/// --- Furnace Interaction Check ---
+/// Allows a player to interact with a furnace if they are close enough.
+#[spacetimedb::reducer]
+pub fn interact_with_furnace(ctx: &ReducerContext, furnace_id: u32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let (_player, _furnace) = validate_furnace_interaction(ctx, furnace_id)?;
+    Ok(())
+}
+
+/// --- Furnace Burning State Toggle ---
+/// Lights or extinguishes the furnace. Lighting requires at least one fuel item in its slots.
+#[spacetimedb::reducer]
+pub fn toggle_furnace_burning(ctx: &ReducerContext, furnace_id: u32) -> Result<(), String> {
+    let (_player, furnace) = validate_furnace_interaction(ctx, furnace_id)?;
+    let burning = !furnace.is_burning;
+    set_furnace_burning(ctx, furnace, burning)
+}
+
+/// Lights or extinguishes a furnace without any interaction checks. Does nothing if it is
+/// already in that state. Used by the toggle reducer and by wired switches (wiring.rs).
+pub(crate) fn set_furnace_burning(ctx: &ReducerContext, mut furnace: Furnace, burning: bool) -> Result<(), String> {
+    if furnace.is_burning == burning {
+        return Ok(());
+    }
+    let furnace_id = furnace.id;
+    if furnace.is_burning {
+        furnace.is_burning = false;
+        furnace.current_fuel_def_id = None;
+        furnace.remaining_fuel_burn_time_secs = None;
+        log::info!("Furnace {} extinguished by player {:?}.", furnace.id, ctx.sender);
+    } else {
+        if !check_if_furnace_has_fuel(ctx, &furnace) {
+            return Err("Cannot light furnace, requires fuel.".to_string());
+        }
+        furnace.is_burning = true;
+        // remaining_fuel_burn_time_secs will be set by the first call to process_furnace_logic_scheduled
+        log::info!("Furnace {} lit by player {:?}.", furnace.id, ctx.sender);
+    }
+    ctx.db.furnace().id().update(furnace);
+    schedule_next_furnace_processing(ctx, furnace_id)
+}
+
+/******************************************************************************
+ *                           SCHEDULED REDUCERS                               *
+ ******************************************************************************/
+
+/// Scheduled reducer: Processes fuel consumption and smelting for a burning furnace.
+#[spacetimedb::reducer]
+pub fn process_furnace_logic_scheduled(ctx: &ReducerContext, schedule_args: FurnaceProcessingSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("Unauthorized scheduler invocation".to_string());
+    }
+
+    let furnace_id = schedule_args.furnace_id as u32;
+    let mut furnace = match ctx.db.furnace().id().find(furnace_id) {
+        Some(f) if !f.is_destroyed => f,
+        _ => {
+            log::debug!("[ProcessFurnaceScheduled] Furnace {} missing or destroyed. Removing schedule.", furnace_id);
+            ctx.db.furnace_processing_schedule().furnace_id().delete(furnace_id as u64);
+            return Ok(());
+        }
+    };
+
+    if !furnace.is_burning {
+        return schedule_next_furnace_processing(ctx, furnace_id);
+    }
+
+    let time_increment = FURNACE_PROCESS_INTERVAL_SECS as f32;
+
+    // --- SMELTING LOGIC (delegated) ---
+    let active_fuel_instance_id = active_fuel_instance_id(&furnace);
+    if let Err(e) = crate::cooking::process_appliance_cooking_tick(ctx, &mut furnace, time_increment, active_fuel_instance_id) {
+        log::error!("[ProcessFurnaceScheduled] Error during smelting tick for furnace {}: {}", furnace.id, e);
+    }
+
+    // --- FUEL CONSUMPTION LOGIC ---
+    match furnace.remaining_fuel_burn_time_secs {
+        Some(remaining_time) if remaining_time - time_increment > 0.0 => {
+            furnace.remaining_fuel_burn_time_secs = Some(remaining_time - time_increment);
+        }
+        Some(_) => {
+            consume_current_fuel_unit(ctx, &mut furnace);
+        }
+        None => {}
+    }
+
+    if furnace.current_fuel_def_id.is_none() && !load_next_fuel_unit(ctx, &mut furnace) {
+        log::info!("[ProcessFurnaceScheduled] Furnace {} ran out of fuel.", furnace.id);
+        furnace.is_burning = false;
+        furnace.remaining_fuel_burn_time_secs = None;
+    }
+
+    // The burn timer changes every tick while lit, so the furnace is always written back.
+    ctx.db.furnace().id().update(furnace);
+    schedule_next_furnace_processing(ctx, furnace_id)
+}
+
+/// Schedules or removes the periodic processing for a furnace based on its burning state.
+/// Call this after lighting, extinguishing, adding, or removing items.
+pub(crate) fn schedule_next_furnace_processing(ctx: &ReducerContext, furnace_id: u32) -> Result<(), String> {
+    let schedules = ctx.db.furnace_processing_schedule();
+    let furnace = match ctx.db.furnace().id().find(furnace_id) {
+        Some(f) if !f.is_destroyed => f,
+        _ => {
+            schedules.furnace_id().delete(furnace_id as u64);
+            return Ok(());
+        }
+    };
+
+    if !furnace.is_burning {
+        schedules.furnace_id().delete(furnace_id as u64);
+        return Ok(());
+    }
+
+    if !check_if_furnace_has_fuel(ctx, &furnace) && furnace.remaining_fuel_burn_time_secs.is_none() {
+        log::info!("[ScheduleFurnace] Furnace {} is burning but found no valid fuel. Extinguishing.", furnace_id);
+        let mut furnace = furnace;
+        furnace.is_burning = false;
+        furnace.current_fuel_def_id = None;
+        ctx.db.furnace().id().update(furnace);
+        schedules.furnace_id().delete(furnace_id as u64);
+        return Ok(());
+    }
+
+    let interval = TimeDuration::from_micros((FURNACE_PROCESS_INTERVAL_SECS * 1_000_000) as i64);
+    if let Some(mut existing_schedule) = schedules.furnace_id().find(furnace_id as u64) {
+        existing_schedule.scheduled_at = interval.into();
+        schedules.furnace_id().update(existing_schedule);
+    } else {
+        schedules.try_insert(FurnaceProcessingSchedule {
+            furnace_id: furnace_id as u64,
+            scheduled_at: interval.into(),
+        }).map_err(|e| format!("Failed to schedule processing for furnace {}: {}", furnace_id, e))?;
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                            TRAIT IMPLEMENTATIONS                           *
+ ******************************************************************************/
+
+/// --- ItemContainer Implementation for Furnace ---
+impl ItemContainer for Furnace {
+    fn num_slots(&self) -> usize {
+        NUM_FURNACE_SLOTS
+    }
+
+    fn get_slot_instance_id(&self, slot_index: u8) -> Option<u64> {
+        match slot_index {
+            0 => self.slot_instance_id_0,
+            1 => self.slot_instance_id_1,
+            2 => self.slot_instance_id_2,
+            3 => self.slot_instance_id_3,
+            4 => self.slot_instance_id_4,
+            _ => None,
+        }
+    }
+
+    fn get_slot_def_id(&self, slot_index: u8) -> Option<u64> {
+        match slot_index {
+            0 => self.slot_def_id_0,
+            1 => self.slot_def_id_1,
+            2 => self.slot_def_id_2,
+            3 => self.slot_def_id_3,
+            4 => self.slot_def_id_4,
+            _ => None,
+        }
+    }
+
+    fn set_slot(&mut self, slot_index: u8, instance_id: Option<u64>, def_id: Option<u64>) {
+        match slot_index {
+            0 => { self.slot_instance_id_0 = instance_id; self.slot_def_id_0 = def_id; if instance_id.is_none() { self.slot_0_cooking_progress = None; } },
+            1 => { self.slot_instance_id_1 = instance_id; self.slot_def_id_1 = def_id; if instance_id.is_none() { self.slot_1_cooking_progress = None; } },
+            2 => { self.slot_instance_id_2 = instance_id; self.slot_def_id_2 = def_id; if instance_id.is_none() { self.slot_2_cooking_progress = None; } },
+            3 => { self.slot_instance_id_3 = instance_id; self.slot_def_id_3 = def_id; if instance_id.is_none() { self.slot_3_cooking_progress = None; } },
+            4 => { self.slot_instance_id_4 = instance_id; self.slot_def_id_4 = def_id; if instance_id.is_none() { self.slot_4_cooking_progress = None; } },
+            _ => {},
+        }
+    }
+
+    fn get_container_type(&self) -> ContainerType {
+        ContainerType::Furnace
+    }
+
+    fn get_container_id(&self) -> u64 {
+        self.id as u64
+    }
+}
+
+// --- CookableAppliance Trait Implementation for Furnace ---
+impl crate::cooking::CookableAppliance for Furnace {
+    fn num_processing_slots(&self) -> usize {
+        NUM_FURNACE_SLOTS // Fuel and ore share the same slots, like the campfire
+    }
+
+    fn get_slot_instance_id(&self, slot_index: u8) -> Option<u64> {
+        <Self as ItemContainer>::get_slot_instance_id(self, slot_index)
+    }
+
+    fn get_slot_def_id(&self, slot_index: u8) -> Option<u64> {
+        <Self as ItemContainer>::get_slot_def_id(self, slot_index)
+    }
+
+    fn set_slot(&mut self, slot_index: u8, instance_id: Option<u64>, def_id: Option<u64>) {
+        <Self as ItemContainer>::set_slot(self, slot_index, instance_id, def_id);
+    }
+
+    fn get_slot_cooking_progress(&self, slot_index: u8) -> Option<CookingProgress> {
+        match slot_index {
+            0 => self.slot_0_cooking_progress.clone(),
+            1 => self.slot_1_cooking_progress.clone(),
+            2 => self.slot_2_cooking_progress.clone(),
+            3 => self.slot_3_cooking_progress.clone(),
+            4 => self.slot_4_cooking_progress.clone(),
+            _ => None,
+        }
+    }
+
+    fn set_slot_cooking_progress(&mut self, slot_index: u8, progress: Option<CookingProgress>) {
+        match slot_index {
+            0 => self.slot_0_cooking_progress = progress,
+            1 => self.slot_1_cooking_progress = progress,
+            2 => self.slot_2_cooking_progress = progress,
+            3 => self.slot_3_cooking_progress = progress,
+            4 => self.slot_4_cooking_progress = progress,
+            _ => { log::warn!("[CookableAppliance] Attempted to set smelting progress for invalid Furnace slot: {}", slot_index); }
+        }
+    }
+
+    fn get_appliance_entity_id(&self) -> u64 {
+        self.id as u64
+    }
+
+    fn get_appliance_world_position(&self) -> (f32, f32) {
+        (self.pos_x, self.pos_y)
+    }
+
+    fn get_appliance_container_type(&self) -> ContainerType {
+        ContainerType::Furnace
+    }
+
+    fn is_smelting_appliance(&self) -> bool {
+        true
+    }
+}
+
+/// --- Helper struct to implement the ContainerItemClearer trait for Furnace ---
+pub struct FurnaceClearer;
+
+impl ContainerItemClearer for FurnaceClearer {
+    fn clear_item(ctx: &ReducerContext, item_instance_id: u64) -> bool {
+        let inventory_table = ctx.db.inventory_item();
+        let mut item_found_and_cleared = false;
+
+        for mut furnace in ctx.db.furnace().iter() {
+            let mut furnace_modified = false;
+            for i in 0..furnace.num_slots() as u8 {
+                if furnace.get_slot_instance_id(i) == Some(item_instance_id) {
+                    if let Some(mut item) = inventory_table.instance_id().find(item_instance_id) {
+                        item.location = ItemLocation::Unknown;
+                        inventory_table.instance_id().update(item);
+                    }
+                    furnace.set_slot(i, None, None);
+                    furnace_modified = true;
+                    item_found_and_cleared = true;
+                }
+            }
+            if furnace_modified {
+                ctx.db.furnace().id().update(furnace);
+            }
+        }
+        item_found_and_cleared
+    }
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Returns true if the item can only be processed in a furnace.
+pub(crate) fn is_smeltable_item(item_def: &ItemDefinition) -> bool {
+    SMELTABLE_ITEM_NAMES.contains(&item_def.name.as_str())
+}
+
+/// --- Furnace Interaction Validation ---
+/// Validates if a player can interact with a specific furnace (checks existence and distance).
+fn validate_furnace_interaction(
+    ctx: &ReducerContext,
+    furnace_id: u32,
+) -> Result<(Player, Furnace), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or_else(|| "Player not found".to_string())?;
+    let furnace = ctx.db.furnace().id().find(furnace_id)
+        .ok_or_else(|| format!("Furnace {} not found", furnace_id))?;
+    if furnace.is_destroyed {
+        return Err(format!("Furnace {} is destroyed", furnace_id));
+    }
+
+    let dx = player.position_x - furnace.pos_x;
+    let dy = player.position_y - (furnace.pos_y - FURNACE_COLLISION_Y_OFFSET);
+    if dx * dx + dy * dy > PLAYER_FURNACE_INTERACTION_DISTANCE_SQUARED {
+        return Err("Too far away from furnace".to_string());
+    }
+    Ok((player, furnace))
+}
+
+/// Checks if any furnace slot holds an item with a fuel burn duration.
+pub(crate) fn check_if_furnace_has_fuel(ctx: &ReducerContext, furnace: &Furnace) -> bool {
+    let item_def_table = ctx.db.item_definition();
+    (0..NUM_FURNACE_SLOTS as u8).any(|i| {
+        furnace.get_slot_instance_id(i)
+            .and_then(|instance_id| ctx.db.inventory_item().instance_id().find(instance_id))
+            .filter(|item| item.quantity > 0)
+            .and_then(|item| item_def_table.id().find(item.item_def_id))
+            .is_some_and(|def| def.fuel_burn_duration_secs.is_some_and(|secs| secs > 0.0))
+    })
+}
+
+/// Extinguishes the furnace if the last fuel item was just taken out.
+fn extinguish_if_out_of_fuel(ctx: &ReducerContext, furnace: &mut Furnace) {
+    if furnace.is_burning && !check_if_furnace_has_fuel(ctx, furnace) {
+        furnace.is_burning = false;
+        furnace.current_fuel_def_id = None;
+        furnace.remaining_fuel_burn_time_secs = None;
+        log::info!("Furnace {} extinguished as last valid fuel was removed.", furnace.id);
+    }
+}
+
+/// Finds the slot instance that is currently burning, so the smelting tick skips it.
+fn active_fuel_instance_id(furnace: &Furnace) -> Option<u64> {
+    let fuel_def_id = furnace.current_fuel_def_id?;
+    if furnace.remaining_fuel_burn_time_secs.unwrap_or(0.0) <= 0.0 {
+        return None;
+    }
+    (0..NUM_FURNACE_SLOTS as u8)
+        .find(|&i| furnace.get_slot_def_id(i) == Some(fuel_def_id))
+        .and_then(|i| furnace.get_slot_instance_id(i))
+}
+
+/// Consumes one unit of the currently burning fuel. Reloads the burn timer from the same
+/// stack if any remains, otherwise clears the current fuel so a new one is picked.
+/// Rolls the fuel's by-products (Wood leaves Charcoal), as in the campfire.
+fn consume_current_fuel_unit(ctx: &ReducerContext, furnace: &mut Furnace) {
+    let inventory_items = ctx.db.inventory_item();
+    let fuel_def_id = furnace.current_fuel_def_id;
+    furnace.current_fuel_def_id = None;
+    furnace.remaining_fuel_burn_time_secs = None;
+
+    let Some(slot_index) = (0..NUM_FURNACE_SLOTS as u8).find(|&i| fuel_def_id.is_some() && furnace.get_slot_def_id(i) == fuel_def_id) else {
+        return;
+    };
+    let Some(instance_id) = furnace.get_slot_instance_id(slot_index) else { return; };
+    let Some(mut fuel_item) = inventory_items.instance_id().find(instance_id) else {
+        furnace.set_slot(slot_index, None, None);
+        return;
+    };
+    let Some(fuel_def) = ctx.db.item_definition().id().find(fuel_item.item_def_id) else { return; };
+
+    fuel_item.quantity = fuel_item.quantity.saturating_sub(1);
+    if fuel_item.quantity > 0 {
+        inventory_items.instance_id().update(fuel_item);
+        furnace.current_fuel_def_id = Some(fuel_def.id);
+        furnace.remaining_fuel_burn_time_secs = fuel_def.fuel_burn_duration_secs;
+    } else {
+        inventory_items.instance_id().delete(instance_id);
+        furnace.set_slot(slot_index, None, None);
+    }
+
+    crate::cooking::roll_by_products(ctx, furnace, &fuel_def.name, crate::cooking::FUEL_BY_PRODUCTS);
+}
+
+/// Picks the first fuel stack in the furnace and starts burning one unit of it.
+/// Returns false if the furnace has no usable fuel.
+fn load_next_fuel_unit(ctx: &ReducerContext, furnace: &mut Furnace) -> bool {
+    for i in 0..NUM_FURNACE_SLOTS as u8 {
+        let Some(instance_id) = furnace.get_slot_instance_id(i) else { continue; };
+        let Some(item) = ctx.db.inventory_item().instance_id().find(instance_id) else { continue; };
+        if item.quantity == 0 {
+            continue;
+        }
+        let Some(def) = ctx.db.item_definition().id().find(item.item_def_id) else { continue; };
+        if let Some(burn_secs) = def.fuel_burn_duration_secs.filter(|secs| *secs > 0.0) {
+            furnace.current_fuel_def_id = Some(def.id);
+            furnace.remaining_fuel_burn_time_secs = Some(burn_secs);
+            return true;
+        }
+    }
+    false
+}
+
@@ -0,0 +1,200 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines the GameConfig singleton holding server-tunable balance values.    *
+ * It is seeded from the compiled-in constants at init, after which admins    *
+ * (see admin.rs) can change individual values with `set_config_value`.       *
+ * Movement, player stats and health regeneration, campfires, combat,         *
+ * sleeping bags, plant regrowth, the crafting queue, hazard zones, dropped   *
+ * item protection, the killer's corpse looting window and the reconnect      *
+ * grace period read their tunables through `get_game_config` instead of      *
+ * using the constants.                                                       *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{ReducerContext, Table, log};
+
+use crate::admin;
+use crate::PLAYER_SPEED;
+use crate::consumables::MAX_STAT_VALUE;
+use crate::player_stats::{
+    HUNGER_DRAIN_PER_SECOND, THIRST_DRAIN_PER_SECOND, STAMINA_DRAIN_PER_SECOND,
+    STAMINA_RECOVERY_PER_SECOND, SPRINT_SPEED_MULTIPLIER, SWIM_SPEED_MULTIPLIER,
+    HEALTH_RECOVERY_PER_SEC, HEALTH_RECOVERY_THRESHOLD, COMBAT_TAG_SECS,
+};
+use crate::campfire::{WARMTH_PER_SECOND, CAMPFIRE_DAMAGE_PER_TICK};
+use crate::combat::{PVP_KNOCKBACK_DISTANCE, DURABILITY_LOSS_PER_SWING};
+use crate::sleeping_bag::SLEEPING_BAG_RESPAWN_COOLDOWN_SECS;
+use crate::crafting_queue::MAX_QUEUED_CRAFTS_PER_PLAYER;
+use crate::hazard_zone::HAZARD_DAMAGE_PER_SECOND;
+use crate::dropped_item::DROPPED_ITEM_PROTECTION_SECS;
+use crate::player_corpse::KILLER_LOOT_WINDOW_SECS;
+use crate::session::RECONNECT_GRACE_SECS;
+use crate::game_config::game_config as GameConfigTableTrait;
+
+// --- Upper bounds for `set_config_value` ---
+// Keep admin-set values in a range the systems reading them can handle, e.g. durations
+// that still convert to a `Duration`.
+const MAX_CONFIG_DURATION_SECS: f32 = 7.0 * 24.0 * 60.0 * 60.0; // One week
+const MAX_CONFIG_MULTIPLIER: f32 = 100.0;
+const MAX_CONFIG_RATE: f32 = 1000.0; // Per second or per hit
+const MAX_CONFIG_DISTANCE: f32 = 100_000.0;
+const MAX_CONFIG_QUEUED_CRAFTS: f32 = 100.0;
+
+/// --- Game Config Data Structure ---
+/// Public so clients can predict movement and show stat rates with the live values.
+#[spacetimedb::table(name = game_config, public)]
+#[derive(Clone, Debug)]
+pub struct GameConfig {
+    #[primary_key]
+    pub id: u8, // Singleton table, ID will always be 0
+    // Movement
+    pub player_speed: f32,
+    pub sprint_speed_multiplier: f32,
+    pub swim_speed_multiplier: f32,
+    // Player stats (per second)
+    pub hunger_drain_per_second: f32,
+    pub thirst_drain_per_second: f32,
+    pub stamina_drain_per_second: f32,
+    pub stamina_recovery_per_second: f32,
+    /// Scales the warmth lost at night; 1.0 keeps the default day/night rates.
+    pub warmth_loss_multiplier: f32,
+    // Health regeneration
+    pub health_regen_per_second: f32,
+    /// Hunger and thirst must both be at least this for health to regenerate.
+    pub health_regen_need_threshold: f32,
+    /// Seconds after taking damage before health starts regenerating again.
+    pub combat_tag_secs: f32,
+    // Campfires
+    pub campfire_warmth_per_second: f32,
+    pub campfire_damage_per_tick: f32,
+    // Combat
+    pub pvp_knockback_distance: f32,
+    pub durability_loss_per_swing: f32,
+    // Respawning
+    pub sleeping_bag_respawn_cooldown_secs: f32,
+    // Plant growth (divides respawn timers; 2.0 regrows twice as fast)
+    /// Mushrooms and hemp, which only regrow at night.
+    pub night_plant_growth_multiplier: f32,
+    /// Corn and pumpkins, which only regrow during the day, and berry bushes.
+    pub day_plant_growth_multiplier: f32,
+    // Crafting
+    /// Most entries a player can have in their crafting queue at once.
+    pub max_queued_crafts: f32,
+    // Hazard zones
+    /// Distance from the map centre beyond which players pick up radiation; 0 disables it.
+    pub hazard_safe_radius: f32,
+    /// Damage per second of each radiation stack picked up outside the safe radius.
+    pub hazard_damage_per_second: f32,
+    // Dropped items
+    /// Seconds only the player who dropped an item can pick it up; 0 disables protection.
+    pub dropped_item_protection_secs: f32,
+    // Corpses
+    /// Seconds a PvP victim's corpse can only be looted by the killer; 0 disables the window.
+    pub killer_loot_window_secs: f32,
+    // Connections
+    /// Seconds a disconnected player stays in the world before going offline; 0 disables the grace period.
+    pub reconnect_grace_secs: f32,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            id: 0,
+            player_speed: PLAYER_SPEED,
+            sprint_speed_multiplier: SPRINT_SPEED_MULTIPLIER,
+            swim_speed_multiplier: SWIM_SPEED_MULTIPLIER,
+            hunger_drain_per_second: HUNGER_DRAIN_PER_SECOND,
+            thirst_drain_per_second: THIRST_DRAIN_PER_SECOND,
+            stamina_drain_per_second: STAMINA_DRAIN_PER_SECOND,
+            stamina_recovery_per_second: STAMINA_RECOVERY_PER_SECOND,
+            warmth_loss_multiplier: 1.0,
+            health_regen_per_second: HEALTH_RECOVERY_PER_SEC,
+            health_regen_need_threshold: HEALTH_RECOVERY_THRESHOLD,
+            combat_tag_secs: COMBAT_TAG_SECS,
+            campfire_warmth_per_second: WARMTH_PER_SECOND,
+            campfire_damage_per_tick: CAMPFIRE_DAMAGE_PER_TICK,
+            pvp_knockback_distance: PVP_KNOCKBACK_DISTANCE,
+            durability_loss_per_swing: DURABILITY_LOSS_PER_SWING,
+            sleeping_bag_respawn_cooldown_secs: SLEEPING_BAG_RESPAWN_COOLDOWN_SECS as f32,
+            night_plant_growth_multiplier: 1.0,
+            day_plant_growth_multiplier: 1.0,
+            max_queued_crafts: MAX_QUEUED_CRAFTS_PER_PLAYER as f32,
+            hazard_safe_radius: 0.0,
+            hazard_damage_per_second: HAZARD_DAMAGE_PER_SECOND,
+            dropped_item_protection_secs: DROPPED_ITEM_PROTECTION_SECS,
+            killer_loot_window_secs: KILLER_LOOT_WINDOW_SECS,
+            reconnect_grace_secs: RECONNECT_GRACE_SECS,
+        }
+    }
+}
+
+/// Seeds the config with the compiled-in defaults if it doesn't exist yet. Called from `init_module`.
+pub fn init_game_config(ctx: &ReducerContext) -> Result<(), String> {
+    let config_table = ctx.db.game_config();
+    if config_table.iter().count() == 0 {
+        config_table.try_insert(GameConfig::default())
+            .map_err(|e| format!("Failed to init GameConfig: {}", e))?;
+        log::info!("GameConfig table initialized with default values.");
+    }
+    Ok(())
+}
+
+/// Admin-only: sets a single tunable by its field name, e.g. `set_config_value("player_speed", 650.0)`.
+/// Each key has an upper bound so a typo can't push a system into overflow.
+#[spacetimedb::reducer]
+pub fn set_config_value(ctx: &ReducerContext, key: String, value: f32) -> Result<(), String> {
+    if !admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can change the game config.".to_string());
+    }
+    if !value.is_finite() || value < 0.0 {
+        return Err(format!("Invalid value {} for '{}'. Values must be finite and non-negative.", value, key));
+    }
+
+    let mut config = get_game_config(ctx);
+    let (field, max_value) = match key.as_str() {
+        "player_speed" => (&mut config.player_speed, MAX_CONFIG_DISTANCE),
+        "sprint_speed_multiplier" => (&mut config.sprint_speed_multiplier, MAX_CONFIG_MULTIPLIER),
+        "swim_speed_multiplier" => (&mut config.swim_speed_multiplier, MAX_CONFIG_MULTIPLIER),
+        "hunger_drain_per_second" => (&mut config.hunger_drain_per_second, MAX_CONFIG_RATE),
+        "thirst_drain_per_second" => (&mut config.thirst_drain_per_second, MAX_CONFIG_RATE),
+        "stamina_drain_per_second" => (&mut config.stamina_drain_per_second, MAX_CONFIG_RATE),
+        "stamina_recovery_per_second" => (&mut config.stamina_recovery_per_second, MAX_CONFIG_RATE),
+        "warmth_loss_multiplier" => (&mut config.warmth_loss_multiplier, MAX_CONFIG_MULTIPLIER),
+        "health_regen_per_second" => (&mut config.health_regen_per_second, MAX_CONFIG_RATE),
+        "health_regen_need_threshold" => (&mut config.health_regen_need_threshold, MAX_STAT_VALUE),
+        "combat_tag_secs" => (&mut config.combat_tag_secs, MAX_CONFIG_DURATION_SECS),
+        "campfire_warmth_per_second" => (&mut config.campfire_warmth_per_second, MAX_CONFIG_RATE),
+        "campfire_damage_per_tick" => (&mut config.campfire_damage_per_tick, MAX_CONFIG_RATE),
+        "pvp_knockback_distance" => (&mut config.pvp_knockback_distance, MAX_CONFIG_DISTANCE),
+        "durability_loss_per_swing" => (&mut config.durability_loss_per_swing, MAX_CONFIG_RATE),
+        "sleeping_bag_respawn_cooldown_secs" => (&mut config.sleeping_bag_respawn_cooldown_secs, MAX_CONFIG_DURATION_SECS),
+        "night_plant_growth_multiplier" => (&mut config.night_plant_growth_multiplier, MAX_CONFIG_MULTIPLIER),
+        "day_plant_growth_multiplier" => (&mut config.day_plant_growth_multiplier, MAX_CONFIG_MULTIPLIER),
+        "max_queued_crafts" => (&mut config.max_queued_crafts, MAX_CONFIG_QUEUED_CRAFTS),
+        "hazard_safe_radius" => (&mut config.hazard_safe_radius, MAX_CONFIG_DISTANCE),
+        "hazard_damage_per_second" => (&mut config.hazard_damage_per_second, MAX_CONFIG_RATE),
+        "dropped_item_protection_secs" => (&mut config.dropped_item_protection_secs, MAX_CONFIG_DURATION_SECS),
+        "killer_loot_window_secs" => (&mut config.killer_loot_window_secs, MAX_CONFIG_DURATION_SECS),
+        "reconnect_grace_secs" => (&mut config.reconnect_grace_secs, MAX_CONFIG_DURATION_SECS),
+        _ => return Err(format!("Unknown config key '{}'.", key)),
+    };
+    if value > max_value {
+        return Err(format!("Value {} for '{}' is too large (max {}).", value, key, max_value));
+    }
+    let old_value = *field;
+    *field = value;
+
+    let config_table = ctx.db.game_config();
+    if config_table.id().find(0).is_some() {
+        config_table.id().update(config);
+    } else {
+        config_table.insert(config);
+    }
+    log::info!("Admin {:?} set config '{}' from {} to {}.", ctx.sender, key, old_value, value);
+    Ok(())
+}
+
+/// Returns the live config, falling back to the defaults if it hasn't been seeded.
+pub(crate) fn get_game_config(ctx: &ReducerContext) -> GameConfig {
+    ctx.db.game_config().id().find(0).unwrap_or_default()
+}
@@ -56,6 +56,18 @@ pub fn process_global_tick(ctx: &ReducerContext, _schedule: GlobalTickSchedule)
         Err(e) => log::error!("Error ticking world state during global tick: {}", e),
     }
 
+    // --- Refresh Server Info ---
+    crate::server_info::tick_server_info(ctx);
+
+    // --- Refresh Carried Weights ---
+    crate::encumbrance::tick_player_weights(ctx);
+
+    // --- Advance Auto-Running Players ---
+    crate::autorun::tick_autorun(ctx);
+
+    // --- Finish Channeled Item Uses ---
+    crate::channeled_use::tick_channeled_uses(ctx);
+
     // --- Check Resource Respawns ---
     match environment::check_resource_respawns(ctx) {
         Ok(_) => {}
@@ -0,0 +1,185 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines hazard zones: places where players build up radiation. There are   *
+ * two kinds. Admins mark circular radiation zones with `create_hazard_zone`, *
+ * and the game config's `hazard_safe_radius` (0 disables it) makes the whole *
+ * world beyond that distance from the map centre hazardous. A schedule adds  *
+ * a stack of the Radiation effect (see active_effects.rs) to every exposed   *
+ * player, so damage ramps up the longer they stay. Armor with enough         *
+ * radiation protection (the Hazmat Suit) keeps a player safe entirely.       *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::{WORLD_WIDTH_PX, WORLD_HEIGHT_PX};
+use crate::admin;
+use crate::models::DamageType;
+use crate::player as PlayerTableTrait;
+use crate::spatial_grid::{self, EntityType};
+use crate::hazard_zone::hazard_zone as HazardZoneTableTrait;
+use crate::hazard_zone::hazard_zone_schedule as HazardZoneScheduleTableTrait;
+
+// --- Constants ---
+const HAZARD_CHECK_INTERVAL_SECS: u64 = 2;
+/// How long a single radiation stack keeps dealing damage after the player was exposed.
+const RADIATION_STACK_DURATION_SECS: f32 = 10.0;
+/// Default damage per second of each radiation stack picked up outside the safe radius.
+pub(crate) const HAZARD_DAMAGE_PER_SECOND: f32 = 0.5;
+/// Players whose armor gives at least this much radiation protection are not affected.
+const HAZARD_IMMUNITY_PROTECTION: f32 = 0.5;
+const MAX_HAZARD_ZONE_RADIUS: f32 = 2000.0;
+
+/// --- Hazard Zone ---
+/// A circular radiation zone. Public so clients can draw the zones on the map.
+#[spacetimedb::table(name = hazard_zone, public)]
+#[derive(Clone, Debug)]
+pub struct HazardZone {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub name: String,
+    pub center_x: f32,
+    pub center_y: f32,
+    pub radius: f32,
+    /// Damage per second of each radiation stack picked up inside the zone.
+    pub damage_per_second: f32,
+    pub created_by: Identity,
+    pub created_at: Timestamp,
+}
+
+/// --- Hazard Zone Schedule ---
+#[spacetimedb::table(name = hazard_zone_schedule, scheduled(process_hazard_zones))]
+#[derive(Clone, Debug)]
+pub struct HazardZoneSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Create Hazard Zone ---
+/// Admin-only: marks a circular radiation zone.
+#[spacetimedb::reducer]
+pub fn create_hazard_zone(ctx: &ReducerContext, name: String, center_x: f32, center_y: f32, radius: f32, damage_per_second: f32) -> Result<(), String> {
+    if !admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can create hazard zones.".to_string());
+    }
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Hazard zone name cannot be empty.".to_string());
+    }
+    if !(0.0..=WORLD_WIDTH_PX).contains(&center_x) || !(0.0..=WORLD_HEIGHT_PX).contains(&center_y) {
+        return Err("Hazard zone centre must be inside the world.".to_string());
+    }
+    if !radius.is_finite() || radius <= 0.0 || radius > MAX_HAZARD_ZONE_RADIUS {
+        return Err(format!("Hazard zone radius must be between 0 and {}.", MAX_HAZARD_ZONE_RADIUS));
+    }
+    if !damage_per_second.is_finite() || damage_per_second <= 0.0 {
+        return Err("Hazard zone damage must be positive.".to_string());
+    }
+
+    let inserted = ctx.db.hazard_zone().insert(HazardZone {
+        id: 0, // Auto-incremented
+        name,
+        center_x,
+        center_y,
+        radius,
+        damage_per_second,
+        created_by: ctx.sender,
+        created_at: ctx.timestamp,
+    });
+    log::info!("[HazardZone] Admin {:?} created hazard zone {} '{}' at ({:.1}, {:.1}), radius {:.1}, {:.1} dmg/s.",
+        ctx.sender, inserted.id, inserted.name, center_x, center_y, radius, damage_per_second);
+    Ok(())
+}
+
+/// --- Remove Hazard Zone ---
+/// Admin-only: deletes a radiation zone. Stacks players already carry run out on their own.
+#[spacetimedb::reducer]
+pub fn remove_hazard_zone(ctx: &ReducerContext, zone_id: u64) -> Result<(), String> {
+    if !admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can remove hazard zones.".to_string());
+    }
+    if !ctx.db.hazard_zone().id().delete(zone_id) {
+        return Err(format!("Hazard zone {} not found.", zone_id));
+    }
+    log::info!("[HazardZone] Admin {:?} removed hazard zone {}.", ctx.sender, zone_id);
+    Ok(())
+}
+
+/******************************************************************************
+ *                           SCHEDULED REDUCERS                               *
+ ******************************************************************************/
+
+/// Starts the hazard zone schedule if it isn't already scheduled. Called from `init_module`.
+pub fn init_hazard_zone_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.hazard_zone_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting hazard zone schedule (every {}s).", HAZARD_CHECK_INTERVAL_SECS);
+        let interval = Duration::from_secs(HAZARD_CHECK_INTERVAL_SECS);
+        schedule_table.try_insert(HazardZoneSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert hazard zone schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: adds a radiation stack to every living player inside a radiation
+/// zone or beyond the safe radius. A player exposed to several hazards at once takes
+/// the strongest one.
+#[spacetimedb::reducer]
+pub fn process_hazard_zones(ctx: &ReducerContext, _schedule: HazardZoneSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("process_hazard_zones may only be called by the scheduler.".to_string());
+    }
+
+    let players = ctx.db.player();
+    let mut exposure: HashMap<Identity, f32> = HashMap::new();
+
+    for zone in ctx.db.hazard_zone().iter() {
+        let radius_sq = zone.radius * zone.radius;
+        for entity in spatial_grid::get_entities_in_radius(ctx, zone.center_x, zone.center_y, zone.radius) {
+            let EntityType::Player(player_id) = entity else { continue; };
+            let Some(player) = players.identity().find(player_id) else { continue; };
+            let dist_sq = (player.position_x - zone.center_x).powi(2) + (player.position_y - zone.center_y).powi(2);
+            if dist_sq <= radius_sq {
+                let damage = exposure.entry(player_id).or_insert(0.0);
+                *damage = damage.max(zone.damage_per_second);
+            }
+        }
+    }
+
+    let config = crate::game_config::get_game_config(ctx);
+    if config.hazard_safe_radius > 0.0 {
+        let (center_x, center_y) = (WORLD_WIDTH_PX / 2.0, WORLD_HEIGHT_PX / 2.0);
+        let safe_radius_sq = config.hazard_safe_radius * config.hazard_safe_radius;
+        for player in players.iter() {
+            let dist_sq = (player.position_x - center_x).powi(2) + (player.position_y - center_y).powi(2);
+            if dist_sq > safe_radius_sq {
+                let damage = exposure.entry(player.identity).or_insert(0.0);
+                *damage = damage.max(config.hazard_damage_per_second);
+            }
+        }
+    }
+
+    for (player_id, damage_per_second) in exposure {
+        let Some(player) = players.identity().find(player_id) else { continue; };
+        if player.is_dead || damage_per_second <= 0.0 || crate::spectator::is_spectating(ctx, player_id) {
+            continue;
+        }
+        if crate::armor::calculate_total_protection(ctx, player_id, DamageType::Radiation) >= HAZARD_IMMUNITY_PROTECTION {
+            continue;
+        }
+        crate::active_effects::apply_radiation_stack(ctx, player_id, damage_per_second, RADIATION_STACK_DURATION_SECS);
+    }
+    Ok(())
+}
@@ -16,6 +16,7 @@ use crate::collectible_resources::{
     BASE_RESOURCE_RADIUS, PLAYER_RESOURCE_INTERACTION_DISTANCE_SQUARED,
     validate_player_resource_interaction,
     collect_resource_and_schedule_respawn,
+    scale_respawn_secs,
     RespawnableResource
 };
 
@@ -78,6 +79,7 @@ impl RespawnableResource for Hemp {
 /// Handles player interactions with hemp, adding cloth to inventory.
 #[spacetimedb::reducer]
 pub fn interact_with_hemp(ctx: &ReducerContext, hemp_id: u64) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
     let sender_id = ctx.sender;
     
     // Find the hemp plant
@@ -95,6 +97,9 @@ pub fn interact_with_hemp(ctx: &ReducerContext, hemp_id: u64) -> Result<(), Stri
     // Calculate primary yield amount for Hemp
     let primary_yield_amount = ctx.rng().gen_range(HEMP_PRIMARY_YIELD_MIN_AMOUNT..=HEMP_PRIMARY_YIELD_MAX_AMOUNT);
 
+    let growth_multiplier = crate::game_config::get_game_config(ctx).night_plant_growth_multiplier
+        * crate::world_state::current_season(ctx).plant_growth_multiplier();
+
     // Call the generic resource collection and respawn scheduling function
     collect_resource_and_schedule_respawn(
         ctx,
@@ -118,8 +123,8 @@ pub fn interact_with_hemp(ctx: &ReducerContext, hemp_id: u64) -> Result<(), Stri
                 Err(format!("Hemp plant {} disappeared before respawn scheduling.", hemp_id))
             }
         },
-        MIN_HEMP_RESPAWN_TIME_SECS,
-        MAX_HEMP_RESPAWN_TIME_SECS
+        scale_respawn_secs(MIN_HEMP_RESPAWN_TIME_SECS, growth_multiplier),
+        scale_respawn_secs(MAX_HEMP_RESPAWN_TIME_SECS, growth_multiplier)
     )?;
 
     // Log statement is now handled within collect_resource_and_schedule_respawn
@@ -0,0 +1,143 @@
+/******************************************************************************
+ *                                                                            *
+ * Context-sensitive "use": `interact_nearest` finds the closest thing the    *
+ * player can interact with (campfire, storage box, dropped item, corpse,     *
+ * stash or sleeping bag) through the spatial grid, checks it is within that  *
+ * entity's own interaction range, and runs the same interaction as the       *
+ * entity-specific reducer. What was used is written to the public            *
+ * InteractionResult table so the client knows which UI to open.              *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp, log};
+
+use crate::player as PlayerTableTrait;
+use crate::campfire::{self, campfire as CampfireTableTrait};
+use crate::wooden_storage_box::{self, wooden_storage_box as WoodenStorageBoxTableTrait};
+use crate::dropped_item::{self, dropped_item as DroppedItemTableTrait};
+use crate::player_corpse::{self, player_corpse as PlayerCorpseTableTrait};
+use crate::stash::{self, stash as StashTableTrait};
+use crate::sleeping_bag::{self, sleeping_bag as SleepingBagTableTrait};
+use crate::spatial_grid::{self, EntityType};
+use crate::interaction::interaction_result as InteractionResultTableTrait;
+
+/// Grid lookup radius: the longest interaction range, plus the campfire's visual offset.
+const INTERACT_SEARCH_RADIUS: f32 = campfire::PLAYER_CAMPFIRE_INTERACTION_DISTANCE + campfire::CAMPFIRE_VISUAL_CENTER_Y_OFFSET;
+
+/// The kinds of entity `interact_nearest` can pick.
+#[derive(SpacetimeType, Debug, Clone, Copy, PartialEq)]
+pub enum InteractionTargetType {
+    Campfire,
+    WoodenStorageBox,
+    DroppedItem,
+    PlayerCorpse,
+    Stash,
+    SleepingBag,
+}
+
+/// --- Interaction Result ---
+/// The entity a player last used through `interact_nearest`.
+#[spacetimedb::table(name = interaction_result, public)]
+#[derive(Clone, Debug)]
+pub struct InteractionResult {
+    #[primary_key]
+    pub player_id: Identity,
+    pub target_type: InteractionTargetType,
+    pub target_id: u64,
+    pub interacted_at: Timestamp,
+}
+
+/// --- Interact Nearest ---
+/// Interacts with the closest interactable entity in range. Containers are only validated
+/// (the client opens them from the result row); dropped items are picked up.
+#[spacetimedb::reducer]
+pub fn interact_nearest(ctx: &ReducerContext) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let sender_id = ctx.sender;
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Player not found.".to_string())?;
+    if player.is_dead {
+        return Err("Cannot interact while dead.".to_string());
+    }
+
+    let (target_type, target_id) = find_nearest_interactable(ctx, sender_id, player.position_x, player.position_y)
+        .ok_or_else(|| "Nothing to interact with nearby.".to_string())?;
+
+    match target_type {
+        InteractionTargetType::Campfire => campfire::interact_with_campfire(ctx, target_id as u32)?,
+        InteractionTargetType::WoodenStorageBox => wooden_storage_box::interact_with_storage_box(ctx, target_id as u32)?,
+        InteractionTargetType::DroppedItem => dropped_item::pickup_dropped_item(ctx, target_id)?,
+        InteractionTargetType::PlayerCorpse => { player_corpse::validate_corpse_interaction(ctx, target_id as u32)?; }
+        InteractionTargetType::Stash => { stash::validate_stash_item_interaction(ctx, target_id as u32)?; }
+        InteractionTargetType::SleepingBag => sleeping_bag::interact_with_sleeping_bag(ctx, target_id as u32)?,
+    }
+
+    let results = ctx.db.interaction_result();
+    let result = InteractionResult { player_id: sender_id, target_type, target_id, interacted_at: ctx.timestamp };
+    if results.player_id().find(sender_id).is_some() {
+        results.player_id().update(result);
+    } else {
+        results.insert(result);
+    }
+    log::debug!("[Interact] Player {:?} interacted with {:?} {}.", sender_id, target_type, target_id);
+    Ok(())
+}
+
+/// Finds the closest entity within its own interaction range of (x, y).
+/// Destroyed entities, hidden stashes and items protected for another player are skipped.
+fn find_nearest_interactable(ctx: &ReducerContext, player_id: Identity, x: f32, y: f32) -> Option<(InteractionTargetType, u64)> {
+    let mut nearest: Option<(InteractionTargetType, u64, f32)> = None;
+    let mut consider = |target_type: InteractionTargetType, id: u64, target_x: f32, target_y: f32, range_sq: f32| {
+        let dist_sq = (x - target_x).powi(2) + (y - target_y).powi(2);
+        if dist_sq <= range_sq && nearest.is_none_or(|(_, _, best)| dist_sq < best) {
+            nearest = Some((target_type, id, dist_sq));
+        }
+    };
+
+    for entity in spatial_grid::get_entities_in_radius(ctx, x, y, INTERACT_SEARCH_RADIUS) {
+        match entity {
+            EntityType::Campfire(id) => {
+                let Some(fire) = ctx.db.campfire().id().find(id) else { continue; };
+                if !fire.is_destroyed {
+                    consider(InteractionTargetType::Campfire, id as u64, fire.pos_x, fire.pos_y - campfire::CAMPFIRE_VISUAL_CENTER_Y_OFFSET,
+                        campfire::PLAYER_CAMPFIRE_INTERACTION_DISTANCE_SQUARED);
+                }
+            }
+            EntityType::WoodenStorageBox(id) => {
+                let Some(storage_box) = ctx.db.wooden_storage_box().id().find(id) else { continue; };
+                if !storage_box.is_destroyed {
+                    consider(InteractionTargetType::WoodenStorageBox, id as u64, storage_box.pos_x, storage_box.pos_y,
+                        wooden_storage_box::BOX_INTERACTION_DISTANCE_SQUARED);
+                }
+            }
+            EntityType::DroppedItem(id) => {
+                let Some(item) = ctx.db.dropped_item().id().find(id) else { continue; };
+                if dropped_item::can_pick_up(&item, player_id, ctx.timestamp) {
+                    consider(InteractionTargetType::DroppedItem, id, item.pos_x, item.pos_y, dropped_item::PICKUP_RADIUS_SQUARED);
+                }
+            }
+            EntityType::PlayerCorpse(id) => {
+                let Some(corpse) = ctx.db.player_corpse().id().find(id) else { continue; };
+                if !player_corpse::can_loot(ctx, &corpse, player_id, ctx.timestamp) { continue; }
+                consider(InteractionTargetType::PlayerCorpse, id as u64, corpse.pos_x, corpse.pos_y,
+                    player_corpse::PLAYER_CORPSE_INTERACTION_DISTANCE_SQUARED);
+            }
+            EntityType::Stash(id) => {
+                let Some(found_stash) = ctx.db.stash().id().find(id) else { continue; };
+                if !found_stash.is_destroyed && !found_stash.is_hidden {
+                    consider(InteractionTargetType::Stash, id as u64, found_stash.pos_x, found_stash.pos_y,
+                        stash::STASH_INTERACTION_DISTANCE_SQUARED);
+                }
+            }
+            EntityType::SleepingBag(id) => {
+                let Some(bag) = ctx.db.sleeping_bag().id().find(id) else { continue; };
+                if !bag.is_destroyed {
+                    consider(InteractionTargetType::SleepingBag, id as u64, bag.pos_x, bag.pos_y,
+                        sleeping_bag::SLEEPING_BAG_INTERACTION_DISTANCE_SQUARED);
+                }
+            }
+            _ => {}
+        }
+    }
+    nearest.map(|(target_type, id, _)| (target_type, id))
+}
@@ -0,0 +1,193 @@
+/******************************************************************************
+ *                                                                            *
+ * Server-side interest management. Each client's viewport (see               *
+ * `update_viewport` in lib.rs) is expanded into the set of world chunks it   *
+ * overlaps, stored in ClientVisibleChunk. Row-level security filters then    *
+ * only stream environment rows (trees, stumps, falling trees, stones,        *
+ * hazards, dropped items, campfires, signs, water tiles) whose chunk is      *
+ * visible to the subscribing client. A chunk's terrain and resources are     *
+ * generated the first time it becomes visible. Reported viewports are        *
+ * clamped to a fixed span around the player, and each update generates only  *
+ * a handful of new chunks.                                                   *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{client_visibility_filter, Filter, Identity, ReducerContext, Table, log};
+use std::collections::HashSet;
+
+use crate::{WORLD_WIDTH_PX, WORLD_HEIGHT_PX};
+use crate::environment::{CHUNK_SIZE_PX, WORLD_WIDTH_CHUNKS};
+use crate::environment::generated_chunk as GeneratedChunkTableTrait;
+use crate::interest_management::client_visible_chunk as ClientVisibleChunkTableTrait;
+use crate::player as PlayerTableTrait;
+use crate::spectator::spectator as SpectatorTableTrait;
+
+// --- Constants ---
+/// Extra ring of chunks streamed around the viewport so entities are already
+/// present when they scroll into view.
+pub const VIEWPORT_CHUNK_MARGIN: u32 = 1;
+/// Furthest a reported viewport edge may sit from the player (or spectator
+/// camera). Larger client rectangles are clamped to this span.
+pub const MAX_VIEWPORT_HALF_SPAN_PX: f32 = 2.0 * CHUNK_SIZE_PX;
+/// Most never-generated chunks a single viewport update may generate. The
+/// rest become visible on later updates as the client keeps reporting.
+pub const MAX_CHUNKS_GENERATED_PER_UPDATE: usize = 8;
+
+/// --- Client Visible Chunk ---
+/// One row per (client, chunk) pair the client is currently allowed to see.
+#[spacetimedb::table(name = client_visible_chunk, public)]
+#[derive(Clone, Debug)]
+pub struct ClientVisibleChunk {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub client_identity: Identity,
+    #[index(btree)]
+    pub chunk_index: u32,
+}
+
+// --- Row-Level Security Filters ---
+
+#[client_visibility_filter]
+const CLIENT_VISIBLE_CHUNK_OWN_ROWS: Filter = Filter::Sql(
+    "SELECT * FROM client_visible_chunk WHERE client_identity = :sender"
+);
+
+#[client_visibility_filter]
+const TREE_IN_VISIBLE_CHUNK: Filter = Filter::Sql(
+    "SELECT tree.* FROM tree JOIN client_visible_chunk ON tree.chunk_index = client_visible_chunk.chunk_index WHERE client_visible_chunk.client_identity = :sender"
+);
+
+#[client_visibility_filter]
+const TREE_STUMP_IN_VISIBLE_CHUNK: Filter = Filter::Sql(
+    "SELECT tree_stump.* FROM tree_stump JOIN client_visible_chunk ON tree_stump.chunk_index = client_visible_chunk.chunk_index WHERE client_visible_chunk.client_identity = :sender"
+);
+
+#[client_visibility_filter]
+const ENVIRONMENT_HAZARD_IN_VISIBLE_CHUNK: Filter = Filter::Sql(
+    "SELECT environment_hazard.* FROM environment_hazard JOIN client_visible_chunk ON environment_hazard.chunk_index = client_visible_chunk.chunk_index WHERE client_visible_chunk.client_identity = :sender"
+);
+
+#[client_visibility_filter]
+const FALLING_TREE_IN_VISIBLE_CHUNK: Filter = Filter::Sql(
+    "SELECT falling_tree.* FROM falling_tree JOIN client_visible_chunk ON falling_tree.chunk_index = client_visible_chunk.chunk_index WHERE client_visible_chunk.client_identity = :sender"
+);
+
+#[client_visibility_filter]
+const STONE_IN_VISIBLE_CHUNK: Filter = Filter::Sql(
+    "SELECT stone.* FROM stone JOIN client_visible_chunk ON stone.chunk_index = client_visible_chunk.chunk_index WHERE client_visible_chunk.client_identity = :sender"
+);
+
+#[client_visibility_filter]
+const DROPPED_ITEM_IN_VISIBLE_CHUNK: Filter = Filter::Sql(
+    "SELECT dropped_item.* FROM dropped_item JOIN client_visible_chunk ON dropped_item.chunk_index = client_visible_chunk.chunk_index WHERE client_visible_chunk.client_identity = :sender"
+);
+
+#[client_visibility_filter]
+const CAMPFIRE_IN_VISIBLE_CHUNK: Filter = Filter::Sql(
+    "SELECT campfire.* FROM campfire JOIN client_visible_chunk ON campfire.chunk_index = client_visible_chunk.chunk_index WHERE client_visible_chunk.client_identity = :sender"
+);
+
+#[client_visibility_filter]
+const SIGN_IN_VISIBLE_CHUNK: Filter = Filter::Sql(
+    "SELECT sign.* FROM sign JOIN client_visible_chunk ON sign.chunk_index = client_visible_chunk.chunk_index WHERE client_visible_chunk.client_identity = :sender"
+);
+
+#[client_visibility_filter]
+const WORLD_TILE_IN_VISIBLE_CHUNK: Filter = Filter::Sql(
+    "SELECT world_tile.* FROM world_tile JOIN client_visible_chunk ON world_tile.chunk_index = client_visible_chunk.chunk_index WHERE client_visible_chunk.client_identity = :sender"
+);
+
+/// Returns the indices of every chunk overlapping the given pixel rectangle,
+/// expanded by `VIEWPORT_CHUNK_MARGIN` chunks on each side.
+pub fn chunks_in_viewport(min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> HashSet<u32> {
+    let max_chunk = WORLD_WIDTH_CHUNKS - 1;
+    let to_chunk = |px: f32, world_px: f32| (px.clamp(0.0, world_px - 1.0) / CHUNK_SIZE_PX).floor() as u32;
+
+    let start_x = to_chunk(min_x.min(max_x), WORLD_WIDTH_PX).saturating_sub(VIEWPORT_CHUNK_MARGIN);
+    let end_x = (to_chunk(min_x.max(max_x), WORLD_WIDTH_PX) + VIEWPORT_CHUNK_MARGIN).min(max_chunk);
+    let start_y = to_chunk(min_y.min(max_y), WORLD_HEIGHT_PX).saturating_sub(VIEWPORT_CHUNK_MARGIN);
+    let end_y = (to_chunk(min_y.max(max_y), WORLD_HEIGHT_PX) + VIEWPORT_CHUNK_MARGIN).min(max_chunk);
+
+    let mut chunks = HashSet::new();
+    for chunk_y in start_y..=end_y {
+        for chunk_x in start_x..=end_x {
+            chunks.insert(chunk_y * WORLD_WIDTH_CHUNKS + chunk_x);
+        }
+    }
+    chunks
+}
+
+/// Brings the client's visible chunk rows in line with its new viewport,
+/// inserting newly visible chunks and deleting ones that scrolled out of range.
+/// The rectangle is clamped around the client's player (or spectator camera)
+/// first, and clients without a player row see nothing.
+pub fn update_visible_chunks(ctx: &ReducerContext, client_id: Identity, min_x: f32, min_y: f32, max_x: f32, max_y: f32) {
+    let visible_chunks = ctx.db.client_visible_chunk();
+    let Some((anchor_x, anchor_y)) = viewport_anchor(ctx, client_id) else {
+        clear_visible_chunks(ctx, client_id);
+        return;
+    };
+    let clamp_x = |x: f32| if x.is_nan() { anchor_x } else { x.clamp(anchor_x - MAX_VIEWPORT_HALF_SPAN_PX, anchor_x + MAX_VIEWPORT_HALF_SPAN_PX) };
+    let clamp_y = |y: f32| if y.is_nan() { anchor_y } else { y.clamp(anchor_y - MAX_VIEWPORT_HALF_SPAN_PX, anchor_y + MAX_VIEWPORT_HALF_SPAN_PX) };
+    let wanted = chunks_in_viewport(clamp_x(min_x), clamp_y(min_y), clamp_x(max_x), clamp_y(max_y));
+
+    let mut already_visible: HashSet<u32> = HashSet::new();
+    let mut stale_ids: Vec<u64> = Vec::new();
+    for row in visible_chunks.client_identity().filter(client_id) {
+        if wanted.contains(&row.chunk_index) {
+            already_visible.insert(row.chunk_index);
+        } else {
+            stale_ids.push(row.id);
+        }
+    }
+
+    for id in &stale_ids {
+        visible_chunks.id().delete(id);
+    }
+    let generated_chunks = ctx.db.generated_chunk();
+    let mut added = 0;
+    let mut generated = 0;
+    for chunk_index in wanted.difference(&already_visible) {
+        // Chunks are generated lazily the first time anyone observes them,
+        // a few per update so one huge viewport can't stall the reducer
+        if generated_chunks.chunk_index().find(*chunk_index).is_none() {
+            if generated >= MAX_CHUNKS_GENERATED_PER_UPDATE {
+                continue;
+            }
+            generated += 1;
+        }
+        if let Err(e) = crate::environment::ensure_chunk_generated(ctx, *chunk_index) {
+            log::error!("[Interest] Failed to generate chunk {}: {}", chunk_index, e);
+        }
+        visible_chunks.insert(ClientVisibleChunk {
+            id: 0, // Auto-incremented
+            client_identity: client_id,
+            chunk_index: *chunk_index,
+        });
+        added += 1;
+    }
+
+    if added > 0 || !stale_ids.is_empty() {
+        log::trace!("[Interest] Client {:?} visible chunks: +{} -{}", client_id, added, stale_ids.len());
+    }
+}
+
+/// Where the client is looking from: the spectator camera if spectating,
+/// otherwise the player's position.
+fn viewport_anchor(ctx: &ReducerContext, client_id: Identity) -> Option<(f32, f32)> {
+    if let Some(spectator) = ctx.db.spectator().player_id().find(client_id) {
+        return Some((spectator.pos_x, spectator.pos_y));
+    }
+    ctx.db.player().identity().find(client_id).map(|player| (player.position_x, player.position_y))
+}
+
+/// Removes every visible chunk row for the client (e.g. on disconnect).
+pub fn clear_visible_chunks(ctx: &ReducerContext, client_id: Identity) {
+    let visible_chunks = ctx.db.client_visible_chunk();
+    let ids: Vec<u64> = visible_chunks.client_identity().filter(client_id).map(|row| row.id).collect();
+    for id in ids {
+        visible_chunks.id().delete(id);
+    }
+}
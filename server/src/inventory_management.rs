@@ -7,7 +7,7 @@
  *                                                                            *
  ******************************************************************************/
 
-use spacetimedb::{ReducerContext, Identity, Table};
+use spacetimedb::{ReducerContext, SpacetimeType};
 use log;
 
 // Import necessary types and Table Traits
@@ -31,7 +31,7 @@ use crate::Player;
 use crate::player; // Trait for ctx.db.player() and its methods
 
 // Import for dropped item creation
-use crate::dropped_item::{create_dropped_item_entity, calculate_drop_position};
+use crate::dropped_item::{create_owned_dropped_item_entity, calculate_drop_position};
 
 // --- Generic Item Container Trait --- 
 
@@ -61,6 +61,66 @@ pub(crate) trait ItemContainer {
     /// This might be a u32 entity ID, a u64 table row ID, or similar.
     /// Needs to be consistently represented, perhaps as u64?
     fn get_container_id(&self) -> u64; 
+
+    /// Maximum total weight of the items this container can hold. None = no weight cap.
+    fn max_weight(&self) -> Option<f32> {
+        None
+    }
+}
+
+// --- Slot-Array Backed Containers ---
+
+/// A single container slot. Containers that store their slots as a
+/// `Vec<ContainerSlot>` column can implement `SlotArrayContainer` instead of
+/// declaring individual `slot_instance_id_N` / `slot_def_id_N` fields.
+#[derive(SpacetimeType, Clone, Copy, Debug, Default, PartialEq)]
+pub struct ContainerSlot {
+    pub instance_id: Option<u64>,
+    pub def_id: Option<u64>,
+}
+
+/// Returns `num_slots` empty slots, for initializing a new slot-array container.
+pub(crate) fn empty_container_slots(num_slots: usize) -> Vec<ContainerSlot> {
+    vec![ContainerSlot::default(); num_slots]
+}
+
+/// Trait for containers backed by a `Vec<ContainerSlot>`.
+/// Every implementor automatically gets an `ItemContainer` implementation,
+/// so the container size is declared once (by the length of the slot array).
+pub(crate) trait SlotArrayContainer {
+    fn slots(&self) -> &[ContainerSlot];
+    fn slots_mut(&mut self) -> &mut [ContainerSlot];
+    fn slot_container_type(&self) -> ContainerType;
+    fn slot_container_id(&self) -> u64;
+}
+
+impl<T: SlotArrayContainer> ItemContainer for T {
+    fn num_slots(&self) -> usize {
+        self.slots().len()
+    }
+
+    fn get_slot_instance_id(&self, slot_index: u8) -> Option<u64> {
+        self.slots().get(slot_index as usize).and_then(|slot| slot.instance_id)
+    }
+
+    fn get_slot_def_id(&self, slot_index: u8) -> Option<u64> {
+        self.slots().get(slot_index as usize).and_then(|slot| slot.def_id)
+    }
+
+    fn set_slot(&mut self, slot_index: u8, instance_id: Option<u64>, def_id: Option<u64>) {
+        match self.slots_mut().get_mut(slot_index as usize) {
+            Some(slot) => *slot = ContainerSlot { instance_id, def_id },
+            None => log::error!("[SlotArrayContainer] Attempted to set invalid slot index: {}", slot_index),
+        }
+    }
+
+    fn get_container_type(&self) -> ContainerType {
+        self.slot_container_type()
+    }
+
+    fn get_container_id(&self) -> u64 {
+        self.slot_container_id()
+    }
 }
 
 // --- Helper: Check if Container is Empty --- 
@@ -75,6 +135,11 @@ pub(crate) fn is_container_empty<C: ItemContainer>(container: &C) -> bool {
     true // Went through all slots, all were empty
 }
 
+/// Returns the index of the first empty slot in the container, if any.
+pub(crate) fn first_empty_container_slot<C: ItemContainer>(container: &C) -> Option<u8> {
+    (0..container.num_slots() as u8).find(|&i| container.get_slot_instance_id(i).is_none())
+}
+
 // --- Container Item Search Helper Interface --- 
 
 /// Trait for clearing an item from a container type.
@@ -97,6 +162,10 @@ pub(crate) fn handle_move_to_container_slot<C: ItemContainer>(
     target_slot_index: u8,
     item_instance_id: u64,
 ) -> Result<(), String> {
+    // Using a container gives up spawn protection
+    crate::moderation::ensure_not_sanctioned(ctx, ctx.sender)?;
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    crate::active_effects::cancel_spawn_protection(ctx, ctx.sender);
     let inventory_table = ctx.db.inventory_item();
     let item_def_table = ctx.db.item_definition();
     let sender_id = ctx.sender;
@@ -143,6 +212,14 @@ pub(crate) fn handle_move_to_container_slot<C: ItemContainer>(
         container_type: container.get_container_type(),
         slot_index: target_slot_index,
     });
+
+    // --- Check Weight Cap (a swapped-out item frees its weight) --- 
+    let swapped_out_weight = target_instance_id_opt
+        .and_then(|id| inventory_table.instance_id().find(id))
+        .filter(|target| target.item_def_id != item_to_move.item_def_id)
+        .and_then(|target| item_def_table.id().find(target.item_def_id).map(|def| crate::encumbrance::item_weight(&def, target.quantity)))
+        .unwrap_or(0.0);
+    crate::encumbrance::check_container_weight_cap(ctx, container, crate::encumbrance::item_weight(&item_def_to_move, item_to_move.quantity), swapped_out_weight)?;
     
     // --- Merge/Swap/Place Logic --- 
     if let Some(target_instance_id) = target_instance_id_opt {
@@ -252,6 +329,7 @@ pub(crate) fn handle_move_to_container_slot<C: ItemContainer>(
     }
     // --- End active item check ---
 
+    crate::cooking::record_cook_contributor(ctx, container, target_slot_index, sender_id);
     Ok(())
 }
 
@@ -263,6 +341,9 @@ pub(crate) fn handle_move_from_container_slot<C: ItemContainer>(
     target_slot_type: String, // "inventory" or "hotbar"
     target_slot_index: u32 
 ) -> Result<(), String> {
+    crate::moderation::ensure_not_sanctioned(ctx, ctx.sender)?;
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    crate::active_effects::cancel_spawn_protection(ctx, ctx.sender);
     let sender_id = ctx.sender;
     let inventory_table = ctx.db.inventory_item();
     let item_def_table = ctx.db.item_definition();
@@ -396,6 +477,9 @@ pub(crate) fn handle_move_within_container<C: ItemContainer>(
     source_slot_index: u8,
     target_slot_index: u8
 ) -> Result<(), String> {
+    crate::moderation::ensure_not_sanctioned(ctx, ctx.sender)?;
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    crate::active_effects::cancel_spawn_protection(ctx, ctx.sender);
     let inventory_table = ctx.db.inventory_item();
     let item_def_table = ctx.db.item_definition();
     let container_id = container.get_container_id();
@@ -625,6 +709,9 @@ pub(crate) fn handle_split_into_container<C: ItemContainer>(
     source_item_instance_id: u64, // ID of original stack owned by player
     quantity_to_split: u32
 ) -> Result<(), String> {
+    crate::moderation::ensure_not_sanctioned(ctx, ctx.sender)?;
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    crate::active_effects::cancel_spawn_protection(ctx, ctx.sender);
     let inventory_table = ctx.db.inventory_item();
     let sender_id = ctx.sender;
     let item_def_table = ctx.db.item_definition();
@@ -660,6 +747,8 @@ pub(crate) fn handle_split_into_container<C: ItemContainer>(
     if target_slot_index >= container.num_slots() as u8 {
         return Err("Invalid target container slot index.".to_string());
     }
+    let source_item_def = item_def_table.id().find(source_item.item_def_id).ok_or("Def for source item not found!")?;
+    crate::encumbrance::check_container_weight_cap(ctx, container, crate::encumbrance::item_weight(&source_item_def, quantity_to_split), 0.0)?;
 
     // --- Determine Initial Location for NEW item --- 
     let initial_location_for_new_item = ItemLocation::Container(crate::models::ContainerLocationData {
@@ -683,6 +772,7 @@ pub(crate) fn handle_split_into_container<C: ItemContainer>(
         Ok(_) => {
             log::info!("[SplitIntoContainer] Successfully split {} from item {} and placed/merged new item {} into container slot {}.", 
                      quantity_to_split, source_item_instance_id, new_item_instance_id, target_slot_index);
+            crate::cooking::record_cook_contributor(ctx, container, target_slot_index, sender_id);
             Ok(())
         }
         Err(e) => {
@@ -705,6 +795,9 @@ pub(crate) fn handle_split_from_container<C: ItemContainer>(
     target_slot_type: String, // "inventory" or "hotbar"
     target_slot_index: u32
 ) -> Result<(), String> {
+    crate::moderation::ensure_not_sanctioned(ctx, ctx.sender)?;
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    crate::active_effects::cancel_spawn_protection(ctx, ctx.sender);
     let sender_id = ctx.sender;
     let inventory_table = ctx.db.inventory_item();
     let item_def_table = ctx.db.item_definition();
@@ -898,6 +991,9 @@ pub(crate) fn handle_split_within_container<C: ItemContainer>(
     target_slot_index: u8,
     quantity_to_split: u32
 ) -> Result<(), String> {
+    crate::moderation::ensure_not_sanctioned(ctx, ctx.sender)?;
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    crate::active_effects::cancel_spawn_protection(ctx, ctx.sender);
     let inventory_table = ctx.db.inventory_item();
     let item_def_table = ctx.db.item_definition();
     let container_id = container.get_container_id();
@@ -974,6 +1070,9 @@ pub(crate) fn handle_quick_move_from_container<C: ItemContainer>(
     container: &mut C, 
     source_slot_index: u8
 ) -> Result<(), String> {
+    crate::moderation::ensure_not_sanctioned(ctx, ctx.sender)?;
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    crate::active_effects::cancel_spawn_protection(ctx, ctx.sender);
     let sender_id = ctx.sender;
     let inventory_table = ctx.db.inventory_item();
     // let item_def_table = ctx.db.item_definition(); // Not needed for this function
@@ -1054,6 +1153,9 @@ pub(crate) fn handle_quick_move_to_container<C: ItemContainer>(
     container: &mut C,
     item_instance_id: u64,
 ) -> Result<(), String> {
+    crate::moderation::ensure_not_sanctioned(ctx, ctx.sender)?;
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    crate::active_effects::cancel_spawn_protection(ctx, ctx.sender);
     log::info!(
         "[InvManager QuickMoveToContainer] Attempting for container type: {:?}, container ID: {}, item ID: {}", 
         container.get_container_type(), 
@@ -1069,6 +1171,9 @@ pub(crate) fn handle_quick_move_to_container<C: ItemContainer>(
         .ok_or(format!("Item instance {} not found", item_instance_id))?;
     let item_def_to_move = item_def_table.id().find(item_to_move.item_def_id)
         .ok_or(format!("Definition missing for item {}", item_to_move.item_def_id))?;
+    if item_to_move.is_locked {
+        return Err("This item is locked. Unlock it before moving it into a container.".to_string());
+    }
     
     // --- 2. Determine Original Location & Validate --- 
     let original_location = item_to_move.location.clone();
@@ -1122,6 +1227,7 @@ pub(crate) fn handle_quick_move_to_container<C: ItemContainer>(
     }
 
     let target_slot_idx = target_slot_index_opt.ok_or_else(|| "Container is full or no suitable slot found.".to_string())?;
+    crate::encumbrance::check_container_weight_cap(ctx, container, crate::encumbrance::item_weight(&item_def_to_move, item_to_move.quantity), 0.0)?;
     
     log::info!("[QuickMoveToContainer] Attempting move item {} from player location {:?} to container slot {}.",
              item_instance_id, original_location, target_slot_idx);
@@ -1129,6 +1235,7 @@ pub(crate) fn handle_quick_move_to_container<C: ItemContainer>(
     match merge_or_place_into_container_slot(ctx, container, target_slot_idx, &mut item_to_move, &item_def_to_move) {
         Ok(_) => {
             log::info!("[QuickMoveToContainer] Successfully moved/merged item {} to container slot {}.", item_instance_id, target_slot_idx);
+            crate::cooking::record_cook_contributor(ctx, container, target_slot_idx, sender_id);
             
             let item_after_merge_opt = inventory_table.instance_id().find(item_instance_id);
             let item_fully_moved_or_merged = item_after_merge_opt.is_none() || 
@@ -1178,6 +1285,9 @@ pub(crate) fn handle_drop_from_container_slot<C: ItemContainer>(
     slot_index: u8,
     player_for_drop_location: &Player, // Player whose position determines drop location
 ) -> Result<(), String> {
+    crate::moderation::ensure_not_sanctioned(ctx, ctx.sender)?;
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    crate::active_effects::cancel_spawn_protection(ctx, ctx.sender);
     let inventory_table = ctx.db.inventory_item();
     let item_def_table = ctx.db.item_definition();
 
@@ -1198,7 +1308,7 @@ pub(crate) fn handle_drop_from_container_slot<C: ItemContainer>(
     let (drop_pos_x, drop_pos_y) = calculate_drop_position(player_for_drop_location);
 
     // 4. Create the dropped item entity in the world
-    create_dropped_item_entity(ctx, item_def.id, item_to_drop.quantity, drop_pos_x, drop_pos_y)?;
+    create_owned_dropped_item_entity(ctx, Some(player_for_drop_location.identity), item_def.id, item_to_drop.quantity, drop_pos_x, drop_pos_y, item_to_drop.durability, item_to_drop.charges)?;
 
     // 5. Delete the original InventoryItem from the database
     inventory_table.instance_id().delete(item_instance_id);
@@ -1222,6 +1332,9 @@ pub(crate) fn handle_split_and_drop_from_container_slot<C: ItemContainer>(
     quantity_to_split: u32,
     player_for_drop_location: &Player, // Player whose position determines drop location
 ) -> Result<(), String> {
+    crate::moderation::ensure_not_sanctioned(ctx, ctx.sender)?;
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    crate::active_effects::cancel_spawn_protection(ctx, ctx.sender);
     let inventory_table = ctx.db.inventory_item();
     let item_def_table = ctx.db.item_definition();
 
@@ -1269,7 +1382,7 @@ pub(crate) fn handle_split_and_drop_from_container_slot<C: ItemContainer>(
     let (drop_pos_x, drop_pos_y) = calculate_drop_position(player_for_drop_location);
 
     // 7. Create the dropped item entity for the new split stack
-    create_dropped_item_entity(ctx, new_item_for_drop.item_def_id, new_item_for_drop.quantity, drop_pos_x, drop_pos_y)?;
+    create_owned_dropped_item_entity(ctx, Some(player_for_drop_location.identity), new_item_for_drop.item_def_id, new_item_for_drop.quantity, drop_pos_x, drop_pos_y, None, None)?;
     log::debug!("[GenericSplitDropFromContainer] Created DroppedItem entity for newly split stack {} (def {}, qty {}).", 
              newly_split_item_id, new_item_for_drop.item_def_id, new_item_for_drop.quantity);
 
@@ -0,0 +1,152 @@
+/******************************************************************************
+ *                                                                            *
+ * Server-computed item stats for tooltips. `seed_item_stat_sheets` derives   *
+ * one ItemStatSheet row per item definition: damage and DPS ranges, yields   *
+ * per target type, bleed totals, fuel burn and cook times, and nutrition.    *
+ * Damage and yield ranges come from combat.rs `damage_and_yield_range`,      *
+ * the same lookup used when a hit lands, so clients can show tooltips        *
+ * without copying the damage formulas.                                       *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{ReducerContext, SpacetimeType, Table, log};
+
+use crate::combat::damage_and_yield_range;
+use crate::items::{FoodGroup, ItemDefinition, item_definition as ItemDefinitionTableTrait};
+use crate::models::TargetType;
+use crate::item_stat_sheet::item_stat_sheet as ItemStatSheetTableTrait;
+
+/// Damage and yield of an item against one kind of target.
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct ItemTargetStats {
+    pub target_type: TargetType,
+    pub damage_min: u32,
+    pub damage_max: u32,
+    pub dps_min: Option<f32>, // None if the item has no attack interval
+    pub dps_max: Option<f32>,
+    pub yield_min: u32,
+    pub yield_max: u32, // Before skill and resource richness bonuses
+    pub yield_resource_name: Option<String>,
+}
+
+/// --- Item Stat Sheet ---
+/// Derived stats for one item definition. Kept in sync with the definitions whenever
+/// they are seeded.
+#[spacetimedb::table(name = item_stat_sheet, public)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ItemStatSheet {
+    #[primary_key]
+    pub item_def_id: u64,
+    pub attack_interval_secs: Option<f32>,
+    pub target_stats: Vec<ItemTargetStats>, // Primary and secondary targets, players, and corpses
+    pub bleed_total_damage: Option<f32>, // Over the whole bleed, per hit
+    pub bleed_duration_secs: Option<f32>,
+    pub slow_percent: Option<f32>,
+    pub slow_duration_secs: Option<f32>,
+    pub fuel_burn_duration_secs: Option<f32>, // Per unit of fuel
+    pub cook_time_secs: Option<f32>,
+    pub health_gain: Option<f32>,
+    pub hunger_satiated: Option<f32>,
+    pub thirst_quenched: Option<f32>,
+    pub stamina_gain: Option<f32>,
+    pub effect_duration_secs: Option<f32>, // None or 0 for instant consumables
+    pub food_group: Option<FoodGroup>,
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Brings the stat sheet in line with the current item definitions. Called from
+/// `identity_connected` right after `seed_items`, so only rows whose stats actually
+/// changed are written and rows for removed definitions are deleted.
+pub fn seed_item_stat_sheets(ctx: &ReducerContext) -> Result<(), String> {
+    let sheets = ctx.db.item_stat_sheet();
+    let item_defs = ctx.db.item_definition();
+    let orphaned: Vec<u64> = sheets.iter()
+        .filter(|s| item_defs.id().find(s.item_def_id).is_none())
+        .map(|s| s.item_def_id)
+        .collect();
+    for item_def_id in &orphaned {
+        sheets.item_def_id().delete(*item_def_id);
+    }
+
+    let mut written = 0;
+    for item_def in item_defs.iter() {
+        let sheet = build_stat_sheet(&item_def);
+        match sheets.item_def_id().find(item_def.id) {
+            Some(existing) if existing == sheet => continue,
+            Some(_) => { sheets.item_def_id().update(sheet); }
+            None => {
+                sheets.try_insert(sheet)
+                    .map_err(|e| format!("Failed to insert stat sheet for '{}': {}", item_def.name, e))?;
+            }
+        }
+        written += 1;
+    }
+    if written > 0 || !orphaned.is_empty() {
+        log::info!("Updated {} item stat sheets, removed {}.", written, orphaned.len());
+    }
+    Ok(())
+}
+
+fn build_stat_sheet(item_def: &ItemDefinition) -> ItemStatSheet {
+    let bleed_total_damage = match (item_def.bleed_damage_per_tick, item_def.bleed_duration_seconds, item_def.bleed_tick_interval_seconds) {
+        // Same total as active_effects.rs `apply_on_hit_effects`
+        (Some(per_tick), Some(duration), Some(interval)) if per_tick > 0.0 && duration > 0.0 && interval > 0.0 => {
+            Some(per_tick * (duration / interval).floor())
+        }
+        _ => None,
+    };
+
+    ItemStatSheet {
+        item_def_id: item_def.id,
+        attack_interval_secs: item_def.attack_interval_secs,
+        target_stats: build_target_stats(item_def),
+        bleed_total_damage,
+        bleed_duration_secs: bleed_total_damage.and(item_def.bleed_duration_seconds),
+        slow_percent: item_def.slow_percent,
+        slow_duration_secs: item_def.slow_duration_seconds,
+        fuel_burn_duration_secs: item_def.fuel_burn_duration_secs,
+        cook_time_secs: item_def.cook_time_secs,
+        health_gain: item_def.consumable_health_gain,
+        hunger_satiated: item_def.consumable_hunger_satiated,
+        thirst_quenched: item_def.consumable_thirst_quenched,
+        stamina_gain: item_def.consumable_stamina_gain,
+        effect_duration_secs: item_def.consumable_duration_secs,
+        food_group: item_def.food_group,
+    }
+}
+
+/// Lists the targets the item is meant for, plus players and corpses if it does PvP damage.
+/// Targets it does nothing to are left out.
+fn build_target_stats(item_def: &ItemDefinition) -> Vec<ItemTargetStats> {
+    let mut target_types: Vec<TargetType> = Vec::new();
+    let candidates = [item_def.primary_target_type, item_def.secondary_target_type, Some(TargetType::Player), Some(TargetType::PlayerCorpse)];
+    for target_type in candidates.into_iter().flatten() {
+        if !target_types.contains(&target_type) {
+            target_types.push(target_type);
+        }
+    }
+
+    let attack_interval = item_def.attack_interval_secs.filter(|secs| *secs > 0.0);
+    target_types.into_iter()
+        .filter_map(|target_type| {
+            let range = damage_and_yield_range(item_def, target_type);
+            if range.damage_max == 0 && range.yield_max == 0 {
+                return None;
+            }
+            let yield_resource_name = if range.resource_name == "None" { None } else { Some(range.resource_name) };
+            Some(ItemTargetStats {
+                target_type,
+                damage_min: range.damage_min,
+                damage_max: range.damage_max,
+                dps_min: attack_interval.map(|secs| range.damage_min as f32 / secs),
+                dps_max: attack_interval.map(|secs| range.damage_max as f32 / secs),
+                yield_min: range.yield_min,
+                yield_max: range.yield_max,
+                yield_resource_name,
+            })
+        })
+        .collect()
+}
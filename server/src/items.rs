@@ -9,7 +9,7 @@ use crate::campfire::campfire as CampfireTableTrait;
 // Import Player table trait
 use crate::player as PlayerTableTrait;
 // Import DroppedItem helpers
-use crate::dropped_item::{calculate_drop_position, create_dropped_item_entity};
+use crate::dropped_item::{calculate_drop_position, create_owned_dropped_item_entity};
 // REMOVE unused concrete table type imports
 // use crate::items::{InventoryItemTable, ItemDefinitionTable};
 use crate::items_database; // ADD import for new module
@@ -26,9 +26,15 @@ use crate::models::{ItemLocation, EquipmentSlotType, TargetType}; // <<< UPDATED
 use std::collections::HashSet;
 use serde::{Deserialize, Serialize};
 use crate::campfire::CampfireClearer; 
+use crate::furnace::FurnaceClearer;
+use crate::large_storage_chest::LargeStorageChestClearer;
+use crate::recycler::RecyclerClearer;
+use crate::flame_turret::FlameTurretClearer;
 use crate::wooden_storage_box::WoodenStorageBoxClearer;
 use crate::player_corpse::PlayerCorpseClearer;
 use crate::stash::StashClearer; // Added StashClearer import
+use crate::overflow_backpack::DroppedBackpackClearer;
+use crate::vending_machine::VendingMachineClearer;
 
 // --- Item Enums and Structs ---
 
@@ -44,6 +50,40 @@ pub enum ItemCategory {
     // Add other categories as needed (Consumable, Wearable, etc.)
 }
 
+// Nutrition groups for food. Eating from several groups makes a player well-fed (see consumables.rs)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, SpacetimeType)]
+pub enum FoodGroup {
+    Protein,
+    Vegetable,
+}
+
+// Consumables in the same category share a use cooldown; each category is tracked separately (see consumables.rs)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, SpacetimeType)]
+pub enum ConsumableCooldownCategory {
+    Food,
+    Medical,
+    Elixir,
+}
+
+// How rare an item is; decides how long it lies on the ground once dropped (see dropped_item.rs)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SpacetimeType)]
+pub enum ItemRarity {
+    Common,
+    Uncommon,
+    Rare,
+}
+
+impl ItemRarity {
+    /// Seconds a dropped item of this rarity stays in the world before despawning.
+    pub fn dropped_despawn_secs(self) -> u64 {
+        match self {
+            ItemRarity::Common => 5 * 60,
+            ItemRarity::Uncommon => 15 * 60,
+            ItemRarity::Rare => 30 * 60,
+        }
+    }
+}
+
 #[derive(SpacetimeType, Clone, Debug, Serialize, Deserialize)] // Added Serialize, Deserialize
 pub struct CostIngredient {
     pub item_name: String,
@@ -88,6 +128,8 @@ pub struct ItemDefinition {
     pub bleed_damage_per_tick: Option<f32>, // ADDED
     pub bleed_duration_seconds: Option<f32>, // ADDED
     pub bleed_tick_interval_seconds: Option<f32>, // ADDED
+    pub slow_percent: Option<f32>, // On-hit slow, e.g. 0.3 = target moves 30% slower
+    pub slow_duration_seconds: Option<f32>,
 
     pub crafting_cost: Option<Vec<CostIngredient>>, // MODIFIED HERE
     pub crafting_output_quantity: Option<u32>,      // How many items this recipe produces
@@ -105,6 +147,13 @@ pub struct ItemDefinition {
     pub warmth_bonus: Option<f32>,      // <<< ADDED: e.g., 0.2 warmth points per effect interval
     pub respawn_time_seconds: Option<u32>, // Time for the item/resource node to respawn in the world
     pub attack_interval_secs: Option<f32>, // Minimum time between attacks for this item
+    pub stamina_cost_per_swing: Option<f32>, // Stamina spent per melee swing. None = swinging is free
+    pub max_durability: Option<f32>, // Tools/weapons wear out when Some. None = never wears out
+    pub food_group: Option<FoodGroup>, // Nutrition group of a food. None = no nutritional value
+    pub is_cooked_food: bool, // Cooked (or burnt) food, as opposed to raw
+    pub consumable_cooldown_category: Option<ConsumableCooldownCategory>, // Shared use cooldown. None = general cooldown only
+    pub weight: f32, // Weight of one unit, counted towards the carrier's encumbrance
+    pub rarity: ItemRarity,
 }
 
 // --- Inventory Table ---
@@ -119,7 +168,9 @@ pub struct InventoryItem {
     pub item_def_id: u64,      // Links to ItemDefinition table (FK)
     pub quantity: u32,         // How many of this item
     pub location: ItemLocation, // <<< NEW FIELD ADDED
-    // Add other instance-specific data later (e.g., current_durability)
+    pub durability: Option<f32>, // Current durability. None = pristine (or item doesn't wear out)
+    pub is_locked: bool, // Locked items can't be dropped, quick-moved into containers or used up by crafting
+    pub charges: Option<u32>, // Uses left in a refillable container (water jugs). None = not a charged item
 }
 
 // --- Item Reducers ---
@@ -287,6 +338,9 @@ pub(crate) fn add_item_to_player_inventory(ctx: &ReducerContext, player_id: Iden
                 item_def_id,
                 quantity: final_quantity_to_add,
                 location: ItemLocation::Hotbar(crate::models::HotbarLocationData { owner_id: player_id, slot_index: empty_hotbar_slot }),
+                durability: None,
+                is_locked: false,
+                charges: None,
             };
             let inserted_item = inventory.insert(new_item);
             log::info!("[AddItem] Added {} of item def {} to hotbar slot {} for player {:?}. New ID: {}",
@@ -306,6 +360,9 @@ pub(crate) fn add_item_to_player_inventory(ctx: &ReducerContext, player_id: Iden
                     item_def_id,
                     quantity: final_quantity_to_add,
                     location: ItemLocation::Inventory(crate::models::InventoryLocationData { owner_id: player_id, slot_index: empty_inventory_slot }),
+                    durability: None,
+                    is_locked: false,
+                    charges: None,
                 };
                 let inserted_item = inventory.insert(new_item);
                 log::info!("[AddItem] Added {} of item def {} to inventory slot {} for player {:?}. (Hotbar was full) New ID: {}",
@@ -322,6 +379,34 @@ pub(crate) fn add_item_to_player_inventory(ctx: &ReducerContext, player_id: Iden
     }
 }
 
+/// How many more of the item the player's hotbar and inventory can hold: free space in
+/// existing stacks plus a full stack per empty slot. Non-stackable items take one slot each.
+pub(crate) fn player_inventory_space_for_item(ctx: &ReducerContext, player_id: Identity, item_def: &ItemDefinition) -> u32 {
+    let mut stack_space = 0u32;
+    let mut used_slots = 0u32;
+    for item in ctx.db.inventory_item().iter() {
+        let owned = match &item.location {
+            ItemLocation::Hotbar(data) => data.owner_id == player_id,
+            ItemLocation::Inventory(data) => data.owner_id == player_id,
+            _ => false,
+        };
+        if !owned {
+            continue;
+        }
+        used_slots += 1;
+        if item_def.is_stackable && item.item_def_id == item_def.id {
+            stack_space += item_def.stack_size.saturating_sub(item.quantity);
+        }
+    }
+    let total_slots = crate::player_inventory::NUM_PLAYER_HOTBAR_SLOTS as u32 + crate::player_inventory::NUM_PLAYER_INVENTORY_SLOTS as u32;
+    let free_slots = total_slots.saturating_sub(used_slots);
+    if item_def.is_stackable {
+        stack_space.saturating_add(free_slots.saturating_mul(item_def.stack_size))
+    } else {
+        free_slots
+    }
+}
+
 // Helper to clear a specific item instance from any equipment slot it might occupy
 pub(crate) fn clear_specific_item_from_equipment_slots(ctx: &ReducerContext, player_id: spacetimedb::Identity, item_instance_id_to_clear: u64) {
     let active_equip_table = ctx.db.active_equipment();
@@ -389,6 +474,30 @@ pub(crate) fn clear_item_from_any_container(ctx: &ReducerContext, item_instance_
         return; // Item found and handled
     }
 
+    // Attempt to clear from Furnace slots
+    if FurnaceClearer::clear_item(ctx, item_instance_id) {
+        log::debug!("[ItemsClear] Item {} cleared from a furnace.", item_instance_id);
+        return; // Item found and handled
+    }
+
+    // Attempt to clear from LargeStorageChest slots
+    if LargeStorageChestClearer::clear_item(ctx, item_instance_id) {
+        log::debug!("[ItemsClear] Item {} cleared from a large storage chest.", item_instance_id);
+        return; // Item found and handled
+    }
+
+    // Attempt to clear from Recycler slots
+    if RecyclerClearer::clear_item(ctx, item_instance_id) {
+        log::debug!("[ItemsClear] Item {} cleared from a recycler.", item_instance_id);
+        return; // Item found and handled
+    }
+
+    // Attempt to clear from FlameTurret slots
+    if FlameTurretClearer::clear_item(ctx, item_instance_id) {
+        log::debug!("[ItemsClear] Item {} cleared from a flame turret.", item_instance_id);
+        return; // Item found and handled
+    }
+
     // Attempt to clear from WoodenStorageBox slots
     if WoodenStorageBoxClearer::clear_item(ctx, item_instance_id) {
         log::debug!("[ItemsClear] Item {} cleared from a wooden storage box.", item_instance_id);
@@ -407,6 +516,18 @@ pub(crate) fn clear_item_from_any_container(ctx: &ReducerContext, item_instance_
         return; // Item found and handled
     }
 
+    // Attempt to clear from DroppedBackpack slots
+    if DroppedBackpackClearer::clear_item(ctx, item_instance_id) {
+        log::debug!("[ItemsClear] Item {} cleared from a dropped backpack.", item_instance_id);
+        return; // Item found and handled
+    }
+
+    // Attempt to clear from VendingMachine slots
+    if VendingMachineClearer::clear_item(ctx, item_instance_id) {
+        log::debug!("[ItemsClear] Item {} cleared from a vending machine.", item_instance_id);
+        return; // Item found and handled
+    }
+
     // If we reach here, the item was not found in any of the explicitly checked containers.
     // The item's own `location` field might be stale or point to a player inventory/hotbar/equipment,
     // which this function is not designed to clear directly.
@@ -463,6 +584,12 @@ pub fn equip_armor_from_drag(ctx: &ReducerContext, item_instance_id: u64, target
              return Err(format!("Item {} in inventory/hotbar not owned by caller.", item_instance_id));
         }
     }
+    // Armor worn by someone else can't be dragged onto the caller
+    if let ItemLocation::Equipped(data) = &original_location {
+        if data.owner_id != sender_id {
+            return Err(format!("Item {} is equipped by another player.", item_instance_id));
+        }
+    }
     // 1. Must be Armor category
     if item_def.category != ItemCategory::Armor {
         return Err(format!("Item '{}' is not armor.", item_def.name));
@@ -598,6 +725,9 @@ pub(crate) fn split_stack_helper(
         item_def_id: source_item.item_def_id,
         quantity: quantity_to_split,
         location: initial_location_for_new_item.clone(), // Set by caller, clone for logging
+        durability: source_item.durability,
+        is_locked: source_item.is_locked, // Both halves of a locked stack stay locked
+        charges: source_item.charges,
     };
     let inserted_item = ctx.db.inventory_item().insert(new_item);
     let new_instance_id = inserted_item.instance_id;
@@ -610,6 +740,31 @@ pub(crate) fn split_stack_helper(
     Ok(new_instance_id)
 }
 
+/// --- Toggle Item Lock ---
+/// Locks or unlocks one of the sender's items. A locked item can't be dropped, quick-moved
+/// into a container or taken as a crafting ingredient until it is unlocked again.
+#[spacetimedb::reducer]
+pub fn toggle_item_lock(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let inventory_items = ctx.db.inventory_item();
+    let mut item = inventory_items.instance_id().find(item_instance_id)
+        .ok_or_else(|| format!("Item instance {} not found.", item_instance_id))?;
+    let owned = match &item.location {
+        ItemLocation::Inventory(data) => data.owner_id == sender_id,
+        ItemLocation::Hotbar(data) => data.owner_id == sender_id,
+        ItemLocation::Equipped(data) => data.owner_id == sender_id,
+        _ => false,
+    };
+    if !owned {
+        return Err("You can only lock items you are carrying.".to_string());
+    }
+
+    item.is_locked = !item.is_locked;
+    log::info!("[ItemLock] Player {:?} {} item {}.", sender_id, if item.is_locked { "locked" } else { "unlocked" }, item_instance_id);
+    inventory_items.instance_id().update(item);
+    Ok(())
+}
+
 // --- NEW: Drop Item into the World ---
 #[spacetimedb::reducer]
 pub fn drop_item(
@@ -617,6 +772,7 @@ pub fn drop_item(
     item_instance_id: u64,
     quantity_to_drop: u32, // How many to drop (can be less than total stack)
 ) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
     let sender_id = ctx.sender;
     log::info!("[DropItem] Player {:?} attempting to drop {} of item instance {}", sender_id, quantity_to_drop, item_instance_id);
 
@@ -632,6 +788,9 @@ pub fn drop_item(
     // --- 2. Find Item & Validate ---
     let mut item_to_drop = inventory_items.instance_id().find(item_instance_id)
         .ok_or_else(|| format!("Item instance {} not found.", item_instance_id))?;
+    if item_to_drop.is_locked {
+        return Err("This item is locked. Unlock it before dropping it.".to_string());
+    }
     
     // Clone the original location *before* any modifications to item_to_drop for partial drops.
     let original_location_of_item = item_to_drop.location.clone();
@@ -684,6 +843,9 @@ pub fn drop_item(
         }
     }
 
+    let dropped_durability = item_to_drop.durability;
+    let dropped_charges = item_to_drop.charges;
+
     // --- 5. Handle Quantity & Potential Splitting ---
     if quantity_to_drop == item_to_drop.quantity {
         // Dropping the entire stack
@@ -708,7 +870,7 @@ pub fn drop_item(
     log::debug!("[DropItem] Calculated drop position: ({:.1}, {:.1}) for player {:?}", drop_x, drop_y, sender_id);
 
     // --- 7. Create Dropped Item Entity in World ---
-    create_dropped_item_entity(ctx, item_def.id, quantity_to_drop, drop_x, drop_y)?;
+    create_owned_dropped_item_entity(ctx, Some(sender_id), item_def.id, quantity_to_drop, drop_x, drop_y, dropped_durability, dropped_charges)?;
 
     log::info!("[DropItem] Successfully dropped {} of item def {} (Original ID: {}) at ({:.1}, {:.1}) for player {:?}.",
             quantity_to_drop, item_def.id, item_instance_id, drop_x, drop_y, sender_id);
@@ -1,5 +1,6 @@
-use crate::items::{ItemDefinition, ItemCategory, CostIngredient};
+use crate::items::{ItemDefinition, ItemCategory, CostIngredient, FoodGroup, ConsumableCooldownCategory, ItemRarity};
 use crate::models::{EquipmentSlotType, TargetType};
+use crate::armor::ARMOR_MAX_DURABILITY;
 
 pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
     let initial_items = vec![
@@ -40,12 +41,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: Some(300),
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.1,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
@@ -83,12 +93,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: Some(30),
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.15,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
@@ -126,12 +145,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: Some(300),
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.02,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
@@ -171,24 +199,33 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: Some(300),
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.05,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
             name: "Charcoal".to_string(),
-            description: "Residue from burnt wood. Useful for crafting.".to_string(),
+            description: "Residue from burnt wood. Burns longer than wood. Useful for crafting.".to_string(),
             category: ItemCategory::Material,
             icon_asset_name: "charcoal.png".to_string(),
             is_stackable: true,
             stack_size: 1000,
             is_equippable: false,
             equipment_slot_type: None,
-            fuel_burn_duration_secs: None,
+            fuel_burn_duration_secs: Some(8.0),
             primary_target_damage_min: None,
             primary_target_damage_max: None,
             primary_target_yield_min: None,
@@ -214,12 +251,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: Some(300),
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.05,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
@@ -257,12 +303,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.02,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
@@ -300,12 +355,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.3,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
@@ -343,12 +407,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: Some(15.0),
             cooked_item_def_name: Some("Tallow".to_string()),
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.1,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
@@ -386,12 +459,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: None,
             attack_interval_secs: Some(2.0),
+            stamina_cost_per_swing: Some(5.0),
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.5,
+            rarity: ItemRarity::Rare,
         },
         ItemDefinition {
             id: 0,
@@ -432,12 +514,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: Some(0.3),
+            slow_duration_seconds: Some(2.0),
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: None,
             attack_interval_secs: Some(0.8),
+            stamina_cost_per_swing: Some(6.0),
+            max_durability: Some(150.0),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 1.5,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
@@ -478,12 +569,229 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: Some(1.0),
             bleed_duration_seconds: Some(5.0),
             bleed_tick_interval_seconds: Some(1.0),
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: None,
             attack_interval_secs: Some(0.5),
+            stamina_cost_per_swing: Some(3.0),
+            max_durability: Some(150.0),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.5,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Metal Ore".to_string(),
+            description: "Raw ore chipped from stone. Smelt it in a furnace to get metal fragments.".to_string(),
+            category: ItemCategory::Material,
+            icon_asset_name: "metal_ore.png".to_string(),
+            is_stackable: true,
+            stack_size: 1000,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: Some(10.0),
+            cooked_item_def_name: Some("Metal Fragments".to_string()),
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(300),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.3,
+            rarity: ItemRarity::Uncommon,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Metal Fragments".to_string(),
+            description: "Smelted metal. Used to craft sturdier tools and structures.".to_string(),
+            category: ItemCategory::Material,
+            icon_asset_name: "metal_fragments.png".to_string(),
+            is_stackable: true,
+            stack_size: 1000,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(300),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.1,
+            rarity: ItemRarity::Uncommon,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Thorns".to_string(),
+            description: "Sharp thorns cut from a thorn bush.".to_string(),
+            category: ItemCategory::Material,
+            icon_asset_name: "thorns.png".to_string(),
+            is_stackable: true,
+            stack_size: 1000,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(300),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.05,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Sulfur".to_string(),
+            description: "Yellow crust chipped from sun-scorched hot stones.".to_string(),
+            category: ItemCategory::Material,
+            icon_asset_name: "sulfur.png".to_string(),
+            is_stackable: true,
+            stack_size: 1000,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(300),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.2,
+            rarity: ItemRarity::Uncommon,
         },
 
         // --- TOOLS ---
@@ -526,12 +834,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: Some(600),
             attack_interval_secs: Some(0.8),
+            stamina_cost_per_swing: Some(5.0),
+            max_durability: Some(200.0),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 1.5,
+            rarity: ItemRarity::Uncommon,
         },
         ItemDefinition {
             id: 0,
@@ -571,12 +888,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: Some(300),
             attack_interval_secs: Some(1.0),
+            stamina_cost_per_swing: Some(4.0),
+            max_durability: Some(100.0),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 1.0,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
@@ -617,12 +943,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: Some(600),
             attack_interval_secs: Some(1.2),
+            stamina_cost_per_swing: Some(7.0),
+            max_durability: Some(200.0),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 2.0,
+            rarity: ItemRarity::Uncommon,
         },
         ItemDefinition {
             id: 0,
@@ -662,12 +997,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: Some(300),
             attack_interval_secs: Some(0.7),
+            stamina_cost_per_swing: Some(4.0),
+            max_durability: Some(50.0),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 1.0,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
@@ -707,12 +1051,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: Some(2.0),
             bleed_duration_seconds: Some(8.0),
             bleed_tick_interval_seconds: Some(1.0),
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: Some(300),
             attack_interval_secs: Some(1.5),
+            stamina_cost_per_swing: Some(6.0),
+            max_durability: Some(100.0),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 1.5,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
@@ -753,12 +1106,76 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: Some(30),
             attack_interval_secs: Some(0.5),
+            stamina_cost_per_swing: Some(3.0),
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.5,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Wooden Shield".to_string(),
+            description: "A round shield of lashed planks. Hold it up to block most of the damage from blows and arrows, at the cost of stamina.".to_string(),
+            category: ItemCategory::Tool,
+            icon_asset_name: "wooden_shield.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
+            is_equippable: true,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Wood".to_string(), quantity: 150 },
+                CostIngredient { item_name: "Plant Fiber".to_string(), quantity: 20 },
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(15),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 3.0,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
@@ -799,12 +1216,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: Some(3.0),
             bleed_duration_seconds: Some(8.0),
             bleed_tick_interval_seconds: Some(1.0),
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: Some(420),
             attack_interval_secs: Some(1.3),
+            stamina_cost_per_swing: Some(7.0),
+            max_durability: Some(150.0),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 2.0,
+            rarity: ItemRarity::Uncommon,
         },
 
         // --- PLACEABLES ---
@@ -847,12 +1273,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: Some(300),
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 5.0,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
@@ -892,12 +1327,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: Some(420),
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 1.0,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
@@ -937,19 +1381,28 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: Some(300),
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 5.0,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
-            name: "Sleeping Bag".to_string(),
-            description: "A rolled-up bag for sleeping outdoors. Sets a respawn point.".to_string(),
+            name: "Large Storage Chest".to_string(),
+            description: "A sturdy chest with plenty of room for your hoard.".to_string(),
             category: ItemCategory::Placeable,
-            icon_asset_name: "sleeping_bag.png".to_string(),
+            icon_asset_name: "large_storage_chest.png".to_string(),
             is_stackable: false,
             stack_size: 1,
             is_equippable: false,
@@ -970,10 +1423,11 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             pvp_damage_min: None,
             pvp_damage_max: None,
             crafting_cost: Some(vec![
-                CostIngredient { item_name: "Cloth".to_string(), quantity: 25 }
+                CostIngredient { item_name: "Wood".to_string(), quantity: 300 },
+                CostIngredient { item_name: "Metal Fragments".to_string(), quantity: 50 },
             ]),
             crafting_output_quantity: Some(1),
-            crafting_time_secs: Some(15),
+            crafting_time_secs: Some(30),
             consumable_health_gain: None,
             consumable_hunger_satiated: None,
             consumable_thirst_quenched: None,
@@ -982,25 +1436,32 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
-            respawn_time_seconds: Some(420),
+            respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 10.0,
+            rarity: ItemRarity::Uncommon,
         },
-
-        // --- ARMOR (CLOTH SET) ---
         ItemDefinition {
             id: 0,
-            name: "Cloth Hood".to_string(),
-            description: "Basic head covering.".to_string(),
-            category: ItemCategory::Armor,
-            icon_asset_name: "cloth_hood.png".to_string(),
-            is_stackable: false,
-            stack_size: 1,
-            is_equippable: true,
-            equipment_slot_type: Some(EquipmentSlotType::Head),
+            name: "Lock".to_string(),
+            description: "A simple key lock. Attach it to a storage container or door to keep others out.".to_string(),
+            category: ItemCategory::Material,
+            icon_asset_name: "lock.png".to_string(),
+            is_stackable: true,
+            stack_size: 10,
+            is_equippable: false,
+            equipment_slot_type: None,
             fuel_burn_duration_secs: None,
             primary_target_damage_min: None,
             primary_target_damage_max: None,
@@ -1017,7 +1478,8 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             pvp_damage_min: None,
             pvp_damage_max: None,
             crafting_cost: Some(vec![
-                CostIngredient { item_name: "Cloth".to_string(), quantity: 20 },
+                CostIngredient { item_name: "Wood".to_string(), quantity: 50 },
+                CostIngredient { item_name: "Metal Fragments".to_string(), quantity: 20 },
             ]),
             crafting_output_quantity: Some(1),
             crafting_time_secs: Some(10),
@@ -1029,23 +1491,32 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
-            damage_resistance: Some(0.01),
-            warmth_bonus: Some(0.2),
-            respawn_time_seconds: Some(420),
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.5,
+            rarity: ItemRarity::Uncommon,
         },
         ItemDefinition {
             id: 0,
-            name: "Cloth Shirt".to_string(),
-            description: "Simple protection for the torso.".to_string(),
-            category: ItemCategory::Armor,
-            icon_asset_name: "cloth_shirt.png".to_string(),
-            is_stackable: false,
-            stack_size: 1,
-            is_equippable: true,
-            equipment_slot_type: Some(EquipmentSlotType::Chest),
+            name: "Satchel Charge".to_string(),
+            description: "A crude explosive bundle. Plant it against a structure and stand back.".to_string(),
+            category: ItemCategory::Tool,
+            icon_asset_name: "satchel_charge.png".to_string(),
+            is_stackable: true,
+            stack_size: 5,
+            is_equippable: false,
+            equipment_slot_type: None,
             fuel_burn_duration_secs: None,
             primary_target_damage_min: None,
             primary_target_damage_max: None,
@@ -1062,10 +1533,12 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             pvp_damage_min: None,
             pvp_damage_max: None,
             crafting_cost: Some(vec![
-                CostIngredient { item_name: "Cloth".to_string(), quantity: 40 },
+                CostIngredient { item_name: "Cloth".to_string(), quantity: 10 },
+                CostIngredient { item_name: "Charcoal".to_string(), quantity: 100 },
+                CostIngredient { item_name: "Metal Fragments".to_string(), quantity: 40 },
             ]),
             crafting_output_quantity: Some(1),
-            crafting_time_secs: Some(15),
+            crafting_time_secs: Some(20),
             consumable_health_gain: None,
             consumable_hunger_satiated: None,
             consumable_thirst_quenched: None,
@@ -1074,23 +1547,32 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
-            damage_resistance: Some(0.01),
-            warmth_bonus: Some(0.2),
-            respawn_time_seconds: Some(420),
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 2.0,
+            rarity: ItemRarity::Rare,
         },
         ItemDefinition {
             id: 0,
-            name: "Cloth Pants".to_string(),
-            description: "Simple protection for the legs.".to_string(),
-            category: ItemCategory::Armor,
-            icon_asset_name: "cloth_pants.png".to_string(),
+            name: "Sleeping Bag".to_string(),
+            description: "A rolled-up bag for sleeping outdoors. Sets a respawn point.".to_string(),
+            category: ItemCategory::Placeable,
+            icon_asset_name: "sleeping_bag.png".to_string(),
             is_stackable: false,
             stack_size: 1,
-            is_equippable: true,
-            equipment_slot_type: Some(EquipmentSlotType::Legs),
+            is_equippable: false,
+            equipment_slot_type: None,
             fuel_burn_duration_secs: None,
             primary_target_damage_min: None,
             primary_target_damage_max: None,
@@ -1107,7 +1589,7 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             pvp_damage_min: None,
             pvp_damage_max: None,
             crafting_cost: Some(vec![
-                CostIngredient { item_name: "Cloth".to_string(), quantity: 30 },
+                CostIngredient { item_name: "Cloth".to_string(), quantity: 25 }
             ]),
             crafting_output_quantity: Some(1),
             crafting_time_secs: Some(15),
@@ -1119,23 +1601,32 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
-            damage_resistance: Some(0.01),
-            warmth_bonus: Some(0.2),
+            damage_resistance: None,
+            warmth_bonus: None,
             respawn_time_seconds: Some(420),
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 2.0,
+            rarity: ItemRarity::Uncommon,
         },
         ItemDefinition {
             id: 0,
-            name: "Cloth Gloves".to_string(),
-            description: "Basic hand coverings.".to_string(),
-            category: ItemCategory::Armor,
-            icon_asset_name: "cloth_gloves.png".to_string(),
+            name: "Furnace".to_string(),
+            description: "A stone furnace for smelting ore. Burns wood or charcoal.".to_string(),
+            category: ItemCategory::Placeable,
+            icon_asset_name: "furnace.png".to_string(),
             is_stackable: false,
             stack_size: 1,
-            is_equippable: true,
-            equipment_slot_type: Some(EquipmentSlotType::Hands),
+            is_equippable: false,
+            equipment_slot_type: None,
             fuel_burn_duration_secs: None,
             primary_target_damage_min: None,
             primary_target_damage_max: None,
@@ -1152,10 +1643,11 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             pvp_damage_min: None,
             pvp_damage_max: None,
             crafting_cost: Some(vec![
-                CostIngredient { item_name: "Cloth".to_string(), quantity: 15 },
+                CostIngredient { item_name: "Stone".to_string(), quantity: 100 },
+                CostIngredient { item_name: "Wood".to_string(), quantity: 50 },
             ]),
             crafting_output_quantity: Some(1),
-            crafting_time_secs: Some(5),
+            crafting_time_secs: Some(10),
             consumable_health_gain: None,
             consumable_hunger_satiated: None,
             consumable_thirst_quenched: None,
@@ -1164,23 +1656,32 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
-            damage_resistance: Some(0.01),
-            warmth_bonus: Some(0.2),
+            damage_resistance: None,
+            warmth_bonus: None,
             respawn_time_seconds: Some(420),
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 15.0,
+            rarity: ItemRarity::Uncommon,
         },
         ItemDefinition {
             id: 0,
-            name: "Cloth Boots".to_string(),
-            description: "Simple footwear.".to_string(),
-            category: ItemCategory::Armor,
-            icon_asset_name: "cloth_boots.png".to_string(),
+            name: "Workbench".to_string(),
+            description: "A sturdy bench for crafting. Required for more advanced recipes.".to_string(),
+            category: ItemCategory::Placeable,
+            icon_asset_name: "workbench.png".to_string(),
             is_stackable: false,
             stack_size: 1,
-            is_equippable: true,
-            equipment_slot_type: Some(EquipmentSlotType::Feet),
+            is_equippable: false,
+            equipment_slot_type: None,
             fuel_burn_duration_secs: None,
             primary_target_damage_min: None,
             primary_target_damage_max: None,
@@ -1197,10 +1698,11 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             pvp_damage_min: None,
             pvp_damage_max: None,
             crafting_cost: Some(vec![
-                CostIngredient { item_name: "Cloth".to_string(), quantity: 15 },
+                CostIngredient { item_name: "Wood".to_string(), quantity: 250 },
+                CostIngredient { item_name: "Stone".to_string(), quantity: 50 },
             ]),
             crafting_output_quantity: Some(1),
-            crafting_time_secs: Some(5),
+            crafting_time_secs: Some(15),
             consumable_health_gain: None,
             consumable_hunger_satiated: None,
             consumable_thirst_quenched: None,
@@ -1209,23 +1711,32 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
-            damage_resistance: Some(0.01),
-            warmth_bonus: Some(0.2),
-            respawn_time_seconds: Some(420),
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 10.0,
+            rarity: ItemRarity::Uncommon,
         },
         ItemDefinition {
             id: 0,
-            name: "Cloth Cape".to_string(),
-            description: "A simple cape made of cloth.".to_string(),
-            category: ItemCategory::Armor,
-            icon_asset_name: "burlap_cape.png".to_string(),
+            name: "Advanced Workbench".to_string(),
+            description: "A reinforced workbench with metal tools. Unlocks the most advanced recipes and crafts faster.".to_string(),
+            category: ItemCategory::Placeable,
+            icon_asset_name: "advanced_workbench.png".to_string(),
             is_stackable: false,
             stack_size: 1,
-            is_equippable: true,
-            equipment_slot_type: Some(EquipmentSlotType::Back),
+            is_equippable: false,
+            equipment_slot_type: None,
             fuel_burn_duration_secs: None,
             primary_target_damage_min: None,
             primary_target_damage_max: None,
@@ -1242,11 +1753,11 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             pvp_damage_min: None,
             pvp_damage_max: None,
             crafting_cost: Some(vec![
-                CostIngredient { item_name: "Cloth".to_string(), quantity: 30 },
-                CostIngredient { item_name: "Plant Fiber".to_string(), quantity: 10 },
+                CostIngredient { item_name: "Wood".to_string(), quantity: 500 },
+                CostIngredient { item_name: "Metal Fragments".to_string(), quantity: 100 },
             ]),
             crafting_output_quantity: Some(1),
-            crafting_time_secs: Some(20),
+            crafting_time_secs: Some(30),
             consumable_health_gain: None,
             consumable_hunger_satiated: None,
             consumable_thirst_quenched: None,
@@ -1255,23 +1766,30 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
-            damage_resistance: Some(0.01),
-            warmth_bonus: Some(0.1),
-            respawn_time_seconds: Some(420),
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 15.0,
+            rarity: ItemRarity::Rare,
         },
-        
-        // --- CONSUMABLES (Rebalanced and New) ---
         ItemDefinition {
             id: 0,
-            name: "Mushroom".to_string(),
-            description: "A raw mushroom. Edible, but provides little sustenance, and makes you thirsty.".to_string(),
-            category: ItemCategory::Consumable,
-            icon_asset_name: "mushroom.png".to_string(),
+            name: "Wooden Sign".to_string(),
+            description: "A wooden board for leaving a short message. Only you can change what it says.".to_string(),
+            category: ItemCategory::Placeable,
+            icon_asset_name: "wooden_sign.png".to_string(),
             is_stackable: true,
-            stack_size: 20,
+            stack_size: 5,
             is_equippable: false,
             equipment_slot_type: None,
             fuel_burn_duration_secs: None,
@@ -1289,32 +1807,43 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             secondary_yield_resource_name: None,
             pvp_damage_min: None,
             pvp_damage_max: None,
-            crafting_cost: None,
-            crafting_output_quantity: None,
-            crafting_time_secs: None,
-            consumable_health_gain: Some(3.0),      
-            consumable_hunger_satiated: Some(5.0),  
-            consumable_thirst_quenched: Some(-2.0), 
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Wood".to_string(), quantity: 100 },
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(5),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
             consumable_stamina_gain: None,
             consumable_duration_secs: None,
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
-            cook_time_secs: Some(20.0), 
-            cooked_item_def_name: Some("Cooked Mushroom".to_string()),
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
-            respawn_time_seconds: Some(180),
+            respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 2.0,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
-            name: "Cooked Mushroom".to_string(),
-            description: "A tasty cooked mushroom. More filling.".to_string(),
-            category: ItemCategory::Consumable,
-            icon_asset_name: "cooked_mushroom.png".to_string(),
+            name: "Switch".to_string(),
+            description: "A lever wired to your campfires and furnaces. Flipping it lights or puts out every linked fire at once.".to_string(),
+            category: ItemCategory::Placeable,
+            icon_asset_name: "switch.png".to_string(),
             is_stackable: true,
-            stack_size: 20,
+            stack_size: 5,
             is_equippable: false,
             equipment_slot_type: None,
             fuel_burn_duration_secs: None,
@@ -1332,32 +1861,44 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             secondary_yield_resource_name: None,
             pvp_damage_min: None,
             pvp_damage_max: None,
-            crafting_cost: None,
-            crafting_output_quantity: None,
-            crafting_time_secs: None,
-            consumable_health_gain: Some(10.0),     
-            consumable_hunger_satiated: Some(20.0), 
-            consumable_thirst_quenched: Some(2.0),  
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Wood".to_string(), quantity: 50 },
+                CostIngredient { item_name: "Metal Fragments".to_string(), quantity: 25 },
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(10),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
             consumable_stamina_gain: None,
             consumable_duration_secs: None,
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
-            cook_time_secs: Some(30.0), 
-            cooked_item_def_name: Some("Burnt Mushroom".to_string()),
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
-            respawn_time_seconds: Some(240),
+            respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 1.5,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
-            name: "Burnt Mushroom".to_string(),
-            description: "Oops, cooked it too long. Barely edible.".to_string(),
-            category: ItemCategory::Consumable,
-            icon_asset_name: "burnt_mushroom.png".to_string(),
-            is_stackable: true,
-            stack_size: 20,
+            name: "Recycler".to_string(),
+            description: "A grinding machine that breaks crafted items back into some of their materials.".to_string(),
+            category: ItemCategory::Placeable,
+            icon_asset_name: "recycler.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
             is_equippable: false,
             equipment_slot_type: None,
             fuel_burn_duration_secs: None,
@@ -1375,32 +1916,45 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             secondary_yield_resource_name: None,
             pvp_damage_min: None,
             pvp_damage_max: None,
-            crafting_cost: None,
-            crafting_output_quantity: None,
-            crafting_time_secs: None,
-            consumable_health_gain: Some(-2.0),    
-            consumable_hunger_satiated: Some(2.0),  
-            consumable_thirst_quenched: Some(-10.0),
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Wood".to_string(), quantity: 300 },
+                CostIngredient { item_name: "Metal Fragments".to_string(), quantity: 150 },
+                CostIngredient { item_name: "Stone".to_string(), quantity: 100 },
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(30),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
             consumable_stamina_gain: None,
             consumable_duration_secs: None,
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
-            respawn_time_seconds: Some(60),
+            respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 20.0,
+            rarity: ItemRarity::Rare,
         },
         ItemDefinition {
             id: 0,
-            name: "Corn".to_string(),
-            description: "Raw corn. A bit tough and not very satisfying.".to_string(),
-            category: ItemCategory::Consumable,
-            icon_asset_name: "corn.png".to_string(),
-            is_stackable: true,
-            stack_size: 20,
+            name: "Flame Turret".to_string(),
+            description: "Burns fuel to set fire to anyone outside your team who comes too close.".to_string(),
+            category: ItemCategory::Placeable,
+            icon_asset_name: "flame_turret.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
             is_equippable: false,
             equipment_slot_type: None,
             fuel_burn_duration_secs: None,
@@ -1418,32 +1972,46 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             secondary_yield_resource_name: None,
             pvp_damage_min: None,
             pvp_damage_max: None,
-            crafting_cost: None,
-            crafting_output_quantity: None,
-            crafting_time_secs: None,
-            consumable_health_gain: Some(2.0),
-            consumable_hunger_satiated: Some(10.0),
-            consumable_thirst_quenched: Some(5.0), 
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Metal Fragments".to_string(), quantity: 200 },
+                CostIngredient { item_name: "Wood".to_string(), quantity: 100 },
+                CostIngredient { item_name: "Stone".to_string(), quantity: 100 },
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(30),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
             consumable_stamina_gain: None,
             consumable_duration_secs: None,
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
-            cook_time_secs: Some(25.0),
-            cooked_item_def_name: Some("Cooked Corn".to_string()),
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
-            respawn_time_seconds: Some(180),
+            respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 20.0,
+            rarity: ItemRarity::Rare,
         },
+
         ItemDefinition {
             id: 0,
-            name: "Cooked Corn".to_string(),
-            description: "Sweet and satisfying. A good source of energy.".to_string(),
+            name: "Wild Berries".to_string(),
+            description: "A handful of tart wild berries. Picked from berry bushes, a few at a time.".to_string(),
             category: ItemCategory::Consumable,
-            icon_asset_name: "cooked_corn.png".to_string(),
+            icon_asset_name: "wild_berries.png".to_string(),
             is_stackable: true,
-            stack_size: 20,
+            stack_size: 30,
             is_equippable: false,
             equipment_slot_type: None,
             fuel_burn_duration_secs: None,
@@ -1464,29 +2032,38 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             crafting_cost: None,
             crafting_output_quantity: None,
             crafting_time_secs: None,
-            consumable_health_gain: Some(15.0),
-            consumable_hunger_satiated: Some(40.0), 
-            consumable_thirst_quenched: Some(10.0), 
-            consumable_stamina_gain: None,          
+            consumable_health_gain: Some(2.0),
+            consumable_hunger_satiated: Some(6.0),
+            consumable_thirst_quenched: Some(3.0),
+            consumable_stamina_gain: None,
             consumable_duration_secs: None,
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
-            cook_time_secs: Some(35.0), 
-            cooked_item_def_name: Some("Burnt Corn".to_string()),
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
-            respawn_time_seconds: Some(240),
+            respawn_time_seconds: Some(120),
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: Some(FoodGroup::Vegetable),
+            is_cooked_food: false,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 0.05,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
-            name: "Burnt Corn".to_string(),
-            description: "Charred and disappointing. Mostly carbon now.".to_string(),
-            category: ItemCategory::Consumable,
-            icon_asset_name: "burnt_corn.png".to_string(),
+            name: "Scrap".to_string(),
+            description: "Salvaged bits and pieces. Spent at a Research Table to learn how to craft an item.".to_string(),
+            category: ItemCategory::Material,
+            icon_asset_name: "scrap.png".to_string(),
             is_stackable: true,
-            stack_size: 20,
+            stack_size: 1000,
             is_equippable: false,
             equipment_slot_type: None,
             fuel_burn_duration_secs: None,
@@ -1507,29 +2084,38 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             crafting_cost: None,
             crafting_output_quantity: None,
             crafting_time_secs: None,
-            consumable_health_gain: Some(-5.0),
-            consumable_hunger_satiated: Some(5.0),
-            consumable_thirst_quenched: Some(-15.0),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
             consumable_stamina_gain: None,
             consumable_duration_secs: None,
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
-            respawn_time_seconds: Some(60),
+            respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.05,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
-            name: "Pumpkin".to_string(),
-            description: "A large, raw pumpkin. Can be cooked.".to_string(),
-            category: ItemCategory::Consumable, 
-            icon_asset_name: "pumpkin.png".to_string(), 
+            name: "Blueprint".to_string(),
+            description: "A worn set of crafting instructions. Use it to learn a random recipe you don't know yet.".to_string(),
+            category: ItemCategory::Material,
+            icon_asset_name: "blueprint.png".to_string(),
             is_stackable: true,
-            stack_size: 5, 
+            stack_size: 10,
             is_equippable: false,
             equipment_slot_type: None,
             fuel_burn_duration_secs: None,
@@ -1550,29 +2136,38 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             crafting_cost: None,
             crafting_output_quantity: None,
             crafting_time_secs: None,
-            consumable_health_gain: Some(5.0),
-            consumable_hunger_satiated: Some(15.0),
-            consumable_thirst_quenched: Some(10.0), 
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
             consumable_stamina_gain: None,
             consumable_duration_secs: None,
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
-            cook_time_secs: Some(60.0), 
-            cooked_item_def_name: Some("Cooked Pumpkin".to_string()),
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
-            respawn_time_seconds: Some(300),
+            respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.01,
+            rarity: ItemRarity::Rare,
         },
         ItemDefinition {
             id: 0,
-            name: "Cooked Pumpkin".to_string(),
-            description: "Soft, sweet, and nutritious cooked pumpkin chunks.".to_string(),
-            category: ItemCategory::Consumable,
-            icon_asset_name: "cooked_pumpkin.png".to_string(), 
-            is_stackable: true,
-            stack_size: 10, 
+            name: "Research Table".to_string(),
+            description: "A workspace for taking items apart to learn how they are made. Costs Scrap to use.".to_string(),
+            category: ItemCategory::Placeable,
+            icon_asset_name: "research_table.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
             is_equippable: false,
             equipment_slot_type: None,
             fuel_burn_duration_secs: None,
@@ -1590,32 +2185,1428 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             secondary_yield_resource_name: None,
             pvp_damage_min: None,
             pvp_damage_max: None,
-            crafting_cost: None,
-            crafting_output_quantity: None,
-            crafting_time_secs: None,
-            consumable_health_gain: Some(20.0),
-            consumable_hunger_satiated: Some(50.0), 
-            consumable_thirst_quenched: Some(15.0), 
-            consumable_stamina_gain: Some(5.0),    
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Wood".to_string(), quantity: 200 },
+                CostIngredient { item_name: "Stone".to_string(), quantity: 50 },
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(15),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
             consumable_duration_secs: None,
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
-            cook_time_secs: Some(45.0), 
-            cooked_item_def_name: Some("Burnt Pumpkin".to_string()),
-            damage_resistance: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 10.0,
+            rarity: ItemRarity::Uncommon,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Vending Machine".to_string(),
+            description: "Sells your goods while you're away. Stock it and list what you want in return.".to_string(),
+            category: ItemCategory::Placeable,
+            icon_asset_name: "vending_machine.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Wood".to_string(), quantity: 150 },
+                CostIngredient { item_name: "Metal Fragments".to_string(), quantity: 100 },
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(30),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 15.0,
+            rarity: ItemRarity::Uncommon,
+        },
+        // --- BUILDING ---
+        ItemDefinition {
+            id: 0,
+            name: "Wooden Foundation".to_string(),
+            description: "A sturdy wooden floor to build on.".to_string(),
+            category: ItemCategory::Placeable,
+            icon_asset_name: "wooden_foundation.png".to_string(),
+            is_stackable: true,
+            stack_size: 10,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Wood".to_string(), quantity: 200 }
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(10),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(600),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 10.0,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Wooden Wall".to_string(),
+            description: "A solid wooden wall. Blocks movement.".to_string(),
+            category: ItemCategory::Placeable,
+            icon_asset_name: "wooden_wall.png".to_string(),
+            is_stackable: true,
+            stack_size: 10,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Wood".to_string(), quantity: 200 }
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(10),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(600),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 8.0,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Wooden Doorway".to_string(),
+            description: "A wooden wall with an opening for a door.".to_string(),
+            category: ItemCategory::Placeable,
+            icon_asset_name: "wooden_doorway.png".to_string(),
+            is_stackable: true,
+            stack_size: 10,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Wood".to_string(), quantity: 150 }
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(10),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(600),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 8.0,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Wooden Door".to_string(),
+            description: "A hinged wooden door. Place it in a doorway.".to_string(),
+            category: ItemCategory::Placeable,
+            icon_asset_name: "wooden_door.png".to_string(),
+            is_stackable: true,
+            stack_size: 5,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Wood".to_string(), quantity: 300 }
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(15),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(600),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 5.0,
+            rarity: ItemRarity::Common,
+        },
+
+        // --- ARMOR (CLOTH SET) ---
+        ItemDefinition {
+            id: 0,
+            name: "Cloth Hood".to_string(),
+            description: "Basic head covering.".to_string(),
+            category: ItemCategory::Armor,
+            icon_asset_name: "cloth_hood.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
+            is_equippable: true,
+            equipment_slot_type: Some(EquipmentSlotType::Head),
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Cloth".to_string(), quantity: 20 },
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(10),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: Some(0.01),
+            warmth_bonus: Some(0.2),
+            respawn_time_seconds: Some(420),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: Some(ARMOR_MAX_DURABILITY),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.3,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Cloth Shirt".to_string(),
+            description: "Simple protection for the torso.".to_string(),
+            category: ItemCategory::Armor,
+            icon_asset_name: "cloth_shirt.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
+            is_equippable: true,
+            equipment_slot_type: Some(EquipmentSlotType::Chest),
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Cloth".to_string(), quantity: 40 },
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(15),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: Some(0.01),
+            warmth_bonus: Some(0.2),
+            respawn_time_seconds: Some(420),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: Some(ARMOR_MAX_DURABILITY),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.5,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Cloth Pants".to_string(),
+            description: "Simple protection for the legs.".to_string(),
+            category: ItemCategory::Armor,
+            icon_asset_name: "cloth_pants.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
+            is_equippable: true,
+            equipment_slot_type: Some(EquipmentSlotType::Legs),
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Cloth".to_string(), quantity: 30 },
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(15),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: Some(0.01),
+            warmth_bonus: Some(0.2),
+            respawn_time_seconds: Some(420),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: Some(ARMOR_MAX_DURABILITY),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.5,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Cloth Gloves".to_string(),
+            description: "Basic hand coverings.".to_string(),
+            category: ItemCategory::Armor,
+            icon_asset_name: "cloth_gloves.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
+            is_equippable: true,
+            equipment_slot_type: Some(EquipmentSlotType::Hands),
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Cloth".to_string(), quantity: 15 },
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(5),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: Some(0.01),
+            warmth_bonus: Some(0.2),
+            respawn_time_seconds: Some(420),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: Some(ARMOR_MAX_DURABILITY),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.2,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Cloth Boots".to_string(),
+            description: "Simple footwear.".to_string(),
+            category: ItemCategory::Armor,
+            icon_asset_name: "cloth_boots.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
+            is_equippable: true,
+            equipment_slot_type: Some(EquipmentSlotType::Feet),
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Cloth".to_string(), quantity: 15 },
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(5),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: Some(0.01),
+            warmth_bonus: Some(0.2),
+            respawn_time_seconds: Some(420),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: Some(ARMOR_MAX_DURABILITY),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.5,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Cloth Cape".to_string(),
+            description: "A simple cape made of cloth.".to_string(),
+            category: ItemCategory::Armor,
+            icon_asset_name: "burlap_cape.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
+            is_equippable: true,
+            equipment_slot_type: Some(EquipmentSlotType::Back),
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Cloth".to_string(), quantity: 30 },
+                CostIngredient { item_name: "Plant Fiber".to_string(), quantity: 10 },
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(20),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: Some(0.01),
+            warmth_bonus: Some(0.1),
+            respawn_time_seconds: Some(420),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: Some(ARMOR_MAX_DURABILITY),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.5,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Hazmat Suit".to_string(),
+            description: "A sealed suit that keeps out radiation. Offers little protection otherwise.".to_string(),
+            category: ItemCategory::Armor,
+            icon_asset_name: "hazmat_suit.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
+            is_equippable: true,
+            equipment_slot_type: Some(EquipmentSlotType::Chest),
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Cloth".to_string(), quantity: 60 },
+                CostIngredient { item_name: "Scrap".to_string(), quantity: 15 },
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(30),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: Some(0.02),
+            warmth_bonus: Some(0.1),
+            respawn_time_seconds: Some(420),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: Some(ARMOR_MAX_DURABILITY),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 3.0,
+            rarity: ItemRarity::Rare,
+        },
+        
+        // --- CONSUMABLES (Rebalanced and New) ---
+        ItemDefinition {
+            id: 0,
+            name: "Mushroom".to_string(),
+            description: "A raw mushroom. Edible, but provides little sustenance, and makes you thirsty.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "mushroom.png".to_string(),
+            is_stackable: true,
+            stack_size: 20,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: Some(3.0),      
+            consumable_hunger_satiated: Some(5.0),  
+            consumable_thirst_quenched: Some(-2.0), 
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: Some(20.0), 
+            cooked_item_def_name: Some("Cooked Mushroom".to_string()),
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(180),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: Some(FoodGroup::Vegetable),
+            is_cooked_food: false,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 0.1,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Cooked Mushroom".to_string(),
+            description: "A tasty cooked mushroom. More filling.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "cooked_mushroom.png".to_string(),
+            is_stackable: true,
+            stack_size: 20,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: Some(10.0),     
+            consumable_hunger_satiated: Some(20.0), 
+            consumable_thirst_quenched: Some(2.0),  
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: Some(30.0), 
+            cooked_item_def_name: Some("Burnt Mushroom".to_string()),
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(240),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: Some(FoodGroup::Vegetable),
+            is_cooked_food: true,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 0.1,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Burnt Mushroom".to_string(),
+            description: "Oops, cooked it too long. Barely edible.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "burnt_mushroom.png".to_string(),
+            is_stackable: true,
+            stack_size: 20,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: Some(-2.0),    
+            consumable_hunger_satiated: Some(2.0),  
+            consumable_thirst_quenched: Some(-10.0),
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(60),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: true,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 0.1,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Corn".to_string(),
+            description: "Raw corn. A bit tough and not very satisfying.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "corn.png".to_string(),
+            is_stackable: true,
+            stack_size: 20,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: Some(2.0),
+            consumable_hunger_satiated: Some(10.0),
+            consumable_thirst_quenched: Some(5.0), 
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: Some(25.0),
+            cooked_item_def_name: Some("Cooked Corn".to_string()),
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(180),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: Some(FoodGroup::Vegetable),
+            is_cooked_food: false,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 0.3,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Cactus Flesh".to_string(),
+            description: "Watery flesh cut from a desert cactus. Not filling, but it quenches thirst.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "cactus_flesh.png".to_string(),
+            is_stackable: true,
+            stack_size: 20,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: None,
+            consumable_hunger_satiated: Some(3.0),
+            consumable_thirst_quenched: Some(15.0),
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: Some(FoodGroup::Vegetable),
+            is_cooked_food: false,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 0.3,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Cooked Corn".to_string(),
+            description: "Sweet and satisfying. A good source of energy.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "cooked_corn.png".to_string(),
+            is_stackable: true,
+            stack_size: 20,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: Some(15.0),
+            consumable_hunger_satiated: Some(40.0), 
+            consumable_thirst_quenched: Some(10.0), 
+            consumable_stamina_gain: None,          
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: Some(35.0), 
+            cooked_item_def_name: Some("Burnt Corn".to_string()),
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(240),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: Some(FoodGroup::Vegetable),
+            is_cooked_food: true,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 0.3,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Burnt Corn".to_string(),
+            description: "Charred and disappointing. Mostly carbon now.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "burnt_corn.png".to_string(),
+            is_stackable: true,
+            stack_size: 20,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: Some(-5.0),
+            consumable_hunger_satiated: Some(5.0),
+            consumable_thirst_quenched: Some(-15.0),
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(60),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: true,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 0.3,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Pumpkin".to_string(),
+            description: "A large, raw pumpkin. Can be cooked.".to_string(),
+            category: ItemCategory::Consumable, 
+            icon_asset_name: "pumpkin.png".to_string(), 
+            is_stackable: true,
+            stack_size: 5, 
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: Some(5.0),
+            consumable_hunger_satiated: Some(15.0),
+            consumable_thirst_quenched: Some(10.0), 
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: Some(60.0), 
+            cooked_item_def_name: Some("Cooked Pumpkin".to_string()),
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(300),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: Some(FoodGroup::Vegetable),
+            is_cooked_food: false,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 2.0,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Cooked Pumpkin".to_string(),
+            description: "Soft, sweet, and nutritious cooked pumpkin chunks.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "cooked_pumpkin.png".to_string(), 
+            is_stackable: true,
+            stack_size: 10, 
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: Some(20.0),
+            consumable_hunger_satiated: Some(50.0), 
+            consumable_thirst_quenched: Some(15.0), 
+            consumable_stamina_gain: Some(5.0),    
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: Some(45.0), 
+            cooked_item_def_name: Some("Burnt Pumpkin".to_string()),
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(360),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: Some(FoodGroup::Vegetable),
+            is_cooked_food: true,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 1.5,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Burnt Pumpkin".to_string(),
+            description: "A blackened, mushy mess. Not recommended.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "burnt_pumpkin.png".to_string(), 
+            is_stackable: true,
+            stack_size: 10,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: Some(-10.0),
+            consumable_hunger_satiated: Some(5.0),
+            consumable_thirst_quenched: Some(-5.0),
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: Some(60),
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: true,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 1.5,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Raw Meat".to_string(),
+            description: "Raw animal meat. Edible in a pinch, but much better cooked.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "raw_meat.png".to_string(),
+            is_stackable: true,
+            stack_size: 20,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: Some(-2.0),
+            consumable_hunger_satiated: Some(10.0),
+            consumable_thirst_quenched: Some(-5.0),
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: Some(30.0),
+            cooked_item_def_name: Some("Cooked Meat".to_string()),
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: Some(FoodGroup::Protein),
+            is_cooked_food: false,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 0.3,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Cooked Meat".to_string(),
+            description: "Well-cooked meat. Very filling.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "cooked_meat.png".to_string(),
+            is_stackable: true,
+            stack_size: 20,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: Some(15.0),
+            consumable_hunger_satiated: Some(50.0),
+            consumable_thirst_quenched: Some(-5.0),
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: Some(40.0),
+            cooked_item_def_name: Some("Burnt Meat".to_string()),
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: Some(FoodGroup::Protein),
+            is_cooked_food: true,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 0.3,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Burnt Meat".to_string(),
+            description: "Charred and dry. Barely edible.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "burnt_meat.png".to_string(),
+            is_stackable: true,
+            stack_size: 20,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: Some(-5.0),
+            consumable_hunger_satiated: Some(5.0),
+            consumable_thirst_quenched: Some(-15.0),
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: true,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 0.3,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Raw Fish".to_string(),
+            description: "A freshly caught fish. Better cooked.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "raw_fish.png".to_string(),
+            is_stackable: true,
+            stack_size: 20,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: Some(-2.0),
+            consumable_hunger_satiated: Some(8.0),
+            consumable_thirst_quenched: Some(-2.0),
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: Some(25.0),
+            cooked_item_def_name: Some("Cooked Fish".to_string()),
+            damage_resistance: None,
             warmth_bonus: None,
-            respawn_time_seconds: Some(360),
+            respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: Some(FoodGroup::Protein),
+            is_cooked_food: false,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 0.4,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
-            name: "Burnt Pumpkin".to_string(),
-            description: "A blackened, mushy mess. Not recommended.".to_string(),
+            name: "Cooked Fish".to_string(),
+            description: "Flaky grilled fish. Light but filling.".to_string(),
             category: ItemCategory::Consumable,
-            icon_asset_name: "burnt_pumpkin.png".to_string(), 
+            icon_asset_name: "cooked_fish.png".to_string(),
             is_stackable: true,
-            stack_size: 10,
+            stack_size: 20,
             is_equippable: false,
             equipment_slot_type: None,
             fuel_burn_duration_secs: None,
@@ -1636,20 +3627,81 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             crafting_cost: None,
             crafting_output_quantity: None,
             crafting_time_secs: None,
-            consumable_health_gain: Some(-10.0),
-            consumable_hunger_satiated: Some(5.0),
-            consumable_thirst_quenched: Some(-5.0),
+            consumable_health_gain: Some(10.0),
+            consumable_hunger_satiated: Some(35.0),
+            consumable_thirst_quenched: Some(-2.0),
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: Some(35.0),
+            cooked_item_def_name: Some("Burnt Fish".to_string()),
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: Some(FoodGroup::Protein),
+            is_cooked_food: true,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 0.4,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Burnt Fish".to_string(),
+            description: "Blackened to a crisp. Barely edible.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "burnt_fish.png".to_string(),
+            is_stackable: true,
+            stack_size: 20,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: Some(-5.0),
+            consumable_hunger_satiated: Some(4.0),
+            consumable_thirst_quenched: Some(-10.0),
             consumable_stamina_gain: None,
             consumable_duration_secs: None,
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
-            respawn_time_seconds: Some(60),
+            respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: true,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Food),
+            weight: 0.4,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
@@ -1687,12 +3739,287 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: Some(300),
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: Some(ConsumableCooldownCategory::Medical),
+            weight: 0.05,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Note".to_string(),
+            description: "A scrap of woven fiber to write on. Whoever holds it can read it.".to_string(),
+            category: ItemCategory::Tool,
+            icon_asset_name: "note.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: Some(5.0),
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: Some(vec![CostIngredient { item_name: "Plant Fiber".to_string(), quantity: 5 }]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(3),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.01,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Water Jug".to_string(),
+            description: "An empty jug. Fill it at a lake or river to carry water with you.".to_string(),
+            category: ItemCategory::Tool,
+            icon_asset_name: "water_jug.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Wood".to_string(), quantity: 20 },
+                CostIngredient { item_name: "Animal Fat".to_string(), quantity: 2 },
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(10),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None, // Per drink
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None, // Charges hold the drinks left (see water.rs)
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.5,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Dirty Water Jug".to_string(),
+            description: "A jug of unboiled lake water. Drinkable, but it may make you sick. Boil it over a campfire first.".to_string(),
+            category: ItemCategory::Tool,
+            icon_asset_name: "water_jug_dirty.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: Some(15.0), // Per drink
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: Some(20.0),
+            cooked_item_def_name: Some("Boiled Water Jug".to_string()),
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None, // Charges hold the drinks left (see water.rs)
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 2.5,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Boiled Water Jug".to_string(),
+            description: "A jug of boiled water, safe to drink.".to_string(),
+            category: ItemCategory::Tool,
+            icon_asset_name: "water_jug_boiled.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
+            is_equippable: false,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: None,
+            crafting_output_quantity: None,
+            crafting_time_secs: None,
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: Some(15.0), // Per drink
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None, // Charges hold the drinks left (see water.rs)
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 2.5,
+            rarity: ItemRarity::Common,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Fishing Rod".to_string(),
+            description: "A simple rod and line. Cast into water and reel in when something bites.".to_string(),
+            category: ItemCategory::Tool,
+            icon_asset_name: "fishing_rod.png".to_string(),
+            is_stackable: false,
+            stack_size: 1,
+            is_equippable: true,
+            equipment_slot_type: None,
+            fuel_burn_duration_secs: None,
+            primary_target_damage_min: None,
+            primary_target_damage_max: None,
+            primary_target_yield_min: None,
+            primary_target_yield_max: None,
+            primary_target_type: None,
+            primary_yield_resource_name: None,
+            secondary_target_damage_min: None,
+            secondary_target_damage_max: None,
+            secondary_target_yield_min: None,
+            secondary_target_yield_max: None,
+            secondary_target_type: None,
+            secondary_yield_resource_name: None,
+            pvp_damage_min: None,
+            pvp_damage_max: None,
+            crafting_cost: Some(vec![
+                CostIngredient { item_name: "Wood".to_string(), quantity: 100 },
+                CostIngredient { item_name: "Cloth".to_string(), quantity: 10 },
+            ]),
+            crafting_output_quantity: Some(1),
+            crafting_time_secs: Some(15),
+            consumable_health_gain: None,
+            consumable_hunger_satiated: None,
+            consumable_thirst_quenched: None,
+            consumable_stamina_gain: None,
+            consumable_duration_secs: None,
+            bleed_damage_per_tick: None,
+            bleed_duration_seconds: None,
+            bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
+            cook_time_secs: None,
+            cooked_item_def_name: None,
+            damage_resistance: None,
+            warmth_bonus: None,
+            respawn_time_seconds: None,
+            attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: Some(100.0),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 1.0,
+            rarity: ItemRarity::Uncommon,
         },
         ItemDefinition {
             id: 0,
@@ -1733,12 +4060,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: None,
             attack_interval_secs: Some(1.0),
+            stamina_cost_per_swing: None,
+            max_durability: Some(150.0),
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 1.0,
+            rarity: ItemRarity::Uncommon,
         },
         ItemDefinition {
             id: 0,
@@ -1779,12 +4115,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: Some(0.5),
             bleed_duration_seconds: Some(4.0),
             bleed_tick_interval_seconds: Some(1.0),
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.05,
+            rarity: ItemRarity::Common,
         },
         ItemDefinition {
             id: 0,
@@ -1825,12 +4170,21 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             bleed_damage_per_tick: None,
             bleed_duration_seconds: None,
             bleed_tick_interval_seconds: None,
+            slow_percent: None,
+            slow_duration_seconds: None,
             cook_time_secs: None,
             cooked_item_def_name: None,
             damage_resistance: None,
             warmth_bonus: None,
             respawn_time_seconds: None,
             attack_interval_secs: None,
+            stamina_cost_per_swing: None,
+            max_durability: None,
+            food_group: None,
+            is_cooked_food: false,
+            consumable_cooldown_category: None,
+            weight: 0.05,
+            rarity: ItemRarity::Uncommon,
         },
     ];
 
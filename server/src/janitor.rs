@@ -0,0 +1,202 @@
+/******************************************************************************
+ *                                                                            *
+ * Database janitor. A slow maintenance schedule that looks for rows left     *
+ * behind by bugs, crashes or interrupted reducers and repairs or removes     *
+ * them: inventory items with an Unknown location, container slots pointing   *
+ * at item instances that no longer exist, processing schedules of deleted    *
+ * campfires and furnaces, text of deleted notes, cooking credit for items    *
+ * that left their campfire or furnace, and viewport rows of clients that     *
+ * disconnected long ago without a clean disconnect. Each run logs a summary  *
+ * of what it fixed so gradual bloat shows up in the logs.                    *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{ReducerContext, Table, TimeDuration, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::models::ItemLocation;
+use crate::inventory_management::ItemContainer;
+use crate::items::inventory_item as InventoryItemTableTrait;
+use crate::campfire::campfire as CampfireTableTrait;
+use crate::campfire::campfire_processing_schedule as CampfireProcessingScheduleTableTrait;
+use crate::furnace::furnace as FurnaceTableTrait;
+use crate::furnace::furnace_processing_schedule as FurnaceProcessingScheduleTableTrait;
+use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
+use crate::large_storage_chest::large_storage_chest as LargeStorageChestTableTrait;
+use crate::player_corpse::player_corpse as PlayerCorpseTableTrait;
+use crate::stash::stash as StashTableTrait;
+use crate::recycler::recycler as RecyclerTableTrait;
+use crate::flame_turret::flame_turret as FlameTurretTableTrait;
+use crate::active_connection as ActiveConnectionTableTrait;
+use crate::client_viewport as ClientViewportTableTrait;
+use crate::janitor::janitor_schedule as JanitorScheduleTableTrait;
+
+// --- Constants ---
+const JANITOR_INTERVAL_SECS: u64 = 600;
+/// Viewports of clients without an active connection are removed once they are this old.
+const STALE_VIEWPORT_SECS: u64 = 1800;
+
+/// --- Janitor Schedule ---
+#[spacetimedb::table(name = janitor_schedule, scheduled(run_janitor))]
+#[derive(Clone, Debug)]
+pub struct JanitorSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/// Counts of what one janitor run repaired or removed.
+#[derive(Default)]
+struct JanitorSummary {
+    unknown_items: u32,
+    dangling_slots: u32,
+    orphaned_schedules: u32,
+    stale_viewports: u32,
+    orphaned_notes: u32,
+    stale_cook_contributors: u32,
+}
+
+/******************************************************************************
+ *                           SCHEDULED REDUCERS                               *
+ ******************************************************************************/
+
+/// Starts the janitor schedule if it isn't already scheduled. Called from `init_module`.
+pub fn init_janitor_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.janitor_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting janitor schedule (every {}s).", JANITOR_INTERVAL_SECS);
+        let interval = Duration::from_secs(JANITOR_INTERVAL_SECS);
+        schedule_table.try_insert(JanitorSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert janitor schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: repairs or removes orphaned rows and logs what it did.
+#[spacetimedb::reducer]
+pub fn run_janitor(ctx: &ReducerContext, _schedule: JanitorSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("run_janitor may only be called by the scheduler.".to_string());
+    }
+
+    let mut summary = JanitorSummary::default();
+    remove_unknown_items(ctx, &mut summary);
+    clear_dangling_container_slots(ctx, &mut summary);
+    remove_orphaned_schedules(ctx, &mut summary);
+    remove_stale_viewports(ctx, &mut summary);
+    summary.orphaned_notes = crate::note::remove_orphaned_notes(ctx);
+    summary.stale_cook_contributors = crate::cooking::remove_stale_cook_contributors(ctx);
+
+    if summary.unknown_items + summary.dangling_slots + summary.orphaned_schedules + summary.stale_viewports + summary.orphaned_notes + summary.stale_cook_contributors > 0 {
+        log::info!("[Janitor] Removed {} unknown-location items, cleared {} dangling container slots, removed {} orphaned schedules, {} stale viewports, {} orphaned notes and {} stale cooking credits.",
+            summary.unknown_items, summary.dangling_slots, summary.orphaned_schedules, summary.stale_viewports, summary.orphaned_notes, summary.stale_cook_contributors);
+    } else {
+        log::debug!("[Janitor] Nothing to clean up.");
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Deletes inventory items whose location was never set or was lost.
+fn remove_unknown_items(ctx: &ReducerContext, summary: &mut JanitorSummary) {
+    let items = ctx.db.inventory_item();
+    let lost: Vec<u64> = items.iter()
+        .filter(|item| item.location == ItemLocation::Unknown)
+        .map(|item| item.instance_id)
+        .collect();
+    for instance_id in lost {
+        log::warn!("[Janitor] Deleting item instance {} with an unknown location.", instance_id);
+        items.instance_id().delete(instance_id);
+        summary.unknown_items += 1;
+    }
+}
+
+/// Empties container slots whose item instance has been deleted.
+fn clear_dangling_container_slots(ctx: &ReducerContext, summary: &mut JanitorSummary) {
+    let existing_items: HashSet<u64> = ctx.db.inventory_item().iter().map(|item| item.instance_id).collect();
+
+    // Same repair for every container table: clear the bad slots, then write the row back
+    macro_rules! repair_container_table {
+        ($table:expr) => {
+            let rows: Vec<_> = $table.iter().collect();
+            for mut container in rows {
+                let cleared = clear_dangling_slots(&mut container, &existing_items);
+                if cleared > 0 {
+                    log::warn!("[Janitor] Cleared {} dangling slots in {:?} {}.", cleared, container.get_container_type(), container.get_container_id());
+                    summary.dangling_slots += cleared;
+                    $table.id().update(container);
+                }
+            }
+        };
+    }
+    repair_container_table!(ctx.db.campfire());
+    repair_container_table!(ctx.db.furnace());
+    repair_container_table!(ctx.db.wooden_storage_box());
+    repair_container_table!(ctx.db.large_storage_chest());
+    repair_container_table!(ctx.db.player_corpse());
+    repair_container_table!(ctx.db.stash());
+    repair_container_table!(ctx.db.recycler());
+    repair_container_table!(ctx.db.flame_turret());
+}
+
+/// Clears every slot of `container` that references an instance not in `existing_items`.
+/// Returns how many slots were cleared.
+fn clear_dangling_slots<C: ItemContainer>(container: &mut C, existing_items: &HashSet<u64>) -> u32 {
+    let mut cleared = 0;
+    for slot_index in 0..container.num_slots() as u8 {
+        if let Some(instance_id) = container.get_slot_instance_id(slot_index) {
+            if !existing_items.contains(&instance_id) {
+                container.set_slot(slot_index, None, None);
+                cleared += 1;
+            }
+        }
+    }
+    cleared
+}
+
+/// Deletes processing schedules whose campfire or furnace no longer exists.
+fn remove_orphaned_schedules(ctx: &ReducerContext, summary: &mut JanitorSummary) {
+    let campfire_schedules = ctx.db.campfire_processing_schedule();
+    let orphaned: Vec<u64> = campfire_schedules.iter()
+        .filter(|s| ctx.db.campfire().id().find(s.campfire_id as u32).is_none())
+        .map(|s| s.campfire_id)
+        .collect();
+    for campfire_id in orphaned {
+        campfire_schedules.campfire_id().delete(campfire_id);
+        summary.orphaned_schedules += 1;
+    }
+
+    let furnace_schedules = ctx.db.furnace_processing_schedule();
+    let orphaned: Vec<u64> = furnace_schedules.iter()
+        .filter(|s| ctx.db.furnace().id().find(s.furnace_id as u32).is_none())
+        .map(|s| s.furnace_id)
+        .collect();
+    for furnace_id in orphaned {
+        furnace_schedules.furnace_id().delete(furnace_id);
+        summary.orphaned_schedules += 1;
+    }
+}
+
+/// Deletes viewports (and their visible chunks) of clients that have been disconnected
+/// for a long time. A clean disconnect removes them already; this catches the rest.
+fn remove_stale_viewports(ctx: &ReducerContext, summary: &mut JanitorSummary) {
+    let viewports = ctx.db.client_viewport();
+    let stale_before = ctx.timestamp - TimeDuration::from(Duration::from_secs(STALE_VIEWPORT_SECS));
+    let stale: Vec<_> = viewports.iter()
+        .filter(|v| v.last_update < stale_before && ctx.db.active_connection().identity().find(v.client_identity).is_none())
+        .map(|v| v.client_identity)
+        .collect();
+    for client_id in stale {
+        viewports.client_identity().delete(client_id);
+        crate::interest_management::clear_visible_chunks(ctx, client_id);
+        summary.stale_viewports += 1;
+    }
+}
@@ -0,0 +1,221 @@
+/******************************************************************************
+ *                                                                            *
+ * Kill feed and lifetime player statistics. Every death is written to the    *
+ * public KillFeedEntry table with its DeathCause: player kills by combat.rs  *
+ * damage_player, everything else (animals, starvation, cold, burns...) by    *
+ * `record_death`. A scheduled cleanup prunes entries once they are old or    *
+ * the feed is too long. Each player's kills, deaths, harvested resources and *
+ * playtime are totalled in PlayerStatsSummary through the helpers at the     *
+ * bottom, which also unlock stat milestone cosmetics (cosmetics.rs).         *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+
+use crate::models::DeathCause;
+use crate::player as PlayerTableTrait;
+use crate::kill_feed::kill_feed as KillFeedTableTrait;
+use crate::kill_feed::player_stats_summary as PlayerStatsSummaryTableTrait;
+use crate::kill_feed::kill_feed_cleanup_schedule as KillFeedCleanupScheduleTableTrait;
+
+// --- Constants ---
+const KILL_FEED_CLEANUP_INTERVAL_SECS: u64 = 15;
+/// Feed entries older than this are removed.
+const KILL_FEED_ENTRY_LIFETIME_SECS: i64 = 300;
+/// At most this many entries are kept, newest first.
+const MAX_KILL_FEED_ENTRIES: usize = 50;
+
+/// --- Kill Feed Entry ---
+/// A player's death. Names are copied so the entry reads correctly after renames.
+/// `attacker_id` is only set for player kills; other deaths leave `attacker_name` empty.
+#[spacetimedb::table(name = kill_feed, public)]
+#[derive(Clone, Debug)]
+pub struct KillFeedEntry {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub attacker_id: Option<Identity>,
+    pub attacker_name: String,
+    pub victim_id: Identity,
+    pub victim_name: String,
+    pub death_cause: DeathCause,
+    pub weapon_name: String, // Weapon for player kills, otherwise the source (e.g. "Wolf"), if any
+    pub distance: f32, // Pixels between attacker and victim at the time of the kill
+    pub killed_at: Timestamp,
+}
+
+/// --- Player Stats Summary ---
+/// Lifetime totals for a player.
+#[spacetimedb::table(name = player_stats_summary, public)]
+#[derive(Clone, Debug)]
+pub struct PlayerStatsSummary {
+    #[primary_key]
+    pub player_id: Identity,
+    pub kills: u32,
+    pub deaths: u32,
+    pub resources_harvested: u64,
+    pub playtime_secs: u64, // Completed sessions only
+    pub session_started_at: Option<Timestamp>, // Set while connected
+}
+
+impl PlayerStatsSummary {
+    fn new(player_id: Identity) -> Self {
+        PlayerStatsSummary {
+            player_id,
+            kills: 0,
+            deaths: 0,
+            resources_harvested: 0,
+            playtime_secs: 0,
+            session_started_at: None,
+        }
+    }
+}
+
+/// --- Kill Feed Cleanup Schedule ---
+#[spacetimedb::table(name = kill_feed_cleanup_schedule, scheduled(prune_kill_feed))]
+#[derive(Clone, Debug)]
+pub struct KillFeedCleanupSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/******************************************************************************
+ *                           SCHEDULED REDUCERS                               *
+ ******************************************************************************/
+
+/// Starts the kill feed cleanup schedule if it isn't already scheduled. Called from `init_module`.
+pub fn init_kill_feed_cleanup_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.kill_feed_cleanup_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting kill feed cleanup schedule (every {}s).", KILL_FEED_CLEANUP_INTERVAL_SECS);
+        let interval = Duration::from_secs(KILL_FEED_CLEANUP_INTERVAL_SECS);
+        schedule_table.try_insert(KillFeedCleanupSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert kill feed cleanup schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: removes expired feed entries and trims the feed to `MAX_KILL_FEED_ENTRIES`.
+#[spacetimedb::reducer]
+pub fn prune_kill_feed(ctx: &ReducerContext, _schedule: KillFeedCleanupSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("prune_kill_feed may only be called by the scheduler.".to_string());
+    }
+
+    let feed = ctx.db.kill_feed();
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let max_age_micros = KILL_FEED_ENTRY_LIFETIME_SECS * 1_000_000;
+
+    let mut entries: Vec<KillFeedEntry> = feed.iter().collect();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.killed_at));
+    let mut removed = 0;
+    for (index, entry) in entries.iter().enumerate() {
+        let age_micros = now_micros - entry.killed_at.to_micros_since_unix_epoch();
+        if index >= MAX_KILL_FEED_ENTRIES || age_micros > max_age_micros {
+            feed.id().delete(entry.id);
+            removed += 1;
+        }
+    }
+    if removed > 0 {
+        log::debug!("[KillFeed] Pruned {} entries.", removed);
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Adds a kill to the feed and credits the attacker. The victim's death is counted
+/// separately by `record_death` with `DeathCause::PvP`.
+pub(crate) fn record_kill(ctx: &ReducerContext, attacker_id: Identity, victim_id: Identity, weapon_name: &str, distance: f32) {
+    ctx.db.kill_feed().insert(KillFeedEntry {
+        id: 0, // Auto-incremented
+        attacker_id: Some(attacker_id),
+        attacker_name: player_name(ctx, attacker_id),
+        victim_id,
+        victim_name: player_name(ctx, victim_id),
+        death_cause: DeathCause::PvP,
+        weapon_name: weapon_name.to_string(),
+        distance,
+        killed_at: ctx.timestamp,
+    });
+    credit_kill(ctx, attacker_id);
+}
+
+/// Counts a kill for the attacker without a feed entry (e.g. killing a sleeping player).
+pub(crate) fn credit_kill(ctx: &ReducerContext, attacker_id: Identity) {
+    update_summary(ctx, attacker_id, |s| s.kills += 1);
+}
+
+/// Counts a death for the player, whatever the cause. Deaths other than player kills
+/// (which `record_kill` adds) also get a feed entry naming `source_name`, e.g. the animal.
+pub(crate) fn record_death(ctx: &ReducerContext, player_id: Identity, death_cause: DeathCause, source_name: &str) {
+    if death_cause != DeathCause::PvP {
+        ctx.db.kill_feed().insert(KillFeedEntry {
+            id: 0, // Auto-incremented
+            attacker_id: None,
+            attacker_name: String::new(),
+            victim_id: player_id,
+            victim_name: player_name(ctx, player_id),
+            death_cause,
+            weapon_name: source_name.to_string(),
+            distance: 0.0,
+            killed_at: ctx.timestamp,
+        });
+    }
+    update_summary(ctx, player_id, |s| s.deaths += 1);
+}
+
+/// Adds gathered resources to the player's harvest total.
+pub(crate) fn record_resources_harvested(ctx: &ReducerContext, player_id: Identity, amount: u32) {
+    update_summary(ctx, player_id, |s| s.resources_harvested += amount as u64);
+}
+
+/// Starts timing a play session. Called when the player connects.
+pub(crate) fn start_play_session(ctx: &ReducerContext, player_id: Identity) {
+    update_summary(ctx, player_id, |s| s.session_started_at = Some(ctx.timestamp));
+}
+
+/// Adds the session that ended at `ended_at` to the player's playtime. Called when the
+/// player goes offline, with the time they disconnected so the reconnect grace period
+/// isn't counted.
+pub(crate) fn end_play_session(ctx: &ReducerContext, player_id: Identity, ended_at: Timestamp) {
+    update_summary(ctx, player_id, |s| {
+        if let Some(started_at) = s.session_started_at.take() {
+            let session_micros = ended_at.to_micros_since_unix_epoch() - started_at.to_micros_since_unix_epoch();
+            s.playtime_secs += (session_micros.max(0) / 1_000_000) as u64;
+        }
+    });
+}
+
+/// The player's current username, or "Unknown" if they no longer exist.
+fn player_name(ctx: &ReducerContext, player_id: Identity) -> String {
+    ctx.db.player().identity().find(player_id)
+        .map(|p| p.username)
+        .unwrap_or_else(|| "Unknown".to_string())
+}
+
+/// Applies `change` to the player's summary, creating it on first use.
+fn update_summary(ctx: &ReducerContext, player_id: Identity, change: impl FnOnce(&mut PlayerStatsSummary)) {
+    let summaries = ctx.db.player_stats_summary();
+    match summaries.player_id().find(player_id) {
+        Some(mut summary) => {
+            change(&mut summary);
+            crate::cosmetics::unlock_for_stats(ctx, &summary);
+            summaries.player_id().update(summary);
+        }
+        None => {
+            let mut summary = PlayerStatsSummary::new(player_id);
+            change(&mut summary);
+            crate::cosmetics::unlock_for_stats(ctx, &summary);
+            summaries.insert(summary);
+        }
+    }
+}
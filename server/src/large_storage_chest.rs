@@ -0,0 +1,316 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines the LargeStorageChest entity: a bigger, sturdier storage box.      *
+ * Its slots are stored as a slot array (see `SlotArrayContainer` in          *
+ * inventory_management.rs), so all item movement goes through the generic    *
+ * container handlers without per-slot fields.                                *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+
+// --- Constants ---
+pub(crate) const LARGE_CHEST_COLLISION_RADIUS: f32 = 28.0;
+pub(crate) const LARGE_CHEST_COLLISION_Y_OFFSET: f32 = 10.0;
+const LARGE_CHEST_INTERACTION_DISTANCE_SQUARED: f32 = 80.0 * 80.0;
+pub const NUM_LARGE_CHEST_SLOTS: usize = 36;
+const LARGE_CHEST_INITIAL_HEALTH: f32 = 1200.0;
+const LARGE_CHEST_ITEM_NAME: &str = "Large Storage Chest";
+
+use crate::player as PlayerTableTrait;
+use crate::Player;
+use crate::items::{
+    ItemDefinition,
+    inventory_item as InventoryItemTableTrait,
+    item_definition as ItemDefinitionTableTrait,
+    add_item_to_player_inventory
+};
+use crate::inventory_management::{self, ItemContainer, ContainerItemClearer, ContainerSlot, SlotArrayContainer, empty_container_slots, is_container_empty};
+use crate::large_storage_chest::large_storage_chest as LargeStorageChestTableTrait;
+use crate::environment::calculate_chunk_index;
+use crate::models::{ContainerType, ItemLocation};
+use crate::locks::LockTargetType;
+
+/// --- Large Storage Chest Data Structure ---
+/// A placeable chest with `NUM_LARGE_CHEST_SLOTS` slots held in a single slot array.
+#[spacetimedb::table(name = large_storage_chest, public)]
+#[derive(Clone, Debug)]
+pub struct LargeStorageChest {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+
+    pub pos_x: f32,
+    pub pos_y: f32,
+    #[index(btree)]
+    pub chunk_index: u32,
+
+    pub placed_by: Identity,
+
+    pub slots: Vec<ContainerSlot>,
+
+    pub health: f32,
+    pub max_health: f32,
+    pub is_destroyed: bool,
+    pub destroyed_at: Option<Timestamp>,
+    pub last_hit_time: Option<Timestamp>,
+}
+
+/******************************************************************************
+ *                           REDUCERS (Generic Handlers)                      *
+ ******************************************************************************/
+
+/// Moves an item from the player's inventory/hotbar INTO a chest slot.
+#[spacetimedb::reducer]
+pub fn move_item_to_large_chest(ctx: &ReducerContext, chest_id: u32, target_slot_index: u8, item_instance_id: u64) -> Result<(), String> {
+    let (_player, mut chest) = validate_large_chest_interaction(ctx, chest_id)?;
+    inventory_management::handle_move_to_container_slot(ctx, &mut chest, target_slot_index, item_instance_id)?;
+    ctx.db.large_storage_chest().id().update(chest);
+    Ok(())
+}
+
+/// Moves an item FROM a chest slot INTO the player's inventory/hotbar.
+#[spacetimedb::reducer]
+pub fn move_item_from_large_chest(
+    ctx: &ReducerContext,
+    chest_id: u32,
+    source_slot_index: u8,
+    target_slot_type: String,
+    target_slot_index: u32
+) -> Result<(), String> {
+    let (_player, mut chest) = validate_large_chest_interaction(ctx, chest_id)?;
+    inventory_management::handle_move_from_container_slot(ctx, &mut chest, source_slot_index, target_slot_type, target_slot_index)?;
+    ctx.db.large_storage_chest().id().update(chest);
+    Ok(())
+}
+
+/// Moves an item BETWEEN two slots within the same chest.
+#[spacetimedb::reducer]
+pub fn move_item_within_large_chest(ctx: &ReducerContext, chest_id: u32, source_slot_index: u8, target_slot_index: u8) -> Result<(), String> {
+    let (_player, mut chest) = validate_large_chest_interaction(ctx, chest_id)?;
+    inventory_management::handle_move_within_container(ctx, &mut chest, source_slot_index, target_slot_index)?;
+    ctx.db.large_storage_chest().id().update(chest);
+    Ok(())
+}
+
+/// Splits a stack from player inventory/hotbar into a specific chest slot.
+#[spacetimedb::reducer]
+pub fn split_stack_into_large_chest(
+    ctx: &ReducerContext,
+    chest_id: u32,
+    target_slot_index: u8,
+    source_item_instance_id: u64,
+    quantity_to_split: u32,
+) -> Result<(), String> {
+    let (_player, mut chest) = validate_large_chest_interaction(ctx, chest_id)?;
+    inventory_management::handle_split_into_container(ctx, &mut chest, target_slot_index, source_item_instance_id, quantity_to_split)?;
+    ctx.db.large_storage_chest().id().update(chest);
+    Ok(())
+}
+
+/// Splits a stack from a chest slot into the player's inventory/hotbar.
+#[spacetimedb::reducer]
+pub fn split_stack_from_large_chest(
+    ctx: &ReducerContext,
+    chest_id: u32,
+    source_slot_index: u8,
+    quantity_to_split: u32,
+    target_slot_type: String,
+    target_slot_index: u32,
+) -> Result<(), String> {
+    let (_player, mut chest) = validate_large_chest_interaction(ctx, chest_id)?;
+    inventory_management::handle_split_from_container(ctx, &mut chest, source_slot_index, quantity_to_split, target_slot_type, target_slot_index)?;
+    ctx.db.large_storage_chest().id().update(chest);
+    Ok(())
+}
+
+/// Splits a stack FROM one chest slot TO another within the same chest.
+#[spacetimedb::reducer]
+pub fn split_stack_within_large_chest(
+    ctx: &ReducerContext,
+    chest_id: u32,
+    source_slot_index: u8,
+    target_slot_index: u8,
+    quantity_to_split: u32,
+) -> Result<(), String> {
+    let (_player, mut chest) = validate_large_chest_interaction(ctx, chest_id)?;
+    inventory_management::handle_split_within_container(ctx, &mut chest, source_slot_index, target_slot_index, quantity_to_split)?;
+    ctx.db.large_storage_chest().id().update(chest);
+    Ok(())
+}
+
+/// Quickly moves an item FROM a chest slot TO the player inventory.
+#[spacetimedb::reducer]
+pub fn quick_move_from_large_chest(ctx: &ReducerContext, chest_id: u32, source_slot_index: u8) -> Result<(), String> {
+    let (_player, mut chest) = validate_large_chest_interaction(ctx, chest_id)?;
+    inventory_management::handle_quick_move_from_container(ctx, &mut chest, source_slot_index)?;
+    ctx.db.large_storage_chest().id().update(chest);
+    Ok(())
+}
+
+/// Quickly moves an item FROM player inventory/hotbar TO the first available/mergeable chest slot.
+#[spacetimedb::reducer]
+pub fn quick_move_to_large_chest(ctx: &ReducerContext, chest_id: u32, item_instance_id: u64) -> Result<(), String> {
+    let (_player, mut chest) = validate_large_chest_interaction(ctx, chest_id)?;
+    inventory_management::handle_quick_move_to_container(ctx, &mut chest, item_instance_id)?;
+    ctx.db.large_storage_chest().id().update(chest);
+    Ok(())
+}
+
+/// Drops the whole stack in a chest slot onto the ground near the player.
+#[spacetimedb::reducer]
+pub fn drop_item_from_large_chest_slot_to_world(ctx: &ReducerContext, chest_id: u32, slot_index: u8) -> Result<(), String> {
+    let (player, mut chest) = validate_large_chest_interaction(ctx, chest_id)?;
+    inventory_management::handle_drop_from_container_slot(ctx, &mut chest, slot_index, &player)?;
+    ctx.db.large_storage_chest().id().update(chest);
+    Ok(())
+}
+
+/// Splits part of a chest slot's stack and drops it onto the ground near the player.
+#[spacetimedb::reducer]
+pub fn split_and_drop_item_from_large_chest_slot_to_world(
+    ctx: &ReducerContext,
+    chest_id: u32,
+    slot_index: u8,
+    quantity_to_split: u32,
+) -> Result<(), String> {
+    let (player, mut chest) = validate_large_chest_interaction(ctx, chest_id)?;
+    inventory_management::handle_split_and_drop_from_container_slot(ctx, &mut chest, slot_index, quantity_to_split, &player)?;
+    ctx.db.large_storage_chest().id().update(chest);
+    Ok(())
+}
+
+/******************************************************************************
+ *                       REDUCERS (Chest-Specific Logic)                      *
+ ******************************************************************************/
+
+/// --- Place Large Storage Chest ---
+/// Places a Large Storage Chest item from the player's inventory/hotbar into the world.
+#[spacetimedb::reducer]
+pub fn place_large_storage_chest(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    crate::placement::place_deployable(ctx, item_instance_id, world_x, world_y, |name| name == LARGE_CHEST_ITEM_NAME)
+}
+
+/// Creates an empty chest. The placement constructor for Large Storage Chest items.
+pub(crate) fn spawn_large_storage_chest(ctx: &ReducerContext, placed_by: Identity, _item_def: &ItemDefinition, _item_durability: Option<f32>, world_x: f32, world_y: f32) -> Result<(), String> {
+    let inserted = ctx.db.large_storage_chest().insert(LargeStorageChest {
+        id: 0, // Auto-incremented
+        pos_x: world_x,
+        pos_y: world_y,
+        chunk_index: calculate_chunk_index(world_x, world_y),
+        placed_by,
+        slots: empty_container_slots(NUM_LARGE_CHEST_SLOTS),
+        health: LARGE_CHEST_INITIAL_HEALTH,
+        max_health: LARGE_CHEST_INITIAL_HEALTH,
+        is_destroyed: false,
+        destroyed_at: None,
+        last_hit_time: None,
+    });
+    crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::LargeStorageChest(inserted.id), world_x, world_y);
+    log::info!("Player {:?} placed Large Storage Chest {} at ({:.1}, {:.1}).", placed_by, inserted.id, world_x, world_y);
+    Ok(())
+}
+
+/// --- Interact with Large Storage Chest ---
+#[spacetimedb::reducer]
+pub fn interact_with_large_chest(ctx: &ReducerContext, chest_id: u32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    validate_large_chest_interaction(ctx, chest_id)?;
+    log::debug!("Player {:?} interaction check OK for large chest {}", ctx.sender, chest_id);
+    Ok(())
+}
+
+/// --- Pickup Large Storage Chest ---
+/// Picks up an *empty* chest, returning it to the player's inventory.
+#[spacetimedb::reducer]
+pub fn pickup_large_chest(ctx: &ReducerContext, chest_id: u32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let (_player, chest) = validate_large_chest_interaction(ctx, chest_id)?;
+    if !is_container_empty(&chest) {
+        return Err("Cannot pick up chest: It is not empty.".to_string());
+    }
+
+    let chest_item_def = ctx.db.item_definition().iter()
+        .find(|def| def.name == LARGE_CHEST_ITEM_NAME)
+        .ok_or_else(|| format!("ItemDefinition for '{}' not found.", LARGE_CHEST_ITEM_NAME))?;
+    add_item_to_player_inventory(ctx, sender_id, chest_item_def.id, 1)
+        .map_err(|e| format!("Could not add {} to your inventory: {}", LARGE_CHEST_ITEM_NAME, e))?;
+    crate::locks::return_lock_for_picked_up_target(ctx, sender_id, LockTargetType::LargeStorageChest, chest_id)?;
+
+    ctx.db.large_storage_chest().id().delete(chest_id);
+    crate::spatial_grid::untrack_entity(ctx, crate::spatial_grid::EntityType::LargeStorageChest(chest_id));
+    log::info!("Large chest {} picked up by player {:?}.", chest_id, sender_id);
+    Ok(())
+}
+
+/******************************************************************************
+ *                            TRAIT IMPLEMENTATIONS                           *
+ ******************************************************************************/
+
+impl SlotArrayContainer for LargeStorageChest {
+    fn slots(&self) -> &[ContainerSlot] {
+        &self.slots
+    }
+
+    fn slots_mut(&mut self) -> &mut [ContainerSlot] {
+        &mut self.slots
+    }
+
+    fn slot_container_type(&self) -> ContainerType {
+        ContainerType::LargeStorageChest
+    }
+
+    fn slot_container_id(&self) -> u64 {
+        self.id as u64
+    }
+}
+
+/// --- Helper struct to implement the ContainerItemClearer trait for LargeStorageChest ---
+pub struct LargeStorageChestClearer;
+
+impl ContainerItemClearer for LargeStorageChestClearer {
+    fn clear_item(ctx: &ReducerContext, item_instance_id: u64) -> bool {
+        let chests = ctx.db.large_storage_chest();
+        for mut chest in chests.iter() {
+            let Some(slot_index) = (0..chest.num_slots() as u8).find(|&i| chest.get_slot_instance_id(i) == Some(item_instance_id)) else {
+                continue;
+            };
+            log::debug!("[LargeStorageChestClearer] Found item {} in chest {} slot {}. Clearing slot.", item_instance_id, chest.id, slot_index);
+            chest.set_slot(slot_index, None, None);
+            chests.id().update(chest);
+
+            let inventory_items = ctx.db.inventory_item();
+            if let Some(mut item) = inventory_items.instance_id().find(item_instance_id) {
+                item.location = ItemLocation::Unknown;
+                inventory_items.instance_id().update(item);
+            }
+            return true;
+        }
+        false
+    }
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Validates that the sender can use the chest: it exists, isn't destroyed, its lock
+/// (if any) lets them in, and it is within reach.
+fn validate_large_chest_interaction(ctx: &ReducerContext, chest_id: u32) -> Result<(Player, LargeStorageChest), String> {
+    let sender_id = ctx.sender;
+    let player = ctx.db.player().identity().find(sender_id).ok_or_else(|| "Player not found".to_string())?;
+    let chest = ctx.db.large_storage_chest().id().find(chest_id)
+        .ok_or_else(|| format!("Large chest {} not found", chest_id))?;
+
+    if chest.is_destroyed {
+        return Err(format!("Large chest {} is destroyed.", chest_id));
+    }
+    crate::locks::check_lock_access(ctx, LockTargetType::LargeStorageChest, chest_id, sender_id)?;
+
+    let dx = player.position_x - chest.pos_x;
+    let dy = player.position_y - chest.pos_y;
+    if (dx * dx + dy * dy) > LARGE_CHEST_INTERACTION_DISTANCE_SQUARED {
+        return Err("Too far away".to_string());
+    }
+    Ok((player, chest))
+}
@@ -2,8 +2,6 @@ use spacetimedb::{Identity, Timestamp, ReducerContext, Table, ConnectionId};
 use log;
 use std::time::Duration;
 use rand::Rng; // Add rand for random respawn location
-use crate::environment::calculate_chunk_index; // Make sure this helper is available
-use crate::models::{ContainerType, ItemLocation}; // Ensure ItemLocation and ContainerType are in scope
 
 // Declare the module
 mod environment;
@@ -35,7 +33,7 @@ mod crafting_queue; // ADD: Crafting queue logic
 mod player_stats; // ADD: Player stat scheduling logic
 mod global_tick; // ADD: Global tick scheduling logic
 mod chat; // ADD: Chat module for message handling
-mod player_pin; // ADD: Player pin module for minimap
+mod player_pin; // Minimap pins, map markers and team pings
 pub mod combat; // Add the new combat module
 mod collectible_resources; // Add the new collectible resources system
 mod corn; // Add the new corn resource module
@@ -49,6 +47,65 @@ pub mod pumpkin;
 pub mod active_effects; // Added for timed consumable effects
 mod cloud; // Add the new cloud module
 mod armor; // <<< ADDED armor module
+mod building; // Walls, doors, foundations
+mod furnace; // Smelting ore into metal
+mod team; // Teams / clans
+mod projectile; // Arrows and other ranged projectiles
+mod animals; // Wild animals (deer, wolves) and their AI
+mod interest_management; // Viewport-based row visibility
+mod skills; // Player skill XP and level bonuses
+mod large_storage_chest; // Bigger storage container using slot arrays
+mod locks; // Locks for containers and doors
+mod explosives; // Satchel charges with timed detonation
+mod trading; // Player-to-player trades
+mod sleeping_player; // Bodies left behind by offline players
+mod world_tile; // Terrain tiles (water)
+mod loot_crate; // Breakable crates that scatter loot
+mod events; // Raid events with hostile NPC raiders
+mod player_settings; // Per-player preferences (auto pickup)
+mod admin; // Server administrators
+mod spectator; // Free camera for dead players and admins
+mod game_config; // Server-tunable balance values
+mod workbench; // Crafting stations that gate recipes
+mod recycler; // Breaks crafted items back into components
+mod server_announcement; // Admin broadcasts and scheduled announcements
+mod kill_feed; // Kill feed and lifetime player statistics
+mod standing_torch; // Torches planted in the ground as small light/warmth sources
+mod berry_bush; // Bushes whose berries are picked a few at a time and regrow
+mod research; // Recipe unlocks via blueprints and the research table
+mod hazard_zone; // Radiation zones and the world safe radius
+mod emote; // Emotes and gestures broadcast to other players
+mod interaction; // Context-sensitive "use" of the nearest interactable
+mod janitor; // Periodic cleanup of orphaned rows
+mod sign; // Placeable signs with owner-editable text
+mod server_info; // Server summary and player list for clients
+mod tree_felling; // Falling trees and harvestable stumps
+mod fishing; // Fishing rods, bites and catches
+mod encumbrance; // Carried weight and its movement penalties
+mod autorun; // Server-driven auto-run and move-to-point
+mod placement; // Placement rules, validation and feedback
+mod world_event; // Transient world events for client audio/visual effects
+mod sweet_spot; // Gathering sweet spots and hit combos
+mod channeled_use; // Timed, interruptible use of medical items
+mod container_access; // Authorization lists for storage boxes and stashes
+mod combat_log; // PvP hit log for moderation
+mod overflow_backpack; // Dropped backpacks for items that don't fit in a full inventory
+mod vending_machine; // Player shops with sell orders
+mod item_stat_sheet; // Server-computed item stats for tooltips
+mod server_modifiers; // Timed global multipliers for server events
+mod moderation; // Player reports, mutes, kicks and bans
+mod water; // Drinking from lakes and rivers, and water jugs
+mod mount; // Rideable horses
+mod zone; // Map zones with their own PvP and building rules
+mod note; // Note items with text readable by whoever holds them
+mod wiring; // Switches wired to campfires and furnaces
+mod blocking; // Raising a shield to block damage
+mod quests; // Tutorial quests with item rewards
+mod environment_hazard; // Cacti, thorn bushes and hot stones that hurt on contact
+mod session; // Connection sessions, reconnect grace period and session metrics
+mod map_summary; // Per-chunk summaries for the client minimap
+mod cosmetics; // Unlockable titles, name colours and corpse styles
+mod flame_turret; // Fuelled defence that burns intruders
 
 // Define a constant for the /kill command cooldown (e.g., 5 minutes)
 pub const KILL_COMMAND_COOLDOWN_SECONDS: u64 = 300;
@@ -89,10 +146,10 @@ use crate::items::item_definition as ItemDefinitionTableTrait;
 use crate::active_equipment::active_equipment as ActiveEquipmentTableTrait;
 use crate::dropped_item::dropped_item_despawn_schedule as DroppedItemDespawnScheduleTableTrait;
 use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
+use crate::building::building_piece as BuildingPieceTableTrait;
 use crate::chat::message as MessageTableTrait; // Import the trait for Message table
 use crate::sleeping_bag::sleeping_bag as SleepingBagTableTrait; // ADD Sleeping Bag trait import
 use crate::hemp::hemp as HempTableTrait; // Added for Hemp resource
-use crate::player_stats::stat_thresholds_config as StatThresholdsConfigTableTrait; // <<< UPDATED: Import StatThresholdsConfig table trait
 
 // Use struct names directly for trait aliases
 use crate::crafting::Recipe as RecipeTableTrait;
@@ -103,15 +160,15 @@ use crate::PlayerLastAttackTimestamp as PlayerLastAttackTimestampTableTrait; //
 
 // Import constants needed from player_stats
 use crate::player_stats::{
-    SPRINT_SPEED_MULTIPLIER,
     JUMP_COOLDOWN_MS,
-    LOW_THIRST_SPEED_PENALTY,
-    LOW_WARMTH_SPEED_PENALTY
+    DODGE_COOLDOWN_MS,
+    DODGE_INVULNERABILITY_MS,
+    DODGE_STAMINA_COST,
+    DODGE_DISTANCE,
 };
 
 // Use specific items needed globally (or use qualified paths)
 use crate::world_state::TimeOfDay; // Keep TimeOfDay if needed elsewhere, otherwise remove
-use crate::campfire::{Campfire, WARMTH_RADIUS_SQUARED, WARMTH_PER_SECOND, CAMPFIRE_COLLISION_RADIUS, CAMPFIRE_CAMPFIRE_COLLISION_DISTANCE_SQUARED, CAMPFIRE_COLLISION_Y_OFFSET, PLAYER_CAMPFIRE_COLLISION_DISTANCE_SQUARED, PLAYER_CAMPFIRE_INTERACTION_DISTANCE_SQUARED };
 
 // Initial Amounts
 pub const INITIAL_CAMPFIRE_FUEL_AMOUNT: u32 = 50; // Example amount
@@ -121,6 +178,7 @@ pub const TILE_SIZE_PX: u32 = 48;
 pub const PLAYER_RADIUS: f32 = 32.0; // Player collision radius
 pub const PLAYER_SPEED: f32 = 600.0; // Speed in pixels per second
 pub const PLAYER_SPRINT_MULTIPLIER: f32 = 1.6;
+pub const MAX_MOVEMENT_DELTA_SECS: f32 = 0.05; // Longest single movement step, to keep collisions from being skipped
 
 // World Dimensions (example)
 pub const WORLD_WIDTH_TILES: u32 = 500;
@@ -131,7 +189,6 @@ pub const WORLD_HEIGHT_PX: f32 = (WORLD_HEIGHT_TILES * TILE_SIZE_PX) as f32;
 
 // Campfire Placement Constants (Restored)
 pub const CAMPFIRE_PLACEMENT_MAX_DISTANCE: f32 = 96.0;
-pub const CAMPFIRE_PLACEMENT_MAX_DISTANCE_SQUARED: f32 = CAMPFIRE_PLACEMENT_MAX_DISTANCE * CAMPFIRE_PLACEMENT_MAX_DISTANCE;
 
 // Respawn Collision Check Constants
 pub const RESPAWN_CHECK_RADIUS: f32 = TILE_SIZE_PX as f32 * 0.8; // Check slightly less than a tile radius
@@ -151,13 +208,17 @@ pub struct Player {
     #[primary_key]
     pub identity: Identity,
     pub username: String,
+    pub active_title: Option<String>, // Cosmetic title shown next to the username (cosmetics.rs)
     pub position_x: f32,
     pub position_y: f32,
+    #[index(btree)]
+    pub chunk_index: u32, // Kept in step with the position by `set_position`
     pub color: String,
     pub direction: String,
     pub last_update: Timestamp, // Timestamp of the last update (movement or stats)
     pub last_stat_update: Timestamp, // Timestamp of the last stat processing tick
     pub jump_start_time_ms: u64,
+    pub dodge_start_time_ms: u64, // Start of the last dodge roll (0 = never)
     pub health: f32,
     pub stamina: f32,
     pub thirst: f32,
@@ -173,6 +234,15 @@ pub struct Player {
     pub is_crouching: bool, // RENAMED: For crouching speed control
 }
 
+impl Player {
+    /// Moves the player, keeping `chunk_index` in step. Use this for every position change.
+    pub(crate) fn set_position(&mut self, x: f32, y: f32) {
+        self.position_x = x;
+        self.position_y = y;
+        environment::sync_chunk_index(&mut self.chunk_index, x, y);
+    }
+}
+
 // Table to store the last attack timestamp for each player
 #[spacetimedb::table(name = player_last_attack_timestamp)]
 #[derive(Clone, Debug)]
@@ -224,8 +294,34 @@ pub fn init_module(ctx: &ReducerContext) -> Result<(), String> {
     crate::player_stats::init_player_stat_schedule(ctx)?;
     // ADD: Initialize the global tick schedule
     crate::global_tick::init_global_tick_schedule(ctx)?;
+    // Initialize the projectile update schedule
+    crate::projectile::init_projectile_schedule(ctx)?;
+    crate::animals::init_animal_ai_schedule(ctx)?;
+    crate::events::init_raid_event_schedule(ctx)?;
+    crate::recycler::init_recycler_schedule(ctx)?;
+    crate::flame_turret::init_flame_turret_schedule(ctx)?;
+    crate::player_pin::init_map_ping_cleanup_schedule(ctx)?;
+    crate::server_announcement::init_announcement_cleanup_schedule(ctx)?;
+    crate::kill_feed::init_kill_feed_cleanup_schedule(ctx)?;
+    crate::standing_torch::init_standing_torch_schedule(ctx)?;
+    crate::berry_bush::init_berry_bush_regrowth_schedule(ctx)?;
+    crate::hazard_zone::init_hazard_zone_schedule(ctx)?;
+    crate::emote::init_emote_cleanup_schedule(ctx)?;
+    crate::janitor::init_janitor_schedule(ctx)?;
+    crate::server_info::init_server_info(ctx)?;
+    crate::fishing::init_fishing_schedule(ctx)?;
+    crate::world_event::init_world_event_cleanup_schedule(ctx)?;
+    crate::combat_log::init_combat_log_cleanup_schedule(ctx)?;
+    crate::overflow_backpack::init_dropped_backpack_cleanup_schedule(ctx)?;
+    crate::server_modifiers::init_modifier_schedules(ctx)?;
+    crate::mount::init_mount_schedule(ctx)?;
+    crate::zone::seed_spawn_safe_zone(ctx)?;
+    crate::chat::init_chat_retention_schedule(ctx)?;
+    crate::session::init_session_grace_schedule(ctx)?;
+    crate::map_summary::init_map_summary_schedule(ctx)?;
     // <<< UPDATED: Initialize StatThresholdsConfig table >>>
     crate::player_stats::init_stat_thresholds_config(ctx)?;
+    crate::game_config::init_game_config(ctx)?;
     // ADD: Initialize active effects processing schedule
     crate::active_effects::schedule_effect_processing(ctx)?;
 
@@ -237,14 +333,20 @@ pub fn init_module(ctx: &ReducerContext) -> Result<(), String> {
 #[spacetimedb::reducer(client_connected)]
 pub fn identity_connected(ctx: &ReducerContext) -> Result<(), String> {
     // Call seeders using qualified paths
-    crate::environment::seed_environment(ctx)?; // Call the updated seeder
+    crate::world_state::seed_world_state(ctx)?; // Call the world state seeder (holds the world seed)
+    crate::environment::seed_environment(ctx)?; // Seeds clouds and the chunks around spawn
+    crate::spatial_grid::ensure_spatial_grid_built(ctx); // Bootstraps the persisted grid for existing worlds
     crate::items::seed_items(ctx)?; // Call the item seeder
-    crate::world_state::seed_world_state(ctx)?; // Call the world state seeder
+    crate::item_stat_sheet::seed_item_stat_sheets(ctx)?; // Derived tooltip stats; only rewrites sheets that changed
+    crate::armor::seed_armor_resistances(ctx)?; // Per-damage-type resistances for armor definitions
+    crate::loot_crate::seed_loot_tables(ctx)?; // Default weighted drops for barrels and crates
     crate::crafting::seed_recipes(ctx)?; // Seed the crafting recipes
+    crate::quests::seed_quests(ctx)?; // Seed the tutorial quests
     // No seeder needed for Campfire yet, table will be empty initially
 
     // --- Track Active Connection --- 
     let client_identity = ctx.sender;
+    crate::moderation::check_connection_allowed(ctx, client_identity)?; // Turns banned players away
     let connection_id = ctx.connection_id.ok_or_else(|| {
         log::error!("[Connect] Missing ConnectionId in client_connected context for {:?}", client_identity);
         "Internal error: Missing connection ID on connect".to_string()
@@ -276,10 +378,15 @@ pub fn identity_connected(ctx: &ReducerContext) -> Result<(), String> {
         }
     }
     // --- End Track Active Connection ---
+    if !crate::session::resume_or_start_session(ctx, client_identity) {
+        crate::kill_feed::start_play_session(ctx, client_identity);
+    }
+    crate::chat::write_chat_snapshot(ctx, client_identity);
 
     // --- Set Player Online Status ---
     let mut players = ctx.db.player();
     if let Some(mut player) = players.identity().find(&client_identity) {
+        crate::server_info::update_player_list_entry(ctx, client_identity, &player.username, true);
         if !player.is_online {
             player.is_online = true;
             players.identity().update(player);
@@ -313,7 +420,6 @@ pub fn identity_disconnected(ctx: &ReducerContext) {
     // log::info!("[Disconnect] Handling disconnect for identity: {:?}, connection_id: {:?}", sender_id, disconnecting_connection_id);
 
     let active_connections = ctx.db.active_connection();
-    let players = ctx.db.player(); // <<< Need players table handle
 
     // --- Check 1: Does the active connection record match the disconnecting one? ---
     if let Some(initial_active_conn) = active_connections.identity().find(&sender_id) {
@@ -323,19 +429,16 @@ pub fn identity_disconnected(ctx: &ReducerContext) {
             // log::info!("[Disconnect] Removing active connection record for identity: {:?}, connection_id: {:?}", 
             //               sender_id, disconnecting_connection_id);
                     active_connections.identity().delete(&sender_id);
+                    ctx.db.client_viewport().client_identity().delete(sender_id);
+                    crate::interest_management::clear_visible_chunks(ctx, sender_id);
+                    crate::trading::cancel_trades_for_player(ctx, sender_id);
+                    crate::spectator::end_spectating(ctx, sender_id);
+                    crate::chat::clear_chat_history(ctx, sender_id);
             // --- END Clean Up Connection --- 
 
-            // --- Set Player Offline Status --- 
-            if let Some(mut player) = players.identity().find(&sender_id) {
-                 if player.is_online { // Only update if they were marked online
-                    player.is_online = false;
-                    players.identity().update(player);
-                    log::info!("[Disconnect] Set player {:?} to offline.", sender_id);
-                 }
-            } else {
-                 log::warn!("[Disconnect] Player {:?} not found in Player table during disconnect cleanup.", sender_id);
-            }
-            // --- END Set Player Offline Status --- 
+            // The player stays in the world until the reconnect grace period runs out,
+            // then goes offline (session.rs).
+            crate::session::begin_disconnect_grace(ctx, sender_id);
 
         } else {
             // The connection ID doesn't match the current active one. 
@@ -371,6 +474,7 @@ pub fn register_player(ctx: &ReducerContext, username: String) -> Result<(), Str
         existing_player.last_update = update_timestamp; // Always update player timestamp
 
         players.identity().update(existing_player.clone()); // Perform the player update
+        crate::sleeping_player::wake_sleeping_player(ctx, sender_id); // Restores position/health from the sleeping body
 
         // --- ALSO Update ActiveConnection record --- 
         let connection_id = ctx.connection_id.ok_or_else(|| {
@@ -499,13 +603,16 @@ pub fn register_player(ctx: &ReducerContext, username: String) -> Result<(), Str
     let player = Player {
         identity: sender_id, // Use the authenticated identity
         username: username.clone(),
+        active_title: None,
         position_x: spawn_x, // Use calculated spawn position
         position_y: spawn_y, // Use calculated spawn position
+        chunk_index: environment::calculate_chunk_index(spawn_x, spawn_y),
         color,
         direction: "down".to_string(),
         last_update: ctx.timestamp,
         last_stat_update: ctx.timestamp,
         jump_start_time_ms: 0,
+        dodge_start_time_ms: 0,
         health: 100.0,
         stamina: 100.0,
         thirst: 100.0,
@@ -525,6 +632,8 @@ pub fn register_player(ctx: &ReducerContext, username: String) -> Result<(), Str
     match players.try_insert(player) {
         Ok(inserted_player) => {
             log::info!("Player registered: {}. Granting starting items...", username);
+            crate::server_info::update_player_list_entry(ctx, sender_id, &username, true);
+            spatial_grid::track_entity(ctx, spatial_grid::EntityType::Player(sender_id), inserted_player.position_x, inserted_player.position_y);
 
             // --- ADD ActiveConnection record for NEW player ---
              let connection_id = ctx.connection_id.ok_or_else(|| {
@@ -568,171 +677,7 @@ pub fn register_player(ctx: &ReducerContext, username: String) -> Result<(), Str
 // Reducer to place a campfire
 #[spacetimedb::reducer]
 pub fn place_campfire(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
-    let sender_id = ctx.sender;
-    let inventory_items = ctx.db.inventory_item();
-    let item_defs = ctx.db.item_definition();
-    let players = ctx.db.player();
-    let campfires = ctx.db.campfire();
-
-    // --- Look up Item Definition IDs by Name ---
-    let campfire_def_id = item_defs.iter()
-        .find(|def| def.name == "Camp Fire")
-        .map(|def| def.id)
-        .ok_or_else(|| "Item definition for 'Camp Fire' not found.".to_string())?;
-
-    let wood_def_id = item_defs.iter()
-        .find(|def| def.name == "Wood")
-        .map(|def| def.id)
-        .ok_or_else(|| "Item definition for 'Wood' not found.".to_string())?;
-    // --- End Look up ---
-
-    log::info!(
-        "[PlaceCampfire] Player {:?} attempting placement of item {} at ({:.1}, {:.1})",
-        sender_id, item_instance_id, world_x, world_y
-    );
-
-    // --- 1. Validate Player and Placement Rules ---
-    let player = players.identity().find(sender_id)
-        .ok_or_else(|| "Player not found".to_string())?;
-
-    let dx_place = world_x - player.position_x;
-    let dy_place = world_y - player.position_y;
-    let dist_sq_place = dx_place * dx_place + dy_place * dy_place;
-    if dist_sq_place > CAMPFIRE_PLACEMENT_MAX_DISTANCE_SQUARED {
-        return Err(format!("Cannot place campfire too far away ({} > {}).",
-                dist_sq_place.sqrt(), CAMPFIRE_PLACEMENT_MAX_DISTANCE));
-    }
-    for other_fire in campfires.iter() {
-        let dx_fire = world_x - other_fire.pos_x;
-        let dy_fire = world_y - other_fire.pos_y;
-        let dist_sq_fire = dx_fire * dx_fire + dy_fire * dy_fire;
-        if dist_sq_fire < CAMPFIRE_CAMPFIRE_COLLISION_DISTANCE_SQUARED {
-            return Err("Cannot place campfire too close to another campfire.".to_string());
-        }
-    }
-
-    // --- 3. Find the specific item instance and validate ---
-    let item_to_consume = inventory_items.instance_id().find(item_instance_id)
-        .ok_or_else(|| format!("Item instance {} not found.", item_instance_id))?;
-
-    // Validate ownership and location based on ItemLocation
-    match item_to_consume.location {
-        ItemLocation::Inventory(data) => {
-            if data.owner_id != sender_id {
-                return Err(format!("Item instance {} for campfire not owned by player {:?}.", item_instance_id, sender_id));
-            }
-        }
-        ItemLocation::Hotbar(data) => {
-            if data.owner_id != sender_id {
-                return Err(format!("Item instance {} for campfire not owned by player {:?}.", item_instance_id, sender_id));
-            }
-        }
-        _ => {
-            return Err(format!("Item instance {} must be in inventory or hotbar to be placed.", item_instance_id));
-        }
-    }
-    if item_to_consume.item_def_id != campfire_def_id {
-        return Err(format!("Item instance {} is not a Camp Fire (expected def {}, got {}).",
-                        item_instance_id, campfire_def_id, item_to_consume.item_def_id));
-    }
-
-    // --- 4. Consume the Item (Delete from InventoryItem table) ---
-    log::info!(
-        "[PlaceCampfire] Consuming item instance {} (Def ID: {}) from player {:?}",
-        item_instance_id, campfire_def_id, sender_id
-    );
-    inventory_items.instance_id().delete(item_instance_id);
-
-    // --- 5. Create Campfire Entity & Initial Fuel ---
-    // --- 5a. Insert Campfire Entity first to get its ID ---
-    let current_time = ctx.timestamp;
-    let chunk_idx = calculate_chunk_index(world_x, world_y);
-
-    // --- 5b. Create Initial Fuel Item (Wood) with correct ItemLocation ---
-    // We need the ItemDefinition of the wood to get its fuel_burn_duration_secs
-    let initial_fuel_item_def = ctx.db.item_definition().id().find(wood_def_id)
-        .ok_or_else(|| "Wood item definition not found for initial fuel.".to_string())?;
-
-    // --- 5a. Insert Campfire Entity first to get its ID ---
-    // The campfire entity is created with initial fuel data directly
-    let new_campfire = Campfire {
-        id: 0, // Auto-incremented
-        pos_x: world_x,
-        pos_y: world_y,
-        chunk_index: chunk_idx,
-        placed_by: sender_id,
-        placed_at: current_time,
-        is_burning: false, // Campfires start unlit
-        // Initialize all fuel slots to None
-        fuel_instance_id_0: None,
-        fuel_def_id_0: None,
-        fuel_instance_id_1: None,
-        fuel_def_id_1: None,
-        fuel_instance_id_2: None,
-        fuel_def_id_2: None,
-        fuel_instance_id_3: None,
-        fuel_def_id_3: None,
-        fuel_instance_id_4: None,
-        fuel_def_id_4: None,
-        current_fuel_def_id: None, 
-        remaining_fuel_burn_time_secs: None,
-        health: 100.0, // Example initial health
-        max_health: 100.0, // Example max health
-        is_destroyed: false,
-        destroyed_at: None,
-        last_hit_time: None,
-        // Initialize cooking progress to None
-        slot_0_cooking_progress: None,
-        slot_1_cooking_progress: None,
-        slot_2_cooking_progress: None,
-        slot_3_cooking_progress: None,
-        slot_4_cooking_progress: None,
-        last_damage_application_time: None,
-        is_player_in_hot_zone: false, // Initialize new field
-    };
-    let inserted_campfire = campfires.try_insert(new_campfire.clone())
-        .map_err(|e| format!("Failed to insert campfire entity: {}", e))?;
-    let new_campfire_id = inserted_campfire.id; 
-
-    let initial_fuel_item = crate::items::InventoryItem {
-        instance_id: 0, // Auto-inc
-        item_def_id: wood_def_id, 
-        quantity: INITIAL_CAMPFIRE_FUEL_AMOUNT, 
-        location: ItemLocation::Container(models::ContainerLocationData {
-            container_type: ContainerType::Campfire,
-            container_id: new_campfire_id as u64, 
-            slot_index: 0, 
-        }),
-    };
-    let inserted_fuel_item = inventory_items.try_insert(initial_fuel_item)
-        .map_err(|e| format!("Failed to insert initial fuel item: {}", e))?;
-    let fuel_instance_id = inserted_fuel_item.instance_id;
-    log::info!("[PlaceCampfire] Created initial fuel item (Wood, instance {}) for campfire {}.", fuel_instance_id, new_campfire_id);
-
-    // --- 5c. Update the Campfire Entity with the Fuel Item's ID in the correct slot --- 
-    let mut campfire_to_update = campfires.id().find(new_campfire_id)
-        .ok_or_else(|| format!("Failed to re-find campfire {} to update with fuel.", new_campfire_id))?;
-    
-    // Set the first fuel slot of the campfire
-    campfire_to_update.fuel_instance_id_0 = Some(fuel_instance_id);
-    campfire_to_update.fuel_def_id_0 = Some(wood_def_id);
-    // DO NOT set current_fuel_def_id or remaining_fuel_burn_time_secs here.
-    // is_burning is already false from new_campfire.
-    // The scheduled process_campfire_logic_scheduled will pick it up.
-    
-    let is_burning_for_log = campfire_to_update.is_burning; // Capture before move
-    campfires.id().update(campfire_to_update); // campfire_to_update is moved here
-    
-    log::info!("Player {} placed a campfire {} at ({:.1}, {:.1}) with initial fuel (Item {} in slot 0). Burning state: {}.",
-             player.username, new_campfire_id, world_x, world_y, fuel_instance_id, is_burning_for_log); // Use captured value
-
-    // Schedule initial processing for the new campfire
-    match crate::campfire::schedule_next_campfire_processing(ctx, new_campfire_id) {
-        Ok(_) => log::info!("[PlaceCampfire] Scheduled initial processing for campfire {}", new_campfire_id),
-        Err(e) => log::error!("[PlaceCampfire] Failed to schedule initial processing for campfire {}: {}", new_campfire_id, e),
-    }
-
-    Ok(())
+    crate::placement::place_deployable(ctx, item_instance_id, world_x, world_y, |name| name == "Camp Fire")
 }
 
 // Called by the client to set the sprinting state
@@ -742,6 +687,9 @@ pub fn set_sprinting(ctx: &ReducerContext, sprinting: bool) -> Result<(), String
     let players = ctx.db.player();
 
     if let Some(mut player) = players.identity().find(&sender_id) {
+        if sprinting && !crate::encumbrance::can_sprint(ctx, sender_id) {
+            return Err("You are carrying too much to sprint.".to_string());
+        }
         // Only update if the state is actually changing
         if player.is_sprinting != sprinting {
             player.is_sprinting = sprinting;
@@ -763,12 +711,32 @@ pub fn update_player_position(
     move_x: f32,
     move_y: f32,
 ) -> Result<(), String> {
-    let sender_id = ctx.sender;
+    // Spectators move their camera through spectator::update_spectator_position instead
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    // Steering by hand takes over from auto-run
+    if move_x.abs() > 0.01 || move_y.abs() > 0.01 {
+        crate::autorun::cancel_autorun(ctx, ctx.sender);
+    }
+    move_player(ctx, ctx.sender, move_x, move_y, None)
+}
+
+/// Moves a player along the normalized direction (move_x, move_y), applying speed
+/// modifiers and collisions. `delta_time_secs` is how long they moved for; None uses
+/// the time since their last update (clamped). Used by `update_player_position` and auto-run.
+pub(crate) fn move_player(
+    ctx: &ReducerContext,
+    sender_id: Identity,
+    move_x: f32,
+    move_y: f32,
+    delta_time_secs: Option<f32>,
+) -> Result<(), String> {
+    crate::moderation::ensure_not_sanctioned(ctx, sender_id)?;
     let players = ctx.db.player();
     let trees = ctx.db.tree();
     let stones = ctx.db.stone();
     let campfires = ctx.db.campfire(); // Get campfire table
     let wooden_storage_boxes = ctx.db.wooden_storage_box(); // <<< ADDED
+    let building_pieces = ctx.db.building_piece();
 
     let current_player = players.identity()
         .find(sender_id)
@@ -799,11 +767,14 @@ pub fn update_player_position(
     // --- End Animation Direction ---
 
     let now = ctx.timestamp;
+    let game_config = crate::game_config::get_game_config(ctx);
 
     // --- Calculate Delta Time ---
-    let elapsed_micros = now.to_micros_since_unix_epoch().saturating_sub(current_player.last_update.to_micros_since_unix_epoch());
-    // Clamp max delta time to avoid huge jumps on first update or after lag spikes (e.g., 100ms)
-    let delta_time_secs = (elapsed_micros as f32 / 1_000_000.0).min(0.05); // Clamp max delta time
+    let delta_time_secs = delta_time_secs.unwrap_or_else(|| {
+        let elapsed_micros = now.to_micros_since_unix_epoch().saturating_sub(current_player.last_update.to_micros_since_unix_epoch());
+        // Clamp max delta time to avoid huge jumps on first update or after lag spikes (e.g., 100ms)
+        (elapsed_micros as f32 / 1_000_000.0).min(MAX_MOVEMENT_DELTA_SECS) // Clamp max delta time
+    });
 
     // --- Stamina Drain & Base Speed Calculation ---
     let mut new_stamina = current_player.stamina; // Base this on current_player for speed calc
@@ -812,9 +783,26 @@ pub fn update_player_position(
     let is_moving = move_x.abs() > 0.01 || move_y.abs() > 0.01;
     let mut current_sprinting_state = current_player.is_sprinting;
 
+    // Swimming: no sprinting in water
+    let is_swimming = crate::world_tile::is_water_at(ctx, current_player.position_x, current_player.position_y);
+    if is_swimming {
+        current_sprinting_state = false;
+    }
+    // Too heavily loaded to sprint
+    if current_sprinting_state && !crate::encumbrance::can_sprint(ctx, sender_id) {
+        current_sprinting_state = false;
+    }
+
+    // Riding (see mount.rs): the mount's stamina decides sprinting, and its speed and size apply below
+    let ridden_mount = crate::mount::ridden_mount(ctx, sender_id);
+    let player_radius = ridden_mount.as_ref().map_or(PLAYER_RADIUS, |m| m.mount_type.stats().collision_radius);
+    if let Some(mount) = &ridden_mount {
+        new_stamina = mount.stamina;
+    }
+
     // Determine speed multiplier based on current sprint state and stamina
     if current_sprinting_state && new_stamina > 0.0 { // Check current stamina > 0
-        base_speed_multiplier = SPRINT_SPEED_MULTIPLIER;
+        base_speed_multiplier = game_config.sprint_speed_multiplier;
     } else if current_sprinting_state && new_stamina <= 0.0 {
         // If trying to sprint but no stamina, force sprint state off for this tick's movement calc
         current_sprinting_state = false;
@@ -826,29 +814,32 @@ pub fn update_player_position(
     let mut final_speed_multiplier = base_speed_multiplier;
     // Use current player stats read at the beginning of the reducer
 
+    if is_swimming {
+        final_speed_multiplier *= game_config.swim_speed_multiplier;
+        log::trace!("Player {:?} is swimming. Speed multiplier adjusted to: {}", sender_id, final_speed_multiplier);
+    }
+
     // Apply fine movement speed reduction if active
     if current_player.is_crouching {
         final_speed_multiplier *= 0.5; // Reduce speed by 50%
         log::trace!("Player {:?} crouching active. Speed multiplier adjusted to: {}", sender_id, final_speed_multiplier);
     }
+    if let Some(mount) = &ridden_mount {
+        final_speed_multiplier *= mount.mount_type.stats().speed_multiplier;
+    }
 
-    // --- <<< UPDATED: Read LOW_NEED_THRESHOLD from StatThresholdsConfig table >>> ---
-    let stat_thresholds_config_table = ctx.db.stat_thresholds_config(); // <<< CORRECT: Use the direct table accessor
-    let stat_thresholds_config = stat_thresholds_config_table.iter().filter(|stc| stc.id == 0).next();
-    
-    let mut effective_speed = PLAYER_SPEED * final_speed_multiplier;
-    if let Some(config) = stat_thresholds_config { // <<< UPDATED variable name
-        let low_need_threshold = config.low_need_threshold;
-        if current_player.thirst < low_need_threshold {
-            effective_speed *= LOW_THIRST_SPEED_PENALTY;
-            log::debug!("Player {:?} has low thirst. Applying speed penalty. New speed: {}", sender_id, effective_speed);
-        }
-        if current_player.warmth < low_need_threshold {
-            effective_speed *= LOW_WARMTH_SPEED_PENALTY;
-            log::debug!("Player {:?} is cold. Applying speed penalty. New speed: {}", sender_id, effective_speed);
-        }
-    } else {
-        log::warn!("StatThresholdsConfig not found for player {}. Using default behavior (no penalty applied from config).", sender_id);
+    // On-hit slows (see active_effects)
+    final_speed_multiplier *= crate::active_effects::get_movement_speed_multiplier(ctx, sender_id);
+    // Carried weight (see encumbrance)
+    final_speed_multiplier *= crate::encumbrance::get_movement_speed_multiplier(ctx, sender_id);
+    // Raised shield (see blocking)
+    final_speed_multiplier *= crate::blocking::get_movement_speed_multiplier(ctx, sender_id);
+
+    let mut effective_speed = game_config.player_speed * final_speed_multiplier;
+    // Low needs slow the player down (see StatThresholdTier)
+    if let Some(tier) = crate::player_stats::need_penalty_tier(ctx, current_player.hunger, current_player.thirst, current_player.warmth) {
+        effective_speed *= tier.speed_multiplier;
+        log::debug!("Player {:?} is in need tier {}. Applying speed penalty. New speed: {}", sender_id, tier.tier, effective_speed);
     }
 
     // --- Calculate Target Velocity & Server Displacement ---
@@ -866,17 +857,15 @@ pub fn update_player_position(
     let proposed_x = current_player.position_x + server_dx;
     let proposed_y = current_player.position_y + server_dy;
 
-    let clamped_x = proposed_x.max(PLAYER_RADIUS).min(WORLD_WIDTH_PX - PLAYER_RADIUS);
-    let clamped_y = proposed_y.max(PLAYER_RADIUS).min(WORLD_HEIGHT_PX - PLAYER_RADIUS);
+    let clamped_x = proposed_x.max(player_radius).min(WORLD_WIDTH_PX - player_radius);
+    let clamped_y = proposed_y.max(player_radius).min(WORLD_HEIGHT_PX - player_radius);
 
     let mut final_x = clamped_x;
     let mut final_y = clamped_y;
     let mut collision_handled = false;
 
     // --- Collision Detection (using spatial grid) ---
-    let mut grid = spatial_grid::SpatialGrid::new();
-    grid.populate_from_world(&ctx.db);
-    let nearby_entities = grid.get_entities_in_range(clamped_x, clamped_y);
+    let nearby_entities = spatial_grid::get_entities_in_range(ctx, clamped_x, clamped_y);
 
     // Check collisions with nearby entities (Slide calculation)
     for entity in &nearby_entities {
@@ -891,7 +880,7 @@ pub fn update_player_position(
                     let dx = clamped_x - other_player.position_x;
                     let dy = clamped_y - other_player.position_y;
                     let dist_sq = dx * dx + dy * dy;
-                    let min_dist = PLAYER_RADIUS * 2.0; // Player-Player collision distance
+                    let min_dist = player_radius + PLAYER_RADIUS; // Player-Player collision distance
                     let min_dist_sq = min_dist * min_dist;
 
                     if dist_sq < min_dist_sq {
@@ -914,8 +903,8 @@ pub fn update_player_position(
                             final_x = current_player.position_x + slide_dx;
                             final_y = current_player.position_y + slide_dy;
                             // Clamp after slide application
-                            final_x = final_x.max(PLAYER_RADIUS).min(WORLD_WIDTH_PX - PLAYER_RADIUS);
-                            final_y = final_y.max(PLAYER_RADIUS).min(WORLD_HEIGHT_PX - PLAYER_RADIUS);
+                            final_x = final_x.max(player_radius).min(WORLD_WIDTH_PX - player_radius);
+                            final_y = final_y.max(player_radius).min(WORLD_HEIGHT_PX - player_radius);
                         } else {
                             // If directly overlapping (dist_sq == 0), just stay put relative to this collision
                             final_x = current_player.position_x;
@@ -953,8 +942,8 @@ pub fn update_player_position(
                             final_x = current_player.position_x + slide_dx;
                             final_y = current_player.position_y + slide_dy;
                              // Clamp after slide application
-                            final_x = final_x.max(PLAYER_RADIUS).min(WORLD_WIDTH_PX - PLAYER_RADIUS);
-                            final_y = final_y.max(PLAYER_RADIUS).min(WORLD_HEIGHT_PX - PLAYER_RADIUS);
+                            final_x = final_x.max(player_radius).min(WORLD_WIDTH_PX - player_radius);
+                            final_y = final_y.max(player_radius).min(WORLD_HEIGHT_PX - player_radius);
                         } else {
                             final_x = current_player.position_x;
                             final_y = current_player.position_y;
@@ -990,8 +979,8 @@ pub fn update_player_position(
                              final_x = current_player.position_x + slide_dx;
                              final_y = current_player.position_y + slide_dy;
                              // Clamp after slide application
-                             final_x = final_x.max(PLAYER_RADIUS).min(WORLD_WIDTH_PX - PLAYER_RADIUS);
-                             final_y = final_y.max(PLAYER_RADIUS).min(WORLD_HEIGHT_PX - PLAYER_RADIUS);
+                             final_x = final_x.max(player_radius).min(WORLD_WIDTH_PX - player_radius);
+                             final_y = final_y.max(player_radius).min(WORLD_HEIGHT_PX - player_radius);
                          } else {
                              final_x = current_player.position_x;
                              final_y = current_player.position_y;
@@ -1026,8 +1015,8 @@ pub fn update_player_position(
                              final_x = current_player.position_x + slide_dx;
                              final_y = current_player.position_y + slide_dy;
                              // Clamp after slide application
-                             final_x = final_x.max(PLAYER_RADIUS).min(WORLD_WIDTH_PX - PLAYER_RADIUS);
-                             final_y = final_y.max(PLAYER_RADIUS).min(WORLD_HEIGHT_PX - PLAYER_RADIUS);
+                             final_x = final_x.max(player_radius).min(WORLD_WIDTH_PX - player_radius);
+                             final_y = final_y.max(player_radius).min(WORLD_HEIGHT_PX - player_radius);
                          } else {
                              final_x = current_player.position_x;
                              final_y = current_player.position_y;
@@ -1035,6 +1024,33 @@ pub fn update_player_position(
                          collision_handled = true; // Mark collision handled
                     }
                 }
+            },
+            spatial_grid::EntityType::BuildingPiece(piece_id) => {
+                if let Some(piece) = building_pieces.id().find(piece_id) {
+                    if !piece.is_solid() { continue; }
+                    let piece_collision_y = piece.pos_y - crate::building::BUILDING_COLLISION_Y_OFFSET;
+                    let dx = clamped_x - piece.pos_x;
+                    let dy = clamped_y - piece_collision_y;
+                    let dist_sq = dx * dx + dy * dy;
+                    let min_dist = player_radius + piece.collision_radius();
+                    if dist_sq < min_dist * min_dist {
+                         log::debug!("Player-Building collision detected between {:?} and piece {}. Calculating slide.", sender_id, piece.id);
+                         // Slide calculation
+                         if dist_sq > 0.0 {
+                             let normal_mag = dist_sq.sqrt();
+                             let norm_x = dx / normal_mag;
+                             let norm_y = dy / normal_mag;
+                             let dot_product = server_dx * norm_x + server_dy * norm_y;
+                             let slide_dx = server_dx - dot_product * norm_x;
+                             let slide_dy = server_dy - dot_product * norm_y;
+                             final_x = (current_player.position_x + slide_dx).clamp(player_radius, WORLD_WIDTH_PX - player_radius);
+                             final_y = (current_player.position_y + slide_dy).clamp(player_radius, WORLD_HEIGHT_PX - player_radius);
+                         } else {
+                             final_x = current_player.position_x;
+                             final_y = current_player.position_y;
+                         }
+                    }
+                }
             },
              spatial_grid::EntityType::Campfire(_) => {
                 // No collision with campfires
@@ -1051,26 +1067,91 @@ pub fn update_player_position(
 
     // --- Iterative Collision Resolution (Push-out) ---
     // Apply push-out based on the potentially slid final_x/final_y
-    let mut resolved_x = final_x;
-    let mut resolved_y = final_y;
+    let (resolved_x, resolved_y) = resolve_player_overlaps(ctx, sender_id, final_x, final_y);
+    // --- End Collision Resolution ---
+
+
+    // --- Final Update ---
+    let mut player_to_update = current_player; // Get a mutable copy from the initial read
+
+    // Check if position or direction actually changed
+    let position_changed = (resolved_x - player_to_update.position_x).abs() > 0.01 ||
+                           (resolved_y - player_to_update.position_y).abs() > 0.01;
+    // Check against the animation direction determined earlier
+    let direction_changed = player_to_update.direction != final_anim_direction;
+    // Don't check stamina/sprint changes here, they are handled by player_stats
+    let should_update_state = position_changed || direction_changed;
+
+    // Always update timestamp if delta_time > 0 to prevent accumulation on next tick
+    // This ensures last_update reflects the time this reducer processed movement,
+    // even if the final position/direction didn't change due to collision or no input.
+    let needs_timestamp_update = delta_time_secs > 0.0;
+
+    if should_update_state {
+        log::trace!("Updating player {:?} - PosChange: {}, DirChange: {}",
+            sender_id, position_changed, direction_changed);
+
+        player_to_update.set_position(resolved_x, resolved_y);
+        player_to_update.direction = final_anim_direction; // Update animation direction
+        player_to_update.last_update = now; // Update timestamp because state changed
+
+        spatial_grid::track_entity(ctx, spatial_grid::EntityType::Player(sender_id), resolved_x, resolved_y);
+        players.identity().update(player_to_update); // Update the modified player struct
+
+        // Medical items can't be used on the move
+        if position_changed {
+            crate::channeled_use::cancel_channel(ctx, sender_id);
+            crate::player_stats::record_player_movement(ctx, sender_id);
+            crate::environment_hazard::apply_hazard_contact(ctx, sender_id, resolved_x, resolved_y);
+            if let Some(mount) = ridden_mount {
+                crate::mount::carry_mount(ctx, mount, resolved_x, resolved_y, current_sprinting_state, delta_time_secs);
+            }
+        }
+        if position_changed && crate::player_settings::get_player_settings(ctx, sender_id).auto_pickup {
+            crate::dropped_item::auto_pickup_nearby_items(ctx, sender_id, resolved_x, resolved_y);
+        }
+    } else if needs_timestamp_update { // If no state changed, but time passed
+         log::trace!("No movement state changes detected for player {:?}, but updating timestamp due to elapsed time.", sender_id);
+         // Update only the timestamp on the existing player data
+         player_to_update.last_update = now;
+         players.identity().update(player_to_update);
+    } else {
+         // This case should be rare (delta_time <= 0.0)
+         log::trace!("No state changes and no time elapsed for player {:?}, skipping update.", sender_id);
+    }
+
+    Ok(())
+}
+
+/// Pushes a player at (x, y) out of any players, trees, stones, boxes and solid building
+/// pieces they overlap, over a few iterations. Returns the resolved, world-clamped position.
+/// Used after movement and when a player is knocked back.
+pub(crate) fn resolve_player_overlaps(ctx: &ReducerContext, player_id: Identity, x: f32, y: f32) -> (f32, f32) {
+    let players = ctx.db.player();
+    let trees = ctx.db.tree();
+    let stones = ctx.db.stone();
+    let wooden_storage_boxes = ctx.db.wooden_storage_box();
+    let building_pieces = ctx.db.building_piece();
+    let player_radius = crate::mount::rider_collision_radius(ctx, player_id);
+    let (mut resolved_x, mut resolved_y) = (x, y);
     let resolution_iterations = 5;
     let epsilon = 0.01;
 
     for _iter in 0..resolution_iterations {
         let mut overlap_found_in_iter = false;
         // Re-query near the currently resolved position for this iteration
-        let nearby_entities_resolve = grid.get_entities_in_range(resolved_x, resolved_y);
+        let nearby_entities_resolve = spatial_grid::get_entities_in_range(ctx, resolved_x, resolved_y);
 
         for entity in &nearby_entities_resolve {
              match entity {
                  spatial_grid::EntityType::Player(other_identity) => {
-                    if *other_identity == sender_id { continue; }
+                    if *other_identity == player_id { continue; }
                     if let Some(other_player) = players.identity().find(other_identity) {
                          if other_player.is_dead { continue; } // Don't resolve against dead players
                          let dx = resolved_x - other_player.position_x;
                          let dy = resolved_y - other_player.position_y;
                          let dist_sq = dx * dx + dy * dy;
-                         let min_dist = PLAYER_RADIUS * 2.0;
+                         let min_dist = player_radius + PLAYER_RADIUS;
                          let min_dist_sq = min_dist * min_dist;
                          if dist_sq < min_dist_sq && dist_sq > 0.0 {
                              overlap_found_in_iter = true;
@@ -1093,7 +1174,7 @@ pub fn update_player_position(
                          let dx = resolved_x - tree.pos_x;
                          let dy = resolved_y - tree_collision_y;
                          let dist_sq = dx * dx + dy * dy;
-                         let min_dist = PLAYER_RADIUS + crate::tree::TREE_TRUNK_RADIUS;
+                         let min_dist = player_radius + crate::tree::TREE_TRUNK_RADIUS;
                          let min_dist_sq = min_dist * min_dist;
                          if dist_sq < min_dist_sq && dist_sq > 0.0 {
                              overlap_found_in_iter = true;
@@ -1113,7 +1194,7 @@ pub fn update_player_position(
                         let dx = resolved_x - stone.pos_x;
                         let dy = resolved_y - stone_collision_y;
                         let dist_sq = dx * dx + dy * dy;
-                        let min_dist = PLAYER_RADIUS + crate::stone::STONE_RADIUS;
+                        let min_dist = player_radius + crate::stone::STONE_RADIUS;
                         let min_dist_sq = min_dist * min_dist;
                         if dist_sq < min_dist_sq && dist_sq > 0.0 {
                              overlap_found_in_iter = true;
@@ -1132,7 +1213,7 @@ pub fn update_player_position(
                          let dx = resolved_x - box_instance.pos_x;
                          let dy = resolved_y - box_collision_y;
                          let dist_sq = dx * dx + dy * dy;
-                         let min_dist = PLAYER_RADIUS + crate::wooden_storage_box::BOX_COLLISION_RADIUS;
+                         let min_dist = player_radius + crate::wooden_storage_box::BOX_COLLISION_RADIUS;
                          let min_dist_sq = min_dist * min_dist;
                          if dist_sq < min_dist_sq && dist_sq > 0.0 {
                              overlap_found_in_iter = true;
@@ -1144,6 +1225,24 @@ pub fn update_player_position(
                              resolved_y += push_y;
                          }
                      }
+                },
+                 spatial_grid::EntityType::BuildingPiece(piece_id) => {
+                     if let Some(piece) = building_pieces.id().find(piece_id) {
+                         if !piece.is_solid() { continue; }
+                         let piece_collision_y = piece.pos_y - crate::building::BUILDING_COLLISION_Y_OFFSET;
+                         let dx = resolved_x - piece.pos_x;
+                         let dy = resolved_y - piece_collision_y;
+                         let dist_sq = dx * dx + dy * dy;
+                         let min_dist = player_radius + piece.collision_radius();
+                         let min_dist_sq = min_dist * min_dist;
+                         if dist_sq < min_dist_sq && dist_sq > 0.0 {
+                             overlap_found_in_iter = true;
+                             let distance = dist_sq.sqrt();
+                             let overlap = (min_dist - distance) + epsilon;
+                             resolved_x += (dx / distance) * overlap;
+                             resolved_y += (dy / distance) * overlap;
+                         }
+                     }
                 },
                  spatial_grid::EntityType::Campfire(_) => {
                      // No overlap resolution with campfires
@@ -1153,57 +1252,18 @@ pub fn update_player_position(
         }
 
         // Clamp position after each iteration's adjustments
-        resolved_x = resolved_x.max(PLAYER_RADIUS).min(WORLD_WIDTH_PX - PLAYER_RADIUS);
-        resolved_y = resolved_y.max(PLAYER_RADIUS).min(WORLD_HEIGHT_PX - PLAYER_RADIUS);
+        resolved_x = resolved_x.max(player_radius).min(WORLD_WIDTH_PX - player_radius);
+        resolved_y = resolved_y.max(player_radius).min(WORLD_HEIGHT_PX - player_radius);
 
         if !overlap_found_in_iter {
             // log::trace!("Overlap resolution complete after {} iterations.", _iter + 1);
             break;
         }
         if _iter == resolution_iterations - 1 {
-            log::warn!("Overlap resolution reached max iterations ({}) for player {:?}. Position might still overlap slightly.", resolution_iterations, sender_id);
+            log::warn!("Overlap resolution reached max iterations ({}) for player {:?}. Position might still overlap slightly.", resolution_iterations, player_id);
         }
     }
-    // --- End Collision Resolution ---
-
-
-    // --- Final Update ---
-    let mut player_to_update = current_player; // Get a mutable copy from the initial read
-
-    // Check if position or direction actually changed
-    let position_changed = (resolved_x - player_to_update.position_x).abs() > 0.01 ||
-                           (resolved_y - player_to_update.position_y).abs() > 0.01;
-    // Check against the animation direction determined earlier
-    let direction_changed = player_to_update.direction != final_anim_direction;
-    // Don't check stamina/sprint changes here, they are handled by player_stats
-    let should_update_state = position_changed || direction_changed;
-
-    // Always update timestamp if delta_time > 0 to prevent accumulation on next tick
-    // This ensures last_update reflects the time this reducer processed movement,
-    // even if the final position/direction didn't change due to collision or no input.
-    let needs_timestamp_update = delta_time_secs > 0.0;
-
-    if should_update_state {
-        log::trace!("Updating player {:?} - PosChange: {}, DirChange: {}",
-            sender_id, position_changed, direction_changed);
-
-        player_to_update.position_x = resolved_x;
-        player_to_update.position_y = resolved_y;
-        player_to_update.direction = final_anim_direction; // Update animation direction
-        player_to_update.last_update = now; // Update timestamp because state changed
-
-        players.identity().update(player_to_update); // Update the modified player struct
-    } else if needs_timestamp_update { // If no state changed, but time passed
-         log::trace!("No movement state changes detected for player {:?}, but updating timestamp due to elapsed time.", sender_id);
-         // Update only the timestamp on the existing player data
-         player_to_update.last_update = now;
-         players.identity().update(player_to_update);
-    } else {
-         // This case should be rare (delta_time <= 0.0)
-         log::trace!("No state changes and no time elapsed for player {:?}, skipping update.", sender_id);
-    }
-
-    Ok(())
+    (resolved_x, resolved_y)
 }
 
 // Helper function to generate a deterministic color based on username
@@ -1227,6 +1287,7 @@ fn random_color(username: &str) -> String {
 // Reducer called by the client to initiate a jump.
 #[spacetimedb::reducer]
 pub fn jump(ctx: &ReducerContext) -> Result<(), String> {
+   crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
    let identity = ctx.sender;
    let players = ctx.db.player();
    if let Some(mut player) = players.identity().find(&identity) {
@@ -1253,6 +1314,70 @@ pub fn jump(ctx: &ReducerContext) -> Result<(), String> {
    }
 }
 
+// Reducer called by the client to dodge roll in the direction (dir_x, dir_y).
+// Costs stamina, moves the player a short burst and grants brief invulnerability.
+#[spacetimedb::reducer]
+pub fn dodge_roll(ctx: &ReducerContext, dir_x: f32, dir_y: f32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let identity = ctx.sender;
+    let players = ctx.db.player();
+    let mut player = players.identity().find(identity)
+        .ok_or_else(|| "Player not found".to_string())?;
+    if player.is_dead {
+        return Err("Cannot dodge while dead.".to_string());
+    }
+
+    let magnitude = (dir_x * dir_x + dir_y * dir_y).sqrt();
+    if !magnitude.is_finite() || magnitude < 0.01 {
+        return Err("Invalid dodge direction.".to_string());
+    }
+    let (norm_x, norm_y) = (dir_x / magnitude, dir_y / magnitude);
+
+    let now_ms = (ctx.timestamp.to_micros_since_unix_epoch() / 1000) as u64;
+    if player.dodge_start_time_ms > 0 && now_ms < player.dodge_start_time_ms + DODGE_COOLDOWN_MS {
+        return Err("Cannot dodge again so soon.".to_string());
+    }
+    if player.stamina < DODGE_STAMINA_COST {
+        return Err("Not enough stamina to dodge.".to_string());
+    }
+    if crate::world_tile::is_water_at(ctx, player.position_x, player.position_y) {
+        return Err("Cannot dodge while swimming.".to_string());
+    }
+    if crate::mount::ridden_mount(ctx, identity).is_some() {
+        return Err("Cannot dodge while riding.".to_string());
+    }
+
+    // Move in small steps so the roll stops at the first obstacle instead of passing through it
+    const DODGE_STEPS: u32 = 4;
+    let step = DODGE_DISTANCE / DODGE_STEPS as f32;
+    let (mut x, mut y) = (player.position_x, player.position_y);
+    for _ in 0..DODGE_STEPS {
+        let (next_x, next_y) = combat::resolve_knockback_collision(ctx, identity, x, y, x + norm_x * step, y + norm_y * step);
+        if next_x == x && next_y == y {
+            break;
+        }
+        x = next_x;
+        y = next_y;
+    }
+
+    player.set_position(x, y);
+    player.direction = if norm_x.abs() > norm_y.abs() {
+        if norm_x > 0.0 { "right" } else { "left" }
+    } else if norm_y > 0.0 { "down" } else { "up" }.to_string();
+    player.stamina -= DODGE_STAMINA_COST;
+    player.dodge_start_time_ms = now_ms;
+    player.last_update = ctx.timestamp;
+    spatial_grid::track_entity(ctx, spatial_grid::EntityType::Player(identity), x, y);
+    players.identity().update(player);
+    Ok(())
+}
+
+/// True while the player is inside the invulnerability window of a dodge roll.
+pub(crate) fn is_dodge_invulnerable(player: &Player, timestamp: Timestamp) -> bool {
+    let now_ms = (timestamp.to_micros_since_unix_epoch() / 1000) as u64;
+    player.dodge_start_time_ms > 0 && now_ms < player.dodge_start_time_ms + DODGE_INVULNERABILITY_MS
+}
+
 // --- Client-Requested Random Respawn Reducer ---
 #[spacetimedb::reducer]
 pub fn respawn_randomly(ctx: &ReducerContext) -> Result<(), String> { // Renamed function
@@ -1285,7 +1410,7 @@ pub fn respawn_randomly(ctx: &ReducerContext) -> Result<(), String> { // Renamed
 
     // --- Grant Starting Rock ---
     log::info!("Granting starting Rock to respawned player: {}", player.username);
-    let opt_instance_id = crate::items::add_item_to_player_inventory(ctx, sender_id, rock_item_def_id, 1)?;
+    let opt_instance_id = crate::overflow_backpack::add_item_to_player_inventory_or_overflow(ctx, sender_id, rock_item_def_id, 1)?;
     match opt_instance_id {
         Some(new_rock_instance_id) => {
             let _ = log::info!("Granted 1 Rock (ID: {}) to player {}.", new_rock_instance_id, player.username);
@@ -1304,7 +1429,7 @@ pub fn respawn_randomly(ctx: &ReducerContext) -> Result<(), String> { // Renamed
     match item_defs.iter().find(|def| def.name == "Torch") {
         Some(torch_def) => {
             log::info!("Granting starting Torch to respawned player: {}", player.username);
-            match crate::items::add_item_to_player_inventory(ctx, sender_id, torch_def.id, 1)? {
+            match crate::overflow_backpack::add_item_to_player_inventory_or_overflow(ctx, sender_id, torch_def.id, 1)? {
                 Some(new_torch_instance_id) => {
                     log::info!("Granted 1 Torch (ID: {}) to player {}.", new_torch_instance_id, player.username);
                 }
@@ -1326,8 +1451,10 @@ pub fn respawn_randomly(ctx: &ReducerContext) -> Result<(), String> { // Renamed
     player.warmth = 100.0;
     player.stamina = 100.0;
     player.jump_start_time_ms = 0;
+    player.dodge_start_time_ms = 0;
     player.is_sprinting = false;
     player.is_dead = false; // Mark as alive again
+    crate::spectator::end_spectating(ctx, sender_id);
     player.death_timestamp = None; // Clear death timestamp
     player.last_hit_time = None;
     player.is_torch_lit = false; // Ensure torch is unlit on respawn
@@ -1357,8 +1484,7 @@ pub fn respawn_randomly(ctx: &ReducerContext) -> Result<(), String> { // Renamed
         log::warn!("Could not find a guaranteed safe random spawn point for player {:?} after {} attempts. Spawning anyway.", sender_id, MAX_SPAWN_ATTEMPTS);
     }
 
-    player.position_x = spawn_x;
-    player.position_y = spawn_y;
+    player.set_position(spawn_x, spawn_y);
     player.direction = "down".to_string();
 
     // --- Update Timestamp ---
@@ -1367,6 +1493,8 @@ pub fn respawn_randomly(ctx: &ReducerContext) -> Result<(), String> { // Renamed
 
     // --- Apply Player Changes ---
     players.identity().update(player);
+    spatial_grid::track_entity(ctx, spatial_grid::EntityType::Player(sender_id), spawn_x, spawn_y);
+    crate::active_effects::apply_spawn_protection(ctx, sender_id);
     log::info!("Player {:?} respawned randomly at ({:.1}, {:.1}).", sender_id, spawn_x, spawn_y);
 
     // Ensure item is unequipped on respawn
@@ -1400,6 +1528,8 @@ pub fn update_viewport(ctx: &ReducerContext, min_x: f32, min_y: f32, max_x: f32,
         last_update: ctx.timestamp,
     };
 
+    crate::interest_management::update_visible_chunks(ctx, client_id, min_x, min_y, max_x, max_y);
+
     // Use insert_or_update logic
     if viewports.client_identity().find(&client_id).is_some() {
         viewports.client_identity().update(viewport_data);
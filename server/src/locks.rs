@@ -0,0 +1,254 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines locks that can be attached to storage containers and doors. An     *
+ * unlocked container or door can be used by anyone; once a Lock item is      *
+ * attached, only the lock's owner, their teammates and players explicitly    *
+ * authorized on the lock can open it. Container and door modules call        *
+ * `check_lock_access` from their interaction validation.                     *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp, log};
+
+use crate::player as PlayerTableTrait;
+use crate::items::{
+    inventory_item as InventoryItemTableTrait,
+    item_definition as ItemDefinitionTableTrait,
+    add_item_to_player_inventory,
+};
+use crate::models::ItemLocation;
+use crate::player_inventory::get_player_item;
+use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
+use crate::large_storage_chest::large_storage_chest as LargeStorageChestTableTrait;
+use crate::building::{BuildingPieceType, building_piece as BuildingPieceTableTrait};
+use crate::locks::attached_lock as AttachedLockTableTrait;
+use crate::locks::lock_authorization as LockAuthorizationTableTrait;
+
+// --- Constants ---
+const LOCK_ITEM_NAME: &str = "Lock";
+const LOCK_INTERACTION_DISTANCE_SQUARED: f32 = 96.0 * 96.0;
+
+/// What a lock is attached to.
+#[derive(SpacetimeType, Copy, Clone, Debug, PartialEq)]
+pub enum LockTargetType {
+    WoodenStorageBox,
+    LargeStorageChest,
+    Door,
+}
+
+/// --- Lock Data Structure ---
+/// A lock attached to a single container or door. There is at most one lock per target.
+#[spacetimedb::table(name = attached_lock, public)]
+#[derive(Clone, Debug)]
+pub struct Lock {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub target_type: LockTargetType,
+    #[index(btree)]
+    pub target_id: u32,
+    pub owner_id: Identity,
+    pub attached_at: Timestamp,
+}
+
+/// --- Lock Authorization ---
+/// Grants a player (other than the owner and their team) access to a lock.
+#[spacetimedb::table(name = lock_authorization, public)]
+#[derive(Clone, Debug)]
+pub struct LockAuthorization {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub lock_id: u64,
+    pub player_id: Identity,
+}
+
+/******************************************************************************
+ *                                 REDUCERS                                   *
+ ******************************************************************************/
+
+/// Consumes a Lock item from the sender's inventory and attaches it to a container or door.
+/// Only the target's owner or their teammates can lock it.
+#[spacetimedb::reducer]
+pub fn attach_lock(ctx: &ReducerContext, item_instance_id: u64, target_type: LockTargetType, target_id: u32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let lock_item = get_player_item(ctx, item_instance_id)?;
+    let lock_def = ctx.db.item_definition().id().find(lock_item.item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found.", lock_item.item_def_id))?;
+    if lock_def.name != LOCK_ITEM_NAME {
+        return Err("Item is not a Lock.".to_string());
+    }
+    if !matches!(lock_item.location, ItemLocation::Inventory(_) | ItemLocation::Hotbar(_)) {
+        return Err("Lock must be in your inventory or hotbar.".to_string());
+    }
+
+    let target_owner = validate_lock_target(ctx, target_type, target_id)?;
+    if !crate::team::is_owner_or_teammate(ctx, sender_id, target_owner) {
+        return Err("You can only lock your own (or your team's) containers and doors.".to_string());
+    }
+    if find_lock(ctx, target_type, target_id).is_some() {
+        return Err("That already has a lock.".to_string());
+    }
+
+    let inventory_items = ctx.db.inventory_item();
+    if lock_item.quantity > 1 {
+        let mut remaining = lock_item.clone();
+        remaining.quantity -= 1;
+        inventory_items.instance_id().update(remaining);
+    } else {
+        inventory_items.instance_id().delete(item_instance_id);
+    }
+
+    let lock = ctx.db.attached_lock().insert(Lock {
+        id: 0, // Auto-incremented
+        target_type,
+        target_id,
+        owner_id: sender_id,
+        attached_at: ctx.timestamp,
+    });
+    log::info!("Player {:?} attached lock {} to {:?} {}.", sender_id, lock.id, target_type, target_id);
+    Ok(())
+}
+
+/// Removes the lock from a container or door and returns the Lock item to the sender.
+/// Only players with access to the lock can remove it.
+#[spacetimedb::reducer]
+pub fn remove_lock(ctx: &ReducerContext, target_type: LockTargetType, target_id: u32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    validate_lock_target(ctx, target_type, target_id)?;
+    let lock = find_lock(ctx, target_type, target_id)
+        .ok_or_else(|| "There is no lock on that.".to_string())?;
+    if !has_lock_access(ctx, &lock, sender_id) {
+        return Err("You are not authorized on this lock.".to_string());
+    }
+
+    let lock_def = ctx.db.item_definition().iter()
+        .find(|def| def.name == LOCK_ITEM_NAME)
+        .ok_or_else(|| "ItemDefinition for 'Lock' not found.".to_string())?;
+    add_item_to_player_inventory(ctx, sender_id, lock_def.id, 1)
+        .map_err(|e| format!("Could not return the lock to your inventory: {}", e))?;
+
+    delete_lock(ctx, lock.id);
+    log::info!("Player {:?} removed lock {} from {:?} {}.", sender_id, lock.id, target_type, target_id);
+    Ok(())
+}
+
+/// Grants another player access to a lock. Only the lock's owner can authorize players.
+#[spacetimedb::reducer]
+pub fn authorize_player_on_lock(ctx: &ReducerContext, target_type: LockTargetType, target_id: u32, player_id: Identity) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    validate_lock_target(ctx, target_type, target_id)?;
+    let lock = find_lock(ctx, target_type, target_id)
+        .ok_or_else(|| "There is no lock on that.".to_string())?;
+    if lock.owner_id != sender_id {
+        return Err("Only the lock's owner can authorize players.".to_string());
+    }
+    if ctx.db.player().identity().find(player_id).is_none() {
+        return Err("Player to authorize not found.".to_string());
+    }
+    if player_id == lock.owner_id || is_authorized(ctx, lock.id, player_id) {
+        return Err("That player already has access.".to_string());
+    }
+
+    ctx.db.lock_authorization().insert(LockAuthorization {
+        id: 0, // Auto-incremented
+        lock_id: lock.id,
+        player_id,
+    });
+    log::info!("Player {:?} authorized {:?} on lock {}.", sender_id, player_id, lock.id);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Returns Ok if the target is unlocked or the player has access to its lock.
+/// Called by container and door interaction checks.
+pub(crate) fn check_lock_access(ctx: &ReducerContext, target_type: LockTargetType, target_id: u32, player_id: Identity) -> Result<(), String> {
+    match find_lock(ctx, target_type, target_id) {
+        Some(lock) if !has_lock_access(ctx, &lock, player_id) => Err("It's locked.".to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Removes the lock (and its authorizations) from a target that is being picked up, giving
+/// the Lock item to the player picking it up. Errors (so the pickup fails) if it won't fit.
+pub(crate) fn return_lock_for_picked_up_target(ctx: &ReducerContext, player_id: Identity, target_type: LockTargetType, target_id: u32) -> Result<(), String> {
+    let Some(lock) = find_lock(ctx, target_type, target_id) else {
+        return Ok(());
+    };
+    let lock_def = ctx.db.item_definition().iter()
+        .find(|def| def.name == LOCK_ITEM_NAME)
+        .ok_or_else(|| "ItemDefinition for 'Lock' not found.".to_string())?;
+    add_item_to_player_inventory(ctx, player_id, lock_def.id, 1)
+        .map_err(|e| format!("Could not return the lock to your inventory: {}", e))?;
+    delete_lock(ctx, lock.id);
+    log::info!("Returned lock {} from picked up {:?} {} to player {:?}.", lock.id, target_type, target_id, player_id);
+    Ok(())
+}
+
+/// Removes the lock (and its authorizations) from a target that was destroyed. The Lock
+/// item is lost with it.
+pub(crate) fn remove_locks_for_target(ctx: &ReducerContext, target_type: LockTargetType, target_id: u32) {
+    if let Some(lock) = find_lock(ctx, target_type, target_id) {
+        delete_lock(ctx, lock.id);
+    }
+}
+
+fn find_lock(ctx: &ReducerContext, target_type: LockTargetType, target_id: u32) -> Option<Lock> {
+    ctx.db.attached_lock().target_id().filter(target_id).find(|lock| lock.target_type == target_type)
+}
+
+/// The owner, the owner's teammates and explicitly authorized players can use a lock.
+fn has_lock_access(ctx: &ReducerContext, lock: &Lock, player_id: Identity) -> bool {
+    crate::team::is_owner_or_teammate(ctx, player_id, lock.owner_id) || is_authorized(ctx, lock.id, player_id)
+}
+
+fn is_authorized(ctx: &ReducerContext, lock_id: u64, player_id: Identity) -> bool {
+    ctx.db.lock_authorization().lock_id().filter(lock_id).any(|auth| auth.player_id == player_id)
+}
+
+fn delete_lock(ctx: &ReducerContext, lock_id: u64) {
+    let authorizations = ctx.db.lock_authorization();
+    let auth_ids: Vec<u64> = authorizations.lock_id().filter(lock_id).map(|auth| auth.id).collect();
+    for auth_id in auth_ids {
+        authorizations.id().delete(auth_id);
+    }
+    ctx.db.attached_lock().id().delete(lock_id);
+}
+
+/// Checks the target exists, is intact and within reach of the sender. Returns the target's owner.
+fn validate_lock_target(ctx: &ReducerContext, target_type: LockTargetType, target_id: u32) -> Result<Identity, String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or_else(|| "Player not found".to_string())?;
+
+    let (pos_x, pos_y, owner_id) = match target_type {
+        LockTargetType::WoodenStorageBox => {
+            let storage_box = ctx.db.wooden_storage_box().id().find(target_id)
+                .filter(|b| !b.is_destroyed)
+                .ok_or_else(|| format!("Storage Box {} not found", target_id))?;
+            (storage_box.pos_x, storage_box.pos_y, storage_box.placed_by)
+        }
+        LockTargetType::LargeStorageChest => {
+            let chest = ctx.db.large_storage_chest().id().find(target_id)
+                .filter(|c| !c.is_destroyed)
+                .ok_or_else(|| format!("Large chest {} not found", target_id))?;
+            (chest.pos_x, chest.pos_y, chest.placed_by)
+        }
+        LockTargetType::Door => {
+            let door = ctx.db.building_piece().id().find(target_id)
+                .filter(|p| !p.is_destroyed && p.piece_type == BuildingPieceType::Door)
+                .ok_or_else(|| format!("Door {} not found", target_id))?;
+            (door.pos_x, door.pos_y, door.placed_by)
+        }
+    };
+
+    let dx = player.position_x - pos_x;
+    let dy = player.position_y - pos_y;
+    if (dx * dx + dy * dy) > LOCK_INTERACTION_DISTANCE_SQUARED {
+        return Err("Too far away".to_string());
+    }
+    Ok(owner_id)
+}
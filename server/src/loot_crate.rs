@@ -0,0 +1,243 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines loot crates: breakable barrels and wooden crates placed by chunk   *
+ * generation (environment.rs), plus the crates raiders leave behind (see     *
+ * events.rs). Crates are broken through combat.rs and scatter loot rolled    *
+ * from the weighted LootTableEntry rows for their type. Loot lands as        *
+ * regular dropped items, so anything left uncollected despawns through the   *
+ * dropped_item schedule. Barrels and wooden crates respawn like trees and    *
+ * stones (`environment::check_resource_respawns`); raider crates are gone    *
+ * once broken, or after EVENT_CRATE_DESPAWN_SECS if nobody breaks them. The  *
+ * world has no roads yet, so world crates are scattered on open ground       *
+ * between trees and stones.                                                  *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{ReducerContext, SpacetimeType, Table, Timestamp, log};
+use rand::Rng;
+use std::time::Duration;
+
+use crate::dropped_item;
+use crate::environment::calculate_chunk_index;
+use crate::items::item_definition as ItemDefinitionTableTrait;
+use crate::spatial_grid::{EntityType, track_entity, untrack_entity};
+use crate::loot_crate::loot_crate as LootCrateTableTrait;
+use crate::loot_crate::loot_table as LootTableTableTrait;
+
+// --- Constants ---
+pub(crate) const LOOT_CRATE_COLLISION_RADIUS: f32 = 20.0;
+pub(crate) const LOOT_CRATE_COLLISION_Y_OFFSET: f32 = 10.0;
+pub(crate) const LOOT_CRATE_DENSITY_PERCENT: f32 = 0.0006;
+pub(crate) const MIN_LOOT_CRATE_DISTANCE_SQ: f32 = 200.0 * 200.0;
+pub(crate) const MIN_LOOT_CRATE_TREE_DISTANCE_SQ: f32 = 40.0 * 40.0;
+pub(crate) const MIN_LOOT_CRATE_STONE_DISTANCE_SQ: f32 = 40.0 * 40.0;
+/// Share of generated world crates that are barrels; the rest are wooden crates.
+pub(crate) const BARREL_SPAWN_CHANCE: f64 = 0.7;
+pub(crate) const MIN_LOOT_CRATE_RESPAWN_TIME_SECS: u64 = 600; // 10 minutes
+pub(crate) const MAX_LOOT_CRATE_RESPAWN_TIME_SECS: u64 = 1200; // 20 minutes
+/// Event crates nobody breaks are removed after this long.
+const EVENT_CRATE_DESPAWN_SECS: u64 = 900; // 15 minutes
+
+/// Default loot tables: (crate type, item name, weight, min quantity, max quantity).
+const DEFAULT_LOOT_TABLES: &[(LootCrateType, &str, u32, u32, u32)] = &[
+    (LootCrateType::Barrel, "Wood", 30, 20, 50),
+    (LootCrateType::Barrel, "Stone", 25, 20, 40),
+    (LootCrateType::Barrel, "Cloth", 20, 5, 15),
+    (LootCrateType::Barrel, "Charcoal", 15, 5, 15),
+    (LootCrateType::Barrel, "Metal Fragments", 10, 5, 15),
+    (LootCrateType::Barrel, "Scrap", 15, 5, 15),
+    (LootCrateType::WoodenCrate, "Metal Fragments", 30, 10, 30),
+    (LootCrateType::WoodenCrate, "Cloth", 20, 10, 20),
+    (LootCrateType::WoodenCrate, "Wooden Arrow", 20, 4, 12),
+    (LootCrateType::WoodenCrate, "Bandage", 15, 1, 2),
+    (LootCrateType::WoodenCrate, "Animal Fat", 15, 3, 8),
+    (LootCrateType::WoodenCrate, "Scrap", 20, 10, 25),
+    (LootCrateType::WoodenCrate, "Blueprint", 8, 1, 1),
+    (LootCrateType::RaiderCrate, "Metal Fragments", 35, 15, 40),
+    (LootCrateType::RaiderCrate, "Wooden Arrow", 25, 6, 15),
+    (LootCrateType::RaiderCrate, "Bandage", 20, 1, 3),
+    (LootCrateType::RaiderCrate, "Cloth", 20, 10, 25),
+    (LootCrateType::RaiderCrate, "Scrap", 25, 20, 40),
+    (LootCrateType::RaiderCrate, "Blueprint", 15, 1, 1),
+];
+
+#[derive(SpacetimeType, Copy, Clone, Debug, PartialEq)]
+pub enum LootCrateType {
+    Barrel,
+    WoodenCrate,
+    RaiderCrate,
+}
+
+impl LootCrateType {
+    pub(crate) fn max_health(&self) -> f32 {
+        match self {
+            LootCrateType::Barrel => 30.0,
+            LootCrateType::WoodenCrate => 60.0,
+            LootCrateType::RaiderCrate => 50.0,
+        }
+    }
+
+    /// How many times the loot table is rolled when the crate breaks.
+    fn loot_rolls(&self) -> u32 {
+        match self {
+            LootCrateType::Barrel => 2,
+            LootCrateType::WoodenCrate => 3,
+            LootCrateType::RaiderCrate => 4,
+        }
+    }
+
+    /// World crates respawn where they stood; event crates are removed once broken.
+    pub(crate) fn respawns(&self) -> bool {
+        !matches!(self, LootCrateType::RaiderCrate)
+    }
+}
+
+/// --- Loot Crate Data Structure ---
+/// A broken world crate keeps its row with zero health until `respawn_at`.
+#[spacetimedb::table(name = loot_crate, public)]
+#[derive(Clone, Debug)]
+pub struct LootCrate {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub crate_type: LootCrateType,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    #[index(btree)]
+    pub chunk_index: u32,
+    pub health: f32,
+    pub max_health: f32,
+    pub last_hit_time: Option<Timestamp>,
+    pub respawn_at: Option<Timestamp>,
+    pub despawn_at: Option<Timestamp>, // Event crates only; removed at this time if still standing
+}
+
+/// --- Loot Table Entry ---
+/// One weighted drop for a crate type. Each roll picks a single entry with probability
+/// `weight / total weight` and drops between `min_quantity` and `max_quantity` of it.
+#[spacetimedb::table(name = loot_table, public)]
+#[derive(Clone, Debug)]
+pub struct LootTableEntry {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub crate_type: LootCrateType,
+    pub item_name: String,
+    pub weight: u32,
+    pub min_quantity: u32,
+    pub max_quantity: u32,
+}
+
+impl LootCrate {
+    /// Creates a full-health crate at the given position. Used by chunk generation and `spawn_loot_crate`.
+    pub(crate) fn new(crate_type: LootCrateType, pos_x: f32, pos_y: f32) -> Self {
+        let max_health = crate_type.max_health();
+        LootCrate {
+            id: 0, // Auto-incremented
+            crate_type,
+            pos_x,
+            pos_y,
+            chunk_index: calculate_chunk_index(pos_x, pos_y),
+            health: max_health,
+            max_health,
+            last_hit_time: None,
+            respawn_at: None,
+            despawn_at: None,
+        }
+    }
+}
+
+/// Seeds the default loot tables if none exist yet. Called on connect after the items are seeded.
+pub fn seed_loot_tables(ctx: &ReducerContext) -> Result<(), String> {
+    let loot_tables = ctx.db.loot_table();
+    if loot_tables.iter().count() > 0 {
+        return Ok(());
+    }
+    for &(crate_type, item_name, weight, min_quantity, max_quantity) in DEFAULT_LOOT_TABLES {
+        loot_tables.try_insert(LootTableEntry {
+            id: 0, // Auto-incremented
+            crate_type,
+            item_name: item_name.to_string(),
+            weight,
+            min_quantity,
+            max_quantity,
+        }).map_err(|e| format!("Failed to seed loot table entry {:?}/{}: {}", crate_type, item_name, e))?;
+    }
+    log::info!("Seeded {} loot table entries.", DEFAULT_LOOT_TABLES.len());
+    Ok(())
+}
+
+/// Places a new crate in the world and registers it with the spatial grid. Crates that
+/// don't respawn (event crates) get a `despawn_at`.
+pub(crate) fn spawn_loot_crate(ctx: &ReducerContext, crate_type: LootCrateType, pos_x: f32, pos_y: f32) -> Result<LootCrate, String> {
+    let mut new_crate = LootCrate::new(crate_type, pos_x, pos_y);
+    if !crate_type.respawns() {
+        new_crate.despawn_at = Some(ctx.timestamp + Duration::from_secs(EVENT_CRATE_DESPAWN_SECS));
+    }
+    let loot_crate = ctx.db.loot_crate().try_insert(new_crate)
+        .map_err(|e| format!("Failed to spawn loot crate: {}", e))?;
+    track_entity(ctx, EntityType::LootCrate(loot_crate.id), pos_x, pos_y);
+    log::info!("[LootCrate] Spawned {:?} {} at ({:.1}, {:.1}).", crate_type, loot_crate.id, pos_x, pos_y);
+    Ok(loot_crate)
+}
+
+/// Removes event crates whose `despawn_at` has passed. Called from the raid event schedule.
+pub(crate) fn despawn_expired_event_crates(ctx: &ReducerContext) {
+    let crates = ctx.db.loot_crate();
+    let expired: Vec<u64> = crates.iter()
+        .filter(|c| c.despawn_at.is_some_and(|at| ctx.timestamp >= at))
+        .map(|c| c.id)
+        .collect();
+    for crate_id in &expired {
+        crates.id().delete(*crate_id);
+        untrack_entity(ctx, EntityType::LootCrate(*crate_id));
+    }
+    if !expired.is_empty() {
+        log::info!("[LootCrate] Despawned {} unopened event crates.", expired.len());
+    }
+}
+
+/// Rolls the crate type's loot table and scatters the results around the crate. Called by combat.rs when it breaks.
+pub(crate) fn drop_crate_loot(ctx: &ReducerContext, loot_crate: &LootCrate, rng: &mut impl Rng) {
+    let entries: Vec<LootTableEntry> = ctx.db.loot_table().iter()
+        .filter(|entry| entry.crate_type == loot_crate.crate_type && entry.weight > 0)
+        .collect();
+    let total_weight: u32 = entries.iter().map(|entry| entry.weight).sum();
+    if total_weight == 0 {
+        log::warn!("[LootCrate] No loot table entries for {:?}.", loot_crate.crate_type);
+        return;
+    }
+
+    // Roll first, then merge repeated picks into one stack per item
+    let mut drops: Vec<(String, u32)> = Vec::new();
+    for _ in 0..loot_crate.crate_type.loot_rolls() {
+        let mut roll = rng.gen_range(0..total_weight);
+        let Some(entry) = entries.iter().find(|entry| {
+            if roll < entry.weight {
+                return true;
+            }
+            roll -= entry.weight;
+            false
+        }) else {
+            continue;
+        };
+        let quantity = rng.gen_range(entry.min_quantity..=entry.max_quantity.max(entry.min_quantity));
+        match drops.iter_mut().find(|(name, _)| *name == entry.item_name) {
+            Some((_, total)) => *total += quantity,
+            None => drops.push((entry.item_name.clone(), quantity)),
+        }
+    }
+
+    let item_defs = ctx.db.item_definition();
+    for (item_name, quantity) in drops {
+        let Some(def) = item_defs.iter().find(|d| d.name == item_name) else {
+            log::error!("[LootCrate] Item definition '{}' not found.", item_name);
+            continue;
+        };
+        let offset_x = (rng.gen::<f32>() - 0.5) * 2.0 * 25.0; // Spread within +/- 25px
+        let offset_y = (rng.gen::<f32>() - 0.5) * 2.0 * 25.0;
+        if let Err(e) = dropped_item::create_dropped_item_entity(ctx, def.id, quantity, loot_crate.pos_x + offset_x, loot_crate.pos_y + offset_y) {
+            log::error!("[LootCrate] Failed to drop {} {}: {}", quantity, item_name, e);
+        }
+    }
+}
@@ -0,0 +1,188 @@
+/******************************************************************************
+ *                                                                            *
+ * World map summary for the client minimap. One map_tile_summary row per     *
+ * chunk holds its biome, how much of it is water and how many resources it   *
+ * has, so the client can draw the whole map without subscribing to every     *
+ * tree and stone. Rows are seeded for the entire world when the environment  *
+ * is first seeded, with biome and water worked out from the world seed.      *
+ * Resource counts fill in when a chunk is generated (environment.rs) and a   *
+ * periodic refresh rewrites a row only once its counts change noticeably.    *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{ReducerContext, Table, Timestamp, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+
+use crate::{WORLD_WIDTH_TILES, WORLD_HEIGHT_TILES};
+use crate::environment::{Biome, biome_for_chunk, CHUNK_SIZE_TILES, WORLD_WIDTH_CHUNKS, WORLD_HEIGHT_CHUNKS};
+use crate::tree::tree as TreeTableTrait;
+use crate::stone::stone as StoneTableTrait;
+use crate::mushroom::mushroom as MushroomTableTrait;
+use crate::corn::corn as CornTableTrait;
+use crate::pumpkin::pumpkin as PumpkinTableTrait;
+use crate::hemp::hemp as HempTableTrait;
+use crate::berry_bush::berry_bush as BerryBushTableTrait;
+use crate::environment::generated_chunk as GeneratedChunkTableTrait;
+use crate::world_state::world_state as WorldStateTableTrait;
+use crate::map_summary::map_tile_summary as MapTileSummaryTableTrait;
+use crate::map_summary::map_summary_refresh_schedule as MapSummaryRefreshScheduleTableTrait;
+
+// --- Constants ---
+const MAP_SUMMARY_REFRESH_INTERVAL_SECS: u64 = 60;
+/// A refresh rewrites a row once its resource count has moved by this fraction...
+const SIGNIFICANT_CHANGE_FRACTION: f32 = 0.25;
+/// ...and by at least this many resources, so nearly empty chunks don't churn.
+const MIN_SIGNIFICANT_CHANGE: u32 = 3;
+
+/// --- Map Tile Summary ---
+/// Minimap data for one chunk. `resource_density` is resources per land tile.
+#[spacetimedb::table(name = map_tile_summary, public)]
+#[derive(Clone, Debug)]
+pub struct MapTileSummary {
+    #[primary_key]
+    pub chunk_index: u32,
+    pub chunk_x: u32,
+    pub chunk_y: u32,
+    pub biome: Biome,
+    pub water_tiles: u32,
+    pub has_water: bool,
+    pub is_generated: bool, // Resource counts stay 0 until the chunk is generated
+    pub tree_count: u32,
+    pub stone_count: u32,
+    pub plant_count: u32, // Mushrooms, corn, pumpkins, hemp and berry bushes
+    pub resource_density: f32,
+    pub updated_at: Timestamp,
+}
+
+#[spacetimedb::table(name = map_summary_refresh_schedule, scheduled(refresh_map_summaries))]
+#[derive(Clone, Debug)]
+pub struct MapSummaryRefreshSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// Scheduled reducer: recounts the resources of every generated chunk and rewrites the
+/// summaries that changed significantly.
+#[spacetimedb::reducer]
+pub fn refresh_map_summaries(ctx: &ReducerContext, _schedule: MapSummaryRefreshSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("refresh_map_summaries may only be called by the scheduler.".to_string());
+    }
+    let chunk_indices: Vec<u32> = ctx.db.generated_chunk().iter().map(|c| c.chunk_index).collect();
+    let mut updated = 0;
+    for chunk_index in chunk_indices {
+        if refresh_chunk_summary(ctx, chunk_index, false) {
+            updated += 1;
+        }
+    }
+    if updated > 0 {
+        log::debug!("[MapSummary] Refreshed {} chunk summaries.", updated);
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+pub(crate) fn init_map_summary_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.map_summary_refresh_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting map summary refresh schedule (every {}s).", MAP_SUMMARY_REFRESH_INTERVAL_SECS);
+        let interval = Duration::from_secs(MAP_SUMMARY_REFRESH_INTERVAL_SECS);
+        schedule_table.try_insert(MapSummaryRefreshSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert map summary refresh schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Seeds a summary row for every chunk of the world if there are none yet, and fills in
+/// the resources of chunks that are already generated. Called from `seed_environment`.
+pub(crate) fn seed_map_summaries(ctx: &ReducerContext) -> Result<(), String> {
+    let summaries = ctx.db.map_tile_summary();
+    if summaries.iter().count() > 0 {
+        return Ok(());
+    }
+    let world_seed = ctx.db.world_state().iter().next()
+        .map(|world_state| world_state.world_seed)
+        .ok_or_else(|| "WorldState not seeded; cannot build the map summary.".to_string())?;
+
+    for chunk_index in 0..WORLD_WIDTH_CHUNKS * WORLD_HEIGHT_CHUNKS {
+        let chunk_x = chunk_index % WORLD_WIDTH_CHUNKS;
+        let chunk_y = chunk_index / WORLD_WIDTH_CHUNKS;
+        let min_tile_x = chunk_x * CHUNK_SIZE_TILES;
+        let min_tile_y = chunk_y * CHUNK_SIZE_TILES;
+        let water_tiles = crate::world_tile::water_tiles_in_range(
+            world_seed,
+            min_tile_x, (min_tile_x + CHUNK_SIZE_TILES).min(WORLD_WIDTH_TILES),
+            min_tile_y, (min_tile_y + CHUNK_SIZE_TILES).min(WORLD_HEIGHT_TILES),
+        ).len() as u32;
+        summaries.try_insert(MapTileSummary {
+            chunk_index,
+            chunk_x,
+            chunk_y,
+            biome: biome_for_chunk(world_seed, chunk_index),
+            water_tiles,
+            has_water: water_tiles > 0,
+            is_generated: false,
+            tree_count: 0,
+            stone_count: 0,
+            plant_count: 0,
+            resource_density: 0.0,
+            updated_at: ctx.timestamp,
+        }).map_err(|e| format!("Failed to insert map summary for chunk {}: {}", chunk_index, e))?;
+    }
+
+    let generated: Vec<u32> = ctx.db.generated_chunk().iter().map(|c| c.chunk_index).collect();
+    for chunk_index in generated {
+        refresh_chunk_summary(ctx, chunk_index, true);
+    }
+    log::info!("Seeded map summaries for {} chunks.", WORLD_WIDTH_CHUNKS * WORLD_HEIGHT_CHUNKS);
+    Ok(())
+}
+
+/// Recounts the chunk's available resources and rewrites its summary if `force` is set,
+/// the chunk was just generated, or the count changed significantly. Returns whether the
+/// row was rewritten.
+pub(crate) fn refresh_chunk_summary(ctx: &ReducerContext, chunk_index: u32, force: bool) -> bool {
+    let summaries = ctx.db.map_tile_summary();
+    let Some(mut summary) = summaries.chunk_index().find(chunk_index) else { return false; };
+
+    let tree_count = ctx.db.tree().chunk_index().filter(chunk_index).filter(|t| t.respawn_at.is_none()).count() as u32;
+    let stone_count = ctx.db.stone().chunk_index().filter(chunk_index).filter(|s| s.respawn_at.is_none()).count() as u32;
+    let plant_count = [
+        ctx.db.mushroom().chunk_index().filter(chunk_index).filter(|p| p.respawn_at.is_none()).count(),
+        ctx.db.corn().chunk_index().filter(chunk_index).filter(|p| p.respawn_at.is_none()).count(),
+        ctx.db.pumpkin().chunk_index().filter(chunk_index).filter(|p| p.respawn_at.is_none()).count(),
+        ctx.db.hemp().chunk_index().filter(chunk_index).filter(|p| p.respawn_at.is_none()).count(),
+        ctx.db.berry_bush().chunk_index().filter(chunk_index).filter(|p| p.respawn_at.is_none()).count(),
+    ].iter().sum::<usize>() as u32;
+
+    let old_total = summary.tree_count + summary.stone_count + summary.plant_count;
+    let new_total = tree_count + stone_count + plant_count;
+    let change = old_total.abs_diff(new_total);
+    let significant = change >= MIN_SIGNIFICANT_CHANGE
+        && change as f32 >= old_total as f32 * SIGNIFICANT_CHANGE_FRACTION;
+    if !force && summary.is_generated && !significant {
+        return false;
+    }
+
+    let land_tiles = (CHUNK_SIZE_TILES * CHUNK_SIZE_TILES).saturating_sub(summary.water_tiles).max(1);
+    summary.is_generated = true;
+    summary.tree_count = tree_count;
+    summary.stone_count = stone_count;
+    summary.plant_count = plant_count;
+    summary.resource_density = new_total as f32 / land_tiles as f32;
+    summary.updated_at = ctx.timestamp;
+    summaries.chunk_index().update(summary);
+    true
+}
@@ -8,6 +8,12 @@ pub enum ContainerType {
     WoodenStorageBox,
     PlayerCorpse,
     Stash,
+    Furnace,
+    LargeStorageChest,
+    Recycler,
+    DroppedBackpack,
+    VendingMachine,
+    FlameTurret,
     // Other container types can be added here
 }
 
@@ -97,4 +103,74 @@ pub enum TargetType {
     Stash,
     SleepingBag,
     Animal, // Added for animal targets
-}
\ No newline at end of file
+    BuildingPiece, // Walls, doors, foundations
+    Furnace,
+    LargeStorageChest,
+    SleepingPlayer, // Body of an offline player
+    LootCrate, // Breakable crates that scatter loot
+    Workbench,
+    Recycler,
+    PlayerCorpse, // Fresh corpses can be harvested for bones
+    Sign,
+    TreeStump, // Left behind by a felled tree, chopped for extra wood
+    Mount, // Rideable horses (see mount.rs)
+    Switch, // Wired switches (see wiring.rs)
+    EnvironmentHazard, // Cacti, thorn bushes and hot stones (see environment_hazard.rs)
+    FlameTurret, // Fuelled defence (see flame_turret.rs)
+}
+
+/// Richness tier of a tree or stone node. Richer nodes have more health and yield more per hit;
+/// the world generator makes them more common the further a node is from the spawn point.
+#[derive(Debug, Clone, Copy, PartialEq, SpacetimeType, serde::Serialize, serde::Deserialize)]
+pub enum ResourceRichness {
+    Sparse,
+    Normal,
+    Rich,
+}
+
+impl ResourceRichness {
+    /// Multiplier applied to the yield range of hits on the node.
+    pub fn yield_multiplier(&self) -> f32 {
+        match self {
+            ResourceRichness::Sparse => 0.6,
+            ResourceRichness::Normal => 1.0,
+            ResourceRichness::Rich => 1.6,
+        }
+    }
+
+    /// Scales a node type's base health for this tier.
+    pub fn scale_health(&self, base_health: u32) -> u32 {
+        let multiplier = match self {
+            ResourceRichness::Sparse => 0.75,
+            ResourceRichness::Normal => 1.0,
+            ResourceRichness::Rich => 1.5,
+        };
+        (base_health as f32 * multiplier).round() as u32
+    }
+}
+
+/// How a hit on a player was dealt. Armor resists each type separately.
+#[derive(Debug, Clone, Copy, PartialEq, SpacetimeType, serde::Serialize, serde::Deserialize)]
+pub enum DamageType {
+    Melee,      // Swung weapons, tools and animal attacks
+    Projectile, // Arrows and explosion blasts
+    Burn,       // Fire damage over time
+    Radiation,  // Hazard zone exposure (see hazard_zone.rs)
+}
+/// What killed a player. Stored on the corpse and in the kill feed so clients can
+/// show the cause on the death screen.
+#[derive(Debug, Clone, Copy, PartialEq, SpacetimeType, serde::Serialize, serde::Deserialize)]
+pub enum DeathCause {
+    PvP,         // Killed by another player's attack
+    Animal,      // Killed by a wild animal
+    Explosion,   // Caught in an explosive blast
+    Starvation,  // Health drained by hunger
+    Dehydration, // Health drained by thirst
+    Cold,        // Health drained by low warmth
+    Burn,        // Fire damage over time
+    Bleed,       // Bleeding out
+    Drowning,    // Swimming with no stamina left
+    Radiation,   // Hazard zone exposure
+    Suicide,     // The /kill chat command
+    FallingTree, // Crushed by a tree someone felled
+}
@@ -0,0 +1,362 @@
+/******************************************************************************
+ *                                                                            *
+ * Player reports and sanctions. Players file reports with `report_player`;   *
+ * they go into the private PlayerReport queue, rate limited per reporter.    *
+ * Admins load the queue into their own ModerationReview rows (like the       *
+ * combat log lookup), resolve or dismiss reports, and sanction players.      *
+ * A PlayerSanction row holds a player's mute, ban and pending kick: chat.rs  *
+ * refuses messages from muted players, `identity_connected` turns banned     *
+ * players away, and clients disconnect when they see their kick flag, which  *
+ * is cleared on their next connection. Until then the server itself refuses  *
+ * movement, attacks, chat and container use from kicked or banned players.   *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{client_visibility_filter, Filter, Identity, ReducerContext, SpacetimeType, Table, Timestamp, TimeDuration, log};
+use std::time::Duration;
+
+use crate::admin;
+use crate::player as PlayerTableTrait;
+use crate::moderation::player_report as PlayerReportTableTrait;
+use crate::moderation::moderation_review as ModerationReviewTableTrait;
+use crate::moderation::player_sanction as PlayerSanctionTableTrait;
+
+// --- Constants ---
+const MAX_REPORT_REASON_LENGTH: usize = 200;
+const MAX_REPORTS_PER_WINDOW: usize = 5;
+const REPORT_RATE_WINDOW_SECS: i64 = 60 * 60;
+/// `review_reports` loads at most this many reports, oldest first.
+const MAX_REVIEW_RESULTS: usize = 200;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum ReportStatus {
+    Open,
+    Resolved, // Acted on
+    Dismissed, // No action needed
+}
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum SanctionKind {
+    Mute,
+    Kick,
+    Ban,
+}
+
+/// --- Player Report ---
+/// One report filed by a player. Private; admins read it through `review_reports`.
+#[spacetimedb::table(name = player_report)]
+#[derive(Clone, Debug)]
+pub struct PlayerReport {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub reporter_id: Identity,
+    #[index(btree)]
+    pub target_id: Identity,
+    pub target_username: String,
+    pub reason: String,
+    pub created_at: Timestamp,
+    pub status: ReportStatus,
+    pub handled_by: Option<Identity>,
+    pub handled_at: Option<Timestamp>,
+    pub resolution_note: Option<String>,
+}
+
+/// --- Moderation Review ---
+/// A copy of a report made for the admin reviewing the queue.
+/// Each admin only sees their own rows (see MODERATION_REVIEW_OWN_ROWS).
+#[spacetimedb::table(name = moderation_review, public)]
+#[derive(Clone, Debug)]
+pub struct ModerationReview {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub admin_id: Identity,
+    pub report: PlayerReport,
+}
+
+/// --- Player Sanction ---
+/// A player's current sanctions. Each player only sees their own row, so the client
+/// can show a mute notice or act on a kick.
+#[spacetimedb::table(name = player_sanction, public)]
+#[derive(Clone, Debug)]
+pub struct PlayerSanction {
+    #[primary_key]
+    pub player_id: Identity,
+    pub muted_until: Option<Timestamp>,
+    pub banned: bool,
+    pub banned_until: Option<Timestamp>, // None with `banned` set = permanent
+    pub kick_requested: bool, // Client disconnects when set; cleared on the next connection
+    pub reason: String, // Reason given for the latest sanction
+    pub issued_by: Identity,
+    pub issued_at: Timestamp,
+}
+
+// --- Row-Level Security Filters ---
+
+#[client_visibility_filter]
+const PLAYER_SANCTION_OWN_ROW: Filter = Filter::Sql(
+    "SELECT * FROM player_sanction WHERE player_id = :sender"
+);
+
+#[client_visibility_filter]
+const MODERATION_REVIEW_OWN_ROWS: Filter = Filter::Sql(
+    "SELECT * FROM moderation_review WHERE admin_id = :sender"
+);
+
+/******************************************************************************
+ *                                 REDUCERS                                   *
+ ******************************************************************************/
+
+/// Files a report against another player. Players can have one open report per target
+/// and file at most MAX_REPORTS_PER_WINDOW reports an hour.
+#[spacetimedb::reducer]
+pub fn report_player(ctx: &ReducerContext, target_id: Identity, reason: String) -> Result<(), String> {
+    let reporter_id = ctx.sender;
+    if target_id == reporter_id {
+        return Err("You can't report yourself.".to_string());
+    }
+    let target = ctx.db.player().identity().find(target_id)
+        .ok_or_else(|| "Player not found".to_string())?;
+    let reason = reason.trim().to_string();
+    if reason.is_empty() || reason.chars().count() > MAX_REPORT_REASON_LENGTH {
+        return Err(format!("Reason must be 1-{} characters.", MAX_REPORT_REASON_LENGTH));
+    }
+
+    let reports = ctx.db.player_report();
+    let window_start_micros = ctx.timestamp.to_micros_since_unix_epoch() - REPORT_RATE_WINDOW_SECS * 1_000_000;
+    let mut recent_count = 0;
+    for report in reports.reporter_id().filter(reporter_id) {
+        if report.target_id == target_id && report.status == ReportStatus::Open {
+            return Err("You already have an open report against this player.".to_string());
+        }
+        if report.created_at.to_micros_since_unix_epoch() > window_start_micros {
+            recent_count += 1;
+        }
+    }
+    if recent_count >= MAX_REPORTS_PER_WINDOW {
+        return Err("You've filed too many reports recently. Try again later.".to_string());
+    }
+
+    let report = reports.insert(PlayerReport {
+        id: 0, // Auto-incremented
+        reporter_id,
+        target_id,
+        target_username: target.username,
+        reason,
+        created_at: ctx.timestamp,
+        status: ReportStatus::Open,
+        handled_by: None,
+        handled_at: None,
+        resolution_note: None,
+    });
+    log::info!("[Moderation] Player {:?} reported {:?} (report {}).", reporter_id, target_id, report.id);
+    Ok(())
+}
+
+/// Admin only. Replaces the sender's ModerationReview rows with the open reports, oldest
+/// first, or with every report if `include_handled` is set.
+#[spacetimedb::reducer]
+pub fn review_reports(ctx: &ReducerContext, include_handled: bool) -> Result<(), String> {
+    let admin_id = ctx.sender;
+    if !admin::is_admin(ctx, admin_id) {
+        return Err("Only admins can review reports.".to_string());
+    }
+
+    let reviews = ctx.db.moderation_review();
+    let previous: Vec<u64> = reviews.admin_id().filter(admin_id).map(|r| r.id).collect();
+    for review_id in previous {
+        reviews.id().delete(review_id);
+    }
+
+    let mut queue: Vec<PlayerReport> = ctx.db.player_report().iter()
+        .filter(|r| include_handled || r.status == ReportStatus::Open)
+        .collect();
+    queue.sort_by_key(|r| r.created_at);
+    queue.truncate(MAX_REVIEW_RESULTS);
+
+    let count = queue.len();
+    for report in queue {
+        reviews.insert(ModerationReview {
+            id: 0, // Auto-incremented
+            admin_id,
+            report,
+        });
+    }
+    log::info!("[Moderation] Admin {:?} loaded {} reports for review.", admin_id, count);
+    Ok(())
+}
+
+/// Admin only. Closes a report as resolved (acted on) or dismissed, with an optional note.
+#[spacetimedb::reducer]
+pub fn resolve_report(ctx: &ReducerContext, report_id: u64, dismissed: bool, note: Option<String>) -> Result<(), String> {
+    if !admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can resolve reports.".to_string());
+    }
+    let reports = ctx.db.player_report();
+    let mut report = reports.id().find(report_id)
+        .ok_or_else(|| format!("Report {} not found.", report_id))?;
+    if report.status != ReportStatus::Open {
+        return Err("That report has already been handled.".to_string());
+    }
+    let note = note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty());
+    if note.as_ref().is_some_and(|n| n.chars().count() > MAX_REPORT_REASON_LENGTH) {
+        return Err(format!("Note must be at most {} characters.", MAX_REPORT_REASON_LENGTH));
+    }
+
+    report.status = if dismissed { ReportStatus::Dismissed } else { ReportStatus::Resolved };
+    report.handled_by = Some(ctx.sender);
+    report.handled_at = Some(ctx.timestamp);
+    report.resolution_note = note;
+    reports.id().update(report.clone());
+
+    // Keep the admin's review rows in step
+    let reviews = ctx.db.moderation_review();
+    let stale: Vec<ModerationReview> = reviews.admin_id().filter(ctx.sender)
+        .filter(|r| r.report.id == report_id)
+        .collect();
+    for mut review in stale {
+        review.report = report.clone();
+        reviews.id().update(review);
+    }
+    log::info!("[Moderation] Admin {:?} marked report {} as {:?}.", ctx.sender, report_id, report.status);
+    Ok(())
+}
+
+/// Admin only. Mutes or bans a player for `duration_secs` (permanently if None), or kicks them.
+#[spacetimedb::reducer]
+pub fn sanction_player(ctx: &ReducerContext, target_id: Identity, kind: SanctionKind, duration_secs: Option<u64>, reason: String) -> Result<(), String> {
+    let admin_id = ctx.sender;
+    if !admin::is_admin(ctx, admin_id) {
+        return Err("Only admins can sanction players.".to_string());
+    }
+    if target_id == admin_id {
+        return Err("You can't sanction yourself.".to_string());
+    }
+    if admin::is_admin(ctx, target_id) {
+        return Err("Admins can't be sanctioned.".to_string());
+    }
+    if duration_secs == Some(0) {
+        return Err("Duration must be greater than zero.".to_string());
+    }
+    let reason = reason.trim().to_string();
+    if reason.chars().count() > MAX_REPORT_REASON_LENGTH {
+        return Err(format!("Reason must be at most {} characters.", MAX_REPORT_REASON_LENGTH));
+    }
+
+    let until = duration_secs.map(|secs| ctx.timestamp + TimeDuration::from(Duration::from_secs(secs)));
+    let mut sanction = find_sanction(ctx, target_id).unwrap_or(PlayerSanction {
+        player_id: target_id,
+        muted_until: None,
+        banned: false,
+        banned_until: None,
+        kick_requested: false,
+        reason: String::new(),
+        issued_by: admin_id,
+        issued_at: ctx.timestamp,
+    });
+    match kind {
+        // A permanent mute is stored as a mute far in the future
+        SanctionKind::Mute => sanction.muted_until = Some(until.unwrap_or(Timestamp::from_micros_since_unix_epoch(i64::MAX))),
+        SanctionKind::Kick => sanction.kick_requested = true,
+        SanctionKind::Ban => {
+            sanction.banned = true;
+            sanction.banned_until = until;
+            sanction.kick_requested = true; // Get them off the server now as well
+        }
+    }
+    sanction.reason = reason;
+    sanction.issued_by = admin_id;
+    sanction.issued_at = ctx.timestamp;
+    save_sanction(ctx, sanction);
+
+    log::info!("[Moderation] Admin {:?} applied {:?} to {:?} for {:?}s.", admin_id, kind, target_id, duration_secs);
+    Ok(())
+}
+
+/// Admin only. Lifts a mute or ban early, or cancels a kick the player hasn't acted on yet.
+#[spacetimedb::reducer]
+pub fn lift_sanction(ctx: &ReducerContext, target_id: Identity, kind: SanctionKind) -> Result<(), String> {
+    if !admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can lift sanctions.".to_string());
+    }
+    let mut sanction = find_sanction(ctx, target_id)
+        .ok_or_else(|| "That player has no sanctions.".to_string())?;
+    match kind {
+        SanctionKind::Mute => sanction.muted_until = None,
+        SanctionKind::Kick => sanction.kick_requested = false,
+        SanctionKind::Ban => {
+            sanction.banned = false;
+            sanction.banned_until = None;
+        }
+    }
+    save_sanction(ctx, sanction);
+    log::info!("[Moderation] Admin {:?} lifted {:?} from {:?}.", ctx.sender, kind, target_id);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Returns an error if the player is muted. Called by chat before sending anything.
+pub(crate) fn ensure_not_muted(ctx: &ReducerContext, player_id: Identity) -> Result<(), String> {
+    match find_sanction(ctx, player_id).and_then(|s| s.muted_until) {
+        Some(until) if until > ctx.timestamp => Err("You are muted.".to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Returns an error if the player is banned or has a pending kick, so a player who is
+/// still connected can't keep playing after an admin removes them. Called by movement,
+/// attacks, chat and the container handlers.
+pub(crate) fn ensure_not_sanctioned(ctx: &ReducerContext, player_id: Identity) -> Result<(), String> {
+    let Some(sanction) = find_sanction(ctx, player_id) else {
+        return Ok(());
+    };
+    if sanction.banned && sanction.banned_until.is_none_or(|until| until > ctx.timestamp) {
+        return Err("You are banned from this server.".to_string());
+    }
+    if sanction.kick_requested {
+        return Err("You have been kicked from this server.".to_string());
+    }
+    Ok(())
+}
+
+/// Rejects banned players and clears a pending kick. Called from `identity_connected`.
+pub(crate) fn check_connection_allowed(ctx: &ReducerContext, player_id: Identity) -> Result<(), String> {
+    let Some(mut sanction) = find_sanction(ctx, player_id) else {
+        return Ok(());
+    };
+    if sanction.banned && sanction.banned_until.is_none_or(|until| until > ctx.timestamp) {
+        log::info!("[Moderation] Refused connection from banned player {:?}.", player_id);
+        return Err(format!("You are banned from this server. Reason: {}", sanction.reason));
+    }
+    if sanction.banned {
+        // Ban ran out
+        sanction.banned = false;
+        sanction.banned_until = None;
+    }
+    sanction.kick_requested = false;
+    save_sanction(ctx, sanction);
+    Ok(())
+}
+
+fn find_sanction(ctx: &ReducerContext, player_id: Identity) -> Option<PlayerSanction> {
+    ctx.db.player_sanction().player_id().find(player_id)
+}
+
+/// Writes the sanction, or removes the row once nothing is in effect any more.
+fn save_sanction(ctx: &ReducerContext, sanction: PlayerSanction) {
+    let sanctions = ctx.db.player_sanction();
+    let muted = sanction.muted_until.is_some_and(|until| until > ctx.timestamp);
+    if !muted && !sanction.banned && !sanction.kick_requested {
+        sanctions.player_id().delete(sanction.player_id);
+    } else if sanctions.player_id().find(sanction.player_id).is_some() {
+        sanctions.player_id().update(sanction);
+    } else {
+        sanctions.insert(sanction);
+    }
+}
@@ -0,0 +1,297 @@
+/******************************************************************************
+ *                                                                            *
+ * Rideable mounts. Horses roam the world and any player can `mount_up` on a  *
+ * riderless one nearby and `dismount` again. While riding, movement in       *
+ * lib.rs `move_player` uses the mount's speed and larger collision radius,   *
+ * and sprinting drains the mount's stamina instead of the rider's. The       *
+ * rider carries the mount along; it stays where it was left when they get    *
+ * off. Horses don't swim: riding into water throws the rider off. Mounts     *
+ * are damaged through combat.rs and are gone for good when killed; a         *
+ * scheduled tick recovers their stamina and keeps the herd topped up.        *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+use rand::{Rng, SeedableRng};
+
+use crate::{PLAYER_RADIUS, WORLD_WIDTH_PX, WORLD_HEIGHT_PX};
+use crate::Player;
+use crate::player as PlayerTableTrait;
+use crate::utils::get_distance_squared;
+use crate::environment::{calculate_chunk_index, sync_chunk_index};
+use crate::spatial_grid::{EntityType, track_entity, untrack_entity};
+use crate::mount::mount as MountTableTrait;
+use crate::mount::mount_schedule as MountScheduleTableTrait;
+
+// --- Constants ---
+const MOUNT_TICK_INTERVAL_SECS: u64 = 1;
+const TARGET_HORSE_COUNT: usize = 12;
+/// Farthest a player can be from a mount to get on it.
+const MOUNT_INTERACT_DISTANCE: f32 = 96.0;
+/// Horses never spawn this close to a living player.
+const MIN_SPAWN_DISTANCE_FROM_PLAYER: f32 = 800.0;
+const MAX_SPAWN_ATTEMPTS: u32 = 10;
+
+/// Per-type tuning values.
+pub(crate) struct MountStats {
+    pub max_health: f32,
+    pub max_stamina: f32,
+    /// Multiplies the rider's walking speed; sprinting applies on top of this.
+    pub speed_multiplier: f32,
+    /// Used in place of PLAYER_RADIUS for the rider's collisions.
+    pub collision_radius: f32,
+    pub stamina_drain_per_second: f32, // While sprinting
+    pub stamina_recovery_per_second: f32, // While not sprinting
+}
+
+const HORSE_STATS: MountStats = MountStats {
+    max_health: 250.0,
+    max_stamina: 100.0,
+    speed_multiplier: 1.6,
+    collision_radius: 48.0,
+    stamina_drain_per_second: 8.0,
+    stamina_recovery_per_second: 5.0,
+};
+
+#[derive(SpacetimeType, Copy, Clone, Debug, PartialEq)]
+pub enum MountType {
+    Horse,
+}
+
+impl MountType {
+    pub(crate) fn stats(&self) -> &'static MountStats {
+        match self {
+            MountType::Horse => &HORSE_STATS,
+        }
+    }
+}
+
+/// --- Mount Data Structure ---
+/// A rideable animal. `rider_id` is the player currently on it, if any.
+#[spacetimedb::table(name = mount, public)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mount {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub mount_type: MountType,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    #[index(btree)]
+    pub chunk_index: u32,
+    pub health: f32,
+    pub max_health: f32,
+    pub stamina: f32,
+    pub rider_id: Option<Identity>,
+    pub last_hit_time: Option<Timestamp>,
+}
+
+/// --- Mount Schedule ---
+#[spacetimedb::table(name = mount_schedule, scheduled(process_mounts))]
+#[derive(Clone, Debug)]
+pub struct MountSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/// Starts the mount tick if it isn't already scheduled. Called from `init_module`.
+pub fn init_mount_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.mount_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting mount schedule (every {}s).", MOUNT_TICK_INTERVAL_SECS);
+        let interval = Duration::from_secs(MOUNT_TICK_INTERVAL_SECS);
+        schedule_table.try_insert(MountSchedule {
+            id: 0,
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert mount schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Mount Up ---
+/// Gets on a nearby riderless mount. The rider moves onto the mount's position.
+#[spacetimedb::reducer]
+pub fn mount_up(ctx: &ReducerContext, mount_id: u64) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let sender_id = ctx.sender;
+    let mut player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Player not found".to_string())?;
+    if player.is_dead {
+        return Err("Cannot mount while dead.".to_string());
+    }
+    if ridden_mount(ctx, sender_id).is_some() {
+        return Err("You are already riding.".to_string());
+    }
+    if crate::world_tile::is_water_at(ctx, player.position_x, player.position_y) {
+        return Err("Cannot mount while swimming.".to_string());
+    }
+
+    let mounts = ctx.db.mount();
+    let mut mount = mounts.id().find(mount_id)
+        .ok_or_else(|| "Mount not found.".to_string())?;
+    if mount.rider_id.is_some() {
+        return Err("Someone is already riding that.".to_string());
+    }
+    if get_distance_squared(player.position_x, player.position_y, mount.pos_x, mount.pos_y) > MOUNT_INTERACT_DISTANCE * MOUNT_INTERACT_DISTANCE {
+        return Err("Too far away to mount.".to_string());
+    }
+
+    mount.rider_id = Some(sender_id);
+    player.set_position(mount.pos_x, mount.pos_y);
+    player.is_crouching = false;
+    player.last_update = ctx.timestamp;
+    track_entity(ctx, EntityType::Player(sender_id), player.position_x, player.position_y);
+    ctx.db.player().identity().update(player);
+    mounts.id().update(mount);
+    crate::channeled_use::cancel_channel(ctx, sender_id);
+    log::info!("[Mount] Player {:?} mounted {}.", sender_id, mount_id);
+    Ok(())
+}
+
+/// --- Dismount ---
+/// Gets off the mount the player is riding. The mount stays where it is.
+#[spacetimedb::reducer]
+pub fn dismount(ctx: &ReducerContext) -> Result<(), String> {
+    if !dismount_player(ctx, ctx.sender) {
+        return Err("You are not riding anything.".to_string());
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                                 MOUNT TICK                                 *
+ ******************************************************************************/
+
+/// Scheduled reducer: drops riders who went offline or died, recovers the stamina of
+/// mounts that aren't sprinting, and spawns horses up to the target count.
+#[spacetimedb::reducer]
+pub fn process_mounts(ctx: &ReducerContext, _schedule: MountSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("process_mounts may only be called by the scheduler.".to_string());
+    }
+
+    let players = ctx.db.player();
+    let delta_secs = MOUNT_TICK_INTERVAL_SECS as f32;
+    let mounts: Vec<Mount> = ctx.db.mount().iter().collect();
+    for mut mount in mounts {
+        let before = mount.clone();
+        let rider = mount.rider_id.and_then(|id| players.identity().find(id));
+        if mount.rider_id.is_some() && !rider.as_ref().is_some_and(|p| p.is_online && !p.is_dead) {
+            log::info!("[Mount] Rider {:?} left mount {}.", mount.rider_id, mount.id);
+            mount.rider_id = None;
+        }
+        let is_sprinting = mount.rider_id.is_some() && rider.as_ref().is_some_and(|p| p.is_sprinting);
+        if !is_sprinting {
+            let stats = mount.mount_type.stats();
+            mount.stamina = (mount.stamina + stats.stamina_recovery_per_second * delta_secs).min(stats.max_stamina);
+        }
+        if mount != before {
+            ctx.db.mount().id().update(mount);
+        }
+    }
+
+    let mut rng = rand::rngs::StdRng::from_rng(ctx.rng()).map_err(|e| format!("Failed to create RNG: {}", e))?;
+    let living_players: Vec<Player> = players.iter().filter(|p| p.is_online && !p.is_dead).collect();
+    maintain_herd(ctx, &living_players, &mut rng);
+    Ok(())
+}
+
+/// Spawns at most one horse per tick while there are fewer than TARGET_HORSE_COUNT.
+fn maintain_herd(ctx: &ReducerContext, players: &[Player], rng: &mut impl Rng) {
+    let mounts = ctx.db.mount();
+    if mounts.iter().filter(|m| m.mount_type == MountType::Horse).count() >= TARGET_HORSE_COUNT {
+        return;
+    }
+    let stats = MountType::Horse.stats();
+    let position = (0..MAX_SPAWN_ATTEMPTS)
+        .map(|_| (
+            rng.gen_range(stats.collision_radius..WORLD_WIDTH_PX - stats.collision_radius),
+            rng.gen_range(stats.collision_radius..WORLD_HEIGHT_PX - stats.collision_radius),
+        ))
+        .find(|&(x, y)| {
+            !crate::world_tile::is_water_at(ctx, x, y)
+                && players.iter().all(|p| {
+                    get_distance_squared(x, y, p.position_x, p.position_y) >= MIN_SPAWN_DISTANCE_FROM_PLAYER * MIN_SPAWN_DISTANCE_FROM_PLAYER
+                })
+        });
+    let Some((pos_x, pos_y)) = position else { return; };
+
+    match mounts.try_insert(Mount {
+        id: 0, // Auto-incremented
+        mount_type: MountType::Horse,
+        pos_x,
+        pos_y,
+        chunk_index: calculate_chunk_index(pos_x, pos_y),
+        health: stats.max_health,
+        max_health: stats.max_health,
+        stamina: stats.max_stamina,
+        rider_id: None,
+        last_hit_time: None,
+    }) {
+        Ok(mount) => {
+            track_entity(ctx, EntityType::Mount(mount.id), pos_x, pos_y);
+            log::debug!("[Mount] Spawned horse {} at ({:.1}, {:.1}).", mount.id, pos_x, pos_y);
+        },
+        Err(e) => log::error!("[Mount] Failed to spawn horse: {}", e),
+    }
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// The mount the player is riding, if any.
+pub(crate) fn ridden_mount(ctx: &ReducerContext, player_id: Identity) -> Option<Mount> {
+    ctx.db.mount().iter().find(|m| m.rider_id == Some(player_id))
+}
+
+/// The player's collision radius: the mount's while riding, PLAYER_RADIUS otherwise.
+pub(crate) fn rider_collision_radius(ctx: &ReducerContext, player_id: Identity) -> f32 {
+    ridden_mount(ctx, player_id).map_or(PLAYER_RADIUS, |m| m.mount_type.stats().collision_radius)
+}
+
+/// Takes the player off their mount. Returns false if they weren't riding.
+/// Called on dismount, on death and when the mount is killed or rides into water.
+pub(crate) fn dismount_player(ctx: &ReducerContext, player_id: Identity) -> bool {
+    let Some(mut mount) = ridden_mount(ctx, player_id) else {
+        return false;
+    };
+    log::info!("[Mount] Player {:?} dismounted {}.", player_id, mount.id);
+    mount.rider_id = None;
+    ctx.db.mount().id().update(mount);
+    true
+}
+
+/// Moves a ridden mount along with its rider after they moved, draining its stamina
+/// if they sprinted for `delta_time_secs`. Throws the rider off if they rode into water,
+/// leaving the mount at its last dry position.
+pub(crate) fn carry_mount(ctx: &ReducerContext, mut mount: Mount, pos_x: f32, pos_y: f32, sprinted: bool, delta_time_secs: f32) {
+    if crate::world_tile::is_water_at(ctx, pos_x, pos_y) {
+        if let Some(rider_id) = mount.rider_id {
+            dismount_player(ctx, rider_id);
+        }
+        return;
+    }
+    if sprinted {
+        mount.stamina = (mount.stamina - mount.mount_type.stats().stamina_drain_per_second * delta_time_secs).max(0.0);
+    }
+    mount.pos_x = pos_x;
+    mount.pos_y = pos_y;
+    sync_chunk_index(&mut mount.chunk_index, pos_x, pos_y);
+    track_entity(ctx, EntityType::Mount(mount.id), pos_x, pos_y);
+    ctx.db.mount().id().update(mount);
+}
+
+/// Removes a mount that was killed. Its rider, if any, is left standing where it fell.
+pub(crate) fn remove_mount(ctx: &ReducerContext, mount_id: u64) {
+    ctx.db.mount().id().delete(mount_id);
+    untrack_entity(ctx, EntityType::Mount(mount_id));
+}
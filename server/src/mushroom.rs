@@ -19,6 +19,7 @@ use crate::collectible_resources::{
     PLAYER_RESOURCE_INTERACTION_DISTANCE_SQUARED,
     validate_player_resource_interaction,
     collect_resource_and_schedule_respawn,
+    scale_respawn_secs,
     RespawnableResource
 };
 
@@ -100,6 +101,7 @@ impl RespawnableResource for Mushroom {
 /// adds the mushroom item to their inventory, and schedules respawn.
 #[spacetimedb::reducer]
 pub fn interact_with_mushroom(ctx: &ReducerContext, mushroom_id: u64) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
     let sender_id = ctx.sender;
     let mushrooms = ctx.db.mushroom();
 
@@ -115,6 +117,9 @@ pub fn interact_with_mushroom(ctx: &ReducerContext, mushroom_id: u64) -> Result<
     // Validate player can interact with this mushroom (distance check)
     let _player = validate_player_resource_interaction(ctx, sender_id, mushroom.pos_x, mushroom.pos_y)?;
 
+    let growth_multiplier = crate::game_config::get_game_config(ctx).night_plant_growth_multiplier
+        * crate::world_state::current_season(ctx).plant_growth_multiplier();
+
     // Use the generic collect_resource function
     collect_resource_and_schedule_respawn(
         ctx,
@@ -139,7 +144,7 @@ pub fn interact_with_mushroom(ctx: &ReducerContext, mushroom_id: u64) -> Result<
                 Err(format!("Mushroom {} disappeared before respawn scheduling.", mushroom_id))
             }
         },
-        MIN_MUSHROOM_RESPAWN_TIME_SECS, // min_respawn_secs
-        MAX_MUSHROOM_RESPAWN_TIME_SECS  // max_respawn_secs
+        scale_respawn_secs(MIN_MUSHROOM_RESPAWN_TIME_SECS, growth_multiplier), // min_respawn_secs
+        scale_respawn_secs(MAX_MUSHROOM_RESPAWN_TIME_SECS, growth_multiplier)  // max_respawn_secs
     )
 } 
\ No newline at end of file
@@ -0,0 +1,169 @@
+/******************************************************************************
+ *                                                                            *
+ * Written notes. A Note item carries text that its holder writes with        *
+ * `write_note`. The text is kept server-side in the private note_text table, *
+ * keyed by the item instance, so it stays with the item through trades,      *
+ * containers and corpses. Dropping a note on the ground does not keep the    *
+ * text: the dropped item is a new instance, so it comes back blank. Only     *
+ * whoever currently holds the note can read it: `read_note` copies the text  *
+ * into the reader's own note_view row. Text of notes that no longer exist is *
+ * removed by the janitor.                                                    *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{client_visibility_filter, Filter, Identity, ReducerContext, Table, Timestamp, log};
+
+use crate::items::{InventoryItem, inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
+use crate::note::note_text as NoteTextTableTrait;
+use crate::note::note_view as NoteViewTableTrait;
+
+// --- Constants ---
+const NOTE_ITEM_NAME: &str = "Note";
+const MAX_NOTE_TEXT_CHARS: usize = 500;
+const MAX_NOTE_TEXT_LINES: usize = 12;
+
+/// --- Note Text ---
+/// What is written on a Note item. Private; holders read it through `read_note`.
+#[spacetimedb::table(name = note_text)]
+#[derive(Clone, Debug)]
+pub struct NoteText {
+    #[primary_key]
+    pub item_instance_id: u64,
+    pub text: String,
+    pub written_by: Identity,
+    pub written_at: Timestamp,
+}
+
+/// --- Note View ---
+/// The note a player last opened. Each player only sees their own row.
+#[spacetimedb::table(name = note_view, public)]
+#[derive(Clone, Debug)]
+pub struct NoteView {
+    #[primary_key]
+    pub reader_id: Identity,
+    pub item_instance_id: u64,
+    pub text: String,
+    pub written_at: Option<Timestamp>, // None for a blank note
+}
+
+// --- Row-Level Security Filters ---
+
+#[client_visibility_filter]
+const NOTE_VIEW_OWN_ROW: Filter = Filter::Sql(
+    "SELECT * FROM note_view WHERE reader_id = :sender"
+);
+
+/******************************************************************************
+ *                                 REDUCERS                                   *
+ ******************************************************************************/
+
+/// Writes on a note the sender holds, replacing anything already on it. Blank text
+/// wipes the note.
+#[spacetimedb::reducer]
+pub fn write_note(ctx: &ReducerContext, item_instance_id: u64, text: String) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let note = get_held_note(ctx, item_instance_id)?;
+    let text = validate_note_text(ctx, &text)?;
+
+    let notes = ctx.db.note_text();
+    if text.is_empty() {
+        notes.item_instance_id().delete(note.instance_id);
+    } else {
+        let written = NoteText {
+            item_instance_id: note.instance_id,
+            text,
+            written_by: ctx.sender,
+            written_at: ctx.timestamp,
+        };
+        if notes.item_instance_id().find(note.instance_id).is_some() {
+            notes.item_instance_id().update(written);
+        } else {
+            notes.insert(written);
+        }
+    }
+    log::info!("[Note] Player {:?} wrote on note {}.", ctx.sender, item_instance_id);
+    Ok(())
+}
+
+/// Opens a note the sender holds, putting its text in their note_view row.
+#[spacetimedb::reducer]
+pub fn read_note(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), String> {
+    let note = get_held_note(ctx, item_instance_id)?;
+    let written = ctx.db.note_text().item_instance_id().find(note.instance_id);
+    let view = NoteView {
+        reader_id: ctx.sender,
+        item_instance_id: note.instance_id,
+        text: written.as_ref().map(|w| w.text.clone()).unwrap_or_default(),
+        written_at: written.map(|w| w.written_at),
+    };
+
+    let views = ctx.db.note_view();
+    if views.reader_id().find(ctx.sender).is_some() {
+        views.reader_id().update(view);
+    } else {
+        views.insert(view);
+    }
+    Ok(())
+}
+
+/// Closes the note the sender has open.
+#[spacetimedb::reducer]
+pub fn close_note(ctx: &ReducerContext) -> Result<(), String> {
+    ctx.db.note_view().reader_id().delete(ctx.sender);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Deletes text and open views of notes whose item no longer exists (burned, despawned
+/// and so on). Called by the janitor. Returns how many notes were removed.
+pub(crate) fn remove_orphaned_notes(ctx: &ReducerContext) -> u32 {
+    let items = ctx.db.inventory_item();
+    let notes = ctx.db.note_text();
+    let orphaned: Vec<u64> = notes.iter()
+        .filter(|n| items.instance_id().find(n.item_instance_id).is_none())
+        .map(|n| n.item_instance_id)
+        .collect();
+    for item_instance_id in &orphaned {
+        notes.item_instance_id().delete(*item_instance_id);
+    }
+
+    let views = ctx.db.note_view();
+    let stale_views: Vec<Identity> = views.iter()
+        .filter(|v| items.instance_id().find(v.item_instance_id).is_none())
+        .map(|v| v.reader_id)
+        .collect();
+    for reader_id in stale_views {
+        views.reader_id().delete(reader_id);
+    }
+    orphaned.len() as u32
+}
+
+/// The Note item with this instance id, if the sender holds it.
+fn get_held_note(ctx: &ReducerContext, item_instance_id: u64) -> Result<InventoryItem, String> {
+    let item = crate::player_inventory::get_player_item(ctx, item_instance_id)?;
+    let def = ctx.db.item_definition().id().find(item.item_def_id)
+        .ok_or_else(|| format!("Definition for item ID {} not found.", item.item_def_id))?;
+    if def.name != NOTE_ITEM_NAME {
+        return Err(format!("{} is not a note.", def.name));
+    }
+    Ok(item)
+}
+
+/// Trims the text, strips control characters other than newlines, checks its length and
+/// runs each line through the chat word filter.
+fn validate_note_text(ctx: &ReducerContext, text: &str) -> Result<String, String> {
+    let text: String = text.trim().chars().filter(|c| *c == '\n' || !c.is_control()).collect();
+    if text.chars().count() > MAX_NOTE_TEXT_CHARS {
+        return Err(format!("Notes can hold at most {} characters.", MAX_NOTE_TEXT_CHARS));
+    }
+    if text.lines().count() > MAX_NOTE_TEXT_LINES {
+        return Err(format!("Notes can hold at most {} lines.", MAX_NOTE_TEXT_LINES));
+    }
+    let lines = text.lines()
+        .map(|line| crate::chat::apply_word_filter(ctx, line))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(lines.join("\n"))
+}
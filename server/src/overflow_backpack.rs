@@ -0,0 +1,356 @@
+/******************************************************************************
+ *                                                                            *
+ * Overflow backpacks. When items are granted to a player whose inventory     *
+ * can't hold them all (crafting output, refunds, respawn grants), the        *
+ * remainder goes into a DroppedBackpack at the player's feet instead of      *
+ * being lost. Backpacks are slot-array containers (see `SlotArrayContainer`  *
+ * in inventory_management.rs) that anyone can loot like a corpse. They are   *
+ * removed once emptied, or with their contents after                         *
+ * DROPPED_BACKPACK_LIFETIME_SECS.                                            *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, TimeDuration, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+
+use crate::Player;
+use crate::player as PlayerTableTrait;
+use crate::items::{ItemDefinition, InventoryItem, add_item_to_player_inventory, player_inventory_space_for_item};
+use crate::items::{inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
+use crate::inventory_management::{self, ItemContainer, ContainerItemClearer, ContainerSlot, SlotArrayContainer, empty_container_slots, first_empty_container_slot, is_container_empty};
+use crate::environment::calculate_chunk_index;
+use crate::models::{ContainerType, ContainerLocationData, ItemLocation};
+use crate::overflow_backpack::dropped_backpack as DroppedBackpackTableTrait;
+use crate::overflow_backpack::dropped_backpack_cleanup_schedule as DroppedBackpackCleanupScheduleTableTrait;
+
+// --- Constants ---
+pub const NUM_DROPPED_BACKPACK_SLOTS: usize = 12;
+const DROPPED_BACKPACK_INTERACTION_DISTANCE_SQUARED: f32 = 64.0 * 64.0;
+/// Overflow joins one of the player's existing backpacks within this distance before a new one is dropped.
+const DROPPED_BACKPACK_REUSE_DISTANCE_SQUARED: f32 = 48.0 * 48.0;
+const DROPPED_BACKPACK_LIFETIME_SECS: i64 = 30 * 60;
+const DROPPED_BACKPACK_CLEANUP_INTERVAL_SECS: u64 = 60;
+
+/// --- Dropped Backpack ---
+/// A lootable bag holding items that didn't fit in `owner_id`'s inventory.
+#[spacetimedb::table(name = dropped_backpack, public)]
+#[derive(Clone, Debug)]
+pub struct DroppedBackpack {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub chunk_index: u32,
+
+    #[index(btree)]
+    pub owner_id: Identity,
+
+    pub slots: Vec<ContainerSlot>,
+
+    pub created_at: Timestamp,
+    pub despawn_at: Timestamp,
+}
+
+/// --- Dropped Backpack Cleanup Schedule ---
+#[spacetimedb::table(name = dropped_backpack_cleanup_schedule, scheduled(despawn_expired_backpacks))]
+#[derive(Clone, Debug)]
+pub struct DroppedBackpackCleanupSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/******************************************************************************
+ *                                 REDUCERS                                   *
+ ******************************************************************************/
+
+/// Moves an item FROM a backpack slot INTO the player's inventory/hotbar.
+#[spacetimedb::reducer]
+pub fn move_item_from_dropped_backpack(
+    ctx: &ReducerContext,
+    backpack_id: u32,
+    source_slot_index: u8,
+    target_slot_type: String,
+    target_slot_index: u32
+) -> Result<(), String> {
+    let (_player, mut backpack) = validate_backpack_interaction(ctx, backpack_id)?;
+    inventory_management::handle_move_from_container_slot(ctx, &mut backpack, source_slot_index, target_slot_type, target_slot_index)?;
+    save_or_remove_backpack(ctx, backpack);
+    Ok(())
+}
+
+/// Splits a stack from a backpack slot into the player's inventory/hotbar.
+#[spacetimedb::reducer]
+pub fn split_stack_from_dropped_backpack(
+    ctx: &ReducerContext,
+    backpack_id: u32,
+    source_slot_index: u8,
+    quantity_to_split: u32,
+    target_slot_type: String,
+    target_slot_index: u32,
+) -> Result<(), String> {
+    let (_player, mut backpack) = validate_backpack_interaction(ctx, backpack_id)?;
+    inventory_management::handle_split_from_container(ctx, &mut backpack, source_slot_index, quantity_to_split, target_slot_type, target_slot_index)?;
+    save_or_remove_backpack(ctx, backpack);
+    Ok(())
+}
+
+/// Quickly moves an item FROM a backpack slot TO the player inventory.
+#[spacetimedb::reducer]
+pub fn quick_move_from_dropped_backpack(ctx: &ReducerContext, backpack_id: u32, source_slot_index: u8) -> Result<(), String> {
+    let (_player, mut backpack) = validate_backpack_interaction(ctx, backpack_id)?;
+    inventory_management::handle_quick_move_from_container(ctx, &mut backpack, source_slot_index)?;
+    save_or_remove_backpack(ctx, backpack);
+    Ok(())
+}
+
+/// Moves an item BETWEEN two slots within the same backpack.
+#[spacetimedb::reducer]
+pub fn move_item_within_dropped_backpack(ctx: &ReducerContext, backpack_id: u32, source_slot_index: u8, target_slot_index: u8) -> Result<(), String> {
+    let (_player, mut backpack) = validate_backpack_interaction(ctx, backpack_id)?;
+    inventory_management::handle_move_within_container(ctx, &mut backpack, source_slot_index, target_slot_index)?;
+    ctx.db.dropped_backpack().id().update(backpack);
+    Ok(())
+}
+
+/// Splits a stack FROM one backpack slot TO another within the same backpack.
+#[spacetimedb::reducer]
+pub fn split_stack_within_dropped_backpack(
+    ctx: &ReducerContext,
+    backpack_id: u32,
+    source_slot_index: u8,
+    target_slot_index: u8,
+    quantity_to_split: u32,
+) -> Result<(), String> {
+    let (_player, mut backpack) = validate_backpack_interaction(ctx, backpack_id)?;
+    inventory_management::handle_split_within_container(ctx, &mut backpack, source_slot_index, target_slot_index, quantity_to_split)?;
+    ctx.db.dropped_backpack().id().update(backpack);
+    Ok(())
+}
+
+/// Drops the whole stack in a backpack slot onto the ground near the player.
+#[spacetimedb::reducer]
+pub fn drop_item_from_dropped_backpack_slot_to_world(ctx: &ReducerContext, backpack_id: u32, slot_index: u8) -> Result<(), String> {
+    let (player, mut backpack) = validate_backpack_interaction(ctx, backpack_id)?;
+    inventory_management::handle_drop_from_container_slot(ctx, &mut backpack, slot_index, &player)?;
+    save_or_remove_backpack(ctx, backpack);
+    Ok(())
+}
+
+/// --- Interact with Dropped Backpack ---
+#[spacetimedb::reducer]
+pub fn interact_with_dropped_backpack(ctx: &ReducerContext, backpack_id: u32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    validate_backpack_interaction(ctx, backpack_id)?;
+    log::debug!("Player {:?} interaction check OK for dropped backpack {}", ctx.sender, backpack_id);
+    Ok(())
+}
+
+/******************************************************************************
+ *                           SCHEDULED REDUCERS                               *
+ ******************************************************************************/
+
+/// Starts the backpack cleanup schedule if it isn't already scheduled. Called from `init_module`.
+pub fn init_dropped_backpack_cleanup_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.dropped_backpack_cleanup_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting dropped backpack cleanup schedule (every {}s).", DROPPED_BACKPACK_CLEANUP_INTERVAL_SECS);
+        let interval = Duration::from_secs(DROPPED_BACKPACK_CLEANUP_INTERVAL_SECS);
+        schedule_table.try_insert(DroppedBackpackCleanupSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert dropped backpack cleanup schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: removes expired backpacks along with whatever is still in them.
+#[spacetimedb::reducer]
+pub fn despawn_expired_backpacks(ctx: &ReducerContext, _schedule: DroppedBackpackCleanupSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("despawn_expired_backpacks may only be called by the scheduler.".to_string());
+    }
+
+    let backpacks = ctx.db.dropped_backpack();
+    let inventory_items = ctx.db.inventory_item();
+    let expired: Vec<DroppedBackpack> = backpacks.iter().filter(|b| ctx.timestamp >= b.despawn_at).collect();
+    for backpack in expired {
+        for item_instance_id in backpack.slots.iter().filter_map(|slot| slot.instance_id) {
+            inventory_items.instance_id().delete(item_instance_id);
+        }
+        backpacks.id().delete(backpack.id);
+        log::info!("[OverflowBackpack] Backpack {} of player {:?} expired.", backpack.id, backpack.owner_id);
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                            TRAIT IMPLEMENTATIONS                           *
+ ******************************************************************************/
+
+impl SlotArrayContainer for DroppedBackpack {
+    fn slots(&self) -> &[ContainerSlot] {
+        &self.slots
+    }
+
+    fn slots_mut(&mut self) -> &mut [ContainerSlot] {
+        &mut self.slots
+    }
+
+    fn slot_container_type(&self) -> ContainerType {
+        ContainerType::DroppedBackpack
+    }
+
+    fn slot_container_id(&self) -> u64 {
+        self.id as u64
+    }
+}
+
+/// --- Helper struct to implement the ContainerItemClearer trait for DroppedBackpack ---
+pub struct DroppedBackpackClearer;
+
+impl ContainerItemClearer for DroppedBackpackClearer {
+    fn clear_item(ctx: &ReducerContext, item_instance_id: u64) -> bool {
+        let backpacks = ctx.db.dropped_backpack();
+        for mut backpack in backpacks.iter() {
+            let Some(slot_index) = (0..backpack.num_slots() as u8).find(|&i| backpack.get_slot_instance_id(i) == Some(item_instance_id)) else {
+                continue;
+            };
+            log::debug!("[DroppedBackpackClearer] Found item {} in backpack {} slot {}. Clearing slot.", item_instance_id, backpack.id, slot_index);
+            backpack.set_slot(slot_index, None, None);
+            backpacks.id().update(backpack);
+
+            let inventory_items = ctx.db.inventory_item();
+            if let Some(mut item) = inventory_items.instance_id().find(item_instance_id) {
+                item.location = ItemLocation::Unknown;
+                inventory_items.instance_id().update(item);
+            }
+            return true;
+        }
+        false
+    }
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Like `add_item_to_player_inventory`, but whatever doesn't fit in the player's hotbar
+/// and inventory goes into a dropped backpack at their feet instead of failing.
+/// Returns the new instance ID if part of the grant took a fresh inventory slot.
+pub(crate) fn add_item_to_player_inventory_or_overflow(ctx: &ReducerContext, player_id: Identity, item_def_id: u64, quantity: u32) -> Result<Option<u64>, String> {
+    let item_def = ctx.db.item_definition().id().find(item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found", item_def_id))?;
+    let fits = player_inventory_space_for_item(ctx, player_id, &item_def).min(quantity);
+
+    let mut new_instance_id = None;
+    if item_def.is_stackable {
+        if fits > 0 {
+            new_instance_id = add_item_to_player_inventory(ctx, player_id, item_def_id, fits)?;
+        }
+    } else {
+        // Non-stackable items are added one per slot
+        for _ in 0..fits {
+            new_instance_id = add_item_to_player_inventory(ctx, player_id, item_def_id, 1)?.or(new_instance_id);
+        }
+    }
+
+    let overflow = quantity - fits;
+    if overflow > 0 {
+        let player = ctx.db.player().identity().find(player_id)
+            .ok_or_else(|| "Player not found".to_string())?;
+        put_in_overflow_backpack(ctx, &player, &item_def, overflow);
+    }
+    Ok(new_instance_id)
+}
+
+/// Puts `quantity` of an item into the player's nearby backpack, dropping new backpacks as
+/// needed. Stacks are split to the item's stack size.
+fn put_in_overflow_backpack(ctx: &ReducerContext, player: &Player, item_def: &ItemDefinition, quantity: u32) {
+    let backpacks = ctx.db.dropped_backpack();
+    let inventory_items = ctx.db.inventory_item();
+    let stack_size = if item_def.is_stackable { item_def.stack_size.max(1) } else { 1 };
+
+    let mut backpack = find_reusable_backpack(ctx, player).unwrap_or_else(|| spawn_backpack(ctx, player));
+    let mut remaining = quantity;
+    while remaining > 0 {
+        let slot_index = match first_empty_container_slot(&backpack) {
+            Some(slot_index) => slot_index,
+            None => {
+                backpacks.id().update(backpack);
+                backpack = spawn_backpack(ctx, player);
+                0
+            }
+        };
+        let stack_quantity = remaining.min(stack_size);
+        let inserted = inventory_items.insert(InventoryItem {
+            instance_id: 0, // Auto-incremented
+            item_def_id: item_def.id,
+            quantity: stack_quantity,
+            location: ItemLocation::Container(ContainerLocationData {
+                container_type: ContainerType::DroppedBackpack,
+                container_id: backpack.id as u64,
+                slot_index,
+            }),
+            durability: None,
+            is_locked: false,
+            charges: None,
+        });
+        backpack.set_slot(slot_index, Some(inserted.instance_id), Some(item_def.id));
+        remaining -= stack_quantity;
+    }
+    log::info!("[OverflowBackpack] {} {} didn't fit in player {:?}'s inventory; put in backpack {}.", quantity, item_def.name, player.identity, backpack.id);
+    backpacks.id().update(backpack);
+}
+
+/// One of the player's backpacks close to them with a free slot, if any.
+fn find_reusable_backpack(ctx: &ReducerContext, player: &Player) -> Option<DroppedBackpack> {
+    ctx.db.dropped_backpack().owner_id().filter(player.identity).find(|backpack| {
+        let dx = player.position_x - backpack.pos_x;
+        let dy = player.position_y - backpack.pos_y;
+        (dx * dx + dy * dy) <= DROPPED_BACKPACK_REUSE_DISTANCE_SQUARED && first_empty_container_slot(backpack).is_some()
+    })
+}
+
+fn spawn_backpack(ctx: &ReducerContext, player: &Player) -> DroppedBackpack {
+    let backpack = ctx.db.dropped_backpack().insert(DroppedBackpack {
+        id: 0, // Auto-incremented
+        pos_x: player.position_x,
+        pos_y: player.position_y,
+        chunk_index: calculate_chunk_index(player.position_x, player.position_y),
+        owner_id: player.identity,
+        slots: empty_container_slots(NUM_DROPPED_BACKPACK_SLOTS),
+        created_at: ctx.timestamp,
+        despawn_at: ctx.timestamp + TimeDuration::from_micros(DROPPED_BACKPACK_LIFETIME_SECS * 1_000_000),
+    });
+    log::info!("[OverflowBackpack] Dropped backpack {} for player {:?} at ({:.1}, {:.1}).", backpack.id, player.identity, backpack.pos_x, backpack.pos_y);
+    backpack
+}
+
+/// Persists a backpack after an item was taken out, removing it once it's empty.
+fn save_or_remove_backpack(ctx: &ReducerContext, backpack: DroppedBackpack) {
+    if is_container_empty(&backpack) {
+        log::info!("[OverflowBackpack] Backpack {} emptied; removing it.", backpack.id);
+        ctx.db.dropped_backpack().id().delete(backpack.id);
+    } else {
+        ctx.db.dropped_backpack().id().update(backpack);
+    }
+}
+
+/// Validates that the backpack exists and is within the sender's reach.
+fn validate_backpack_interaction(ctx: &ReducerContext, backpack_id: u32) -> Result<(Player, DroppedBackpack), String> {
+    let player = ctx.db.player().identity().find(ctx.sender).ok_or_else(|| "Player not found".to_string())?;
+    let backpack = ctx.db.dropped_backpack().id().find(backpack_id)
+        .ok_or_else(|| format!("Backpack {} not found", backpack_id))?;
+
+    let dx = player.position_x - backpack.pos_x;
+    let dy = player.position_y - backpack.pos_y;
+    if (dx * dx + dy * dy) > DROPPED_BACKPACK_INTERACTION_DISTANCE_SQUARED {
+        return Err("Too far away".to_string());
+    }
+    Ok((player, backpack))
+}
@@ -0,0 +1,473 @@
+/******************************************************************************
+ *                                                                            *
+ * Placement of deployables. PLACEABLE_RULES is the registry of everything    *
+ * that can be placed: reach, footprint, whether it can go in water and the   *
+ * constructor that creates its entity. `place_item` places any of them (and  *
+ * building pieces) by looking the item up there; the older per-entity        *
+ * `place_*` reducers are thin wrappers over the same code. `check_placement` *
+ * applies the rules together with a footprint check against every blocking   *
+ * entity and building privilege (no building next to other players'          *
+ * structures unless you're on their team). `validate_placement` runs the     *
+ * same checks without placing anything and writes the verdict to             *
+ * PlacementFeedback, so the client's placement ghost always agrees with the  *
+ * server.                                                                    *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+
+use crate::{Player, PLAYER_RADIUS, WORLD_WIDTH_PX, WORLD_HEIGHT_PX};
+use crate::models::ItemLocation;
+use crate::items::ItemDefinition;
+use crate::player_inventory::get_player_item;
+use crate::spatial_grid::{self, EntityType};
+use crate::building::{BuildingPieceType, BUILDING_COLLISION_Y_OFFSET};
+use crate::player as PlayerTableTrait;
+use crate::items::{inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
+use crate::active_equipment::active_equipment as ActiveEquipmentTableTrait;
+use crate::tree::tree as TreeTableTrait;
+use crate::stone::stone as StoneTableTrait;
+use crate::campfire::campfire as CampfireTableTrait;
+use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
+use crate::building::building_piece as BuildingPieceTableTrait;
+use crate::furnace::furnace as FurnaceTableTrait;
+use crate::large_storage_chest::large_storage_chest as LargeStorageChestTableTrait;
+use crate::loot_crate::loot_crate as LootCrateTableTrait;
+use crate::workbench::workbench as WorkbenchTableTrait;
+use crate::recycler::recycler as RecyclerTableTrait;
+use crate::flame_turret::flame_turret as FlameTurretTableTrait;
+use crate::standing_torch::standing_torch as StandingTorchTableTrait;
+use crate::research::research_table as ResearchTableTableTrait;
+use crate::sleeping_bag::sleeping_bag as SleepingBagTableTrait;
+use crate::sign::sign as SignTableTrait;
+use crate::wiring::wiring_switch as WiringSwitchTableTrait;
+use crate::tree_felling::tree_stump as TreeStumpTableTrait;
+use crate::environment_hazard::environment_hazard as EnvironmentHazardTableTrait;
+use crate::vending_machine::vending_machine as VendingMachineTableTrait;
+use crate::placement::placement_feedback as PlacementFeedbackTableTrait;
+
+// --- Constants ---
+/// How far from themselves players can place deployables.
+pub(crate) const DEFAULT_PLACEMENT_MAX_DISTANCE: f32 = 96.0;
+/// Players can't place anything this close to another player's building pieces
+/// unless they placed them or are on the same team.
+pub(crate) const BUILDING_PRIVILEGE_RADIUS: f32 = 144.0;
+/// Largest footprint of anything placement can collide with (stones), to size the grid query.
+const MAX_BLOCKING_RADIUS: f32 = crate::stone::STONE_RADIUS;
+
+/// Creates the world entity for a placed item at (world_x, world_y), owned by `placed_by`.
+/// Gets the placed item's durability for entities that keep it (standing torches).
+/// Only called once `check_placement` has passed; the item itself is consumed by the caller.
+pub(crate) type PlaceableConstructor = fn(&ReducerContext, Identity, &ItemDefinition, Option<f32>, f32, f32) -> Result<(), String>;
+
+/// Placement rules for one kind of deployable.
+pub(crate) struct PlaceableRule {
+    pub item_name: &'static str,
+    pub max_distance: f32,
+    /// Radius of the placed entity's footprint, centred `collision_y_offset` above the placement point.
+    pub collision_radius: f32,
+    pub collision_y_offset: f32,
+    pub allow_water: bool,
+    pub construct: PlaceableConstructor,
+}
+
+/// Every deployable `place_item` knows about. Adding a placeable means adding a row here
+/// and a constructor next to its table.
+pub(crate) const PLACEABLE_RULES: &[PlaceableRule] = &[
+    PlaceableRule {
+        item_name: "Camp Fire",
+        max_distance: crate::CAMPFIRE_PLACEMENT_MAX_DISTANCE,
+        collision_radius: crate::campfire::CAMPFIRE_COLLISION_RADIUS,
+        collision_y_offset: crate::campfire::CAMPFIRE_COLLISION_Y_OFFSET,
+        allow_water: false,
+        construct: crate::campfire::spawn_campfire,
+    },
+    PlaceableRule {
+        item_name: "Wooden Storage Box",
+        max_distance: DEFAULT_PLACEMENT_MAX_DISTANCE,
+        collision_radius: crate::wooden_storage_box::BOX_COLLISION_RADIUS,
+        collision_y_offset: crate::wooden_storage_box::BOX_COLLISION_Y_OFFSET,
+        allow_water: false,
+        construct: crate::wooden_storage_box::spawn_wooden_storage_box,
+    },
+    PlaceableRule {
+        item_name: "Large Storage Chest",
+        max_distance: DEFAULT_PLACEMENT_MAX_DISTANCE,
+        collision_radius: crate::large_storage_chest::LARGE_CHEST_COLLISION_RADIUS,
+        collision_y_offset: crate::large_storage_chest::LARGE_CHEST_COLLISION_Y_OFFSET,
+        allow_water: false,
+        construct: crate::large_storage_chest::spawn_large_storage_chest,
+    },
+    PlaceableRule {
+        item_name: "Sleeping Bag",
+        max_distance: DEFAULT_PLACEMENT_MAX_DISTANCE,
+        collision_radius: crate::sleeping_bag::SLEEPING_BAG_COLLISION_RADIUS,
+        collision_y_offset: crate::sleeping_bag::SLEEPING_BAG_COLLISION_Y_OFFSET,
+        allow_water: false,
+        construct: crate::sleeping_bag::spawn_sleeping_bag,
+    },
+    PlaceableRule {
+        item_name: "Stash",
+        max_distance: DEFAULT_PLACEMENT_MAX_DISTANCE,
+        collision_radius: 0.0, // Stashes sit flat and can overlap anything on land
+        collision_y_offset: 0.0,
+        allow_water: false,
+        construct: crate::stash::spawn_stash,
+    },
+    PlaceableRule {
+        item_name: "Furnace",
+        max_distance: DEFAULT_PLACEMENT_MAX_DISTANCE,
+        collision_radius: crate::furnace::FURNACE_COLLISION_RADIUS,
+        collision_y_offset: crate::furnace::FURNACE_COLLISION_Y_OFFSET,
+        allow_water: false,
+        construct: crate::furnace::spawn_furnace,
+    },
+    PlaceableRule {
+        item_name: "Workbench",
+        max_distance: DEFAULT_PLACEMENT_MAX_DISTANCE,
+        collision_radius: crate::workbench::WORKBENCH_COLLISION_RADIUS,
+        collision_y_offset: crate::workbench::WORKBENCH_COLLISION_Y_OFFSET,
+        allow_water: false,
+        construct: crate::workbench::spawn_workbench,
+    },
+    PlaceableRule {
+        item_name: "Advanced Workbench",
+        max_distance: DEFAULT_PLACEMENT_MAX_DISTANCE,
+        collision_radius: crate::workbench::WORKBENCH_COLLISION_RADIUS,
+        collision_y_offset: crate::workbench::WORKBENCH_COLLISION_Y_OFFSET,
+        allow_water: false,
+        construct: crate::workbench::spawn_workbench,
+    },
+    PlaceableRule {
+        item_name: "Wooden Sign",
+        max_distance: DEFAULT_PLACEMENT_MAX_DISTANCE,
+        collision_radius: crate::sign::SIGN_COLLISION_RADIUS,
+        collision_y_offset: crate::sign::SIGN_COLLISION_Y_OFFSET,
+        allow_water: false,
+        construct: crate::sign::spawn_sign,
+    },
+    PlaceableRule {
+        item_name: "Switch",
+        max_distance: DEFAULT_PLACEMENT_MAX_DISTANCE,
+        collision_radius: crate::wiring::SWITCH_COLLISION_RADIUS,
+        collision_y_offset: crate::wiring::SWITCH_COLLISION_Y_OFFSET,
+        allow_water: false,
+        construct: crate::wiring::spawn_switch,
+    },
+    PlaceableRule {
+        item_name: "Recycler",
+        max_distance: DEFAULT_PLACEMENT_MAX_DISTANCE,
+        collision_radius: crate::recycler::RECYCLER_COLLISION_RADIUS,
+        collision_y_offset: crate::recycler::RECYCLER_COLLISION_Y_OFFSET,
+        allow_water: false,
+        construct: crate::recycler::spawn_recycler,
+    },
+    PlaceableRule {
+        item_name: "Flame Turret",
+        max_distance: DEFAULT_PLACEMENT_MAX_DISTANCE,
+        collision_radius: crate::flame_turret::FLAME_TURRET_COLLISION_RADIUS,
+        collision_y_offset: crate::flame_turret::FLAME_TURRET_COLLISION_Y_OFFSET,
+        allow_water: false,
+        construct: crate::flame_turret::spawn_flame_turret,
+    },
+    PlaceableRule {
+        item_name: "Research Table",
+        max_distance: DEFAULT_PLACEMENT_MAX_DISTANCE,
+        collision_radius: crate::research::RESEARCH_TABLE_COLLISION_RADIUS,
+        collision_y_offset: 0.0,
+        allow_water: false,
+        construct: crate::research::spawn_research_table,
+    },
+    PlaceableRule {
+        item_name: "Vending Machine",
+        max_distance: DEFAULT_PLACEMENT_MAX_DISTANCE,
+        collision_radius: crate::vending_machine::VENDING_MACHINE_COLLISION_RADIUS,
+        collision_y_offset: crate::vending_machine::VENDING_MACHINE_COLLISION_Y_OFFSET,
+        allow_water: false,
+        construct: crate::vending_machine::spawn_vending_machine,
+    },
+    PlaceableRule {
+        item_name: "Torch",
+        max_distance: DEFAULT_PLACEMENT_MAX_DISTANCE,
+        collision_radius: crate::standing_torch::STANDING_TORCH_COLLISION_RADIUS,
+        collision_y_offset: 0.0,
+        allow_water: false,
+        construct: crate::standing_torch::spawn_standing_torch,
+    },
+];
+
+/// --- Placement Feedback ---
+/// Result of a player's latest `validate_placement`, for drawing the placement ghost.
+#[spacetimedb::table(name = placement_feedback, public)]
+#[derive(Clone, Debug)]
+pub struct PlacementFeedback {
+    #[primary_key]
+    pub player_id: Identity,
+    pub item_def_id: u64,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub is_valid: bool,
+    pub reason: Option<String>, // Why placement would be refused
+    pub checked_at: Timestamp,
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Validate Placement ---
+/// Checks whether the sender could place the item at (world_x, world_y) right now,
+/// using the same rules as the `place_*` reducers, and stores the result in
+/// PlacementFeedback. Nothing is placed or consumed.
+#[spacetimedb::reducer]
+pub fn validate_placement(ctx: &ReducerContext, item_def_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let result = check_placement_for_def(ctx, sender_id, item_def_id, world_x, world_y);
+    let feedback = PlacementFeedback {
+        player_id: sender_id,
+        item_def_id,
+        pos_x: world_x,
+        pos_y: world_y,
+        is_valid: result.is_ok(),
+        reason: result.err(),
+        checked_at: ctx.timestamp,
+    };
+    let feedback_table = ctx.db.placement_feedback();
+    if feedback_table.player_id().find(sender_id).is_some() {
+        feedback_table.player_id().update(feedback);
+    } else {
+        feedback_table.insert(feedback);
+    }
+    Ok(())
+}
+
+/// --- Place Item ---
+/// Places any deployable (or building piece) from the sender's inventory/hotbar at
+/// (world_x, world_y), picking the entity to create from the item's definition.
+#[spacetimedb::reducer]
+pub fn place_item(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    place_deployable(ctx, item_instance_id, world_x, world_y, |_| true)
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Shared body of `place_item` and the per-entity `place_*` reducers, which pass `accepts`
+/// to only take their own kind of item.
+pub(crate) fn place_deployable(
+    ctx: &ReducerContext,
+    item_instance_id: u64,
+    world_x: f32,
+    world_y: f32,
+    accepts: fn(&str) -> bool,
+) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let sender_id = ctx.sender;
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Player not found".to_string())?;
+
+    let item_to_place = get_player_item(ctx, item_instance_id)?;
+    let item_def = ctx.db.item_definition().id().find(item_to_place.item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found.", item_to_place.item_def_id))?;
+    if !accepts(&item_def.name) {
+        return Err(format!("{} can't be placed here.", item_def.name));
+    }
+    match &item_to_place.location {
+        ItemLocation::Inventory(data) if data.owner_id == sender_id => {},
+        ItemLocation::Hotbar(data) if data.owner_id == sender_id => {},
+        _ => return Err(format!("{} must be in your inventory or hotbar to be placed.", item_def.name)),
+    }
+
+    // Building pieces snap to the tile grid and have their own rules
+    if BuildingPieceType::from_item_name(&item_def.name).is_some() {
+        return crate::building::place_building_piece(ctx, item_instance_id, world_x, world_y);
+    }
+
+    let rule = get_placeable_rule(&item_def.name)
+        .ok_or_else(|| format!("{} cannot be placed.", item_def.name))?;
+    check_placement(ctx, &player, &item_def.name, world_x, world_y)?;
+    (rule.construct)(ctx, sender_id, &item_def, item_to_place.durability, world_x, world_y)?;
+    consume_placed_item(ctx, sender_id, item_instance_id)?;
+
+    log::info!("[Placement] Player {:?} placed {} at ({:.1}, {:.1}).", sender_id, item_def.name, world_x, world_y);
+    Ok(())
+}
+
+/// Uses up one of the placed item, putting it away first if it was the last one and in hand.
+fn consume_placed_item(ctx: &ReducerContext, player_id: Identity, item_instance_id: u64) -> Result<(), String> {
+    let inventory_items = ctx.db.inventory_item();
+    let mut item = inventory_items.instance_id().find(item_instance_id)
+        .ok_or_else(|| format!("Item instance {} not found.", item_instance_id))?;
+    if item.quantity > 1 {
+        item.quantity -= 1;
+        inventory_items.instance_id().update(item);
+        return Ok(());
+    }
+    let is_held = ctx.db.active_equipment().player_identity().find(player_id)
+        .is_some_and(|e| e.equipped_item_instance_id == Some(item_instance_id));
+    if is_held {
+        crate::active_equipment::clear_active_item_reducer(ctx, player_id)?;
+    }
+    inventory_items.instance_id().delete(item_instance_id);
+    Ok(())
+}
+
+/// The placement rules for a deployable item, if it is one.
+pub(crate) fn get_placeable_rule(item_name: &str) -> Option<&'static PlaceableRule> {
+    PLACEABLE_RULES.iter().find(|rule| rule.item_name == item_name)
+}
+
+/// Errors unless `player` can place the deployable named `item_name` at (world_x, world_y):
+/// within reach, inside the world, on an allowed surface, clear of anything that blocks,
+/// outside other players' building privilege and not in a no-build zone (see zone.rs).
+pub(crate) fn check_placement(ctx: &ReducerContext, player: &Player, item_name: &str, world_x: f32, world_y: f32) -> Result<(), String> {
+    let rule = get_placeable_rule(item_name)
+        .ok_or_else(|| format!("'{}' cannot be placed.", item_name))?;
+    if player.is_dead {
+        return Err("Cannot place items while dead.".to_string());
+    }
+    check_in_world(world_x, world_y)?;
+    let dx = world_x - player.position_x;
+    let dy = world_y - player.position_y;
+    if dx * dx + dy * dy > rule.max_distance * rule.max_distance {
+        return Err("Too far away to place that there.".to_string());
+    }
+    if !rule.allow_water && crate::world_tile::is_water_at(ctx, world_x, world_y) {
+        return Err("Cannot place that in water.".to_string());
+    }
+    check_footprint_clear(ctx, world_x, world_y - rule.collision_y_offset, rule.collision_radius)?;
+    check_building_privilege(ctx, player.identity, world_x, world_y)?;
+    crate::zone::check_build_allowed(ctx, world_x, world_y)
+}
+
+/// Errors if (world_x, world_y) is outside the world.
+pub(crate) fn check_in_world(world_x: f32, world_y: f32) -> Result<(), String> {
+    if !(0.0..=WORLD_WIDTH_PX).contains(&world_x) || !(0.0..=WORLD_HEIGHT_PX).contains(&world_y) {
+        return Err("Cannot place things outside the world.".to_string());
+    }
+    Ok(())
+}
+
+/// Errors if a building piece placed by someone other than the player or a teammate
+/// is within BUILDING_PRIVILEGE_RADIUS of (world_x, world_y).
+pub(crate) fn check_building_privilege(ctx: &ReducerContext, player_id: Identity, world_x: f32, world_y: f32) -> Result<(), String> {
+    let pieces = ctx.db.building_piece();
+    let radius_sq = BUILDING_PRIVILEGE_RADIUS * BUILDING_PRIVILEGE_RADIUS;
+    for entity in spatial_grid::get_entities_in_radius(ctx, world_x, world_y, BUILDING_PRIVILEGE_RADIUS) {
+        let EntityType::BuildingPiece(piece_id) = entity else { continue };
+        let Some(piece) = pieces.id().find(piece_id) else { continue };
+        if piece.is_destroyed || crate::team::is_owner_or_teammate(ctx, player_id, piece.placed_by) {
+            continue;
+        }
+        let (dx, dy) = (piece.pos_x - world_x, piece.pos_y - world_y);
+        if dx * dx + dy * dy <= radius_sq {
+            return Err("Too close to someone else's building.".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Errors if a footprint of `radius` at (center_x, center_y) overlaps any entity that blocks placement.
+fn check_footprint_clear(ctx: &ReducerContext, center_x: f32, center_y: f32, radius: f32) -> Result<(), String> {
+    if radius <= 0.0 {
+        return Ok(());
+    }
+    for entity in spatial_grid::get_entities_in_radius(ctx, center_x, center_y, radius + MAX_BLOCKING_RADIUS) {
+        let Some((x, y, entity_radius)) = blocking_footprint(ctx, &entity) else { continue };
+        let (dx, dy) = (x - center_x, y - center_y);
+        let min_dist = radius + entity_radius;
+        if dx * dx + dy * dy < min_dist * min_dist {
+            log::debug!("[Placement] Footprint at ({:.1}, {:.1}) blocked by {:?}.", center_x, center_y, entity);
+            return Err("Something is in the way.".to_string());
+        }
+    }
+    Ok(())
+}
+
+/// Collision circle (x, y, radius) of an entity that placed things can't overlap, or
+/// None for things that don't block placement (items, plants, animals, corpses, stashes).
+fn blocking_footprint(ctx: &ReducerContext, entity: &EntityType) -> Option<(f32, f32, f32)> {
+    match *entity {
+        EntityType::Player(id) => ctx.db.player().identity().find(id)
+            .filter(|p| !p.is_dead)
+            .map(|p| (p.position_x, p.position_y, PLAYER_RADIUS)),
+        EntityType::Tree(id) => ctx.db.tree().id().find(id)
+            .filter(|t| t.health > 0)
+            .map(|t| (t.pos_x, t.pos_y - crate::tree::TREE_COLLISION_Y_OFFSET, crate::tree::TREE_TRUNK_RADIUS)),
+        EntityType::TreeStump(id) => ctx.db.tree_stump().id().find(id)
+            .map(|s| (s.pos_x, s.pos_y - crate::tree::TREE_COLLISION_Y_OFFSET, crate::tree::TREE_TRUNK_RADIUS)),
+        EntityType::Stone(id) => ctx.db.stone().id().find(id)
+            .filter(|s| s.health > 0)
+            .map(|s| (s.pos_x, s.pos_y - crate::stone::STONE_COLLISION_Y_OFFSET, crate::stone::STONE_RADIUS)),
+        EntityType::Campfire(id) => ctx.db.campfire().id().find(id)
+            .filter(|c| !c.is_destroyed)
+            .map(|c| (c.pos_x, c.pos_y - crate::campfire::CAMPFIRE_COLLISION_Y_OFFSET, crate::campfire::CAMPFIRE_COLLISION_RADIUS)),
+        EntityType::WoodenStorageBox(id) => ctx.db.wooden_storage_box().id().find(id)
+            .filter(|b| !b.is_destroyed)
+            .map(|b| (b.pos_x, b.pos_y - crate::wooden_storage_box::BOX_COLLISION_Y_OFFSET, crate::wooden_storage_box::BOX_COLLISION_RADIUS)),
+        EntityType::LargeStorageChest(id) => ctx.db.large_storage_chest().id().find(id)
+            .filter(|c| !c.is_destroyed)
+            .map(|c| (c.pos_x, c.pos_y - crate::large_storage_chest::LARGE_CHEST_COLLISION_Y_OFFSET, crate::large_storage_chest::LARGE_CHEST_COLLISION_RADIUS)),
+        EntityType::BuildingPiece(id) => ctx.db.building_piece().id().find(id)
+            .filter(|p| !p.is_destroyed && p.is_solid())
+            .map(|p| (p.pos_x, p.pos_y - BUILDING_COLLISION_Y_OFFSET, p.collision_radius())),
+        EntityType::Furnace(id) => ctx.db.furnace().id().find(id)
+            .filter(|f| !f.is_destroyed)
+            .map(|f| (f.pos_x, f.pos_y - crate::furnace::FURNACE_COLLISION_Y_OFFSET, crate::furnace::FURNACE_COLLISION_RADIUS)),
+        EntityType::Workbench(id) => ctx.db.workbench().id().find(id)
+            .filter(|w| !w.is_destroyed)
+            .map(|w| (w.pos_x, w.pos_y - crate::workbench::WORKBENCH_COLLISION_Y_OFFSET, crate::workbench::WORKBENCH_COLLISION_RADIUS)),
+        EntityType::Recycler(id) => ctx.db.recycler().id().find(id)
+            .filter(|r| !r.is_destroyed)
+            .map(|r| (r.pos_x, r.pos_y - crate::recycler::RECYCLER_COLLISION_Y_OFFSET, crate::recycler::RECYCLER_COLLISION_RADIUS)),
+        EntityType::FlameTurret(id) => ctx.db.flame_turret().id().find(id)
+            .filter(|t| !t.is_destroyed)
+            .map(|t| (t.pos_x, t.pos_y - crate::flame_turret::FLAME_TURRET_COLLISION_Y_OFFSET, crate::flame_turret::FLAME_TURRET_COLLISION_RADIUS)),
+        EntityType::StandingTorch(id) => ctx.db.standing_torch().id().find(id)
+            .map(|t| (t.pos_x, t.pos_y, crate::standing_torch::STANDING_TORCH_COLLISION_RADIUS)),
+        EntityType::ResearchTable(id) => ctx.db.research_table().id().find(id)
+            .map(|t| (t.pos_x, t.pos_y, crate::research::RESEARCH_TABLE_COLLISION_RADIUS)),
+        EntityType::SleepingBag(id) => ctx.db.sleeping_bag().id().find(id)
+            .filter(|b| !b.is_destroyed)
+            .map(|b| (b.pos_x, b.pos_y - crate::sleeping_bag::SLEEPING_BAG_COLLISION_Y_OFFSET, crate::sleeping_bag::SLEEPING_BAG_COLLISION_RADIUS)),
+        EntityType::Sign(id) => ctx.db.sign().id().find(id)
+            .filter(|s| !s.is_destroyed)
+            .map(|s| (s.pos_x, s.pos_y - crate::sign::SIGN_COLLISION_Y_OFFSET, crate::sign::SIGN_COLLISION_RADIUS)),
+        EntityType::Switch(id) => ctx.db.wiring_switch().id().find(id)
+            .filter(|s| !s.is_destroyed)
+            .map(|s| (s.pos_x, s.pos_y - crate::wiring::SWITCH_COLLISION_Y_OFFSET, crate::wiring::SWITCH_COLLISION_RADIUS)),
+        EntityType::VendingMachine(id) => ctx.db.vending_machine().id().find(id)
+            .map(|m| (m.pos_x, m.pos_y - crate::vending_machine::VENDING_MACHINE_COLLISION_Y_OFFSET, crate::vending_machine::VENDING_MACHINE_COLLISION_RADIUS)),
+        EntityType::LootCrate(id) => ctx.db.loot_crate().id().find(id)
+            .map(|c| (c.pos_x, c.pos_y - crate::loot_crate::LOOT_CRATE_COLLISION_Y_OFFSET, crate::loot_crate::LOOT_CRATE_COLLISION_RADIUS)),
+        EntityType::EnvironmentHazard(id) => ctx.db.environment_hazard().id().find(id)
+            .filter(|h| h.health > 0)
+            .map(|h| (h.pos_x, h.pos_y, crate::environment_hazard::HAZARD_RADIUS)),
+        EntityType::Mushroom(_) | EntityType::DroppedItem(_) | EntityType::Animal(_)
+            | EntityType::PlayerCorpse(_) | EntityType::Stash(_) | EntityType::Mount(_) => None,
+    }
+}
+
+/// Runs the placement checks for whichever kind of placeable `item_def_id` is,
+/// after checking the player actually carries one.
+fn check_placement_for_def(ctx: &ReducerContext, player_id: Identity, item_def_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, player_id)?;
+    let player = ctx.db.player().identity().find(player_id)
+        .ok_or_else(|| "Player not found".to_string())?;
+    let item_def = ctx.db.item_definition().id().find(item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found.", item_def_id))?;
+    let carries_item = ctx.db.inventory_item().iter().any(|item| {
+        item.item_def_id == item_def_id
+            && item.location.is_player_bound() == Some(player_id)
+            && !matches!(item.location, ItemLocation::Equipped(_))
+    });
+    if !carries_item {
+        return Err(format!("You don't have a {} in your inventory or hotbar.", item_def.name));
+    }
+
+    match BuildingPieceType::from_item_name(&item_def.name) {
+        Some(piece_type) => crate::building::check_building_piece_placement(ctx, &player, piece_type, world_x, world_y).map(|_| ()),
+        None => check_placement(ctx, &player, &item_def.name, world_x, world_y),
+    }
+}
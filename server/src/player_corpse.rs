@@ -1,17 +1,21 @@
 /******************************************************************************
  *                                                                            *
  * Defines the PlayerCorpse entity, representing a lootable container dropped *
- * upon player death.                                                         *
+ * upon player death. Corpses decay in stages: a fresh corpse holds the whole *
+ * inventory and can be harvested for Bone Fragments; after a timer (or once  *
+ * stripped of flesh) it becomes a smaller backpack holding what is left, and *
+ * finally despawns. A player killed in PvP leaves a corpse only their killer *
+ * (and the killer's team) can loot for the first few seconds.                *
  *                                                                            *
  ******************************************************************************/
 
-use spacetimedb::{Identity, Timestamp, ReducerContext, SpacetimeType, Table};
+use spacetimedb::{Identity, Timestamp, TimeDuration, ReducerContext, SpacetimeType, Table};
 use log;
 use spacetimedb::spacetimedb_lib::ScheduleAt;
 use std::time::Duration;
 
 // Import new models
-use crate::models::{ItemLocation, ContainerType, EquipmentSlotType, ContainerLocationData}; // <<< ADDED ContainerLocationData
+use crate::models::{ItemLocation, ContainerType, ContainerLocationData, DeathCause}; // <<< ADDED ContainerLocationData
 
 // Define constants for the corpse
 const DEFAULT_CORPSE_DESPAWN_SECONDS: u64 = 300; // Default to 5 minutes if no items or no respawn times set
@@ -20,6 +24,15 @@ pub(crate) const CORPSE_COLLISION_Y_OFFSET: f32 = 10.0; // Similar to box/campfi
 pub(crate) const PLAYER_CORPSE_COLLISION_DISTANCE_SQUARED: f32 = (super::PLAYER_RADIUS + CORPSE_COLLISION_RADIUS) * (super::PLAYER_RADIUS + CORPSE_COLLISION_RADIUS);
 pub(crate) const PLAYER_CORPSE_INTERACTION_DISTANCE_SQUARED: f32 = 64.0 * 64.0; // Similar interaction range
 pub(crate) const NUM_CORPSE_SLOTS: usize = 30 + 5; // 24 inv + 6 hotbar + 5 equipment (example)
+pub(crate) const NUM_BACKPACK_SLOTS: usize = 12; // Slots left once the corpse has decayed into a backpack
+const CORPSE_DECAY_TO_BACKPACK_SECS: u64 = 120; // Fresh corpse lifetime before it becomes a backpack
+const CORPSE_FLESH_HEALTH: f32 = 100.0; // Harvest damage a fresh corpse absorbs before only bones are left
+pub(crate) const CORPSE_BONE_YIELD_MIN: u32 = 2; // Bone Fragments per harvesting hit
+pub(crate) const CORPSE_BONE_YIELD_MAX: u32 = 4;
+/// Default seconds a PvP victim's corpse is reserved for the killer (see `GameConfig::killer_loot_window_secs`).
+pub(crate) const KILLER_LOOT_WINDOW_SECS: f32 = 60.0;
+/// Whether the killer's teammates share the killer's looting window.
+const KILLER_TEAM_CAN_LOOT: bool = true;
 
 // Import required items
 use crate::environment::calculate_chunk_index;
@@ -31,6 +44,16 @@ use crate::player_corpse::player_corpse as PlayerCorpseTableTrait; // Self trait
 use crate::player;
 use crate::player_inventory::{move_item_to_inventory, move_item_to_hotbar, NUM_PLAYER_INVENTORY_SLOTS, NUM_PLAYER_HOTBAR_SLOTS};
 use crate::items::add_item_to_player_inventory;
+use crate::player_corpse::player_corpse_decay_schedule as PlayerCorpseDecayScheduleTableTrait;
+use crate::spatial_grid::{self, EntityType};
+use crate::dropped_item;
+
+/// Decay stage of a corpse.
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum CorpseStage {
+    Fresh,    // Full corpse with every slot, harvestable for bones
+    Backpack, // Decayed; only the first NUM_BACKPACK_SLOTS slots remain
+}
 
 /// --- Player Corpse Data Structure ---
 /// Represents a lootable backpack dropped when a player dies.
@@ -47,10 +70,16 @@ pub struct PlayerCorpse {
 
     pub pos_x: f32,
     pub pos_y: f32,
+    #[index(btree)]
     pub chunk_index: u32, // For spatial queries
 
     pub death_time: Timestamp,
+    pub death_cause: DeathCause, // Shown on the death screen
     pub despawn_scheduled_at: Timestamp, // When this corpse should be removed
+    pub stage: CorpseStage,
+    pub flesh_remaining: f32, // Harvest damage left before the corpse is stripped to a backpack
+    pub killer_identity: Option<Identity>, // Player who landed the killing blow, if any
+    pub killer_loot_until: Option<Timestamp>, // Until then only the killer (and their team) can loot
 
     // --- Inventory Slots (0-NUM_CORPSE_SLOTS-1) ---
     // Conceptually: Player inv (0-23), hotbar (24-29), equipment (30-34)
@@ -94,7 +123,10 @@ pub struct PlayerCorpse {
 
 impl ItemContainer for PlayerCorpse {
     fn num_slots(&self) -> usize {
-        NUM_CORPSE_SLOTS
+        match self.stage {
+            CorpseStage::Fresh => NUM_CORPSE_SLOTS,
+            CorpseStage::Backpack => NUM_BACKPACK_SLOTS,
+        }
     }
 
     fn get_slot_instance_id(&self, slot_index: u8) -> Option<u64> {
@@ -307,17 +339,97 @@ pub fn process_corpse_despawn(ctx: &ReducerContext, args: PlayerCorpseDespawnSch
     // The schedule entry is automatically removed by SpacetimeDB when the scheduled reducer runs.
     // No need to manually delete from PlayerCorpseDespawnSchedule table here.
     player_corpse_table.id().delete(corpse_id_to_despawn as u32); // Cast u64 to u32 for delete
+    ctx.db.player_corpse_decay_schedule().corpse_id().delete(corpse_id_to_despawn); // In case it despawns before decaying
+    spatial_grid::untrack_entity(ctx, EntityType::PlayerCorpse(corpse_id_to_despawn as u32));
     log::info!("[CorpseDespawn:{}] Corpse and its items ({} count) deleted.", corpse_id_to_despawn, items_deleted_count);
 
     Ok(())
 }
 
+/******************************************************************************
+ *                            DECAY SCHEDULING                                *
+ ******************************************************************************/
+
+#[spacetimedb::table(name = player_corpse_decay_schedule, scheduled(process_corpse_decay))]
+#[derive(Clone)]
+pub struct PlayerCorpseDecaySchedule {
+    #[primary_key]
+    pub corpse_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/// --- Corpse Decay (Scheduled) ---
+/// Scheduled reducer that turns a fresh corpse into a backpack.
+#[spacetimedb::reducer]
+pub fn process_corpse_decay(ctx: &ReducerContext, args: PlayerCorpseDecaySchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("process_corpse_decay can only be called by the scheduler".to_string());
+    }
+
+    let mut corpse = match ctx.db.player_corpse().id().find(args.corpse_id as u32) {
+        Some(corpse) => corpse,
+        None => return Ok(()), // Already despawned
+    };
+    if corpse.stage == CorpseStage::Fresh {
+        convert_corpse_to_backpack(ctx, &mut corpse);
+        ctx.db.player_corpse().id().update(corpse);
+    }
+    Ok(())
+}
+
+/// Decays a fresh corpse into a backpack. Remaining items are packed into the first
+/// `NUM_BACKPACK_SLOTS` slots; anything that does not fit is dropped on the ground
+/// beside it. The caller is responsible for persisting `corpse`.
+pub(crate) fn convert_corpse_to_backpack(ctx: &ReducerContext, corpse: &mut PlayerCorpse) {
+    let inventory_table = ctx.db.inventory_item();
+    let mut next_slot: u8 = 0;
+    let mut items_to_drop: Vec<InventoryItem> = Vec::new();
+
+    for i in 0..NUM_CORPSE_SLOTS as u8 {
+        let (Some(instance_id), def_id) = (corpse.get_slot_instance_id(i), corpse.get_slot_def_id(i)) else {
+            continue;
+        };
+        corpse.set_slot(i, None, None);
+        let Some(mut item) = inventory_table.instance_id().find(instance_id) else {
+            continue;
+        };
+        if (next_slot as usize) < NUM_BACKPACK_SLOTS {
+            corpse.set_slot(next_slot, Some(instance_id), def_id);
+            item.location = ItemLocation::Container(ContainerLocationData {
+                container_type: ContainerType::PlayerCorpse,
+                container_id: corpse.id as u64,
+                slot_index: next_slot,
+            });
+            inventory_table.instance_id().update(item);
+            next_slot += 1;
+        } else {
+            items_to_drop.push(item);
+        }
+    }
+
+    for (n, item) in items_to_drop.iter().enumerate() {
+        // Spread overflow in a small ring around the corpse
+        let angle = n as f32 * 0.9;
+        let drop_x = corpse.pos_x + angle.cos() * 24.0;
+        let drop_y = corpse.pos_y + angle.sin() * 24.0;
+        inventory_table.instance_id().delete(item.instance_id);
+        if let Err(e) = dropped_item::create_owned_dropped_item_entity(ctx, Some(corpse.player_identity), item.item_def_id, item.quantity, drop_x, drop_y, item.durability, item.charges) {
+            log::error!("[CorpseDecay:{}] Failed to drop overflow item {}: {}", corpse.id, item.instance_id, e);
+        }
+    }
+
+    corpse.stage = CorpseStage::Backpack;
+    corpse.flesh_remaining = 0.0;
+    ctx.db.player_corpse_decay_schedule().corpse_id().delete(corpse.id as u64);
+    log::info!("[CorpseDecay:{}] Corpse decayed into a backpack ({} items kept, {} dropped).", corpse.id, next_slot, items_to_drop.len());
+}
+
 /******************************************************************************
  *                          INTERACTION REDUCERS                            *
  ******************************************************************************/
 
 /// Helper to validate player distance and fetch corpse/player entities.
-fn validate_corpse_interaction(
+pub(crate) fn validate_corpse_interaction(
     ctx: &ReducerContext,
     corpse_id: u32,
 ) -> Result<(Player, PlayerCorpse), String> { 
@@ -331,9 +443,23 @@ fn validate_corpse_interaction(
     if dist_sq > PLAYER_CORPSE_INTERACTION_DISTANCE_SQUARED {
         return Err("Too far away from corpse".to_string());
     }
+    if !can_loot(ctx, &corpse, player.identity, ctx.timestamp) {
+        return Err("Only the killer can loot this corpse right now.".to_string());
+    }
     Ok((player, corpse))
 }
 
+/// True unless the corpse is still reserved for its killer and the player isn't them
+/// (or, with KILLER_TEAM_CAN_LOOT, one of their teammates).
+pub(crate) fn can_loot(ctx: &ReducerContext, corpse: &PlayerCorpse, player_id: Identity, now: Timestamp) -> bool {
+    match (corpse.killer_identity, corpse.killer_loot_until) {
+        (Some(killer_id), Some(until)) if now < until => {
+            player_id == killer_id || (KILLER_TEAM_CAN_LOOT && crate::team::are_teammates(ctx, killer_id, player_id))
+        }
+        _ => true,
+    }
+}
+
 /// --- Move Item FROM Corpse --- 
 /// Moves an item FROM a corpse slot INTO the player's inventory/hotbar.
 #[spacetimedb::reducer]
@@ -523,7 +649,7 @@ pub fn split_and_drop_item_from_corpse_slot_to_world(
 /// and schedules despawn.
 
 // Placeholder for the missing function
-fn transfer_inventory_to_corpse(ctx: &ReducerContext, dead_player: &Player) -> Result<u32, String> {
+fn transfer_inventory_to_corpse(ctx: &ReducerContext, dead_player: &Player, death_cause: DeathCause, killer_id: Option<Identity>) -> Result<u32, String> {
     let mut inventory_table = ctx.db.inventory_item();
     let mut player_corpse_table = ctx.db.player_corpse();
     let player_id = dead_player.identity;
@@ -554,6 +680,11 @@ fn transfer_inventory_to_corpse(ctx: &ReducerContext, dead_player: &Player) -> R
     }
 
     // 2. Create a new PlayerCorpse instance
+    let loot_window_secs = crate::game_config::get_game_config(ctx).killer_loot_window_secs;
+    let killer_identity = killer_id.filter(|id| *id != player_id);
+    let killer_loot_until = killer_identity
+        .filter(|_| loot_window_secs > 0.0)
+        .map(|_| ctx.timestamp + TimeDuration::from_micros((loot_window_secs * 1_000_000.0) as i64));
     let mut new_corpse = PlayerCorpse {
         id: 0, // Will be auto-incremented
         player_identity: player_id,
@@ -562,7 +693,12 @@ fn transfer_inventory_to_corpse(ctx: &ReducerContext, dead_player: &Player) -> R
         pos_y: dead_player.position_y,
         chunk_index: calculate_chunk_index(dead_player.position_x, dead_player.position_y),
         death_time: ctx.timestamp,
+        death_cause,
         despawn_scheduled_at: ctx.timestamp + Duration::from_secs(DEFAULT_CORPSE_DESPAWN_SECONDS), // This will be set in create_corpse_for_player
+        stage: CorpseStage::Fresh,
+        flesh_remaining: CORPSE_FLESH_HEALTH,
+        killer_identity,
+        killer_loot_until,
         slot_instance_id_0: None, slot_def_id_0: None,
         slot_instance_id_1: None, slot_def_id_1: None,
         slot_instance_id_2: None, slot_def_id_2: None,
@@ -636,6 +772,7 @@ fn transfer_inventory_to_corpse(ctx: &ReducerContext, dead_player: &Player) -> R
         }
     };
     log::info!("[PlayerCorpse] Inserted corpse with ID {} for player {}", inserted_corpse.id, player_id);
+    spatial_grid::track_entity(ctx, EntityType::PlayerCorpse(inserted_corpse.id), inserted_corpse.pos_x, inserted_corpse.pos_y);
 
     // 5. Update ItemLocation for all transferred items with the actual corpse ID
     for (item_instance_id, mut target_location) in updated_item_locations {
@@ -656,11 +793,12 @@ fn transfer_inventory_to_corpse(ctx: &ReducerContext, dead_player: &Player) -> R
 }
 
 /// --- Main public function to create a corpse and transfer items ---
-/// This is intended to be called when a player dies.
-pub fn create_player_corpse(ctx: &ReducerContext, dead_player_id: Identity, death_x: f32, death_y: f32, dead_player_username: &str) -> Result<(), String> {
+/// This is intended to be called when a player dies. `killer_id` is the player who
+/// landed the killing blow, who gets the corpse to themselves for a while.
+pub fn create_player_corpse(ctx: &ReducerContext, dead_player_id: Identity, death_x: f32, death_y: f32, dead_player_username: &str, death_cause: DeathCause, killer_id: Option<Identity>) -> Result<(), String> {
     log::info!(
-        "Creating corpse for player {} ({:?}) at ({:.1}, {:.1}).",
-        dead_player_username, dead_player_id, death_x, death_y
+        "Creating corpse for player {} ({:?}) at ({:.1}, {:.1}), cause {:?}.",
+        dead_player_username, dead_player_id, death_x, death_y, death_cause
     );
 
     let player_table = ctx.db.player();
@@ -676,10 +814,8 @@ pub fn create_player_corpse(ctx: &ReducerContext, dead_player_id: Identity, deat
     // The transfer_inventory_to_corpse function should handle un-equipping armor and moving it.
     // So, explicit calls to clear_all_equipped_armor_from_player are likely redundant here.
 
-    let new_corpse_id = match transfer_inventory_to_corpse(ctx, &player_table.identity().find(dead_player_id).ok_or_else(|| format!("Player {} not found", dead_player_id))?) {
-        Ok(id) => id,
-        Err(e) => return Err(e),
-    };
+    let dead_player = player_table.identity().find(dead_player_id).ok_or_else(|| format!("Player {} not found", dead_player_id))?;
+    let new_corpse_id = transfer_inventory_to_corpse(ctx, &dead_player, death_cause, killer_id)?;
 
     // --- 4. Schedule Despawn (Dynamically based on corpse contents) --- 
     let corpse_for_despawn_check = match ctx.db.player_corpse().id().find(new_corpse_id) {
@@ -736,5 +872,13 @@ pub fn create_player_corpse(ctx: &ReducerContext, dead_player_id: Identity, deat
         scheduled_at: despawn_time.into(),
     });
 
+    // Decay into a backpack first, unless the corpse despawns before then
+    if despawn_duration_seconds > CORPSE_DECAY_TO_BACKPACK_SECS {
+        ctx.db.player_corpse_decay_schedule().insert(PlayerCorpseDecaySchedule {
+            corpse_id: new_corpse_id as u64,
+            scheduled_at: (ctx.timestamp + Duration::from_secs(CORPSE_DECAY_TO_BACKPACK_SECS)).into(),
+        });
+    }
+
     Ok(())
 } 
\ No newline at end of file
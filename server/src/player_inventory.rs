@@ -1,24 +1,39 @@
-use spacetimedb::{ReducerContext, Identity, Table};
+use spacetimedb::{ReducerContext, Identity, SpacetimeType, Table};
 use log;
 use std::collections::HashSet; // Needed for slot checks
 
 // Import necessary types, traits, and helpers from other modules
 use crate::items::{
     InventoryItem, ItemDefinition, calculate_merge_result, split_stack_helper,
-    clear_specific_item_from_equipment_slots
+    clear_specific_item_from_equipment_slots, add_item_to_player_inventory
 };
 use crate::items::{
     inventory_item as InventoryItemTableTrait,
     item_definition as ItemDefinitionTableTrait
 };
 use crate::active_equipment::active_equipment as ActiveEquipmentTableTrait; // Needed for clearing equip slot
+use crate::player as PlayerTableTrait; // Needed for give_item_to_player range checks and dead checks
 use crate::models::{ItemLocation, EquipmentSlotType}; // <<< ADDED IMPORT
+use crate::models::ContainerType;
+use crate::inventory_management::first_empty_container_slot;
+use crate::campfire::campfire as CampfireTableTrait;
+use crate::furnace::furnace as FurnaceTableTrait;
+use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
+use crate::large_storage_chest::large_storage_chest as LargeStorageChestTableTrait;
+use crate::stash::stash as StashTableTrait;
+use crate::player_corpse::player_corpse as PlayerCorpseTableTrait;
+use crate::recycler::recycler as RecyclerTableTrait;
+use crate::flame_turret::flame_turret as FlameTurretTableTrait;
+use crate::overflow_backpack::dropped_backpack as DroppedBackpackTableTrait;
+use crate::vending_machine::vending_machine as VendingMachineTableTrait;
 
 // Placeholder for future content 
 
 // <<< ADDED Constants >>>
 pub(crate) const NUM_PLAYER_INVENTORY_SLOTS: u16 = 24;
 pub(crate) const NUM_PLAYER_HOTBAR_SLOTS: u8 = 6;
+const GIVE_ITEM_DISTANCE_SQUARED: f32 = 96.0 * 96.0;
+const MAX_MOVE_BATCH_SIZE: usize = 64;
 // <<< END Added Constants >>>
 
 // --- Helper Functions --- 
@@ -598,6 +613,99 @@ pub fn split_stack(
     Ok(())
 }
 
+/// --- Split Stack Half ---
+/// Splits half of a stack (rounded down) into the first empty slot next to it.
+#[spacetimedb::reducer]
+pub fn split_stack_half(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), String> {
+    split_stack_percent(ctx, item_instance_id, 50)
+}
+
+/// --- Split Stack Percent ---
+/// Splits `percent` of a stack (rounded down, at least one) into the first empty slot of
+/// wherever the stack is: the player's inventory/hotbar or the container it's in.
+/// Goes through the same split reducers as a manual split, so the usual checks apply.
+#[spacetimedb::reducer]
+pub fn split_stack_percent(ctx: &ReducerContext, item_instance_id: u64, percent: u8) -> Result<(), String> {
+    if !(1..=99).contains(&percent) {
+        return Err("Split percentage must be between 1 and 99.".to_string());
+    }
+    let item = ctx.db.inventory_item().instance_id().find(item_instance_id)
+        .ok_or_else(|| format!("Item instance {} not found.", item_instance_id))?;
+    if item.quantity < 2 {
+        return Err("Stack is too small to split.".to_string());
+    }
+    let quantity_to_split = ((item.quantity as u64 * percent as u64 / 100) as u32).clamp(1, item.quantity - 1);
+
+    match &item.location {
+        ItemLocation::Inventory(_) | ItemLocation::Hotbar(_) => {
+            let (target_slot_type, target_slot_index) = match find_first_empty_player_slot(ctx, ctx.sender) {
+                Some(ItemLocation::Inventory(data)) => ("inventory", data.slot_index as u32),
+                Some(ItemLocation::Hotbar(data)) => ("hotbar", data.slot_index as u32),
+                _ => return Err("No empty slot to split into.".to_string()),
+            };
+            split_stack(ctx, item_instance_id, quantity_to_split, target_slot_type.to_string(), target_slot_index)
+        }
+        ItemLocation::Container(data) => {
+            let container_id = data.container_id as u32;
+            let source_slot_index = data.slot_index;
+            let no_space = || "No empty slot to split into.".to_string();
+            match data.container_type {
+                ContainerType::Campfire => {
+                    let campfire = ctx.db.campfire().id().find(container_id).ok_or("Campfire not found.")?;
+                    let target = first_empty_container_slot(&campfire).ok_or_else(no_space)?;
+                    crate::campfire::split_stack_within_campfire(ctx, container_id, source_slot_index, quantity_to_split, target)
+                }
+                ContainerType::Furnace => {
+                    let furnace = ctx.db.furnace().id().find(container_id).ok_or("Furnace not found.")?;
+                    let target = first_empty_container_slot(&furnace).ok_or_else(no_space)?;
+                    crate::furnace::split_stack_within_furnace(ctx, container_id, source_slot_index, quantity_to_split, target)
+                }
+                ContainerType::WoodenStorageBox => {
+                    let storage_box = ctx.db.wooden_storage_box().id().find(container_id).ok_or("Storage box not found.")?;
+                    let target = first_empty_container_slot(&storage_box).ok_or_else(no_space)?;
+                    crate::wooden_storage_box::split_stack_within_box(ctx, container_id, source_slot_index, target, quantity_to_split)
+                }
+                ContainerType::LargeStorageChest => {
+                    let chest = ctx.db.large_storage_chest().id().find(container_id).ok_or("Large chest not found.")?;
+                    let target = first_empty_container_slot(&chest).ok_or_else(no_space)?;
+                    crate::large_storage_chest::split_stack_within_large_chest(ctx, container_id, source_slot_index, target, quantity_to_split)
+                }
+                ContainerType::Stash => {
+                    let stash = ctx.db.stash().id().find(container_id).ok_or("Stash not found.")?;
+                    let target = first_empty_container_slot(&stash).ok_or_else(no_space)?;
+                    crate::stash::split_stack_within_stash(ctx, container_id, source_slot_index, target, quantity_to_split)
+                }
+                ContainerType::PlayerCorpse => {
+                    let corpse = ctx.db.player_corpse().id().find(container_id).ok_or("Corpse not found.")?;
+                    let target = first_empty_container_slot(&corpse).ok_or_else(no_space)?;
+                    crate::player_corpse::split_stack_within_corpse(ctx, container_id, source_slot_index, target, quantity_to_split)
+                }
+                ContainerType::Recycler => {
+                    let recycler = ctx.db.recycler().id().find(container_id).ok_or("Recycler not found.")?;
+                    let target = first_empty_container_slot(&recycler).ok_or_else(no_space)?;
+                    crate::recycler::split_stack_within_recycler(ctx, container_id, source_slot_index, target, quantity_to_split)
+                }
+                ContainerType::DroppedBackpack => {
+                    let backpack = ctx.db.dropped_backpack().id().find(container_id).ok_or("Backpack not found.")?;
+                    let target = first_empty_container_slot(&backpack).ok_or_else(no_space)?;
+                    crate::overflow_backpack::split_stack_within_dropped_backpack(ctx, container_id, source_slot_index, target, quantity_to_split)
+                }
+                ContainerType::VendingMachine => {
+                    let machine = ctx.db.vending_machine().id().find(container_id).ok_or("Vending machine not found.")?;
+                    let target = first_empty_container_slot(&machine).ok_or_else(no_space)?;
+                    crate::vending_machine::split_stack_within_vending_machine(ctx, container_id, source_slot_index, target, quantity_to_split)
+                }
+                ContainerType::FlameTurret => {
+                    let turret = ctx.db.flame_turret().id().find(container_id).ok_or("Flame turret not found.")?;
+                    let target = first_empty_container_slot(&turret).ok_or_else(no_space)?;
+                    crate::flame_turret::split_stack_within_flame_turret(ctx, container_id, source_slot_index, target, quantity_to_split)
+                }
+            }
+        }
+        _ => Err("Only stacks in your inventory, hotbar or a container can be split.".to_string()),
+    }
+}
+
 #[spacetimedb::reducer]
 pub fn move_to_first_available_hotbar_slot(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), String> {
     let sender_id = ctx.sender;
@@ -673,4 +781,150 @@ pub fn move_to_first_available_hotbar_slot(ctx: &ReducerContext, item_instance_i
     }
 }
 
+/// Swaps the contents of two of the sender's hotbar slots (either may be empty).
+/// Unlike `move_item_to_hotbar`, stacks are never merged and the active item stays active.
+#[spacetimedb::reducer]
+pub fn swap_hotbar_slots(ctx: &ReducerContext, slot_a: u8, slot_b: u8) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Player not found.".to_string())?;
+    if player.is_dead {
+        return Err("Cannot rearrange the hotbar while dead.".to_string());
+    }
+    if slot_a >= NUM_PLAYER_HOTBAR_SLOTS || slot_b >= NUM_PLAYER_HOTBAR_SLOTS {
+        return Err("Invalid hotbar slot index".to_string());
+    }
+    if slot_a == slot_b {
+        return Ok(());
+    }
+
+    let inventory_items = ctx.db.inventory_item();
+    let item_a = find_item_in_hotbar_slot(ctx, slot_a);
+    let item_b = find_item_in_hotbar_slot(ctx, slot_b);
+    for (item_opt, new_slot) in [(item_a, slot_b), (item_b, slot_a)] {
+        if let Some(mut item) = item_opt {
+            item.location = ItemLocation::Hotbar(crate::models::HotbarLocationData { owner_id: sender_id, slot_index: new_slot });
+            inventory_items.instance_id().update(item);
+        }
+    }
+    log::debug!("[SwapHotbar] Player {:?} swapped hotbar slots {} and {}.", sender_id, slot_a, slot_b);
+    Ok(())
+}
+
+/// A single step of `move_stack_batch`.
+#[derive(SpacetimeType, Clone, Debug)]
+pub struct InventoryMoveOp {
+    pub item_instance_id: u64,
+    pub target_slot_type: String, // "inventory" or "hotbar"
+    pub target_slot_index: u32,
+    pub quantity: Option<u32>,    // None moves the whole stack, Some(n) splits n off into the target slot
+}
+
+/// Applies a list of inventory/hotbar moves in order as one transaction, so the client can
+/// reorganize the inventory in a single round trip. Each step behaves like `move_item_to_inventory`,
+/// `move_item_to_hotbar` or `split_stack`; if any step fails, none of the moves are applied.
+#[spacetimedb::reducer]
+pub fn move_stack_batch(ctx: &ReducerContext, operations: Vec<InventoryMoveOp>) -> Result<(), String> {
+    if operations.len() > MAX_MOVE_BATCH_SIZE {
+        return Err(format!("Too many moves in one batch (max {}).", MAX_MOVE_BATCH_SIZE));
+    }
+
+    for (index, op) in operations.into_iter().enumerate() {
+        let result = match (op.quantity, op.target_slot_type.as_str()) {
+            (Some(quantity), _) => split_stack(ctx, op.item_instance_id, quantity, op.target_slot_type.clone(), op.target_slot_index),
+            (None, "inventory") => {
+                let slot = u16::try_from(op.target_slot_index).map_err(|_| "Invalid target inventory slot index".to_string())?;
+                move_item_to_inventory(ctx, op.item_instance_id, slot)
+            }
+            (None, "hotbar") => {
+                let slot = u8::try_from(op.target_slot_index).map_err(|_| "Invalid target hotbar slot index".to_string())?;
+                move_item_to_hotbar(ctx, op.item_instance_id, slot)
+            }
+            (None, other) => Err(format!("Invalid target slot type: {}", other)),
+        };
+        // Returning the error rolls back every move already applied in this batch
+        result.map_err(|e| format!("Move {} failed: {}", index + 1, e))?;
+    }
+    Ok(())
+}
+
+/// Hands an item (or part of a stack) from the sender's inventory or hotbar to a nearby player.
+/// Partial stacks are split straight into a free slot of the target, whole stacks keep their
+/// instance. If the target has no free slot, stackable items are merged into their existing stacks.
+#[spacetimedb::reducer]
+pub fn give_item_to_player(ctx: &ReducerContext, target_identity: Identity, item_instance_id: u64, quantity: u32) -> Result<(), String> {
+    crate::moderation::ensure_not_sanctioned(ctx, ctx.sender)?;
+    let sender_id = ctx.sender;
+    if target_identity == sender_id {
+        return Err("Cannot give an item to yourself.".to_string());
+    }
+
+    // --- 1. Validate both players ---
+    let players = ctx.db.player();
+    let giver = players.identity().find(sender_id)
+        .ok_or_else(|| "Player not found.".to_string())?;
+    let receiver = players.identity().find(target_identity)
+        .ok_or_else(|| "Target player not found.".to_string())?;
+    if giver.is_dead || receiver.is_dead {
+        return Err("Both players must be alive to hand over items.".to_string());
+    }
+    if !receiver.is_online {
+        return Err("Target player is not online.".to_string());
+    }
+    let dx = giver.position_x - receiver.position_x;
+    let dy = giver.position_y - receiver.position_y;
+    if (dx * dx + dy * dy) > GIVE_ITEM_DISTANCE_SQUARED {
+        return Err("Target player is too far away.".to_string());
+    }
+
+    // --- 2. Validate the item ---
+    let mut item = get_player_item(ctx, item_instance_id)?;
+    if matches!(item.location, ItemLocation::Equipped(_)) {
+        return Err("Unequip the item before giving it away.".to_string());
+    }
+    if quantity == 0 || quantity > item.quantity {
+        return Err(format!("Invalid quantity {} (stack has {}).", quantity, item.quantity));
+    }
+    let item_def = ctx.db.item_definition().id().find(item.item_def_id)
+        .ok_or_else(|| format!("Definition not found for item ID {}", item.item_def_id))?;
+
+    // --- 3. Transfer ---
+    let target_slot = find_first_empty_player_slot(ctx, target_identity);
+    if quantity < item.quantity {
+        match target_slot {
+            Some(location) => {
+                split_stack_helper(ctx, &mut item, quantity, location)?;
+            }
+            None => {
+                add_item_to_player_inventory(ctx, target_identity, item.item_def_id, quantity)?;
+                item.quantity -= quantity;
+                ctx.db.inventory_item().instance_id().update(item);
+            }
+        }
+    } else {
+        let is_active_item = ctx.db.active_equipment().player_identity().find(sender_id)
+            .is_some_and(|equip| equip.equipped_item_instance_id == Some(item_instance_id));
+        if is_active_item {
+            crate::active_equipment::clear_active_item_reducer(ctx, sender_id)?;
+        }
+        match target_slot {
+            Some(location) => {
+                item.location = location;
+                ctx.db.inventory_item().instance_id().update(item);
+            }
+            None if item_def.is_stackable => {
+                add_item_to_player_inventory(ctx, target_identity, item.item_def_id, quantity)?;
+                ctx.db.inventory_item().instance_id().delete(item_instance_id);
+            }
+            None => return Err("Target player's inventory is full.".to_string()),
+        }
+    }
+
+    log::info!(
+        "[GiveItem] Player {:?} gave {} x {} (item {}) to {:?}.",
+        sender_id, quantity, item_def.name, item_instance_id, target_identity
+    );
+    Ok(())
+}
+
 // ... rest of items.rs ... 
\ No newline at end of file
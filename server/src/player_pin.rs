@@ -1,4 +1,29 @@
-use spacetimedb::{table, reducer, ReducerContext, Identity, Table, log};
+/******************************************************************************
+ *                                                                            *
+ * Minimap pins and markers. Each player has one quick pin (PlayerPin), any   *
+ * number of named, colored map markers up to a limit, and short-lived pings. *
+ * Markers can be shared with the owner's team; pings are always shared when  *
+ * the player is in a team. Row-level security filters only stream a player's *
+ * own markers and those shared with their team. Expired pings are removed by *
+ * a scheduled cleanup.                                                       *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{table, reducer, client_visibility_filter, Filter, ReducerContext, Identity, SpacetimeType, Table, Timestamp, TimeDuration, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+
+use crate::player_pin::map_marker as MapMarkerTableTrait;
+use crate::player_pin::map_ping_cleanup_schedule as MapPingCleanupScheduleTableTrait;
+
+// --- Constants ---
+pub const MAX_MAP_MARKERS_PER_PLAYER: usize = 20;
+const MAX_ACTIVE_PINGS_PER_PLAYER: usize = 3;
+const MAX_MARKER_LABEL_LENGTH: usize = 32;
+const PING_LIFETIME_SECS: u64 = 10;
+const PING_CLEANUP_INTERVAL_SECS: u64 = 2;
+/// Team id stored on markers that are not shared with a team (team ids start at 1).
+const UNSHARED_TEAM_ID: u64 = 0;
 
 // --- PlayerPin Table Definition ---
 #[table(name = player_pin, public)]
@@ -42,4 +67,230 @@ pub fn set_player_pin(ctx: &ReducerContext, pin_x: i32, pin_y: i32) -> Result<()
     }
 
     Ok(())
-} 
\ No newline at end of file
+}
+
+// --- Map Marker Table Definition ---
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum MapMarkerKind {
+    Marker, // Placed by the player, stays until removed
+    Ping,   // Transient, removed after PING_LIFETIME_SECS
+}
+
+#[table(name = map_marker, public)]
+#[derive(Clone, Debug)]
+pub struct MapMarker {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub owner_id: Identity,
+    #[index(btree)]
+    pub team_id: u64,    // Team the marker is shared with, or UNSHARED_TEAM_ID
+    pub kind: MapMarkerKind,
+    pub label: String,
+    pub color: String,   // "#rrggbb"
+    pub pos_x: i32,      // World X coordinate of the marker
+    pub pos_y: i32,      // World Y coordinate of the marker
+    pub created_at: Timestamp,
+    pub expires_at: Option<Timestamp>, // Only set for pings
+}
+
+// --- Ping Cleanup Schedule ---
+#[table(name = map_ping_cleanup_schedule, scheduled(cleanup_expired_pings))]
+#[derive(Clone, Debug)]
+pub struct MapPingCleanupSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+// --- Row-Level Security Filters ---
+
+#[client_visibility_filter]
+const MAP_MARKER_OWN_ROWS: Filter = Filter::Sql(
+    "SELECT * FROM map_marker WHERE owner_id = :sender"
+);
+
+#[client_visibility_filter]
+const MAP_MARKER_SHARED_WITH_TEAM: Filter = Filter::Sql(
+    "SELECT map_marker.* FROM map_marker JOIN team_member ON map_marker.team_id = team_member.team_id WHERE team_member.player_id = :sender"
+);
+
+// --- Marker Reducers ---
+
+/// Adds a named, colored marker at the given world position, optionally shared with the sender's team.
+#[reducer]
+pub fn add_map_marker(ctx: &ReducerContext, label: String, color: String, pos_x: i32, pos_y: i32, share_with_team: bool) -> Result<(), String> {
+    let owner_id = ctx.sender;
+    let label = validate_label(&label)?;
+    validate_color(&color)?;
+
+    let markers = ctx.db.map_marker();
+    let marker_count = markers.owner_id().filter(owner_id)
+        .filter(|m| m.kind == MapMarkerKind::Marker)
+        .count();
+    if marker_count >= MAX_MAP_MARKERS_PER_PLAYER {
+        return Err(format!("You can't have more than {} map markers.", MAX_MAP_MARKERS_PER_PLAYER));
+    }
+    let team_id = if share_with_team { require_team_id(ctx, owner_id)? } else { UNSHARED_TEAM_ID };
+
+    let inserted = markers.try_insert(MapMarker {
+        id: 0, // Auto-incremented
+        owner_id,
+        team_id,
+        kind: MapMarkerKind::Marker,
+        label,
+        color,
+        pos_x,
+        pos_y,
+        created_at: ctx.timestamp,
+        expires_at: None,
+    }).map_err(|e| format!("Failed to add map marker: {}", e))?;
+    log::info!("Player {} added map marker {} at ({}, {})", owner_id, inserted.id, pos_x, pos_y);
+    Ok(())
+}
+
+/// Changes the label and color of one of the sender's markers.
+#[reducer]
+pub fn update_map_marker(ctx: &ReducerContext, marker_id: u64, label: String, color: String) -> Result<(), String> {
+    let mut marker = get_own_marker(ctx, marker_id)?;
+    marker.label = validate_label(&label)?;
+    validate_color(&color)?;
+    marker.color = color;
+    ctx.db.map_marker().id().update(marker);
+    Ok(())
+}
+
+/// Shares one of the sender's markers with their current team, or makes it private again.
+#[reducer]
+pub fn set_map_marker_shared(ctx: &ReducerContext, marker_id: u64, shared: bool) -> Result<(), String> {
+    let mut marker = get_own_marker(ctx, marker_id)?;
+    marker.team_id = if shared { require_team_id(ctx, ctx.sender)? } else { UNSHARED_TEAM_ID };
+    ctx.db.map_marker().id().update(marker);
+    Ok(())
+}
+
+/// Removes one of the sender's markers or pings.
+#[reducer]
+pub fn remove_map_marker(ctx: &ReducerContext, marker_id: u64) -> Result<(), String> {
+    get_own_marker(ctx, marker_id)?;
+    ctx.db.map_marker().id().delete(marker_id);
+    Ok(())
+}
+
+/// Places a short-lived ping, visible to the sender's team if they have one.
+/// The sender's oldest ping is replaced once they reach the active ping limit.
+#[reducer]
+pub fn ping_map(ctx: &ReducerContext, pos_x: i32, pos_y: i32) -> Result<(), String> {
+    let owner_id = ctx.sender;
+    let markers = ctx.db.map_marker();
+
+    let mut pings: Vec<MapMarker> = markers.owner_id().filter(owner_id)
+        .filter(|m| m.kind == MapMarkerKind::Ping)
+        .collect();
+    if pings.len() >= MAX_ACTIVE_PINGS_PER_PLAYER {
+        pings.sort_by_key(|m| m.created_at);
+        for old_ping in pings.iter().take(pings.len() + 1 - MAX_ACTIVE_PINGS_PER_PLAYER) {
+            markers.id().delete(old_ping.id);
+        }
+    }
+
+    let expires_at = ctx.timestamp + TimeDuration::from(Duration::from_secs(PING_LIFETIME_SECS));
+    markers.try_insert(MapMarker {
+        id: 0, // Auto-incremented
+        owner_id,
+        team_id: crate::team::get_player_team_id(ctx, owner_id).unwrap_or(UNSHARED_TEAM_ID),
+        kind: MapMarkerKind::Ping,
+        label: String::new(),
+        color: String::new(),
+        pos_x,
+        pos_y,
+        created_at: ctx.timestamp,
+        expires_at: Some(expires_at),
+    }).map_err(|e| format!("Failed to place ping: {}", e))?;
+    log::debug!("Player {} pinged ({}, {})", owner_id, pos_x, pos_y);
+    Ok(())
+}
+
+// --- Ping Cleanup ---
+
+/// Starts the expired ping cleanup schedule if it isn't already scheduled. Called from `init_module`.
+pub fn init_map_ping_cleanup_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.map_ping_cleanup_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting map ping cleanup schedule (every {}s).", PING_CLEANUP_INTERVAL_SECS);
+        let interval = Duration::from_secs(PING_CLEANUP_INTERVAL_SECS);
+        schedule_table.try_insert(MapPingCleanupSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert map ping cleanup schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: deletes pings whose lifetime has run out.
+#[reducer]
+pub fn cleanup_expired_pings(ctx: &ReducerContext, _schedule: MapPingCleanupSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("cleanup_expired_pings may only be called by the scheduler.".to_string());
+    }
+    let markers = ctx.db.map_marker();
+    let expired: Vec<u64> = markers.iter()
+        .filter(|m| m.expires_at.is_some_and(|t| t <= ctx.timestamp))
+        .map(|m| m.id)
+        .collect();
+    for marker_id in expired {
+        markers.id().delete(marker_id);
+    }
+    Ok(())
+}
+
+// --- Helpers ---
+
+/// Stops sharing the player's markers and pings with their team. Called when they leave or are kicked.
+pub(crate) fn unshare_player_markers(ctx: &ReducerContext, player_id: Identity) {
+    let markers = ctx.db.map_marker();
+    let shared: Vec<MapMarker> = markers.owner_id().filter(player_id)
+        .filter(|m| m.team_id != UNSHARED_TEAM_ID)
+        .collect();
+    for mut marker in shared {
+        marker.team_id = UNSHARED_TEAM_ID;
+        markers.id().update(marker);
+    }
+}
+
+fn get_own_marker(ctx: &ReducerContext, marker_id: u64) -> Result<MapMarker, String> {
+    let marker = ctx.db.map_marker().id().find(marker_id)
+        .ok_or_else(|| format!("Map marker {} not found", marker_id))?;
+    if marker.owner_id != ctx.sender {
+        return Err("You can only change your own map markers.".to_string());
+    }
+    Ok(marker)
+}
+
+fn require_team_id(ctx: &ReducerContext, player_id: Identity) -> Result<u64, String> {
+    crate::team::get_player_team_id(ctx, player_id)
+        .ok_or_else(|| "You must be in a team to share markers.".to_string())
+}
+
+fn validate_label(label: &str) -> Result<String, String> {
+    let label = label.trim();
+    if label.is_empty() {
+        return Err("Marker label cannot be empty.".to_string());
+    }
+    if label.chars().count() > MAX_MARKER_LABEL_LENGTH {
+        return Err(format!("Marker label too long (max {} characters).", MAX_MARKER_LABEL_LENGTH));
+    }
+    Ok(label.to_string())
+}
+
+fn validate_color(color: &str) -> Result<(), String> {
+    let valid = color.len() == 7
+        && color.starts_with('#')
+        && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+    if !valid {
+        return Err("Marker color must be a hex color like #ff8800.".to_string());
+    }
+    Ok(())
+}
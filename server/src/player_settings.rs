@@ -0,0 +1,64 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines per-player gameplay preferences. Players without a settings row    *
+ * use the defaults. Other modules read preferences through the helpers at    *
+ * the bottom (e.g. auto-pickup of dropped items on movement).                *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, log};
+
+use crate::player as PlayerTableTrait;
+use crate::player_settings::player_settings as PlayerSettingsTableTrait;
+
+/// --- Player Settings Data Structure ---
+/// Public so each client can read its own preferences back.
+#[spacetimedb::table(name = player_settings, public)]
+#[derive(Clone, Debug)]
+pub struct PlayerSettings {
+    #[primary_key]
+    pub player_id: Identity,
+    /// Collect nearby dropped items automatically while walking.
+    pub auto_pickup: bool,
+}
+
+impl PlayerSettings {
+    fn defaults(player_id: Identity) -> Self {
+        PlayerSettings {
+            player_id,
+            auto_pickup: false,
+        }
+    }
+}
+
+/// Turns automatic pickup of nearby dropped items on or off for the calling player.
+#[spacetimedb::reducer]
+pub fn set_auto_pickup(ctx: &ReducerContext, enabled: bool) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    if ctx.db.player().identity().find(sender_id).is_none() {
+        return Err("Player not found".to_string());
+    }
+
+    let settings_table = ctx.db.player_settings();
+    match settings_table.player_id().find(sender_id) {
+        Some(mut settings) => {
+            settings.auto_pickup = enabled;
+            settings_table.player_id().update(settings);
+        }
+        None => {
+            settings_table.insert(PlayerSettings { auto_pickup: enabled, ..PlayerSettings::defaults(sender_id) });
+        }
+    }
+    log::info!("Player {:?} set auto pickup to {}.", sender_id, enabled);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Returns the player's settings, or the defaults if they never changed any.
+pub(crate) fn get_player_settings(ctx: &ReducerContext, player_id: Identity) -> PlayerSettings {
+    ctx.db.player_settings().player_id().find(player_id)
+        .unwrap_or_else(|| PlayerSettings::defaults(player_id))
+}
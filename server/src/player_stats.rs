@@ -5,6 +5,7 @@ use log;
 use std::time::Duration;
 
 use crate::inventory_management::ItemContainer;
+use crate::models::DeathCause;
 
 // --- StatThresholdsConfig Table Definition (Formerly GameConfig) ---
 pub const DEFAULT_LOW_NEED_THRESHOLD: f32 = 20.0;
@@ -14,10 +15,34 @@ pub const DEFAULT_LOW_NEED_THRESHOLD: f32 = 20.0;
 pub struct StatThresholdsConfig {
     #[primary_key]
     pub id: u8, // Singleton table, ID will always be 0
-    pub low_need_threshold: f32,
+    pub low_need_threshold: f32, // Below this a need starts draining health
     // Add other global config values here in the future
 }
 
+/// --- Stat Threshold Tier ---
+/// One step of the low-need penalties. When a player's lowest need (hunger, thirst or
+/// warmth) is below `need_below`, their movement speed, stamina recovery and melee damage
+/// are scaled by the tier's multipliers. Only the most severe matching tier applies.
+/// Read by `update_player_position`, `process_player_stats` and combat; admins edit the
+/// tiers live with `set_stat_threshold_tier` and `remove_stat_threshold_tier`.
+#[table(name = stat_threshold_tier, public)]
+#[derive(Clone, Debug)]
+pub struct StatThresholdTier {
+    #[primary_key]
+    pub tier: u8,
+    pub need_below: f32,
+    pub speed_multiplier: f32,
+    pub stamina_regen_multiplier: f32,
+    pub damage_multiplier: f32,
+}
+
+/// Seeded tiers: (tier, need_below, speed, stamina regen, damage).
+const DEFAULT_STAT_THRESHOLD_TIERS: &[(u8, f32, f32, f32, f32)] = &[
+    (1, 50.0, 1.0, 0.85, 1.0),
+    (2, 25.0, 0.85, 0.6, 0.9),
+    (3, 10.0, 0.7, 0.35, 0.75),
+];
+
 pub fn init_stat_thresholds_config(ctx: &ReducerContext) -> Result<(), String> {
     let config_table = ctx.db.stat_thresholds_config();
     if config_table.iter().count() == 0 {
@@ -38,43 +63,133 @@ pub fn init_stat_thresholds_config(ctx: &ReducerContext) -> Result<(), String> {
     } else {
         log::debug!("StatThresholdsConfig table already initialized (in player_stats).");
     }
+
+    let tier_table = ctx.db.stat_threshold_tier();
+    if tier_table.iter().count() == 0 {
+        for &(tier, need_below, speed_multiplier, stamina_regen_multiplier, damage_multiplier) in DEFAULT_STAT_THRESHOLD_TIERS {
+            tier_table.try_insert(StatThresholdTier { tier, need_below, speed_multiplier, stamina_regen_multiplier, damage_multiplier })
+                .map_err(|e| format!("Failed to init stat threshold tier {}: {}", tier, e))?;
+        }
+        log::info!("Seeded {} stat threshold tiers.", DEFAULT_STAT_THRESHOLD_TIERS.len());
+    }
+    Ok(())
+}
+
+/// Admin-only: adds a stat threshold tier or replaces the one with the same number.
+#[spacetimedb::reducer]
+pub fn set_stat_threshold_tier(
+    ctx: &ReducerContext,
+    tier: u8,
+    need_below: f32,
+    speed_multiplier: f32,
+    stamina_regen_multiplier: f32,
+    damage_multiplier: f32,
+) -> Result<(), String> {
+    if !crate::admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can change the stat thresholds.".to_string());
+    }
+    if !need_below.is_finite() || !(0.0..=100.0).contains(&need_below) {
+        return Err(format!("Invalid threshold {}. It must be between 0 and 100.", need_below));
+    }
+    for multiplier in [speed_multiplier, stamina_regen_multiplier, damage_multiplier] {
+        if !multiplier.is_finite() || multiplier < 0.0 {
+            return Err(format!("Invalid multiplier {}. Multipliers must be finite and non-negative.", multiplier));
+        }
+    }
+
+    let tier_table = ctx.db.stat_threshold_tier();
+    let row = StatThresholdTier { tier, need_below, speed_multiplier, stamina_regen_multiplier, damage_multiplier };
+    if tier_table.tier().find(tier).is_some() {
+        tier_table.tier().update(row);
+    } else {
+        tier_table.insert(row);
+    }
+    log::info!("Admin {:?} set stat threshold tier {}: below {} -> speed x{}, stamina regen x{}, damage x{}.",
+        ctx.sender, tier, need_below, speed_multiplier, stamina_regen_multiplier, damage_multiplier);
     Ok(())
 }
+
+/// Admin-only: removes a stat threshold tier.
+#[spacetimedb::reducer]
+pub fn remove_stat_threshold_tier(ctx: &ReducerContext, tier: u8) -> Result<(), String> {
+    if !crate::admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can change the stat thresholds.".to_string());
+    }
+    if !ctx.db.stat_threshold_tier().tier().delete(tier) {
+        return Err(format!("Stat threshold tier {} not found.", tier));
+    }
+    log::info!("Admin {:?} removed stat threshold tier {}.", ctx.sender, tier);
+    Ok(())
+}
+
+/// The most severe tier the lowest of the given needs falls into, if any.
+pub(crate) fn need_penalty_tier(ctx: &ReducerContext, hunger: f32, thirst: f32, warmth: f32) -> Option<StatThresholdTier> {
+    let lowest_need = hunger.min(thirst).min(warmth);
+    ctx.db.stat_threshold_tier().iter()
+        .filter(|t| lowest_need < t.need_below)
+        .min_by(|a, b| a.need_below.total_cmp(&b.need_below))
+}
+
+/// Melee damage multiplier from the attacker's current need tier (1.0 when none applies).
+pub(crate) fn get_need_damage_multiplier(ctx: &ReducerContext, player_id: Identity) -> f32 {
+    ctx.db.player().identity().find(player_id)
+        .and_then(|p| need_penalty_tier(ctx, p.hunger, p.thirst, p.warmth))
+        .map_or(1.0, |t| t.damage_multiplier)
+}
 // --- End StatThresholdsConfig Table Definition ---
 
 // Define Constants locally
-const HUNGER_DRAIN_PER_SECOND: f32 = 100.0 / (30.0 * 60.0);
-const THIRST_DRAIN_PER_SECOND: f32 = 100.0 / (20.0 * 60.0);
+pub(crate) const HUNGER_DRAIN_PER_SECOND: f32 = 100.0 / (30.0 * 60.0);
+pub(crate) const THIRST_DRAIN_PER_SECOND: f32 = 100.0 / (20.0 * 60.0);
 // Make stat constants pub(crate) as well for consistency, although not strictly needed if only used here
 pub(crate) const STAMINA_DRAIN_PER_SECOND: f32 = 2.5;
 pub(crate) const STAMINA_RECOVERY_PER_SECOND: f32 = 1.0;
+pub(crate) const SWIM_STAMINA_DRAIN_PER_SECOND: f32 = 2.0;
+pub(crate) const DROWNING_DAMAGE_PER_SECOND: f32 = 5.0;
 pub(crate) const HEALTH_LOSS_PER_SEC_LOW_THIRST: f32 = 0.5;
 pub(crate) const HEALTH_LOSS_PER_SEC_LOW_HUNGER: f32 = 0.4;
 pub(crate) const HEALTH_LOSS_MULTIPLIER_AT_ZERO: f32 = 2.0;
 pub(crate) const HEALTH_RECOVERY_THRESHOLD: f32 = 51.0;
 pub(crate) const HEALTH_RECOVERY_PER_SEC: f32 = 1.0;
+pub(crate) const COMBAT_TAG_SECS: f32 = 10.0; // No passive health recovery for this long after taking damage
 pub(crate) const HEALTH_LOSS_PER_SEC_LOW_WARMTH: f32 = 0.6;
+pub(crate) const SNOW_WARMTH_DRAIN_PER_SECOND: f32 = 1.0; // On top of the time-of-day change, which can't go positive in snow
+pub(crate) const DESERT_THIRST_DRAIN_MULTIPLIER: f32 = 1.75;
+// Activity scales hunger and thirst drain (see PlayerActivity)
+pub(crate) const ACTIVITY_WINDOW_SECS: f32 = 3.0; // Moving or attacking within this long counts as active
+pub(crate) const SPRINTING_NEED_DRAIN_MULTIPLIER: f32 = 1.5;
+pub(crate) const ATTACKING_NEED_DRAIN_MULTIPLIER: f32 = 1.3;
+pub(crate) const IDLE_NEED_DRAIN_MULTIPLIER: f32 = 0.75;
+pub(crate) const CAMPFIRE_REST_NEED_DRAIN_MULTIPLIER: f32 = 0.5; // Standing still by a burning campfire
+pub(crate) const MOVING_STAMINA_RECOVERY_MULTIPLIER: f32 = 0.5; // Stamina recovers slower while walking...
+pub(crate) const CROUCHING_STAMINA_RECOVERY_MULTIPLIER: f32 = 0.8; // ...but crouch-walking barely slows it
 
 // Add the constants moved from lib.rs and make them pub(crate)
 pub(crate) const SPRINT_SPEED_MULTIPLIER: f32 = 1.5;
 pub(crate) const JUMP_COOLDOWN_MS: u64 = 500;
-pub(crate) const LOW_THIRST_SPEED_PENALTY: f32 = 0.75;
-pub(crate) const LOW_WARMTH_SPEED_PENALTY: f32 = 0.8;
+pub(crate) const DODGE_COOLDOWN_MS: u64 = 1000;
+pub(crate) const DODGE_INVULNERABILITY_MS: u64 = 300; // I-frames at the start of a roll
+pub(crate) const DODGE_STAMINA_COST: f32 = 20.0;
+pub(crate) const DODGE_DISTANCE: f32 = 120.0; // Pixels covered by one roll
+pub(crate) const SWIM_SPEED_MULTIPLIER: f32 = 0.5;
 
 // Import necessary items from the main lib module or other modules
 use crate::{
     Player, // Player struct
     world_state::{self, TimeOfDay, BASE_WARMTH_DRAIN_PER_SECOND, WARMTH_DRAIN_MULTIPLIER_DAWN_DUSK, WARMTH_DRAIN_MULTIPLIER_NIGHT, WARMTH_DRAIN_MULTIPLIER_MIDNIGHT},
-    campfire::{self, Campfire, WARMTH_RADIUS_SQUARED, WARMTH_PER_SECOND},
+    campfire::{self, Campfire, WARMTH_RADIUS_SQUARED},
     active_equipment, // For unequipping on death
     player_corpse::{self, PlayerCorpse, NUM_CORPSE_SLOTS, PlayerCorpseDespawnSchedule},
-    environment::calculate_chunk_index,
+    environment::{self, Biome, calculate_chunk_index},
+    standing_torch,
+    spatial_grid,
 };
 
 // Import table traits
 use crate::Player as PlayerTableTrait;
 use crate::world_state::world_state as WorldStateTableTrait;
 use crate::campfire::campfire as CampfireTableTrait;
+use crate::standing_torch::standing_torch as StandingTorchTableTrait;
 use crate::active_equipment::active_equipment as ActiveEquipmentTableTrait; // Needed for unequip on death
 use crate::player; // Added missing import for Player trait
 use crate::player_stats::PlayerStatSchedule as PlayerStatScheduleTableTrait; // Added Self trait import
@@ -96,6 +211,81 @@ pub struct PlayerStatSchedule {
     pub scheduled_at: ScheduleAt, // Added scheduled_at field
 }
 
+// --- Combat Tag Table ---
+/// When a player last took damage from an attack or burning. Passive health
+/// recovery is suppressed while the tag is fresh (see `GameConfig::combat_tag_secs`).
+#[spacetimedb::table(name = combat_tag)]
+#[derive(Clone, Debug)]
+pub struct CombatTag {
+    #[primary_key]
+    pub player_id: Identity,
+    pub last_damaged_at: Timestamp,
+}
+
+/// Marks the player as having just taken damage. Called from `combat::damage_player` and burn effects.
+pub(crate) fn tag_player_in_combat(ctx: &ReducerContext, player_id: Identity) {
+    let tags = ctx.db.combat_tag();
+    let tag = CombatTag { player_id, last_damaged_at: ctx.timestamp };
+    if tags.player_id().find(player_id).is_some() {
+        tags.player_id().update(tag);
+    } else {
+        tags.insert(tag);
+    }
+}
+
+// --- Player Activity Table ---
+/// When a player last moved and last swung or fired a weapon or tool. The stat tick reads
+/// it to drain hunger and thirst faster for active players and slower for resting ones.
+#[spacetimedb::table(name = player_activity)]
+#[derive(Clone, Debug)]
+pub struct PlayerActivity {
+    #[primary_key]
+    pub player_id: Identity,
+    pub last_moved_at: Option<Timestamp>,
+    pub last_attacked_at: Option<Timestamp>,
+}
+
+/// Records that the player just changed position. Called from `move_player`.
+pub(crate) fn record_player_movement(ctx: &ReducerContext, player_id: Identity) {
+    upsert_player_activity(ctx, player_id, |activity| activity.last_moved_at = Some(ctx.timestamp));
+}
+
+/// Records that the player just swung a tool or weapon, or fired a projectile.
+pub(crate) fn record_player_attack(ctx: &ReducerContext, player_id: Identity) {
+    upsert_player_activity(ctx, player_id, |activity| activity.last_attacked_at = Some(ctx.timestamp));
+}
+
+fn upsert_player_activity(ctx: &ReducerContext, player_id: Identity, update: impl FnOnce(&mut PlayerActivity)) {
+    let activities = ctx.db.player_activity();
+    match activities.player_id().find(player_id) {
+        Some(mut activity) => {
+            update(&mut activity);
+            activities.player_id().update(activity);
+        }
+        None => {
+            let mut activity = PlayerActivity { player_id, last_moved_at: None, last_attacked_at: None };
+            update(&mut activity);
+            activities.insert(activity);
+        }
+    }
+}
+
+/// Whether `at` is within the last ACTIVITY_WINDOW_SECS seconds.
+fn is_recent_activity(ctx: &ReducerContext, at: Option<Timestamp>) -> bool {
+    at.is_some_and(|at| {
+        let elapsed_micros = ctx.timestamp.to_micros_since_unix_epoch() - at.to_micros_since_unix_epoch();
+        (elapsed_micros as f32 / 1_000_000.0) < ACTIVITY_WINDOW_SECS
+    })
+}
+
+/// Whether the player took damage within the last `tag_secs` seconds.
+fn is_combat_tagged(ctx: &ReducerContext, player_id: Identity, tag_secs: f32) -> bool {
+    ctx.db.combat_tag().player_id().find(player_id).is_some_and(|tag| {
+        let elapsed_micros = ctx.timestamp.to_micros_since_unix_epoch() - tag.last_damaged_at.to_micros_since_unix_epoch();
+        (elapsed_micros as f32 / 1_000_000.0) < tag_secs
+    })
+}
+
 // --- Function to Initialize the Stat Update Schedule ---
 pub fn init_player_stat_schedule(ctx: &ReducerContext) -> Result<(), String> {
     let schedule_table = ctx.db.player_stat_schedule();
@@ -131,6 +321,7 @@ pub fn process_player_stats(ctx: &ReducerContext, _schedule: PlayerStatSchedule)
     let config = game_config_table.iter().next()
         .ok_or_else(|| "StatThresholdsConfig not found. Critical error during stat processing.".to_string())?;
     let low_need_threshold = config.low_need_threshold;
+    let game_config = crate::game_config::get_game_config(ctx);
 
     let world_state = world_states.iter().next()
         .ok_or_else(|| "WorldState not found during stat processing".to_string())?;
@@ -155,13 +346,15 @@ pub fn process_player_stats(ctx: &ReducerContext, _schedule: PlayerStatSchedule)
 
         let elapsed_seconds = (elapsed_micros as f64 / 1_000_000.0) as f32;
 
-        // --- Calculate Stat Changes ---
-        let new_hunger = (player.hunger - (elapsed_seconds * HUNGER_DRAIN_PER_SECOND)).max(0.0);
-        let new_thirst = (player.thirst - (elapsed_seconds * THIRST_DRAIN_PER_SECOND)).max(0.0);
+        let biome = environment::get_biome_at(ctx, player.position_x, player.position_y);
+
+        let activity = ctx.db.player_activity().player_id().find(player_id);
+        let moved_recently = is_recent_activity(ctx, activity.as_ref().and_then(|a| a.last_moved_at));
+        let attacked_recently = is_recent_activity(ctx, activity.as_ref().and_then(|a| a.last_attacked_at));
 
         // Calculate Warmth
         // NEW WARMTH LOGIC: Base warmth change per second based on TimeOfDay
-        let base_warmth_change_per_sec = match world_state.time_of_day {
+        let base_warmth_change_per_sec: f32 = match world_state.time_of_day {
             TimeOfDay::Midnight => -2.0,
             TimeOfDay::Night => -1.5,
             TimeOfDay::TwilightEvening => -0.5,
@@ -173,20 +366,56 @@ pub fn process_player_stats(ctx: &ReducerContext, _schedule: PlayerStatSchedule)
             TimeOfDay::Dawn => 0.0,
         };
 
-        let mut total_warmth_change_per_sec = base_warmth_change_per_sec;
+        // Snow never warms the player up by itself; only fires and warm armor help
+        let base_warmth_change_per_sec = if biome == Biome::Snow {
+            base_warmth_change_per_sec.min(0.0) - SNOW_WARMTH_DRAIN_PER_SECOND
+        } else {
+            base_warmth_change_per_sec
+        };
+
+        // Only the loss side is tunable or seasonal; daytime recovery stays as-is
+        let mut total_warmth_change_per_sec = if base_warmth_change_per_sec < 0.0 {
+            base_warmth_change_per_sec * game_config.warmth_loss_multiplier * world_state.season.warmth_drain_multiplier()
+        } else {
+            base_warmth_change_per_sec
+        };
 
-        for fire in campfires.iter() {
+        let nearby_campfires = spatial_grid::get_entities_in_radius(ctx, player.position_x, player.position_y, campfire::WARMTH_RADIUS)
+            .into_iter()
+            .filter_map(|entity| match entity {
+                spatial_grid::EntityType::Campfire(campfire_id) => campfires.id().find(campfire_id),
+                _ => None,
+            });
+        let mut near_burning_campfire = false;
+        for fire in nearby_campfires {
             // Only gain warmth from burning campfires
             if fire.is_burning {
                 let dx = player.position_x - fire.pos_x;
                 let dy = player.position_y - fire.pos_y;
                 if (dx * dx + dy * dy) < WARMTH_RADIUS_SQUARED {
-                    total_warmth_change_per_sec += WARMTH_PER_SECOND;
+                    near_burning_campfire = true;
+                    total_warmth_change_per_sec += game_config.campfire_warmth_per_second;
                     log::trace!("Player {:?} gaining warmth from campfire {}", player_id, fire.id);
                 }
             }
         }
 
+        // Standing torches give a smaller amount of warmth over a smaller radius
+        let torch_warmth_radius_sq = standing_torch::STANDING_TORCH_WARMTH_RADIUS * standing_torch::STANDING_TORCH_WARMTH_RADIUS;
+        let nearby_torches = spatial_grid::get_entities_in_radius(ctx, player.position_x, player.position_y, standing_torch::STANDING_TORCH_WARMTH_RADIUS)
+            .into_iter()
+            .filter_map(|entity| match entity {
+                spatial_grid::EntityType::StandingTorch(torch_id) => ctx.db.standing_torch().id().find(torch_id),
+                _ => None,
+            });
+        for torch in nearby_torches.filter(|t| t.is_lit) {
+            let dx = player.position_x - torch.pos_x;
+            let dy = player.position_y - torch.pos_y;
+            if (dx * dx + dy * dy) < torch_warmth_radius_sq {
+                total_warmth_change_per_sec += standing_torch::STANDING_TORCH_WARMTH_PER_SECOND;
+            }
+        }
+
         // <<< ADD WARMTH BONUS FROM ARMOR >>>
         let armor_warmth_bonus_per_interval = armor::calculate_total_warmth_bonus(ctx, player_id);
         // Assuming PLAYER_STAT_UPDATE_INTERVAL_SECS is the interval length in seconds for this stat processing.
@@ -205,6 +434,21 @@ pub fn process_player_stats(ctx: &ReducerContext, _schedule: PlayerStatSchedule)
         let new_warmth = (player.warmth + (total_warmth_change_per_sec * elapsed_seconds))
                          .max(0.0).min(100.0);
 
+        // --- Calculate Hunger and Thirst (scaled by activity) ---
+        let mut need_drain_multiplier: f32 = 1.0;
+        if player.is_sprinting && moved_recently {
+            need_drain_multiplier = need_drain_multiplier.max(SPRINTING_NEED_DRAIN_MULTIPLIER);
+        }
+        if attacked_recently {
+            need_drain_multiplier = need_drain_multiplier.max(ATTACKING_NEED_DRAIN_MULTIPLIER);
+        }
+        if !moved_recently && !attacked_recently {
+            need_drain_multiplier = if near_burning_campfire { CAMPFIRE_REST_NEED_DRAIN_MULTIPLIER } else { IDLE_NEED_DRAIN_MULTIPLIER };
+        }
+        let new_hunger = (player.hunger - (elapsed_seconds * game_config.hunger_drain_per_second * need_drain_multiplier)).max(0.0);
+        let thirst_drain_multiplier = if biome == Biome::Desert { DESERT_THIRST_DRAIN_MULTIPLIER } else { 1.0 };
+        let new_thirst = (player.thirst - (elapsed_seconds * game_config.thirst_drain_per_second * thirst_drain_multiplier * need_drain_multiplier)).max(0.0);
+
         // Calculate Stamina (Drain happens first if sprinting+moving, then recovery if not sprinting)
         let mut new_stamina = player.stamina;
         let mut new_sprinting_state = player.is_sprinting; // Start with current state
@@ -212,43 +456,66 @@ pub fn process_player_stats(ctx: &ReducerContext, _schedule: PlayerStatSchedule)
         // Check if player likely moved since last stat update
         let likely_moved = player.last_update > player.last_stat_update;
 
-        if new_sprinting_state && likely_moved {
-            // Apply drain if sprinting and likely moved
-            new_stamina = (new_stamina - (elapsed_seconds * STAMINA_DRAIN_PER_SECOND)).max(0.0);
+        let is_swimming = crate::world_tile::is_water_at(ctx, player.position_x, player.position_y);
+        // Getting too heavy ends a sprint (see encumbrance)
+        if new_sprinting_state && !crate::encumbrance::can_sprint(ctx, player_id) {
+            new_sprinting_state = false;
+        }
+
+        if is_swimming {
+            // Swimming always drains stamina; once it runs out the player starts drowning
+            new_stamina = (new_stamina - (elapsed_seconds * SWIM_STAMINA_DRAIN_PER_SECOND)).max(0.0);
+            new_sprinting_state = false;
+            if new_stamina <= 0.0 {
+                crate::active_effects::apply_drowning_damage(ctx, player_id, DROWNING_DAMAGE_PER_SECOND * elapsed_seconds);
+            }
+        } else if new_sprinting_state && likely_moved && crate::mount::ridden_mount(ctx, player_id).is_none() {
+            // Apply drain if sprinting and likely moved. Riders sprint on their mount's stamina instead (see mount.rs)
+            let drain_multiplier = crate::active_effects::get_stamina_drain_multiplier(ctx, player_id);
+            new_stamina = (new_stamina - (elapsed_seconds * game_config.stamina_drain_per_second * drain_multiplier)).max(0.0);
             if new_stamina <= 0.0 {
                 new_sprinting_state = false; // Force sprinting off if out of stamina
                 log::debug!("Player {:?} ran out of stamina (stat tick).", player_id);
             }
+        } else if crate::blocking::is_blocking(ctx, player_id) {
+            // A raised shield drains stamina instead of recovering it; running out breaks the block
+            new_stamina = crate::blocking::drain_block_stamina(ctx, player_id, new_stamina, elapsed_seconds);
         } else if !new_sprinting_state {
             // Apply recovery only if not sprinting (or just stopped sprinting this tick)
-            new_stamina = (new_stamina + (elapsed_seconds * STAMINA_RECOVERY_PER_SECOND)).min(100.0);
+            let mut recovery_multiplier = match (moved_recently, player.is_crouching) {
+                (false, _) => 1.0,
+                (true, true) => CROUCHING_STAMINA_RECOVERY_MULTIPLIER,
+                (true, false) => MOVING_STAMINA_RECOVERY_MULTIPLIER,
+            };
+            // Going hungry, thirsty or cold slows recovery (see StatThresholdTier)
+            if let Some(tier) = need_penalty_tier(ctx, new_hunger, new_thirst, new_warmth) {
+                recovery_multiplier *= tier.stamina_regen_multiplier;
+            }
+            new_stamina = (new_stamina + (elapsed_seconds * game_config.stamina_recovery_per_second * recovery_multiplier)).min(100.0);
         }
 
         // Calculate Health
-        let mut health_change_per_sec: f32 = 0.0;
-        if new_thirst <= 0.0 {
-            health_change_per_sec -= HEALTH_LOSS_PER_SEC_LOW_THIRST * HEALTH_LOSS_MULTIPLIER_AT_ZERO;
-        } else if new_thirst < low_need_threshold {
-            health_change_per_sec -= HEALTH_LOSS_PER_SEC_LOW_THIRST;
-        }
-        if new_hunger <= 0.0 {
-            health_change_per_sec -= HEALTH_LOSS_PER_SEC_LOW_HUNGER * HEALTH_LOSS_MULTIPLIER_AT_ZERO;
-        } else if new_hunger < low_need_threshold {
-            health_change_per_sec -= HEALTH_LOSS_PER_SEC_LOW_HUNGER;
-        }
-        if new_warmth <= 0.0 {
-            health_change_per_sec -= HEALTH_LOSS_PER_SEC_LOW_WARMTH * HEALTH_LOSS_MULTIPLIER_AT_ZERO;
-        } else if new_warmth < low_need_threshold {
-            health_change_per_sec -= HEALTH_LOSS_PER_SEC_LOW_WARMTH;
-        }
+        let need_loss_per_sec = |value: f32, loss_per_sec: f32| {
+            if value <= 0.0 {
+                loss_per_sec * HEALTH_LOSS_MULTIPLIER_AT_ZERO
+            } else if value < low_need_threshold {
+                loss_per_sec
+            } else {
+                0.0
+            }
+        };
+        let thirst_loss = need_loss_per_sec(new_thirst, HEALTH_LOSS_PER_SEC_LOW_THIRST);
+        let hunger_loss = need_loss_per_sec(new_hunger, HEALTH_LOSS_PER_SEC_LOW_HUNGER);
+        let warmth_loss = need_loss_per_sec(new_warmth, HEALTH_LOSS_PER_SEC_LOW_WARMTH);
+        let mut health_change_per_sec: f32 = -(thirst_loss + hunger_loss + warmth_loss);
 
-        // Health recovery only if needs are met and not taking damage
+        // Health recovery only if needs are met and not recently damaged
         if health_change_per_sec == 0.0 && // No damage from needs
-           player.health >= HEALTH_RECOVERY_THRESHOLD && // ADDED: Only regen if health is already high
-           new_hunger >= HEALTH_RECOVERY_THRESHOLD &&
-           new_thirst >= HEALTH_RECOVERY_THRESHOLD &&
-           new_warmth >= low_need_threshold { // Ensure warmth is also at a decent level (using low_need_threshold for now)
-            health_change_per_sec += HEALTH_RECOVERY_PER_SEC;
+           new_hunger >= game_config.health_regen_need_threshold &&
+           new_thirst >= game_config.health_regen_need_threshold &&
+           new_warmth >= low_need_threshold && // Ensure warmth is also at a decent level (using low_need_threshold for now)
+           !is_combat_tagged(ctx, player_id, game_config.combat_tag_secs) {
+            health_change_per_sec += game_config.health_regen_per_second;
         }
 
         let health_change = health_change_per_sec * elapsed_seconds;
@@ -261,9 +528,18 @@ pub fn process_player_stats(ctx: &ReducerContext, _schedule: PlayerStatSchedule)
                      player.username, player_id, final_health);
             player.is_dead = true;
             player.death_timestamp = Some(ctx.timestamp); // Set death timestamp
+            // Blame whichever need was draining health fastest
+            let death_cause = if warmth_loss > thirst_loss && warmth_loss > hunger_loss {
+                DeathCause::Cold
+            } else if thirst_loss > hunger_loss {
+                DeathCause::Dehydration
+            } else {
+                DeathCause::Starvation
+            };
+            crate::kill_feed::record_death(ctx, player_id, death_cause, "");
 
             // --- <<< CHANGED: Call refactored corpse creation function >>> ---
-            match player_corpse::create_player_corpse(ctx, player_id, player.position_x, player.position_y, &player.username) {
+            match player_corpse::create_player_corpse(ctx, player_id, player.position_x, player.position_y, &player.username, death_cause, None) {
                 Ok(_) => {
                     log::info!("Successfully created corpse via stats decay for player {:?}", player_id);
                     // If player was holding an item, it should be unequipped (returned to inventory or dropped)
@@ -0,0 +1,497 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines ranged projectiles (arrows). `fire_projectile` consumes ammo from  *
+ * the hotbar and spawns a Projectile; a scheduled tick advances every        *
+ * projectile, checks collisions via the spatial grid and applies damage      *
+ * through the existing combat.rs damage functions.                           *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+use rand::SeedableRng;
+
+use crate::{PLAYER_RADIUS, WORLD_WIDTH_PX, WORLD_HEIGHT_PX};
+use crate::player as PlayerTableTrait;
+use crate::items::{
+    ItemDefinition, ItemCategory,
+    inventory_item as InventoryItemTableTrait,
+    item_definition as ItemDefinitionTableTrait,
+};
+use crate::models::{DamageType, ItemLocation, ResourceRichness, TargetType};
+use crate::active_equipment::active_equipment as ActiveEquipmentTableTrait;
+use crate::combat;
+use crate::environment::{calculate_chunk_index, sync_chunk_index};
+use crate::skills::{self, SkillType};
+use crate::spatial_grid::{EntityType, get_entities_in_range};
+use crate::tree::{tree as TreeTableTrait, TREE_TRUNK_RADIUS, TREE_COLLISION_Y_OFFSET};
+use crate::stone::{stone as StoneTableTrait, STONE_RADIUS, STONE_COLLISION_Y_OFFSET};
+use crate::campfire::{campfire as CampfireTableTrait, CAMPFIRE_COLLISION_RADIUS, CAMPFIRE_COLLISION_Y_OFFSET};
+use crate::wooden_storage_box::{wooden_storage_box as WoodenStorageBoxTableTrait, BOX_COLLISION_RADIUS, BOX_COLLISION_Y_OFFSET};
+use crate::furnace::{furnace as FurnaceTableTrait, FURNACE_COLLISION_RADIUS, FURNACE_COLLISION_Y_OFFSET};
+use crate::building::{building_piece as BuildingPieceTableTrait, BUILDING_COLLISION_Y_OFFSET};
+use crate::animals::{wild_animal as WildAnimalTableTrait, ANIMAL_COLLISION_RADIUS};
+use crate::large_storage_chest::{large_storage_chest as LargeStorageChestTableTrait, LARGE_CHEST_COLLISION_RADIUS, LARGE_CHEST_COLLISION_Y_OFFSET};
+use crate::sleeping_player::sleeping_player as SleepingPlayerTableTrait;
+use crate::loot_crate::{loot_crate as LootCrateTableTrait, LOOT_CRATE_COLLISION_RADIUS, LOOT_CRATE_COLLISION_Y_OFFSET};
+use crate::workbench::{workbench as WorkbenchTableTrait, WORKBENCH_COLLISION_RADIUS, WORKBENCH_COLLISION_Y_OFFSET};
+use crate::sign::{sign as SignTableTrait, SIGN_COLLISION_RADIUS, SIGN_COLLISION_Y_OFFSET};
+use crate::wiring::{wiring_switch as WiringSwitchTableTrait, SWITCH_COLLISION_RADIUS, SWITCH_COLLISION_Y_OFFSET};
+use crate::mount::mount as MountTableTrait;
+use crate::recycler::{recycler as RecyclerTableTrait, RECYCLER_COLLISION_RADIUS, RECYCLER_COLLISION_Y_OFFSET};
+use crate::flame_turret::{flame_turret as FlameTurretTableTrait, FLAME_TURRET_COLLISION_RADIUS, FLAME_TURRET_COLLISION_Y_OFFSET};
+use crate::PlayerLastAttackTimestamp;
+use crate::player_last_attack_timestamp as PlayerLastAttackTimestampTableTrait;
+use crate::projectile::projectile as ProjectileTableTrait;
+use crate::projectile::projectile_update_schedule as ProjectileUpdateScheduleTableTrait;
+
+// --- Constants ---
+pub(crate) const PROJECTILE_TICK_INTERVAL_MS: u64 = 50;
+pub(crate) const PROJECTILE_SPEED_PX_PER_SEC: f32 = 800.0;
+pub(crate) const PROJECTILE_MAX_RANGE_PX: f32 = 600.0;
+const PROJECTILE_HIT_RADIUS: f32 = 4.0;
+/// Weapons that fire ammunition instead of swinging.
+pub(crate) const RANGED_WEAPON_NAMES: &[&str] = &["Hunting Bow"];
+
+/// --- Projectile Data Structure ---
+/// An arrow in flight. Position is advanced every tick; the row is deleted on impact
+/// or once it has flown `max_range` pixels.
+#[spacetimedb::table(name = projectile, public)]
+#[derive(Clone, Debug)]
+pub struct Projectile {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub owner_id: Identity,
+    pub weapon_item_def_id: u64,
+    pub ammo_item_def_id: u64,
+    pub fired_at: Timestamp,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    #[index(btree)]
+    pub chunk_index: u32, // Updated as the projectile flies
+    pub velocity_x: f32,
+    pub velocity_y: f32,
+    pub distance_traveled: f32,
+    pub max_range: f32,
+}
+
+// --- Projectile Update Schedule Table ---
+#[spacetimedb::table(name = projectile_update_schedule, scheduled(update_projectiles))]
+#[derive(Clone)]
+pub struct ProjectileUpdateSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/// What a projectile ran into during a tick.
+enum ProjectileImpact {
+    Player(Identity),
+    Structure(combat::Target),
+    Obstacle,
+}
+
+// --- Function to Initialize the Projectile Schedule ---
+pub fn init_projectile_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.projectile_update_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting projectile update schedule (every {}ms).", PROJECTILE_TICK_INTERVAL_MS);
+        let interval = Duration::from_millis(PROJECTILE_TICK_INTERVAL_MS);
+        schedule_table.try_insert(ProjectileUpdateSchedule {
+            id: 0,
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert projectile schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                                 REDUCERS                                   *
+ ******************************************************************************/
+
+/// Fires the equipped ranged weapon, consuming one unit of ammunition from the hotbar.
+///
+/// `aim_x`/`aim_y` is an aim vector relative to the player. When it is zero the
+/// projectile is fired in the player's facing direction.
+#[spacetimedb::reducer]
+pub fn fire_projectile(ctx: &ReducerContext, aim_x: f32, aim_y: f32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    crate::moderation::ensure_not_sanctioned(ctx, ctx.sender)?;
+    let sender_id = ctx.sender;
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Player not found".to_string())?;
+    if player.is_dead {
+        return Err("Cannot fire while dead.".to_string());
+    }
+
+    // --- 1. Validate the equipped weapon ---
+    let equipment = ctx.db.active_equipment().player_identity().find(sender_id)
+        .ok_or_else(|| "No active equipment record found.".to_string())?;
+    let weapon_instance_id = equipment.equipped_item_instance_id
+        .ok_or_else(|| "No weapon equipped.".to_string())?;
+    let weapon_def = equipment.equipped_item_def_id
+        .and_then(|def_id| ctx.db.item_definition().id().find(def_id))
+        .ok_or_else(|| "Equipped item definition not found".to_string())?;
+    if !RANGED_WEAPON_NAMES.contains(&weapon_def.name.as_str()) {
+        return Err(format!("{} cannot fire projectiles.", weapon_def.name));
+    }
+    if let Some(weapon_item) = ctx.db.inventory_item().instance_id().find(weapon_instance_id) {
+        if combat::is_item_broken(&weapon_item, &weapon_def) {
+            return Err(format!("Your {} is broken and needs to be repaired.", weapon_def.name));
+        }
+    }
+
+    // --- 2. Attack speed check (shared with melee attacks) ---
+    let last_attacks = ctx.db.player_last_attack_timestamp();
+    if let Some(interval_secs) = weapon_def.attack_interval_secs.filter(|secs| *secs > 0.0) {
+        if let Some(last_attack) = last_attacks.player_id().find(sender_id) {
+            let elapsed_micros = ctx.timestamp.to_micros_since_unix_epoch()
+                .saturating_sub(last_attack.last_attack_timestamp.to_micros_since_unix_epoch());
+            if elapsed_micros < (interval_secs * 1_000_000.0) as i64 {
+                return Err("Attacking too quickly.".to_string());
+            }
+        }
+    }
+
+    // --- 3. Resolve the firing direction ---
+    let (dir_x, dir_y) = {
+        let length = (aim_x * aim_x + aim_y * aim_y).sqrt();
+        if length > f32::EPSILON {
+            (aim_x / length, aim_y / length)
+        } else {
+            combat::get_player_forward_vector(&player.direction)
+        }
+    };
+
+    // --- 4. Consume one unit of ammunition from the hotbar ---
+    let ammo_def = consume_ammo_from_hotbar(ctx, sender_id)?;
+
+    // --- 5. Spawn the projectile just outside the shooter's collision radius ---
+    let spawn_x = player.position_x + dir_x * (PLAYER_RADIUS + PROJECTILE_HIT_RADIUS);
+    let spawn_y = player.position_y + dir_y * (PLAYER_RADIUS + PROJECTILE_HIT_RADIUS);
+    ctx.db.projectile().insert(Projectile {
+        id: 0, // Auto-incremented
+        owner_id: sender_id,
+        weapon_item_def_id: weapon_def.id,
+        ammo_item_def_id: ammo_def.id,
+        fired_at: ctx.timestamp,
+        pos_x: spawn_x,
+        pos_y: spawn_y,
+        chunk_index: calculate_chunk_index(spawn_x, spawn_y),
+        velocity_x: dir_x * PROJECTILE_SPEED_PX_PER_SEC,
+        velocity_y: dir_y * PROJECTILE_SPEED_PX_PER_SEC,
+        distance_traveled: 0.0,
+        max_range: PROJECTILE_MAX_RANGE_PX,
+    });
+
+    let attack_record = PlayerLastAttackTimestamp { player_id: sender_id, last_attack_timestamp: ctx.timestamp };
+    if last_attacks.player_id().find(sender_id).is_some() {
+        last_attacks.player_id().update(attack_record);
+    } else {
+        last_attacks.insert(attack_record);
+    }
+    crate::player_stats::record_player_attack(ctx, sender_id);
+    crate::active_effects::cancel_spawn_protection(ctx, sender_id);
+
+    if combat::apply_durability_loss(ctx, weapon_instance_id, &weapon_def) {
+        log::info!("Player {:?}'s {} broke.", sender_id, weapon_def.name);
+    }
+
+    log::info!("Player {:?} fired {} from {} towards ({:.2}, {:.2}).", sender_id, ammo_def.name, weapon_def.name, dir_x, dir_y);
+    Ok(())
+}
+
+/// Scheduled reducer: advances all projectiles and resolves impacts.
+#[spacetimedb::reducer]
+pub fn update_projectiles(ctx: &ReducerContext, _schedule: ProjectileUpdateSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("update_projectiles may only be called by the scheduler.".to_string());
+    }
+
+    let projectiles: Vec<Projectile> = ctx.db.projectile().iter().collect();
+    if projectiles.is_empty() {
+        return Ok(());
+    }
+
+    let mut rng = rand::rngs::StdRng::from_rng(ctx.rng()).map_err(|e| format!("Failed to create RNG: {}", e))?;
+    let delta_secs = PROJECTILE_TICK_INTERVAL_MS as f32 / 1000.0;
+
+    for mut projectile in projectiles {
+        let step_x = projectile.velocity_x * delta_secs;
+        let step_y = projectile.velocity_y * delta_secs;
+        let step_len = (step_x * step_x + step_y * step_y).sqrt();
+        let next_x = projectile.pos_x + step_x;
+        let next_y = projectile.pos_y + step_y;
+
+        if let Some(impact) = find_impact(ctx, &projectile, next_x, next_y) {
+            resolve_impact(ctx, &projectile, impact, next_x, next_y, &mut rng);
+            ctx.db.projectile().id().delete(projectile.id);
+            continue;
+        }
+
+        projectile.pos_x = next_x;
+        projectile.pos_y = next_y;
+        sync_chunk_index(&mut projectile.chunk_index, next_x, next_y);
+        projectile.distance_traveled += step_len;
+
+        let out_of_world = next_x < 0.0 || next_y < 0.0 || next_x >= WORLD_WIDTH_PX || next_y >= WORLD_HEIGHT_PX;
+        if projectile.distance_traveled >= projectile.max_range || out_of_world {
+            // Spent arrows land on the ground where they stopped
+            if !out_of_world {
+                drop_spent_ammo(ctx, &projectile, next_x, next_y);
+            }
+            ctx.db.projectile().id().delete(projectile.id);
+        } else {
+            ctx.db.projectile().id().update(projectile);
+        }
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Consumes one ammunition item from the lowest hotbar slot holding ammo and returns its definition.
+fn consume_ammo_from_hotbar(ctx: &ReducerContext, player_id: Identity) -> Result<ItemDefinition, String> {
+    let inventory_items = ctx.db.inventory_item();
+    let item_defs = ctx.db.item_definition();
+
+    let mut ammo_in_hotbar: Vec<(u8, crate::items::InventoryItem, ItemDefinition)> = inventory_items.iter()
+        .filter_map(|item| match &item.location {
+            ItemLocation::Hotbar(data) if data.owner_id == player_id => Some((data.slot_index, item.clone())),
+            _ => None,
+        })
+        .filter_map(|(slot, item)| {
+            item_defs.id().find(item.item_def_id)
+                .filter(|def| def.category == ItemCategory::Ammunition)
+                .map(|def| (slot, item, def))
+        })
+        .collect();
+    ammo_in_hotbar.sort_by_key(|(slot, _, _)| *slot);
+
+    let (_, mut ammo_item, ammo_def) = ammo_in_hotbar.into_iter().next()
+        .ok_or_else(|| "No arrows in your hotbar.".to_string())?;
+
+    if ammo_item.quantity > 1 {
+        ammo_item.quantity -= 1;
+        inventory_items.instance_id().update(ammo_item);
+    } else {
+        inventory_items.instance_id().delete(ammo_item.instance_id);
+    }
+    Ok(ammo_def)
+}
+
+/// Returns the first thing the projectile hits at its next position, if any.
+fn find_impact(ctx: &ReducerContext, projectile: &Projectile, x: f32, y: f32) -> Option<ProjectileImpact> {
+    let within = |cx: f32, cy: f32, radius: f32| {
+        let dx = x - cx;
+        let dy = y - cy;
+        let min_dist = radius + PROJECTILE_HIT_RADIUS;
+        dx * dx + dy * dy < min_dist * min_dist
+    };
+    let structure = |target_type: TargetType, id: combat::TargetId| {
+        ProjectileImpact::Structure(combat::Target { target_type, id, distance_sq: 0.0 })
+    };
+
+    for entity in get_entities_in_range(ctx, x, y) {
+        match entity {
+            EntityType::Player(player_id) => {
+                if player_id == projectile.owner_id { continue; }
+                if let Some(p) = ctx.db.player().identity().find(player_id) {
+                    if !p.is_dead && within(p.position_x, p.position_y, PLAYER_RADIUS) {
+                        if p.is_online {
+                            return Some(ProjectileImpact::Player(player_id));
+                        }
+                        if let Some(sleeper) = ctx.db.sleeping_player().player_identity().find(player_id) {
+                            return Some(structure(TargetType::SleepingPlayer, combat::TargetId::SleepingPlayer(sleeper.id)));
+                        }
+                    }
+                }
+            },
+            EntityType::Tree(tree_id) => {
+                if let Some(tree) = ctx.db.tree().id().find(tree_id) {
+                    if tree.health > 0 && within(tree.pos_x, tree.pos_y - TREE_COLLISION_Y_OFFSET, TREE_TRUNK_RADIUS) {
+                        return Some(ProjectileImpact::Obstacle);
+                    }
+                }
+            },
+            EntityType::Stone(stone_id) => {
+                if let Some(stone) = ctx.db.stone().id().find(stone_id) {
+                    if stone.health > 0 && within(stone.pos_x, stone.pos_y - STONE_COLLISION_Y_OFFSET, STONE_RADIUS) {
+                        return Some(ProjectileImpact::Obstacle);
+                    }
+                }
+            },
+            EntityType::Campfire(campfire_id) => {
+                if let Some(campfire) = ctx.db.campfire().id().find(campfire_id) {
+                    if !campfire.is_destroyed && within(campfire.pos_x, campfire.pos_y - CAMPFIRE_COLLISION_Y_OFFSET, CAMPFIRE_COLLISION_RADIUS) {
+                        return Some(structure(TargetType::Campfire, combat::TargetId::Campfire(campfire_id)));
+                    }
+                }
+            },
+            EntityType::WoodenStorageBox(box_id) => {
+                if let Some(storage_box) = ctx.db.wooden_storage_box().id().find(box_id) {
+                    if !storage_box.is_destroyed && within(storage_box.pos_x, storage_box.pos_y - BOX_COLLISION_Y_OFFSET, BOX_COLLISION_RADIUS) {
+                        return Some(structure(TargetType::WoodenStorageBox, combat::TargetId::WoodenStorageBox(box_id)));
+                    }
+                }
+            },
+            EntityType::Furnace(furnace_id) => {
+                if let Some(furnace) = ctx.db.furnace().id().find(furnace_id) {
+                    if !furnace.is_destroyed && within(furnace.pos_x, furnace.pos_y - FURNACE_COLLISION_Y_OFFSET, FURNACE_COLLISION_RADIUS) {
+                        return Some(structure(TargetType::Furnace, combat::TargetId::Furnace(furnace_id)));
+                    }
+                }
+            },
+            EntityType::BuildingPiece(piece_id) => {
+                if let Some(piece) = ctx.db.building_piece().id().find(piece_id) {
+                    if piece.is_solid() && within(piece.pos_x, piece.pos_y - BUILDING_COLLISION_Y_OFFSET, piece.collision_radius()) {
+                        return Some(structure(TargetType::BuildingPiece, combat::TargetId::BuildingPiece(piece_id)));
+                    }
+                }
+            },
+            EntityType::LargeStorageChest(chest_id) => {
+                if let Some(chest) = ctx.db.large_storage_chest().id().find(chest_id) {
+                    if !chest.is_destroyed && within(chest.pos_x, chest.pos_y - LARGE_CHEST_COLLISION_Y_OFFSET, LARGE_CHEST_COLLISION_RADIUS) {
+                        return Some(structure(TargetType::LargeStorageChest, combat::TargetId::LargeStorageChest(chest_id)));
+                    }
+                }
+            },
+            EntityType::LootCrate(crate_id) => {
+                if let Some(loot_crate) = ctx.db.loot_crate().id().find(crate_id) {
+                    if loot_crate.health > 0.0 && within(loot_crate.pos_x, loot_crate.pos_y - LOOT_CRATE_COLLISION_Y_OFFSET, LOOT_CRATE_COLLISION_RADIUS) {
+                        return Some(structure(TargetType::LootCrate, combat::TargetId::LootCrate(crate_id)));
+                    }
+                }
+            },
+            EntityType::Workbench(workbench_id) => {
+                if let Some(workbench) = ctx.db.workbench().id().find(workbench_id) {
+                    if !workbench.is_destroyed && within(workbench.pos_x, workbench.pos_y - WORKBENCH_COLLISION_Y_OFFSET, WORKBENCH_COLLISION_RADIUS) {
+                        return Some(structure(TargetType::Workbench, combat::TargetId::Workbench(workbench_id)));
+                    }
+                }
+            },
+            EntityType::Sign(sign_id) => {
+                if let Some(sign) = ctx.db.sign().id().find(sign_id) {
+                    if !sign.is_destroyed && within(sign.pos_x, sign.pos_y - SIGN_COLLISION_Y_OFFSET, SIGN_COLLISION_RADIUS) {
+                        return Some(structure(TargetType::Sign, combat::TargetId::Sign(sign_id)));
+                    }
+                }
+            },
+            EntityType::Switch(switch_id) => {
+                if let Some(switch) = ctx.db.wiring_switch().id().find(switch_id) {
+                    if !switch.is_destroyed && within(switch.pos_x, switch.pos_y - SWITCH_COLLISION_Y_OFFSET, SWITCH_COLLISION_RADIUS) {
+                        return Some(structure(TargetType::Switch, combat::TargetId::Switch(switch_id)));
+                    }
+                }
+            },
+            EntityType::Recycler(recycler_id) => {
+                if let Some(recycler) = ctx.db.recycler().id().find(recycler_id) {
+                    if !recycler.is_destroyed && within(recycler.pos_x, recycler.pos_y - RECYCLER_COLLISION_Y_OFFSET, RECYCLER_COLLISION_RADIUS) {
+                        return Some(structure(TargetType::Recycler, combat::TargetId::Recycler(recycler_id)));
+                    }
+                }
+            },
+            EntityType::FlameTurret(turret_id) => {
+                if let Some(turret) = ctx.db.flame_turret().id().find(turret_id) {
+                    if !turret.is_destroyed && within(turret.pos_x, turret.pos_y - FLAME_TURRET_COLLISION_Y_OFFSET, FLAME_TURRET_COLLISION_RADIUS) {
+                        return Some(structure(TargetType::FlameTurret, combat::TargetId::FlameTurret(turret_id)));
+                    }
+                }
+            },
+            EntityType::Animal(animal_id) => {
+                if let Some(animal) = ctx.db.wild_animal().id().find(animal_id) {
+                    if within(animal.pos_x, animal.pos_y, ANIMAL_COLLISION_RADIUS) {
+                        return Some(structure(TargetType::Animal, combat::TargetId::Animal(animal_id)));
+                    }
+                }
+            },
+            EntityType::Mount(mount_id) => {
+                if let Some(mount) = ctx.db.mount().id().find(mount_id) {
+                    if mount.rider_id != Some(projectile.owner_id) && within(mount.pos_x, mount.pos_y, mount.mount_type.stats().collision_radius) {
+                        return Some(structure(TargetType::Mount, combat::TargetId::Mount(mount_id)));
+                    }
+                }
+            },
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Applies the effect of an impact: damages players/structures through combat.rs,
+/// or drops the arrow when it hits an obstacle like a tree or stone.
+fn resolve_impact(ctx: &ReducerContext, projectile: &Projectile, impact: ProjectileImpact, x: f32, y: f32, rng: &mut impl rand::Rng) {
+    let item_defs = ctx.db.item_definition();
+    let (Some(weapon_def), Some(ammo_def)) = (
+        item_defs.id().find(projectile.weapon_item_def_id),
+        item_defs.id().find(projectile.ammo_item_def_id),
+    ) else {
+        log::error!("[Projectile] Definitions missing for projectile {}. Discarding.", projectile.id);
+        return;
+    };
+
+    // The bow sets the damage, the arrow type decides bleed
+    let mut hit_def = weapon_def.clone();
+    hit_def.bleed_damage_per_tick = ammo_def.bleed_damage_per_tick;
+    hit_def.bleed_duration_seconds = ammo_def.bleed_duration_seconds;
+    hit_def.bleed_tick_interval_seconds = ammo_def.bleed_tick_interval_seconds;
+    hit_def.slow_percent = ammo_def.slow_percent;
+    hit_def.slow_duration_seconds = ammo_def.slow_duration_seconds;
+
+    let result = match impact {
+        ProjectileImpact::Player(target_id) => {
+            let (damage, _, _) = combat::calculate_damage_and_yield(ctx, &hit_def, TargetType::Player, 0.0, ResourceRichness::Normal, rng);
+            let damage = damage * skills::get_combat_damage_multiplier(ctx, projectile.owner_id);
+            combat::damage_player(ctx, projectile.owner_id, target_id, damage, &hit_def, DamageType::Projectile, ctx.timestamp)
+        },
+        ProjectileImpact::Structure(target) => {
+            let (mut damage, _, _) = combat::calculate_damage_and_yield(ctx, &hit_def, target.target_type, 0.0, ResourceRichness::Normal, rng);
+            if matches!(target.target_type, TargetType::Animal | TargetType::SleepingPlayer) {
+                damage *= skills::get_combat_damage_multiplier(ctx, projectile.owner_id);
+            }
+            match target.id {
+                combat::TargetId::Campfire(id) => combat::damage_campfire(ctx, projectile.owner_id, id, damage, ctx.timestamp, rng),
+                combat::TargetId::WoodenStorageBox(id) => combat::damage_wooden_storage_box(ctx, projectile.owner_id, id, damage, ctx.timestamp, rng),
+                combat::TargetId::Furnace(id) => combat::damage_furnace(ctx, projectile.owner_id, id, damage, ctx.timestamp, rng),
+                combat::TargetId::BuildingPiece(id) => combat::damage_building_piece(ctx, projectile.owner_id, id, damage, ctx.timestamp),
+                combat::TargetId::LargeStorageChest(id) => combat::damage_large_storage_chest(ctx, projectile.owner_id, id, damage, ctx.timestamp, rng),
+                combat::TargetId::Animal(id) => combat::damage_animal(ctx, projectile.owner_id, id, damage, ctx.timestamp, rng),
+                combat::TargetId::SleepingPlayer(id) => combat::damage_sleeping_player(ctx, projectile.owner_id, id, damage, DamageType::Projectile, ctx.timestamp),
+                combat::TargetId::LootCrate(id) => combat::damage_loot_crate(ctx, projectile.owner_id, id, damage, ctx.timestamp, rng),
+                combat::TargetId::Workbench(id) => combat::damage_workbench(ctx, projectile.owner_id, id, damage, ctx.timestamp),
+                combat::TargetId::Sign(id) => combat::damage_sign(ctx, projectile.owner_id, id, damage, ctx.timestamp),
+                combat::TargetId::Switch(id) => combat::damage_switch(ctx, projectile.owner_id, id, damage, ctx.timestamp),
+                combat::TargetId::Recycler(id) => combat::damage_recycler(ctx, projectile.owner_id, id, damage, ctx.timestamp, rng),
+                combat::TargetId::FlameTurret(id) => combat::damage_flame_turret(ctx, projectile.owner_id, id, damage, ctx.timestamp, rng),
+                combat::TargetId::Mount(id) => combat::damage_mount(ctx, projectile.owner_id, id, damage, ctx.timestamp),
+                _ => Err("Unsupported projectile target".to_string()),
+            }
+        },
+        ProjectileImpact::Obstacle => {
+            drop_spent_ammo(ctx, projectile, x, y);
+            return;
+        },
+    };
+
+    match result {
+        Ok(attack_result) => {
+            let is_combat_target = matches!(attack_result.target_type, Some(TargetType::Player) | Some(TargetType::Animal));
+            if attack_result.hit && is_combat_target {
+                skills::award_xp(ctx, projectile.owner_id, SkillType::Combat, skills::XP_PER_COMBAT_HIT);
+            }
+        }
+        Err(e) => log::error!("[Projectile] Failed to apply damage from projectile {}: {}", projectile.id, e),
+    }
+}
+
+/// Drops the projectile's ammunition as a world item so it can be picked back up.
+fn drop_spent_ammo(ctx: &ReducerContext, projectile: &Projectile, x: f32, y: f32) {
+    if let Err(e) = crate::dropped_item::create_dropped_item_entity(ctx, projectile.ammo_item_def_id, 1, x, y) {
+        log::error!("[Projectile] Failed to drop spent ammo for projectile {}: {}", projectile.id, e);
+    }
+}
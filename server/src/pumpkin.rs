@@ -17,6 +17,7 @@ use crate::collectible_resources::{
     BASE_RESOURCE_RADIUS, PLAYER_RESOURCE_INTERACTION_DISTANCE_SQUARED,
     validate_player_resource_interaction,
     collect_resource_and_schedule_respawn,
+    scale_respawn_secs,
     RespawnableResource
 };
 
@@ -94,6 +95,7 @@ impl RespawnableResource for Pumpkin {
 /// inventory and the pumpkin resource is scheduled for respawn.
 #[spacetimedb::reducer]
 pub fn interact_with_pumpkin(ctx: &ReducerContext, pumpkin_id: u64) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
     let player_id = ctx.sender;
     
     // Find the pumpkin
@@ -108,6 +110,9 @@ pub fn interact_with_pumpkin(ctx: &ReducerContext, pumpkin_id: u64) -> Result<()
     // Validate player can interact with this pumpkin (distance check)
     let _player = validate_player_resource_interaction(ctx, player_id, pumpkin.pos_x, pumpkin.pos_y)?;
 
+    let growth_multiplier = crate::game_config::get_game_config(ctx).day_plant_growth_multiplier
+        * crate::world_state::current_season(ctx).plant_growth_multiplier();
+
     // Add to inventory and schedule respawn
     collect_resource_and_schedule_respawn(
         ctx,
@@ -132,8 +137,8 @@ pub fn interact_with_pumpkin(ctx: &ReducerContext, pumpkin_id: u64) -> Result<()
                 Err(format!("Pumpkin {} disappeared before respawn scheduling.", pumpkin.id))
             }
         },
-        MIN_PUMPKIN_RESPAWN_TIME_SECS,  // min_respawn_secs
-        MAX_PUMPKIN_RESPAWN_TIME_SECS   // max_respawn_secs
+        scale_respawn_secs(MIN_PUMPKIN_RESPAWN_TIME_SECS, growth_multiplier),  // min_respawn_secs
+        scale_respawn_secs(MAX_PUMPKIN_RESPAWN_TIME_SECS, growth_multiplier)   // max_respawn_secs
     )?;
 
     // Log statement is now handled within collect_resource_and_schedule_respawn
@@ -0,0 +1,159 @@
+/******************************************************************************
+ *                                                                            *
+ * Tutorial quests. A short list of seeded quest definitions (gather wood,    *
+ * craft a campfire, cook meat) walks new players through the basics. Each    *
+ * player's progress is tracked in player_quest, updated by hooks where       *
+ * resources are granted (combat.rs), crafts finish (crafting_queue.rs) and   *
+ * food finishes cooking (cooking.rs, credited to whoever put the food in).   *
+ * Finishing a quest grants its reward items through the usual inventory      *
+ * helpers and any cosmetics tied to it (cosmetics.rs), and sends a private   *
+ * message. Players only see their own progress rows.                         *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{client_visibility_filter, Filter, Identity, ReducerContext, SpacetimeType, Table, Timestamp, log};
+
+use crate::PrivateMessage;
+use crate::private_message as PrivateMessageTableTrait;
+use crate::items::item_definition as ItemDefinitionTableTrait;
+use crate::quests::quest_definition as QuestDefinitionTableTrait;
+use crate::quests::player_quest as PlayerQuestTableTrait;
+
+/// What a quest asks the player to do with its target item.
+#[derive(SpacetimeType, Copy, Clone, Debug, PartialEq)]
+pub enum QuestObjective {
+    Gather, // Harvest the resource from trees, stones and the like
+    Craft,
+    Cook, // Have the item come out of a campfire or furnace
+}
+
+/// Seeded quests: (name, description, objective, target item, quantity, reward item, reward quantity).
+/// Listed in the order they are meant to be done.
+const QUEST_DEFINITIONS: &[(&str, &str, QuestObjective, &str, u32, &str, u32)] = &[
+    ("Timber!", "Chop trees until you have gathered 50 Wood.", QuestObjective::Gather, "Wood", 50, "Stone", 50),
+    ("Light in the Dark", "Craft a Camp Fire.", QuestObjective::Craft, "Camp Fire", 1, "Raw Meat", 3),
+    ("A Hot Meal", "Cook 3 Cooked Meat over a campfire.", QuestObjective::Cook, "Cooked Meat", 3, "Bandage", 2),
+];
+
+/// --- Quest Definition ---
+/// A quest any player can complete once. Seeded from QUEST_DEFINITIONS.
+#[spacetimedb::table(name = quest_definition, public)]
+#[derive(Clone, Debug)]
+pub struct QuestDefinition {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub sort_order: u32, // Position in the tutorial, from 0
+    pub name: String,
+    pub description: String,
+    pub objective: QuestObjective,
+    pub target_item_name: String,
+    pub target_quantity: u32,
+    pub reward_item_name: String,
+    pub reward_quantity: u32,
+}
+
+/// --- Player Quest ---
+/// A player's progress on one quest, created the first time they make progress on it.
+#[spacetimedb::table(name = player_quest, public)]
+#[derive(Clone, Debug)]
+pub struct PlayerQuest {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub player_id: Identity,
+    pub quest_id: u64,
+    pub progress: u32,
+    pub completed_at: Option<Timestamp>,
+}
+
+/// Players only see their own quest progress.
+#[client_visibility_filter]
+const PLAYER_QUEST_OWN_ROWS: Filter = Filter::Sql(
+    "SELECT * FROM player_quest WHERE player_id = :sender"
+);
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Seeds the quest definitions if there are none yet.
+pub fn seed_quests(ctx: &ReducerContext) -> Result<(), String> {
+    let quests = ctx.db.quest_definition();
+    if quests.iter().count() > 0 {
+        return Ok(());
+    }
+    for (sort_order, &(name, description, objective, target_item_name, target_quantity, reward_item_name, reward_quantity)) in QUEST_DEFINITIONS.iter().enumerate() {
+        quests.try_insert(QuestDefinition {
+            id: 0, // Auto-incremented
+            sort_order: sort_order as u32,
+            name: name.to_string(),
+            description: description.to_string(),
+            objective,
+            target_item_name: target_item_name.to_string(),
+            target_quantity,
+            reward_item_name: reward_item_name.to_string(),
+            reward_quantity,
+        }).map_err(|e| format!("Failed to insert quest '{}': {}", name, e))?;
+    }
+    log::info!("Seeded {} quests.", QUEST_DEFINITIONS.len());
+    Ok(())
+}
+
+/// Counts `amount` of `item_name` towards the player's unfinished quests with this
+/// objective, completing any that reach their target.
+pub(crate) fn record_progress(ctx: &ReducerContext, player_id: Identity, objective: QuestObjective, item_name: &str, amount: u32) {
+    if amount == 0 {
+        return;
+    }
+    let matching: Vec<QuestDefinition> = ctx.db.quest_definition().iter()
+        .filter(|q| q.objective == objective && q.target_item_name == item_name)
+        .collect();
+    for quest in matching {
+        let player_quests = ctx.db.player_quest();
+        let mut entry = player_quests.player_id().filter(player_id)
+            .find(|pq| pq.quest_id == quest.id)
+            .unwrap_or_else(|| player_quests.insert(PlayerQuest {
+                id: 0, // Auto-incremented
+                player_id,
+                quest_id: quest.id,
+                progress: 0,
+                completed_at: None,
+            }));
+        if entry.completed_at.is_some() {
+            continue;
+        }
+        entry.progress = (entry.progress + amount).min(quest.target_quantity);
+        if entry.progress >= quest.target_quantity {
+            entry.completed_at = Some(ctx.timestamp);
+            complete_quest(ctx, player_id, &quest);
+        }
+        player_quests.id().update(entry);
+    }
+}
+
+/// Grants the quest's reward and tells the player.
+fn complete_quest(ctx: &ReducerContext, player_id: Identity, quest: &QuestDefinition) {
+    let reward_def_id = ctx.db.item_definition().iter()
+        .find(|def| def.name == quest.reward_item_name)
+        .map(|def| def.id);
+    match reward_def_id {
+        Some(def_id) => {
+            if let Err(e) = crate::overflow_backpack::add_item_to_player_inventory_or_overflow(ctx, player_id, def_id, quest.reward_quantity) {
+                log::error!("[Quests] Failed to grant reward for quest {} to player {:?}: {}", quest.id, player_id, e);
+            }
+        }
+        None => log::error!("[Quests] Reward item '{}' for quest {} not found.", quest.reward_item_name, quest.id),
+    }
+
+    ctx.db.private_message().insert(PrivateMessage {
+        id: 0, // Auto-incremented
+        recipient_identity: player_id,
+        sender_display_name: "SYSTEM".to_string(),
+        text: format!("Quest complete: {}! You received {} {}.", quest.name, quest.reward_quantity, quest.reward_item_name),
+        sent: ctx.timestamp,
+    });
+    crate::cosmetics::unlock_for_quest(ctx, player_id, &quest.name);
+    log::info!("[Quests] Player {:?} completed quest {} '{}'.", player_id, quest.id, quest.name);
+}
@@ -0,0 +1,527 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines the Recycler entity: a placeable container that breaks crafted     *
+ * items back into part of their ingredients. Items in the input slots are    *
+ * recycled one at a time while the recycler is switched on, returning a      *
+ * share of their recipe cost (see crafting.rs) into the output slots, scaled *
+ * down for worn tools. Items without a recipe cannot be inserted and are     *
+ * skipped if they end up in an input slot anyway. Slots are stored as a      *
+ * slot array, so item movement goes through the generic container handlers.  *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+
+use crate::player as PlayerTableTrait;
+use crate::Player;
+use crate::items::{
+    InventoryItem, ItemDefinition,
+    inventory_item as InventoryItemTableTrait,
+    item_definition as ItemDefinitionTableTrait,
+    add_item_to_player_inventory
+};
+use crate::crafting;
+use crate::dropped_item;
+use crate::inventory_management::{self, ItemContainer, ContainerItemClearer, ContainerSlot, SlotArrayContainer, empty_container_slots, is_container_empty};
+use crate::environment::calculate_chunk_index;
+use crate::models::{ContainerType, ContainerLocationData, ItemLocation};
+use crate::recycler::recycler as RecyclerTableTrait;
+use crate::recycler::recycler_schedule as RecyclerScheduleTableTrait;
+
+// --- Constants ---
+pub(crate) const RECYCLER_COLLISION_RADIUS: f32 = 28.0;
+pub(crate) const RECYCLER_COLLISION_Y_OFFSET: f32 = 10.0;
+const RECYCLER_INTERACTION_DISTANCE_SQUARED: f32 = 80.0 * 80.0;
+/// Slots 0..NUM_RECYCLER_INPUT_SLOTS take items to recycle; the rest receive the output.
+pub const NUM_RECYCLER_INPUT_SLOTS: usize = 6;
+pub const NUM_RECYCLER_SLOTS: usize = 12;
+const RECYCLER_INITIAL_HEALTH: f32 = 800.0;
+const RECYCLER_ITEM_NAME: &str = "Recycler";
+const RECYCLER_PROCESS_INTERVAL_SECS: u64 = 1;
+/// Seconds needed to recycle a single item.
+const RECYCLE_TIME_PER_ITEM_SECS: f32 = 5.0;
+/// Share of each recipe ingredient returned for an item in full repair.
+const RECYCLE_YIELD_FRACTION: f32 = 0.5;
+
+/// --- Recycler Data Structure ---
+#[spacetimedb::table(name = recycler, public)]
+#[derive(Clone, Debug)]
+pub struct Recycler {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub chunk_index: u32,
+
+    pub placed_by: Identity,
+
+    pub slots: Vec<ContainerSlot>,
+    pub is_active: bool,
+    /// Seconds spent on the item currently being recycled.
+    pub recycle_progress_secs: f32,
+
+    pub health: f32,
+    pub max_health: f32,
+    pub is_destroyed: bool,
+    pub destroyed_at: Option<Timestamp>,
+    pub last_hit_time: Option<Timestamp>,
+}
+
+/// --- Recycler Processing Schedule ---
+#[spacetimedb::table(name = recycler_schedule, scheduled(process_recyclers))]
+#[derive(Clone, Debug)]
+pub struct RecyclerSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/******************************************************************************
+ *                           REDUCERS (Generic Handlers)                      *
+ ******************************************************************************/
+
+/// Moves an item from the player's inventory/hotbar INTO a recycler slot.
+#[spacetimedb::reducer]
+pub fn move_item_to_recycler(ctx: &ReducerContext, recycler_id: u32, target_slot_index: u8, item_instance_id: u64) -> Result<(), String> {
+    let (_player, mut recycler) = validate_recycler_interaction(ctx, recycler_id)?;
+    ensure_recyclable(ctx, item_instance_id)?;
+    inventory_management::handle_move_to_container_slot(ctx, &mut recycler, target_slot_index, item_instance_id)?;
+    ctx.db.recycler().id().update(recycler);
+    Ok(())
+}
+
+/// Moves an item FROM a recycler slot INTO the player's inventory/hotbar.
+#[spacetimedb::reducer]
+pub fn move_item_from_recycler(
+    ctx: &ReducerContext,
+    recycler_id: u32,
+    source_slot_index: u8,
+    target_slot_type: String,
+    target_slot_index: u32
+) -> Result<(), String> {
+    let (_player, mut recycler) = validate_recycler_interaction(ctx, recycler_id)?;
+    inventory_management::handle_move_from_container_slot(ctx, &mut recycler, source_slot_index, target_slot_type, target_slot_index)?;
+    ctx.db.recycler().id().update(recycler);
+    Ok(())
+}
+
+/// Moves an item BETWEEN two slots within the same recycler.
+#[spacetimedb::reducer]
+pub fn move_item_within_recycler(ctx: &ReducerContext, recycler_id: u32, source_slot_index: u8, target_slot_index: u8) -> Result<(), String> {
+    let (_player, mut recycler) = validate_recycler_interaction(ctx, recycler_id)?;
+    inventory_management::handle_move_within_container(ctx, &mut recycler, source_slot_index, target_slot_index)?;
+    ctx.db.recycler().id().update(recycler);
+    Ok(())
+}
+
+/// Splits a stack from player inventory/hotbar into a specific recycler slot.
+#[spacetimedb::reducer]
+pub fn split_stack_into_recycler(
+    ctx: &ReducerContext,
+    recycler_id: u32,
+    target_slot_index: u8,
+    source_item_instance_id: u64,
+    quantity_to_split: u32,
+) -> Result<(), String> {
+    let (_player, mut recycler) = validate_recycler_interaction(ctx, recycler_id)?;
+    ensure_recyclable(ctx, source_item_instance_id)?;
+    inventory_management::handle_split_into_container(ctx, &mut recycler, target_slot_index, source_item_instance_id, quantity_to_split)?;
+    ctx.db.recycler().id().update(recycler);
+    Ok(())
+}
+
+/// Splits a stack from a recycler slot into the player's inventory/hotbar.
+#[spacetimedb::reducer]
+pub fn split_stack_from_recycler(
+    ctx: &ReducerContext,
+    recycler_id: u32,
+    source_slot_index: u8,
+    quantity_to_split: u32,
+    target_slot_type: String,
+    target_slot_index: u32,
+) -> Result<(), String> {
+    let (_player, mut recycler) = validate_recycler_interaction(ctx, recycler_id)?;
+    inventory_management::handle_split_from_container(ctx, &mut recycler, source_slot_index, quantity_to_split, target_slot_type, target_slot_index)?;
+    ctx.db.recycler().id().update(recycler);
+    Ok(())
+}
+
+/// Splits a stack FROM one recycler slot TO another within the same recycler.
+#[spacetimedb::reducer]
+pub fn split_stack_within_recycler(
+    ctx: &ReducerContext,
+    recycler_id: u32,
+    source_slot_index: u8,
+    target_slot_index: u8,
+    quantity_to_split: u32,
+) -> Result<(), String> {
+    let (_player, mut recycler) = validate_recycler_interaction(ctx, recycler_id)?;
+    inventory_management::handle_split_within_container(ctx, &mut recycler, source_slot_index, target_slot_index, quantity_to_split)?;
+    ctx.db.recycler().id().update(recycler);
+    Ok(())
+}
+
+/// Quickly moves an item FROM a recycler slot TO the player inventory.
+#[spacetimedb::reducer]
+pub fn quick_move_from_recycler(ctx: &ReducerContext, recycler_id: u32, source_slot_index: u8) -> Result<(), String> {
+    let (_player, mut recycler) = validate_recycler_interaction(ctx, recycler_id)?;
+    inventory_management::handle_quick_move_from_container(ctx, &mut recycler, source_slot_index)?;
+    ctx.db.recycler().id().update(recycler);
+    Ok(())
+}
+
+/// Quickly moves an item FROM player inventory/hotbar TO the first available/mergeable recycler slot.
+#[spacetimedb::reducer]
+pub fn quick_move_to_recycler(ctx: &ReducerContext, recycler_id: u32, item_instance_id: u64) -> Result<(), String> {
+    let (_player, mut recycler) = validate_recycler_interaction(ctx, recycler_id)?;
+    ensure_recyclable(ctx, item_instance_id)?;
+    inventory_management::handle_quick_move_to_container(ctx, &mut recycler, item_instance_id)?;
+    ctx.db.recycler().id().update(recycler);
+    Ok(())
+}
+
+/// Drops the whole stack in a recycler slot onto the ground near the player.
+#[spacetimedb::reducer]
+pub fn drop_item_from_recycler_slot_to_world(ctx: &ReducerContext, recycler_id: u32, slot_index: u8) -> Result<(), String> {
+    let (player, mut recycler) = validate_recycler_interaction(ctx, recycler_id)?;
+    inventory_management::handle_drop_from_container_slot(ctx, &mut recycler, slot_index, &player)?;
+    ctx.db.recycler().id().update(recycler);
+    Ok(())
+}
+
+/// Splits part of a recycler slot's stack and drops it onto the ground near the player.
+#[spacetimedb::reducer]
+pub fn split_and_drop_item_from_recycler_slot_to_world(
+    ctx: &ReducerContext,
+    recycler_id: u32,
+    slot_index: u8,
+    quantity_to_split: u32,
+) -> Result<(), String> {
+    let (player, mut recycler) = validate_recycler_interaction(ctx, recycler_id)?;
+    inventory_management::handle_split_and_drop_from_container_slot(ctx, &mut recycler, slot_index, quantity_to_split, &player)?;
+    ctx.db.recycler().id().update(recycler);
+    Ok(())
+}
+
+/******************************************************************************
+ *                     REDUCERS (Recycler-Specific Logic)                     *
+ ******************************************************************************/
+
+/// --- Place Recycler ---
+/// Places a Recycler item from the player's inventory/hotbar into the world.
+#[spacetimedb::reducer]
+pub fn place_recycler(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    crate::placement::place_deployable(ctx, item_instance_id, world_x, world_y, |name| name == RECYCLER_ITEM_NAME)
+}
+
+/// Creates an idle, empty recycler. The placement constructor for Recycler items.
+pub(crate) fn spawn_recycler(ctx: &ReducerContext, placed_by: Identity, _item_def: &ItemDefinition, _item_durability: Option<f32>, world_x: f32, world_y: f32) -> Result<(), String> {
+    let inserted = ctx.db.recycler().insert(Recycler {
+        id: 0, // Auto-incremented
+        pos_x: world_x,
+        pos_y: world_y,
+        chunk_index: calculate_chunk_index(world_x, world_y),
+        placed_by,
+        slots: empty_container_slots(NUM_RECYCLER_SLOTS),
+        is_active: false,
+        recycle_progress_secs: 0.0,
+        health: RECYCLER_INITIAL_HEALTH,
+        max_health: RECYCLER_INITIAL_HEALTH,
+        is_destroyed: false,
+        destroyed_at: None,
+        last_hit_time: None,
+    });
+    crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::Recycler(inserted.id), world_x, world_y);
+    log::info!("Player {:?} placed Recycler {} at ({:.1}, {:.1}).", placed_by, inserted.id, world_x, world_y);
+    Ok(())
+}
+
+/// --- Interact with Recycler ---
+#[spacetimedb::reducer]
+pub fn interact_with_recycler(ctx: &ReducerContext, recycler_id: u32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    validate_recycler_interaction(ctx, recycler_id)?;
+    log::debug!("Player {:?} interaction check OK for recycler {}", ctx.sender, recycler_id);
+    Ok(())
+}
+
+/// --- Toggle Recycler ---
+/// Switches the recycler on or off. Switching on requires something recyclable in the input slots.
+#[spacetimedb::reducer]
+pub fn toggle_recycler(ctx: &ReducerContext, recycler_id: u32) -> Result<(), String> {
+    let (_player, mut recycler) = validate_recycler_interaction(ctx, recycler_id)?;
+    if recycler.is_active {
+        recycler.is_active = false;
+        recycler.recycle_progress_secs = 0.0;
+        log::info!("Recycler {} switched off by player {:?}.", recycler_id, ctx.sender);
+    } else {
+        if find_next_recyclable_slot(ctx, &recycler).is_none() {
+            return Err("Nothing to recycle. Put crafted items in the input slots.".to_string());
+        }
+        recycler.is_active = true;
+        log::info!("Recycler {} switched on by player {:?}.", recycler_id, ctx.sender);
+    }
+    ctx.db.recycler().id().update(recycler);
+    Ok(())
+}
+
+/// --- Pickup Recycler ---
+/// Picks up an *empty* recycler, returning it to the player's inventory.
+#[spacetimedb::reducer]
+pub fn pickup_recycler(ctx: &ReducerContext, recycler_id: u32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let (_player, recycler) = validate_recycler_interaction(ctx, recycler_id)?;
+    if !is_container_empty(&recycler) {
+        return Err("Cannot pick up recycler: It is not empty.".to_string());
+    }
+
+    let recycler_item_def = ctx.db.item_definition().iter()
+        .find(|def| def.name == RECYCLER_ITEM_NAME)
+        .ok_or_else(|| format!("ItemDefinition for '{}' not found.", RECYCLER_ITEM_NAME))?;
+    add_item_to_player_inventory(ctx, sender_id, recycler_item_def.id, 1)
+        .map_err(|e| format!("Could not add {} to your inventory: {}", RECYCLER_ITEM_NAME, e))?;
+
+    ctx.db.recycler().id().delete(recycler_id);
+    crate::spatial_grid::untrack_entity(ctx, crate::spatial_grid::EntityType::Recycler(recycler_id));
+    log::info!("Recycler {} picked up by player {:?}.", recycler_id, sender_id);
+    Ok(())
+}
+
+/******************************************************************************
+ *                           SCHEDULED REDUCERS                               *
+ ******************************************************************************/
+
+/// Starts the recycler processing schedule if it isn't already scheduled. Called from `init_module`.
+pub fn init_recycler_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.recycler_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting recycler schedule (every {}s).", RECYCLER_PROCESS_INTERVAL_SECS);
+        let interval = Duration::from_secs(RECYCLER_PROCESS_INTERVAL_SECS);
+        schedule_table.try_insert(RecyclerSchedule {
+            id: 0,
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert recycler schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: advances every active recycler and recycles an item whenever one finishes.
+#[spacetimedb::reducer]
+pub fn process_recyclers(ctx: &ReducerContext, _schedule: RecyclerSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("process_recyclers may only be called by the scheduler.".to_string());
+    }
+
+    let active: Vec<Recycler> = ctx.db.recycler().iter()
+        .filter(|r| r.is_active && !r.is_destroyed)
+        .collect();
+    for mut recycler in active {
+        let Some(slot_index) = find_next_recyclable_slot(ctx, &recycler) else {
+            log::debug!("[Recycler] Recycler {} has nothing left to recycle. Switching off.", recycler.id);
+            recycler.is_active = false;
+            recycler.recycle_progress_secs = 0.0;
+            ctx.db.recycler().id().update(recycler);
+            continue;
+        };
+
+        recycler.recycle_progress_secs += RECYCLER_PROCESS_INTERVAL_SECS as f32;
+        if recycler.recycle_progress_secs >= RECYCLE_TIME_PER_ITEM_SECS {
+            recycler.recycle_progress_secs = 0.0;
+            if let Err(e) = recycle_one_item(ctx, &mut recycler, slot_index) {
+                log::error!("[Recycler] Recycler {} failed to recycle slot {}: {}", recycler.id, slot_index, e);
+            }
+        }
+        ctx.db.recycler().id().update(recycler);
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                            TRAIT IMPLEMENTATIONS                           *
+ ******************************************************************************/
+
+impl SlotArrayContainer for Recycler {
+    fn slots(&self) -> &[ContainerSlot] {
+        &self.slots
+    }
+
+    fn slots_mut(&mut self) -> &mut [ContainerSlot] {
+        &mut self.slots
+    }
+
+    fn slot_container_type(&self) -> ContainerType {
+        ContainerType::Recycler
+    }
+
+    fn slot_container_id(&self) -> u64 {
+        self.id as u64
+    }
+}
+
+/// --- Helper struct to implement the ContainerItemClearer trait for Recycler ---
+pub struct RecyclerClearer;
+
+impl ContainerItemClearer for RecyclerClearer {
+    fn clear_item(ctx: &ReducerContext, item_instance_id: u64) -> bool {
+        let recyclers = ctx.db.recycler();
+        for mut recycler in recyclers.iter() {
+            let Some(slot_index) = (0..recycler.num_slots() as u8).find(|&i| recycler.get_slot_instance_id(i) == Some(item_instance_id)) else {
+                continue;
+            };
+            log::debug!("[RecyclerClearer] Found item {} in recycler {} slot {}. Clearing slot.", item_instance_id, recycler.id, slot_index);
+            recycler.set_slot(slot_index, None, None);
+            recyclers.id().update(recycler);
+
+            let inventory_items = ctx.db.inventory_item();
+            if let Some(mut item) = inventory_items.instance_id().find(item_instance_id) {
+                item.location = ItemLocation::Unknown;
+                inventory_items.instance_id().update(item);
+            }
+            return true;
+        }
+        false
+    }
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Validates that the sender can use the recycler: it exists, isn't destroyed and is within reach.
+fn validate_recycler_interaction(ctx: &ReducerContext, recycler_id: u32) -> Result<(Player, Recycler), String> {
+    let sender_id = ctx.sender;
+    let player = ctx.db.player().identity().find(sender_id).ok_or_else(|| "Player not found".to_string())?;
+    let recycler = ctx.db.recycler().id().find(recycler_id)
+        .ok_or_else(|| format!("Recycler {} not found", recycler_id))?;
+
+    if recycler.is_destroyed {
+        return Err(format!("Recycler {} is destroyed.", recycler_id));
+    }
+
+    let dx = player.position_x - recycler.pos_x;
+    let dy = player.position_y - recycler.pos_y;
+    if (dx * dx + dy * dy) > RECYCLER_INTERACTION_DISTANCE_SQUARED {
+        return Err("Too far away".to_string());
+    }
+    Ok((player, recycler))
+}
+
+/// Rejects items that have no crafting recipe before they are put into a recycler.
+fn ensure_recyclable(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), String> {
+    let item = ctx.db.inventory_item().instance_id().find(item_instance_id)
+        .ok_or_else(|| format!("Item instance {} not found.", item_instance_id))?;
+    if crafting::find_recipe_for_item(ctx, item.item_def_id).is_none() {
+        let item_name = ctx.db.item_definition().id().find(item.item_def_id)
+            .map(|def| def.name)
+            .unwrap_or_else(|| format!("ID {}", item.item_def_id));
+        return Err(format!("{} cannot be recycled.", item_name));
+    }
+    Ok(())
+}
+
+/// Returns the first input slot holding an item with a recipe.
+fn find_next_recyclable_slot(ctx: &ReducerContext, recycler: &Recycler) -> Option<u8> {
+    (0..NUM_RECYCLER_INPUT_SLOTS as u8).find(|&i| {
+        recycler.get_slot_def_id(i)
+            .is_some_and(|def_id| crafting::find_recipe_for_item(ctx, def_id).is_some())
+    })
+}
+
+/// Consumes one item from the input slot and places its recycled ingredients in the output slots.
+fn recycle_one_item(ctx: &ReducerContext, recycler: &mut Recycler, slot_index: u8) -> Result<(), String> {
+    let inventory_items = ctx.db.inventory_item();
+    let instance_id = recycler.get_slot_instance_id(slot_index)
+        .ok_or_else(|| format!("Slot {} is empty.", slot_index))?;
+    let mut item = inventory_items.instance_id().find(instance_id)
+        .ok_or_else(|| format!("Item instance {} not found.", instance_id))?;
+    let item_def = ctx.db.item_definition().id().find(item.item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found.", item.item_def_id))?;
+    let recipe = crafting::find_recipe_for_item(ctx, item_def.id)
+        .ok_or_else(|| format!("{} has no recipe.", item_def.name))?;
+
+    // Worn tools return proportionally less
+    let condition = match (item.durability, item_def.max_durability) {
+        (Some(durability), Some(max)) if max > 0.0 => (durability / max).clamp(0.0, 1.0),
+        _ => 1.0,
+    };
+
+    if item.quantity > 1 {
+        item.quantity -= 1;
+        inventory_items.instance_id().update(item);
+    } else {
+        inventory_items.instance_id().delete(instance_id);
+        recycler.set_slot(slot_index, None, None);
+    }
+
+    for ingredient in &recipe.ingredients {
+        let per_item = ingredient.quantity as f32 / recipe.output_quantity.max(1) as f32;
+        let quantity = (per_item * RECYCLE_YIELD_FRACTION * condition).floor() as u32;
+        if quantity > 0 {
+            add_recycler_output(ctx, recycler, ingredient.item_def_id, quantity)?;
+        }
+    }
+    log::info!("[Recycler] Recycler {} recycled 1 {}.", recycler.id, item_def.name);
+    Ok(())
+}
+
+/// Stacks output onto matching output slots, then fills empty ones. Anything left over is dropped beside the recycler.
+fn add_recycler_output(ctx: &ReducerContext, recycler: &mut Recycler, item_def_id: u64, mut quantity: u32) -> Result<(), String> {
+    let inventory_items = ctx.db.inventory_item();
+    let item_def = ctx.db.item_definition().id().find(item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found.", item_def_id))?;
+    let output_slots = NUM_RECYCLER_INPUT_SLOTS as u8..NUM_RECYCLER_SLOTS as u8;
+
+    for i in output_slots.clone() {
+        if quantity == 0 {
+            return Ok(());
+        }
+        if recycler.get_slot_def_id(i) != Some(item_def_id) {
+            continue;
+        }
+        let Some(mut existing) = recycler.get_slot_instance_id(i).and_then(|id| inventory_items.instance_id().find(id)) else {
+            continue;
+        };
+        let added = quantity.min(item_def.stack_size.saturating_sub(existing.quantity));
+        if added > 0 {
+            existing.quantity += added;
+            inventory_items.instance_id().update(existing);
+            quantity -= added;
+        }
+    }
+
+    for i in output_slots {
+        if quantity == 0 {
+            return Ok(());
+        }
+        if recycler.get_slot_instance_id(i).is_some() {
+            continue;
+        }
+        let stack = quantity.min(item_def.stack_size.max(1));
+        let inserted = inventory_items.try_insert(InventoryItem {
+            instance_id: 0, // Auto-incremented
+            item_def_id,
+            quantity: stack,
+            location: ItemLocation::Container(ContainerLocationData {
+                container_type: ContainerType::Recycler,
+                container_id: recycler.id as u64,
+                slot_index: i,
+            }),
+            durability: None,
+            is_locked: false,
+            charges: None,
+        }).map_err(|e| format!("Failed to create recycled item: {}", e))?;
+        recycler.set_slot(i, Some(inserted.instance_id), Some(item_def_id));
+        quantity -= stack;
+    }
+
+    if quantity > 0 {
+        log::debug!("[Recycler] Recycler {} output is full. Dropping {} {}.", recycler.id, quantity, item_def.name);
+        dropped_item::create_dropped_item_entity(ctx, item_def_id, quantity, recycler.pos_x, recycler.pos_y + dropped_item::DROP_OFFSET / 2.0)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,255 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines recipe unlock progression. Only a handful of starter recipes are   *
+ * known from the beginning; everything else has to be learned, either by     *
+ * using a Blueprint found in loot crates (teaches a random unknown recipe)   *
+ * or by researching an item at a Research Table, which consumes the item     *
+ * plus some Scrap. The crafting queue refuses recipes the player doesn't     *
+ * know (see `ensure_recipe_known`).                                          *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+use rand::Rng;
+
+use crate::player as PlayerTableTrait;
+use crate::items::{ItemDefinition, inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
+use crate::crafting::{Recipe, RecipeIngredient};
+use crate::crafting::recipe as RecipeTableTrait;
+use crate::active_equipment::active_equipment as ActiveEquipmentTableTrait;
+use crate::environment::calculate_chunk_index;
+use crate::player_inventory::get_player_item;
+use crate::spatial_grid::{self, EntityType};
+use crate::research::known_recipe as KnownRecipeTableTrait;
+use crate::research::research_table as ResearchTableTableTrait;
+
+// --- Constants ---
+/// Recipes every player knows from the start, by output item name.
+const STARTER_RECIPES: &[&str] = &[
+    "Cloth",
+    "Rock",
+    "Stone Hatchet",
+    "Stone Pickaxe",
+    "Wooden Spear",
+    "Torch",
+    "Camp Fire",
+    "Wooden Storage Box",
+    "Sleeping Bag",
+    "Bandage",
+    "Workbench",
+    "Research Table",
+];
+const BLUEPRINT_ITEM_NAME: &str = "Blueprint";
+const SCRAP_ITEM_NAME: &str = "Scrap";
+const RESEARCH_TABLE_ITEM_NAME: &str = "Research Table";
+/// Scrap to research a recipe that needs no station; each workbench tier it needs adds more.
+const RESEARCH_BASE_SCRAP_COST: u32 = 20;
+const RESEARCH_SCRAP_COST_PER_STATION_TIER: u32 = 40;
+pub(crate) const RESEARCH_TABLE_COLLISION_RADIUS: f32 = 24.0;
+const RESEARCH_TABLE_INTERACTION_DISTANCE_SQUARED: f32 = 96.0 * 96.0;
+
+/// --- Known Recipe ---
+/// A recipe the player has learned. Starter recipes are known without a row.
+#[spacetimedb::table(name = known_recipe, public)]
+#[derive(Clone, Debug)]
+pub struct KnownRecipe {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub player_identity: Identity,
+    pub recipe_id: u64,
+    pub learned_at: Timestamp,
+}
+
+/// --- Research Table ---
+/// A placed station where items are researched into known recipes.
+#[spacetimedb::table(name = research_table, public)]
+#[derive(Clone, Debug)]
+pub struct ResearchTable {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub chunk_index: u32,
+    pub placed_by: Identity,
+    pub placed_at: Timestamp,
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Place Research Table ---
+/// Consumes a Research Table item from the player's inventory/hotbar and places the entity in the world.
+#[spacetimedb::reducer]
+pub fn place_research_table(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    crate::placement::place_deployable(ctx, item_instance_id, world_x, world_y, |name| name == RESEARCH_TABLE_ITEM_NAME)
+}
+
+/// Creates a research table. The placement constructor for Research Table items.
+pub(crate) fn spawn_research_table(ctx: &ReducerContext, placed_by: Identity, _item_def: &ItemDefinition, _item_durability: Option<f32>, world_x: f32, world_y: f32) -> Result<(), String> {
+    let inserted = ctx.db.research_table().try_insert(ResearchTable {
+        id: 0, // Auto-incremented
+        pos_x: world_x,
+        pos_y: world_y,
+        chunk_index: calculate_chunk_index(world_x, world_y),
+        placed_by,
+        placed_at: ctx.timestamp,
+    }).map_err(|e| format!("Failed to insert research table entity: {}", e))?;
+    spatial_grid::track_entity(ctx, EntityType::ResearchTable(inserted.id), inserted.pos_x, inserted.pos_y);
+    log::info!("[Research] Player {:?} placed Research Table {} at ({:.1}, {:.1}).", placed_by, inserted.id, world_x, world_y);
+    Ok(())
+}
+
+/// --- Pickup Research Table ---
+/// Returns a research table to the inventory of the player who placed it.
+#[spacetimedb::reducer]
+pub fn pickup_research_table(ctx: &ReducerContext, research_table_id: u32) -> Result<(), String> {
+    let research_table = validate_research_table_interaction(ctx, research_table_id)?;
+    if research_table.placed_by != ctx.sender {
+        return Err("Only the player who placed this research table can pick it up.".to_string());
+    }
+    let item_def = ctx.db.item_definition().iter()
+        .find(|def| def.name == RESEARCH_TABLE_ITEM_NAME)
+        .ok_or_else(|| format!("Item definition '{}' not found.", RESEARCH_TABLE_ITEM_NAME))?;
+
+    crate::items::add_item_to_player_inventory(ctx, ctx.sender, item_def.id, 1)?;
+    ctx.db.research_table().id().delete(research_table_id);
+    spatial_grid::untrack_entity(ctx, EntityType::ResearchTable(research_table_id));
+    Ok(())
+}
+
+/// --- Research Item ---
+/// Takes one of the item apart at a research table to learn its recipe. Costs the item plus Scrap.
+#[spacetimedb::reducer]
+pub fn research_item(ctx: &ReducerContext, research_table_id: u32, item_instance_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    validate_research_table_interaction(ctx, research_table_id)?;
+
+    let item = get_player_item(ctx, item_instance_id)?;
+    let item_def = ctx.db.item_definition().id().find(item.item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found.", item.item_def_id))?;
+    let recipe = crate::crafting::find_recipe_for_item(ctx, item_def.id)
+        .ok_or_else(|| format!("{} can't be crafted, so there is nothing to learn from it.", item_def.name))?;
+    if is_recipe_known(ctx, sender_id, &recipe) {
+        return Err(format!("You already know how to craft {}.", item_def.name));
+    }
+
+    let scrap_def = ctx.db.item_definition().iter()
+        .find(|def| def.name == SCRAP_ITEM_NAME)
+        .ok_or_else(|| format!("Item definition '{}' not found.", SCRAP_ITEM_NAME))?;
+    let scrap_cost = RecipeIngredient {
+        item_def_id: scrap_def.id,
+        quantity: research_scrap_cost(&recipe),
+    };
+    crate::crafting::consume_player_resources(ctx, sender_id, &[scrap_cost])?;
+    consume_one(ctx, sender_id, item_instance_id)?;
+
+    learn_recipe(ctx, sender_id, &recipe);
+    log::info!("[Research] Player {:?} researched {} (Recipe ID {}).", sender_id, item_def.name, recipe.recipe_id);
+    Ok(())
+}
+
+/// --- Use Blueprint ---
+/// Consumes a Blueprint to learn a random recipe the player doesn't know yet.
+#[spacetimedb::reducer]
+pub fn use_blueprint(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    crate::spectator::ensure_not_spectating(ctx, sender_id)?;
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Player not found".to_string())?;
+    if player.is_dead {
+        return Err("Cannot use a blueprint while dead.".to_string());
+    }
+    let item = get_player_item(ctx, item_instance_id)?;
+    let item_def = ctx.db.item_definition().id().find(item.item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found.", item.item_def_id))?;
+    if item_def.name != BLUEPRINT_ITEM_NAME {
+        return Err("Item is not a Blueprint.".to_string());
+    }
+
+    let unknown: Vec<Recipe> = ctx.db.recipe().iter()
+        .filter(|r| !is_recipe_known(ctx, sender_id, r))
+        .collect();
+    if unknown.is_empty() {
+        return Err("You already know every recipe.".to_string());
+    }
+    let recipe = &unknown[ctx.rng().gen_range(0..unknown.len())];
+    consume_one(ctx, sender_id, item_instance_id)?;
+
+    learn_recipe(ctx, sender_id, recipe);
+    log::info!("[Research] Player {:?} learned Recipe ID {} from a blueprint.", sender_id, recipe.recipe_id);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Whether the player can craft this recipe: it's a starter recipe or one they have learned.
+pub(crate) fn is_recipe_known(ctx: &ReducerContext, player_id: Identity, recipe: &Recipe) -> bool {
+    let is_starter = ctx.db.item_definition().id().find(recipe.output_item_def_id)
+        .is_some_and(|def| STARTER_RECIPES.contains(&def.name.as_str()));
+    is_starter || ctx.db.known_recipe().player_identity().filter(player_id).any(|k| k.recipe_id == recipe.recipe_id)
+}
+
+/// Errors unless the player knows the recipe. Used by the crafting queue before consuming ingredients.
+pub(crate) fn ensure_recipe_known(ctx: &ReducerContext, player_id: Identity, recipe: &Recipe) -> Result<(), String> {
+    if is_recipe_known(ctx, player_id, recipe) {
+        return Ok(());
+    }
+    let item_name = ctx.db.item_definition().id().find(recipe.output_item_def_id)
+        .map_or_else(|| format!("ID {}", recipe.output_item_def_id), |def| def.name);
+    Err(format!("You haven't learned how to craft {} yet. Find a blueprint or research one.", item_name))
+}
+
+fn learn_recipe(ctx: &ReducerContext, player_id: Identity, recipe: &Recipe) {
+    ctx.db.known_recipe().insert(KnownRecipe {
+        id: 0, // Auto-incremented
+        player_identity: player_id,
+        recipe_id: recipe.recipe_id,
+        learned_at: ctx.timestamp,
+    });
+}
+
+fn research_scrap_cost(recipe: &Recipe) -> u32 {
+    RESEARCH_BASE_SCRAP_COST + RESEARCH_SCRAP_COST_PER_STATION_TIER * recipe.required_station_tier as u32
+}
+
+/// Removes one unit of the player's item, putting it away first if it is the equipped item.
+fn consume_one(ctx: &ReducerContext, player_id: Identity, item_instance_id: u64) -> Result<(), String> {
+    let inventory_items = ctx.db.inventory_item();
+    let mut item = inventory_items.instance_id().find(item_instance_id)
+        .ok_or_else(|| format!("Item instance {} not found.", item_instance_id))?;
+    if item.quantity > 1 {
+        item.quantity -= 1;
+        inventory_items.instance_id().update(item);
+        return Ok(());
+    }
+    let is_held = ctx.db.active_equipment().player_identity().find(player_id)
+        .is_some_and(|e| e.equipped_item_instance_id == Some(item_instance_id));
+    if is_held {
+        crate::active_equipment::clear_active_item_reducer(ctx, player_id)?;
+    }
+    inventory_items.instance_id().delete(item_instance_id);
+    Ok(())
+}
+
+/// Checks the sender is alive, not spectating and close enough to the research table.
+fn validate_research_table_interaction(ctx: &ReducerContext, research_table_id: u32) -> Result<ResearchTable, String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or_else(|| "Player not found".to_string())?;
+    if player.is_dead {
+        return Err("Cannot use a research table while dead.".to_string());
+    }
+    let research_table = ctx.db.research_table().id().find(research_table_id)
+        .ok_or_else(|| format!("Research table {} not found.", research_table_id))?;
+    let dist_sq = (player.position_x - research_table.pos_x).powi(2) + (player.position_y - research_table.pos_y).powi(2);
+    if dist_sq > RESEARCH_TABLE_INTERACTION_DISTANCE_SQUARED {
+        return Err("Too far away from the research table.".to_string());
+    }
+    Ok(research_table)
+}
@@ -0,0 +1,235 @@
+/******************************************************************************
+ *                                                                            *
+ * Server announcements shown to every client as a banner. Admins broadcast   *
+ * immediately with `broadcast_announcement`, or schedule one-off and         *
+ * recurring announcements (restart warnings, event notices). Scheduled       *
+ * announcements are rows of a scheduled table, so the configuration and the  *
+ * timer are the same row. Expired banners are cleaned up periodically.       *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, TimeDuration, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+
+use crate::admin;
+use crate::server_announcement::server_announcement as ServerAnnouncementTableTrait;
+use crate::server_announcement::scheduled_announcement as ScheduledAnnouncementTableTrait;
+use crate::server_announcement::announcement_cleanup_schedule as AnnouncementCleanupScheduleTableTrait;
+
+// --- Constants ---
+const MAX_ANNOUNCEMENT_LENGTH: usize = 200;
+const MAX_ANNOUNCEMENT_DISPLAY_SECS: u32 = 300;
+const ANNOUNCEMENT_CLEANUP_INTERVAL_SECS: u64 = 5;
+/// How long before a restart each warning is shown, in minutes.
+const RESTART_WARNING_LEAD_MINUTES: &[u32] = &[30, 15, 5, 1];
+const RESTART_WARNING_DISPLAY_SECS: u32 = 20;
+
+/// --- Server Announcement ---
+/// A banner currently shown to all clients. Removed once `expires_at` has passed.
+#[spacetimedb::table(name = server_announcement, public)]
+#[derive(Clone, Debug)]
+pub struct ServerAnnouncement {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub text: String,
+    pub posted_by: Identity, // Admin, or the module identity for scheduled announcements
+    pub created_at: Timestamp,
+    pub expires_at: Timestamp,
+}
+
+/// --- Scheduled Announcement ---
+/// An announcement that fires at a set time (`ScheduleAt::Time`) or repeatedly (`ScheduleAt::Interval`).
+/// One-off rows are removed by the scheduler after they fire.
+#[spacetimedb::table(name = scheduled_announcement, scheduled(fire_scheduled_announcement))]
+#[derive(Clone, Debug)]
+pub struct ScheduledAnnouncement {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+    pub text: String,
+    pub display_secs: u32,
+    pub created_by: Identity,
+}
+
+/// --- Banner Cleanup Schedule ---
+#[spacetimedb::table(name = announcement_cleanup_schedule, scheduled(cleanup_expired_announcements))]
+#[derive(Clone, Debug)]
+pub struct AnnouncementCleanupSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// Admin only: shows a banner to every client for `duration_secs` seconds.
+#[spacetimedb::reducer]
+pub fn broadcast_announcement(ctx: &ReducerContext, text: String, duration_secs: u32) -> Result<(), String> {
+    require_admin(ctx)?;
+    let text = validate_announcement(&text, duration_secs)?;
+    post_announcement(ctx, text, duration_secs, ctx.sender);
+    Ok(())
+}
+
+/// Admin only: schedules an announcement `delay_secs` from now. With a non-zero
+/// `repeat_every_secs` it then repeats at that interval until cancelled.
+#[spacetimedb::reducer]
+pub fn schedule_announcement(
+    ctx: &ReducerContext,
+    text: String,
+    display_secs: u32,
+    delay_secs: u32,
+    repeat_every_secs: u32,
+) -> Result<(), String> {
+    require_admin(ctx)?;
+    let text = validate_announcement(&text, display_secs)?;
+    let scheduled_at = if repeat_every_secs > 0 {
+        if delay_secs > 0 {
+            return Err("Repeating announcements start one interval from now; use delay 0.".to_string());
+        }
+        ScheduleAt::Interval(Duration::from_secs(repeat_every_secs as u64).into())
+    } else {
+        ScheduleAt::Time(ctx.timestamp + TimeDuration::from(Duration::from_secs(delay_secs as u64)))
+    };
+    let inserted = ctx.db.scheduled_announcement().try_insert(ScheduledAnnouncement {
+        id: 0, // Auto-incremented
+        scheduled_at,
+        text,
+        display_secs,
+        created_by: ctx.sender,
+    }).map_err(|e| format!("Failed to schedule announcement: {}", e))?;
+    log::info!("[Announcement] Admin {:?} scheduled announcement {}.", ctx.sender, inserted.id);
+    Ok(())
+}
+
+/// Admin only: schedules the standard series of restart warnings ahead of a restart
+/// `minutes_until_restart` minutes from now. Warnings further out than that are skipped.
+#[spacetimedb::reducer]
+pub fn schedule_restart_warnings(ctx: &ReducerContext, minutes_until_restart: u32) -> Result<(), String> {
+    require_admin(ctx)?;
+    if minutes_until_restart == 0 {
+        return Err("Restart must be at least one minute away.".to_string());
+    }
+    for &lead_minutes in RESTART_WARNING_LEAD_MINUTES.iter().filter(|&&m| m <= minutes_until_restart) {
+        let delay = Duration::from_secs((minutes_until_restart - lead_minutes) as u64 * 60);
+        let unit = if lead_minutes == 1 { "minute" } else { "minutes" };
+        ctx.db.scheduled_announcement().try_insert(ScheduledAnnouncement {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Time(ctx.timestamp + TimeDuration::from(delay)),
+            text: format!("Server restarting in {} {}.", lead_minutes, unit),
+            display_secs: RESTART_WARNING_DISPLAY_SECS,
+            created_by: ctx.sender,
+        }).map_err(|e| format!("Failed to schedule restart warning: {}", e))?;
+    }
+    log::info!("[Announcement] Admin {:?} scheduled restart warnings for a restart in {} minutes.", ctx.sender, minutes_until_restart);
+    Ok(())
+}
+
+/// Admin only: cancels a scheduled (one-off or recurring) announcement.
+#[spacetimedb::reducer]
+pub fn cancel_scheduled_announcement(ctx: &ReducerContext, scheduled_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !ctx.db.scheduled_announcement().id().delete(scheduled_id) {
+        return Err(format!("Scheduled announcement {} not found.", scheduled_id));
+    }
+    log::info!("[Announcement] Admin {:?} cancelled scheduled announcement {}.", ctx.sender, scheduled_id);
+    Ok(())
+}
+
+/// Admin only: takes a banner down before it expires.
+#[spacetimedb::reducer]
+pub fn clear_announcement(ctx: &ReducerContext, announcement_id: u64) -> Result<(), String> {
+    require_admin(ctx)?;
+    if !ctx.db.server_announcement().id().delete(announcement_id) {
+        return Err(format!("Announcement {} not found.", announcement_id));
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                           SCHEDULED REDUCERS                               *
+ ******************************************************************************/
+
+/// Scheduled reducer: posts the banner for a scheduled announcement.
+#[spacetimedb::reducer]
+pub fn fire_scheduled_announcement(ctx: &ReducerContext, scheduled: ScheduledAnnouncement) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("fire_scheduled_announcement may only be called by the scheduler.".to_string());
+    }
+    post_announcement(ctx, scheduled.text, scheduled.display_secs, ctx.identity());
+    Ok(())
+}
+
+/// Starts the banner cleanup schedule if it isn't already scheduled. Called from `init_module`.
+pub fn init_announcement_cleanup_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.announcement_cleanup_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting announcement cleanup schedule (every {}s).", ANNOUNCEMENT_CLEANUP_INTERVAL_SECS);
+        let interval = Duration::from_secs(ANNOUNCEMENT_CLEANUP_INTERVAL_SECS);
+        schedule_table.try_insert(AnnouncementCleanupSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert announcement cleanup schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: removes banners whose display time has run out.
+#[spacetimedb::reducer]
+pub fn cleanup_expired_announcements(ctx: &ReducerContext, _schedule: AnnouncementCleanupSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("cleanup_expired_announcements may only be called by the scheduler.".to_string());
+    }
+    let announcements = ctx.db.server_announcement();
+    let expired: Vec<u64> = announcements.iter()
+        .filter(|a| a.expires_at <= ctx.timestamp)
+        .map(|a| a.id)
+        .collect();
+    for announcement_id in expired {
+        announcements.id().delete(announcement_id);
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Shows a banner to every client.
+pub(crate) fn post_announcement(ctx: &ReducerContext, text: String, display_secs: u32, posted_by: Identity) {
+    log::info!("[Announcement] {}", text);
+    ctx.db.server_announcement().insert(ServerAnnouncement {
+        id: 0, // Auto-incremented
+        text,
+        posted_by,
+        created_at: ctx.timestamp,
+        expires_at: ctx.timestamp + TimeDuration::from(Duration::from_secs(display_secs as u64)),
+    });
+}
+
+fn require_admin(ctx: &ReducerContext) -> Result<(), String> {
+    if !admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can manage announcements.".to_string());
+    }
+    Ok(())
+}
+
+fn validate_announcement(text: &str, display_secs: u32) -> Result<String, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Announcement cannot be empty.".to_string());
+    }
+    if text.chars().count() > MAX_ANNOUNCEMENT_LENGTH {
+        return Err(format!("Announcement too long (max {} characters).", MAX_ANNOUNCEMENT_LENGTH));
+    }
+    if display_secs == 0 || display_secs > MAX_ANNOUNCEMENT_DISPLAY_SECS {
+        return Err(format!("Display time must be between 1 and {} seconds.", MAX_ANNOUNCEMENT_DISPLAY_SECS));
+    }
+    Ok(text.to_string())
+}
@@ -0,0 +1,127 @@
+/******************************************************************************
+ *                                                                            *
+ * Lightweight public views for the client's server browser and TAB player    *
+ * list. ServerInfo is a single row (player counts, uptime, world size and    *
+ * the current day/time) refreshed by the global tick. PlayerListEntry holds  *
+ * one small row per registered player (name, online status, last seen) kept  *
+ * up to date on registration, connect and disconnect, so clients can show    *
+ * the player list without subscribing to the full Player table.              *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+
+use crate::{WORLD_WIDTH_TILES, WORLD_HEIGHT_TILES};
+use crate::player as PlayerTableTrait;
+use crate::world_state::{TimeOfDay, world_state as WorldStateTableTrait};
+use crate::server_info::server_info as ServerInfoTableTrait;
+use crate::server_info::player_list_entry as PlayerListEntryTableTrait;
+
+/// Primary key of the single ServerInfo row.
+const SERVER_INFO_ID: u8 = 0;
+
+/// --- Server Info ---
+/// Summary of the server's state. Singleton table, `id` is always 0.
+#[spacetimedb::table(name = server_info, public)]
+#[derive(Clone, Debug)]
+pub struct ServerInfo {
+    #[primary_key]
+    pub id: u8,
+    pub online_players: u32,
+    pub registered_players: u32,
+    pub started_at: Timestamp, // When the module was last published
+    pub uptime_secs: u64,
+    pub world_width_tiles: u32,
+    pub world_height_tiles: u32,
+    pub day: u32, // 1 on the first day, counting completed day/night cycles
+    pub time_of_day: TimeOfDay,
+    pub cycle_progress: f32,
+    pub updated_at: Timestamp,
+}
+
+/// --- Player List Entry ---
+/// The public slice of a player shown in the player list.
+#[spacetimedb::table(name = player_list_entry, public)]
+#[derive(Clone, Debug)]
+pub struct PlayerListEntry {
+    #[primary_key]
+    pub player_id: Identity,
+    pub username: String,
+    pub is_online: bool,
+    pub last_seen: Timestamp, // Last connect, disconnect or registration
+}
+
+/// Resets the uptime and builds the player list from existing players. Called from `init_module`.
+pub fn init_server_info(ctx: &ReducerContext) -> Result<(), String> {
+    let infos = ctx.db.server_info();
+    let info = build_server_info(ctx, ctx.timestamp);
+    if infos.id().find(SERVER_INFO_ID).is_some() {
+        infos.id().update(info);
+    } else {
+        infos.try_insert(info).map_err(|e| format!("Failed to insert server info: {}", e))?;
+    }
+
+    let entries = ctx.db.player_list_entry();
+    let mut added = 0;
+    for player in ctx.db.player().iter() {
+        if entries.player_id().find(player.identity).is_none() {
+            entries.insert(PlayerListEntry {
+                player_id: player.identity,
+                username: player.username.clone(),
+                is_online: player.is_online,
+                last_seen: player.last_update,
+            });
+            added += 1;
+        }
+    }
+    log::info!("[ServerInfo] Initialized server info; added {} player list entries.", added);
+    Ok(())
+}
+
+/// Refreshes the ServerInfo row. Called every global tick.
+pub(crate) fn tick_server_info(ctx: &ReducerContext) {
+    let infos = ctx.db.server_info();
+    let Some(existing) = infos.id().find(SERVER_INFO_ID) else {
+        log::warn!("[ServerInfo] Server info row missing; was init_server_info run?");
+        return;
+    };
+    infos.id().update(build_server_info(ctx, existing.started_at));
+}
+
+/// Creates or updates the player's list entry, marking them as seen now.
+pub(crate) fn update_player_list_entry(ctx: &ReducerContext, player_id: Identity, username: &str, is_online: bool) {
+    let entries = ctx.db.player_list_entry();
+    let entry = PlayerListEntry {
+        player_id,
+        username: username.to_string(),
+        is_online,
+        last_seen: ctx.timestamp,
+    };
+    if entries.player_id().find(player_id).is_some() {
+        entries.player_id().update(entry);
+    } else {
+        entries.insert(entry);
+    }
+}
+
+/// Current server info, with uptime measured from `started_at`.
+fn build_server_info(ctx: &ReducerContext, started_at: Timestamp) -> ServerInfo {
+    let (online_players, registered_players) = ctx.db.player().iter()
+        .fold((0, 0), |(online, total), p| (online + p.is_online as u32, total + 1));
+    let uptime_micros = ctx.timestamp.to_micros_since_unix_epoch() - started_at.to_micros_since_unix_epoch();
+    let world_state = ctx.db.world_state().iter().next();
+
+    ServerInfo {
+        id: SERVER_INFO_ID,
+        online_players,
+        registered_players,
+        started_at,
+        uptime_secs: (uptime_micros.max(0) / 1_000_000) as u64,
+        world_width_tiles: WORLD_WIDTH_TILES,
+        world_height_tiles: WORLD_HEIGHT_TILES,
+        day: world_state.as_ref().map_or(1, |w| w.cycle_count + 1),
+        time_of_day: world_state.as_ref().map_or(TimeOfDay::Morning, |w| w.time_of_day.clone()),
+        cycle_progress: world_state.as_ref().map_or(0.0, |w| w.cycle_progress),
+        updated_at: ctx.timestamp,
+    }
+}
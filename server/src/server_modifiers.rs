@@ -0,0 +1,257 @@
+/******************************************************************************
+ *                                                                            *
+ * Global gameplay modifiers for server events ("double wood weekend",        *
+ * crafting happy hours). Each running event is a public ActiveModifier row   *
+ * so clients can show a banner. WEEKLY_EVENTS are started by one-shot        *
+ * schedules that re-arm themselves for the next week; admins can also start  *
+ * and stop events by hand. combat.rs folds the yield modifiers into          *
+ * `calculate_damage_and_yield` and crafting_queue.rs the crafting speed.     *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp, TimeDuration, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+
+use crate::admin;
+use crate::chat;
+use crate::server_modifiers::active_modifiers as ActiveModifiersTableTrait;
+use crate::server_modifiers::modifier_event_schedule as ModifierEventScheduleTableTrait;
+use crate::server_modifiers::modifier_expiry_schedule as ModifierExpiryScheduleTableTrait;
+
+// --- Constants ---
+const MODIFIER_EXPIRY_INTERVAL_SECS: u64 = 30;
+const MAX_MODIFIER_MULTIPLIER: f32 = 10.0;
+const MAX_MODIFIER_LABEL_LENGTH: usize = 64;
+const MICROS_PER_HOUR: i64 = 60 * 60 * 1_000_000;
+const MICROS_PER_DAY: i64 = 24 * MICROS_PER_HOUR;
+const MICROS_PER_WEEK: i64 = 7 * MICROS_PER_DAY;
+
+/// What a modifier changes. Multipliers of the same kind stack multiplicatively.
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum ModifierKind {
+    WoodYield,
+    StoneYield,
+    HarvestYield, // Every gathered resource, including wood and stone
+    CraftingSpeed, // 2.0 crafts twice as fast; applies to crafts queued while it runs
+}
+
+/// A recurring event, in UTC. `start_day` counts from Monday (0) to Sunday (6).
+struct WeeklyEvent {
+    label: &'static str,
+    kind: ModifierKind,
+    multiplier: f32,
+    start_day: i64,
+    start_hour: i64,
+    duration_hours: i64,
+}
+
+const WEEKLY_EVENTS: &[WeeklyEvent] = &[
+    WeeklyEvent { label: "Double Wood Weekend", kind: ModifierKind::WoodYield, multiplier: 2.0, start_day: 5, start_hour: 0, duration_hours: 48 },
+    WeeklyEvent { label: "Crafting Happy Hour", kind: ModifierKind::CraftingSpeed, multiplier: 2.0, start_day: 2, start_hour: 18, duration_hours: 2 },
+];
+
+/// --- Active Modifier ---
+/// A running event. Public so clients can show what's boosted and until when.
+#[spacetimedb::table(name = active_modifiers, public)]
+#[derive(Clone, Debug)]
+pub struct ActiveModifier {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub kind: ModifierKind,
+    pub multiplier: f32,
+    pub label: String,
+    pub started_at: Timestamp,
+    pub ends_at: Option<Timestamp>, // None = runs until an admin stops it
+    pub started_by: Option<Identity>, // None for scheduled weekly events
+}
+
+/// --- Modifier Event Schedule ---
+/// One-shot start of the next occurrence of a weekly event.
+#[spacetimedb::table(name = modifier_event_schedule, scheduled(start_weekly_modifier_event))]
+#[derive(Clone, Debug)]
+pub struct ModifierEventSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub event_index: u32, // Index into WEEKLY_EVENTS
+    pub ends_at: Timestamp, // End of this occurrence, so a late start doesn't run long
+    pub scheduled_at: ScheduleAt,
+}
+
+/// --- Modifier Expiry Schedule ---
+#[spacetimedb::table(name = modifier_expiry_schedule, scheduled(expire_modifiers))]
+#[derive(Clone, Debug)]
+pub struct ModifierExpirySchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/******************************************************************************
+ *                                 REDUCERS                                   *
+ ******************************************************************************/
+
+/// Admin only. Starts a modifier event now, for `duration_secs` or until stopped if None.
+#[spacetimedb::reducer]
+pub fn start_modifier_event(ctx: &ReducerContext, kind: ModifierKind, multiplier: f32, label: String, duration_secs: Option<u64>) -> Result<(), String> {
+    if !admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can start events.".to_string());
+    }
+    if !(multiplier > 0.0 && multiplier <= MAX_MODIFIER_MULTIPLIER) {
+        return Err(format!("Multiplier must be above 0 and at most {}.", MAX_MODIFIER_MULTIPLIER));
+    }
+    let label = label.trim().to_string();
+    if label.is_empty() || label.chars().count() > MAX_MODIFIER_LABEL_LENGTH {
+        return Err(format!("Event name must be 1-{} characters.", MAX_MODIFIER_LABEL_LENGTH));
+    }
+    if duration_secs == Some(0) {
+        return Err("Duration must be greater than zero.".to_string());
+    }
+
+    let ends_at = duration_secs.map(|secs| ctx.timestamp + TimeDuration::from(Duration::from_secs(secs)));
+    start_modifier(ctx, kind, multiplier, label, ends_at, Some(ctx.sender));
+    Ok(())
+}
+
+/// Admin only. Ends a running modifier event early. Scheduled weekly events still come back next week.
+#[spacetimedb::reducer]
+pub fn stop_modifier_event(ctx: &ReducerContext, modifier_id: u64) -> Result<(), String> {
+    if !admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can stop events.".to_string());
+    }
+    let modifier = ctx.db.active_modifiers().id().find(modifier_id)
+        .ok_or_else(|| format!("Event {} not found.", modifier_id))?;
+    end_modifier(ctx, &modifier);
+    log::info!("[Modifiers] Admin {:?} stopped event {} ('{}').", ctx.sender, modifier.id, modifier.label);
+    Ok(())
+}
+
+/******************************************************************************
+ *                           SCHEDULED REDUCERS                               *
+ ******************************************************************************/
+
+/// Schedules the next occurrence of every weekly event that isn't scheduled yet, and starts
+/// the modifier expiry check. Called from `init_module`.
+pub fn init_modifier_schedules(ctx: &ReducerContext) -> Result<(), String> {
+    let event_schedules = ctx.db.modifier_event_schedule();
+    for event_index in 0..WEEKLY_EVENTS.len() as u32 {
+        if !event_schedules.iter().any(|s| s.event_index == event_index) {
+            schedule_next_occurrence(ctx, event_index, ctx.timestamp)?;
+        }
+    }
+
+    let expiry_schedule = ctx.db.modifier_expiry_schedule();
+    if expiry_schedule.iter().count() == 0 {
+        log::info!("Starting modifier expiry schedule (every {}s).", MODIFIER_EXPIRY_INTERVAL_SECS);
+        let interval = Duration::from_secs(MODIFIER_EXPIRY_INTERVAL_SECS);
+        expiry_schedule.try_insert(ModifierExpirySchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert modifier expiry schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: starts one occurrence of a weekly event and schedules the next.
+#[spacetimedb::reducer]
+pub fn start_weekly_modifier_event(ctx: &ReducerContext, schedule: ModifierEventSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("start_weekly_modifier_event may only be called by the scheduler.".to_string());
+    }
+    let event = WEEKLY_EVENTS.get(schedule.event_index as usize)
+        .ok_or_else(|| format!("Weekly event {} no longer exists.", schedule.event_index))?;
+
+    if ctx.timestamp < schedule.ends_at {
+        start_modifier(ctx, event.kind, event.multiplier, event.label.to_string(), Some(schedule.ends_at), None);
+    } else {
+        log::info!("[Modifiers] Skipping missed occurrence of '{}'.", event.label);
+    }
+    schedule_next_occurrence(ctx, schedule.event_index, schedule.ends_at)
+}
+
+/// Scheduled reducer: ends modifiers whose time is up.
+#[spacetimedb::reducer]
+pub fn expire_modifiers(ctx: &ReducerContext, _schedule: ModifierExpirySchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("expire_modifiers may only be called by the scheduler.".to_string());
+    }
+    let expired: Vec<ActiveModifier> = ctx.db.active_modifiers().iter()
+        .filter(|m| m.ends_at.is_some_and(|ends_at| ends_at <= ctx.timestamp))
+        .collect();
+    for modifier in &expired {
+        end_modifier(ctx, modifier);
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Combined multiplier of the running modifiers of this kind, 1.0 if there are none.
+pub(crate) fn modifier_multiplier(ctx: &ReducerContext, kind: ModifierKind) -> f32 {
+    ctx.db.active_modifiers().iter()
+        .filter(|m| m.kind == kind && m.ends_at.is_none_or(|ends_at| ends_at > ctx.timestamp))
+        .map(|m| m.multiplier)
+        .product()
+}
+
+/// Yield multiplier for gathering `resource_name`, from the harvest events running now.
+pub(crate) fn yield_multiplier_for_resource(ctx: &ReducerContext, resource_name: &str) -> f32 {
+    let resource_specific = match resource_name {
+        "Wood" => modifier_multiplier(ctx, ModifierKind::WoodYield),
+        "Stone" => modifier_multiplier(ctx, ModifierKind::StoneYield),
+        _ => 1.0,
+    };
+    resource_specific * modifier_multiplier(ctx, ModifierKind::HarvestYield)
+}
+
+fn start_modifier(ctx: &ReducerContext, kind: ModifierKind, multiplier: f32, label: String, ends_at: Option<Timestamp>, started_by: Option<Identity>) {
+    let modifier = ctx.db.active_modifiers().insert(ActiveModifier {
+        id: 0, // Auto-incremented
+        kind,
+        multiplier,
+        label,
+        started_at: ctx.timestamp,
+        ends_at,
+        started_by,
+    });
+    log::info!("[Modifiers] Started event {} ('{}'): {:?} x{} until {:?}.",
+        modifier.id, modifier.label, modifier.kind, modifier.multiplier, modifier.ends_at);
+    chat::send_system_message(ctx, format!("{} has started!", modifier.label));
+}
+
+fn end_modifier(ctx: &ReducerContext, modifier: &ActiveModifier) {
+    ctx.db.active_modifiers().id().delete(modifier.id);
+    log::info!("[Modifiers] Ended event {} ('{}').", modifier.id, modifier.label);
+    chat::send_system_message(ctx, format!("{} has ended.", modifier.label));
+}
+
+/// Schedules the first occurrence of the weekly event that ends after `after`.
+/// An occurrence already under way is scheduled to start right away.
+fn schedule_next_occurrence(ctx: &ReducerContext, event_index: u32, after: Timestamp) -> Result<(), String> {
+    let event = &WEEKLY_EVENTS[event_index as usize];
+    let after_micros = after.to_micros_since_unix_epoch();
+    // 1970-01-01 was a Thursday, three days after the start of its week
+    let days_since_epoch = after_micros.div_euclid(MICROS_PER_DAY);
+    let week_start = (days_since_epoch - (days_since_epoch + 3).rem_euclid(7)) * MICROS_PER_DAY;
+
+    let mut start = week_start + event.start_day * MICROS_PER_DAY + event.start_hour * MICROS_PER_HOUR;
+    let duration = event.duration_hours * MICROS_PER_HOUR;
+    if start + duration <= after_micros {
+        start += MICROS_PER_WEEK;
+    }
+    let start_at = Timestamp::from_micros_since_unix_epoch(start.max(ctx.timestamp.to_micros_since_unix_epoch()));
+
+    ctx.db.modifier_event_schedule().try_insert(ModifierEventSchedule {
+        id: 0, // Auto-incremented
+        event_index,
+        ends_at: Timestamp::from_micros_since_unix_epoch(start + duration),
+        scheduled_at: ScheduleAt::Time(start_at),
+    }).map_err(|e| format!("Failed to schedule weekly event '{}': {}", event.label, e))?;
+    log::debug!("[Modifiers] Next '{}' starts at {:?}.", event.label, start_at);
+    Ok(())
+}
@@ -0,0 +1,192 @@
+/******************************************************************************
+ *                                                                            *
+ * Connection sessions and the reconnect grace period. A player's session     *
+ * starts when they connect and survives a dropped connection for the         *
+ * configured grace period (`reconnect_grace_secs` in game_config.rs): their  *
+ * character stays awake in the world, and connecting again within the        *
+ * window picks the session up where it left off. Once the grace period runs  *
+ * out, `process_session_grace` takes the player offline, leaving the usual   *
+ * lootable sleeping body, and records the session in session_metric.         *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, TimeDuration, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+
+use crate::player as PlayerTableTrait;
+use crate::session::player_session as PlayerSessionTableTrait;
+use crate::session::session_metric as SessionMetricTableTrait;
+use crate::session::session_grace_schedule as SessionGraceScheduleTableTrait;
+
+// --- Constants ---
+/// Default for `reconnect_grace_secs`.
+pub(crate) const RECONNECT_GRACE_SECS: f32 = 30.0;
+const SESSION_GRACE_CHECK_INTERVAL_SECS: u64 = 5;
+
+/// --- Player Session ---
+/// A player's current session. While `disconnected_at` is set the player has no
+/// connection but is still in the world until `grace_expires_at`.
+#[spacetimedb::table(name = player_session)]
+#[derive(Clone, Debug)]
+pub struct PlayerSession {
+    #[primary_key]
+    pub player_id: Identity,
+    pub started_at: Timestamp,
+    pub reconnects: u32, // Times the player came back within the grace period
+    pub disconnected_at: Option<Timestamp>,
+    pub grace_expires_at: Option<Timestamp>,
+}
+
+/// --- Session Metric ---
+/// A finished session. The duration runs from connecting to the last disconnect, so
+/// the grace period that followed isn't counted.
+#[spacetimedb::table(name = session_metric)]
+#[derive(Clone, Debug)]
+pub struct SessionMetric {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub player_id: Identity,
+    pub started_at: Timestamp,
+    pub ended_at: Timestamp,
+    pub duration_secs: u64,
+    pub reconnects: u32,
+}
+
+#[spacetimedb::table(name = session_grace_schedule, scheduled(process_session_grace))]
+#[derive(Clone, Debug)]
+pub struct SessionGraceSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// Scheduled reducer: takes players whose grace period has run out offline.
+#[spacetimedb::reducer]
+pub fn process_session_grace(ctx: &ReducerContext, _schedule: SessionGraceSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("process_session_grace may only be called by the scheduler.".to_string());
+    }
+    let expired: Vec<PlayerSession> = ctx.db.player_session().iter()
+        .filter(|s| s.grace_expires_at.is_some_and(|expires_at| ctx.timestamp >= expires_at))
+        .collect();
+    for session in expired {
+        finalize_session(ctx, session);
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+pub(crate) fn init_session_grace_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.session_grace_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting session grace schedule (every {}s).", SESSION_GRACE_CHECK_INTERVAL_SECS);
+        let interval = Duration::from_secs(SESSION_GRACE_CHECK_INTERVAL_SECS);
+        schedule_table.try_insert(SessionGraceSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert session grace schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Picks up the player's session if they are reconnecting within the grace period,
+/// otherwise starts a new one. Returns true if an existing session was resumed.
+/// Called from identity_connected.
+pub(crate) fn resume_or_start_session(ctx: &ReducerContext, player_id: Identity) -> bool {
+    let sessions = ctx.db.player_session();
+    match sessions.player_id().find(player_id) {
+        Some(mut session) => {
+            if session.disconnected_at.take().is_some() {
+                session.reconnects += 1;
+                log::info!("[Session] Player {:?} reconnected within the grace period.", player_id);
+            }
+            session.grace_expires_at = None;
+            sessions.player_id().update(session);
+            true
+        }
+        None => {
+            sessions.insert(PlayerSession {
+                player_id,
+                started_at: ctx.timestamp,
+                reconnects: 0,
+                disconnected_at: None,
+                grace_expires_at: None,
+            });
+            false
+        }
+    }
+}
+
+/// Starts the grace period for a player whose connection just dropped, or takes them
+/// offline straight away if the grace period is disabled. Called from identity_disconnected.
+pub(crate) fn begin_disconnect_grace(ctx: &ReducerContext, player_id: Identity) {
+    let sessions = ctx.db.player_session();
+    let Some(mut session) = sessions.player_id().find(player_id) else {
+        // Nothing to hold open, but the player must still go offline
+        log::warn!("[Session] No session found for disconnecting player {:?}; taking them offline now.", player_id);
+        take_player_offline(ctx, player_id);
+        return;
+    };
+    session.disconnected_at = Some(ctx.timestamp);
+
+    let grace_secs = crate::game_config::get_game_config(ctx).reconnect_grace_secs;
+    if grace_secs <= 0.0 {
+        finalize_session(ctx, session);
+        return;
+    }
+    let Ok(grace) = Duration::try_from_secs_f32(grace_secs) else {
+        log::warn!("[Session] Invalid reconnect grace of {}s; taking player {:?} offline now.", grace_secs, player_id);
+        finalize_session(ctx, session);
+        return;
+    };
+    session.grace_expires_at = Some(ctx.timestamp + TimeDuration::from(grace));
+    sessions.player_id().update(session);
+    log::info!("[Session] Player {:?} disconnected; going offline in {:.0}s unless they reconnect.", player_id, grace_secs);
+}
+
+/// Ends the session: records it, adds it to the player's playtime and takes the player
+/// offline, leaving a sleeping body behind.
+fn finalize_session(ctx: &ReducerContext, session: PlayerSession) {
+    let player_id = session.player_id;
+    let ended_at = session.disconnected_at.unwrap_or(ctx.timestamp);
+    ctx.db.player_session().player_id().delete(player_id);
+
+    let duration_micros = ended_at.to_micros_since_unix_epoch() - session.started_at.to_micros_since_unix_epoch();
+    ctx.db.session_metric().insert(SessionMetric {
+        id: 0, // Auto-incremented
+        player_id,
+        started_at: session.started_at,
+        ended_at,
+        duration_secs: (duration_micros.max(0) / 1_000_000) as u64,
+        reconnects: session.reconnects,
+    });
+    crate::kill_feed::end_play_session(ctx, player_id, ended_at);
+    take_player_offline(ctx, player_id);
+}
+
+/// Marks the player offline in the player list and Player table, leaving a sleeping body behind.
+fn take_player_offline(ctx: &ReducerContext, player_id: Identity) {
+    let players = ctx.db.player();
+    if let Some(mut player) = players.identity().find(player_id) {
+        crate::server_info::update_player_list_entry(ctx, player_id, &player.username, false);
+        if player.is_online { // Only update if they were marked online
+            player.is_online = false;
+            crate::sleeping_player::spawn_sleeping_player(ctx, &player);
+            players.identity().update(player);
+            log::info!("[Session] Set player {:?} to offline.", player_id);
+        }
+    } else {
+        log::warn!("[Session] Player {:?} not found in Player table when ending their session.", player_id);
+    }
+}
@@ -0,0 +1,145 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines the Sign entity: a placeable wooden board showing a short message. *
+ * Only the player who placed a sign can change its text with `set_sign_text` *
+ * and the text goes through the chat word filter. Signs live in the public   *
+ * Sign table, streamed to clients whose viewport covers the sign's chunk     *
+ * (see interest_management.rs). They are damaged and destroyed through       *
+ * combat.rs like other placed structures.                                    *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+
+use crate::player as PlayerTableTrait;
+use crate::items::ItemDefinition;
+use crate::environment::calculate_chunk_index;
+use crate::spatial_grid::{self, EntityType};
+use crate::sign::sign as SignTableTrait;
+
+// --- Constants ---
+pub(crate) const SIGN_COLLISION_RADIUS: f32 = 16.0;
+pub(crate) const SIGN_COLLISION_Y_OFFSET: f32 = 0.0;
+/// Players must be this close to a sign to edit it.
+const SIGN_EDIT_DISTANCE_SQUARED: f32 = 96.0 * 96.0;
+const SIGN_MAX_HEALTH: f32 = 150.0;
+pub(crate) const SIGN_ITEM_NAME: &str = "Wooden Sign";
+/// Longest message a sign can show, in characters.
+const MAX_SIGN_TEXT_CHARS: usize = 120;
+const MAX_SIGN_TEXT_LINES: usize = 4;
+
+/// --- Sign Data Structure ---
+/// A placed sign and the message written on it.
+#[spacetimedb::table(name = sign, public)]
+#[derive(Clone, Debug)]
+pub struct Sign {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    #[index(btree)]
+    pub chunk_index: u32,
+    pub placed_by: Identity,
+    pub placed_at: Timestamp,
+    pub text: String, // Empty until the owner writes on it
+    pub text_updated_at: Option<Timestamp>,
+    pub health: f32,
+    pub max_health: f32,
+    pub is_destroyed: bool,
+    pub destroyed_at: Option<Timestamp>,
+    pub last_hit_time: Option<Timestamp>,
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Place Sign ---
+/// Consumes a Wooden Sign from the player's inventory/hotbar and places a blank sign in the world.
+#[spacetimedb::reducer]
+pub fn place_sign(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    crate::placement::place_deployable(ctx, item_instance_id, world_x, world_y, |name| name == SIGN_ITEM_NAME)
+}
+
+/// Creates a blank sign. The placement constructor for Wooden Sign items.
+pub(crate) fn spawn_sign(ctx: &ReducerContext, placed_by: Identity, _item_def: &ItemDefinition, _item_durability: Option<f32>, world_x: f32, world_y: f32) -> Result<(), String> {
+    let inserted = ctx.db.sign().try_insert(Sign {
+        id: 0, // Auto-incremented
+        pos_x: world_x,
+        pos_y: world_y,
+        chunk_index: calculate_chunk_index(world_x, world_y),
+        placed_by,
+        placed_at: ctx.timestamp,
+        text: String::new(),
+        text_updated_at: None,
+        health: SIGN_MAX_HEALTH,
+        max_health: SIGN_MAX_HEALTH,
+        is_destroyed: false,
+        destroyed_at: None,
+        last_hit_time: None,
+    }).map_err(|e| format!("Failed to insert sign entity: {}", e))?;
+    spatial_grid::track_entity(ctx, EntityType::Sign(inserted.id), inserted.pos_x, inserted.pos_y);
+    log::info!("[PlaceSign] Player {:?} placed Sign {} at ({:.1}, {:.1}).", placed_by, inserted.id, world_x, world_y);
+    Ok(())
+}
+
+/// --- Set Sign Text ---
+/// Replaces the message on a sign. Only the player who placed the sign can write on it,
+/// and they must be standing next to it. An empty message clears the sign.
+#[spacetimedb::reducer]
+pub fn set_sign_text(ctx: &ReducerContext, sign_id: u32, text: String) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let sender_id = ctx.sender;
+    let signs = ctx.db.sign();
+
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Player not found".to_string())?;
+    if player.is_dead {
+        return Err("Cannot write on signs while dead.".to_string());
+    }
+    let mut sign = signs.id().find(sign_id)
+        .ok_or_else(|| format!("Sign {} not found.", sign_id))?;
+    if sign.is_destroyed {
+        return Err("This sign has been destroyed.".to_string());
+    }
+    if sign.placed_by != sender_id {
+        return Err("Only the player who placed this sign can change it.".to_string());
+    }
+    let dx = player.position_x - sign.pos_x;
+    let dy = player.position_y - sign.pos_y;
+    if dx * dx + dy * dy > SIGN_EDIT_DISTANCE_SQUARED {
+        return Err("Too far away to write on the sign.".to_string());
+    }
+
+    sign.text = validate_sign_text(ctx, &text)?;
+    sign.text_updated_at = Some(ctx.timestamp);
+    log::info!("[Sign] Player {:?} set the text of sign {} ({} chars).", sender_id, sign_id, sign.text.chars().count());
+    signs.id().update(sign);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Trims the text, checks its length and line count, strips control characters other
+/// than line breaks and runs it through the chat word filter. Returns the text to store.
+fn validate_sign_text(ctx: &ReducerContext, text: &str) -> Result<String, String> {
+    let cleaned: String = text.trim()
+        .chars()
+        .filter(|c| *c == '\n' || !c.is_control())
+        .collect();
+    if cleaned.chars().count() > MAX_SIGN_TEXT_CHARS {
+        return Err(format!("Sign text cannot be longer than {} characters.", MAX_SIGN_TEXT_CHARS));
+    }
+    if cleaned.lines().count() > MAX_SIGN_TEXT_LINES {
+        return Err(format!("Sign text cannot have more than {} lines.", MAX_SIGN_TEXT_LINES));
+    }
+
+    // The word filter works on space-separated words, so filter each line on its own
+    let filtered: Result<Vec<String>, String> = cleaned.lines()
+        .map(|line| crate::chat::apply_word_filter(ctx, line))
+        .collect();
+    Ok(filtered?.join("\n"))
+}
@@ -0,0 +1,131 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines player skills (Woodcutting, Mining, Crafting, Combat). Players     *
+ * earn XP from gathering in combat.rs and finishing crafts in                *
+ * crafting_queue.rs; each level grants a small server-side bonus (extra      *
+ * gather yield, faster crafting, extra combat damage).                       *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, log};
+
+use crate::models::TargetType;
+use crate::skills::player_skill as PlayerSkillTableTrait;
+
+// --- Constants ---
+pub const MAX_SKILL_LEVEL: u32 = 20;
+/// XP needed for level L is `XP_CURVE_FACTOR * L * (L - 1)` (100 for level 2, 300 for 3, 600 for 4, ...).
+const XP_CURVE_FACTOR: u64 = 50;
+/// Woodcutting/Mining XP earned per unit of resource gathered.
+pub const XP_PER_RESOURCE_GATHERED: u64 = 1;
+pub const XP_PER_CRAFT_COMPLETED: u64 = 10;
+pub const XP_PER_COMBAT_HIT: u64 = 10;
+/// Extra gather yield per level above 1 (+2% per level).
+const YIELD_BONUS_PER_LEVEL: f32 = 0.02;
+/// Craft time reduction per level above 1 (2% per level).
+const CRAFT_TIME_REDUCTION_PER_LEVEL: f32 = 0.02;
+/// Extra damage against players and animals per level above 1 (+1% per level).
+const COMBAT_DAMAGE_BONUS_PER_LEVEL: f32 = 0.01;
+
+#[derive(SpacetimeType, Copy, Clone, Debug, PartialEq)]
+pub enum SkillType {
+    Woodcutting,
+    Mining,
+    Crafting,
+    Combat,
+}
+
+/// --- Player Skill Data Structure ---
+/// One row per player per skill, created the first time the player earns XP in it.
+#[spacetimedb::table(name = player_skill, public)]
+#[derive(Clone, Debug)]
+pub struct PlayerSkill {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub player_id: Identity,
+    pub skill: SkillType,
+    pub xp: u64,
+    pub level: u32,
+}
+
+/// Returns the total XP required to reach `level`.
+pub fn xp_for_level(level: u32) -> u64 {
+    let level = level as u64;
+    XP_CURVE_FACTOR * level * level.saturating_sub(1)
+}
+
+/// Returns the level reached with `xp` total experience, capped at `MAX_SKILL_LEVEL`.
+pub fn level_for_xp(xp: u64) -> u32 {
+    let mut level = 1;
+    while level < MAX_SKILL_LEVEL && xp >= xp_for_level(level + 1) {
+        level += 1;
+    }
+    level
+}
+
+/// Returns the player's level in a skill (1 if they have never trained it).
+pub fn get_skill_level(ctx: &ReducerContext, player_id: Identity, skill: SkillType) -> u32 {
+    ctx.db.player_skill().player_id().filter(player_id)
+        .find(|row| row.skill == skill)
+        .map_or(1, |row| row.level)
+}
+
+/// Adds XP to one of the player's skills, creating the row if needed and logging level-ups.
+pub fn award_xp(ctx: &ReducerContext, player_id: Identity, skill: SkillType, amount: u64) {
+    if amount == 0 {
+        return;
+    }
+    let skills = ctx.db.player_skill();
+    let existing = skills.player_id().filter(player_id).find(|row| row.skill == skill);
+
+    let mut row = existing.clone().unwrap_or(PlayerSkill {
+        id: 0, // Auto-incremented
+        player_id,
+        skill,
+        xp: 0,
+        level: 1,
+    });
+    let old_level = row.level;
+    row.xp = row.xp.saturating_add(amount);
+    row.level = level_for_xp(row.xp);
+
+    if row.level > old_level {
+        log::info!("[Skills] Player {:?} reached {:?} level {}.", player_id, skill, row.level);
+    }
+    if existing.is_some() {
+        skills.id().update(row);
+    } else {
+        skills.insert(row);
+    }
+}
+
+/// Maps a gather target to the skill it trains, if any.
+pub fn gathering_skill_for_target(target_type: TargetType) -> Option<SkillType> {
+    match target_type {
+        TargetType::Tree | TargetType::TreeStump => Some(SkillType::Woodcutting),
+        TargetType::Stone => Some(SkillType::Mining),
+        _ => None,
+    }
+}
+
+/// Fractional yield bonus for gathering from `target_type` (0.0 at level 1).
+pub fn get_yield_bonus(ctx: &ReducerContext, player_id: Identity, target_type: TargetType) -> f32 {
+    match gathering_skill_for_target(target_type) {
+        Some(skill) => (get_skill_level(ctx, player_id, skill) - 1) as f32 * YIELD_BONUS_PER_LEVEL,
+        None => 0.0,
+    }
+}
+
+/// Damage multiplier applied to attacks against players and animals (1.0 at level 1).
+pub fn get_combat_damage_multiplier(ctx: &ReducerContext, player_id: Identity) -> f32 {
+    1.0 + (get_skill_level(ctx, player_id, SkillType::Combat) - 1) as f32 * COMBAT_DAMAGE_BONUS_PER_LEVEL
+}
+
+/// Returns the craft time in milliseconds after the player's Crafting level reduction.
+pub fn get_adjusted_craft_time_ms(ctx: &ReducerContext, player_id: Identity, base_secs: u32) -> u64 {
+    let level = get_skill_level(ctx, player_id, SkillType::Crafting);
+    let reduction = (level - 1) as f32 * CRAFT_TIME_REDUCTION_PER_LEVEL;
+    (base_secs as f32 * 1000.0 * (1.0 - reduction)).round() as u64
+}
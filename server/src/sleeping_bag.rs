@@ -2,23 +2,37 @@
  *                                                                            *
  * Defines the SleepingBag entity, its data structure, and associated logic.  *
  * Handles placing the sleeping bag, interaction checks, and picking it up.   *
+ * Dead players can respawn at their own bags; each bag has a cooldown after  *
+ * use, must not be obstructed, and players have a limited number of bags.    *
  *                                                                            *
  ******************************************************************************/
 
-use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, TimeDuration};
 use log;
+use std::time::Duration;
 
 // --- Constants --- 
 pub(crate) const SLEEPING_BAG_COLLISION_RADIUS: f32 = 18.0; // Width approx 36
 pub(crate) const SLEEPING_BAG_COLLISION_Y_OFFSET: f32 = 5.0; // Low profile
 pub(crate) const PLAYER_SLEEPING_BAG_COLLISION_DISTANCE_SQUARED: f32 = (super::PLAYER_RADIUS + SLEEPING_BAG_COLLISION_RADIUS) * (super::PLAYER_RADIUS + SLEEPING_BAG_COLLISION_RADIUS);
-const SLEEPING_BAG_INTERACTION_DISTANCE_SQUARED: f32 = 64.0 * 64.0; // Same as box/campfire
-pub(crate) const SLEEPING_BAG_SLEEPING_BAG_COLLISION_DISTANCE_SQUARED: f32 = (SLEEPING_BAG_COLLISION_RADIUS * 2.0) * (SLEEPING_BAG_COLLISION_RADIUS * 2.0);
-const PLACEMENT_RANGE_SQ: f32 = 96.0 * 96.0; // Standard placement range
+pub(crate) const SLEEPING_BAG_INTERACTION_DISTANCE_SQUARED: f32 = 64.0 * 64.0; // Same as box/campfire
+/// Placing a bag beyond this count destroys the player's oldest bag.
+pub(crate) const MAX_SLEEPING_BAGS_PER_PLAYER: usize = 10;
+/// How long a bag is unavailable after it was used to respawn.
+pub(crate) const SLEEPING_BAG_RESPAWN_COOLDOWN_SECS: u64 = 300; // 5 minutes
 
 // --- Import Dependencies ---
 use crate::environment::calculate_chunk_index;
 use crate::sleeping_bag::sleeping_bag as SleepingBagTableTrait; // Import self trait
+use crate::spatial_grid::{self, EntityType};
+use crate::building::{BUILDING_COLLISION_Y_OFFSET, building_piece as BuildingPieceTableTrait};
+use crate::campfire::{CAMPFIRE_COLLISION_RADIUS, CAMPFIRE_COLLISION_Y_OFFSET, campfire as CampfireTableTrait};
+use crate::wooden_storage_box::{BOX_COLLISION_RADIUS, BOX_COLLISION_Y_OFFSET, wooden_storage_box as WoodenStorageBoxTableTrait};
+use crate::furnace::{FURNACE_COLLISION_RADIUS, FURNACE_COLLISION_Y_OFFSET, furnace as FurnaceTableTrait};
+use crate::workbench::{WORKBENCH_COLLISION_RADIUS, WORKBENCH_COLLISION_Y_OFFSET, workbench as WorkbenchTableTrait};
+use crate::recycler::{RECYCLER_COLLISION_RADIUS, RECYCLER_COLLISION_Y_OFFSET, recycler as RecyclerTableTrait};
+use crate::flame_turret::{FLAME_TURRET_COLLISION_RADIUS, FLAME_TURRET_COLLISION_Y_OFFSET, flame_turret as FlameTurretTableTrait};
+use crate::large_storage_chest::{LARGE_CHEST_COLLISION_RADIUS, LARGE_CHEST_COLLISION_Y_OFFSET, large_storage_chest as LargeStorageChestTableTrait};
 use crate::Player; // Import Player struct directly from crate root
 use crate::player as PlayerTableTrait; // Import the trait for ctx.db.player()
 use crate::items::{
@@ -45,11 +59,12 @@ pub struct SleepingBag {
 
     pub pos_x: f32,
     pub pos_y: f32,
+    #[index(btree)]
     pub chunk_index: u32, 
 
     pub placed_by: Identity, // Who placed this sleeping bag
     pub placed_at: Timestamp, // When it was placed
-    // Add future fields here (e.g., is_occupied, owner_identity for respawn)
+    pub respawn_available_at: Option<Timestamp>, // Set after each respawn; None = ready
 
     // --- Destruction Fields ---
     pub health: f32,
@@ -73,82 +88,38 @@ pub struct SleepingBag {
 /// Places a sleeping bag from the player's inventory into the world.
 #[spacetimedb::reducer]
 pub fn place_sleeping_bag(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
-    let sender_id = ctx.sender;
-    let inventory_items = ctx.db.inventory_item();
-    let item_defs = ctx.db.item_definition();
-    let players = ctx.db.player();
-    let sleeping_bags = ctx.db.sleeping_bag(); 
-
-    log::info!(
-        "[PlaceSleepingBag] Player {:?} attempting placement of item {} at ({:.1}, {:.1})",
-        sender_id, item_instance_id, world_x, world_y
-    );
-
-    // 1. Find the 'Sleeping Bag' Item Definition ID
-    let bag_def_id = item_defs.iter()
-        .find(|def| def.name == "Sleeping Bag")
-        .map(|def| def.id)
-        .ok_or_else(|| "Sleeping Bag definition not found.".to_string())?;
-
-    // 2. Find the specific item instance and validate
-    let item_to_consume = inventory_items.instance_id().find(item_instance_id)
-        .ok_or_else(|| format!("Item instance {} not found.", item_instance_id))?;
-    
-    // Validate ownership and location
-    let is_owned_and_in_player_slots = match &item_to_consume.location {
-        ItemLocation::Inventory(crate::models::InventoryLocationData { owner_id, .. }) => *owner_id == sender_id,
-        ItemLocation::Hotbar(crate::models::HotbarLocationData { owner_id, .. }) => *owner_id == sender_id,
-        _ => false,
-    };
-
-    if !is_owned_and_in_player_slots {
-        return Err(format!(
-            "Item instance {} must be in your inventory or hotbar to be placed. Location: {:?}", 
-            item_instance_id, item_to_consume.location
-        ));
-    }
-
-    if item_to_consume.item_def_id != bag_def_id {
-        return Err(format!("Item instance {} is not a Sleeping Bag.", item_instance_id));
-    }
+    crate::placement::place_deployable(ctx, item_instance_id, world_x, world_y, |name| name == "Sleeping Bag")
+}
 
-    // 3. Validate Placement Distance
-    if let Some(player) = players.identity().find(sender_id) {
-        let dx = player.position_x - world_x;
-        let dy = player.position_y - world_y;
-        if (dx * dx + dy * dy) > PLACEMENT_RANGE_SQ {
-            return Err("Placement location is too far away.".to_string());
-        }
-    } else {
-        return Err("Could not find player data.".to_string());
-    }
+/// Creates a sleeping bag, destroying the player's oldest bags if this one would put them
+/// over MAX_SLEEPING_BAGS_PER_PLAYER. The placement constructor for Sleeping Bag items.
+pub(crate) fn spawn_sleeping_bag(ctx: &ReducerContext, placed_by: Identity, _item_def: &ItemDefinition, _item_durability: Option<f32>, world_x: f32, world_y: f32) -> Result<(), String> {
+    let sleeping_bags = ctx.db.sleeping_bag();
 
-    // 4. Validate Collision with other Sleeping Bags
-    for other_bag in sleeping_bags.iter() {
-        let dx = world_x - other_bag.pos_x;
-        let dy = world_y - other_bag.pos_y;
-        if (dx * dx + dy * dy) < SLEEPING_BAG_SLEEPING_BAG_COLLISION_DISTANCE_SQUARED {
-            return Err("Cannot place sleeping bag too close to another.".to_string());
-        }
+    // Enforce the per-player bag limit by destroying the oldest bags
+    let mut owned_bags: Vec<SleepingBag> = sleeping_bags.iter()
+        .filter(|bag| bag.placed_by == placed_by)
+        .collect();
+    owned_bags.sort_by_key(|bag| bag.placed_at);
+    let excess = (owned_bags.len() + 1).saturating_sub(MAX_SLEEPING_BAGS_PER_PLAYER);
+    for old_bag in owned_bags.iter().take(excess) {
+        sleeping_bags.id().delete(old_bag.id);
+        crate::spatial_grid::untrack_entity(ctx, crate::spatial_grid::EntityType::SleepingBag(old_bag.id));
+        log::info!(
+            "[PlaceSleepingBag] Player {:?} is over the limit of {} bags. Destroyed oldest bag {}.",
+            placed_by, MAX_SLEEPING_BAGS_PER_PLAYER, old_bag.id
+        );
     }
-    // TODO: Add collision checks against other entities if needed (trees, stones, boxes, etc.)
 
-    // 5. Consume the Item
-    log::info!(
-        "[PlaceSleepingBag] Consuming item instance {} from player {:?}",
-        item_instance_id, sender_id
-    );
-    inventory_items.instance_id().delete(item_instance_id);
-
-    // 6. Create the SleepingBag Entity
     let chunk_idx = calculate_chunk_index(world_x, world_y);
     let new_bag = SleepingBag {
         id: 0, // Auto-incremented
         pos_x: world_x,
         pos_y: world_y,
         chunk_index: chunk_idx,
-        placed_by: sender_id,
+        placed_by,
         placed_at: ctx.timestamp,
+        respawn_available_at: None,
         // --- Destruction Fields Initialization ---
         health: 250.0,
         max_health: 250.0,
@@ -156,13 +127,13 @@ pub fn place_sleeping_bag(ctx: &ReducerContext, item_instance_id: u64, world_x:
         destroyed_at: None,
         last_hit_time: None,
     };
-    sleeping_bags.insert(new_bag);
+    let inserted_bag = sleeping_bags.insert(new_bag);
+    crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::SleepingBag(inserted_bag.id), world_x, world_y);
 
     log::info!(
         "[PlaceSleepingBag] Successfully placed Sleeping Bag at ({:.1}, {:.1}) by {:?}",
-        world_x, world_y, sender_id
+        world_x, world_y, placed_by
     );
-
     Ok(())
 }
 
@@ -197,6 +168,21 @@ pub fn respawn_at_sleeping_bag(ctx: &ReducerContext, bag_id: u32) -> Result<(),
     if sleeping_bag.placed_by != sender_id {
         return Err("Cannot respawn at a sleeping bag you didn't place.".to_string());
     }
+    if sleeping_bag.is_destroyed {
+        return Err(format!("Sleeping Bag {} is destroyed.", bag_id));
+    }
+
+    // 3b. Check the bag's cooldown and that nothing has been built on top of it
+    if let Some(available_at) = sleeping_bag.respawn_available_at {
+        if ctx.timestamp < available_at {
+            let remaining_secs = available_at.to_micros_since_unix_epoch()
+                .saturating_sub(ctx.timestamp.to_micros_since_unix_epoch()) / 1_000_000;
+            return Err(format!("Sleeping Bag {} is on cooldown for {} more seconds.", bag_id, remaining_secs + 1));
+        }
+    }
+    if is_sleeping_bag_blocked(ctx, &sleeping_bag) {
+        return Err(format!("Sleeping Bag {} is obstructed.", bag_id));
+    }
 
     log::info!(
         "Respawning player {} ({:?}) at sleeping bag {}. Clearing inventory and crafting queue...", 
@@ -238,6 +224,9 @@ pub fn respawn_at_sleeping_bag(ctx: &ReducerContext, bag_id: u32) -> Result<(),
             item_def_id: rock_def.id,
             quantity: 1,
             location: rock_location,
+            durability: None,
+            is_locked: false,
+            charges: None,
         }) {
             Ok(_) => log::info!("Granted 1 Rock (slot 0) to player {}", player.username),
             Err(e) => log::error!("Failed to grant starting Rock to player {}: {}", player.username, e),
@@ -257,12 +246,15 @@ pub fn respawn_at_sleeping_bag(ctx: &ReducerContext, bag_id: u32) -> Result<(),
                 item_def_id: torch_def.id,
                 quantity: 1,
                 location: torch_location, // Attempt hotbar slot 1
+                durability: None,
+                is_locked: false,
+                charges: None,
             }) {
                 Ok(_) => log::info!("Granted 1 Torch (slot 1) to player {}", player.username),
                 Err(_e) => {
                      log::warn!("Hotbar slot 1 occupied, granting Torch to general inventory for player {}.", player.username);
                      // Fallback to general inventory add if hotbar slot is taken or try_insert fails for other reasons
-                     match add_item_to_player_inventory(ctx, sender_id, torch_def.id, 1) {
+                     match crate::overflow_backpack::add_item_to_player_inventory_or_overflow(ctx, sender_id, torch_def.id, 1) {
                         Ok(Some(new_torch_instance_id)) => {
                             log::info!("Granted 1 Torch (ID: {}) to player {} (inventory fallback).", new_torch_instance_id, player.username);
                         }
@@ -284,9 +276,10 @@ pub fn respawn_at_sleeping_bag(ctx: &ReducerContext, bag_id: u32) -> Result<(),
 
     // 4. Respawn Player at Bag Location (Reset stats)
     player.is_dead = false;
+    crate::spectator::end_spectating(ctx, sender_id);
     player.health = crate::player_stats::PLAYER_MAX_HEALTH; // Use fully qualified path
-    player.position_x = sleeping_bag.pos_x;
-    player.position_y = sleeping_bag.pos_y;
+    player.set_position(sleeping_bag.pos_x, sleeping_bag.pos_y);
+    crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::Player(player.identity), sleeping_bag.pos_x, sleeping_bag.pos_y);
     player.death_timestamp = None; // Clear death timestamp
     // Reset other stats like in respawn_randomly
     player.hunger = 100.0;
@@ -294,6 +287,7 @@ pub fn respawn_at_sleeping_bag(ctx: &ReducerContext, bag_id: u32) -> Result<(),
     player.warmth = 100.0;
     player.stamina = 100.0;
     player.jump_start_time_ms = 0;
+    player.dodge_start_time_ms = 0;
     player.is_sprinting = false;
     player.last_hit_time = None;
     // Update timestamps
@@ -301,6 +295,12 @@ pub fn respawn_at_sleeping_bag(ctx: &ReducerContext, bag_id: u32) -> Result<(),
     player.last_stat_update = ctx.timestamp;
 
     players.identity().update(player);
+    crate::active_effects::apply_spawn_protection(ctx, sender_id);
+
+    sleeping_bags.id().update(SleepingBag {
+        respawn_available_at: Some(ctx.timestamp + TimeDuration::from(Duration::from_secs_f32(crate::game_config::get_game_config(ctx).sleeping_bag_respawn_cooldown_secs))),
+        ..sleeping_bag.clone()
+    });
 
     // Clear equipped items (this should ideally happen before corpse creation if items are to be moved to it)
     // If create_corpse_for_player handles un-equipping, these might be redundant or need careful ordering.
@@ -325,6 +325,7 @@ pub fn respawn_at_sleeping_bag(ctx: &ReducerContext, bag_id: u32) -> Result<(),
 /// Basic interaction check (currently just distance).
 #[spacetimedb::reducer]
 pub fn interact_with_sleeping_bag(ctx: &ReducerContext, bag_id: u32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
     validate_sleeping_bag_interaction(ctx, bag_id)?; // Use helper for validation
     log::debug!("Player {:?} interaction check OK for sleeping bag {}", ctx.sender, bag_id);
     // Currently no action on interact, but check succeeds if close enough.
@@ -361,4 +362,37 @@ fn validate_sleeping_bag_interaction(
         return Err("Too far away".to_string());
     }
     Ok((player, sleeping_bag))
-}
\ No newline at end of file
+}
+/// --- Sleeping Bag Obstruction Check ---
+/// True if a wall, closed door or placed structure now overlaps the spot a player would respawn on.
+fn is_sleeping_bag_blocked(ctx: &ReducerContext, bag: &SleepingBag) -> bool {
+    let overlaps = |pos_x: f32, center_y: f32, radius: f32| {
+        let dx = bag.pos_x - pos_x;
+        let dy = bag.pos_y - center_y;
+        let min_dist = crate::PLAYER_RADIUS + radius;
+        (dx * dx + dy * dy) < min_dist * min_dist
+    };
+
+    let search_radius = crate::PLAYER_RADIUS + LARGE_CHEST_COLLISION_RADIUS;
+    spatial_grid::get_entities_in_radius(ctx, bag.pos_x, bag.pos_y, search_radius)
+        .into_iter()
+        .any(|entity| match entity {
+            EntityType::BuildingPiece(id) => ctx.db.building_piece().id().find(id)
+                .is_some_and(|p| p.is_solid() && overlaps(p.pos_x, p.pos_y - BUILDING_COLLISION_Y_OFFSET, p.collision_radius())),
+            EntityType::Campfire(id) => ctx.db.campfire().id().find(id)
+                .is_some_and(|c| !c.is_destroyed && overlaps(c.pos_x, c.pos_y - CAMPFIRE_COLLISION_Y_OFFSET, CAMPFIRE_COLLISION_RADIUS)),
+            EntityType::WoodenStorageBox(id) => ctx.db.wooden_storage_box().id().find(id)
+                .is_some_and(|b| !b.is_destroyed && overlaps(b.pos_x, b.pos_y - BOX_COLLISION_Y_OFFSET, BOX_COLLISION_RADIUS)),
+            EntityType::Furnace(id) => ctx.db.furnace().id().find(id)
+                .is_some_and(|f| !f.is_destroyed && overlaps(f.pos_x, f.pos_y - FURNACE_COLLISION_Y_OFFSET, FURNACE_COLLISION_RADIUS)),
+            EntityType::LargeStorageChest(id) => ctx.db.large_storage_chest().id().find(id)
+                .is_some_and(|c| !c.is_destroyed && overlaps(c.pos_x, c.pos_y - LARGE_CHEST_COLLISION_Y_OFFSET, LARGE_CHEST_COLLISION_RADIUS)),
+            EntityType::Workbench(id) => ctx.db.workbench().id().find(id)
+                .is_some_and(|w| !w.is_destroyed && overlaps(w.pos_x, w.pos_y - WORKBENCH_COLLISION_Y_OFFSET, WORKBENCH_COLLISION_RADIUS)),
+            EntityType::Recycler(id) => ctx.db.recycler().id().find(id)
+                .is_some_and(|r| !r.is_destroyed && overlaps(r.pos_x, r.pos_y - RECYCLER_COLLISION_Y_OFFSET, RECYCLER_COLLISION_RADIUS)),
+            EntityType::FlameTurret(id) => ctx.db.flame_turret().id().find(id)
+                .is_some_and(|t| !t.is_destroyed && overlaps(t.pos_x, t.pos_y - FLAME_TURRET_COLLISION_Y_OFFSET, FLAME_TURRET_COLLISION_RADIUS)),
+            _ => false,
+        })
+}
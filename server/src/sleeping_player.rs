@@ -0,0 +1,83 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines sleeping player bodies. When a living player disconnects, a        *
+ * SleepingPlayer is spawned where they stood so other players can still see  *
+ * and attack them. Killing a sleeper kills the offline player, which leaves  *
+ * the usual corpse with their full inventory. When the player logs back in,  *
+ * the body is removed and their position and health are restored from it.    *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+
+use crate::Player;
+use crate::player as PlayerTableTrait;
+use crate::environment::calculate_chunk_index;
+use crate::sleeping_player::sleeping_player as SleepingPlayerTableTrait;
+
+/// --- Sleeping Player Data Structure ---
+/// The body an offline player leaves behind in the world.
+#[spacetimedb::table(name = sleeping_player, public)]
+#[derive(Clone, Debug)]
+pub struct SleepingPlayer {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+    #[unique]
+    pub player_identity: Identity,
+    pub username: String,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    #[index(btree)]
+    pub chunk_index: u32,
+    pub health: f32,
+    pub fell_asleep_at: Timestamp,
+    pub last_hit_time: Option<Timestamp>,
+}
+
+/// Spawns a sleeping body for a player who just went offline. Dead players don't leave one.
+pub(crate) fn spawn_sleeping_player(ctx: &ReducerContext, player: &Player) {
+    if player.is_dead {
+        return;
+    }
+    let sleepers = ctx.db.sleeping_player();
+    if sleepers.player_identity().find(player.identity).is_some() {
+        return;
+    }
+    sleepers.insert(SleepingPlayer {
+        id: 0, // Auto-incremented
+        player_identity: player.identity,
+        username: player.username.clone(),
+        pos_x: player.position_x,
+        pos_y: player.position_y,
+        chunk_index: calculate_chunk_index(player.position_x, player.position_y),
+        health: player.health,
+        fell_asleep_at: ctx.timestamp,
+        last_hit_time: None,
+    });
+    log::info!("[Sleeper] Player {} ({:?}) is now sleeping at ({:.1}, {:.1}).",
+             player.username, player.identity, player.position_x, player.position_y);
+}
+
+/// Removes the player's sleeping body on reconnect and restores their position and health from it.
+/// If the body was killed while they were away, the player row is already marked dead.
+pub(crate) fn wake_sleeping_player(ctx: &ReducerContext, player_id: Identity) {
+    let sleepers = ctx.db.sleeping_player();
+    let sleeper = match sleepers.player_identity().find(player_id) {
+        Some(sleeper) => sleeper,
+        None => return,
+    };
+    sleepers.id().delete(sleeper.id);
+
+    let players = ctx.db.player();
+    if let Some(mut player) = players.identity().find(player_id) {
+        if !player.is_dead {
+            player.set_position(sleeper.pos_x, sleeper.pos_y);
+            crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::Player(player_id), sleeper.pos_x, sleeper.pos_y);
+            player.health = sleeper.health;
+            player.last_update = ctx.timestamp;
+            players.identity().update(player);
+        }
+    }
+    log::info!("[Sleeper] Player {:?} woke up.", player_id);
+}
@@ -4,21 +4,24 @@
  * Purpose: Implements a spatial partitioning system to optimize collision detection
  * by only checking entities that are close to each other.
  *
+ * The grid is persisted in the `spatial_grid_entry` table (one row per entity, indexed
+ * by cell) and updated incrementally: call `track_entity` whenever an entity is
+ * spawned or moves, and `untrack_entity` when it is deleted. Lookups only read the
+ * handful of cells around a point, so their cost does not grow with world size.
+ *
+ * Entries only record position. Callers resolve each entry to its row and apply their
+ * own liveness checks (dead players, depleted trees, destroyed structures, open doors).
+ *
  * Benefits:
  *   - Reduces collision checks from O(n²) to O(n)
  *   - Significantly improves performance with multiple players/entities
  *   - Scales better as the world gets more populated
  */
 
-use spacetimedb::Identity;
-use spacetimedb::Table;
-use std::collections::HashMap;
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table};
 
 // Importing constants from parent module
-use crate::{
-    WORLD_WIDTH_PX, WORLD_HEIGHT_PX, 
-    PLAYER_RADIUS, WORLD_WIDTH_TILES, WORLD_HEIGHT_TILES
-};
+use crate::{WORLD_WIDTH_PX, WORLD_HEIGHT_PX, PLAYER_RADIUS};
 
 // Import table traits for entities with positions
 use crate::player as PlayerTableTrait;
@@ -28,6 +31,26 @@ use crate::campfire::campfire as CampfireTableTrait;
 use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
 use crate::mushroom::mushroom as MushroomTableTrait;
 use crate::dropped_item::dropped_item as DroppedItemTableTrait;
+use crate::building::building_piece as BuildingPieceTableTrait;
+use crate::furnace::furnace as FurnaceTableTrait;
+use crate::animals::wild_animal as WildAnimalTableTrait;
+use crate::large_storage_chest::large_storage_chest as LargeStorageChestTableTrait;
+use crate::loot_crate::loot_crate as LootCrateTableTrait;
+use crate::workbench::workbench as WorkbenchTableTrait;
+use crate::recycler::recycler as RecyclerTableTrait;
+use crate::flame_turret::flame_turret as FlameTurretTableTrait;
+use crate::player_corpse::player_corpse as PlayerCorpseTableTrait;
+use crate::standing_torch::standing_torch as StandingTorchTableTrait;
+use crate::research::research_table as ResearchTableTableTrait;
+use crate::stash::stash as StashTableTrait;
+use crate::sleeping_bag::sleeping_bag as SleepingBagTableTrait;
+use crate::sign::sign as SignTableTrait;
+use crate::wiring::wiring_switch as WiringSwitchTableTrait;
+use crate::tree_felling::tree_stump as TreeStumpTableTrait;
+use crate::vending_machine::vending_machine as VendingMachineTableTrait;
+use crate::mount::mount as MountTableTrait;
+use crate::environment_hazard::environment_hazard as EnvironmentHazardTableTrait;
+use crate::spatial_grid::spatial_grid_entry as SpatialGridEntryTableTrait;
 
 // Cell size should be larger than the largest collision radius to ensure
 // we only need to check adjacent cells. We use 4x the player radius as a safe default.
@@ -43,7 +66,7 @@ pub fn grid_height() -> usize {
 }
 
 // Entities supported by the spatial grid
-#[derive(Debug, Clone, Copy)]
+#[derive(SpacetimeType, Debug, Clone, Copy, PartialEq)]
 pub enum EntityType {
     Player(Identity),
     Tree(u64),
@@ -52,156 +75,236 @@ pub enum EntityType {
     WoodenStorageBox(u32),
     Mushroom(u32),
     DroppedItem(u64),
+    BuildingPiece(u32),
+    Furnace(u32),
+    Animal(u64),
+    LargeStorageChest(u32),
+    LootCrate(u64),
+    Workbench(u32),
+    Recycler(u32),
+    PlayerCorpse(u32),
+    StandingTorch(u32),
+    ResearchTable(u32),
+    Stash(u32),
+    SleepingBag(u32),
+    Sign(u32),
+    TreeStump(u64),
+    VendingMachine(u32),
+    Mount(u64),
+    Switch(u32),
+    EnvironmentHazard(u64),
+    FlameTurret(u32),
 }
 
-// Grid cell that stores entities
-#[derive(Debug, Default)]
-pub struct GridCell {
-    pub entities: Vec<EntityType>,
+impl EntityType {
+    /// Stable key identifying the entity's row in the grid table.
+    fn grid_key(&self) -> String {
+        match self {
+            EntityType::Player(id) => format!("player:{}", id.to_hex()),
+            EntityType::Tree(id) => format!("tree:{}", id),
+            EntityType::Stone(id) => format!("stone:{}", id),
+            EntityType::Campfire(id) => format!("campfire:{}", id),
+            EntityType::WoodenStorageBox(id) => format!("box:{}", id),
+            EntityType::Mushroom(id) => format!("mushroom:{}", id),
+            EntityType::DroppedItem(id) => format!("dropped_item:{}", id),
+            EntityType::BuildingPiece(id) => format!("building_piece:{}", id),
+            EntityType::Furnace(id) => format!("furnace:{}", id),
+            EntityType::Animal(id) => format!("animal:{}", id),
+            EntityType::LargeStorageChest(id) => format!("large_chest:{}", id),
+            EntityType::LootCrate(id) => format!("loot_crate:{}", id),
+            EntityType::Workbench(id) => format!("workbench:{}", id),
+            EntityType::Recycler(id) => format!("recycler:{}", id),
+            EntityType::PlayerCorpse(id) => format!("corpse:{}", id),
+            EntityType::StandingTorch(id) => format!("standing_torch:{}", id),
+            EntityType::ResearchTable(id) => format!("research_table:{}", id),
+            EntityType::Stash(id) => format!("stash:{}", id),
+            EntityType::SleepingBag(id) => format!("sleeping_bag:{}", id),
+            EntityType::Sign(id) => format!("sign:{}", id),
+            EntityType::TreeStump(id) => format!("tree_stump:{}", id),
+            EntityType::VendingMachine(id) => format!("vending_machine:{}", id),
+            EntityType::Mount(id) => format!("mount:{}", id),
+            EntityType::Switch(id) => format!("switch:{}", id),
+            EntityType::EnvironmentHazard(id) => format!("hazard:{}", id),
+            EntityType::FlameTurret(id) => format!("flame_turret:{}", id),
+        }
+    }
 }
 
-// The spatial grid containing all cells
-#[derive(Debug)]
-pub struct SpatialGrid {
-    cells: Vec<GridCell>,
-    width: usize,
-    height: usize,
+/// --- Spatial Grid Entry ---
+/// The grid cell an entity currently occupies. Server-side only.
+#[spacetimedb::table(name = spatial_grid_entry)]
+#[derive(Clone, Debug)]
+pub struct SpatialGridEntry {
+    #[primary_key]
+    pub entity_key: String,
+    #[index(btree)]
+    pub cell_index: u32,
+    pub entity: EntityType,
 }
 
-impl SpatialGrid {
-    // Create a new empty spatial grid
-    pub fn new() -> Self {
-        let width = grid_width();
-        let height = grid_height();
-        let mut cells = Vec::with_capacity(width * height);
-        for _ in 0..(width * height) {
-            cells.push(GridCell { entities: Vec::new() });
-        }
-        SpatialGrid { cells, width, height }
+// Get the cell index for a given world position
+pub fn get_cell_index(x: f32, y: f32) -> Option<u32> {
+    if x < 0.0 || y < 0.0 || x >= WORLD_WIDTH_PX || y >= WORLD_HEIGHT_PX {
+        return None;
     }
 
-    // Get the cell index for a given world position
-    pub fn get_cell_index(&self, x: f32, y: f32) -> Option<usize> {
-        if x < 0.0 || y < 0.0 || x >= WORLD_WIDTH_PX || y >= WORLD_HEIGHT_PX {
-            return None;
-        }
-        
-        let cell_x = (x / GRID_CELL_SIZE) as usize;
-        let cell_y = (y / GRID_CELL_SIZE) as usize;
-        
-        // Bounds check
-        if cell_x >= self.width || cell_y >= self.height {
-            return None;
-        }
-        
-        Some(cell_y * self.width + cell_x)
-    }
-    
-    // Clear all cells
-    pub fn clear(&mut self) {
-        for cell in &mut self.cells {
-            cell.entities.clear();
-        }
+    let cell_x = (x / GRID_CELL_SIZE) as usize;
+    let cell_y = (y / GRID_CELL_SIZE) as usize;
+
+    // Bounds check
+    if cell_x >= grid_width() || cell_y >= grid_height() {
+        return None;
     }
-    
-    // Add an entity to the appropriate cell
-    pub fn add_entity(&mut self, entity_type: EntityType, x: f32, y: f32) {
-        if let Some(index) = self.get_cell_index(x, y) {
-            self.cells[index].entities.push(entity_type);
+
+    Some((cell_y * grid_width() + cell_x) as u32)
+}
+
+/// Records the entity at its current position. Call on spawn and after every move;
+/// entries are only rewritten when the entity changes cell.
+pub fn track_entity(ctx: &ReducerContext, entity: EntityType, x: f32, y: f32) {
+    let entries = ctx.db.spatial_grid_entry();
+    let key = entity.grid_key();
+    let Some(cell_index) = get_cell_index(x, y) else {
+        entries.entity_key().delete(&key);
+        return;
+    };
+
+    match entries.entity_key().find(&key) {
+        Some(entry) if entry.cell_index == cell_index => {}
+        Some(mut entry) => {
+            entry.cell_index = cell_index;
+            entries.entity_key().update(entry);
         }
-    }
-    
-    // Get all entities in the cell containing the given position
-    pub fn get_entities_at(&self, x: f32, y: f32) -> &[EntityType] {
-        if let Some(index) = self.get_cell_index(x, y) {
-            &self.cells[index].entities
-        } else {
-            &[]
+        None => {
+            entries.insert(SpatialGridEntry { entity_key: key, cell_index, entity });
         }
     }
-    
-    // Get all entities in the cell and neighboring cells
-    pub fn get_entities_in_range(&self, x: f32, y: f32) -> Vec<EntityType> {
-        let mut result = Vec::new();
-        
-        // Calculate the cell coordinates
-        let cell_x = (x / GRID_CELL_SIZE) as isize;
-        let cell_y = (y / GRID_CELL_SIZE) as isize;
-        
-        // Check the cell and its neighbors (3x3 grid around the cell)
-        for dy in -1..=1 {
-            for dx in -1..=1 {
-                let nx = cell_x + dx;
-                let ny = cell_y + dy;
-                
-                // Skip if out of bounds
-                if nx < 0 || ny < 0 || nx >= self.width as isize || ny >= self.height as isize {
-                    continue;
-                }
-                
-                let index = (ny as usize) * self.width + (nx as usize);
-                if index < self.cells.len() {
-                    result.extend_from_slice(&self.cells[index].entities);
-                }
-            }
-        }
-        
-        result
-    }
-    
-    // Helper function to populate the grid with all world entities
-    pub fn populate_from_world<DB: PlayerTableTrait + TreeTableTrait + StoneTableTrait 
-                                  + CampfireTableTrait + WoodenStorageBoxTableTrait 
-                                  + MushroomTableTrait + DroppedItemTableTrait>
-                                 (&mut self, db: &DB) {
-        self.clear();
-        
-        // Add players
-        for player in db.player().iter() {
-            if !player.is_dead {
-                self.add_entity(EntityType::Player(player.identity), player.position_x, player.position_y);
-            }
-        }
-        
-        // Add trees (only those with health > 0)
-        for tree in db.tree().iter() {
-            if tree.health > 0 {
-                self.add_entity(EntityType::Tree(tree.id as u64), tree.pos_x, tree.pos_y);
-            }
-        }
-        
-        // Add stones (only those with health > 0)
-        for stone in db.stone().iter() {
-            if stone.health > 0 {
-                self.add_entity(EntityType::Stone(stone.id as u64), stone.pos_x, stone.pos_y);
-            }
-        }
-        
-        // Add campfires
-        for campfire in db.campfire().iter() {
-            self.add_entity(EntityType::Campfire(campfire.id as u32), campfire.pos_x, campfire.pos_y);
-        }
-        
-        // Add wooden storage boxes
-        for box_instance in db.wooden_storage_box().iter() {
-            self.add_entity(EntityType::WoodenStorageBox(box_instance.id as u32), box_instance.pos_x, box_instance.pos_y);
-        }
-        
-        // Add mushrooms
-        for mushroom in db.mushroom().iter() {
-            self.add_entity(EntityType::Mushroom(mushroom.id as u32), mushroom.pos_x, mushroom.pos_y);
-        }
-        
-        // Add dropped items
-        for item in db.dropped_item().iter() {
-            self.add_entity(EntityType::DroppedItem(item.id), item.pos_x, item.pos_y);
+}
+
+/// Removes the entity from the grid. Call when its row is deleted.
+pub fn untrack_entity(ctx: &ReducerContext, entity: EntityType) {
+    ctx.db.spatial_grid_entry().entity_key().delete(entity.grid_key());
+}
+
+// Get all entities in the cells covering the given rectangle of cells
+fn collect_cells(ctx: &ReducerContext, min_cell_x: isize, max_cell_x: isize, min_cell_y: isize, max_cell_y: isize) -> Vec<EntityType> {
+    let width = grid_width() as isize;
+    let height = grid_height() as isize;
+    let entries = ctx.db.spatial_grid_entry();
+    let mut result = Vec::new();
+
+    for ny in min_cell_y.max(0)..=max_cell_y.min(height - 1) {
+        for nx in min_cell_x.max(0)..=max_cell_x.min(width - 1) {
+            let index = (ny * width + nx) as u32;
+            result.extend(entries.cell_index().filter(index).map(|entry| entry.entity));
         }
     }
+    result
+}
+
+// Get all entities in the cell and neighboring cells (3x3 grid around the cell)
+pub fn get_entities_in_range(ctx: &ReducerContext, x: f32, y: f32) -> Vec<EntityType> {
+    let cell_x = (x / GRID_CELL_SIZE).floor() as isize;
+    let cell_y = (y / GRID_CELL_SIZE).floor() as isize;
+    collect_cells(ctx, cell_x - 1, cell_x + 1, cell_y - 1, cell_y + 1)
+}
+
+/// Get all entities in every cell overlapping the square around (x, y) with the given radius.
+/// The result is a superset; callers still do their own exact distance checks.
+pub fn get_entities_in_radius(ctx: &ReducerContext, x: f32, y: f32, radius: f32) -> Vec<EntityType> {
+    collect_cells(
+        ctx,
+        ((x - radius) / GRID_CELL_SIZE).floor() as isize,
+        ((x + radius) / GRID_CELL_SIZE).floor() as isize,
+        ((y - radius) / GRID_CELL_SIZE).floor() as isize,
+        ((y + radius) / GRID_CELL_SIZE).floor() as isize,
+    )
 }
 
-// Implement Default
-impl Default for SpatialGrid {
-    fn default() -> Self {
-        Self::new()
+/// Builds the grid from scratch if it is empty, e.g. for a world that existed
+/// before the grid was persisted. Normal operation keeps it up to date incrementally.
+pub fn ensure_spatial_grid_built(ctx: &ReducerContext) {
+    if ctx.db.spatial_grid_entry().count() > 0 {
+        return;
+    }
+
+    for player in ctx.db.player().iter() {
+        track_entity(ctx, EntityType::Player(player.identity), player.position_x, player.position_y);
+    }
+    for tree in ctx.db.tree().iter() {
+        track_entity(ctx, EntityType::Tree(tree.id), tree.pos_x, tree.pos_y);
+    }
+    for stone in ctx.db.stone().iter() {
+        track_entity(ctx, EntityType::Stone(stone.id), stone.pos_x, stone.pos_y);
+    }
+    for campfire in ctx.db.campfire().iter() {
+        track_entity(ctx, EntityType::Campfire(campfire.id), campfire.pos_x, campfire.pos_y);
+    }
+    for box_instance in ctx.db.wooden_storage_box().iter() {
+        track_entity(ctx, EntityType::WoodenStorageBox(box_instance.id), box_instance.pos_x, box_instance.pos_y);
+    }
+    for mushroom in ctx.db.mushroom().iter() {
+        track_entity(ctx, EntityType::Mushroom(mushroom.id as u32), mushroom.pos_x, mushroom.pos_y);
+    }
+    for item in ctx.db.dropped_item().iter() {
+        track_entity(ctx, EntityType::DroppedItem(item.id), item.pos_x, item.pos_y);
+    }
+    for piece in ctx.db.building_piece().iter() {
+        track_entity(ctx, EntityType::BuildingPiece(piece.id), piece.pos_x, piece.pos_y);
+    }
+    for furnace in ctx.db.furnace().iter() {
+        track_entity(ctx, EntityType::Furnace(furnace.id), furnace.pos_x, furnace.pos_y);
+    }
+    for chest in ctx.db.large_storage_chest().iter() {
+        track_entity(ctx, EntityType::LargeStorageChest(chest.id), chest.pos_x, chest.pos_y);
+    }
+    for animal in ctx.db.wild_animal().iter() {
+        track_entity(ctx, EntityType::Animal(animal.id), animal.pos_x, animal.pos_y);
+    }
+    for loot_crate in ctx.db.loot_crate().iter() {
+        track_entity(ctx, EntityType::LootCrate(loot_crate.id), loot_crate.pos_x, loot_crate.pos_y);
+    }
+    for workbench in ctx.db.workbench().iter() {
+        track_entity(ctx, EntityType::Workbench(workbench.id), workbench.pos_x, workbench.pos_y);
     }
-} 
\ No newline at end of file
+    for recycler in ctx.db.recycler().iter() {
+        track_entity(ctx, EntityType::Recycler(recycler.id), recycler.pos_x, recycler.pos_y);
+    }
+    for turret in ctx.db.flame_turret().iter() {
+        track_entity(ctx, EntityType::FlameTurret(turret.id), turret.pos_x, turret.pos_y);
+    }
+    for corpse in ctx.db.player_corpse().iter() {
+        track_entity(ctx, EntityType::PlayerCorpse(corpse.id), corpse.pos_x, corpse.pos_y);
+    }
+    for torch in ctx.db.standing_torch().iter() {
+        track_entity(ctx, EntityType::StandingTorch(torch.id), torch.pos_x, torch.pos_y);
+    }
+    for research_table in ctx.db.research_table().iter() {
+        track_entity(ctx, EntityType::ResearchTable(research_table.id), research_table.pos_x, research_table.pos_y);
+    }
+    for stash in ctx.db.stash().iter() {
+        track_entity(ctx, EntityType::Stash(stash.id), stash.pos_x, stash.pos_y);
+    }
+    for bag in ctx.db.sleeping_bag().iter() {
+        track_entity(ctx, EntityType::SleepingBag(bag.id), bag.pos_x, bag.pos_y);
+    }
+    for sign in ctx.db.sign().iter() {
+        track_entity(ctx, EntityType::Sign(sign.id), sign.pos_x, sign.pos_y);
+    }
+    for machine in ctx.db.vending_machine().iter() {
+        track_entity(ctx, EntityType::VendingMachine(machine.id), machine.pos_x, machine.pos_y);
+    }
+    for stump in ctx.db.tree_stump().iter() {
+        track_entity(ctx, EntityType::TreeStump(stump.id), stump.pos_x, stump.pos_y);
+    }
+    for mount in ctx.db.mount().iter() {
+        track_entity(ctx, EntityType::Mount(mount.id), mount.pos_x, mount.pos_y);
+    }
+    for switch in ctx.db.wiring_switch().iter() {
+        track_entity(ctx, EntityType::Switch(switch.id), switch.pos_x, switch.pos_y);
+    }
+    for hazard in ctx.db.environment_hazard().iter() {
+        track_entity(ctx, EntityType::EnvironmentHazard(hazard.id), hazard.pos_x, hazard.pos_y);
+    }
+    log::info!("[SpatialGrid] Built grid with {} entries.", ctx.db.spatial_grid_entry().count());
+}
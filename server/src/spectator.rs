@@ -0,0 +1,122 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines spectator (free camera) mode. Dead players waiting to respawn and  *
+ * admins can detach their camera from their body. While spectating, the      *
+ * camera is moved through `update_spectator_position`, which ignores         *
+ * collision and never touches player stats; the player's body stays where    *
+ * it is. Combat and world interactions are rejected for spectators via       *
+ * `ensure_not_spectating`. Spectating ends on respawn or disconnect.         *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+
+use crate::{WORLD_WIDTH_PX, WORLD_HEIGHT_PX};
+use crate::admin;
+use crate::player as PlayerTableTrait;
+use crate::spectator::spectator as SpectatorTableTrait;
+
+// --- Constants ---
+/// Camera speed in pixels per second.
+const SPECTATOR_SPEED: f32 = 900.0;
+/// Caps the time step of a single update so a late message can't teleport the camera.
+const MAX_SPECTATOR_DELTA_SECS: f32 = 0.25;
+
+/// --- Spectator Data Structure ---
+/// A player currently in spectator mode and where their camera is.
+/// Public so clients can follow the camera instead of the player's body.
+#[spacetimedb::table(name = spectator, public)]
+#[derive(Clone, Debug)]
+pub struct Spectator {
+    #[primary_key]
+    pub player_id: Identity,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub started_at: Timestamp,
+    pub last_update: Timestamp,
+}
+
+/// Enters spectator mode at the player's current position.
+/// Only dead players (before respawning) and admins may spectate.
+#[spacetimedb::reducer]
+pub fn spectate(ctx: &ReducerContext) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Player not found".to_string())?;
+
+    if !player.is_dead && !admin::is_admin(ctx, sender_id) {
+        return Err("Only dead players and admins can spectate.".to_string());
+    }
+    if ctx.db.spectator().player_id().find(sender_id).is_some() {
+        return Err("Already spectating.".to_string());
+    }
+
+    ctx.db.spectator().insert(Spectator {
+        player_id: sender_id,
+        pos_x: player.position_x,
+        pos_y: player.position_y,
+        started_at: ctx.timestamp,
+        last_update: ctx.timestamp,
+    });
+    log::info!("Player {:?} started spectating.", sender_id);
+    Ok(())
+}
+
+/// Leaves spectator mode, returning the camera to the player's body.
+#[spacetimedb::reducer]
+pub fn stop_spectating(ctx: &ReducerContext) -> Result<(), String> {
+    if !end_spectating(ctx, ctx.sender) {
+        return Err("Not spectating.".to_string());
+    }
+    Ok(())
+}
+
+/// Moves the spectator camera. Unlike `update_player_position`, this skips collision,
+/// sprinting and stat drain entirely; the camera is only kept inside the world.
+#[spacetimedb::reducer]
+pub fn update_spectator_position(ctx: &ReducerContext, move_x: f32, move_y: f32) -> Result<(), String> {
+    let spectators = ctx.db.spectator();
+    let mut spectator = spectators.player_id().find(ctx.sender)
+        .ok_or_else(|| "Not spectating.".to_string())?;
+
+    let elapsed_micros = ctx.timestamp.to_micros_since_unix_epoch()
+        .saturating_sub(spectator.last_update.to_micros_since_unix_epoch());
+    let delta_time_secs = (elapsed_micros as f32 / 1_000_000.0).min(MAX_SPECTATOR_DELTA_SECS);
+
+    let length = (move_x * move_x + move_y * move_y).sqrt();
+    if length > 0.0 {
+        let step = SPECTATOR_SPEED * delta_time_secs / length.max(1.0);
+        spectator.pos_x = (spectator.pos_x + move_x * step).clamp(0.0, WORLD_WIDTH_PX);
+        spectator.pos_y = (spectator.pos_y + move_y * step).clamp(0.0, WORLD_HEIGHT_PX);
+    }
+    spectator.last_update = ctx.timestamp;
+    spectators.player_id().update(spectator);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Returns true if the player is currently in spectator mode.
+pub(crate) fn is_spectating(ctx: &ReducerContext, player_id: Identity) -> bool {
+    ctx.db.spectator().player_id().find(player_id).is_some()
+}
+
+/// Rejects combat and interaction requests from spectators.
+pub(crate) fn ensure_not_spectating(ctx: &ReducerContext, player_id: Identity) -> Result<(), String> {
+    if is_spectating(ctx, player_id) {
+        return Err("Cannot do that while spectating.".to_string());
+    }
+    Ok(())
+}
+
+/// Ends spectator mode for the player if active. Called on respawn and disconnect.
+/// Returns true if the player was spectating.
+pub(crate) fn end_spectating(ctx: &ReducerContext, player_id: Identity) -> bool {
+    if !ctx.db.spectator().player_id().delete(player_id) {
+        return false;
+    }
+    log::info!("Player {:?} stopped spectating.", player_id);
+    true
+}
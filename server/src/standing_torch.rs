@@ -0,0 +1,217 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines the Standing Torch entity: a Torch item planted in the ground as a *
+ * light source. It gives a little warmth to nearby players (see the stat     *
+ * tick in player_stats.rs) but cannot cook. A lit torch burns down over time *
+ * and is gone once it burns out; players can extinguish, relight and refuel  *
+ * it with any item that burns in a campfire, or pick it back up as a Torch   *
+ * with the durability it was planted with.                                   *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+
+use crate::player as PlayerTableTrait;
+use crate::items::{ItemDefinition, add_item_to_player_inventory, inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
+use crate::campfire;
+use crate::environment::calculate_chunk_index;
+use crate::player_inventory::get_player_item;
+use crate::spatial_grid::{self, EntityType};
+use crate::standing_torch::standing_torch as StandingTorchTableTrait;
+use crate::standing_torch::standing_torch_schedule as StandingTorchScheduleTableTrait;
+
+// --- Constants ---
+const TORCH_ITEM_NAME: &str = "Torch";
+pub(crate) const STANDING_TORCH_COLLISION_RADIUS: f32 = 10.0;
+const STANDING_TORCH_PROCESS_INTERVAL_SECS: u64 = 5;
+/// Seconds a freshly placed torch burns for. Refuelling can't go above this.
+const STANDING_TORCH_MAX_BURN_SECS: f32 = 600.0;
+pub(crate) const STANDING_TORCH_WARMTH_RADIUS: f32 = 100.0;
+pub(crate) const STANDING_TORCH_WARMTH_PER_SECOND: f32 = 1.5;
+
+/// --- Standing Torch ---
+/// A placed torch. `burn_secs_remaining` works like durability: it only drains while lit.
+#[spacetimedb::table(name = standing_torch, public)]
+#[derive(Clone, Debug)]
+pub struct StandingTorch {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    #[index(btree)]
+    pub chunk_index: u32,
+    pub placed_by: Identity,
+    pub placed_at: Timestamp,
+    pub is_lit: bool,
+    pub burn_secs_remaining: f32,
+    pub item_durability: Option<f32>, // The Torch item's durability, given back on pickup
+}
+
+/// --- Standing Torch Schedule ---
+#[spacetimedb::table(name = standing_torch_schedule, scheduled(process_standing_torches))]
+#[derive(Clone, Debug)]
+pub struct StandingTorchSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Place Standing Torch ---
+/// Plants a Torch from the player's inventory/hotbar in the ground, already lit.
+#[spacetimedb::reducer]
+pub fn place_standing_torch(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    crate::placement::place_deployable(ctx, item_instance_id, world_x, world_y, |name| name == TORCH_ITEM_NAME)
+}
+
+/// Creates a lit standing torch with a full burn. The placement constructor for Torch items
+/// (planting the torch you are holding puts it away first, see `placement::place_deployable`).
+pub(crate) fn spawn_standing_torch(ctx: &ReducerContext, placed_by: Identity, _item_def: &ItemDefinition, item_durability: Option<f32>, world_x: f32, world_y: f32) -> Result<(), String> {
+    let inserted = ctx.db.standing_torch().insert(StandingTorch {
+        id: 0, // Auto-incremented
+        pos_x: world_x,
+        pos_y: world_y,
+        chunk_index: calculate_chunk_index(world_x, world_y),
+        placed_by,
+        placed_at: ctx.timestamp,
+        is_lit: true,
+        burn_secs_remaining: STANDING_TORCH_MAX_BURN_SECS,
+        item_durability,
+    });
+    spatial_grid::track_entity(ctx, EntityType::StandingTorch(inserted.id), inserted.pos_x, inserted.pos_y);
+    log::info!("[StandingTorch] Player {:?} placed standing torch {} at ({:.1}, {:.1}).", placed_by, inserted.id, world_x, world_y);
+    Ok(())
+}
+
+/// --- Toggle Standing Torch ---
+/// Extinguishes a lit torch or relights an extinguished one.
+#[spacetimedb::reducer]
+pub fn toggle_standing_torch(ctx: &ReducerContext, torch_id: u32) -> Result<(), String> {
+    let mut torch = validate_torch_interaction(ctx, torch_id)?;
+    torch.is_lit = !torch.is_lit;
+    log::info!("[StandingTorch] Player {:?} {} standing torch {}.", ctx.sender, if torch.is_lit { "lit" } else { "extinguished" }, torch_id);
+    ctx.db.standing_torch().id().update(torch);
+    Ok(())
+}
+
+/// --- Refuel Standing Torch ---
+/// Burns one unit of a campfire fuel item from the player's inventory into the torch,
+/// adding that fuel's burn time (up to the torch's maximum).
+#[spacetimedb::reducer]
+pub fn refuel_standing_torch(ctx: &ReducerContext, torch_id: u32, fuel_item_instance_id: u64) -> Result<(), String> {
+    let mut torch = validate_torch_interaction(ctx, torch_id)?;
+    let inventory_items = ctx.db.inventory_item();
+
+    let mut fuel_item = get_player_item(ctx, fuel_item_instance_id)?;
+    let fuel_def = ctx.db.item_definition().id().find(fuel_item.item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found.", fuel_item.item_def_id))?;
+    let burn_secs = fuel_def.fuel_burn_duration_secs
+        .filter(|secs| *secs > 0.0)
+        .ok_or_else(|| format!("{} is not a fuel.", fuel_def.name))?;
+    if torch.burn_secs_remaining >= STANDING_TORCH_MAX_BURN_SECS {
+        return Err("The torch is already fully fuelled.".to_string());
+    }
+
+    if fuel_item.quantity > 1 {
+        fuel_item.quantity -= 1;
+        inventory_items.instance_id().update(fuel_item);
+    } else {
+        inventory_items.instance_id().delete(fuel_item_instance_id);
+    }
+    torch.burn_secs_remaining = (torch.burn_secs_remaining + burn_secs).min(STANDING_TORCH_MAX_BURN_SECS);
+    ctx.db.standing_torch().id().update(torch);
+    Ok(())
+}
+
+/// --- Pickup Standing Torch ---
+/// Pulls the torch out of the ground, returning the Torch item with the durability it
+/// was planted with.
+#[spacetimedb::reducer]
+pub fn pickup_standing_torch(ctx: &ReducerContext, torch_id: u32) -> Result<(), String> {
+    let torch = validate_torch_interaction(ctx, torch_id)?;
+    let torch_item_def = ctx.db.item_definition().iter()
+        .find(|def| def.name == TORCH_ITEM_NAME)
+        .ok_or_else(|| format!("ItemDefinition for '{}' not found.", TORCH_ITEM_NAME))?;
+    let new_instance_id = add_item_to_player_inventory(ctx, ctx.sender, torch_item_def.id, 1)
+        .map_err(|e| format!("Could not add {} to your inventory: {}", TORCH_ITEM_NAME, e))?;
+    if let (Some(instance_id), Some(durability)) = (new_instance_id, torch.item_durability) {
+        let inventory_items = ctx.db.inventory_item();
+        if let Some(mut torch_item) = inventory_items.instance_id().find(instance_id) {
+            torch_item.durability = Some(durability);
+            inventory_items.instance_id().update(torch_item);
+        }
+    }
+
+    ctx.db.standing_torch().id().delete(torch_id);
+    spatial_grid::untrack_entity(ctx, EntityType::StandingTorch(torch_id));
+    log::info!("[StandingTorch] Standing torch {} picked up by player {:?}.", torch_id, ctx.sender);
+    Ok(())
+}
+
+/******************************************************************************
+ *                           SCHEDULED REDUCERS                               *
+ ******************************************************************************/
+
+/// Starts the standing torch burn schedule if it isn't already scheduled. Called from `init_module`.
+pub fn init_standing_torch_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.standing_torch_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting standing torch schedule (every {}s).", STANDING_TORCH_PROCESS_INTERVAL_SECS);
+        let interval = Duration::from_secs(STANDING_TORCH_PROCESS_INTERVAL_SECS);
+        schedule_table.try_insert(StandingTorchSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert standing torch schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: burns down every lit torch and removes the ones that burn out.
+#[spacetimedb::reducer]
+pub fn process_standing_torches(ctx: &ReducerContext, _schedule: StandingTorchSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("process_standing_torches may only be called by the scheduler.".to_string());
+    }
+
+    let torches = ctx.db.standing_torch();
+    let lit_torches: Vec<StandingTorch> = torches.iter().filter(|t| t.is_lit).collect();
+    for mut torch in lit_torches {
+        torch.burn_secs_remaining -= STANDING_TORCH_PROCESS_INTERVAL_SECS as f32;
+        if torch.burn_secs_remaining <= 0.0 {
+            log::info!("[StandingTorch] Standing torch {} burned out.", torch.id);
+            torches.id().delete(torch.id);
+            spatial_grid::untrack_entity(ctx, EntityType::StandingTorch(torch.id));
+        } else {
+            torches.id().update(torch);
+        }
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Checks the sender is alive, not spectating and within campfire interaction range of the torch.
+fn validate_torch_interaction(ctx: &ReducerContext, torch_id: u32) -> Result<StandingTorch, String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or_else(|| "Player not found.".to_string())?;
+    if player.is_dead {
+        return Err("Cannot use a torch while dead.".to_string());
+    }
+    let torch = ctx.db.standing_torch().id().find(torch_id)
+        .ok_or_else(|| format!("Standing torch {} not found.", torch_id))?;
+    let dist_sq = (player.position_x - torch.pos_x).powi(2) + (player.position_y - torch.pos_y).powi(2);
+    if dist_sq > campfire::PLAYER_CAMPFIRE_INTERACTION_DISTANCE_SQUARED {
+        return Err("Too far away from the torch.".to_string());
+    }
+    Ok(torch)
+}
@@ -70,6 +70,9 @@ pub(crate) fn grant_starting_items(ctx: &ReducerContext, player_id: Identity, us
                 item_def_id: item_def.id,
                 quantity: *quantity,
                 location,
+                durability: None,
+                is_locked: false,
+                charges: None,
             };
             match inventory.try_insert(item_to_insert) {
                 Ok(_) => {
@@ -142,6 +145,9 @@ pub(crate) fn grant_starting_items(ctx: &ReducerContext, player_id: Identity, us
                 item_def_id: item_def.id,
                 quantity: 1, // Equipment is typically quantity 1
                 location: ItemLocation::Equipped(crate::models::EquippedLocationData { owner_id: player_id, slot_type: target_slot_type.clone() }),
+                durability: None,
+                is_locked: false,
+                charges: None,
             };
             match inventory.try_insert(item_to_equip_for_insert) {
                 Ok(inserted_item) => {
@@ -1,19 +1,30 @@
 /******************************************************************************
  *                                                                            *
  * Defines the Stash entity, its data structure, and associated logic.        *
- * Stashes are hidden containers that players can place and use to store      *
- * items securely.                                                            *
+ * Stashes are small containers that can be buried to hide them. Only the     *
+ * owner can dig up a buried stash directly; anyone else has to stand over it *
+ * and use `search_ground`, which finds it with a chance that grows the       *
+ * closer they are. Buried stashes are hidden from every client but the       *
+ * owner's. Stashes can't be buried in water or on building pieces.           *
  *                                                                            *
  ******************************************************************************/
 
-use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table};
+use spacetimedb::{client_visibility_filter, Filter, Identity, ReducerContext, Table, TimeDuration};
 use log;
 use spacetimedb::Timestamp;
+use rand::Rng;
+use std::time::Duration;
 
 // --- Constants ---
 pub(crate) const STASH_INTERACTION_DISTANCE_SQUARED: f32 = 48.0 * 48.0; // Closer interaction than a box
 pub(crate) const STASH_SURFACE_INTERACTION_DISTANCE_SQUARED: f32 = 24.0 * 24.0; // Must be very close to surface
 pub const NUM_STASH_SLOTS: usize = 6; // Stashes have fewer slots
+const STASH_MAX_WEIGHT: f32 = 25.0; // Stashes are small holes in the ground
+/// Buried stashes within this distance of a searching player can be found.
+const STASH_SEARCH_RADIUS: f32 = 48.0;
+/// Chance of finding a stash the player is standing exactly on top of. Falls off linearly to 0 at the search radius.
+const STASH_SEARCH_MAX_FIND_CHANCE: f32 = 0.6;
+const GROUND_SEARCH_COOLDOWN_SECS: u64 = 2;
 // No collision constants as stashes are walkable
 
 // --- Import Table Traits and Concrete Types ---
@@ -22,13 +33,14 @@ use crate::Player;
 use crate::items::{
     InventoryItem, ItemDefinition,
     inventory_item as InventoryItemTableTrait,
-    item_definition as ItemDefinitionTableTrait,
-    add_item_to_player_inventory, get_player_item
 };
 use crate::inventory_management::{self, ItemContainer, ContainerItemClearer};
 use crate::stash::stash as StashTableTrait; // For ctx.db.stash()
 use crate::environment::calculate_chunk_index;
 use crate::models::{ContainerType, ItemLocation};
+use crate::spatial_grid::{self, EntityType};
+use crate::building::building_piece as BuildingPieceTableTrait;
+use crate::stash::ground_search_cooldown as GroundSearchCooldownTableTrait;
 
 
 /// --- Stash Data Structure ---
@@ -42,6 +54,7 @@ pub struct Stash {
 
     pub pos_x: f32,
     pub pos_y: f32,
+    #[index(btree)]
     pub chunk_index: u32,
 
     pub placed_by: Identity, // Who placed this stash
@@ -70,6 +83,30 @@ pub struct Stash {
     pub last_hit_time: Option<Timestamp>,
 }
 
+// --- Row-Level Security Filters ---
+// A buried stash is only sent to the player who buried it; everyone else has to find it
+// with `search_ground`.
+
+#[client_visibility_filter]
+const STASH_VISIBLE_ON_SURFACE: Filter = Filter::Sql(
+    "SELECT * FROM stash WHERE is_hidden = false"
+);
+
+#[client_visibility_filter]
+const STASH_OWN_ROWS: Filter = Filter::Sql(
+    "SELECT * FROM stash WHERE placed_by = :sender"
+);
+
+/// --- Ground Search Cooldown ---
+/// When each player last searched the ground for stashes.
+#[spacetimedb::table(name = ground_search_cooldown)]
+#[derive(Clone, Debug)]
+pub struct GroundSearchCooldown {
+    #[primary_key]
+    pub player_id: Identity,
+    pub last_search_at: Timestamp,
+}
+
 /******************************************************************************
  *                            TRAIT IMPLEMENTATIONS                           *
  ******************************************************************************/
@@ -80,6 +117,10 @@ impl ItemContainer for Stash {
         NUM_STASH_SLOTS
     }
 
+    fn max_weight(&self) -> Option<f32> {
+        Some(STASH_MAX_WEIGHT)
+    }
+
     fn get_slot_instance_id(&self, slot_index: u8) -> Option<u64> {
         match slot_index {
             0 => self.slot_instance_id_0,
@@ -201,6 +242,18 @@ fn validate_basic_stash_interaction(
     Ok((player, stash))
 }
 
+/// Checks that the ground at (pos_x, pos_y) can hold a buried stash:
+/// not in water and not on a tile with a building piece.
+fn check_stash_burial_surface(ctx: &ReducerContext, pos_x: f32, pos_y: f32) -> Result<(), String> {
+    if crate::world_tile::is_water_at(ctx, pos_x, pos_y) {
+        return Err("Stashes can't be buried in water.".to_string());
+    }
+    let (tile_x, tile_y) = crate::building::world_pos_to_tile(pos_x, pos_y);
+    if ctx.db.building_piece().iter().any(|p| !p.is_destroyed && p.tile_x == tile_x && p.tile_y == tile_y) {
+        return Err("Stashes can't be buried on a building.".to_string());
+    }
+    Ok(())
+}
 
 /******************************************************************************
  *                         REDUCERS (Stash-Specific Logic)                    *
@@ -208,42 +261,19 @@ fn validate_basic_stash_interaction(
 
 #[spacetimedb::reducer]
 pub fn place_stash(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
-    let sender_id = ctx.sender;
-    let mut inventory_items = ctx.db.inventory_item();
-    let item_defs = ctx.db.item_definition();
-    let mut stashes = ctx.db.stash(); // Plural for table access
-
-    log::info!("Player {:?} attempting to place stash (item instance {}) at ({}, {}).", sender_id, item_instance_id, world_x, world_y);
-
-    // 1. Validate Player (Existence checked by get_player_item)
-    // 2. Validate Item to be Placed
-    let mut item_to_place = get_player_item(ctx, item_instance_id)?; // Validates ownership and player existence
-    let item_def = item_defs.id().find(item_to_place.item_def_id)
-        .ok_or_else(|| format!("Item definition {} not found for item instance {}.", item_to_place.item_def_id, item_instance_id))?;
-
-    if item_def.name != "Stash" { // Assuming "Stash" is the ItemDefinition.name
-        return Err("Item is not a Stash.".to_string());
-    }
-    match &item_to_place.location {
-        ItemLocation::Inventory(_) | ItemLocation::Hotbar(_) => {
-            // Ownership and possession by sender already checked by get_player_item.
-        }
-        _ => return Err("Stash must be in inventory or hotbar to be placed.".to_string()),
-    }
-
-    // 3. Validate Placement Location (No collision with other stashes, unlike boxes)
-    // Stashes can overlap.
+    crate::placement::place_deployable(ctx, item_instance_id, world_x, world_y, |name| name == "Stash")
+}
 
-    // 4. Create the Stash entity
-    let new_chunk_index = calculate_chunk_index(world_x, world_y);
+/// Creates a visible, empty stash. The placement constructor for Stash items.
+pub(crate) fn spawn_stash(ctx: &ReducerContext, placed_by: Identity, _item_def: &ItemDefinition, _item_durability: Option<f32>, world_x: f32, world_y: f32) -> Result<(), String> {
     let new_stash = Stash {
         id: 0, // Auto-incremented
         pos_x: world_x,
         pos_y: world_y,
-        chunk_index: new_chunk_index,
-        placed_by: sender_id,
+        chunk_index: calculate_chunk_index(world_x, world_y),
+        placed_by,
         is_hidden: false, // Explicitly set to not hidden on placement
-        last_surfaced_by: Some(sender_id), // Player who placed it is the one who last surfaced it
+        last_surfaced_by: Some(placed_by), // Player who placed it is the one who last surfaced it
         slot_instance_id_0: None,
         slot_def_id_0: None,
         slot_instance_id_1: None,
@@ -262,21 +292,9 @@ pub fn place_stash(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, wo
         destroyed_at: None,
         last_hit_time: None,
     };
-    let inserted_stash = stashes.insert(new_stash);
-    log::info!("Player {:?} placed new Stash with ID {}. Location: {:?}", sender_id, inserted_stash.id, item_to_place.location);
-
-    // 5. Consume the item from player's inventory
-    if item_to_place.quantity > 1 {
-        item_to_place.quantity -= 1;
-        inventory_items.instance_id().update(item_to_place);
-    } else {
-        // Update location to Unknown before deleting for safety
-        item_to_place.location = ItemLocation::Unknown;
-        inventory_items.instance_id().update(item_to_place.clone()); // Persist unknown location
-        inventory_items.instance_id().delete(item_instance_id);
-    }
-    log::info!("Stash (item instance {}) consumed from player {:?} inventory after placement.", item_instance_id, sender_id);
-
+    let inserted_stash = ctx.db.stash().insert(new_stash);
+    crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::Stash(inserted_stash.id), inserted_stash.pos_x, inserted_stash.pos_y);
+    log::info!("Player {:?} placed new Stash with ID {}.", placed_by, inserted_stash.id);
     Ok(())
 }
 
@@ -290,8 +308,10 @@ pub fn toggle_stash_visibility(ctx: &ReducerContext, stash_id: u32) -> Result<()
         .ok_or_else(|| format!("Stash {} not found", stash_id))?;
 
     if stash.is_hidden {
-        // Trying to SURFACE the stash
-        // Ensure this uses the general, larger STASH_INTERACTION_DISTANCE_SQUARED
+        // Trying to SURFACE the stash. Only the owner knows where it is; others must search for it.
+        if stash.placed_by != sender_id {
+            return Err("You don't know where this stash is buried. Try searching the ground.".to_string());
+        }
         let (_player, _stash_validated) = validate_basic_stash_interaction(ctx, stash_id, STASH_INTERACTION_DISTANCE_SQUARED) 
             .map_err(|e| format!("Cannot surface stash: {}", e))?;
 
@@ -303,6 +323,8 @@ pub fn toggle_stash_visibility(ctx: &ReducerContext, stash_id: u32) -> Result<()
         // Trying to HIDE the stash
         let (_player, _stash_validated) = validate_basic_stash_interaction(ctx, stash_id, STASH_INTERACTION_DISTANCE_SQUARED)
             .map_err(|e| format!("Cannot hide stash: {}", e))?; // Normal interaction distance
+        check_stash_burial_surface(ctx, stash.pos_x, stash.pos_y)
+            .map_err(|e| format!("Cannot hide stash: {}", e))?;
 
         stash.is_hidden = true;
         // last_surfaced_by remains who last surfaced it, or placer if never hidden then surfaced.
@@ -312,14 +334,69 @@ pub fn toggle_stash_visibility(ctx: &ReducerContext, stash_id: u32) -> Result<()
     Ok(())
 }
 
+/// --- Search Ground ---
+/// Searches the ground around the player for other players' buried stashes.
+/// Each buried stash within STASH_SEARCH_RADIUS is found with a chance that scales
+/// with how close the player stands to it. Found stashes are surfaced.
+#[spacetimedb::reducer]
+pub fn search_ground(ctx: &ReducerContext) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let sender_id = ctx.sender;
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Player not found".to_string())?;
+    if player.is_dead {
+        return Err("Cannot search the ground while dead.".to_string());
+    }
+
+    let cooldowns = ctx.db.ground_search_cooldown();
+    let cooldown = TimeDuration::from(Duration::from_secs(GROUND_SEARCH_COOLDOWN_SECS));
+    if let Some(last) = cooldowns.player_id().find(sender_id) {
+        if ctx.timestamp < last.last_search_at + cooldown {
+            return Err("You are searching too quickly.".to_string());
+        }
+        cooldowns.player_id().update(GroundSearchCooldown { player_id: sender_id, last_search_at: ctx.timestamp });
+    } else {
+        cooldowns.insert(GroundSearchCooldown { player_id: sender_id, last_search_at: ctx.timestamp });
+    }
+
+    let stashes = ctx.db.stash();
+    let mut found = 0;
+    for entity in spatial_grid::get_entities_in_radius(ctx, player.position_x, player.position_y, STASH_SEARCH_RADIUS) {
+        let EntityType::Stash(stash_id) = entity else { continue };
+        let Some(mut stash) = stashes.id().find(stash_id) else { continue };
+        if !stash.is_hidden || stash.is_destroyed || stash.placed_by == sender_id {
+            continue;
+        }
+        let distance = ((player.position_x - stash.pos_x).powi(2) + (player.position_y - stash.pos_y).powi(2)).sqrt();
+        if distance > STASH_SEARCH_RADIUS {
+            continue;
+        }
+        let find_chance = STASH_SEARCH_MAX_FIND_CHANCE * (1.0 - distance / STASH_SEARCH_RADIUS);
+        if ctx.rng().gen::<f32>() < find_chance {
+            stash.is_hidden = false;
+            stash.last_surfaced_by = Some(sender_id);
+            stashes.id().update(stash);
+            log::info!("Player {} found buried stash {} while searching ({:.1}px away).", sender_id, stash_id, distance);
+            found += 1;
+        }
+    }
+
+    if found == 0 {
+        log::debug!("Player {} searched the ground and found nothing.", sender_id);
+    }
+    Ok(())
+}
+
 // --- Item Interaction Reducers for Stash ---
 
 /// Helper to validate stash interaction for item operations (not hidden, player close).
-fn validate_stash_item_interaction(ctx: &ReducerContext, stash_id: u32) -> Result<(Player, Stash), String> {
+pub(crate) fn validate_stash_item_interaction(ctx: &ReducerContext, stash_id: u32) -> Result<(Player, Stash), String> {
     let (_player, stash) = validate_basic_stash_interaction(ctx, stash_id, STASH_INTERACTION_DISTANCE_SQUARED)?;
     if stash.is_hidden {
         return Err(format!("Stash {} is hidden.", stash_id));
     }
+    // Stashes with an authorization list only open for the owner and those on it
+    crate::container_access::check_container_access(ctx, ContainerType::Stash, stash_id, stash.placed_by, ctx.sender)?;
     Ok((_player, stash))
 }
 
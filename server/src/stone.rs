@@ -1,4 +1,5 @@
 use spacetimedb::{Timestamp};
+use crate::models::ResourceRichness;
 
 // Import necessary constants
 use crate::{PLAYER_RADIUS}; // Removed unused TILE_SIZE_PX
@@ -30,9 +31,11 @@ pub struct Stone {
     pub id: u64,
     pub pos_x: f32,
     pub pos_y: f32,
-    pub health: u32, // Stones just disappear when health is 0
+    pub health: u32, // Stones just disappear when health is 0. Starts at STONE_INITIAL_HEALTH scaled by richness
+    pub richness: ResourceRichness,
     #[index(btree)]
     pub chunk_index: u32, // Added for spatial filtering/queries
     pub last_hit_time: Option<Timestamp>, // Added for shake effect
     pub respawn_at: Option<Timestamp>, // Added for respawn timer
+    pub sweet_spot_angle: f32, // Degrees around the rock; see sweet_spot.rs
 }
@@ -0,0 +1,130 @@
+/******************************************************************************
+ *                                                                            *
+ * Gathering sweet spots. Every tree and stone has a sweet spot: an angle     *
+ * around its trunk/rock the client marks on screen. Hitting it (the client   *
+ * sends where on the node the hit landed via `use_equipped_item_at`)         *
+ * multiplies the yield, and each consecutive sweet-spot hit on the same      *
+ * node grows the combo. After a sweet-spot hit the spot jumps elsewhere      *
+ * around the node. Offsets are checked on the server: they must be on the    *
+ * node, on the side facing the attacker, and aimed hits can't come faster    *
+ * than SWEET_SPOT_MIN_HIT_INTERVAL_MS.                                       *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use rand::Rng;
+
+use crate::models::TargetType;
+use crate::player as PlayerTableTrait;
+use crate::sweet_spot::gather_combo as GatherComboTableTrait;
+
+// --- Constants ---
+/// Width of the sweet spot, in degrees around the node.
+const SWEET_SPOT_ARC_DEGREES: f32 = 40.0;
+/// Hit offsets farther than this many node radii from the centre are rejected.
+const SWEET_SPOT_MAX_OFFSET_RADII: f32 = 1.5;
+/// Yield multiplier for the first sweet-spot hit in a row.
+const SWEET_SPOT_BASE_MULTIPLIER: f32 = 1.5;
+/// Extra multiplier for each further sweet-spot hit in a row.
+const SWEET_SPOT_COMBO_STEP: f32 = 0.25;
+const MAX_SWEET_SPOT_COMBO: u32 = 4;
+/// Aimed hits closer together than this don't count, so spamming can't farm the bonus.
+const SWEET_SPOT_MIN_HIT_INTERVAL_MS: i64 = 400;
+
+/// --- Gather Combo ---
+/// A player's current run of sweet-spot hits. Public so the client can show the combo.
+#[spacetimedb::table(name = gather_combo, public)]
+#[derive(Clone, Debug)]
+pub struct GatherCombo {
+    #[primary_key]
+    pub player_id: Identity,
+    pub target_type: TargetType,
+    pub target_id: u64,
+    pub combo: u32, // Sweet-spot hits in a row on this node
+    pub last_aimed_hit_at: Timestamp,
+}
+
+/// A gatherable node as seen by the sweet-spot check.
+pub(crate) struct SweetSpotNode {
+    pub target_type: TargetType,
+    pub target_id: u64,
+    pub center_x: f32,
+    pub center_y: f32,
+    pub radius: f32,
+}
+
+/// Starting sweet-spot angle (degrees) for a node at (pos_x, pos_y). Derived from the
+/// position so world generation doesn't need an RNG for it.
+pub(crate) fn initial_sweet_spot_angle(pos_x: f32, pos_y: f32) -> f32 {
+    let hash = ((pos_x * 12.9898 + pos_y * 78.233).sin() * 43_758.547).fract().abs();
+    hash * 360.0
+}
+
+/// Yield multiplier for a hit on `node` whose sweet spot is at `sweet_spot_angle`.
+/// `hit_offset` is where on the node the client says the hit landed, relative to its centre;
+/// None (a plain `use_equipped_item`) never earns a bonus. A sweet-spot hit moves the
+/// sweet spot, so callers must save `sweet_spot_angle` back to the node.
+pub(crate) fn resolve_hit(
+    ctx: &ReducerContext,
+    attacker_id: Identity,
+    node: &SweetSpotNode,
+    sweet_spot_angle: &mut f32,
+    hit_offset: Option<(f32, f32)>,
+    rng: &mut impl Rng,
+) -> f32 {
+    let Some((offset_x, offset_y)) = hit_offset else { return 1.0 };
+    let combos = ctx.db.gather_combo();
+    let previous = combos.player_id().find(attacker_id);
+    let too_soon = previous.as_ref().is_some_and(|c| {
+        ctx.timestamp.to_micros_since_unix_epoch() - c.last_aimed_hit_at.to_micros_since_unix_epoch() < SWEET_SPOT_MIN_HIT_INTERVAL_MS * 1000
+    });
+
+    let on_sweet_spot = !too_soon
+        && is_valid_offset(ctx, attacker_id, node, offset_x, offset_y)
+        && angle_distance(offset_y.atan2(offset_x).to_degrees(), *sweet_spot_angle) <= SWEET_SPOT_ARC_DEGREES / 2.0;
+    let same_node = previous.as_ref().is_some_and(|c| c.target_type == node.target_type && c.target_id == node.target_id);
+    let combo = match (on_sweet_spot, same_node) {
+        (false, _) => 0,
+        (true, true) => (previous.as_ref().map_or(0, |c| c.combo) + 1).min(MAX_SWEET_SPOT_COMBO),
+        (true, false) => 1,
+    };
+
+    let row = GatherCombo {
+        player_id: attacker_id,
+        target_type: node.target_type,
+        target_id: node.target_id,
+        combo,
+        last_aimed_hit_at: ctx.timestamp,
+    };
+    if previous.is_some() {
+        combos.player_id().update(row);
+    } else {
+        combos.insert(row);
+    }
+
+    if !on_sweet_spot {
+        return 1.0;
+    }
+    *sweet_spot_angle = (*sweet_spot_angle + rng.gen_range(90.0..270.0)) % 360.0;
+    SWEET_SPOT_BASE_MULTIPLIER + SWEET_SPOT_COMBO_STEP * (combo - 1) as f32
+}
+
+/// An offset is plausible if it's finite, on the node and on the side facing the attacker.
+fn is_valid_offset(ctx: &ReducerContext, attacker_id: Identity, node: &SweetSpotNode, offset_x: f32, offset_y: f32) -> bool {
+    if !offset_x.is_finite() || !offset_y.is_finite() {
+        return false;
+    }
+    let max_offset = node.radius * SWEET_SPOT_MAX_OFFSET_RADII;
+    if offset_x * offset_x + offset_y * offset_y > max_offset * max_offset {
+        return false;
+    }
+    let Some(attacker) = ctx.db.player().identity().find(attacker_id) else { return false };
+    let (to_attacker_x, to_attacker_y) = (attacker.position_x - node.center_x, attacker.position_y - node.center_y);
+    offset_x * to_attacker_x + offset_y * to_attacker_y > 0.0
+}
+
+/// Smallest difference between two angles in degrees (0..=180).
+fn angle_distance(a: f32, b: f32) -> f32 {
+    let diff = (a - b).rem_euclid(360.0);
+    diff.min(360.0 - diff)
+}
@@ -0,0 +1,310 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines teams (clans): the Team, TeamMember and TeamInvite tables and the  *
+ * reducers to create, join, leave and manage a team. Other modules use the   *
+ * helpers at the bottom to share access (storage boxes), block friendly      *
+ * fire (combat) and route team chat.                                         *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+
+use crate::player as PlayerTableTrait;
+use crate::team::team as TeamTableTrait;
+use crate::team::team_member as TeamMemberTableTrait;
+use crate::team::team_invite as TeamInviteTableTrait;
+
+// --- Constants ---
+pub const MAX_TEAM_SIZE: usize = 8;
+const MAX_TEAM_NAME_LENGTH: usize = 24;
+
+/// --- Team Data Structure ---
+/// A named group of players. Friendly fire is off by default and can be toggled by the leader.
+#[spacetimedb::table(name = team, public)]
+#[derive(Clone, Debug)]
+pub struct Team {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub name: String,
+    pub leader_id: Identity,
+    pub created_at: Timestamp,
+    pub friendly_fire_enabled: bool,
+}
+
+/// --- Team Membership ---
+/// One row per player in a team; a player can only be in one team at a time.
+#[spacetimedb::table(name = team_member, public)]
+#[derive(Clone, Debug)]
+pub struct TeamMember {
+    #[primary_key]
+    pub player_id: Identity,
+    #[index(btree)]
+    pub team_id: u64,
+    pub joined_at: Timestamp,
+}
+
+/// --- Pending Team Invite ---
+#[spacetimedb::table(name = team_invite, public)]
+#[derive(Clone, Debug)]
+pub struct TeamInvite {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub team_id: u64,
+    pub inviter_id: Identity,
+    pub invitee_id: Identity,
+    pub sent_at: Timestamp,
+}
+
+/******************************************************************************
+ *                                 REDUCERS                                   *
+ ******************************************************************************/
+
+/// Creates a new team with the sender as leader and only member.
+#[spacetimedb::reducer]
+pub fn create_team(ctx: &ReducerContext, name: String) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Team name cannot be empty.".to_string());
+    }
+    if name.len() > MAX_TEAM_NAME_LENGTH {
+        return Err(format!("Team name too long (max {} characters).", MAX_TEAM_NAME_LENGTH));
+    }
+    if ctx.db.player().identity().find(sender_id).is_none() {
+        return Err("Player not found".to_string());
+    }
+    if ctx.db.team_member().player_id().find(sender_id).is_some() {
+        return Err("You are already in a team. Leave it first.".to_string());
+    }
+    if ctx.db.team().iter().any(|t| t.name.eq_ignore_ascii_case(&name)) {
+        return Err(format!("A team named '{}' already exists.", name));
+    }
+
+    let team = ctx.db.team().try_insert(Team {
+        id: 0, // Auto-incremented
+        name: name.clone(),
+        leader_id: sender_id,
+        created_at: ctx.timestamp,
+        friendly_fire_enabled: false,
+    }).map_err(|e| format!("Failed to create team: {}", e))?;
+
+    ctx.db.team_member().insert(TeamMember {
+        player_id: sender_id,
+        team_id: team.id,
+        joined_at: ctx.timestamp,
+    });
+    clear_invites_for_player(ctx, sender_id);
+
+    log::info!("Player {:?} created team {} ('{}').", sender_id, team.id, name);
+    Ok(())
+}
+
+/// Invites another player to the sender's team. Any member can invite.
+#[spacetimedb::reducer]
+pub fn invite_to_team(ctx: &ReducerContext, invitee_id: Identity) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let team_id = get_player_team_id(ctx, sender_id)
+        .ok_or_else(|| "You are not in a team.".to_string())?;
+
+    if invitee_id == sender_id {
+        return Err("You cannot invite yourself.".to_string());
+    }
+    if ctx.db.player().identity().find(invitee_id).is_none() {
+        return Err("Invited player not found.".to_string());
+    }
+    if ctx.db.team_member().player_id().find(invitee_id).is_some() {
+        return Err("That player is already in a team.".to_string());
+    }
+    if ctx.db.team_member().team_id().filter(team_id).count() >= MAX_TEAM_SIZE {
+        return Err(format!("Team is full (max {} members).", MAX_TEAM_SIZE));
+    }
+    if ctx.db.team_invite().iter().any(|inv| inv.team_id == team_id && inv.invitee_id == invitee_id) {
+        return Err("That player already has a pending invite to your team.".to_string());
+    }
+
+    ctx.db.team_invite().insert(TeamInvite {
+        id: 0, // Auto-incremented
+        team_id,
+        inviter_id: sender_id,
+        invitee_id,
+        sent_at: ctx.timestamp,
+    });
+    log::info!("Player {:?} invited {:?} to team {}.", sender_id, invitee_id, team_id);
+    Ok(())
+}
+
+/// Accepts a pending invite, joining that team. All other invites for the player are discarded.
+#[spacetimedb::reducer]
+pub fn accept_invite(ctx: &ReducerContext, invite_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let invite = ctx.db.team_invite().id().find(invite_id)
+        .ok_or_else(|| format!("Invite {} not found.", invite_id))?;
+    if invite.invitee_id != sender_id {
+        return Err("This invite is not for you.".to_string());
+    }
+    if ctx.db.team_member().player_id().find(sender_id).is_some() {
+        return Err("You are already in a team. Leave it first.".to_string());
+    }
+    if ctx.db.team().id().find(invite.team_id).is_none() {
+        ctx.db.team_invite().id().delete(invite_id);
+        return Err("That team no longer exists.".to_string());
+    }
+    if ctx.db.team_member().team_id().filter(invite.team_id).count() >= MAX_TEAM_SIZE {
+        return Err(format!("Team is full (max {} members).", MAX_TEAM_SIZE));
+    }
+
+    ctx.db.team_member().insert(TeamMember {
+        player_id: sender_id,
+        team_id: invite.team_id,
+        joined_at: ctx.timestamp,
+    });
+    clear_invites_for_player(ctx, sender_id);
+
+    log::info!("Player {:?} joined team {}.", sender_id, invite.team_id);
+    Ok(())
+}
+
+/// Declines (deletes) a pending invite addressed to the sender.
+#[spacetimedb::reducer]
+pub fn decline_invite(ctx: &ReducerContext, invite_id: u64) -> Result<(), String> {
+    let invite = ctx.db.team_invite().id().find(invite_id)
+        .ok_or_else(|| format!("Invite {} not found.", invite_id))?;
+    if invite.invitee_id != ctx.sender {
+        return Err("This invite is not for you.".to_string());
+    }
+    ctx.db.team_invite().id().delete(invite_id);
+    Ok(())
+}
+
+/// Leaves the sender's team. If the leader leaves, the longest-standing member takes over;
+/// the team is disbanded when its last member leaves.
+#[spacetimedb::reducer]
+pub fn leave_team(ctx: &ReducerContext) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let team_id = get_player_team_id(ctx, sender_id)
+        .ok_or_else(|| "You are not in a team.".to_string())?;
+    remove_member(ctx, team_id, sender_id);
+    log::info!("Player {:?} left team {}.", sender_id, team_id);
+    Ok(())
+}
+
+/// Removes a member from the team. Only the leader can kick.
+#[spacetimedb::reducer]
+pub fn kick_member(ctx: &ReducerContext, member_id: Identity) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let team = get_player_team(ctx, sender_id)
+        .ok_or_else(|| "You are not in a team.".to_string())?;
+    if team.leader_id != sender_id {
+        return Err("Only the team leader can kick members.".to_string());
+    }
+    if member_id == sender_id {
+        return Err("Use leave_team to leave your own team.".to_string());
+    }
+    if get_player_team_id(ctx, member_id) != Some(team.id) {
+        return Err("That player is not in your team.".to_string());
+    }
+    remove_member(ctx, team.id, member_id);
+    log::info!("Player {:?} kicked {:?} from team {}.", sender_id, member_id, team.id);
+    Ok(())
+}
+
+/// Toggles whether team members can damage each other. Only the leader can change it.
+#[spacetimedb::reducer]
+pub fn set_team_friendly_fire(ctx: &ReducerContext, enabled: bool) -> Result<(), String> {
+    let mut team = get_player_team(ctx, ctx.sender)
+        .ok_or_else(|| "You are not in a team.".to_string())?;
+    if team.leader_id != ctx.sender {
+        return Err("Only the team leader can change friendly fire.".to_string());
+    }
+    team.friendly_fire_enabled = enabled;
+    ctx.db.team().id().update(team);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Returns the id of the team the player belongs to, if any.
+pub(crate) fn get_player_team_id(ctx: &ReducerContext, player_id: Identity) -> Option<u64> {
+    ctx.db.team_member().player_id().find(player_id).map(|m| m.team_id)
+}
+
+/// Returns the team the player belongs to, if any.
+pub(crate) fn get_player_team(ctx: &ReducerContext, player_id: Identity) -> Option<Team> {
+    get_player_team_id(ctx, player_id).and_then(|team_id| ctx.db.team().id().find(team_id))
+}
+
+/// Returns true if both players are in the same team. A player is not their own teammate.
+pub(crate) fn are_teammates(ctx: &ReducerContext, a: Identity, b: Identity) -> bool {
+    if a == b {
+        return false;
+    }
+    match (get_player_team_id(ctx, a), get_player_team_id(ctx, b)) {
+        (Some(team_a), Some(team_b)) => team_a == team_b,
+        _ => false,
+    }
+}
+
+/// Returns true if `player_id` is the owner (`owner_id`) or on the owner's team.
+pub(crate) fn is_owner_or_teammate(ctx: &ReducerContext, player_id: Identity, owner_id: Identity) -> bool {
+    player_id == owner_id || are_teammates(ctx, player_id, owner_id)
+}
+
+/// Returns true if an attack between these players should be blocked because they are
+/// teammates and their team has friendly fire disabled.
+pub(crate) fn is_friendly_fire_blocked(ctx: &ReducerContext, attacker_id: Identity, target_id: Identity) -> bool {
+    if !are_teammates(ctx, attacker_id, target_id) {
+        return false;
+    }
+    get_player_team(ctx, attacker_id).is_some_and(|team| !team.friendly_fire_enabled)
+}
+
+/// Returns the identities of every member of the team.
+pub(crate) fn get_team_member_ids(ctx: &ReducerContext, team_id: u64) -> Vec<Identity> {
+    ctx.db.team_member().team_id().filter(team_id).map(|m| m.player_id).collect()
+}
+
+/// Deletes the membership row and fixes up leadership, disbanding the team if it is now empty.
+fn remove_member(ctx: &ReducerContext, team_id: u64, player_id: Identity) {
+    ctx.db.team_member().player_id().delete(player_id);
+    crate::player_pin::unshare_player_markers(ctx, player_id);
+
+    let Some(mut team) = ctx.db.team().id().find(team_id) else { return; };
+    let next_leader = ctx.db.team_member().team_id().filter(team_id)
+        .min_by_key(|m| m.joined_at)
+        .map(|m| m.player_id);
+
+    match next_leader {
+        None => {
+            ctx.db.team().id().delete(team_id);
+            let stale_invites: Vec<u64> = ctx.db.team_invite().iter()
+                .filter(|inv| inv.team_id == team_id)
+                .map(|inv| inv.id)
+                .collect();
+            for invite_id in stale_invites {
+                ctx.db.team_invite().id().delete(invite_id);
+            }
+            log::info!("Team {} disbanded (no members left).", team_id);
+        }
+        Some(new_leader) if team.leader_id == player_id => {
+            team.leader_id = new_leader;
+            ctx.db.team().id().update(team);
+            log::info!("Team {} leadership passed to {:?}.", team_id, new_leader);
+        }
+        Some(_) => {}
+    }
+}
+
+/// Removes all pending invites addressed to a player.
+fn clear_invites_for_player(ctx: &ReducerContext, player_id: Identity) {
+    let invite_ids: Vec<u64> = ctx.db.team_invite().iter()
+        .filter(|inv| inv.invitee_id == player_id)
+        .map(|inv| inv.id)
+        .collect();
+    for invite_id in invite_ids {
+        ctx.db.team_invite().id().delete(invite_id);
+    }
+}
@@ -0,0 +1,363 @@
+/******************************************************************************
+ *                                                                            *
+ * Player-to-player trading. One player requests a trade and the other        *
+ * accepts by requesting back. Both sides then offer items from their         *
+ * inventory or hotbar. Items stay where they are until both players confirm. *
+ * At that point every offer is checked again and moved in a single           *
+ * transaction. Changing an offer clears both confirmations.                  *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp, log};
+
+use crate::player as PlayerTableTrait;
+use crate::items::{InventoryItem, inventory_item as InventoryItemTableTrait};
+use crate::models::ItemLocation;
+use crate::active_equipment::active_equipment as ActiveEquipmentTableTrait;
+use crate::player_inventory::find_first_empty_player_slot;
+use crate::trading::trade_session as TradeSessionTableTrait;
+use crate::trading::trade_offer as TradeOfferTableTrait;
+
+// --- Constants ---
+/// Both players must stay within this distance of each other to trade.
+const TRADE_DISTANCE_SQUARED: f32 = 128.0 * 128.0;
+/// Maximum number of separate item stacks each player can put in a trade.
+const MAX_OFFERS_PER_PLAYER: usize = 12;
+
+#[derive(SpacetimeType, Copy, Clone, Debug, PartialEq)]
+pub enum TradeStatus {
+    /// Waiting for the partner to accept the request.
+    Requested,
+    /// Both players can offer items and confirm.
+    Open,
+}
+
+/// --- Trade Session Data Structure ---
+/// A trade between two players. The initiator is whoever sent the request.
+#[spacetimedb::table(name = trade_session, public)]
+#[derive(Clone, Debug)]
+pub struct TradeSession {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub initiator_id: Identity,
+    #[index(btree)]
+    pub partner_id: Identity,
+    pub status: TradeStatus,
+    pub initiator_confirmed: bool,
+    pub partner_confirmed: bool,
+    pub created_at: Timestamp,
+}
+
+impl TradeSession {
+    fn is_participant(&self, player_id: Identity) -> bool {
+        self.initiator_id == player_id || self.partner_id == player_id
+    }
+
+    fn other_participant(&self, player_id: Identity) -> Identity {
+        if self.initiator_id == player_id { self.partner_id } else { self.initiator_id }
+    }
+}
+
+/// --- Trade Offer Data Structure ---
+/// One item stack (or part of one) a player has put into a trade.
+#[spacetimedb::table(name = trade_offer, public)]
+#[derive(Clone, Debug)]
+pub struct TradeOffer {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub session_id: u64,
+    pub offered_by: Identity,
+    pub item_instance_id: u64,
+    pub quantity: u32,
+}
+
+/******************************************************************************
+ *                                 REDUCERS                                   *
+ ******************************************************************************/
+
+/// Sends a trade request to another player, or accepts theirs if they already sent one.
+#[spacetimedb::reducer]
+pub fn request_trade(ctx: &ReducerContext, target_player_id: Identity) -> Result<(), String> {
+    crate::moderation::ensure_not_sanctioned(ctx, ctx.sender)?;
+    let sender_id = ctx.sender;
+    crate::spectator::ensure_not_spectating(ctx, sender_id)?;
+    if target_player_id == sender_id {
+        return Err("You cannot trade with yourself.".to_string());
+    }
+    let sessions = ctx.db.trade_session();
+
+    // Accept a pending request from the target
+    if let Some(mut incoming) = sessions.initiator_id().filter(target_player_id)
+        .find(|s| s.partner_id == sender_id && s.status == TradeStatus::Requested)
+    {
+        validate_trade_partners(ctx, sender_id, target_player_id)?;
+        if find_open_session(ctx, sender_id).is_some() || find_open_session(ctx, target_player_id).is_some() {
+            return Err("One of you is already trading.".to_string());
+        }
+        incoming.status = TradeStatus::Open;
+        let session_id = incoming.id;
+        sessions.id().update(incoming);
+        log::info!("[Trade] Player {:?} accepted trade {} from {:?}.", sender_id, session_id, target_player_id);
+        return Ok(());
+    }
+
+    validate_trade_partners(ctx, sender_id, target_player_id)?;
+    if sessions.initiator_id().filter(sender_id).any(|s| s.partner_id == target_player_id) {
+        return Err("You already sent this player a trade request.".to_string());
+    }
+    if find_open_session(ctx, sender_id).is_some() {
+        return Err("You are already trading.".to_string());
+    }
+
+    let session = sessions.insert(TradeSession {
+        id: 0, // Auto-incremented
+        initiator_id: sender_id,
+        partner_id: target_player_id,
+        status: TradeStatus::Requested,
+        initiator_confirmed: false,
+        partner_confirmed: false,
+        created_at: ctx.timestamp,
+    });
+    log::info!("[Trade] Player {:?} requested trade {} with {:?}.", sender_id, session.id, target_player_id);
+    Ok(())
+}
+
+/// Adds an item from the sender's inventory or hotbar to an open trade.
+/// Offering an item that is already in the trade just changes its quantity.
+#[spacetimedb::reducer]
+pub fn offer_item(ctx: &ReducerContext, session_id: u64, item_instance_id: u64, quantity: u32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    crate::spectator::ensure_not_spectating(ctx, sender_id)?;
+    let session = get_open_session_for(ctx, session_id, sender_id)?;
+    let item = get_tradable_item(ctx, item_instance_id, sender_id)?;
+    if quantity == 0 || quantity > item.quantity {
+        return Err(format!("Invalid quantity {} (stack has {}).", quantity, item.quantity));
+    }
+
+    let offers = ctx.db.trade_offer();
+    let own_offers: Vec<TradeOffer> = offers.session_id().filter(session_id)
+        .filter(|o| o.offered_by == sender_id)
+        .collect();
+    if let Some(mut existing) = own_offers.iter().find(|o| o.item_instance_id == item_instance_id).cloned() {
+        existing.quantity = quantity;
+        offers.id().update(existing);
+    } else {
+        if own_offers.len() >= MAX_OFFERS_PER_PLAYER {
+            return Err(format!("You can offer at most {} stacks.", MAX_OFFERS_PER_PLAYER));
+        }
+        offers.insert(TradeOffer {
+            id: 0, // Auto-incremented
+            session_id,
+            offered_by: sender_id,
+            item_instance_id,
+            quantity,
+        });
+    }
+    reset_confirmations(ctx, session);
+    Ok(())
+}
+
+/// Changes how many of an offered stack go into the trade. A quantity of 0 withdraws the offer.
+#[spacetimedb::reducer]
+pub fn set_trade_quantity(ctx: &ReducerContext, offer_id: u64, quantity: u32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    crate::spectator::ensure_not_spectating(ctx, sender_id)?;
+    let offers = ctx.db.trade_offer();
+    let mut offer = offers.id().find(offer_id)
+        .ok_or_else(|| format!("Trade offer {} not found.", offer_id))?;
+    if offer.offered_by != sender_id {
+        return Err("You can only change your own offers.".to_string());
+    }
+    let session = get_open_session_for(ctx, offer.session_id, sender_id)?;
+
+    if quantity == 0 {
+        offers.id().delete(offer_id);
+    } else {
+        let item = get_tradable_item(ctx, offer.item_instance_id, sender_id)?;
+        if quantity > item.quantity {
+            return Err(format!("Invalid quantity {} (stack has {}).", quantity, item.quantity));
+        }
+        offer.quantity = quantity;
+        offers.id().update(offer);
+    }
+    reset_confirmations(ctx, session);
+    Ok(())
+}
+
+/// Confirms the current offers. Once both players have confirmed, the items are exchanged.
+#[spacetimedb::reducer]
+pub fn confirm_trade(ctx: &ReducerContext, session_id: u64) -> Result<(), String> {
+    crate::moderation::ensure_not_sanctioned(ctx, ctx.sender)?;
+    let sender_id = ctx.sender;
+    crate::spectator::ensure_not_spectating(ctx, sender_id)?;
+    let mut session = get_open_session_for(ctx, session_id, sender_id)?;
+    validate_trade_partners(ctx, session.initiator_id, session.partner_id)?;
+
+    if session.initiator_id == sender_id {
+        session.initiator_confirmed = true;
+    } else {
+        session.partner_confirmed = true;
+    }
+
+    if session.initiator_confirmed && session.partner_confirmed {
+        execute_trade(ctx, &session)?;
+        delete_session(ctx, session_id);
+        log::info!("[Trade] Trade {} between {:?} and {:?} completed.", session_id, session.initiator_id, session.partner_id);
+    } else {
+        ctx.db.trade_session().id().update(session);
+    }
+    Ok(())
+}
+
+/// Cancels a trade (or declines/withdraws a trade request). Either participant can cancel.
+#[spacetimedb::reducer]
+pub fn cancel_trade(ctx: &ReducerContext, session_id: u64) -> Result<(), String> {
+    let session = ctx.db.trade_session().id().find(session_id)
+        .ok_or_else(|| format!("Trade {} not found.", session_id))?;
+    if !session.is_participant(ctx.sender) {
+        return Err("You are not part of this trade.".to_string());
+    }
+    delete_session(ctx, session_id);
+    log::info!("[Trade] Player {:?} cancelled trade {}.", ctx.sender, session_id);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Cancels every trade and trade request involving the player (e.g. on disconnect).
+pub(crate) fn cancel_trades_for_player(ctx: &ReducerContext, player_id: Identity) {
+    let sessions = ctx.db.trade_session();
+    let session_ids: Vec<u64> = sessions.initiator_id().filter(player_id)
+        .chain(sessions.partner_id().filter(player_id))
+        .map(|s| s.id)
+        .collect();
+    for session_id in session_ids {
+        delete_session(ctx, session_id);
+    }
+}
+
+fn find_open_session(ctx: &ReducerContext, player_id: Identity) -> Option<TradeSession> {
+    let sessions = ctx.db.trade_session();
+    sessions.initiator_id().filter(player_id)
+        .chain(sessions.partner_id().filter(player_id))
+        .find(|s| s.status == TradeStatus::Open)
+}
+
+fn get_open_session_for(ctx: &ReducerContext, session_id: u64, player_id: Identity) -> Result<TradeSession, String> {
+    let session = ctx.db.trade_session().id().find(session_id)
+        .ok_or_else(|| format!("Trade {} not found.", session_id))?;
+    if !session.is_participant(player_id) {
+        return Err("You are not part of this trade.".to_string());
+    }
+    if session.status != TradeStatus::Open {
+        return Err("This trade has not been accepted yet.".to_string());
+    }
+    Ok(session)
+}
+
+/// Checks both players exist, are online and alive, and are standing close to each other.
+fn validate_trade_partners(ctx: &ReducerContext, player_a: Identity, player_b: Identity) -> Result<(), String> {
+    let players = ctx.db.player();
+    let a = players.identity().find(player_a).ok_or_else(|| "Player not found.".to_string())?;
+    let b = players.identity().find(player_b).ok_or_else(|| "Trade partner not found.".to_string())?;
+    if !a.is_online || !b.is_online || a.is_dead || b.is_dead {
+        return Err("Both players must be online and alive to trade.".to_string());
+    }
+    if crate::spectator::is_spectating(ctx, player_a) || crate::spectator::is_spectating(ctx, player_b) {
+        return Err("Cannot trade with a spectating player.".to_string());
+    }
+    let dx = a.position_x - b.position_x;
+    let dy = a.position_y - b.position_y;
+    if (dx * dx + dy * dy) > TRADE_DISTANCE_SQUARED {
+        return Err("You are too far away from each other to trade.".to_string());
+    }
+    Ok(())
+}
+
+/// Finds an item in the player's inventory or hotbar. Equipped armor can't be traded.
+fn get_tradable_item(ctx: &ReducerContext, item_instance_id: u64, player_id: Identity) -> Result<InventoryItem, String> {
+    let item = ctx.db.inventory_item().instance_id().find(item_instance_id)
+        .ok_or_else(|| format!("Item instance {} not found.", item_instance_id))?;
+    match &item.location {
+        ItemLocation::Inventory(data) if data.owner_id == player_id => Ok(item),
+        ItemLocation::Hotbar(data) if data.owner_id == player_id => Ok(item),
+        _ => Err(format!("Item instance {} must be in your inventory or hotbar to trade.", item_instance_id)),
+    }
+}
+
+fn reset_confirmations(ctx: &ReducerContext, mut session: TradeSession) {
+    if session.initiator_confirmed || session.partner_confirmed {
+        session.initiator_confirmed = false;
+        session.partner_confirmed = false;
+        ctx.db.trade_session().id().update(session);
+    }
+}
+
+fn delete_session(ctx: &ReducerContext, session_id: u64) {
+    let offers = ctx.db.trade_offer();
+    let offer_ids: Vec<u64> = offers.session_id().filter(session_id).map(|o| o.id).collect();
+    for offer_id in offer_ids {
+        offers.id().delete(offer_id);
+    }
+    ctx.db.trade_session().id().delete(session_id);
+}
+
+/// Moves every offered item to the other player. Offers are re-validated first; any
+/// error aborts the reducer, so the exchange either happens completely or not at all.
+fn execute_trade(ctx: &ReducerContext, session: &TradeSession) -> Result<(), String> {
+    let inventory_items = ctx.db.inventory_item();
+    let offers: Vec<TradeOffer> = ctx.db.trade_offer().session_id().filter(session.id).collect();
+
+    // Phase 1: take everything out of the givers' slots so freed slots can be reused.
+    // Whole stacks keep their instance (and durability); partial stacks are split off.
+    let mut to_deliver: Vec<(Identity, InventoryItem)> = Vec::new();
+    for offer in &offers {
+        let mut item = get_tradable_item(ctx, offer.item_instance_id, offer.offered_by)?;
+        if offer.quantity > item.quantity {
+            return Err(format!("Item {} no longer has {} to trade.", item.instance_id, offer.quantity));
+        }
+        let receiver_id = session.other_participant(offer.offered_by);
+
+        if offer.quantity == item.quantity {
+            let is_active_item = ctx.db.active_equipment().player_identity().find(offer.offered_by)
+                .is_some_and(|equip| equip.equipped_item_instance_id == Some(item.instance_id));
+            if is_active_item {
+                crate::active_equipment::clear_active_item_reducer(ctx, offer.offered_by)?;
+            }
+            item.location = ItemLocation::Unknown;
+            inventory_items.instance_id().update(item.clone());
+            to_deliver.push((receiver_id, item));
+        } else {
+            let split_off = InventoryItem {
+                instance_id: 0, // Inserted in phase 2
+                item_def_id: item.item_def_id,
+                quantity: offer.quantity,
+                location: ItemLocation::Unknown,
+                durability: item.durability,
+                is_locked: false,
+                charges: item.charges,
+            };
+            item.quantity -= offer.quantity;
+            inventory_items.instance_id().update(item);
+            to_deliver.push((receiver_id, split_off));
+        }
+    }
+
+    // Phase 2: place each item in the receiver's first free slot.
+    for (receiver_id, mut item) in to_deliver {
+        item.location = find_first_empty_player_slot(ctx, receiver_id)
+            .ok_or_else(|| "Not enough free inventory space to complete the trade.".to_string())?;
+        if item.instance_id == 0 {
+            inventory_items.insert(item);
+        } else {
+            inventory_items.instance_id().update(item);
+        }
+    }
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use spacetimedb::{SpacetimeType, Timestamp};
+use crate::models::ResourceRichness;
 
 // Import necessary constants from the main crate or environment
 use crate::{PLAYER_RADIUS}; // Removed unused TILE_SIZE_PX
@@ -42,10 +43,12 @@ pub struct Tree {
     pub id: u64,
     pub pos_x: f32,
     pub pos_y: f32,
-    pub health: u32,
+    pub health: u32, // Starts at TREE_INITIAL_HEALTH scaled by richness
     pub tree_type: TreeType,
+    pub richness: ResourceRichness,
     #[index(btree)]
     pub chunk_index: u32,
     pub last_hit_time: Option<Timestamp>,
     pub respawn_at: Option<Timestamp>,
+    pub sweet_spot_angle: f32, // Degrees around the trunk; see sweet_spot.rs
 }
\ No newline at end of file
@@ -0,0 +1,191 @@
+/******************************************************************************
+ *                                                                            *
+ * Tree felling. When a tree's health reaches 0 it doesn't just vanish: a     *
+ * short-lived FallingTree row tells clients to play the fall, away from the  *
+ * player who landed the last hit, and a stump is left behind. When the tree  *
+ * hits the ground (FALL_DURATION_MS later) every player lying in its path    *
+ * takes a Crushed effect (see active_effects.rs). Stumps can be chopped for  *
+ * extra wood until they are used up, and are cleared when the tree regrows.  *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, TimeDuration, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+
+use crate::PLAYER_RADIUS;
+use crate::player as PlayerTableTrait;
+use crate::tree::{Tree, TreeType, TREE_COLLISION_Y_OFFSET};
+use crate::environment::calculate_chunk_index;
+use crate::spatial_grid::{self, EntityType};
+use crate::utils::get_distance_squared_to_segment;
+use crate::world_event::WorldEventKind;
+use crate::tree_felling::falling_tree as FallingTreeTableTrait;
+use crate::tree_felling::falling_tree_landing_schedule as FallingTreeLandingScheduleTableTrait;
+use crate::tree_felling::tree_stump as TreeStumpTableTrait;
+
+// --- Constants ---
+/// Time between the killing blow and the trunk hitting the ground.
+const FALL_DURATION_MS: u64 = 1500;
+/// How far from the stump the fallen trunk reaches.
+const FALLING_TREE_LENGTH: f32 = 220.0;
+/// Half the width of the strip the trunk lands on.
+const FALLING_TREE_HALF_WIDTH: f32 = 24.0;
+const FALLING_TREE_DAMAGE: f32 = 45.0;
+pub(crate) const TREE_STUMP_INITIAL_HEALTH: u32 = 400;
+
+/// --- Falling Tree ---
+/// A tree that has been felled and is on its way down. Deleted once it lands.
+#[spacetimedb::table(name = falling_tree, public)]
+#[derive(Clone, Debug)]
+pub struct FallingTree {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub tree_id: u64,
+    pub tree_type: TreeType,
+    pub pos_x: f32, // Base of the trunk
+    pub pos_y: f32,
+    pub direction_x: f32, // Unit vector the tree falls along
+    pub direction_y: f32,
+    #[index(btree)]
+    pub chunk_index: u32,
+    pub felled_by: Identity,
+    pub felled_at: Timestamp,
+    pub lands_at: Timestamp,
+}
+
+/// --- Falling Tree Landing Schedule ---
+/// One-off schedule that lands a falling tree.
+#[spacetimedb::table(name = falling_tree_landing_schedule, scheduled(land_falling_tree))]
+#[derive(Clone, Debug)]
+pub struct FallingTreeLandingSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub falling_tree_id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/// --- Tree Stump ---
+/// What is left of a felled tree. Harvested like a tree, removed when used up or when the tree respawns.
+#[spacetimedb::table(name = tree_stump, public)]
+#[derive(Clone, Debug)]
+pub struct TreeStump {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub tree_id: u64,
+    pub tree_type: TreeType,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    #[index(btree)]
+    pub chunk_index: u32,
+    pub health: u32,
+    pub last_hit_time: Option<Timestamp>,
+}
+
+/******************************************************************************
+ *                           SCHEDULED REDUCERS                               *
+ ******************************************************************************/
+
+/// Scheduled reducer: the tree hits the ground. Players in its path are crushed.
+#[spacetimedb::reducer]
+pub fn land_falling_tree(ctx: &ReducerContext, schedule: FallingTreeLandingSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("land_falling_tree may only be called by the scheduler.".to_string());
+    }
+    let falling_trees = ctx.db.falling_tree();
+    let Some(falling_tree) = falling_trees.id().find(schedule.falling_tree_id) else {
+        return Ok(());
+    };
+
+    let (start_x, start_y) = (falling_tree.pos_x, falling_tree.pos_y - TREE_COLLISION_Y_OFFSET);
+    let end_x = start_x + falling_tree.direction_x * FALLING_TREE_LENGTH;
+    let end_y = start_y + falling_tree.direction_y * FALLING_TREE_LENGTH;
+    let hit_distance = FALLING_TREE_HALF_WIDTH + PLAYER_RADIUS;
+    let search_radius = FALLING_TREE_LENGTH / 2.0 + hit_distance;
+
+    let players = ctx.db.player();
+    for entity in spatial_grid::get_entities_in_radius(ctx, (start_x + end_x) / 2.0, (start_y + end_y) / 2.0, search_radius) {
+        let EntityType::Player(player_id) = entity else { continue };
+        let Some(player) = players.identity().find(player_id) else { continue };
+        if player.is_dead || crate::spectator::is_spectating(ctx, player_id) {
+            continue;
+        }
+        if get_distance_squared_to_segment(player.position_x, player.position_y, start_x, start_y, end_x, end_y) <= hit_distance * hit_distance {
+            crate::active_effects::apply_crush_damage(ctx, player_id, FALLING_TREE_DAMAGE);
+            log::info!("[TreeFelling] Player {:?} was caught under falling tree {}.", player_id, falling_tree.tree_id);
+        }
+    }
+
+    falling_trees.id().delete(falling_tree.id);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Starts a felled tree falling away from the player who felled it and leaves a stump.
+/// Called from `combat::damage_tree` when the tree's health reaches 0.
+pub(crate) fn fell_tree(ctx: &ReducerContext, tree: &Tree, felled_by: Identity) {
+    // Fall away from the feller; if they're somehow standing on the trunk, pick a fixed direction
+    let (mut direction_x, mut direction_y) = (1.0, 0.0);
+    if let Some(player) = ctx.db.player().identity().find(felled_by) {
+        let (dx, dy) = (tree.pos_x - player.position_x, (tree.pos_y - TREE_COLLISION_Y_OFFSET) - player.position_y);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length > f32::EPSILON {
+            (direction_x, direction_y) = (dx / length, dy / length);
+        }
+    }
+
+    let chunk_index = calculate_chunk_index(tree.pos_x, tree.pos_y);
+    let lands_at = ctx.timestamp + TimeDuration::from_micros(FALL_DURATION_MS as i64 * 1000);
+    let falling_tree = ctx.db.falling_tree().insert(FallingTree {
+        id: 0, // Auto-incremented
+        tree_id: tree.id,
+        tree_type: tree.tree_type.clone(),
+        pos_x: tree.pos_x,
+        pos_y: tree.pos_y,
+        direction_x,
+        direction_y,
+        chunk_index,
+        felled_by,
+        felled_at: ctx.timestamp,
+        lands_at,
+    });
+    if let Err(e) = ctx.db.falling_tree_landing_schedule().try_insert(FallingTreeLandingSchedule {
+        id: 0, // Auto-incremented
+        falling_tree_id: falling_tree.id,
+        scheduled_at: ScheduleAt::Time(lands_at),
+    }) {
+        log::error!("[TreeFelling] Failed to schedule landing of falling tree {}: {}", falling_tree.id, e);
+        ctx.db.falling_tree().id().delete(falling_tree.id);
+    }
+
+    let stump = ctx.db.tree_stump().insert(TreeStump {
+        id: 0, // Auto-incremented
+        tree_id: tree.id,
+        tree_type: tree.tree_type.clone(),
+        pos_x: tree.pos_x,
+        pos_y: tree.pos_y,
+        chunk_index,
+        health: TREE_STUMP_INITIAL_HEALTH,
+        last_hit_time: None,
+    });
+    spatial_grid::track_entity(ctx, EntityType::TreeStump(stump.id), stump.pos_x, stump.pos_y);
+    crate::world_event::emit_world_event(ctx, WorldEventKind::TreeFell, tree.pos_x, tree.pos_y, Some(felled_by), None);
+    log::info!("[TreeFelling] Tree {} felled by {:?}, falling towards ({:.2}, {:.2}). Left stump {}.",
+        tree.id, felled_by, direction_x, direction_y, stump.id);
+}
+
+/// Removes the stumps of a tree. Called when the tree respawns.
+pub(crate) fn remove_stumps_for_tree(ctx: &ReducerContext, tree_id: u64) {
+    let stumps = ctx.db.tree_stump();
+    let stump_ids: Vec<u64> = stumps.tree_id().filter(tree_id).map(|s| s.id).collect();
+    for stump_id in stump_ids {
+        stumps.id().delete(stump_id);
+        spatial_grid::untrack_entity(ctx, EntityType::TreeStump(stump_id));
+    }
+}
@@ -65,6 +65,23 @@ pub fn get_distance_squared(x1: f32, y1: f32, x2: f32, y2: f32) -> f32 {
     dx * dx + dy * dy
 }
 
+/// Whether a point lies inside a circular damage zone. Shared by campfire hot zones and
+/// environmental hazards.
+pub fn is_in_damage_zone(pos_x: f32, pos_y: f32, zone_x: f32, zone_y: f32, zone_radius_sq: f32) -> bool {
+    get_distance_squared(pos_x, pos_y, zone_x, zone_y) < zone_radius_sq
+}
+
+/// Squared distance from point (px, py) to the line segment from (ax, ay) to (bx, by).
+pub fn get_distance_squared_to_segment(px: f32, py: f32, ax: f32, ay: f32, bx: f32, by: f32) -> f32 {
+    let (abx, aby) = (bx - ax, by - ay);
+    let length_sq = abx * abx + aby * aby;
+    if length_sq == 0.0 {
+        return get_distance_squared(px, py, ax, ay);
+    }
+    let t = (((px - ax) * abx + (py - ay) * aby) / length_sq).clamp(0.0, 1.0);
+    get_distance_squared(px, py, ax + abx * t, ay + aby * t)
+}
+
 /// Checks if a target position is clear of players, campfires, and wooden storage boxes
 /// within a given squared radius. Returns true if clear, false otherwise.
 pub fn is_respawn_position_clear(ctx: &ReducerContext, target_x: f32, target_y: f32, check_radius_sq: f32) -> bool {
@@ -0,0 +1,533 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines the VendingMachine entity: a placeable shop. Its owner stocks its  *
+ * slots (a slot array, see `SlotArrayContainer` in inventory_management.rs)  *
+ * and lists sell orders of the form "N of item X for M of item Y". Other     *
+ * players trade with `buy_from_vending_machine` while the owner is away:     *
+ * the stock goes to the buyer and the payment goes into the machine's slots  *
+ * for the owner to collect. Everything is checked before anything moves, so  *
+ * a trade either happens in full or not at all. Only the owner can touch     *
+ * the slots, and orders can't be edited, only removed, so a buyer always     *
+ * gets the terms they were shown.                                            *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+
+use crate::Player;
+use crate::player as PlayerTableTrait;
+use crate::items::{ItemDefinition, InventoryItem, add_item_to_player_inventory, player_inventory_space_for_item};
+use crate::items::{inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
+use crate::inventory_management::{self, ItemContainer, ContainerItemClearer, ContainerSlot, SlotArrayContainer, empty_container_slots, is_container_empty};
+use crate::active_equipment::active_equipment as ActiveEquipmentTableTrait;
+use crate::environment::calculate_chunk_index;
+use crate::models::{ContainerType, ContainerLocationData, ItemLocation};
+use crate::player_inventory::find_first_empty_player_slot;
+use crate::spatial_grid::{self, EntityType};
+use crate::vending_machine::vending_machine as VendingMachineTableTrait;
+use crate::vending_machine::vending_order as VendingOrderTableTrait;
+
+// --- Constants ---
+pub(crate) const VENDING_MACHINE_COLLISION_RADIUS: f32 = 24.0;
+pub(crate) const VENDING_MACHINE_COLLISION_Y_OFFSET: f32 = 10.0;
+const VENDING_MACHINE_INTERACTION_DISTANCE_SQUARED: f32 = 80.0 * 80.0;
+pub const NUM_VENDING_MACHINE_SLOTS: usize = 18;
+const MAX_ORDERS_PER_VENDING_MACHINE: usize = 8;
+const VENDING_MACHINE_ITEM_NAME: &str = "Vending Machine";
+
+/// --- Vending Machine ---
+/// A placed shop holding its stock, and the payments it has taken, in one slot array.
+#[spacetimedb::table(name = vending_machine, public)]
+#[derive(Clone, Debug)]
+pub struct VendingMachine {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub chunk_index: u32,
+
+    pub placed_by: Identity,
+    pub placed_at: Timestamp,
+
+    pub slots: Vec<ContainerSlot>,
+}
+
+/// --- Vending Order ---
+/// One listing on a vending machine: `sell_quantity` of the sold item for
+/// `price_quantity` of the price item.
+#[spacetimedb::table(name = vending_order, public)]
+#[derive(Clone, Debug)]
+pub struct VendingOrder {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub machine_id: u32,
+    pub sell_item_def_id: u64,
+    pub sell_quantity: u32,
+    pub price_item_def_id: u64,
+    pub price_quantity: u32,
+    pub created_at: Timestamp,
+}
+
+/******************************************************************************
+ *                     REDUCERS (Stock Handling, Owner Only)                  *
+ ******************************************************************************/
+
+/// Moves an item from the owner's inventory/hotbar INTO a machine slot.
+#[spacetimedb::reducer]
+pub fn move_item_to_vending_machine(ctx: &ReducerContext, machine_id: u32, target_slot_index: u8, item_instance_id: u64) -> Result<(), String> {
+    let (_player, mut machine) = validate_vending_stock_access(ctx, machine_id)?;
+    inventory_management::handle_move_to_container_slot(ctx, &mut machine, target_slot_index, item_instance_id)?;
+    ctx.db.vending_machine().id().update(machine);
+    Ok(())
+}
+
+/// Moves an item FROM a machine slot INTO the owner's inventory/hotbar.
+#[spacetimedb::reducer]
+pub fn move_item_from_vending_machine(
+    ctx: &ReducerContext,
+    machine_id: u32,
+    source_slot_index: u8,
+    target_slot_type: String,
+    target_slot_index: u32
+) -> Result<(), String> {
+    let (_player, mut machine) = validate_vending_stock_access(ctx, machine_id)?;
+    inventory_management::handle_move_from_container_slot(ctx, &mut machine, source_slot_index, target_slot_type, target_slot_index)?;
+    ctx.db.vending_machine().id().update(machine);
+    Ok(())
+}
+
+/// Moves an item BETWEEN two slots within the same machine.
+#[spacetimedb::reducer]
+pub fn move_item_within_vending_machine(ctx: &ReducerContext, machine_id: u32, source_slot_index: u8, target_slot_index: u8) -> Result<(), String> {
+    let (_player, mut machine) = validate_vending_stock_access(ctx, machine_id)?;
+    inventory_management::handle_move_within_container(ctx, &mut machine, source_slot_index, target_slot_index)?;
+    ctx.db.vending_machine().id().update(machine);
+    Ok(())
+}
+
+/// Splits a stack FROM one machine slot TO another within the same machine.
+#[spacetimedb::reducer]
+pub fn split_stack_within_vending_machine(
+    ctx: &ReducerContext,
+    machine_id: u32,
+    source_slot_index: u8,
+    target_slot_index: u8,
+    quantity_to_split: u32,
+) -> Result<(), String> {
+    let (_player, mut machine) = validate_vending_stock_access(ctx, machine_id)?;
+    inventory_management::handle_split_within_container(ctx, &mut machine, source_slot_index, target_slot_index, quantity_to_split)?;
+    ctx.db.vending_machine().id().update(machine);
+    Ok(())
+}
+
+/// Quickly moves an item FROM a machine slot TO the owner's inventory.
+#[spacetimedb::reducer]
+pub fn quick_move_from_vending_machine(ctx: &ReducerContext, machine_id: u32, source_slot_index: u8) -> Result<(), String> {
+    let (_player, mut machine) = validate_vending_stock_access(ctx, machine_id)?;
+    inventory_management::handle_quick_move_from_container(ctx, &mut machine, source_slot_index)?;
+    ctx.db.vending_machine().id().update(machine);
+    Ok(())
+}
+
+/// Quickly moves an item FROM the owner's inventory/hotbar TO the first available/mergeable machine slot.
+#[spacetimedb::reducer]
+pub fn quick_move_to_vending_machine(ctx: &ReducerContext, machine_id: u32, item_instance_id: u64) -> Result<(), String> {
+    let (_player, mut machine) = validate_vending_stock_access(ctx, machine_id)?;
+    inventory_management::handle_quick_move_to_container(ctx, &mut machine, item_instance_id)?;
+    ctx.db.vending_machine().id().update(machine);
+    Ok(())
+}
+
+/******************************************************************************
+ *                          REDUCERS (Orders & Trading)                       *
+ ******************************************************************************/
+
+/// --- Add Vending Order ---
+/// Lists a new sell order on the sender's machine. Payment must be a stackable item.
+#[spacetimedb::reducer]
+pub fn add_vending_order(
+    ctx: &ReducerContext,
+    machine_id: u32,
+    sell_item_def_id: u64,
+    sell_quantity: u32,
+    price_item_def_id: u64,
+    price_quantity: u32,
+) -> Result<(), String> {
+    validate_vending_stock_access(ctx, machine_id)?;
+    let item_defs = ctx.db.item_definition();
+    let sell_def = item_defs.id().find(sell_item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found.", sell_item_def_id))?;
+    let price_def = item_defs.id().find(price_item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found.", price_item_def_id))?;
+    if sell_quantity == 0 || price_quantity == 0 {
+        return Err("Order quantities must be at least 1.".to_string());
+    }
+    if !price_def.is_stackable {
+        return Err(format!("{} can't be used as payment.", price_def.name));
+    }
+    if sell_item_def_id == price_item_def_id {
+        return Err("An order can't sell an item for itself.".to_string());
+    }
+    let orders = ctx.db.vending_order();
+    if orders.machine_id().filter(machine_id).count() >= MAX_ORDERS_PER_VENDING_MACHINE {
+        return Err(format!("A vending machine can list at most {} orders.", MAX_ORDERS_PER_VENDING_MACHINE));
+    }
+
+    orders.insert(VendingOrder {
+        id: 0, // Auto-incremented
+        machine_id,
+        sell_item_def_id,
+        sell_quantity,
+        price_item_def_id,
+        price_quantity,
+        created_at: ctx.timestamp,
+    });
+    log::info!("[VendingMachine] Player {:?} listed {} {} for {} {} on machine {}.",
+              ctx.sender, sell_quantity, sell_def.name, price_quantity, price_def.name, machine_id);
+    Ok(())
+}
+
+/// --- Remove Vending Order ---
+#[spacetimedb::reducer]
+pub fn remove_vending_order(ctx: &ReducerContext, order_id: u64) -> Result<(), String> {
+    let order = ctx.db.vending_order().id().find(order_id)
+        .ok_or_else(|| format!("Order {} not found.", order_id))?;
+    validate_vending_stock_access(ctx, order.machine_id)?;
+    ctx.db.vending_order().id().delete(order_id);
+    log::info!("[VendingMachine] Player {:?} removed order {} from machine {}.", ctx.sender, order_id, order.machine_id);
+    Ok(())
+}
+
+/// --- Buy From Vending Machine ---
+/// Executes an order once: the machine's stock goes to the buyer and the buyer's payment
+/// goes into the machine. Fails without moving anything if the machine is out of stock or
+/// full, or the buyer can't pay or has no room.
+#[spacetimedb::reducer]
+pub fn buy_from_vending_machine(ctx: &ReducerContext, order_id: u64) -> Result<(), String> {
+    let buyer_id = ctx.sender;
+    crate::spectator::ensure_not_spectating(ctx, buyer_id)?;
+    let order = ctx.db.vending_order().id().find(order_id)
+        .ok_or_else(|| format!("Order {} not found.", order_id))?;
+    let (buyer, mut machine) = validate_vending_machine_interaction(ctx, order.machine_id)?;
+    if buyer.is_dead {
+        return Err("Cannot trade while dead.".to_string());
+    }
+    if machine.placed_by == buyer_id {
+        return Err("You can't buy from your own vending machine.".to_string());
+    }
+    let item_defs = ctx.db.item_definition();
+    let sell_def = item_defs.id().find(order.sell_item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found.", order.sell_item_def_id))?;
+    let price_def = item_defs.id().find(order.price_item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found.", order.price_item_def_id))?;
+
+    // --- Validate the whole trade before moving anything ---
+    if count_stock(ctx, &machine, sell_def.id) < order.sell_quantity {
+        return Err(format!("This vending machine is out of {}.", sell_def.name));
+    }
+    if count_carried(ctx, buyer_id, price_def.id) < order.price_quantity {
+        return Err(format!("You need {} {} to buy this.", order.price_quantity, price_def.name));
+    }
+    if player_inventory_space_for_item(ctx, buyer_id, &sell_def) < order.sell_quantity {
+        return Err("Not enough room in your inventory.".to_string());
+    }
+    if machine_space_for_item(ctx, &machine, &price_def) < order.price_quantity {
+        return Err("This vending machine is full and can't take payment.".to_string());
+    }
+
+    // --- Exchange ---
+    crate::active_effects::cancel_spawn_protection(ctx, buyer_id);
+    take_payment(ctx, buyer_id, &price_def, order.price_quantity)?;
+    hand_over_stock(ctx, &mut machine, buyer_id, &sell_def, order.sell_quantity)?;
+    deposit_payment(ctx, &mut machine, &price_def, order.price_quantity)?;
+    ctx.db.vending_machine().id().update(machine);
+
+    log::info!("[VendingMachine] Player {:?} bought {} {} for {} {} from machine {} (order {}).",
+              buyer_id, order.sell_quantity, sell_def.name, order.price_quantity, price_def.name, order.machine_id, order_id);
+    Ok(())
+}
+
+/******************************************************************************
+ *                       REDUCERS (Machine-Specific Logic)                    *
+ ******************************************************************************/
+
+/// --- Place Vending Machine ---
+/// Places a Vending Machine item from the player's inventory/hotbar into the world.
+#[spacetimedb::reducer]
+pub fn place_vending_machine(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    crate::placement::place_deployable(ctx, item_instance_id, world_x, world_y, |name| name == VENDING_MACHINE_ITEM_NAME)
+}
+
+/// Creates an empty vending machine. The placement constructor for Vending Machine items.
+pub(crate) fn spawn_vending_machine(ctx: &ReducerContext, placed_by: Identity, _item_def: &ItemDefinition, _item_durability: Option<f32>, world_x: f32, world_y: f32) -> Result<(), String> {
+    let inserted = ctx.db.vending_machine().insert(VendingMachine {
+        id: 0, // Auto-incremented
+        pos_x: world_x,
+        pos_y: world_y,
+        chunk_index: calculate_chunk_index(world_x, world_y),
+        placed_by,
+        placed_at: ctx.timestamp,
+        slots: empty_container_slots(NUM_VENDING_MACHINE_SLOTS),
+    });
+    spatial_grid::track_entity(ctx, EntityType::VendingMachine(inserted.id), world_x, world_y);
+    log::info!("Player {:?} placed Vending Machine {} at ({:.1}, {:.1}).", placed_by, inserted.id, world_x, world_y);
+    Ok(())
+}
+
+/// --- Interact with Vending Machine ---
+/// Anyone in reach can open a machine to see its orders; only the owner sees the stock.
+#[spacetimedb::reducer]
+pub fn interact_with_vending_machine(ctx: &ReducerContext, machine_id: u32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    validate_vending_machine_interaction(ctx, machine_id)?;
+    log::debug!("Player {:?} interaction check OK for vending machine {}", ctx.sender, machine_id);
+    Ok(())
+}
+
+/// --- Pickup Vending Machine ---
+/// Picks up an *empty* machine, returning it to its owner's inventory. Its orders are removed.
+#[spacetimedb::reducer]
+pub fn pickup_vending_machine(ctx: &ReducerContext, machine_id: u32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let (_player, machine) = validate_vending_stock_access(ctx, machine_id)?;
+    if !is_container_empty(&machine) {
+        return Err("Cannot pick up vending machine: It is not empty.".to_string());
+    }
+
+    let machine_item_def = ctx.db.item_definition().iter()
+        .find(|def| def.name == VENDING_MACHINE_ITEM_NAME)
+        .ok_or_else(|| format!("ItemDefinition for '{}' not found.", VENDING_MACHINE_ITEM_NAME))?;
+    add_item_to_player_inventory(ctx, sender_id, machine_item_def.id, 1)
+        .map_err(|e| format!("Could not add {} to your inventory: {}", VENDING_MACHINE_ITEM_NAME, e))?;
+
+    let orders = ctx.db.vending_order();
+    let order_ids: Vec<u64> = orders.machine_id().filter(machine_id).map(|o| o.id).collect();
+    for order_id in order_ids {
+        orders.id().delete(order_id);
+    }
+    ctx.db.vending_machine().id().delete(machine_id);
+    spatial_grid::untrack_entity(ctx, EntityType::VendingMachine(machine_id));
+    log::info!("Vending machine {} picked up by player {:?}.", machine_id, sender_id);
+    Ok(())
+}
+
+/******************************************************************************
+ *                            TRAIT IMPLEMENTATIONS                           *
+ ******************************************************************************/
+
+impl SlotArrayContainer for VendingMachine {
+    fn slots(&self) -> &[ContainerSlot] {
+        &self.slots
+    }
+
+    fn slots_mut(&mut self) -> &mut [ContainerSlot] {
+        &mut self.slots
+    }
+
+    fn slot_container_type(&self) -> ContainerType {
+        ContainerType::VendingMachine
+    }
+
+    fn slot_container_id(&self) -> u64 {
+        self.id as u64
+    }
+}
+
+/// --- Helper struct to implement the ContainerItemClearer trait for VendingMachine ---
+pub struct VendingMachineClearer;
+
+impl ContainerItemClearer for VendingMachineClearer {
+    fn clear_item(ctx: &ReducerContext, item_instance_id: u64) -> bool {
+        let machines = ctx.db.vending_machine();
+        for mut machine in machines.iter() {
+            let Some(slot_index) = (0..machine.num_slots() as u8).find(|&i| machine.get_slot_instance_id(i) == Some(item_instance_id)) else {
+                continue;
+            };
+            log::debug!("[VendingMachineClearer] Found item {} in machine {} slot {}. Clearing slot.", item_instance_id, machine.id, slot_index);
+            machine.set_slot(slot_index, None, None);
+            machines.id().update(machine);
+
+            let inventory_items = ctx.db.inventory_item();
+            if let Some(mut item) = inventory_items.instance_id().find(item_instance_id) {
+                item.location = ItemLocation::Unknown;
+                inventory_items.instance_id().update(item);
+            }
+            return true;
+        }
+        false
+    }
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Validates that the machine exists and is within the sender's reach.
+fn validate_vending_machine_interaction(ctx: &ReducerContext, machine_id: u32) -> Result<(Player, VendingMachine), String> {
+    let player = ctx.db.player().identity().find(ctx.sender).ok_or_else(|| "Player not found".to_string())?;
+    let machine = ctx.db.vending_machine().id().find(machine_id)
+        .ok_or_else(|| format!("Vending machine {} not found", machine_id))?;
+
+    let dx = player.position_x - machine.pos_x;
+    let dy = player.position_y - machine.pos_y;
+    if (dx * dx + dy * dy) > VENDING_MACHINE_INTERACTION_DISTANCE_SQUARED {
+        return Err("Too far away".to_string());
+    }
+    Ok((player, machine))
+}
+
+/// Like `validate_vending_machine_interaction`, but the sender must also own the machine.
+fn validate_vending_stock_access(ctx: &ReducerContext, machine_id: u32) -> Result<(Player, VendingMachine), String> {
+    let (player, machine) = validate_vending_machine_interaction(ctx, machine_id)?;
+    if machine.placed_by != ctx.sender {
+        return Err("Only the vending machine's owner can manage it.".to_string());
+    }
+    Ok((player, machine))
+}
+
+/// Stock items of `item_def_id` in the machine, as (slot, item).
+fn stock_items(ctx: &ReducerContext, machine: &VendingMachine, item_def_id: u64) -> Vec<(u8, InventoryItem)> {
+    let inventory_items = ctx.db.inventory_item();
+    (0..machine.num_slots() as u8)
+        .filter(|&i| machine.get_slot_def_id(i) == Some(item_def_id))
+        .filter_map(|i| machine.get_slot_instance_id(i)
+            .and_then(|instance_id| inventory_items.instance_id().find(instance_id))
+            .map(|item| (i, item)))
+        .collect()
+}
+
+fn count_stock(ctx: &ReducerContext, machine: &VendingMachine, item_def_id: u64) -> u32 {
+    stock_items(ctx, machine, item_def_id).iter().map(|(_, item)| item.quantity).sum()
+}
+
+/// The player's hotbar and inventory stacks of `item_def_id`.
+fn carried_items(ctx: &ReducerContext, player_id: Identity, item_def_id: u64) -> Vec<InventoryItem> {
+    ctx.db.inventory_item().iter()
+        .filter(|item| item.item_def_id == item_def_id && match &item.location {
+            ItemLocation::Inventory(data) => data.owner_id == player_id,
+            ItemLocation::Hotbar(data) => data.owner_id == player_id,
+            _ => false,
+        })
+        .collect()
+}
+
+fn count_carried(ctx: &ReducerContext, player_id: Identity, item_def_id: u64) -> u32 {
+    carried_items(ctx, player_id, item_def_id).iter().map(|item| item.quantity).sum()
+}
+
+/// How much of a stackable item the machine can take: room in its existing stacks plus a
+/// full stack per empty slot.
+fn machine_space_for_item(ctx: &ReducerContext, machine: &VendingMachine, item_def: &ItemDefinition) -> u32 {
+    let stack_room: u32 = stock_items(ctx, machine, item_def.id).iter()
+        .map(|(_, item)| item_def.stack_size.saturating_sub(item.quantity))
+        .sum();
+    let empty_slots = (0..machine.num_slots() as u8).filter(|&i| machine.get_slot_instance_id(i).is_none()).count() as u32;
+    stack_room + empty_slots * item_def.stack_size
+}
+
+/// Removes `quantity` of the (stackable) payment item from the buyer's hotbar and inventory.
+fn take_payment(ctx: &ReducerContext, buyer_id: Identity, price_def: &ItemDefinition, quantity: u32) -> Result<(), String> {
+    let inventory_items = ctx.db.inventory_item();
+    let mut remaining = quantity;
+    for mut item in carried_items(ctx, buyer_id, price_def.id) {
+        if remaining == 0 {
+            break;
+        }
+        let taken = remaining.min(item.quantity);
+        remaining -= taken;
+        if taken < item.quantity {
+            item.quantity -= taken;
+            inventory_items.instance_id().update(item);
+            continue;
+        }
+        let is_held = ctx.db.active_equipment().player_identity().find(buyer_id)
+            .is_some_and(|e| e.equipped_item_instance_id == Some(item.instance_id));
+        if is_held {
+            crate::active_equipment::clear_active_item_reducer(ctx, buyer_id)?;
+        }
+        inventory_items.instance_id().delete(item.instance_id);
+    }
+    if remaining > 0 {
+        return Err(format!("Not enough {} to pay.", price_def.name));
+    }
+    Ok(())
+}
+
+/// Moves `quantity` of the sold item from the machine to the buyer. Stackable stock is
+/// split off and added to the buyer's inventory; non-stackable items are moved as-is so
+/// their durability carries over.
+fn hand_over_stock(ctx: &ReducerContext, machine: &mut VendingMachine, buyer_id: Identity, sell_def: &ItemDefinition, quantity: u32) -> Result<(), String> {
+    let inventory_items = ctx.db.inventory_item();
+    let mut remaining = quantity;
+    for (slot_index, mut item) in stock_items(ctx, machine, sell_def.id) {
+        if remaining == 0 {
+            break;
+        }
+        if sell_def.is_stackable {
+            let taken = remaining.min(item.quantity);
+            remaining -= taken;
+            if taken < item.quantity {
+                item.quantity -= taken;
+                inventory_items.instance_id().update(item);
+            } else {
+                machine.set_slot(slot_index, None, None);
+                inventory_items.instance_id().delete(item.instance_id);
+            }
+            add_item_to_player_inventory(ctx, buyer_id, sell_def.id, taken)?;
+        } else {
+            let location = find_first_empty_player_slot(ctx, buyer_id)
+                .ok_or_else(|| "Not enough room in your inventory.".to_string())?;
+            machine.set_slot(slot_index, None, None);
+            item.location = location;
+            inventory_items.instance_id().update(item);
+            remaining -= 1;
+        }
+    }
+    if remaining > 0 {
+        return Err(format!("This vending machine is out of {}.", sell_def.name));
+    }
+    Ok(())
+}
+
+/// Puts `quantity` of the (stackable) payment item into the machine, topping up existing
+/// stacks before starting new ones.
+fn deposit_payment(ctx: &ReducerContext, machine: &mut VendingMachine, price_def: &ItemDefinition, quantity: u32) -> Result<(), String> {
+    let inventory_items = ctx.db.inventory_item();
+    let mut remaining = quantity;
+    for (_, mut item) in stock_items(ctx, machine, price_def.id) {
+        if remaining == 0 {
+            break;
+        }
+        let added = remaining.min(price_def.stack_size.saturating_sub(item.quantity));
+        if added > 0 {
+            item.quantity += added;
+            remaining -= added;
+            inventory_items.instance_id().update(item);
+        }
+    }
+    while remaining > 0 {
+        let slot_index = (0..machine.num_slots() as u8).find(|&i| machine.get_slot_instance_id(i).is_none())
+            .ok_or_else(|| "This vending machine is full and can't take payment.".to_string())?;
+        let added = remaining.min(price_def.stack_size.max(1));
+        let inserted = inventory_items.insert(InventoryItem {
+            instance_id: 0, // Auto-incremented
+            item_def_id: price_def.id,
+            quantity: added,
+            location: ItemLocation::Container(ContainerLocationData {
+                container_type: ContainerType::VendingMachine,
+                container_id: machine.id as u64,
+                slot_index,
+            }),
+            durability: None,
+            is_locked: false,
+            charges: None,
+        });
+        machine.set_slot(slot_index, Some(inserted.instance_id), Some(price_def.id));
+        remaining -= added;
+    }
+    Ok(())
+}
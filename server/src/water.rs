@@ -0,0 +1,169 @@
+/******************************************************************************
+ *                                                                            *
+ * Drinking water. Players can drink straight from a lake or river tile with  *
+ * `drink_from_world`, or carry water in a Water Jug: `fill_water_jug` at the *
+ * shore gives a Dirty Water Jug, which boils into a Boiled Water Jug over a  *
+ * campfire (cooking.rs). A filled jug holds WATER_JUG_CHARGES drinks, stored *
+ * in the item's `charges`, and turns back into an empty jug when drained.    *
+ * Unboiled water, from the world or a dirty jug, can give dysentery.         *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, TimeDuration, log};
+use rand::Rng;
+
+use crate::Player;
+use crate::player as PlayerTableTrait;
+use crate::items::{InventoryItem, inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
+use crate::consumables::MAX_STAT_VALUE;
+
+// --- Constants ---
+const EMPTY_WATER_JUG_NAME: &str = "Water Jug";
+const DIRTY_WATER_JUG_NAME: &str = "Dirty Water Jug";
+const BOILED_WATER_JUG_NAME: &str = "Boiled Water Jug";
+/// Drinks in a freshly filled jug.
+const WATER_JUG_CHARGES: u32 = 5;
+/// Farthest from the player a water tile can be drunk from or filled at.
+const MAX_WATER_REACH: f32 = 96.0;
+/// Thirst restored per sip from a lake or river.
+const WORLD_DRINK_THIRST: f32 = 10.0;
+const DRINK_COOLDOWN_MICROS: i64 = 1_000_000;
+/// Chance that a drink of unboiled water gives dysentery.
+const DYSENTERY_CHANCE: f32 = 0.08;
+const DYSENTERY_DURATION_SECS: f32 = 60.0;
+/// Thirst drained over the whole of a dysentery.
+const DYSENTERY_THIRST_LOSS: f32 = 30.0;
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Drink From World ---
+/// Drinks from the water tile at (target_x, target_y). Restores a little thirst per sip,
+/// but the water is unboiled.
+#[spacetimedb::reducer]
+pub fn drink_from_world(ctx: &ReducerContext, target_x: f32, target_y: f32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let mut player = find_thirsty_player(ctx, ctx.sender)?;
+    check_water_in_reach(ctx, &player, target_x, target_y)?;
+
+    drink(ctx, &mut player, WORLD_DRINK_THIRST, false);
+    ctx.db.player().identity().update(player);
+    Ok(())
+}
+
+/// --- Fill Water Jug ---
+/// Fills a jug at the water tile at (target_x, target_y). Any water already in it is
+/// topped up with unboiled water, so the jug always comes out dirty and full.
+#[spacetimedb::reducer]
+pub fn fill_water_jug(ctx: &ReducerContext, item_instance_id: u64, target_x: f32, target_y: f32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or_else(|| "Player not found".to_string())?;
+    if player.is_dead {
+        return Err("Cannot fill a jug while dead.".to_string());
+    }
+    let mut jug = crate::player_inventory::get_player_item(ctx, item_instance_id)?;
+    let jug_name = item_name(ctx, &jug)?;
+    if ![EMPTY_WATER_JUG_NAME, DIRTY_WATER_JUG_NAME, BOILED_WATER_JUG_NAME].contains(&jug_name.as_str()) {
+        return Err(format!("{} cannot hold water.", jug_name));
+    }
+    if jug_name == DIRTY_WATER_JUG_NAME && charges_left(&jug) >= WATER_JUG_CHARGES {
+        return Err("The jug is already full.".to_string());
+    }
+    check_water_in_reach(ctx, &player, target_x, target_y)?;
+
+    jug.item_def_id = find_def_id(ctx, DIRTY_WATER_JUG_NAME)?;
+    jug.charges = Some(WATER_JUG_CHARGES);
+    ctx.db.inventory_item().instance_id().update(jug);
+    log::info!("[Water] Player {:?} filled water jug {}.", ctx.sender, item_instance_id);
+    Ok(())
+}
+
+/// --- Drink From Water Jug ---
+/// Takes one drink from a filled jug. Dirty water can give dysentery; boiled water is safe.
+#[spacetimedb::reducer]
+pub fn drink_from_water_jug(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let mut player = find_thirsty_player(ctx, ctx.sender)?;
+    let mut jug = crate::player_inventory::get_player_item(ctx, item_instance_id)?;
+    let jug_def = ctx.db.item_definition().id().find(jug.item_def_id)
+        .ok_or_else(|| format!("Definition for item ID {} not found.", jug.item_def_id))?;
+    let is_boiled = match jug_def.name.as_str() {
+        BOILED_WATER_JUG_NAME => true,
+        DIRTY_WATER_JUG_NAME => false,
+        EMPTY_WATER_JUG_NAME => return Err("The jug is empty. Fill it at a lake or river.".to_string()),
+        _ => return Err(format!("{} is not a water jug.", jug_def.name)),
+    };
+
+    drink(ctx, &mut player, jug_def.consumable_thirst_quenched.unwrap_or(0.0), is_boiled);
+    ctx.db.player().identity().update(player);
+
+    let remaining = charges_left(&jug).saturating_sub(1);
+    if remaining == 0 {
+        jug.item_def_id = find_def_id(ctx, EMPTY_WATER_JUG_NAME)?;
+        jug.charges = None;
+    } else {
+        jug.charges = Some(remaining);
+    }
+    ctx.db.inventory_item().instance_id().update(jug);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// The player, if they are alive and not drinking faster than the drink cooldown allows.
+fn find_thirsty_player(ctx: &ReducerContext, player_id: Identity) -> Result<Player, String> {
+    let player = ctx.db.player().identity().find(player_id)
+        .ok_or_else(|| "Player not found".to_string())?;
+    if player.is_dead {
+        return Err("Cannot drink while dead.".to_string());
+    }
+    if let Some(last_consumed_at) = player.last_consumed_at {
+        if ctx.timestamp < last_consumed_at + TimeDuration::from_micros(DRINK_COOLDOWN_MICROS) {
+            return Err("You are drinking too quickly.".to_string());
+        }
+    }
+    Ok(player)
+}
+
+fn check_water_in_reach(ctx: &ReducerContext, player: &Player, target_x: f32, target_y: f32) -> Result<(), String> {
+    let dx = target_x - player.position_x;
+    let dy = target_y - player.position_y;
+    if dx * dx + dy * dy > MAX_WATER_REACH * MAX_WATER_REACH {
+        return Err("The water is too far away.".to_string());
+    }
+    if !crate::world_tile::is_water_at(ctx, target_x, target_y) {
+        return Err("There is no water there.".to_string());
+    }
+    Ok(())
+}
+
+/// Restores thirst and, for unboiled water, rolls for dysentery. The caller saves the player.
+fn drink(ctx: &ReducerContext, player: &mut Player, thirst: f32, is_boiled: bool) {
+    player.thirst = (player.thirst + thirst).min(MAX_STAT_VALUE);
+    player.last_consumed_at = Some(ctx.timestamp);
+    if !is_boiled && ctx.rng().gen::<f32>() < DYSENTERY_CHANCE {
+        crate::active_effects::apply_dysentery(ctx, player.identity, DYSENTERY_THIRST_LOSS, DYSENTERY_DURATION_SECS);
+    }
+}
+
+/// Drinks left in a jug. A filled jug without a count is treated as full.
+fn charges_left(jug: &InventoryItem) -> u32 {
+    jug.charges.unwrap_or(WATER_JUG_CHARGES)
+}
+
+fn item_name(ctx: &ReducerContext, item: &InventoryItem) -> Result<String, String> {
+    ctx.db.item_definition().id().find(item.item_def_id)
+        .map(|def| def.name)
+        .ok_or_else(|| format!("Definition for item ID {} not found.", item.item_def_id))
+}
+
+fn find_def_id(ctx: &ReducerContext, name: &str) -> Result<u64, String> {
+    ctx.db.item_definition().iter()
+        .find(|def| def.name == name)
+        .map(|def| def.id)
+        .ok_or_else(|| format!("Item definition '{}' not found.", name))
+}
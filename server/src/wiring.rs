@@ -0,0 +1,243 @@
+/******************************************************************************
+ *                                                                            *
+ * Simple base automation. A Switch is a placeable lever its owner links to   *
+ * their own campfires and furnaces nearby with `link_switch`. Flipping it    *
+ * with `toggle_switch` lights or puts out every linked appliance at once,    *
+ * through the same logic as the appliances' own toggles, so an appliance     *
+ * without fuel simply stays out. Links to appliances that were destroyed or  *
+ * picked up are dropped the next time the switch is flipped. Switches are    *
+ * damaged and destroyed through combat.rs like other placed structures.      *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+
+use crate::player as PlayerTableTrait;
+use crate::items::ItemDefinition;
+use crate::models::ContainerType;
+use crate::environment::calculate_chunk_index;
+use crate::spatial_grid::{self, EntityType};
+use crate::campfire::campfire as CampfireTableTrait;
+use crate::furnace::furnace as FurnaceTableTrait;
+use crate::wiring::wiring_switch as WiringSwitchTableTrait;
+use crate::wiring::switch_link as SwitchLinkTableTrait;
+
+// --- Constants ---
+pub(crate) const SWITCH_COLLISION_RADIUS: f32 = 14.0;
+pub(crate) const SWITCH_COLLISION_Y_OFFSET: f32 = 0.0;
+const SWITCH_MAX_HEALTH: f32 = 200.0;
+pub(crate) const SWITCH_ITEM_NAME: &str = "Switch";
+/// Players must be this close to a switch to flip or link it.
+const SWITCH_INTERACTION_DISTANCE_SQUARED: f32 = 96.0 * 96.0;
+/// Linked appliances must be within this distance of the switch.
+const MAX_LINK_DISTANCE_SQUARED: f32 = 600.0 * 600.0;
+const MAX_LINKS_PER_SWITCH: usize = 8;
+
+/// --- Wiring Switch ---
+/// A placed switch and whether it is currently on.
+#[spacetimedb::table(name = wiring_switch, public)]
+#[derive(Clone, Debug)]
+pub struct WiringSwitch {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub chunk_index: u32,
+    pub placed_by: Identity,
+    pub placed_at: Timestamp,
+    pub is_on: bool,
+    pub health: f32,
+    pub max_health: f32,
+    pub is_destroyed: bool,
+    pub destroyed_at: Option<Timestamp>,
+    pub last_hit_time: Option<Timestamp>,
+}
+
+/// --- Switch Link ---
+/// Wires a campfire or furnace to a switch. Public so clients can draw the wires.
+#[spacetimedb::table(name = switch_link, public)]
+#[derive(Clone, Debug)]
+pub struct SwitchLink {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub switch_id: u32,
+    pub target_type: ContainerType, // Campfire or Furnace
+    pub target_id: u32,
+    pub linked_at: Timestamp,
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Place Switch ---
+/// Consumes a Switch from the player's inventory/hotbar and places it, switched off.
+#[spacetimedb::reducer]
+pub fn place_switch(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    crate::placement::place_deployable(ctx, item_instance_id, world_x, world_y, |name| name == SWITCH_ITEM_NAME)
+}
+
+/// Creates a switch that is off. The placement constructor for Switch items.
+pub(crate) fn spawn_switch(ctx: &ReducerContext, placed_by: Identity, _item_def: &ItemDefinition, _item_durability: Option<f32>, world_x: f32, world_y: f32) -> Result<(), String> {
+    let inserted = ctx.db.wiring_switch().try_insert(WiringSwitch {
+        id: 0, // Auto-incremented
+        pos_x: world_x,
+        pos_y: world_y,
+        chunk_index: calculate_chunk_index(world_x, world_y),
+        placed_by,
+        placed_at: ctx.timestamp,
+        is_on: false,
+        health: SWITCH_MAX_HEALTH,
+        max_health: SWITCH_MAX_HEALTH,
+        is_destroyed: false,
+        destroyed_at: None,
+        last_hit_time: None,
+    }).map_err(|e| format!("Failed to insert switch entity: {}", e))?;
+    spatial_grid::track_entity(ctx, EntityType::Switch(inserted.id), inserted.pos_x, inserted.pos_y);
+    log::info!("[PlaceSwitch] Player {:?} placed Switch {} at ({:.1}, {:.1}).", placed_by, inserted.id, world_x, world_y);
+    Ok(())
+}
+
+/// --- Link Switch ---
+/// Wires one of the sender's campfires or furnaces to their switch. The sender must be at
+/// the switch, and the appliance within MAX_LINK_DISTANCE of it.
+#[spacetimedb::reducer]
+pub fn link_switch(ctx: &ReducerContext, switch_id: u32, target_type: ContainerType, target_id: u32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let switch = validate_switch_interaction(ctx, switch_id)?;
+    if switch.placed_by != ctx.sender {
+        return Err("Only the player who placed this switch can wire it.".to_string());
+    }
+    let (target_x, target_y, target_owner) = find_target(ctx, target_type, target_id)?;
+    if target_owner != ctx.sender {
+        return Err(format!("You can only wire your own {:?}.", target_type));
+    }
+    let dx = target_x - switch.pos_x;
+    let dy = target_y - switch.pos_y;
+    if dx * dx + dy * dy > MAX_LINK_DISTANCE_SQUARED {
+        return Err(format!("The {:?} is too far from the switch.", target_type));
+    }
+
+    let links = ctx.db.switch_link();
+    let existing: Vec<SwitchLink> = links.switch_id().filter(switch_id).collect();
+    if existing.iter().any(|l| l.target_type == target_type && l.target_id == target_id) {
+        return Err("Already wired to this switch.".to_string());
+    }
+    if existing.len() >= MAX_LINKS_PER_SWITCH {
+        return Err(format!("A switch can be wired to at most {} appliances.", MAX_LINKS_PER_SWITCH));
+    }
+
+    links.insert(SwitchLink {
+        id: 0, // Auto-incremented
+        switch_id,
+        target_type,
+        target_id,
+        linked_at: ctx.timestamp,
+    });
+    log::info!("[Wiring] Player {:?} wired {:?} {} to switch {}.", ctx.sender, target_type, target_id, switch_id);
+    Ok(())
+}
+
+/// --- Unlink Switch ---
+/// Removes a wire from the sender's switch.
+#[spacetimedb::reducer]
+pub fn unlink_switch(ctx: &ReducerContext, switch_id: u32, target_type: ContainerType, target_id: u32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let switch = validate_switch_interaction(ctx, switch_id)?;
+    if switch.placed_by != ctx.sender {
+        return Err("Only the player who placed this switch can wire it.".to_string());
+    }
+    let links = ctx.db.switch_link();
+    let link = links.switch_id().filter(switch_id)
+        .find(|l| l.target_type == target_type && l.target_id == target_id)
+        .ok_or_else(|| "That isn't wired to this switch.".to_string())?;
+    links.id().delete(link.id);
+    log::info!("[Wiring] Player {:?} unwired {:?} {} from switch {}.", ctx.sender, target_type, target_id, switch_id);
+    Ok(())
+}
+
+/// --- Toggle Switch ---
+/// Flips a switch and lights or puts out everything wired to it. Anyone at the switch can
+/// flip it, just as anyone can light a campfire.
+#[spacetimedb::reducer]
+pub fn toggle_switch(ctx: &ReducerContext, switch_id: u32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
+    let mut switch = validate_switch_interaction(ctx, switch_id)?;
+    switch.is_on = !switch.is_on;
+    let is_on = switch.is_on;
+    ctx.db.wiring_switch().id().update(switch);
+
+    let links = ctx.db.switch_link();
+    let mut switched = 0;
+    for link in links.switch_id().filter(switch_id).collect::<Vec<_>>() {
+        let result = match link.target_type {
+            ContainerType::Campfire => ctx.db.campfire().id().find(link.target_id)
+                .filter(|c| !c.is_destroyed)
+                .map(|campfire| crate::campfire::set_campfire_burning(ctx, campfire, is_on)),
+            ContainerType::Furnace => ctx.db.furnace().id().find(link.target_id)
+                .filter(|f| !f.is_destroyed)
+                .map(|furnace| crate::furnace::set_furnace_burning(ctx, furnace, is_on)),
+            _ => None,
+        };
+        match result {
+            Some(Ok(())) => switched += 1,
+            // Usually out of fuel; the other appliances still switch
+            Some(Err(e)) => log::debug!("[Wiring] Switch {} could not switch {:?} {}: {}", switch_id, link.target_type, link.target_id, e),
+            None => {
+                links.id().delete(link.id);
+            }
+        }
+    }
+    log::info!("[Wiring] Player {:?} turned switch {} {} ({} appliances).",
+        ctx.sender, switch_id, if is_on { "on" } else { "off" }, switched);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Removes every wire from a switch that was destroyed.
+pub(crate) fn remove_switch_links(ctx: &ReducerContext, switch_id: u32) {
+    let links = ctx.db.switch_link();
+    let link_ids: Vec<u64> = links.switch_id().filter(switch_id).map(|l| l.id).collect();
+    for link_id in link_ids {
+        links.id().delete(link_id);
+    }
+}
+
+/// Checks the player is alive and next to an intact switch.
+fn validate_switch_interaction(ctx: &ReducerContext, switch_id: u32) -> Result<WiringSwitch, String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or_else(|| "Player not found".to_string())?;
+    if player.is_dead {
+        return Err("Cannot use switches while dead.".to_string());
+    }
+    let switch = ctx.db.wiring_switch().id().find(switch_id)
+        .filter(|s| !s.is_destroyed)
+        .ok_or_else(|| format!("Switch {} not found.", switch_id))?;
+    let dx = player.position_x - switch.pos_x;
+    let dy = player.position_y - switch.pos_y;
+    if dx * dx + dy * dy > SWITCH_INTERACTION_DISTANCE_SQUARED {
+        return Err("Too far away".to_string());
+    }
+    Ok(switch)
+}
+
+/// Position and owner of an intact campfire or furnace that can be wired.
+fn find_target(ctx: &ReducerContext, target_type: ContainerType, target_id: u32) -> Result<(f32, f32, Identity), String> {
+    match target_type {
+        ContainerType::Campfire => ctx.db.campfire().id().find(target_id)
+            .filter(|c| !c.is_destroyed)
+            .map(|c| (c.pos_x, c.pos_y, c.placed_by))
+            .ok_or_else(|| format!("Campfire {} not found.", target_id)),
+        ContainerType::Furnace => ctx.db.furnace().id().find(target_id)
+            .filter(|f| !f.is_destroyed)
+            .map(|f| (f.pos_x, f.pos_y, f.placed_by))
+            .ok_or_else(|| format!("Furnace {} not found.", target_id)),
+        _ => Err(format!("{:?} can't be wired to a switch.", target_type)),
+    }
+}
@@ -14,9 +14,8 @@ use log;
 pub(crate) const BOX_COLLISION_RADIUS: f32 = 18.0; // Similar to campfire
 pub(crate) const BOX_COLLISION_Y_OFFSET: f32 = 10.0; // Similar to campfire
 pub(crate) const PLAYER_BOX_COLLISION_DISTANCE_SQUARED: f32 = (super::PLAYER_RADIUS + BOX_COLLISION_RADIUS) * (super::PLAYER_RADIUS + BOX_COLLISION_RADIUS);
-const BOX_INTERACTION_DISTANCE_SQUARED: f32 = 64.0 * 64.0; // Similar to campfire interaction
+pub(crate) const BOX_INTERACTION_DISTANCE_SQUARED: f32 = 64.0 * 64.0; // Similar to campfire interaction
 pub const NUM_BOX_SLOTS: usize = 18;
-pub(crate) const BOX_BOX_COLLISION_DISTANCE_SQUARED: f32 = (BOX_COLLISION_RADIUS * 2.0) * (BOX_COLLISION_RADIUS * 2.0);
 
 // --- Import Table Traits and Concrete Types ---
 // Import necessary table traits and concrete types for working with players,
@@ -33,7 +32,6 @@ use crate::inventory_management::{self, ItemContainer, ContainerItemClearer, mer
 use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
 use crate::environment::calculate_chunk_index;
 use crate::models::{ContainerType, ItemLocation, InventoryLocationData, HotbarLocationData, DroppedLocationData, EquippedLocationData, ContainerLocationData};
-use crate::player_inventory::{find_first_empty_player_slot, move_item_to_inventory, move_item_to_hotbar, get_player_item};
 
 /// --- Wooden Storage Box Data Structure ---
 /// Represents a storage box in the game world with position, owner, and
@@ -48,6 +46,7 @@ pub struct WoodenStorageBox {
 
     pub pos_x: f32,
     pub pos_y: f32,
+    #[index(btree)]
     pub chunk_index: u32, // <<< ADDED chunk_index
 
     pub placed_by: Identity, // Who placed this storage box
@@ -357,64 +356,21 @@ pub fn quick_move_to_box(
 
 /// --- Place Wooden Storage Box ---
 /// Places a wooden storage box from the player's inventory into the world at specified coordinates.
-/// Validates item ownership, type, and placement distance before consuming the item and creating
-/// the storage box entity. Uses the generic container system for item management.
+/// Validation, item consumption and placement rules are shared with every other deployable
+/// (see placement.rs); `spawn_wooden_storage_box` creates the entity itself.
 #[spacetimedb::reducer]
 pub fn place_wooden_storage_box(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
-    let sender_id = ctx.sender;
-    let mut inventory_items = ctx.db.inventory_item();
-    let item_defs = ctx.db.item_definition();
-    let mut boxes = ctx.db.wooden_storage_box();
-    let players = ctx.db.player();
-
-    log::info!("Player {:?} attempting to place wooden storage box (item instance {}) at ({}, {}).", sender_id, item_instance_id, world_x, world_y);
-
-    // 1. Validate Player
-    let player = players.identity().find(sender_id)
-        .ok_or_else(|| "Player not found.".to_string())?;
-
-    // 2. Validate Item to be Placed ---
-    // Get the item definition to ensure it's a placeable box
-    let mut item_to_place = get_player_item(ctx, item_instance_id)?;
-    let item_def = item_defs.id().find(item_to_place.item_def_id)
-        .ok_or_else(|| format!("Item definition {} not found for item instance {}.", item_to_place.item_def_id, item_instance_id))?;
-
-    // Check if the item is a Wooden Storage Box and is in player's inventory/hotbar
-    if item_def.name != "Wooden Storage Box" {
-        return Err("Item is not a Wooden Storage Box.".to_string());
-    }
-
-    match &item_to_place.location { 
-        ItemLocation::Inventory(data) => {
-            if data.owner_id != sender_id {
-                return Err("Item to place storage box not owned by player or not in direct possession.".to_string());
-            }
-        }
-        ItemLocation::Hotbar(data) => {
-            if data.owner_id != sender_id {
-                return Err("Item to place storage box not owned by player or not in direct possession.".to_string());
-            }
-        }
-        _ => return Err("Wooden Storage Box must be in inventory or hotbar to be placed.".to_string()),
-    }
-
-    // 3. Validate Placement Location (Collision Checks)
-    let new_chunk_index = calculate_chunk_index(world_x, world_y);
-    if boxes.iter().any(|b| {
-        let dist_sq = (b.pos_x - world_x).powi(2) + (b.pos_y - world_y).powi(2);
-        dist_sq < BOX_BOX_COLLISION_DISTANCE_SQUARED
-    }) {
-        return Err("Too close to another storage box.".to_string());
-    }
-    // Add other collision checks as needed (e.g., with players, other entities)
+    crate::placement::place_deployable(ctx, item_instance_id, world_x, world_y, |name| name == "Wooden Storage Box")
+}
 
-    // 4. Create the WoodenStorageBox entity
+/// Creates an empty storage box. The placement constructor for Wooden Storage Box items.
+pub(crate) fn spawn_wooden_storage_box(ctx: &ReducerContext, placed_by: Identity, _item_def: &ItemDefinition, _item_durability: Option<f32>, world_x: f32, world_y: f32) -> Result<(), String> {
     let new_box = WoodenStorageBox {
         id: 0, // Auto-incremented
         pos_x: world_x,
         pos_y: world_y,
-        chunk_index: new_chunk_index,
-        placed_by: sender_id,
+        chunk_index: calculate_chunk_index(world_x, world_y),
+        placed_by,
         slot_instance_id_0: None, slot_def_id_0: None,
         slot_instance_id_1: None, slot_def_id_1: None,
         slot_instance_id_2: None, slot_def_id_2: None,
@@ -439,20 +395,9 @@ pub fn place_wooden_storage_box(ctx: &ReducerContext, item_instance_id: u64, wor
         destroyed_at: None,
         last_hit_time: None,
     };
-    let inserted_box = boxes.insert(new_box);
-    log::info!("Player {:?} placed new Wooden Storage Box with ID {}.\nLocation: {:?}", sender_id, inserted_box.id, item_to_place.location);
-
-
-    // 5. Consume the item from player's inventory
-    if item_to_place.quantity > 1 {
-        item_to_place.quantity -= 1;
-        inventory_items.instance_id().update(item_to_place);
-    } else {
-        inventory_items.instance_id().delete(item_instance_id);
-    }
-    
-    log::info!("Wooden Storage Box (item instance {}) consumed from player {:?} inventory after placement.", item_instance_id, sender_id);
-
+    let inserted_box = ctx.db.wooden_storage_box().insert(new_box);
+    crate::spatial_grid::track_entity(ctx, crate::spatial_grid::EntityType::WoodenStorageBox(inserted_box.id), inserted_box.pos_x, inserted_box.pos_y);
+    log::info!("Player {:?} placed new Wooden Storage Box with ID {}.", placed_by, inserted_box.id);
     Ok(())
 }
 
@@ -461,6 +406,7 @@ pub fn place_wooden_storage_box(ctx: &ReducerContext, item_instance_id: u64, wor
 /// Uses the helper function for validation before proceeding.
 #[spacetimedb::reducer]
 pub fn interact_with_storage_box(ctx: &ReducerContext, box_id: u32) -> Result<(), String> {
+    crate::spectator::ensure_not_spectating(ctx, ctx.sender)?;
     validate_box_interaction(ctx, box_id)?; // Use helper for validation
     log::debug!("Player {:?} interaction check OK for box {}", ctx.sender, box_id);
     Ok(())
@@ -506,8 +452,13 @@ pub fn pickup_storage_box(ctx: &ReducerContext, box_id: u32) -> Result<(), Strin
         }
     }
 
-    // 5. Delete the WoodenStorageBox entity from the world
+    // 5. Hand back any lock attached to the box
+    crate::locks::return_lock_for_picked_up_target(ctx, sender_id, crate::locks::LockTargetType::WoodenStorageBox, box_id)?;
+
+    // 6. Delete the WoodenStorageBox entity from the world
     boxes_table.id().delete(box_id);
+    crate::spatial_grid::untrack_entity(ctx, crate::spatial_grid::EntityType::WoodenStorageBox(box_id));
+    crate::container_access::remove_container_access(ctx, ContainerType::WoodenStorageBox, box_id);
     log::info!("Storage box {} picked up and removed from world by player {:?}.", box_id, sender_id);
 
     Ok(())
@@ -749,6 +700,11 @@ fn validate_box_interaction(
         return Err(format!("Storage Box {} is destroyed.", box_id));
     }
 
+    // Locked boxes can only be opened by the lock's owner, their team or authorized players
+    crate::locks::check_lock_access(ctx, crate::locks::LockTargetType::WoodenStorageBox, box_id, sender_id)?;
+    // Boxes with an authorization list only open for the owner and those on it
+    crate::container_access::check_container_access(ctx, ContainerType::WoodenStorageBox, box_id, storage_box.placed_by, sender_id)?;
+
     // Check distance between the interacting player and the box
     let dx = player.position_x - storage_box.pos_x;
     let dy = player.position_y - storage_box.pos_y;
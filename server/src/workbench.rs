@@ -0,0 +1,134 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines the Workbench entity, its data structure, and associated logic.    *
+ * Workbenches are crafting stations: recipes with a required station tier    *
+ * (see crafting.rs) can only be queued while the player stands near a        *
+ * workbench of at least that tier, and every tier of the nearest bench       *
+ * speeds up crafting. Workbenches are damaged and destroyed through          *
+ * combat.rs like other placed structures.                                    *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+
+use crate::player as PlayerTableTrait;
+use crate::items::ItemDefinition;
+use crate::environment::calculate_chunk_index;
+use crate::spatial_grid::{self, EntityType};
+use crate::workbench::workbench as WorkbenchTableTrait;
+
+// --- Constants ---
+pub(crate) const WORKBENCH_COLLISION_RADIUS: f32 = 24.0;
+pub(crate) const WORKBENCH_COLLISION_Y_OFFSET: f32 = 0.0;
+/// Players must be this close to a workbench to use it for crafting.
+pub(crate) const WORKBENCH_CRAFTING_RANGE: f32 = 200.0;
+/// Crafting time reduction per tier of the nearest workbench (10% per tier).
+const CRAFT_TIME_REDUCTION_PER_TIER: f32 = 0.1;
+
+/// Placeable items that create a workbench, and the tier of bench they create.
+const WORKBENCH_ITEM_TIERS: &[(&str, u8)] = &[
+    ("Workbench", 1),
+    ("Advanced Workbench", 2),
+];
+
+/// --- Workbench Data Structure ---
+/// A placed crafting station. Higher tiers unlock more recipes and craft faster.
+#[spacetimedb::table(name = workbench, public)]
+#[derive(Clone, Debug)]
+pub struct Workbench {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub chunk_index: u32,
+    pub tier: u8,
+    pub placed_by: Identity,
+    pub placed_at: Timestamp,
+    pub health: f32,
+    pub max_health: f32,
+    pub is_destroyed: bool,
+    pub destroyed_at: Option<Timestamp>,
+    pub last_hit_time: Option<Timestamp>,
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Place Workbench ---
+/// Consumes a workbench item from the player's inventory/hotbar and places the entity in the world.
+#[spacetimedb::reducer]
+pub fn place_workbench(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    crate::placement::place_deployable(ctx, item_instance_id, world_x, world_y, |name| workbench_tier_for_item(name).is_some())
+}
+
+/// Creates a workbench of the item's tier. The placement constructor for workbench items.
+pub(crate) fn spawn_workbench(ctx: &ReducerContext, placed_by: Identity, item_def: &ItemDefinition, _item_durability: Option<f32>, world_x: f32, world_y: f32) -> Result<(), String> {
+    let tier = workbench_tier_for_item(&item_def.name)
+        .ok_or_else(|| "Item is not a Workbench.".to_string())?;
+    let max_health = workbench_max_health(tier);
+    let inserted = ctx.db.workbench().try_insert(Workbench {
+        id: 0, // Auto-incremented
+        pos_x: world_x,
+        pos_y: world_y,
+        chunk_index: calculate_chunk_index(world_x, world_y),
+        tier,
+        placed_by,
+        placed_at: ctx.timestamp,
+        health: max_health,
+        max_health,
+        is_destroyed: false,
+        destroyed_at: None,
+        last_hit_time: None,
+    }).map_err(|e| format!("Failed to insert workbench entity: {}", e))?;
+    spatial_grid::track_entity(ctx, EntityType::Workbench(inserted.id), inserted.pos_x, inserted.pos_y);
+    log::info!("[PlaceWorkbench] Player {:?} placed tier {} Workbench {} at ({:.1}, {:.1}).", placed_by, tier, inserted.id, world_x, world_y);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Returns the bench tier created by placing the named item, if it is a workbench.
+fn workbench_tier_for_item(item_name: &str) -> Option<u8> {
+    WORKBENCH_ITEM_TIERS.iter()
+        .find(|(name, _)| *name == item_name)
+        .map(|&(_, tier)| tier)
+}
+
+fn workbench_max_health(tier: u8) -> f32 {
+    300.0 + 200.0 * tier as f32
+}
+
+/// Returns the highest tier of intact workbench within crafting range of the position, or 0 if none.
+pub(crate) fn get_nearby_workbench_tier(ctx: &ReducerContext, pos_x: f32, pos_y: f32) -> u8 {
+    let range_sq = WORKBENCH_CRAFTING_RANGE * WORKBENCH_CRAFTING_RANGE;
+    spatial_grid::get_entities_in_radius(ctx, pos_x, pos_y, WORKBENCH_CRAFTING_RANGE)
+        .into_iter()
+        .filter_map(|entity| match entity {
+            EntityType::Workbench(id) => ctx.db.workbench().id().find(id),
+            _ => None,
+        })
+        .filter(|w| !w.is_destroyed && (w.pos_x - pos_x).powi(2) + (w.pos_y - pos_y).powi(2) <= range_sq)
+        .map(|w| w.tier)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Crafting time multiplier for the given workbench tier (1.0 without a bench).
+pub(crate) fn craft_time_multiplier(workbench_tier: u8) -> f32 {
+    (1.0 - workbench_tier as f32 * CRAFT_TIME_REDUCTION_PER_TIER).max(0.5)
+}
+
+/// Checks that the player is near a workbench good enough for the recipe and returns that bench's tier.
+pub(crate) fn require_station_tier(ctx: &ReducerContext, player_id: Identity, required_tier: u8) -> Result<u8, String> {
+    let player = ctx.db.player().identity().find(player_id)
+        .ok_or_else(|| "Player not found".to_string())?;
+    let tier = get_nearby_workbench_tier(ctx, player.position_x, player.position_y);
+    if tier < required_tier {
+        return Err(format!("Requires a tier {} workbench nearby.", required_tier));
+    }
+    Ok(tier)
+}
@@ -0,0 +1,129 @@
+/******************************************************************************
+ *                                                                            *
+ * Transient world events for audio/visual effects. Server systems call       *
+ * `emit_world_event` when something happens that players nearby should see   *
+ * or hear even though it wasn't their own reducer call: a tree falling, an   *
+ * explosion, a structure being destroyed. Clients subscribe to the public    *
+ * WorldEvent table by chunk and play a spatialized sound or particle effect  *
+ * for each new row. Rows only live a few seconds; a schedule prunes them.    *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp, TimeDuration, log};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+
+use crate::environment::calculate_chunk_index;
+use crate::world_event::world_event as WorldEventTableTrait;
+use crate::world_event::world_event_cleanup_schedule as WorldEventCleanupScheduleTableTrait;
+
+// --- Constants ---
+/// How long an event row stays around for clients to pick it up.
+const WORLD_EVENT_LIFETIME_SECS: u64 = 5;
+const WORLD_EVENT_CLEANUP_INTERVAL_SECS: u64 = 2;
+
+#[derive(SpacetimeType, Clone, Copy, Debug, PartialEq)]
+pub enum WorldEventKind {
+    TreeFell,
+    Explosion,
+    Thunder, // Reserved for weather; nothing emits it yet
+    StructureDestroyed,
+}
+
+/// --- World Event ---
+/// Something that happened at a point in the world, for clients to play effects for.
+#[spacetimedb::table(name = world_event, public)]
+#[derive(Clone, Debug)]
+pub struct WorldEvent {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub kind: WorldEventKind,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    #[index(btree)]
+    pub chunk_index: u32,
+    pub caused_by: Option<Identity>,
+    pub detail: Option<String>, // E.g. what kind of structure was destroyed
+    pub occurred_at: Timestamp,
+}
+
+/// --- World Event Cleanup Schedule ---
+#[spacetimedb::table(name = world_event_cleanup_schedule, scheduled(cleanup_world_events))]
+#[derive(Clone, Debug)]
+pub struct WorldEventCleanupSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/******************************************************************************
+ *                           SCHEDULED REDUCERS                               *
+ ******************************************************************************/
+
+/// Starts the world event cleanup schedule if it isn't already scheduled. Called from `init_module`.
+pub fn init_world_event_cleanup_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.world_event_cleanup_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting world event cleanup schedule (every {}s).", WORLD_EVENT_CLEANUP_INTERVAL_SECS);
+        let interval = Duration::from_secs(WORLD_EVENT_CLEANUP_INTERVAL_SECS);
+        schedule_table.try_insert(WorldEventCleanupSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        }).map_err(|e| format!("Failed to insert world event cleanup schedule: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Scheduled reducer: removes events older than WORLD_EVENT_LIFETIME_SECS.
+#[spacetimedb::reducer]
+pub fn cleanup_world_events(ctx: &ReducerContext, _schedule: WorldEventCleanupSchedule) -> Result<(), String> {
+    if ctx.sender != ctx.identity() {
+        return Err("cleanup_world_events may only be called by the scheduler.".to_string());
+    }
+
+    let events = ctx.db.world_event();
+    let lifetime = TimeDuration::from(Duration::from_secs(WORLD_EVENT_LIFETIME_SECS));
+    let expired: Vec<u64> = events.iter()
+        .filter(|e| ctx.timestamp >= e.occurred_at + lifetime)
+        .map(|e| e.id)
+        .collect();
+    for event_id in &expired {
+        events.id().delete(*event_id);
+    }
+    if !expired.is_empty() {
+        log::debug!("[WorldEvent] Pruned {} expired events.", expired.len());
+    }
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Records a world event at (pos_x, pos_y) for clients to play effects for.
+pub(crate) fn emit_world_event(
+    ctx: &ReducerContext,
+    kind: WorldEventKind,
+    pos_x: f32,
+    pos_y: f32,
+    caused_by: Option<Identity>,
+    detail: Option<String>,
+) {
+    ctx.db.world_event().insert(WorldEvent {
+        id: 0, // Auto-incremented
+        kind,
+        pos_x,
+        pos_y,
+        chunk_index: calculate_chunk_index(pos_x, pos_y),
+        caused_by,
+        detail,
+        occurred_at: ctx.timestamp,
+    });
+}
+
+/// Records that a structure was destroyed. `structure` names what it was (e.g. "Campfire").
+pub(crate) fn emit_structure_destroyed(ctx: &ReducerContext, structure: &str, pos_x: f32, pos_y: f32, destroyed_by: Identity) {
+    emit_world_event(ctx, WorldEventKind::StructureDestroyed, pos_x, pos_y, Some(destroyed_by), Some(structure.to_string()));
+}
@@ -1,5 +1,7 @@
 use spacetimedb::{ReducerContext, Table, Timestamp};
 use log;
+use crate::admin;
+use rand::Rng;
 use std::f32::consts::PI;
 use crate::campfire::Campfire;
 use crate::campfire::campfire as CampfireTableTrait;
@@ -27,6 +29,9 @@ pub(crate) const WARMTH_DRAIN_MULTIPLIER_NIGHT: f32 = 2.0;
 pub(crate) const WARMTH_DRAIN_MULTIPLIER_MIDNIGHT: f32 = 3.0;
 pub(crate) const WARMTH_DRAIN_MULTIPLIER_DAWN_DUSK: f32 = 1.5;
 
+// Each season lasts this long in real time, so a full year takes four days
+const SEASON_DURATION_SECONDS: i64 = 24 * 60 * 60;
+
 #[derive(Clone, Debug, PartialEq, spacetimedb::SpacetimeType)]
 pub enum TimeOfDay {
     Dawn,    // Transition from night to day
@@ -40,6 +45,67 @@ pub enum TimeOfDay {
     Midnight, // Middle of the night
 }
 
+impl TimeOfDay {
+    /// Night-blooming plants (mushrooms, hemp) only sprout in these phases.
+    pub fn is_night(&self) -> bool {
+        matches!(self, TimeOfDay::TwilightEvening | TimeOfDay::Night | TimeOfDay::Midnight)
+    }
+
+    /// Sun-grown crops (corn, pumpkins) only grow back in these phases.
+    pub fn is_daytime(&self) -> bool {
+        matches!(self, TimeOfDay::Morning | TimeOfDay::Noon | TimeOfDay::Afternoon)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, spacetimedb::SpacetimeType)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub fn next(self) -> Season {
+        match self {
+            Season::Spring => Season::Summer,
+            Season::Summer => Season::Autumn,
+            Season::Autumn => Season::Winter,
+            Season::Winter => Season::Spring,
+        }
+    }
+
+    /// Scales how fast players lose warmth. Warming up is unaffected.
+    pub fn warmth_drain_multiplier(self) -> f32 {
+        match self {
+            Season::Spring => 1.0,
+            Season::Summer => 0.6,
+            Season::Autumn => 1.25,
+            Season::Winter => 1.75,
+        }
+    }
+
+    /// Scales how fast crops and wild plants regrow (2.0 regrows in half the time).
+    pub fn plant_growth_multiplier(self) -> f32 {
+        match self {
+            Season::Spring => 1.25,
+            Season::Summer => 1.0,
+            Season::Autumn => 0.8,
+            Season::Winter => 0.5,
+        }
+    }
+
+    /// Scales how long felled trees and depleted stones take to respawn.
+    pub fn resource_respawn_time_multiplier(self) -> f32 {
+        match self {
+            Season::Spring => 0.85,
+            Season::Summer => 1.0,
+            Season::Autumn => 1.0,
+            Season::Winter => 1.5,
+        }
+    }
+}
+
 #[spacetimedb::table(name = world_state, public)]
 #[derive(Clone)]
 pub struct WorldState {
@@ -51,6 +117,9 @@ pub struct WorldState {
     pub cycle_count: u32, // How many full cycles have passed
     pub is_full_moon: bool, // Flag for special night lighting
     pub last_tick: Timestamp,
+    pub world_seed: u64, // Seed for deterministic per-chunk world generation
+    pub season: Season,
+    pub season_started_at: Timestamp,
 }
 
 // Reducer to initialize the world state if it doesn't exist
@@ -66,6 +135,9 @@ pub fn seed_world_state(ctx: &ReducerContext) -> Result<(), String> {
             cycle_count: 0,
             is_full_moon: false,
             last_tick: ctx.timestamp,
+            world_seed: ctx.rng().gen(),
+            season: Season::Spring,
+            season_started_at: ctx.timestamp,
         })?;
     } else {
         log::debug!("WorldState already seeded.");
@@ -135,6 +207,14 @@ pub fn tick_world_state(ctx: &ReducerContext, _timestamp: Timestamp) -> Result<(
         world_state.is_full_moon = new_is_full_moon; // Use the correctly determined flag
         world_state.last_tick = now;
 
+        let season_elapsed_micros = now.to_micros_since_unix_epoch() - world_state.season_started_at.to_micros_since_unix_epoch();
+        if season_elapsed_micros >= SEASON_DURATION_SECONDS * 1_000_000 {
+            let next_season = world_state.season.next();
+            log::info!("Season changed ({:?} -> {:?}).", world_state.season, next_season);
+            world_state.season = next_season;
+            world_state.season_started_at = now;
+        }
+
         // Pass a clone to update
         ctx.db.world_state().id().update(world_state.clone());
         
@@ -144,6 +224,31 @@ pub fn tick_world_state(ctx: &ReducerContext, _timestamp: Timestamp) -> Result<(
     Ok(())
 }
 
+/// Admin-only: switches the world to `season`, which then lasts a full season from now.
+#[spacetimedb::reducer]
+pub fn set_season(ctx: &ReducerContext, season: Season) -> Result<(), String> {
+    if !admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can change the season.".to_string());
+    }
+    let mut world_state = ctx.db.world_state().iter().next()
+        .ok_or_else(|| "WorldState singleton not found".to_string())?;
+    log::info!("Admin {:?} set the season ({:?} -> {:?}).", ctx.sender, world_state.season, season);
+    world_state.season = season;
+    world_state.season_started_at = ctx.timestamp;
+    ctx.db.world_state().id().update(world_state);
+    Ok(())
+}
+
+/// Current season, or `Spring` if the world state isn't seeded yet.
+pub(crate) fn current_season(ctx: &ReducerContext) -> Season {
+    ctx.db.world_state().iter().next().map_or(Season::Spring, |world_state| world_state.season)
+}
+
+/// Current phase of the day/night cycle, or `Morning` if the world state isn't seeded yet.
+pub(crate) fn current_time_of_day(ctx: &ReducerContext) -> TimeOfDay {
+    ctx.db.world_state().iter().next().map_or(TimeOfDay::Morning, |world_state| world_state.time_of_day)
+}
+
 // Helper function potentially needed later for client-side interpolation/lighting
 pub fn get_light_intensity(progress: f32) -> f32 {
     // Simple sinusoidal model: peaks at noon (0.5 progress), troughs at midnight (0.0/1.0 progress)
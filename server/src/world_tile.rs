@@ -0,0 +1,146 @@
+/******************************************************************************
+ *                                                                            *
+ * Defines world tile terrain. Only non-land tiles (lakes and rivers) are     *
+ * stored; any tile without a WorldTile row is regular land. Water tiles are  *
+ * generated per chunk from the world seed (see `environment` chunk           *
+ * generation) and read by movement (swim speed) and the stat tick (swim      *
+ * stamina drain and drowning). The minimap summary counts a chunk's water    *
+ * straight from the seed with `water_tiles_in_range`.                        *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{ReducerContext, SpacetimeType, Table};
+use noise::{NoiseFn, Perlin};
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::collections::HashSet;
+
+use crate::{TILE_SIZE_PX, WORLD_WIDTH_TILES, WORLD_HEIGHT_TILES};
+use crate::environment::calculate_chunk_index;
+use crate::world_tile::world_tile as WorldTileTableTrait;
+
+// --- Constants ---
+/// Low-frequency noise above this value becomes a lake.
+const LAKE_NOISE_FREQUENCY: f64 = 0.025;
+const LAKE_NOISE_THRESHOLD: f64 = 0.45;
+const LAKE_NOISE_SALT: u32 = 0x4C41_4B45;
+const NUM_RIVERS: u32 = 2;
+const RIVER_NOISE_SALT: u32 = 0x5256_4552;
+/// How far (in tiles) and how quickly rivers wander from their base column.
+const RIVER_MEANDER_TILES: f64 = 30.0;
+const RIVER_MEANDER_FREQUENCY: f64 = 0.02;
+/// Rivers are this many tiles wide on each side of their center line.
+const RIVER_HALF_WIDTH_TILES: i32 = 1;
+/// No water is generated within this many tiles of the default spawn point.
+const SPAWN_CLEARANCE_TILES: i32 = 12;
+const DEFAULT_SPAWN_TILE: (i32, i32) = (13, 10); // (640, 480) px
+
+#[derive(SpacetimeType, Copy, Clone, Debug, PartialEq)]
+pub enum TileType {
+    Water,
+}
+
+/// --- World Tile Data Structure ---
+/// A non-land tile. Keyed by `tile_y * WORLD_WIDTH_TILES + tile_x` for direct lookups.
+#[spacetimedb::table(name = world_tile, public)]
+#[derive(Clone, Debug)]
+pub struct WorldTile {
+    #[primary_key]
+    pub tile_index: u32,
+    pub tile_x: u32,
+    pub tile_y: u32,
+    #[index(btree)]
+    pub chunk_index: u32,
+    pub tile_type: TileType,
+}
+
+/// Returns the tile type at a world pixel position, or None for land.
+pub fn tile_type_at(ctx: &ReducerContext, pos_x: f32, pos_y: f32) -> Option<TileType> {
+    if pos_x < 0.0 || pos_y < 0.0 {
+        return None;
+    }
+    let tile_x = (pos_x / TILE_SIZE_PX as f32).floor() as u32;
+    let tile_y = (pos_y / TILE_SIZE_PX as f32).floor() as u32;
+    if tile_x >= WORLD_WIDTH_TILES || tile_y >= WORLD_HEIGHT_TILES {
+        return None;
+    }
+    ctx.db.world_tile().tile_index().find(tile_y * WORLD_WIDTH_TILES + tile_x).map(|tile| tile.tile_type)
+}
+
+/// Whether the given world pixel position is on a water tile.
+pub fn is_water_at(ctx: &ReducerContext, pos_x: f32, pos_y: f32) -> bool {
+    tile_type_at(ctx, pos_x, pos_y) == Some(TileType::Water)
+}
+
+/// Generates the lakes and rivers that fall inside the given tile range, inserts them as
+/// water tiles and returns their coordinates so resource generation can keep off them.
+pub fn generate_chunk_water_tiles(
+    ctx: &ReducerContext,
+    world_seed: u64,
+    min_tile_x: u32,
+    max_tile_x: u32,
+    min_tile_y: u32,
+    max_tile_y: u32,
+) -> HashSet<(u32, u32)> {
+    let water = water_tiles_in_range(world_seed, min_tile_x, max_tile_x, min_tile_y, max_tile_y);
+    let tiles = ctx.db.world_tile();
+    for &(tile_x, tile_y) in &water {
+        let center_x = (tile_x as f32 + 0.5) * TILE_SIZE_PX as f32;
+        let center_y = (tile_y as f32 + 0.5) * TILE_SIZE_PX as f32;
+        tiles.insert(WorldTile {
+            tile_index: tile_y * WORLD_WIDTH_TILES + tile_x,
+            tile_x,
+            tile_y,
+            chunk_index: calculate_chunk_index(center_x, center_y),
+            tile_type: TileType::Water,
+        });
+    }
+    water
+}
+
+/// Coordinates of the lake (noise blob) and meandering river tiles inside the given tile
+/// range, without storing them. Water is a pure function of the world seed and tile
+/// coordinates, so rivers line up across chunk borders and the map summary can count a
+/// chunk's water before it is generated.
+pub fn water_tiles_in_range(
+    world_seed: u64,
+    min_tile_x: u32,
+    max_tile_x: u32,
+    min_tile_y: u32,
+    max_tile_y: u32,
+) -> HashSet<(u32, u32)> {
+    let mut water = HashSet::new();
+    let lake_noise = Perlin::new((world_seed as u32) ^ LAKE_NOISE_SALT);
+    let river_noise = Perlin::new((world_seed as u32) ^ RIVER_NOISE_SALT);
+
+    // Each river flows top to bottom around a seed-chosen column
+    let mut river_rng = StdRng::seed_from_u64(world_seed ^ RIVER_NOISE_SALT as u64);
+    let river_columns: Vec<f64> = (0..NUM_RIVERS)
+        .map(|_| river_rng.gen_range(WORLD_WIDTH_TILES / 5..WORLD_WIDTH_TILES * 4 / 5) as f64)
+        .collect();
+
+    for tile_y in min_tile_y..max_tile_y {
+        // --- Rivers --- (drift sideways along a noise curve)
+        let river_centers: Vec<i32> = river_columns.iter().enumerate()
+            .map(|(river, &column)| {
+                let meander = river_noise.get([tile_y as f64 * RIVER_MEANDER_FREQUENCY, river as f64 * 10.0]);
+                (column + meander * RIVER_MEANDER_TILES).round() as i32
+            })
+            .collect();
+
+        for tile_x in min_tile_x..max_tile_x {
+            // Keep the spawn area dry
+            if (tile_x as i32 - DEFAULT_SPAWN_TILE.0).abs() <= SPAWN_CLEARANCE_TILES
+                && (tile_y as i32 - DEFAULT_SPAWN_TILE.1).abs() <= SPAWN_CLEARANCE_TILES {
+                continue;
+            }
+            // --- Lakes ---
+            let is_lake = lake_noise.get([tile_x as f64 * LAKE_NOISE_FREQUENCY, tile_y as f64 * LAKE_NOISE_FREQUENCY]) > LAKE_NOISE_THRESHOLD;
+            let is_river = river_centers.iter().any(|&center| (tile_x as i32 - center).abs() <= RIVER_HALF_WIDTH_TILES);
+            if is_lake || is_river {
+                water.insert((tile_x, tile_y));
+            }
+        }
+    }
+    water
+}
@@ -0,0 +1,198 @@
+/******************************************************************************
+ *                                                                            *
+ * Map zones with their own rules. A zone is a circle that allows or blocks   *
+ * PvP and building inside it, e.g. a safe zone around spawn (no PvP, no      *
+ * building) or an arena (PvP on, no building). Where zones overlap, the      *
+ * smallest one containing the point decides, so an arena can sit inside a    *
+ * larger safe zone. Outside every zone PvP and building are allowed.         *
+ * combat.rs blocks player damage when the attacker or victim stands where    *
+ * PvP is off, and placement.rs and building.rs refuse to build where         *
+ * building is off. Admins define and edit zones at runtime; a safe zone      *
+ * around spawn is created when the module starts with no zones.              *
+ *                                                                            *
+ ******************************************************************************/
+
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp, log};
+
+use crate::{WORLD_WIDTH_PX, WORLD_HEIGHT_PX};
+use crate::admin;
+use crate::player as PlayerTableTrait;
+use crate::zone::zone as ZoneTableTrait;
+
+// --- Constants ---
+const MAX_ZONE_RADIUS: f32 = 4000.0;
+const MAX_ZONE_NAME_LENGTH: usize = 48;
+const SPAWN_SAFE_ZONE_NAME: &str = "Spawn";
+const SPAWN_SAFE_ZONE_RADIUS: f32 = 480.0;
+
+/// --- Zone ---
+/// A circular area with its own PvP and building rules. Public so clients can draw
+/// zones on the map and warn players as they cross into one.
+#[spacetimedb::table(name = zone, public)]
+#[derive(Clone, Debug)]
+pub struct Zone {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub name: String,
+    pub center_x: f32,
+    pub center_y: f32,
+    pub radius: f32,
+    pub pvp_allowed: bool,
+    pub build_allowed: bool,
+    pub created_by: Identity,
+    pub created_at: Timestamp,
+}
+
+/******************************************************************************
+ *                                REDUCERS                                    *
+ ******************************************************************************/
+
+/// --- Create Zone ---
+/// Admin-only: defines a new zone.
+#[spacetimedb::reducer]
+pub fn create_zone(ctx: &ReducerContext, name: String, center_x: f32, center_y: f32, radius: f32, pvp_allowed: bool, build_allowed: bool) -> Result<(), String> {
+    if !admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can create zones.".to_string());
+    }
+    let name = validate_zone(&name, center_x, center_y, radius)?;
+    let inserted = ctx.db.zone().insert(Zone {
+        id: 0, // Auto-incremented
+        name,
+        center_x,
+        center_y,
+        radius,
+        pvp_allowed,
+        build_allowed,
+        created_by: ctx.sender,
+        created_at: ctx.timestamp,
+    });
+    log::info!("[Zone] Admin {:?} created zone {} '{}' at ({:.1}, {:.1}), radius {:.1}, pvp {}, build {}.",
+        ctx.sender, inserted.id, inserted.name, center_x, center_y, radius, pvp_allowed, build_allowed);
+    Ok(())
+}
+
+/// --- Update Zone ---
+/// Admin-only: moves, resizes or changes the rules of an existing zone.
+#[spacetimedb::reducer]
+pub fn update_zone(ctx: &ReducerContext, zone_id: u64, center_x: f32, center_y: f32, radius: f32, pvp_allowed: bool, build_allowed: bool) -> Result<(), String> {
+    if !admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can edit zones.".to_string());
+    }
+    let zones = ctx.db.zone();
+    let mut zone = zones.id().find(zone_id)
+        .ok_or_else(|| format!("Zone {} not found.", zone_id))?;
+    validate_zone(&zone.name, center_x, center_y, radius)?;
+    zone.center_x = center_x;
+    zone.center_y = center_y;
+    zone.radius = radius;
+    zone.pvp_allowed = pvp_allowed;
+    zone.build_allowed = build_allowed;
+    zones.id().update(zone);
+    log::info!("[Zone] Admin {:?} updated zone {}.", ctx.sender, zone_id);
+    Ok(())
+}
+
+/// --- Rename Zone ---
+/// Admin-only: changes the name shown for a zone.
+#[spacetimedb::reducer]
+pub fn rename_zone(ctx: &ReducerContext, zone_id: u64, name: String) -> Result<(), String> {
+    if !admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can edit zones.".to_string());
+    }
+    let zones = ctx.db.zone();
+    let mut zone = zones.id().find(zone_id)
+        .ok_or_else(|| format!("Zone {} not found.", zone_id))?;
+    zone.name = validate_zone(&name, zone.center_x, zone.center_y, zone.radius)?;
+    log::info!("[Zone] Admin {:?} renamed zone {} to '{}'.", ctx.sender, zone_id, zone.name);
+    zones.id().update(zone);
+    Ok(())
+}
+
+/// --- Remove Zone ---
+/// Admin-only: deletes a zone. Anything already built inside it stays.
+#[spacetimedb::reducer]
+pub fn remove_zone(ctx: &ReducerContext, zone_id: u64) -> Result<(), String> {
+    if !admin::is_admin(ctx, ctx.sender) {
+        return Err("Only admins can remove zones.".to_string());
+    }
+    if !ctx.db.zone().id().delete(zone_id) {
+        return Err(format!("Zone {} not found.", zone_id));
+    }
+    log::info!("[Zone] Admin {:?} removed zone {}.", ctx.sender, zone_id);
+    Ok(())
+}
+
+/******************************************************************************
+ *                             HELPER FUNCTIONS                               *
+ ******************************************************************************/
+
+/// Creates the safe zone around the default spawn point when no zones exist yet.
+/// Called from `init_module`.
+pub fn seed_spawn_safe_zone(ctx: &ReducerContext) -> Result<(), String> {
+    if ctx.db.zone().iter().count() > 0 {
+        return Ok(());
+    }
+    let (center_x, center_y) = crate::environment::DEFAULT_SPAWN_POS;
+    ctx.db.zone().try_insert(Zone {
+        id: 0, // Auto-incremented
+        name: SPAWN_SAFE_ZONE_NAME.to_string(),
+        center_x,
+        center_y,
+        radius: SPAWN_SAFE_ZONE_RADIUS,
+        pvp_allowed: false,
+        build_allowed: false,
+        created_by: ctx.identity(),
+        created_at: ctx.timestamp,
+    }).map_err(|e| format!("Failed to insert spawn safe zone: {}", e))?;
+    log::info!("[Zone] Created spawn safe zone (radius {:.0}).", SPAWN_SAFE_ZONE_RADIUS);
+    Ok(())
+}
+
+/// The zone whose rules apply at (x, y): the smallest one containing it, if any.
+pub(crate) fn zone_at(ctx: &ReducerContext, x: f32, y: f32) -> Option<Zone> {
+    ctx.db.zone().iter()
+        .filter(|z| {
+            let (dx, dy) = (x - z.center_x, y - z.center_y);
+            dx * dx + dy * dy <= z.radius * z.radius
+        })
+        .min_by(|a, b| a.radius.total_cmp(&b.radius))
+}
+
+pub(crate) fn is_pvp_allowed_at(ctx: &ReducerContext, x: f32, y: f32) -> bool {
+    zone_at(ctx, x, y).is_none_or(|z| z.pvp_allowed)
+}
+
+/// Whether the attacker may hurt a player standing at (target_x, target_y): PvP must be
+/// allowed both where the victim is and where the attacker is, so no one can shoot out
+/// of a safe zone or into one.
+pub(crate) fn is_pvp_allowed_between(ctx: &ReducerContext, attacker_id: Identity, target_x: f32, target_y: f32) -> bool {
+    if !is_pvp_allowed_at(ctx, target_x, target_y) {
+        return false;
+    }
+    ctx.db.player().identity().find(attacker_id)
+        .is_none_or(|attacker| is_pvp_allowed_at(ctx, attacker.position_x, attacker.position_y))
+}
+
+/// Errors if building is off in the zone at (world_x, world_y).
+pub(crate) fn check_build_allowed(ctx: &ReducerContext, world_x: f32, world_y: f32) -> Result<(), String> {
+    match zone_at(ctx, world_x, world_y) {
+        Some(zone) if !zone.build_allowed => Err(format!("Building is not allowed in {}.", zone.name)),
+        _ => Ok(()),
+    }
+}
+
+/// Trims the name and checks the zone lies in the world with a sensible radius.
+fn validate_zone(name: &str, center_x: f32, center_y: f32, radius: f32) -> Result<String, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_ZONE_NAME_LENGTH {
+        return Err(format!("Zone name must be 1-{} characters.", MAX_ZONE_NAME_LENGTH));
+    }
+    if !(0.0..=WORLD_WIDTH_PX).contains(&center_x) || !(0.0..=WORLD_HEIGHT_PX).contains(&center_y) {
+        return Err("Zone centre must be inside the world.".to_string());
+    }
+    if !radius.is_finite() || radius <= 0.0 || radius > MAX_ZONE_RADIUS {
+        return Err(format!("Zone radius must be between 0 and {}.", MAX_ZONE_RADIUS));
+    }
+    Ok(name)
+}